@@ -0,0 +1,405 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+
+/// Counts of the rewrites applied by boolean simplification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BooleanSimplificationStatistics {
+    /// `!!a` rewritten to `a`.
+    pub double_negations: usize,
+    /// Identity constants dropped, as in `a && true`, and annihilator constants taking over, as in `a || true`.
+    pub constants: usize,
+    /// Operands implied by another operand dropped, as in `a && (a || b)` or `a && a`.
+    pub absorptions: usize,
+    /// Operands shared by several terms factored out, as in `(a && b) || (a && c)`.
+    pub factorings: usize,
+    /// Negations merged or pushed inwards by De Morgan's laws, as in `!a && !b`.
+    pub de_morgans: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Rule {
+    DoubleNegation,
+    Constant,
+    Absorption,
+    Factoring,
+    DeMorgan,
+}
+
+impl BooleanSimplificationStatistics {
+    /// Returns the number of rewrites of every kind.
+    pub fn total(&self) -> usize {
+        self.double_negations + self.constants + self.absorptions + self.factorings + self.de_morgans
+    }
+
+    fn record(&mut self, rule: Rule) {
+        match rule {
+            Rule::DoubleNegation => self.double_negations += 1,
+            Rule::Constant => self.constants += 1,
+            Rule::Absorption => self.absorptions += 1,
+            Rule::Factoring => self.factorings += 1,
+            Rule::DeMorgan => self.de_morgans += 1,
+        }
+    }
+}
+
+///
+/// A boolean expression over opaque atoms, numbered from zero.
+///
+/// Conjunctions and disjunctions take any number of operands, and stand for a chain of binary operations.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BooleanFormula {
+    Constant(bool),
+    Atom(usize),
+    Not(Box<BooleanFormula>),
+    And(Vec<BooleanFormula>),
+    Or(Vec<BooleanFormula>),
+}
+
+impl BooleanFormula {
+    ///
+    /// Returns the number of nodes of the formula as a tree of unary and binary operations.
+    ///
+    pub fn size(&self) -> usize {
+        match self {
+            BooleanFormula::Constant(_) | BooleanFormula::Atom(_) => 1,
+            BooleanFormula::Not(inner) => 1 + inner.size(),
+            BooleanFormula::And(operands) | BooleanFormula::Or(operands) => {
+                operands.len().saturating_sub(1) + operands.iter().map(|x| x.size()).sum::<usize>()
+            }
+        }
+    }
+
+    ///
+    /// Returns the value of the formula where atom `i` has the value `assignment[i]`.
+    ///
+    pub fn evaluate(&self, assignment: &[bool]) -> bool {
+        match self {
+            BooleanFormula::Constant(value) => *value,
+            BooleanFormula::Atom(atom) => assignment[*atom],
+            BooleanFormula::Not(inner) => !inner.evaluate(assignment),
+            BooleanFormula::And(operands) => operands.iter().all(|x| x.evaluate(assignment)),
+            BooleanFormula::Or(operands) => operands.iter().any(|x| x.evaluate(assignment)),
+        }
+    }
+
+    /// Returns the atoms the formula reads.
+    pub fn atoms(&self) -> BTreeSet<usize> {
+        let mut atoms = BTreeSet::new();
+        self.collect_atoms(&mut atoms);
+        atoms
+    }
+
+    fn collect_atoms(&self, atoms: &mut BTreeSet<usize>) {
+        match self {
+            BooleanFormula::Constant(_) => (),
+            BooleanFormula::Atom(atom) => {
+                atoms.insert(*atom);
+            }
+            BooleanFormula::Not(inner) => inner.collect_atoms(atoms),
+            BooleanFormula::And(operands) | BooleanFormula::Or(operands) => {
+                operands.iter().for_each(|x| x.collect_atoms(atoms))
+            }
+        }
+    }
+
+    ///
+    /// Rewrites the formula until no rule applies, counting the rewrites in `statistics`.
+    ///
+    /// Every rewrite makes the formula smaller, so this terminates. Atoms are only dropped if
+    /// `droppable` allows it, since dropping an atom skips whatever its evaluation would report.
+    ///
+    pub fn simplify(
+        self,
+        droppable: &dyn Fn(usize) -> bool,
+        statistics: &mut BooleanSimplificationStatistics,
+    ) -> BooleanFormula {
+        let mut formula = self;
+        loop {
+            let next = formula.clone().simplify_once(droppable, statistics);
+            if next == formula {
+                return formula;
+            }
+            debug_assert!(next.size() <= formula.size());
+            formula = next;
+        }
+    }
+
+    /// Simplifies the operands of the formula, then the formula itself.
+    fn simplify_once(
+        self,
+        droppable: &dyn Fn(usize) -> bool,
+        statistics: &mut BooleanSimplificationStatistics,
+    ) -> Self {
+        let mut formula = match self {
+            BooleanFormula::Not(inner) => BooleanFormula::Not(Box::new(inner.simplify_once(droppable, statistics))),
+            BooleanFormula::And(operands) => BooleanFormula::And(
+                operands
+                    .into_iter()
+                    .map(|x| x.simplify_once(droppable, statistics))
+                    .collect(),
+            ),
+            BooleanFormula::Or(operands) => BooleanFormula::Or(
+                operands
+                    .into_iter()
+                    .map(|x| x.simplify_once(droppable, statistics))
+                    .collect(),
+            ),
+            leaf => leaf,
+        }
+        .normalize();
+
+        // Only rewrites that shrink the formula are taken, the first of them in the order of the rules.
+        loop {
+            let size = formula.size();
+            let next = formula
+                .rewrites(droppable)
+                .into_iter()
+                .map(|(next, rule)| (next.normalize(), rule))
+                .find(|(next, _)| next.size() < size);
+            match next {
+                Some((next, rule)) => {
+                    statistics.record(rule);
+                    formula = next;
+                }
+                None => return formula,
+            }
+        }
+    }
+
+    ///
+    /// Merges operands of the same operation into their parent and unwraps operations of fewer than two operands,
+    /// which leaves the size of the formula as it is.
+    ///
+    fn normalize(self) -> Self {
+        match self {
+            BooleanFormula::And(operands) => Self::junction(true, operands),
+            BooleanFormula::Or(operands) => Self::junction(false, operands),
+            formula => formula,
+        }
+    }
+
+    /// Returns the conjunction of `operands` if `conjunction` is set, or their disjunction.
+    fn junction(conjunction: bool, operands: Vec<BooleanFormula>) -> Self {
+        let mut flat = Vec::with_capacity(operands.len());
+        for operand in operands {
+            match (conjunction, operand) {
+                (true, BooleanFormula::And(inner)) | (false, BooleanFormula::Or(inner)) => flat.extend(inner),
+                (_, operand) => flat.push(operand),
+            }
+        }
+        match flat.len() {
+            0 => BooleanFormula::Constant(conjunction),
+            1 => flat.pop().unwrap(),
+            _ if conjunction => BooleanFormula::And(flat),
+            _ => BooleanFormula::Or(flat),
+        }
+    }
+
+    /// Returns the operands of the conjunction or disjunction, as `conjunction` says, and `None` for anything else.
+    fn operands(&self, conjunction: bool) -> Option<&Vec<BooleanFormula>> {
+        match (conjunction, self) {
+            (true, BooleanFormula::And(operands)) | (false, BooleanFormula::Or(operands)) => Some(operands),
+            _ => None,
+        }
+    }
+
+    /// Returns the negation of the formula, cancelling a negation it starts with.
+    fn negate(self) -> Self {
+        match self {
+            BooleanFormula::Not(inner) => *inner,
+            BooleanFormula::Constant(value) => BooleanFormula::Constant(!value),
+            formula => BooleanFormula::Not(Box::new(formula)),
+        }
+    }
+
+    /// Returns the rewrites of the top of the formula, in the order of the rules.
+    fn rewrites(&self, droppable: &dyn Fn(usize) -> bool) -> Vec<(Self, Rule)> {
+        match self {
+            BooleanFormula::Not(inner) => match &**inner {
+                BooleanFormula::Not(inner) => vec![((**inner).clone(), Rule::DoubleNegation)],
+                BooleanFormula::Constant(value) => vec![(BooleanFormula::Constant(!value), Rule::Constant)],
+                // `!(!a && b)` is `a || !b`, which is smaller.
+                BooleanFormula::And(operands) => vec![(
+                    BooleanFormula::Or(operands.iter().cloned().map(Self::negate).collect()),
+                    Rule::DeMorgan,
+                )],
+                BooleanFormula::Or(operands) => vec![(
+                    BooleanFormula::And(operands.iter().cloned().map(Self::negate).collect()),
+                    Rule::DeMorgan,
+                )],
+                BooleanFormula::Atom(_) => vec![],
+            },
+            BooleanFormula::And(operands) => Self::junction_rewrites(true, operands, droppable),
+            BooleanFormula::Or(operands) => Self::junction_rewrites(false, operands, droppable),
+            _ => vec![],
+        }
+    }
+
+    fn junction_rewrites(
+        conjunction: bool,
+        operands: &[BooleanFormula],
+        droppable: &dyn Fn(usize) -> bool,
+    ) -> Vec<(Self, Rule)> {
+        let rebuild = |operands: Vec<BooleanFormula>| Self::junction(conjunction, operands);
+        let mut rewrites = Vec::new();
+
+        // `a && true` is `a`, and `a && false` is `false` if `a` may be dropped.
+        let identity = BooleanFormula::Constant(conjunction);
+        if operands.contains(&identity) {
+            rewrites.push((
+                rebuild(operands.iter().filter(|x| **x != identity).cloned().collect()),
+                Rule::Constant,
+            ));
+        }
+        let annihilator = BooleanFormula::Constant(!conjunction);
+        if operands.contains(&annihilator) && can_drop(operands, &[], droppable) {
+            rewrites.push((annihilator, Rule::Constant));
+        }
+
+        // `a && a` is `a`.
+        let mut distinct: Vec<BooleanFormula> = Vec::with_capacity(operands.len());
+        for operand in operands {
+            if !distinct.contains(operand) {
+                distinct.push(operand.clone());
+            }
+        }
+        if distinct.len() < operands.len() {
+            rewrites.push((rebuild(distinct), Rule::Absorption));
+        }
+
+        // `a && (a || b)` is `a`, if `b` may be dropped.
+        for (index, operand) in operands.iter().enumerate() {
+            let inner = match operand.operands(!conjunction) {
+                Some(inner) => inner,
+                None => continue,
+            };
+            let kept = operands
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, x)| x.clone())
+                .collect::<Vec<_>>();
+            if inner.iter().any(|x| kept.contains(x)) && can_drop(std::slice::from_ref(operand), &kept, droppable) {
+                rewrites.push((rebuild(kept), Rule::Absorption));
+                break;
+            }
+        }
+
+        // `(a && b) || (a && c)` is `a && (b || c)`.
+        if let Some(factored) = Self::factor(conjunction, operands) {
+            rewrites.push((factored, Rule::Factoring));
+        }
+
+        // `!a && !b` is `!(a || b)`.
+        let negated = operands
+            .iter()
+            .filter_map(|x| match x {
+                BooleanFormula::Not(inner) => Some((**inner).clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if negated.len() >= 2 {
+            let merged = Self::junction(!conjunction, negated).negate();
+            rewrites.push((
+                rebuild(Self::replace_first(
+                    operands,
+                    |x| matches!(x, BooleanFormula::Not(_)),
+                    merged,
+                )),
+                Rule::DeMorgan,
+            ));
+        }
+
+        rewrites
+    }
+
+    ///
+    /// Returns `operands` with the first operand that `matches` replaced by `replacement`,
+    /// and the other operands that match left out.
+    ///
+    fn replace_first(
+        operands: &[BooleanFormula],
+        matches: impl Fn(&BooleanFormula) -> bool,
+        replacement: BooleanFormula,
+    ) -> Vec<BooleanFormula> {
+        let mut replacement = Some(replacement);
+        operands
+            .iter()
+            .filter_map(|x| {
+                if matches(x) {
+                    replacement.take()
+                } else {
+                    Some(x.clone())
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Factors out the operand shared by the most terms of the junction, where the terms are
+    /// the operands of the dual junction, as in `(a && b) || (a && c)`.
+    ///
+    fn factor(conjunction: bool, operands: &[BooleanFormula]) -> Option<Self> {
+        let terms = operands
+            .iter()
+            .map(|x| match x.operands(!conjunction) {
+                Some(inner) => inner.clone(),
+                None => vec![x.clone()],
+            })
+            .collect::<Vec<_>>();
+
+        let mut best: Option<(&BooleanFormula, usize)> = None;
+        for term in terms.iter() {
+            for candidate in term {
+                let sharing = terms.iter().filter(|x| x.contains(candidate)).count();
+                if sharing >= 2 && best.map(|(_, count)| sharing > count).unwrap_or(true) {
+                    best = Some((candidate, sharing));
+                }
+            }
+        }
+        let (common, _) = best?;
+
+        let remainders = terms
+            .iter()
+            .filter(|term| term.contains(common))
+            .map(|term| Self::junction(!conjunction, term.iter().filter(|x| *x != common).cloned().collect()))
+            .collect();
+        let factored = Self::junction(
+            !conjunction,
+            vec![common.clone(), Self::junction(conjunction, remainders)],
+        );
+        let sharing = |x: &BooleanFormula| match x.operands(!conjunction) {
+            Some(inner) => inner.contains(common),
+            None => x == common,
+        };
+        Some(Self::junction(
+            conjunction,
+            Self::replace_first(operands, sharing, factored),
+        ))
+    }
+}
+
+/// Returns `true` if the atoms of `dropped` that do not also appear in `kept` may be dropped.
+fn can_drop(dropped: &[BooleanFormula], kept: &[BooleanFormula], droppable: &dyn Fn(usize) -> bool) -> bool {
+    let kept = kept.iter().flat_map(|x| x.atoms()).collect::<BTreeSet<_>>();
+    dropped
+        .iter()
+        .flat_map(|x| x.atoms())
+        .all(|atom| kept.contains(&atom) || droppable(atom))
+}
@@ -0,0 +1,253 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+mod formula;
+pub use formula::*;
+
+use std::{cell::Cell, collections::HashSet};
+
+use leo_asg::*;
+use leo_errors::{Result, Span};
+
+///
+/// Rewrites boolean expressions built from `&&`, `||` and `!` into smaller equivalent ones,
+/// so that they synthesize to fewer constraints.
+///
+/// The operands that are not themselves boolean operations are kept as they are, and an expression
+/// is only replaced if it ends up with fewer nodes. Operands that could fail, such as arithmetic that
+/// may overflow, are never dropped, and expressions with calls are left alone, since their operands
+/// may not be evaluated as often or in the same order once rewritten.
+///
+pub struct BooleanSimplifier<'a, 'b> {
+    program: &'b Program<'a>,
+    /// The addresses of the operations below the root of an expression that was already simplified.
+    interior: HashSet<usize>,
+    statistics: BooleanSimplificationStatistics,
+}
+
+impl<'a, 'b> BooleanSimplifier<'a, 'b> {
+    ///
+    /// Simplifies the boolean expressions of the program, returning the counts of the rewrites applied.
+    ///
+    pub fn do_pass_with_statistics(asg: Program<'a>) -> Result<(Program<'a>, BooleanSimplificationStatistics)> {
+        let pass = BooleanSimplifier {
+            program: &asg,
+            interior: HashSet::new(),
+            statistics: BooleanSimplificationStatistics::default(),
+        };
+        let mut director = VisitorDirector::new(pass);
+        director.visit_program(&asg).ok();
+        let statistics = director.visitor().statistics;
+        Ok((asg, statistics))
+    }
+
+    ///
+    /// Returns the formula of the boolean operations at the top of the expression, with its other subexpressions as atoms.
+    ///
+    fn formula(&mut self, expr: &'a Expression<'a>, atoms: &mut Vec<&'a Expression<'a>>) -> BooleanFormula {
+        if is_operation(expr) {
+            self.interior.insert(address(expr));
+        }
+        match expr {
+            Expression::Binary(binary) if binary.operation == BinaryOperation::And => BooleanFormula::And(vec![
+                self.formula(binary.left.get(), atoms),
+                self.formula(binary.right.get(), atoms),
+            ]),
+            Expression::Binary(binary) if binary.operation == BinaryOperation::Or => BooleanFormula::Or(vec![
+                self.formula(binary.left.get(), atoms),
+                self.formula(binary.right.get(), atoms),
+            ]),
+            Expression::Unary(unary) if unary.operation == UnaryOperation::Not => {
+                BooleanFormula::Not(Box::new(self.formula(unary.inner.get(), atoms)))
+            }
+            Expression::Constant(Constant {
+                value: ConstValue::Boolean(value),
+                ..
+            }) => BooleanFormula::Constant(*value),
+            _ => match atoms.iter().position(|atom| same_atom(*atom, expr)) {
+                Some(index) => BooleanFormula::Atom(index),
+                None => {
+                    atoms.push(expr);
+                    BooleanFormula::Atom(atoms.len() - 1)
+                }
+            },
+        }
+    }
+
+    ///
+    /// Returns the expression of the formula, made of new operations on the given atoms.
+    ///
+    fn build(
+        &mut self,
+        formula: &BooleanFormula,
+        atoms: &[&'a Expression<'a>],
+        span: &Option<Span>,
+    ) -> &'a Expression<'a> {
+        let context = self.program.context;
+        let expr = match formula {
+            BooleanFormula::Atom(atom) => return atoms[*atom],
+            BooleanFormula::Constant(value) => {
+                return context.alloc_expression(Expression::Constant(Constant {
+                    parent: Cell::new(None),
+                    span: span.clone(),
+                    value: ConstValue::Boolean(*value),
+                }));
+            }
+            BooleanFormula::Not(inner) => context.alloc_expression(Expression::Unary(UnaryExpression {
+                parent: Cell::new(None),
+                span: span.clone(),
+                operation: UnaryOperation::Not,
+                inner: Cell::new(self.build(inner, atoms, span)),
+            })),
+            BooleanFormula::And(operands) | BooleanFormula::Or(operands) => {
+                let operation = match formula {
+                    BooleanFormula::And(_) => BinaryOperation::And,
+                    _ => BinaryOperation::Or,
+                };
+                let mut operands = operands.iter();
+                let first = operands.next().expect("junction without operands");
+                let mut left = self.build(first, atoms, span);
+                for operand in operands {
+                    let right = self.build(operand, atoms, span);
+                    left = context.alloc_expression(Expression::Binary(BinaryExpression {
+                        parent: Cell::new(None),
+                        span: span.clone(),
+                        operation: operation.clone(),
+                        left: Cell::new(left),
+                        right: Cell::new(right),
+                    }));
+                    left.enforce_parents(left);
+                    self.interior.insert(address(left));
+                }
+                return left;
+            }
+        };
+        expr.enforce_parents(expr);
+        self.interior.insert(address(expr));
+        expr
+    }
+}
+
+fn address<'a>(expr: &'a Expression<'a>) -> usize {
+    expr as *const Expression<'a> as usize
+}
+
+/// Returns `true` if the expression is a boolean operation the pass rewrites.
+fn is_operation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Binary(binary) => matches!(binary.operation, BinaryOperation::And | BinaryOperation::Or),
+        Expression::Unary(unary) => unary.operation == UnaryOperation::Not,
+        _ => false,
+    }
+}
+
+///
+/// Returns `true` if the expressions have the same value, because they apply the same operations
+/// to the same variables and constants.
+///
+fn same_atom<'a>(left: &'a Expression<'a>, right: &'a Expression<'a>) -> bool {
+    match (left, right) {
+        (Expression::VariableRef(left), Expression::VariableRef(right)) => std::ptr::eq(left.variable, right.variable),
+        (Expression::Constant(left), Expression::Constant(right)) => left.value == right.value,
+        (Expression::Binary(left), Expression::Binary(right)) => {
+            left.operation == right.operation
+                && same_atom(left.left.get(), right.left.get())
+                && same_atom(left.right.get(), right.right.get())
+        }
+        (Expression::Unary(left), Expression::Unary(right)) => {
+            left.operation == right.operation && same_atom(left.inner.get(), right.inner.get())
+        }
+        (Expression::Cast(left), Expression::Cast(right)) => {
+            left.target_type == right.target_type && same_atom(left.inner.get(), right.inner.get())
+        }
+        _ => std::ptr::eq(left, right),
+    }
+}
+
+///
+/// Returns `true` if evaluating the expression cannot fail, so that leaving it out changes nothing but the constraints.
+///
+fn is_droppable(expr: &Expression) -> bool {
+    match expr {
+        Expression::VariableRef(_) | Expression::Constant(_) => true,
+        Expression::Binary(binary) => {
+            use BinaryOperation::*;
+            matches!(binary.operation, Eq | Ne | Lt | Le | Gt | Ge | And | Or)
+                && is_droppable(binary.left.get())
+                && is_droppable(binary.right.get())
+        }
+        Expression::Unary(unary) => unary.operation == UnaryOperation::Not && is_droppable(unary.inner.get()),
+        _ => false,
+    }
+}
+
+/// Finds whether an expression calls a function.
+#[derive(Default)]
+struct Calls {
+    found: bool,
+}
+
+impl<'a> ExpressionVisitor<'a> for Calls {
+    fn visit_call(&mut self, _input: &CallExpression<'a>) -> VisitResult {
+        self.found = true;
+        VisitResult::Exit
+    }
+}
+
+fn has_call<'a>(expr: &'a Expression<'a>) -> bool {
+    let mut director = VisitorDirector::new(Calls::default());
+    director.visit_expression(&Cell::new(expr)).ok();
+    director.visitor().found
+}
+
+impl<'a, 'b> ExpressionVisitor<'a> for BooleanSimplifier<'a, 'b> {
+    fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        let expr = input.get();
+        if !is_operation(expr) || self.interior.contains(&address(expr)) {
+            return VisitResult::VisitChildren;
+        }
+
+        let mut atoms = vec![];
+        let formula = self.formula(expr, &mut atoms);
+        if atoms.iter().copied().any(has_call) {
+            return VisitResult::VisitChildren;
+        }
+        let size = formula.size();
+        let droppable = |atom: usize| is_droppable(atoms[atom]);
+        let simplified = formula.simplify(&droppable, &mut self.statistics);
+        if simplified.size() >= size {
+            return VisitResult::VisitChildren;
+        }
+
+        // The atoms are visited as part of the new expression, and simplified in turn.
+        let simplified_expr = self.build(&simplified, &atoms, &expr.span().cloned());
+        if let Some(parent) = expr.get_parent() {
+            simplified_expr.set_parent(parent);
+        }
+        input.set(simplified_expr);
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a, 'b> StatementVisitor<'a> for BooleanSimplifier<'a, 'b> {}
+
+impl<'a, 'b> ProgramVisitor<'a> for BooleanSimplifier<'a, 'b> {}
+
+impl<'a, 'b> AsgPass<'a> for BooleanSimplifier<'a, 'b> {
+    fn do_pass(asg: Program<'a>) -> Result<Program<'a>> {
+        Ok(Self::do_pass_with_statistics(asg)?.0)
+    }
+}
@@ -0,0 +1,527 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::Cell;
+
+use indexmap::IndexMap;
+use leo_asg::*;
+use leo_errors::{CancellationToken, Checkpoint, LeoError, Span};
+
+/// The amount of fuel a single call evaluation may burn before it is abandoned.
+pub const DEFAULT_CALL_FUEL: usize = 10_000;
+
+/// Calls nested deeper than this are abandoned, so that recursive functions cannot overflow the stack.
+pub const MAX_CALL_DEPTH: usize = 64;
+
+/// Values of the variables in scope, keyed by variable id.
+type Environment<'a> = IndexMap<u32, ConstValue<'a>>;
+
+enum Flow<'a> {
+    Next,
+    Return(ConstValue<'a>),
+}
+
+/// Counts of the lookups in the cache of evaluated calls.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationStatistics {
+    /// Calls whose value was found in the cache.
+    pub hits: usize,
+    /// Calls that had to be evaluated.
+    pub misses: usize,
+    /// Values held by the cache.
+    pub entries: usize,
+}
+
+/// Values of evaluated calls, keyed by the address of the call expression and the values of the
+/// variables its arguments read.
+///
+/// A call of a pure function depends on nothing else, so its value is reused wherever the same call
+/// is reached again with the same variable values, within one evaluation and across the whole pass.
+#[derive(Default)]
+struct CallCache<'a> {
+    /// The variables read by the arguments of each call, found the first time the call is reached.
+    free_variables: IndexMap<usize, Vec<u32>>,
+    values: IndexMap<(usize, Vec<Option<ConstValue<'a>>>), ConstValue<'a>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<'a> CallCache<'a> {
+    fn key(
+        &mut self,
+        expression: &'a Expression<'a>,
+        environment: &Environment<'a>,
+    ) -> (usize, Vec<Option<ConstValue<'a>>>) {
+        let address = expression as *const Expression<'a> as usize;
+        let variables = self.free_variables.entry(address).or_insert_with(|| {
+            let mut director = VisitorDirector::new(FreeVariables::default());
+            director.visit_expression(&Cell::new(expression)).ok();
+            director.visitor().variables
+        });
+        let fingerprint = variables.iter().map(|id| environment.get(id).cloned()).collect();
+        (address, fingerprint)
+    }
+}
+
+/// Evaluates calls of pure functions whose arguments are all constant.
+///
+/// Every statement and expression evaluated burns one unit of fuel, so a call that would take too
+/// long to evaluate is abandoned and left to be inlined as usual. Anything the evaluator does not
+/// understand abandons the call the same way. Values of calls are cached, and a cached value costs
+/// no more fuel than a constant.
+pub struct CallEvaluator<'a> {
+    purity: IndexMap<u32, bool>,
+    cache: CallCache<'a>,
+    max_fuel: usize,
+    fuel: usize,
+    depth: usize,
+    /// Checked as fuel burns, so that a long evaluation can be cancelled.
+    checkpoint: Checkpoint,
+    /// The error of the check that stopped the evaluation, after which nothing more is evaluated.
+    interruption: Option<LeoError>,
+}
+
+impl<'a> CallEvaluator<'a> {
+    pub fn new(max_fuel: usize) -> Self {
+        Self {
+            purity: IndexMap::new(),
+            cache: CallCache::default(),
+            max_fuel,
+            fuel: max_fuel,
+            depth: 0,
+            checkpoint: CancellationToken::default().checkpoint("constant folding"),
+            interruption: None,
+        }
+    }
+
+    /// Returns the evaluator with its evaluations stopped once `cancellation` is cancelled or past its deadline.
+    pub fn with_cancellation(mut self, cancellation: &CancellationToken) -> Self {
+        self.checkpoint = cancellation.checkpoint("constant folding");
+        self
+    }
+
+    /// Returns `true` if the evaluations were cancelled.
+    pub fn interrupted(&self) -> bool {
+        self.interruption.is_some()
+    }
+
+    /// Returns the error that stopped the evaluations, if they were cancelled.
+    pub fn take_interruption(&mut self) -> Option<LeoError> {
+        self.interruption.take()
+    }
+
+    /// Returns the value of the given call, if it can be computed at compile time.
+    pub fn evaluate(&mut self, expression: &'a Expression<'a>) -> Option<ConstValue<'a>> {
+        if !matches!(expression, Expression::Call(_)) || self.interruption.is_some() {
+            return None;
+        }
+        self.fuel = self.max_fuel;
+        self.depth = 0;
+        self.expression(expression, &Environment::new())
+    }
+
+    /// Returns the counts of the lookups in the cache of evaluated calls so far.
+    pub fn statistics(&self) -> EvaluationStatistics {
+        EvaluationStatistics {
+            hits: self.cache.hits,
+            misses: self.cache.misses,
+            entries: self.cache.values.len(),
+        }
+    }
+
+    /// Returns `true` if the function neither logs to the console nor reads the program input,
+    /// directly or through any function it calls.
+    ///
+    /// Calls within a cycle of recursive functions do not make them impure by themselves,
+    /// so a recursive function is pure unless a function of its cycle has an effect.
+    pub fn is_pure(&mut self, function: &'a Function<'a>) -> bool {
+        if let Some(pure) = self.purity.get(&function.id) {
+            return *pure;
+        }
+
+        // The effects and callees of every function reachable from this one whose purity is not known yet.
+        let mut effects: IndexMap<u32, (bool, Vec<&'a Function<'a>>)> = IndexMap::new();
+        let mut pending = vec![function];
+        while let Some(function) = pending.pop() {
+            if self.purity.contains_key(&function.id) || effects.contains_key(&function.id) {
+                continue;
+            }
+            let (impure, callees) = match function.body.get() {
+                Some(body) => {
+                    let mut director = VisitorDirector::new(EffectCollector::new(function));
+                    director.visit_statement(&Cell::new(body)).ok();
+                    let collector = director.visitor();
+                    (collector.impure, collector.callees)
+                }
+                None => (true, vec![]),
+            };
+            pending.extend(callees.iter().copied());
+            effects.insert(function.id, (impure, callees));
+        }
+
+        // Impurity spreads from the functions with an effect to their callers until nothing changes.
+        let mut impure: IndexMap<u32, bool> = effects.iter().map(|(id, (impure, _))| (*id, *impure)).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (id, (_, callees)) in effects.iter() {
+                if impure[id] {
+                    continue;
+                }
+                let calls_impure = callees.iter().any(|callee| match impure.get(&callee.id) {
+                    Some(impure) => *impure,
+                    None => !self.purity[&callee.id],
+                });
+                if calls_impure {
+                    impure.insert(*id, true);
+                    changed = true;
+                }
+            }
+        }
+
+        for (id, impure) in impure {
+            self.purity.insert(id, !impure);
+        }
+        self.purity[&function.id]
+    }
+
+    fn burn(&mut self) -> Option<()> {
+        self.fuel = self.fuel.checked_sub(1)?;
+        if let Err(error) = self.checkpoint.tick() {
+            self.interruption = Some(error);
+            return None;
+        }
+        Some(())
+    }
+
+    fn call(&mut self, function: &'a Function<'a>, arguments: Vec<ConstValue<'a>>) -> Option<ConstValue<'a>> {
+        if function.arguments.len() != arguments.len() || self.depth >= MAX_CALL_DEPTH {
+            return None;
+        }
+        let mut environment = Environment::new();
+        for (variable, value) in function.arguments.values().zip(arguments) {
+            environment.insert(variable.get().borrow().id, value);
+        }
+        self.depth += 1;
+        let flow = self.statement(function.body.get()?, &mut environment);
+        self.depth -= 1;
+        match flow? {
+            Flow::Return(value) => Some(value),
+            Flow::Next if function.output.is_unit() => Some(ConstValue::Tuple(vec![])),
+            Flow::Next => None,
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Statement<'a>, environment: &mut Environment<'a>) -> Option<Flow<'a>> {
+        self.burn()?;
+        match statement {
+            Statement::Return(statement) => {
+                Some(Flow::Return(self.expression(statement.expression.get(), environment)?))
+            }
+            Statement::Definition(statement) => {
+                let value = self.expression(statement.value.get(), environment)?;
+                match (statement.variables.as_slice(), value) {
+                    ([variable], value) => {
+                        environment.insert(variable.borrow().id, value);
+                    }
+                    (variables, ConstValue::Tuple(values)) if variables.len() == values.len() => {
+                        for (variable, value) in variables.iter().zip(values) {
+                            environment.insert(variable.borrow().id, value);
+                        }
+                    }
+                    _ => return None,
+                }
+                Some(Flow::Next)
+            }
+            Statement::Assign(statement) => {
+                let value = self.expression(statement.value.get(), environment)?;
+                let id = statement.target_variable.get().borrow().id;
+                let mut target = environment.get(&id)?.clone();
+                self.assign(
+                    &mut target,
+                    &statement.operation,
+                    &statement.target_accesses,
+                    value,
+                    environment,
+                )?;
+                environment.insert(id, target);
+                Some(Flow::Next)
+            }
+            Statement::Conditional(statement) => match self.expression(statement.condition.get(), environment)? {
+                ConstValue::Boolean(true) => self.statement(statement.result.get(), environment),
+                ConstValue::Boolean(false) => match statement.next.get() {
+                    Some(next) => self.statement(next, environment),
+                    None => Some(Flow::Next),
+                },
+                _ => None,
+            },
+            Statement::Iteration(statement) => {
+                let index_type = match &statement.variable.borrow().type_ {
+                    Type::Integer(index_type) => index_type.clone(),
+                    _ => return None,
+                };
+                let bound = |value: ConstValue| match value {
+                    ConstValue::Int(value) => value.to_string().parse::<i128>().ok(),
+                    _ => None,
+                };
+                let from = bound(self.expression(statement.start.get(), environment)?)?;
+                let to = bound(self.expression(statement.stop.get(), environment)?)?;
+                let step = match statement.step.get() {
+                    Some(step) => Some(bound(self.expression(step, environment)?)?),
+                    None => None,
+                };
+                let iter = statement.counters(from, to, step).ok()?;
+                let id = statement.variable.borrow().id;
+                for i in iter {
+                    let index = ConstInt::parse(&index_type, &i.to_string(), &Span::default()).ok()?;
+                    environment.insert(id, ConstValue::Int(index));
+                    if let Flow::Return(value) = self.statement(statement.body.get(), environment)? {
+                        return Some(Flow::Return(value));
+                    }
+                }
+                Some(Flow::Next)
+            }
+            Statement::Block(statement) => {
+                for statement in statement.statements.iter() {
+                    if let Flow::Return(value) = self.statement(statement.get(), environment)? {
+                        return Some(Flow::Return(value));
+                    }
+                }
+                Some(Flow::Next)
+            }
+            Statement::Expression(statement) => {
+                self.expression(statement.expression.get(), environment)?;
+                Some(Flow::Next)
+            }
+            Statement::Empty(_) => Some(Flow::Next),
+            Statement::Console(_) => None,
+        }
+    }
+
+    fn assign(
+        &mut self,
+        target: &mut ConstValue<'a>,
+        operation: &AssignOperation,
+        accesses: &[AssignAccess<'a>],
+        value: ConstValue<'a>,
+        environment: &Environment<'a>,
+    ) -> Option<()> {
+        match (accesses.split_first(), target) {
+            (None, target) => {
+                let operation = match operation {
+                    AssignOperation::Assign => {
+                        *target = value;
+                        return Some(());
+                    }
+                    AssignOperation::Add => BinaryOperation::Add,
+                    AssignOperation::Sub => BinaryOperation::Sub,
+                    AssignOperation::Mul => BinaryOperation::Mul,
+                    AssignOperation::Div => BinaryOperation::Div,
+                    AssignOperation::Pow => BinaryOperation::Pow,
+                    _ => return None,
+                };
+                *target = BinaryExpression::evaluate(&operation, target.clone(), value)?;
+                Some(())
+            }
+            (Some((AssignAccess::ArrayIndex(index), rest)), ConstValue::Array(items)) => {
+                let index = self.index(index.get(), environment)?;
+                self.assign(items.get_mut(index)?, operation, rest, value, environment)
+            }
+            (Some((AssignAccess::Tuple(index), rest)), ConstValue::Tuple(items)) => {
+                self.assign(items.get_mut(*index)?, operation, rest, value, environment)
+            }
+            (Some((AssignAccess::Member(name), rest)), ConstValue::Circuit(_, members)) => {
+                let (_, member) = members.get_mut(name.name.as_ref())?;
+                self.assign(member, operation, rest, value, environment)
+            }
+            _ => None,
+        }
+    }
+
+    fn index(&mut self, expression: &'a Expression<'a>, environment: &Environment<'a>) -> Option<usize> {
+        match self.expression(expression, environment)? {
+            ConstValue::Int(index) => index.to_usize(),
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self, expression: &'a Expression<'a>, environment: &Environment<'a>) -> Option<ConstValue<'a>> {
+        self.burn()?;
+        match expression {
+            Expression::Constant(constant) => Some(constant.value.clone()),
+            Expression::VariableRef(reference) => environment
+                .get(&reference.variable.borrow().id)
+                .cloned()
+                .or_else(|| expression.const_value()),
+            Expression::Binary(binary) => {
+                let left = self.expression(binary.left.get(), environment)?;
+                let right = self.expression(binary.right.get(), environment)?;
+                BinaryExpression::evaluate(&binary.operation, left, right)
+            }
+            Expression::Unary(unary) => {
+                let inner = self.expression(unary.inner.get(), environment)?;
+                UnaryExpression::evaluate(&unary.operation, inner)
+            }
+            Expression::Ternary(ternary) => match self.expression(ternary.condition.get(), environment)? {
+                ConstValue::Boolean(true) => self.expression(ternary.if_true.get(), environment),
+                ConstValue::Boolean(false) => self.expression(ternary.if_false.get(), environment),
+                _ => None,
+            },
+            Expression::Cast(cast) => match (self.expression(cast.inner.get(), environment)?, &cast.target_type) {
+                (ConstValue::Int(int), Type::Integer(target)) => Some(ConstValue::Int(int.cast_to(target))),
+                _ => None,
+            },
+            Expression::LengthOf(length) => match self.expression(length.inner.get(), environment)? {
+                ConstValue::Array(items) => Some(ConstValue::Int(ConstInt::U32(items.len() as u32))),
+                _ => None,
+            },
+            Expression::ArrayInline(array) => {
+                let mut items = Vec::with_capacity(array.elements.len());
+                for (element, spread) in array.elements.iter() {
+                    match (self.expression(element.get(), environment)?, spread) {
+                        (ConstValue::Array(inner), true) => items.extend(inner),
+                        (_, true) => return None,
+                        (value, false) => items.push(value),
+                    }
+                }
+                Some(ConstValue::Array(items))
+            }
+            Expression::ArrayInit(array) => {
+                let element = self.expression(array.element.get(), environment)?;
+                Some(ConstValue::Array(vec![element; array.len]))
+            }
+            Expression::ArrayAccess(access) => {
+                let array = self.expression(access.array.get(), environment)?;
+                let index = self.index(access.index.get(), environment)?;
+                match array {
+                    ConstValue::Array(mut items) if index < items.len() => Some(items.swap_remove(index)),
+                    _ => None,
+                }
+            }
+            Expression::TupleInit(tuple) => Some(ConstValue::Tuple(
+                tuple
+                    .elements
+                    .iter()
+                    .map(|element| self.expression(element.get(), environment))
+                    .collect::<Option<Vec<_>>>()?,
+            )),
+            Expression::TupleAccess(access) => match self.expression(access.tuple_ref.get(), environment)? {
+                ConstValue::Tuple(mut items) if access.index < items.len() => Some(items.swap_remove(access.index)),
+                _ => None,
+            },
+            Expression::CircuitInit(init) => {
+                let mut members = IndexMap::new();
+                for (name, value) in init.values.iter() {
+                    let value = self.expression(value.get(), environment)?;
+                    members.insert(name.name.to_string(), (name.clone(), value));
+                }
+                Some(ConstValue::Circuit(init.circuit.get(), members))
+            }
+            Expression::CircuitAccess(access) => match access.target.get() {
+                // Static members are constant.
+                None => access.const_value(),
+                Some(target) => match self.expression(target, environment)? {
+                    ConstValue::Circuit(_, mut members) => {
+                        members.remove(access.member.name.as_ref()).map(|(_, value)| value)
+                    }
+                    _ => None,
+                },
+            },
+            Expression::Call(call) => {
+                if call.target.get().is_some() || !self.is_pure(call.function.get()) {
+                    return None;
+                }
+                let key = self.cache.key(expression, environment);
+                if let Some(value) = self.cache.values.get(&key) {
+                    self.cache.hits += 1;
+                    return Some(value.clone());
+                }
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|argument| self.expression(argument.get(), environment))
+                    .collect::<Option<Vec<_>>>()?;
+                self.cache.misses += 1;
+                let value = self.call(call.function.get(), arguments)?;
+                self.cache.values.insert(key, value.clone());
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Records the variables an expression reads, in the order they are first read.
+#[derive(Default)]
+struct FreeVariables {
+    variables: Vec<u32>,
+}
+
+impl<'a> ExpressionVisitor<'a> for FreeVariables {
+    fn visit_variable_ref(&mut self, input: &VariableRef<'a>) -> VisitResult {
+        let id = input.variable.borrow().id;
+        if !self.variables.contains(&id) {
+            self.variables.push(id);
+        }
+        Default::default()
+    }
+}
+
+impl<'a> StatementVisitor<'a> for FreeVariables {}
+
+impl<'a> ProgramVisitor<'a> for FreeVariables {}
+
+/// Records the side effects and callees of a function body.
+struct EffectCollector<'a> {
+    input: Option<&'a Variable<'a>>,
+    impure: bool,
+    callees: Vec<&'a Function<'a>>,
+}
+
+impl<'a> EffectCollector<'a> {
+    fn new(function: &'a Function<'a>) -> Self {
+        Self {
+            input: function.scope.resolve_input().map(|input| input.container),
+            impure: false,
+            callees: vec![],
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for EffectCollector<'a> {
+    fn visit_variable_ref(&mut self, input: &VariableRef<'a>) -> VisitResult {
+        if let Some(container) = self.input {
+            if std::ptr::eq(input.variable, container) {
+                self.impure = true;
+            }
+        }
+        Default::default()
+    }
+
+    fn visit_call(&mut self, input: &CallExpression<'a>) -> VisitResult {
+        self.callees.push(input.function.get());
+        Default::default()
+    }
+}
+
+impl<'a> StatementVisitor<'a> for EffectCollector<'a> {
+    fn visit_console(&mut self, _input: &ConsoleStatement<'a>) -> VisitResult {
+        self.impure = true;
+        Default::default()
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for EffectCollector<'a> {}
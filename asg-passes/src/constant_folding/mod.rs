@@ -14,19 +14,35 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+mod evaluator;
+pub use evaluator::*;
+
 use std::cell::Cell;
 
 use leo_asg::*;
-use leo_errors::Result;
+use leo_errors::{CancellationToken, Result};
 
 pub struct ConstantFolding<'a, 'b> {
     program: &'b Program<'a>,
+    calls: CallEvaluator<'a>,
+    /// The condition of the `console.assert` being folded.
+    assert_condition: Option<&'a Expression<'a>>,
 }
 
 impl<'a, 'b> ExpressionVisitor<'a> for ConstantFolding<'a, 'b> {
     fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
         let expr = input.get();
-        if let Some(const_value) = expr.const_value() {
+        // An asserted comparison keeps its folded operands, so an assert that always fails can show what it compares.
+        if let (Some(condition), Expression::Binary(binary)) = (self.assert_condition, expr) {
+            if std::ptr::eq(condition, expr) && is_comparison(&binary.operation) {
+                return VisitResult::VisitChildren;
+            }
+        }
+        let const_value = expr.const_value().or_else(|| self.calls.evaluate(expr));
+        if self.calls.interrupted() {
+            return VisitResult::Exit;
+        }
+        if let Some(const_value) = const_value {
             let folded_expr = Expression::Constant(Constant {
                 parent: Cell::new(expr.get_parent()),
                 span: expr.span().cloned(),
@@ -41,15 +57,60 @@ impl<'a, 'b> ExpressionVisitor<'a> for ConstantFolding<'a, 'b> {
     }
 }
 
-impl<'a, 'b> StatementVisitor<'a> for ConstantFolding<'a, 'b> {}
+impl<'a, 'b> StatementVisitor<'a> for ConstantFolding<'a, 'b> {
+    fn visit_console(&mut self, input: &ConsoleStatement<'a>) -> VisitResult {
+        self.assert_condition = match &input.function {
+            ConsoleFunction::Assert(condition) => Some(condition.get()),
+            _ => None,
+        };
+        VisitResult::VisitChildren
+    }
+}
 
 impl<'a, 'b> ProgramVisitor<'a> for ConstantFolding<'a, 'b> {}
 
 impl<'a, 'b> AsgPass<'a> for ConstantFolding<'a, 'b> {
     fn do_pass(asg: Program<'a>) -> Result<Program<'a>> {
-        let pass = ConstantFolding { program: &asg };
+        Self::do_pass_with_fuel(asg, DEFAULT_CALL_FUEL)
+    }
+}
+
+impl<'a, 'b> ConstantFolding<'a, 'b> {
+    /// Folds constant expressions, giving up on any call whose evaluation burns more than `fuel`.
+    pub fn do_pass_with_fuel(asg: Program<'a>, fuel: usize) -> Result<Program<'a>> {
+        Self::do_pass_with_statistics(asg, fuel).map(|(asg, _)| asg)
+    }
+
+    /// Folds constant expressions like [`Self::do_pass_with_fuel`], and returns the counts of the
+    /// lookups in the cache of evaluated calls.
+    pub fn do_pass_with_statistics(asg: Program<'a>, fuel: usize) -> Result<(Program<'a>, EvaluationStatistics)> {
+        Self::do_pass_with_cancellation(asg, fuel, &CancellationToken::default())
+    }
+
+    /// Folds constant expressions like [`Self::do_pass_with_statistics`], stopping with an error
+    /// once `cancellation` is cancelled or past its deadline.
+    pub fn do_pass_with_cancellation(
+        asg: Program<'a>,
+        fuel: usize,
+        cancellation: &CancellationToken,
+    ) -> Result<(Program<'a>, EvaluationStatistics)> {
+        let pass = ConstantFolding {
+            program: &asg,
+            calls: CallEvaluator::new(fuel).with_cancellation(cancellation),
+            assert_condition: None,
+        };
         let mut director = VisitorDirector::new(pass);
         director.visit_program(&asg).ok();
-        Ok(asg)
+        let mut calls = director.visitor().calls;
+        if let Some(error) = calls.take_interruption() {
+            return Err(error);
+        }
+        Ok((asg, calls.statistics()))
     }
 }
+
+/// Returns `true` for the operations comparing their operands.
+pub(crate) fn is_comparison(operation: &BinaryOperation) -> bool {
+    use BinaryOperation::*;
+    matches!(operation, Eq | Ne | Lt | Le | Gt | Ge)
+}
@@ -0,0 +1,195 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg::*;
+use leo_errors::Result;
+
+use indexmap::IndexMap;
+
+/// Returns `true` if the name is private to the program that defines it, which by convention means it starts with `_`.
+fn is_private(name: &str) -> bool {
+    name.starts_with('_')
+}
+
+///
+/// Warns about functions that other programs can call but whose parameters take circuits they cannot construct.
+///
+/// A circuit can be constructed outside of its program if none of its variable members is private and the circuits
+/// of those members can be constructed, or if a public function or static member gives one without needing one already.
+/// `main` takes its parameters from the program input, so it is never checked.
+///
+pub struct Constructibility<'a> {
+    functions: IndexMap<u32, &'a Function<'a>>,
+    circuits: IndexMap<u32, &'a Circuit<'a>>,
+}
+
+impl<'a> Constructibility<'a> {
+    fn new(program: &Program<'a>) -> Self {
+        let mut pass = Constructibility {
+            functions: IndexMap::new(),
+            circuits: IndexMap::new(),
+        };
+        pass.collect(program);
+        pass
+    }
+
+    /// Gathers the functions and circuits of the program and of every program it imports.
+    fn collect(&mut self, program: &Program<'a>) {
+        for function in program.functions.values() {
+            self.functions.insert(function.id, *function);
+        }
+        for circuit in program.circuits.values() {
+            self.circuits.insert(circuit.id, *circuit);
+            for member in circuit.members.borrow().values() {
+                if let CircuitMember::Function(function) = member {
+                    self.functions.insert(function.id, *function);
+                }
+            }
+        }
+        for imported in program.imported_modules.values() {
+            self.collect(imported);
+        }
+    }
+
+    /// Returns `true` if another program can call the function.
+    fn is_public(function: &Function<'a>) -> bool {
+        let circuit_is_public = match function.circuit.get() {
+            Some(circuit) => !is_private(circuit.name.borrow().name.as_ref()),
+            None => function.name.borrow().name.as_ref() != "main",
+        };
+        circuit_is_public && !is_private(function.name.borrow().name.as_ref()) && !function.is_test()
+    }
+
+    /// Returns `true` if another program can get the circuit from a function or a static member without having one.
+    fn has_factory(&self, circuit: &'a Circuit<'a>) -> bool {
+        let returns_circuit = self.functions.values().any(|function| {
+            let needs_circuit = function
+                .arguments
+                .values()
+                .any(|argument| contains_circuit(&argument.get().borrow().type_, circuit))
+                || (function.circuit.get().map(|owner| owner.id) == Some(circuit.id)
+                    && function.qualifier != FunctionQualifier::Static);
+            Self::is_public(function) && contains_circuit(&function.output, circuit) && !needs_circuit
+        });
+
+        returns_circuit
+            || self.circuits.values().any(|owner| {
+                !is_private(owner.name.borrow().name.as_ref())
+                    && owner.members.borrow().iter().any(|(name, member)| match member {
+                        CircuitMember::Static(type_, _) => !is_private(name) && contains_circuit(type_, circuit),
+                        _ => false,
+                    })
+            })
+    }
+
+    /// Returns why another program cannot construct the circuit, if it cannot.
+    fn blocked_reason(&self, circuit: &'a Circuit<'a>) -> Option<String> {
+        if self.has_factory(circuit) {
+            return None;
+        }
+
+        let members = circuit.members.borrow();
+        let private = members
+            .iter()
+            .filter(|(name, member)| matches!(member, CircuitMember::Variable(_)) && is_private(name))
+            .map(|(name, _)| format!("`{}`", name))
+            .collect::<Vec<_>>();
+        if !private.is_empty() {
+            let (noun, verb) = if private.len() == 1 {
+                ("member", "is")
+            } else {
+                ("members", "are")
+            };
+            return Some(format!(
+                "its {} {} {} private and no public function returns it",
+                noun,
+                private.join(", "),
+                verb
+            ));
+        }
+
+        for (name, member) in members.iter() {
+            if let CircuitMember::Variable(type_) = member {
+                for inner in circuits_of(type_) {
+                    if self.blocked_reason(inner).is_some() {
+                        return Some(format!(
+                            "its member `{}` takes circuit `{}`, which cannot be constructed either",
+                            name,
+                            inner.name.borrow().name
+                        ));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn check(&self, program: &Program<'a>) {
+        for function in self.functions.values() {
+            if !Self::is_public(function) || function.allows_unconstructible_parameters() {
+                continue;
+            }
+            let function_name = match function.circuit.get() {
+                Some(circuit) => format!("{}::{}", circuit.name.borrow().name, function.name.borrow().name),
+                None => function.name.borrow().name.to_string(),
+            };
+            for argument in function.arguments.values() {
+                let argument = argument.get().borrow();
+                for circuit in circuits_of(&argument.type_) {
+                    if let Some(reason) = self.blocked_reason(circuit) {
+                        program.context.warn(
+                            format!(
+                                "parameter `{}` of function `{}` takes circuit `{}`, which other programs cannot construct: {}",
+                                argument.name.name,
+                                function_name,
+                                circuit.name.borrow().name,
+                                reason
+                            ),
+                            &argument.name.span,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the circuits the type is made of, without repeating any.
+fn circuits_of<'a>(type_: &Type<'a>) -> Vec<&'a Circuit<'a>> {
+    let mut circuits: Vec<&'a Circuit<'a>> = vec![];
+    let mut types = vec![type_];
+    while let Some(type_) = types.pop() {
+        match type_ {
+            Type::Circuit(circuit) if !circuits.iter().any(|known| known.id == circuit.id) => circuits.push(circuit),
+            Type::Array(inner, _) | Type::ArrayWithoutSize(inner) => types.push(inner),
+            Type::Tuple(inner) => types.extend(inner.iter()),
+            _ => (),
+        }
+    }
+    circuits
+}
+
+/// Returns `true` if the type is or contains the circuit.
+fn contains_circuit<'a>(type_: &Type<'a>, circuit: &Circuit<'a>) -> bool {
+    circuits_of(type_).iter().any(|inner| inner.id == circuit.id)
+}
+
+impl<'a> AsgPass<'a> for Constructibility<'a> {
+    fn do_pass(asg: Program<'a>) -> Result<Program<'a>> {
+        Constructibility::new(&asg).check(&asg);
+        Ok(asg)
+    }
+}
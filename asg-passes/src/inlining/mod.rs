@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::Cell;
+
+use leo_asg::*;
+use leo_errors::{AsgError, Result, Span};
+
+use indexmap::IndexMap;
+
+/// Why the calls to a function are inlined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlineReason {
+    /// The function is annotated with `@inline(always)`.
+    Annotated,
+    /// The function has no more expressions than the inline threshold.
+    BelowThreshold,
+    /// The function has more expressions than the inline threshold, so it would be called rather than inlined,
+    /// but circuits have no call instruction.
+    NoCallInstruction,
+}
+
+/// How the calls to a function are compiled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InlineDecision {
+    /// The number of places the function is called from.
+    pub calls: usize,
+    /// The number of expressions of the function.
+    pub size: usize,
+    pub reason: InlineReason,
+}
+
+///
+/// Decides how the calls to every function of the program are compiled, failing on calls that cannot be.
+///
+/// Circuits have no call instruction, so every call is inlined, and a call to a function annotated with
+/// `@inline(never)` is an error. The decision records the reason a call would be inlined were there a way
+/// to call a function, which for functions without an annotation is their size against `threshold`.
+///
+#[derive(Default)]
+pub struct Inlining<'a> {
+    /// The id of the function whose body is visited.
+    current: Option<u32>,
+    /// Maps function id => number of expressions of its body.
+    sizes: IndexMap<u32, usize>,
+    /// Maps function id => the function and where it is called.
+    calls: IndexMap<u32, (&'a Function<'a>, Vec<Option<Span>>)>,
+}
+
+impl<'a> Inlining<'a> {
+    ///
+    /// Returns the decisions for the functions that are called, keyed by name, or `Circuit::function`
+    /// for circuit member functions.
+    ///
+    pub fn do_pass_with_threshold(
+        asg: Program<'a>,
+        threshold: usize,
+    ) -> Result<(Program<'a>, IndexMap<String, InlineDecision>)> {
+        let mut director = VisitorDirector::new(Inlining::default());
+        director.visit_program(&asg).ok();
+        let inlining = director.visitor();
+
+        let mut decisions = IndexMap::new();
+        for (id, (function, calls)) in inlining.calls.iter() {
+            let name = match function.circuit.get() {
+                Some(circuit) => format!("{}::{}", circuit.name.borrow().name, function.name.borrow().name),
+                None => function.name.borrow().name.to_string(),
+            };
+            let size = inlining.sizes.get(id).copied().unwrap_or_default();
+            let reason = match function.inline_request() {
+                Some(InlineRequest::Never) => {
+                    let span = calls.iter().flatten().next().cloned().unwrap_or_default();
+                    return Err(AsgError::inline_never_called(name, &span).into());
+                }
+                Some(InlineRequest::Always) => InlineReason::Annotated,
+                None if size <= threshold => InlineReason::BelowThreshold,
+                None => InlineReason::NoCallInstruction,
+            };
+            decisions.insert(
+                name,
+                InlineDecision {
+                    calls: calls.len(),
+                    size,
+                    reason,
+                },
+            );
+        }
+        Ok((asg, decisions))
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for Inlining<'a> {
+    fn visit_expression(&mut self, _input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        if let Some(id) = self.current {
+            *self.sizes.entry(id).or_default() += 1;
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_call(&mut self, input: &CallExpression<'a>) -> VisitResult {
+        let function = input.function.get();
+        self.calls
+            .entry(function.id)
+            .or_insert_with(|| (function, vec![]))
+            .1
+            .push(input.span.clone());
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Inlining<'a> {}
+
+impl<'a> ProgramVisitor<'a> for Inlining<'a> {
+    fn visit_function(&mut self, input: &'a Function<'a>) -> VisitResult {
+        self.current = Some(input.id);
+        self.sizes.entry(input.id).or_default();
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_member(&mut self, input: &CircuitMember<'a>) -> VisitResult {
+        if let CircuitMember::Static(..) = input {
+            self.current = None;
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_global_const(&mut self, _input: &'a DefinitionStatement<'a>) -> VisitResult {
+        self.current = None;
+        VisitResult::VisitChildren
+    }
+}
@@ -16,8 +16,29 @@
 
 #![doc = include_str!("../README.md")]
 
+pub mod boolean_simplification;
+pub use boolean_simplification::*;
+
 pub mod constant_folding;
 pub use constant_folding::*;
 
+pub mod constructibility;
+pub use constructibility::*;
+
 pub mod dead_code_elimination;
 pub use dead_code_elimination::*;
+
+pub mod inlining;
+pub use inlining::*;
+
+pub mod naming;
+pub use naming::*;
+
+pub mod range_comparison;
+pub use range_comparison::*;
+
+pub mod static_asserts;
+pub use static_asserts::*;
+
+pub mod usage;
+pub use usage::*;
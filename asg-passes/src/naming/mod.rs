@@ -0,0 +1,324 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg::*;
+use leo_errors::Result;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// A way of writing the words of a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// `like_this`
+    Snake,
+    /// `LikeThis`
+    Pascal,
+    /// `LIKE_THIS`
+    ScreamingSnake,
+}
+
+impl Case {
+    fn describe(self) -> &'static str {
+        match self {
+            Case::Snake => "snake_case",
+            Case::Pascal => "PascalCase",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    /// Writes the words in this case, spelling the allowed acronyms as given in PascalCase.
+    fn join(self, words: &[String], acronyms: &[String]) -> String {
+        match self {
+            Case::Snake => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::ScreamingSnake => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::Pascal => words
+                .iter()
+                .map(
+                    |word| match acronyms.iter().find(|acronym| acronym.eq_ignore_ascii_case(word)) {
+                        Some(acronym) => acronym.clone(),
+                        None => {
+                            let mut chars = word.chars();
+                            let first = chars.next().map(|c| c.to_ascii_uppercase());
+                            first.into_iter().chain(chars.map(|c| c.to_ascii_lowercase())).collect()
+                        }
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+///
+/// The case the names of each kind of declaration must be written in, or `None` where they are not checked.
+///
+#[derive(Clone, Debug, Default)]
+pub struct NamingRules {
+    pub functions: Option<Case>,
+    pub circuits: Option<Case>,
+    pub consts: Option<Case>,
+    pub variables: Option<Case>,
+    /// Acronyms that PascalCase names may spell in capitals, such as `R1CS`.
+    pub acronyms: Vec<String>,
+}
+
+impl NamingRules {
+    /// Returns `true` if the names of some kind of declaration are checked.
+    pub fn is_enabled(&self) -> bool {
+        self.functions.is_some() || self.circuits.is_some() || self.consts.is_some() || self.variables.is_some()
+    }
+}
+
+///
+/// Warns about declarations whose names are not written in the case the rules ask of their kind.
+///
+/// Only the names of the declarations are checked, not the places that use them. Where the name splits into
+/// words without guessing and the name written in the right case is not taken, the warning suggests it.
+/// Functions annotated with `@allow_naming` are not checked, nor are their parameters and variables.
+/// Leading underscores mark private names, so they are kept as written.
+///
+pub struct NamingConventions<'a, 'b> {
+    program: &'b Program<'a>,
+    rules: &'b NamingRules,
+    /// Maps the short names given by renaming => the names they were declared with.
+    originals: &'b IndexMap<String, String>,
+}
+
+impl<'a, 'b> NamingConventions<'a, 'b> {
+    ///
+    /// Reports the declarations of the program that break the rules.
+    ///
+    /// The names of a renamed program are checked as they were declared, by their entries in `originals`.
+    ///
+    pub fn do_pass(asg: Program<'a>, rules: &NamingRules, originals: &IndexMap<String, String>) -> Result<Program<'a>> {
+        let pass = NamingConventions {
+            program: &asg,
+            rules,
+            originals,
+        };
+        pass.check_program();
+        Ok(asg)
+    }
+
+    fn check_program(&self) {
+        let program = self.program;
+        for (name, definition) in program.global_consts.iter() {
+            for variable in definition.variables.iter() {
+                let variable = variable.borrow();
+                if variable.name.name.as_ref() == name.as_str() {
+                    self.check("constant", &variable.name, self.rules.consts, |name| {
+                        self.is_global(name)
+                    });
+                }
+            }
+        }
+
+        for function in program.functions.values() {
+            self.check_function(*function);
+        }
+
+        for circuit in program.circuits.values() {
+            self.check("circuit", &circuit.name.borrow(), self.rules.circuits, |name| {
+                self.is_global(name)
+            });
+            for member in circuit.members.borrow().values() {
+                if let CircuitMember::Function(function) = member {
+                    self.check_function(*function);
+                }
+            }
+        }
+    }
+
+    fn check_function(&self, function: &'a Function<'a>) {
+        if function.allows_naming() {
+            return;
+        }
+
+        let circuit = function.circuit.get();
+        self.check(
+            "function",
+            &function.name.borrow(),
+            self.rules.functions,
+            |name| match circuit {
+                Some(circuit) => circuit.members.borrow().contains_key(name),
+                None => self.is_global(name),
+            },
+        );
+
+        let mut variables = function
+            .arguments
+            .values()
+            .map(|argument| argument.get())
+            .collect::<Vec<_>>();
+        if let Some(body) = function.body.get() {
+            declarations(body, &mut variables);
+        }
+        let locals = variables
+            .iter()
+            .map(|variable| variable.borrow().name.name.to_string())
+            .collect::<IndexSet<_>>();
+        for variable in variables.iter() {
+            self.check("variable", &variable.borrow().name, self.rules.variables, |name| {
+                locals.contains(name) || self.is_global(name)
+            });
+        }
+    }
+
+    /// Warns if the declared name is not written in `case`, suggesting a name for which `is_taken` does not hold.
+    fn check(&self, kind: &str, identifier: &Identifier, case: Option<Case>, is_taken: impl Fn(&str) -> bool) {
+        let case = match case {
+            Some(case) => case,
+            None => return,
+        };
+        let declared = self.original(identifier.name.as_ref());
+        let body = declared.trim_start_matches('_');
+        if body.is_empty() {
+            return;
+        }
+        let prefix = &declared[..declared.len() - body.len()];
+
+        let written = words(body, &self.rules.acronyms).map(|words| case.join(&words, &self.rules.acronyms));
+        if written.as_deref() == Some(body) {
+            return;
+        }
+
+        let suggestion = written
+            .map(|written| format!("{}{}", prefix, written))
+            .filter(|suggestion| !is_taken(&self.renamed(suggestion)));
+        let message = match suggestion {
+            Some(suggestion) => format!(
+                "{} `{}` is not {}, rename it to `{}`",
+                kind,
+                declared,
+                case.describe(),
+                suggestion
+            ),
+            None => format!("{} `{}` is not {}", kind, declared, case.describe()),
+        };
+        self.program.context.warn(message, &identifier.span);
+    }
+
+    /// Returns `true` if the name is declared at the top level of the program.
+    fn is_global(&self, name: &str) -> bool {
+        let scope = self.program.scope;
+        scope.resolve_variable(name).is_some()
+            || scope.resolve_global_const(name).is_some()
+            || scope.resolve_function(name).is_some()
+            || scope.resolve_circuit(name).is_some()
+            || scope.resolve_alias(name).is_some()
+    }
+
+    fn original(&self, name: &str) -> String {
+        self.originals.get(name).cloned().unwrap_or_else(|| name.to_string())
+    }
+
+    /// Returns the name that renaming gave to the declarations named `original`.
+    fn renamed(&self, original: &str) -> String {
+        self.originals
+            .iter()
+            .find(|(_, name)| name.as_str() == original)
+            .map(|(renamed, _)| renamed.clone())
+            .unwrap_or_else(|| original.to_string())
+    }
+}
+
+/// Collects the variables declared by the statement.
+fn declarations<'a>(statement: &'a Statement<'a>, variables: &mut Vec<&'a Variable<'a>>) {
+    match statement {
+        Statement::Block(block) => {
+            for statement in block.statements.iter() {
+                declarations(statement.get(), variables);
+            }
+        }
+        Statement::Conditional(conditional) => {
+            declarations(conditional.result.get(), variables);
+            if let Some(next) = conditional.next.get() {
+                declarations(next, variables);
+            }
+        }
+        Statement::Iteration(iteration) => {
+            variables.push(iteration.variable);
+            declarations(iteration.body.get(), variables);
+        }
+        Statement::Definition(definition) => variables.extend(definition.variables.iter().copied()),
+        _ => (),
+    }
+}
+
+///
+/// Splits a name into its words, at underscores and where the case changes.
+///
+/// The acronyms are kept whole where a word starts with one. Returns `None` where the split is a guess,
+/// which is where a run of capitals is followed by a lowercase letter, as in `HTTPServer`.
+///
+fn words(name: &str, acronyms: &[String]) -> Option<Vec<String>> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            index += 1;
+            continue;
+        }
+
+        if c.is_ascii_uppercase() && !word.is_empty() {
+            if word.chars().any(|c| c.is_ascii_lowercase()) {
+                // A capital starts a word after lowercase letters, as in `myValue` and `vec2D`.
+                words.push(std::mem::take(&mut word));
+            } else if chars.get(index + 1).map(|c| c.is_ascii_lowercase()).unwrap_or(false) {
+                return None;
+            }
+        }
+
+        if word.is_empty() {
+            let rest = chars[index..].iter().collect::<String>();
+            let acronym = acronyms.iter().find(|acronym| {
+                rest.starts_with(acronym.as_str())
+                    && !rest[acronym.len()..]
+                        .chars()
+                        .next()
+                        .map(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                        .unwrap_or(false)
+            });
+            if let Some(acronym) = acronym {
+                words.push(acronym.clone());
+                index += acronym.chars().count();
+                continue;
+            }
+        }
+
+        word.push(c);
+        index += 1;
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Some(words)
+}
@@ -0,0 +1,149 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::Cell;
+
+use leo_asg::*;
+use leo_errors::Result;
+use num_bigint::BigInt;
+
+/// Warns about integer comparisons whose result is decided by the ranges of their operand types,
+/// such as `a >= 0u8` or `(a as u16) < 256u16` for `a: u8`, and optionally folds them to their result.
+pub struct RangeComparison<'a, 'b> {
+    program: &'b Program<'a>,
+    fold: bool,
+}
+
+impl<'a, 'b> RangeComparison<'a, 'b> {
+    ///
+    /// Warns about the comparisons decided by the ranges of their operand types,
+    /// and replaces them with their result if `fold` is set.
+    ///
+    pub fn do_pass_with_folding(asg: Program<'a>, fold: bool) -> Result<Program<'a>> {
+        let pass = RangeComparison { program: &asg, fold };
+        let mut director = VisitorDirector::new(pass);
+        director.visit_program(&asg).ok();
+        Ok(asg)
+    }
+
+    ///
+    /// Returns the smallest and largest value the expression can take, if it is an integer.
+    ///
+    pub(crate) fn range(expr: &'a Expression<'a>) -> Option<(BigInt, BigInt)> {
+        if let Some(ConstValue::Int(value)) = expr.const_value() {
+            let value: BigInt = value.raw_value().parse().ok()?;
+            return Some((value.clone(), value));
+        }
+
+        match (expr, expr.get_type()?) {
+            // Casts keep the range of their operand unless it doesn't fit the target type.
+            (Expression::Cast(cast), Type::Integer(target)) => {
+                let (min, max) = type_range(&target);
+                match Self::range(cast.inner.get()) {
+                    Some((low, high)) if low >= min && high <= max => Some((low, high)),
+                    _ => Some((min, max)),
+                }
+            }
+            (_, Type::Integer(type_)) => Some(type_range(&type_)),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the result of the comparison if it is the same for every value of the operands.
+    ///
+    pub(crate) fn decide(
+        operation: &BinaryOperation,
+        left: &(BigInt, BigInt),
+        right: &(BigInt, BigInt),
+    ) -> Option<bool> {
+        match operation {
+            BinaryOperation::Lt if left.1 < right.0 => Some(true),
+            BinaryOperation::Lt if left.0 >= right.1 => Some(false),
+            BinaryOperation::Le if left.1 <= right.0 => Some(true),
+            BinaryOperation::Le if left.0 > right.1 => Some(false),
+            BinaryOperation::Gt => Self::decide(&BinaryOperation::Lt, right, left),
+            BinaryOperation::Ge => Self::decide(&BinaryOperation::Le, right, left),
+            BinaryOperation::Eq if left.1 < right.0 || right.1 < left.0 => Some(false),
+            BinaryOperation::Ne => Self::decide(&BinaryOperation::Eq, left, right).map(|equal| !equal),
+            _ => None,
+        }
+    }
+}
+
+///
+/// Returns the smallest and largest value of the integer type.
+///
+fn type_range(type_: &IntegerType) -> (BigInt, BigInt) {
+    let bits = type_.bit_width();
+    if type_.is_signed() {
+        let bound = BigInt::from(1) << (bits - 1);
+        (-bound.clone(), bound - 1)
+    } else {
+        (BigInt::from(0), (BigInt::from(1) << bits) - 1)
+    }
+}
+
+impl<'a, 'b> ExpressionVisitor<'a> for RangeComparison<'a, 'b> {
+    fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        let expr = input.get();
+        let binary = match expr {
+            // Comparisons of constants are left to constant folding.
+            Expression::Binary(binary) if expr.const_value().is_none() => binary,
+            _ => return VisitResult::VisitChildren,
+        };
+
+        let result = Self::range(binary.left.get())
+            .zip(Self::range(binary.right.get()))
+            .and_then(|(left, right)| Self::decide(&binary.operation, &left, &right));
+        let result = match result {
+            Some(result) => result,
+            None => return VisitResult::VisitChildren,
+        };
+
+        if let Some(span) = expr.span() {
+            self.program.context.warn(
+                format!(
+                    "comparison is always `{}` because of the ranges of its operand types",
+                    result
+                ),
+                span,
+            );
+        }
+
+        if self.fold {
+            let folded_expr = Expression::Constant(Constant {
+                parent: Cell::new(expr.get_parent()),
+                span: expr.span().cloned(),
+                value: ConstValue::Boolean(result),
+            });
+            input.set(self.program.context.alloc_expression(folded_expr));
+            VisitResult::SkipChildren
+        } else {
+            VisitResult::VisitChildren
+        }
+    }
+}
+
+impl<'a, 'b> StatementVisitor<'a> for RangeComparison<'a, 'b> {}
+
+impl<'a, 'b> ProgramVisitor<'a> for RangeComparison<'a, 'b> {}
+
+impl<'a, 'b> AsgPass<'a> for RangeComparison<'a, 'b> {
+    fn do_pass(asg: Program<'a>) -> Result<Program<'a>> {
+        Self::do_pass_with_folding(asg, false)
+    }
+}
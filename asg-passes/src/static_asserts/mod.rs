@@ -0,0 +1,227 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::Cell;
+
+use crate::{constant_folding::is_comparison, RangeComparison};
+use leo_asg::*;
+use leo_errors::{AsgError, Result};
+
+/// Counts of the asserts removed because they always hold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssertStatistics {
+    /// Asserts whose condition folded to `true`.
+    pub constant: usize,
+    /// Asserts whose condition the ranges of its operand types decide.
+    pub from_types: usize,
+}
+
+impl AssertStatistics {
+    /// Returns the number of asserts removed.
+    pub fn total(&self) -> usize {
+        self.constant + self.from_types
+    }
+}
+
+/// What shows that an assert always holds or always fails.
+enum Proof {
+    /// The condition folded to a constant, written with the values it compares where it is a comparison.
+    Constant(String),
+    /// The condition compares operands whose types do not have a value for which the result differs.
+    Types,
+}
+
+///
+/// Fails the compilation on `console.assert`s that fail whenever they are reached, and removes those that always hold.
+///
+/// An assert is decided by its condition once constant folding ran, or by the ranges of the types of the operands
+/// of the comparison it asserts. An assert that always fails is an error where the entrypoint reaches it
+/// unconditionally, and a warning anywhere else, as a branch or function that is never taken may hold it.
+///
+pub struct StaticAsserts<'a, 'b> {
+    program: &'b Program<'a>,
+    entrypoints: &'b [String],
+    /// Keeps the asserts that always hold, as documentation.
+    keep_true: bool,
+    statistics: AssertStatistics,
+}
+
+impl<'a, 'b> StaticAsserts<'a, 'b> {
+    ///
+    /// Checks the asserts of the program, whose entrypoints are the functions named `entrypoints`,
+    /// and removes those that always hold unless `keep_true` is set.
+    ///
+    pub fn do_pass_with_options(
+        asg: Program<'a>,
+        entrypoints: &[String],
+        keep_true: bool,
+    ) -> Result<(Program<'a>, AssertStatistics)> {
+        let mut pass = StaticAsserts {
+            program: &asg,
+            entrypoints,
+            keep_true,
+            statistics: AssertStatistics::default(),
+        };
+        pass.check_program(&asg, true)?;
+        let statistics = pass.statistics;
+        Ok((asg, statistics))
+    }
+
+    fn check_program(&mut self, program: &Program<'a>, root: bool) -> Result<()> {
+        for import in program.imported_modules.values() {
+            self.check_program(import, false)?;
+        }
+        for (name, function) in program.functions.iter() {
+            let entrypoint = root && self.entrypoints.contains(name);
+            self.check_function(*function, entrypoint)?;
+        }
+        for circuit in program.circuits.values() {
+            for member in circuit.members.borrow().values() {
+                if let CircuitMember::Function(function) = member {
+                    self.check_function(*function, false)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_function(&mut self, function: &'a Function<'a>, entrypoint: bool) -> Result<()> {
+        if let Some(body) = function.body.get() {
+            let body = Cell::new(body);
+            self.check_statement(&body, entrypoint)?;
+            function.body.set(Some(body.get()));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Checks the asserts of the statement, which runs whenever the entrypoint runs if `always_reached` is set.
+    ///
+    fn check_statement(&mut self, input: &Cell<&'a Statement<'a>>, always_reached: bool) -> Result<()> {
+        match input.get() {
+            Statement::Block(block) => {
+                let mut reached = always_reached;
+                for statement in block.statements.iter() {
+                    self.check_statement(statement, reached)?;
+                    // The statements after a return that may be taken are only reached on the other paths.
+                    reached = reached && !returns(statement.get());
+                }
+            }
+            Statement::Conditional(conditional) => {
+                self.check_statement(&conditional.result, false)?;
+                if let Some(next) = conditional.next.get() {
+                    let next = Cell::new(next);
+                    self.check_statement(&next, false)?;
+                    conditional.next.set(Some(next.get()));
+                }
+            }
+            Statement::Iteration(iteration) => self.check_statement(&iteration.body, false)?,
+            Statement::Console(console) => {
+                let (holds, proof) = match Self::decide(&console.function) {
+                    Some(decided) => decided,
+                    None => return Ok(()),
+                };
+                let span = console.span.clone().unwrap_or_default();
+                match (holds, proof) {
+                    (true, _) if self.keep_true => (),
+                    (true, proof) => {
+                        match proof {
+                            Proof::Constant(_) => self.statistics.constant += 1,
+                            Proof::Types => self.statistics.from_types += 1,
+                        }
+                        input.set(
+                            self.program
+                                .context
+                                .alloc_statement(Statement::Empty(console.span.clone())),
+                        );
+                    }
+                    (false, Proof::Constant(condition)) if always_reached => {
+                        return Err(AsgError::assert_always_fails(condition, &span).into());
+                    }
+                    (false, Proof::Types) if always_reached => {
+                        return Err(AsgError::assert_always_fails_from_types(&span).into());
+                    }
+                    (false, Proof::Constant(condition)) => self.program.context.warn(
+                        format!(
+                            "assertion fails whenever it is reached, as its condition is `{}`",
+                            condition
+                        ),
+                        &span,
+                    ),
+                    (false, Proof::Types) => self.program.context.warn(
+                        "assertion fails whenever it is reached, as the ranges of its operand types decide its condition"
+                            .to_string(),
+                        &span,
+                    ),
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns whether the assert always holds and what shows it, if it holds for all or no values.
+    ///
+    fn decide(function: &ConsoleFunction<'a>) -> Option<(bool, Proof)> {
+        match function {
+            ConsoleFunction::Assert(condition) => match condition.get() {
+                Expression::Binary(binary) if is_comparison(&binary.operation) => {
+                    Self::compare(&binary.operation, binary.left.get(), binary.right.get())
+                }
+                condition => match condition.const_value() {
+                    Some(ConstValue::Boolean(holds)) => Some((holds, Proof::Constant(holds.to_string()))),
+                    _ => None,
+                },
+            },
+            ConsoleFunction::AssertEq(args) => Self::compare(&BinaryOperation::Eq, args.left.get(), args.right.get()),
+            ConsoleFunction::AssertNe(args) => Self::compare(&BinaryOperation::Ne, args.left.get(), args.right.get()),
+            ConsoleFunction::Error(_) | ConsoleFunction::Log(_) => None,
+        }
+    }
+
+    fn compare(
+        operation: &BinaryOperation,
+        left: &'a Expression<'a>,
+        right: &'a Expression<'a>,
+    ) -> Option<(bool, Proof)> {
+        if let (Some(left), Some(right)) = (left.const_value(), right.const_value()) {
+            let condition = format!("{} {} {}", left, operation.as_ref(), right);
+            return match BinaryExpression::evaluate(operation, left, right)? {
+                ConstValue::Boolean(holds) => Some((holds, Proof::Constant(condition))),
+                _ => None,
+            };
+        }
+
+        let holds = RangeComparison::range(left)
+            .zip(RangeComparison::range(right))
+            .and_then(|(left, right)| RangeComparison::decide(operation, &left, &right))?;
+        Some((holds, Proof::Types))
+    }
+}
+
+/// Returns `true` if the statement may return from its function.
+fn returns(statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(_) => true,
+        Statement::Block(block) => block.statements.iter().any(|statement| returns(statement.get())),
+        Statement::Conditional(conditional) => {
+            returns(conditional.result.get()) || conditional.next.get().map(returns).unwrap_or(false)
+        }
+        Statement::Iteration(iteration) => returns(iteration.body.get()),
+        _ => false,
+    }
+}
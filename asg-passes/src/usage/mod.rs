@@ -0,0 +1,227 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg::*;
+use leo_errors::{Result, Span};
+
+use indexmap::{IndexMap, IndexSet};
+
+///
+/// Warns about function parameters that are never read, and about circuit variable members that no code reads.
+///
+/// Parameters whose name starts with `_` are left alone. A member is read by a member access anywhere in the
+/// program, or when a value of its circuit is compared or logged as a whole, which reads every member.
+/// The members of the circuits in the signature of an entrypoint are its interface, so they are always used.
+///
+/// The compiler runs the pass after dead code elimination, so reads in code that it removes do not count.
+/// Without dead code elimination, they count like any other read.
+///
+#[derive(Default)]
+pub struct Usage {
+    /// The ids of the variables that are read.
+    read_variables: IndexSet<u32>,
+    /// Maps circuit id => names of its members that are read.
+    read_members: IndexMap<u32, IndexSet<String>>,
+    /// Maps circuit id => where it is constructed.
+    constructions: IndexMap<u32, Vec<Span>>,
+}
+
+impl Usage {
+    ///
+    /// Reports the unused parameters and members of the program, whose entrypoints are the functions named `entrypoints`.
+    ///
+    pub fn do_pass_with_entrypoints<'a>(asg: Program<'a>, entrypoints: &[String]) -> Result<Program<'a>> {
+        let mut director = VisitorDirector::new(Usage::default());
+        director.visit_program(&asg).ok();
+        director.visitor().check(&asg, entrypoints);
+        Ok(asg)
+    }
+
+    /// Marks every member of the circuits the type is made of as read, as comparing or logging a value of it does.
+    fn read_whole(&mut self, type_: Option<Type>) {
+        if let Some(type_) = type_ {
+            for circuit in circuits_of(&type_) {
+                let members = circuit.members.borrow();
+                let names = members.iter().filter_map(|(name, member)| match member {
+                    CircuitMember::Variable(_) => Some(name.clone()),
+                    _ => None,
+                });
+                self.read_members.entry(circuit.id).or_default().extend(names);
+            }
+        }
+    }
+
+    fn check(&self, program: &Program, entrypoints: &[String]) {
+        let mut functions = program.functions.values().copied().collect::<Vec<_>>();
+        for circuit in program.circuits.values() {
+            for member in circuit.members.borrow().values() {
+                if let CircuitMember::Function(function) = member {
+                    functions.push(*function);
+                }
+            }
+        }
+
+        let mut interface = IndexSet::new();
+        for function in functions.iter() {
+            let name = function.name.borrow().name.to_string();
+            if function.circuit.get().is_none() && entrypoints.contains(&name) {
+                let types = function
+                    .arguments
+                    .values()
+                    .map(|argument| argument.get().borrow().type_.clone())
+                    .chain(std::iter::once(function.output.clone()));
+                for type_ in types {
+                    interface.extend(circuits_of(&type_).into_iter().map(|circuit| circuit.id));
+                }
+            }
+
+            for argument in function.arguments.values() {
+                let argument = argument.get().borrow();
+                if argument.name.name.starts_with('_') || self.read_variables.contains(&argument.id) {
+                    continue;
+                }
+                let function_name = match function.circuit.get() {
+                    Some(circuit) => format!("{}::{}", circuit.name.borrow().name, name),
+                    None => name.clone(),
+                };
+                program.context.warn(
+                    format!(
+                        "parameter `{}` of function `{}` is never read",
+                        argument.name.name, function_name
+                    ),
+                    &argument.name.span,
+                );
+            }
+        }
+
+        for circuit in program.circuits.values() {
+            if interface.contains(&circuit.id) {
+                continue;
+            }
+            let read = self.read_members.get(&circuit.id);
+            for (name, member) in circuit.members.borrow().iter() {
+                if !matches!(member, CircuitMember::Variable(_))
+                    || read.map(|read| read.contains(name)).unwrap_or(false)
+                {
+                    continue;
+                }
+                let span = match circuit.member_spans.get(name) {
+                    Some(span) => span,
+                    None => continue,
+                };
+                let mut message = format!(
+                    "member `{}` of circuit `{}` is never read",
+                    name,
+                    circuit.name.borrow().name
+                );
+                if let Some(constructions) = self.constructions.get(&circuit.id) {
+                    let sites = constructions
+                        .iter()
+                        .map(|span| format!("{}:{}", span.line_start, span.col_start))
+                        .collect::<Vec<_>>();
+                    message.push_str(&format!(
+                        ", but the construction{} at {} still set{} it",
+                        if sites.len() == 1 { "" } else { "s" },
+                        sites.join(", "),
+                        if sites.len() == 1 { "s" } else { "" },
+                    ));
+                }
+                program.context.warn(message, span);
+            }
+        }
+    }
+}
+
+/// Returns the circuits the type is made of, including those of their members, without repeating any.
+fn circuits_of<'a>(type_: &Type<'a>) -> Vec<&'a Circuit<'a>> {
+    let mut circuits: Vec<&'a Circuit<'a>> = vec![];
+    let mut types = vec![type_.clone()];
+    while let Some(type_) = types.pop() {
+        match type_ {
+            Type::Circuit(circuit) if !circuits.iter().any(|known| known.id == circuit.id) => {
+                circuits.push(circuit);
+                for member in circuit.members.borrow().values() {
+                    if let CircuitMember::Variable(type_) = member {
+                        types.push(type_.clone());
+                    }
+                }
+            }
+            Type::Array(inner, _) | Type::ArrayWithoutSize(inner) => types.push(*inner),
+            Type::Tuple(inner) => types.extend(inner),
+            _ => (),
+        }
+    }
+    circuits
+}
+
+impl<'a> ExpressionVisitor<'a> for Usage {
+    fn visit_variable_ref(&mut self, input: &VariableRef<'a>) -> VisitResult {
+        self.read_variables.insert(input.variable.borrow().id);
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_access(&mut self, input: &CircuitAccessExpression<'a>) -> VisitResult {
+        if input.target.get().is_some() {
+            self.read_members
+                .entry(input.circuit.get().id)
+                .or_default()
+                .insert(input.member.name.to_string());
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_init(&mut self, input: &CircuitInitExpression<'a>) -> VisitResult {
+        if let Some(span) = &input.span {
+            self.constructions
+                .entry(input.circuit.get().id)
+                .or_default()
+                .push(span.clone());
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_binary(&mut self, input: &BinaryExpression<'a>) -> VisitResult {
+        if matches!(input.operation, BinaryOperation::Eq | BinaryOperation::Ne) {
+            self.read_whole(input.left.get().get_type());
+        }
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Usage {
+    fn visit_console(&mut self, input: &ConsoleStatement<'a>) -> VisitResult {
+        match &input.function {
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                self.read_whole(args.left.get().get_type())
+            }
+            ConsoleFunction::Log(args) | ConsoleFunction::Error(args) => {
+                for parameter in args.parameters.iter() {
+                    self.read_whole(parameter.get().get_type());
+                }
+            }
+            ConsoleFunction::Assert(_) => (),
+        }
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for Usage {}
+
+impl<'a> AsgPass<'a> for Usage {
+    fn do_pass(asg: Program<'a>) -> Result<Program<'a>> {
+        Self::do_pass_with_entrypoints(asg, &["main".to_string()])
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg_passes::{BooleanFormula, BooleanSimplificationStatistics};
+use rand::Rng;
+use rand_core::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+fn atom(index: usize) -> BooleanFormula {
+    BooleanFormula::Atom(index)
+}
+
+fn not(formula: BooleanFormula) -> BooleanFormula {
+    BooleanFormula::Not(Box::new(formula))
+}
+
+fn and(operands: Vec<BooleanFormula>) -> BooleanFormula {
+    BooleanFormula::And(operands)
+}
+
+fn or(operands: Vec<BooleanFormula>) -> BooleanFormula {
+    BooleanFormula::Or(operands)
+}
+
+fn simplify_formula(formula: BooleanFormula) -> (BooleanFormula, BooleanSimplificationStatistics) {
+    let mut statistics = BooleanSimplificationStatistics::default();
+    let simplified = formula.simplify(&|_| true, &mut statistics);
+    (simplified, statistics)
+}
+
+#[test]
+fn test_boolean_double_negation() {
+    let (simplified, statistics) = simplify_formula(not(not(atom(0))));
+    assert_eq!(simplified, atom(0));
+    assert_eq!(statistics.double_negations, 1);
+    assert_eq!(statistics.total(), 1);
+}
+
+#[test]
+fn test_boolean_constants() {
+    let (simplified, statistics) = simplify_formula(and(vec![atom(0), BooleanFormula::Constant(true)]));
+    assert_eq!(simplified, atom(0));
+    assert_eq!(statistics.constants, 1);
+
+    let (simplified, statistics) = simplify_formula(or(vec![atom(0), BooleanFormula::Constant(true)]));
+    assert_eq!(simplified, BooleanFormula::Constant(true));
+    assert_eq!(statistics.constants, 1);
+
+    // An atom that may fail is not dropped in favor of the annihilator.
+    let formula = or(vec![atom(0), BooleanFormula::Constant(true)]);
+    let mut statistics = BooleanSimplificationStatistics::default();
+    assert_eq!(formula.clone().simplify(&|_| false, &mut statistics), formula);
+    assert_eq!(statistics.total(), 0);
+}
+
+#[test]
+fn test_boolean_absorption() {
+    let (simplified, statistics) = simplify_formula(and(vec![atom(0), or(vec![atom(0), atom(1)])]));
+    assert_eq!(simplified, atom(0));
+    assert_eq!(statistics.absorptions, 1);
+
+    let (simplified, statistics) = simplify_formula(or(vec![atom(0), and(vec![atom(1), atom(0)])]));
+    assert_eq!(simplified, atom(0));
+    assert_eq!(statistics.absorptions, 1);
+
+    let (simplified, statistics) = simplify_formula(and(vec![atom(0), atom(0)]));
+    assert_eq!(simplified, atom(0));
+    assert_eq!(statistics.absorptions, 1);
+}
+
+#[test]
+fn test_boolean_factoring() {
+    let (simplified, statistics) = simplify_formula(or(vec![and(vec![atom(0), atom(1)]), and(vec![atom(0), atom(2)])]));
+    assert_eq!(simplified, and(vec![atom(0), or(vec![atom(1), atom(2)])]));
+    assert_eq!(statistics.factorings, 1);
+    assert_eq!(statistics.total(), 1);
+
+    let (simplified, statistics) = simplify_formula(and(vec![
+        or(vec![atom(0), atom(1)]),
+        atom(3),
+        or(vec![atom(2), atom(0)]),
+    ]));
+    assert_eq!(
+        simplified,
+        and(vec![or(vec![atom(0), and(vec![atom(1), atom(2)])]), atom(3)])
+    );
+    assert_eq!(statistics.factorings, 1);
+}
+
+#[test]
+fn test_boolean_de_morgan() {
+    let (simplified, statistics) = simplify_formula(and(vec![not(atom(0)), not(atom(1))]));
+    assert_eq!(simplified, not(or(vec![atom(0), atom(1)])));
+    assert_eq!(statistics.de_morgans, 1);
+
+    let (simplified, statistics) = simplify_formula(not(and(vec![not(atom(0)), atom(1)])));
+    assert_eq!(simplified, or(vec![atom(0), not(atom(1))]));
+    assert_eq!(statistics.de_morgans, 1);
+
+    // Pushing a negation inwards that does not cancel another one makes the formula larger.
+    let formula = not(and(vec![atom(0), atom(1)]));
+    let (simplified, statistics) = simplify_formula(formula.clone());
+    assert_eq!(simplified, formula);
+    assert_eq!(statistics.total(), 0);
+}
+
+#[test]
+fn test_boolean_simplification_fixed_point() {
+    // Factoring `a` out leaves `(b && c) || (b && d)`, which is only factored in the next round.
+    let formula = or(vec![
+        and(vec![atom(0), atom(1), atom(2)]),
+        and(vec![atom(0), atom(1), atom(3)]),
+    ]);
+    let (simplified, statistics) = simplify_formula(formula.clone());
+    assert_eq!(simplified, and(vec![atom(0), atom(1), or(vec![atom(2), atom(3)])]));
+    assert_eq!(statistics.factorings, 2);
+    assert!(simplified.size() < formula.size());
+
+    let (again, statistics) = simplify_formula(simplified.clone());
+    assert_eq!(again, simplified);
+    assert_eq!(statistics.total(), 0);
+}
+
+fn random_formula(rng: &mut XorShiftRng, atoms: usize, depth: usize) -> BooleanFormula {
+    if depth == 0 || rng.gen_bool(0.25) {
+        return if rng.gen_bool(0.1) {
+            BooleanFormula::Constant(rng.gen())
+        } else {
+            atom(rng.gen_range(0..atoms))
+        };
+    }
+    let operation = rng.gen_range(0..3);
+    let operands = (0..rng.gen_range(2..4))
+        .map(|_| random_formula(rng, atoms, depth - 1))
+        .collect::<Vec<_>>();
+    match operation {
+        0 => not(operands.into_iter().next().unwrap()),
+        1 => and(operands),
+        _ => or(operands),
+    }
+}
+
+#[test]
+fn test_boolean_simplification_matches_evaluation() {
+    const ATOMS: usize = 5;
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let mut total = BooleanSimplificationStatistics::default();
+
+    for _ in 0..2000 {
+        let formula = random_formula(&mut rng, ATOMS, 5);
+        let droppable: Vec<bool> = (0..ATOMS).map(|_| rng.gen_bool(0.8)).collect();
+        let mut statistics = BooleanSimplificationStatistics::default();
+        let simplified = formula.clone().simplify(&|atom| droppable[atom], &mut statistics);
+
+        assert!(
+            simplified.size() <= formula.size(),
+            "{:?} grew to {:?}",
+            formula,
+            simplified
+        );
+        assert_eq!(statistics.total() == 0, simplified.size() == formula.size());
+        for atom in formula.atoms() {
+            assert!(
+                droppable[atom] || simplified.atoms().contains(&atom),
+                "{:?} lost atom {} in {:?}",
+                formula,
+                atom,
+                simplified
+            );
+        }
+        for _ in 0..16 {
+            let assignment: Vec<bool> = (0..ATOMS).map(|_| rng.gen()).collect();
+            assert_eq!(
+                formula.evaluate(&assignment),
+                simplified.evaluate(&assignment),
+                "{:?} and {:?} differ at {:?}",
+                formula,
+                simplified,
+                assignment
+            );
+        }
+
+        total.double_negations += statistics.double_negations;
+        total.constants += statistics.constants;
+        total.absorptions += statistics.absorptions;
+        total.factorings += statistics.factorings;
+        total.de_morgans += statistics.de_morgans;
+    }
+
+    // Every rule was exercised.
+    assert!(total.double_negations > 0, "{:?}", total);
+    assert!(total.constants > 0, "{:?}", total);
+    assert!(total.absorptions > 0, "{:?}", total);
+    assert!(total.factorings > 0, "{:?}", total);
+    assert!(total.de_morgans > 0, "{:?}", total);
+}
@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg::{new_alloc_context, new_context, Asg, ConstInt, ConstValue, Expression, Statement};
+use leo_asg_passes::{ConstantFolding, DEFAULT_CALL_FUEL};
+
+/// Folds the program with `fuel`, returning the values of the variables `main` defines, if they were folded.
+fn fold_calls(program_string: &str, fuel: usize) -> Vec<Option<ConstValue<'static>>> {
+    let allocator = Box::leak(Box::new(new_alloc_context()));
+    let ast = leo_parser::parse_ast("input.leo", program_string).unwrap();
+    let asg = Asg::new(new_context(allocator), ast.as_repr()).unwrap().into_repr();
+    let asg = ConstantFolding::do_pass_with_fuel(asg, fuel).unwrap();
+
+    let main = *asg.functions.get("main").unwrap();
+    match main.body.get().unwrap() {
+        Statement::Block(block) => block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement.get() {
+                Statement::Definition(definition) => Some(match definition.value.get() {
+                    Expression::Constant(constant) => Some(constant.value.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => panic!("expected a block"),
+    }
+}
+
+const PURE_CALL_PROGRAM: &str = r#"
+const TABLE: [u8; 4] = [3, 1, 4, 1];
+
+function lookup(i: u32) -> u8 {
+    return TABLE[i];
+}
+
+function weigh(values: [u8; 4]) -> u32 {
+    let total = 0u32;
+    for i in 0..4 {
+        total += (values[i] as u32) * (lookup(i) as u32);
+    }
+    return total;
+}
+
+function main(a: u32) {
+    let x = lookup(2);
+    let y = weigh([1, 2, 3, 4]);
+    let z = lookup(a);
+}
+"#;
+
+#[test]
+fn test_pure_calls_folded() {
+    let values = fold_calls(PURE_CALL_PROGRAM, DEFAULT_CALL_FUEL);
+
+    assert_eq!(values.len(), 3);
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U8(4)))));
+    assert!(matches!(values[1], Some(ConstValue::Int(ConstInt::U32(21)))));
+    assert!(
+        values[2].is_none(),
+        "a call with a variable argument must not be folded"
+    );
+}
+
+#[test]
+fn test_pure_call_out_of_fuel() {
+    let values = fold_calls(PURE_CALL_PROGRAM, 20);
+
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U8(4)))));
+    assert!(
+        values[1].is_none(),
+        "an evaluation that runs out of fuel must be left as a call"
+    );
+}
+
+#[test]
+fn test_impure_call_not_folded() {
+    let values = fold_calls(
+        r#"
+function noisy(a: u8) -> u8 {
+    console.log("a is {}", a);
+    return a + 1;
+}
+
+function reads_input(a: bool) -> bool {
+    return a == input.registers.b;
+}
+
+function main() {
+    let x = noisy(1);
+    let y = reads_input(true);
+}
+"#,
+        DEFAULT_CALL_FUEL,
+    );
+
+    assert!(values.iter().all(Option::is_none));
+}
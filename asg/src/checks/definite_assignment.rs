@@ -0,0 +1,262 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    statement::*, ConstValue, Expression, ExpressionNode, ExpressionVisitor, Node, VariableRef, VisitResult,
+    VisitorDirector,
+};
+
+use leo_errors::Span;
+
+use indexmap::{IndexMap, IndexSet};
+use std::cell::Cell;
+
+/// Collects the variables read by an expression tree.
+#[derive(Default)]
+struct VariableReads {
+    reads: Vec<(u32, String, Span)>,
+}
+
+impl<'a> ExpressionVisitor<'a> for VariableReads {
+    fn visit_variable_ref(&mut self, input: &VariableRef<'a>) -> VisitResult {
+        let variable = input.variable.borrow();
+        self.reads.push((
+            variable.id,
+            variable.name.name.to_string(),
+            input.span.clone().unwrap_or_default(),
+        ));
+        VisitResult::VisitChildren
+    }
+}
+
+/// The set of variables which are not yet assigned on some path, and the branches which skipped them.
+#[derive(Clone, Default)]
+struct AssignmentState {
+    unassigned: IndexSet<u32>,
+    skipped_by: IndexMap<u32, Vec<Span>>,
+}
+
+impl AssignmentState {
+    fn assign(&mut self, id: u32) {
+        self.unassigned.remove(&id);
+        self.skipped_by.remove(&id);
+    }
+
+    /// Joins the states of two paths. A variable is unassigned if it is unassigned on either path.
+    fn join(mut self, other: AssignmentState, self_span: Option<&Span>, other_span: Option<&Span>) -> Self {
+        for id in other.unassigned.iter() {
+            if !self.unassigned.contains(id) {
+                self.skipped_by.entry(*id).or_default().extend(other_span.cloned());
+            }
+        }
+        for id in self.unassigned.clone().iter() {
+            if !other.unassigned.contains(id) {
+                self.skipped_by.entry(*id).or_default().extend(self_span.cloned());
+            }
+        }
+        for (id, spans) in other.skipped_by.into_iter() {
+            self.skipped_by.entry(id).or_default().extend(spans);
+        }
+        self.unassigned.extend(other.unassigned);
+        self
+    }
+}
+
+/// Checks that every variable read in a function body is definitely assigned on every path leading to the read.
+///
+/// Conditionals with a constant condition only consider the taken branch.
+/// Loop bodies are assumed to possibly execute zero times.
+pub struct DefiniteAssignment {
+    state: AssignmentState,
+    pub errors: Vec<(Span, String, String)>,
+}
+
+impl DefiniteAssignment {
+    pub fn new() -> DefiniteAssignment {
+        DefiniteAssignment {
+            state: AssignmentState::default(),
+            errors: vec![],
+        }
+    }
+
+    /// Marks a variable as declared without being assigned.
+    pub fn declare_unassigned(&mut self, id: u32) {
+        self.state.unassigned.insert(id);
+    }
+
+    fn check_expression<'a>(&mut self, input: &Cell<&'a Expression<'a>>) {
+        let mut director = VisitorDirector::new(VariableReads::default());
+        director.visit_expression(input).ok();
+
+        for (id, name, span) in director.visitor().reads {
+            if self.state.unassigned.contains(&id) {
+                let notes = self
+                    .state
+                    .skipped_by
+                    .get(&id)
+                    .map(|spans| {
+                        spans
+                            .iter()
+                            .map(|span| format!("\n  not assigned on the path through {}", span))
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+                self.errors.push((span, name, notes));
+            }
+        }
+    }
+
+    /// Checks a block, returning `true` if every path through it returns.
+    pub fn check_block<'a>(&mut self, input: &BlockStatement<'a>) -> bool {
+        for statement in input.statements.iter() {
+            if self.check_statement(statement.get()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Checks a statement, returning `true` if every path through it returns.
+    fn check_statement<'a>(&mut self, input: &'a Statement<'a>) -> bool {
+        match input {
+            Statement::Return(statement) => {
+                self.check_expression(&statement.expression);
+                true
+            }
+            Statement::Definition(statement) => {
+                self.check_expression(&statement.value);
+                for variable in statement.variables.iter() {
+                    let id = variable.borrow().id;
+                    if statement.initialized {
+                        self.state.assign(id);
+                    } else {
+                        self.declare_unassigned(id);
+                    }
+                }
+                false
+            }
+            Statement::Assign(statement) => {
+                for access in statement.target_accesses.iter() {
+                    match access {
+                        AssignAccess::ArrayRange(left, right) => {
+                            for index in [left.get(), right.get()].iter().flatten() {
+                                self.check_expression(&Cell::new(*index));
+                            }
+                        }
+                        AssignAccess::ArrayIndex(index) => self.check_expression(index),
+                        _ => (),
+                    }
+                }
+                self.check_expression(&statement.value);
+
+                let variable = statement.target_variable.get().borrow();
+                if statement.target_accesses.is_empty() {
+                    self.state.assign(variable.id);
+                } else if self.state.unassigned.contains(&variable.id) {
+                    // Assigning into part of a variable reads the rest of it.
+                    self.errors.push((
+                        statement.span.clone().unwrap_or_default(),
+                        variable.name.name.to_string(),
+                        String::new(),
+                    ));
+                }
+                false
+            }
+            Statement::Conditional(statement) => {
+                self.check_expression(&statement.condition);
+
+                match statement.condition.get().const_value() {
+                    Some(ConstValue::Boolean(true)) => self.check_statement(statement.result.get()),
+                    Some(ConstValue::Boolean(false)) => statement
+                        .next
+                        .get()
+                        .map(|next| self.check_statement(next))
+                        .unwrap_or(false),
+                    _ => {
+                        let before = self.state.clone();
+                        let result_returns = self.check_statement(statement.result.get());
+                        let after_result = std::mem::replace(&mut self.state, before);
+                        let next_returns = statement
+                            .next
+                            .get()
+                            .map(|next| self.check_statement(next))
+                            .unwrap_or(false);
+                        let after_next = std::mem::take(&mut self.state);
+
+                        let next_span = statement.next.get().and_then(|next| next.span()).or(statement.span());
+                        self.state = match (result_returns, next_returns) {
+                            (true, true) => return true,
+                            (true, false) => after_next,
+                            (false, true) => after_result,
+                            (false, false) => after_result.join(after_next, statement.result.get().span(), next_span),
+                        };
+                        false
+                    }
+                }
+            }
+            Statement::Iteration(statement) => {
+                self.check_expression(&statement.start);
+                self.check_expression(&statement.stop);
+                if let Some(step) = statement.step.get() {
+                    self.check_expression(&Cell::new(step));
+                }
+
+                // The body may execute zero times, so its assignments do not carry over.
+                let before = self.state.clone();
+                self.check_statement(statement.body.get());
+                let mut after = std::mem::replace(&mut self.state, before);
+                for id in self.state.unassigned.iter() {
+                    if !after.unassigned.contains(id) {
+                        after.skipped_by.insert(*id, statement.span.iter().cloned().collect());
+                    }
+                }
+                for (id, spans) in after.skipped_by {
+                    if self.state.unassigned.contains(&id) {
+                        self.state.skipped_by.entry(id).or_default().extend(spans);
+                    }
+                }
+                false
+            }
+            Statement::Console(statement) => {
+                match &statement.function {
+                    ConsoleFunction::Assert(expression) => self.check_expression(expression),
+                    ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                        self.check_expression(&args.left);
+                        self.check_expression(&args.right);
+                    }
+                    ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => {
+                        for parameter in args.parameters.iter() {
+                            self.check_expression(parameter);
+                        }
+                    }
+                }
+                false
+            }
+            Statement::Expression(statement) => {
+                self.check_expression(&statement.expression);
+                false
+            }
+            Statement::Block(block) => self.check_block(block),
+            Statement::Empty(_) => false,
+        }
+    }
+}
+
+impl Default for DefiniteAssignment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -18,3 +18,6 @@
 
 mod return_path;
 pub use return_path::*;
+
+mod definite_assignment;
+pub use definite_assignment::*;
@@ -14,16 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Circuit, Identifier, IntegerType, Type};
+use crate::{
+    group_generator, group_identity, is_on_curve, reduce_field, Circuit, CircuitMember, GroupPoint, Identifier,
+    IntegerType, Type,
+};
 use leo_errors::{AsgError, Result, Span};
 
 use indexmap::IndexMap;
 use num_bigint::BigInt;
-use std::{convert::TryInto, fmt};
+use std::{
+    convert::TryInto,
+    fmt,
+    hash::{Hash, Hasher},
+};
 use tendril::StrTendril;
 
 /// Constant integer values in a program.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ConstInt {
     I8(i8),
     I16(i16),
@@ -38,7 +45,7 @@ pub enum ConstInt {
 }
 
 /// Specifies how to calculate a group coordinate in a program.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum GroupCoordinate {
     /// Explicit field element number string.
     Number(StrTendril),
@@ -88,12 +95,53 @@ impl Into<leo_ast::GroupCoordinate> for &GroupCoordinate {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum GroupValue {
     Single(StrTendril),
     Tuple(GroupCoordinate, GroupCoordinate),
 }
 
+impl GroupValue {
+    ///
+    /// Returns the point of the curve this value stands for, if it is known at compile time.
+    ///
+    /// Only the identity, the generator and points given by both of their coordinates are known;
+    /// the points of other literals are left to the circuit.
+    ///
+    pub fn to_point(&self) -> Option<GroupPoint> {
+        match self {
+            GroupValue::Single(value) => match value.parse::<BigInt>().ok()? {
+                value if value == BigInt::from(0) => Some(group_identity()),
+                value if value == BigInt::from(1) => Some(group_generator()),
+                _ => None,
+            },
+            GroupValue::Tuple(GroupCoordinate::Number(x), GroupCoordinate::Number(y)) => {
+                let point = (
+                    reduce_field(&x.parse::<BigInt>().ok()?),
+                    reduce_field(&y.parse::<BigInt>().ok()?),
+                );
+                Some(point).filter(is_on_curve)
+            }
+            GroupValue::Tuple(_, _) => None,
+        }
+    }
+
+    /// Returns the value of a point of the curve, as a literal the circuit can allocate.
+    pub fn from_point(point: GroupPoint) -> Self {
+        if point == group_identity() {
+            GroupValue::Single("0".into())
+        } else if point == group_generator() {
+            GroupValue::Single("1".into())
+        } else {
+            let (x, y) = point;
+            GroupValue::Tuple(
+                GroupCoordinate::Number(x.to_string().into()),
+                GroupCoordinate::Number(y.to_string().into()),
+            )
+        }
+    }
+}
+
 impl From<leo_ast::GroupValue> for GroupValue {
     fn from(other: leo_ast::GroupValue) -> Self {
         use leo_ast::GroupValue::*;
@@ -104,7 +152,7 @@ impl From<leo_ast::GroupValue> for GroupValue {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum CharValue {
     Scalar(char),
     NonScalar(u32),
@@ -151,6 +199,28 @@ pub enum ConstValue<'a> {
     Circuit(&'a Circuit<'a>, IndexMap<String, (Identifier, ConstValue<'a>)>),
 }
 
+impl<'a> Eq for ConstValue<'a> {}
+
+impl<'a> Hash for ConstValue<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ConstValue::Int(value) => value.hash(state),
+            ConstValue::Group(value) => value.hash(state),
+            ConstValue::Field(value) => value.hash(state),
+            ConstValue::Address(value) => value.hash(state),
+            ConstValue::Boolean(value) => value.hash(state),
+            ConstValue::Char(value) => value.hash(state),
+            ConstValue::Tuple(values) | ConstValue::Array(values) => values.hash(state),
+            // Members compare equal in any order, so only their number is hashed.
+            ConstValue::Circuit(circuit, members) => {
+                circuit.id.hash(state);
+                members.len().hash(state);
+            }
+        }
+    }
+}
+
 macro_rules! const_int_op {
     ($name: ident, $retType: ty, $x: ident, $transform: expr) => {
         pub fn $name(&self) -> $retType {
@@ -347,6 +417,33 @@ impl<'a> ConstValue<'a> {
         })
     }
 
+    ///
+    /// Returns the value of a variable of type `type_` that is declared without an initializer,
+    /// or `None` if there is no such value, as for addresses.
+    ///
+    pub fn default_of(type_: &Type<'a>) -> Option<ConstValue<'a>> {
+        Some(match type_ {
+            Type::Address | Type::ArrayWithoutSize(_) => return None,
+            Type::Boolean => ConstValue::Boolean(false),
+            Type::Char => ConstValue::Char(CharValue::Scalar('\0')),
+            Type::Field => ConstValue::Field(BigInt::from(0)),
+            Type::Group => ConstValue::Group(GroupValue::Single("0".into())),
+            Type::Integer(int_type) => ConstValue::Int(ConstInt::U8(0).cast_to(int_type)),
+            Type::Array(element, length) => ConstValue::Array(vec![Self::default_of(element)?; *length]),
+            Type::Tuple(types) => ConstValue::Tuple(types.iter().map(Self::default_of).collect::<Option<Vec<_>>>()?),
+            Type::Circuit(circuit) => {
+                let mut members = IndexMap::new();
+                for (name, member) in circuit.members.borrow().iter() {
+                    if let CircuitMember::Variable(type_) = member {
+                        let identifier = Identifier::new(name.as_str().into());
+                        members.insert(name.clone(), (identifier, Self::default_of(type_)?));
+                    }
+                }
+                ConstValue::Circuit(circuit, members)
+            }
+        })
+    }
+
     pub fn int(&self) -> Option<&ConstInt> {
         match self {
             ConstValue::Int(x) => Some(x),
@@ -361,3 +458,51 @@ impl<'a> ConstValue<'a> {
         }
     }
 }
+
+impl<'a> fmt::Display for ConstValue<'a> {
+    ///
+    /// Writes the value as the literal, array, tuple or circuit expression that produces it.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstValue::Int(int) => write!(f, "{}{}", int.raw_value(), int.get_int_type()),
+            ConstValue::Group(GroupValue::Single(value)) => write!(f, "{}group", value),
+            ConstValue::Group(GroupValue::Tuple(x, y)) => write!(f, "({}, {})group", x, y),
+            ConstValue::Field(value) => write!(f, "{}field", value),
+            ConstValue::Address(address) => write!(f, "{}", address),
+            ConstValue::Boolean(value) => write!(f, "{}", value),
+            ConstValue::Char(CharValue::Scalar(character)) => write!(f, "{:?}", character),
+            ConstValue::Char(CharValue::NonScalar(character)) => write!(f, "'\\u{{{:x}}}'", character),
+            ConstValue::Tuple(values) => {
+                write!(f, "(")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, ")")
+            }
+            ConstValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            ConstValue::Circuit(circuit, members) => {
+                write!(f, "{} {{ ", circuit.name.borrow().name)?;
+                for (i, (name, (_, value))) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Parameters of the prime field that Leo programs compute over, and of the curve whose points are group elements.
+//!
+//! Every place that depends on the field or the curve should read it from here, so supporting another curve
+//! only requires changing this module.
+
+use num_bigint::BigInt;
+
+/// The modulus of the scalar field of BLS12-377, which is the base field of the Edwards BLS12 curve.
+pub const FIELD_MODULUS: &str = "8444461749428370424248824938781546531375899335154063827935233455917409239041";
+
+/// The number of bits in the canonical representation of a field element.
+pub const FIELD_BIT_WIDTH: usize = 253;
+
+/// The coefficient `a` of the twisted Edwards curve `a * x^2 + y^2 = 1 + d * x^2 * y^2` of group elements.
+pub const EDWARDS_COEFF_A: i64 = -1;
+
+/// The coefficient `d` of the twisted Edwards curve of group elements.
+pub const EDWARDS_COEFF_D: i64 = 3021;
+
+/// The x-coordinate of the generator of the group, which the literal `1group` stands for.
+pub const GROUP_GENERATOR_X: &str = "7810607721416582242904415504650443951498042435501746664987470571546413371306";
+
+/// The y-coordinate of the generator of the group.
+pub const GROUP_GENERATOR_Y: &str = "1867362672570137759132108893390349941423731440336755218616442213142473202417";
+
+/// A point of the curve in affine coordinates, which are canonical field elements.
+pub type GroupPoint = (BigInt, BigInt);
+
+/// Returns the field modulus.
+pub fn field_modulus() -> BigInt {
+    FIELD_MODULUS.parse().expect("invalid field modulus")
+}
+
+/// Returns the canonical representative of `value` in `[0, modulus)`.
+pub fn reduce_field(value: &BigInt) -> BigInt {
+    let modulus = field_modulus();
+    let reduced = value % &modulus;
+    if reduced < BigInt::from(0) {
+        reduced + modulus
+    } else {
+        reduced
+    }
+}
+
+/// Returns the multiplicative inverse of a canonical field element, or `None` for zero.
+pub fn invert_field(value: &BigInt) -> Option<BigInt> {
+    if *value == BigInt::from(0) {
+        return None;
+    }
+    let modulus = field_modulus();
+    // Fermat's little theorem: value^(p - 2) = value^-1 (mod p).
+    Some(value.modpow(&(&modulus - BigInt::from(2)), &modulus))
+}
+
+/// Returns `base` raised to the power `exponent`, or `None` for a negative power of zero.
+pub fn pow_field(base: &BigInt, exponent: &BigInt) -> Option<BigInt> {
+    let modulus = field_modulus();
+    if *exponent < BigInt::from(0) {
+        return Some(invert_field(base)?.modpow(&-exponent, &modulus));
+    }
+    Some(base.modpow(exponent, &modulus))
+}
+
+/// Returns the identity of the group, which the literal `0group` stands for.
+pub fn group_identity() -> GroupPoint {
+    (BigInt::from(0), BigInt::from(1))
+}
+
+/// Returns the generator of the group, which the literal `1group` stands for.
+pub fn group_generator() -> GroupPoint {
+    (
+        GROUP_GENERATOR_X.parse().expect("invalid generator"),
+        GROUP_GENERATOR_Y.parse().expect("invalid generator"),
+    )
+}
+
+/// Returns `true` if the canonical coordinates `(x, y)` are a point of the curve.
+pub fn is_on_curve((x, y): &GroupPoint) -> bool {
+    let (xx, yy) = (x * x, y * y);
+    let left = BigInt::from(EDWARDS_COEFF_A) * &xx + &yy;
+    let right = BigInt::from(1) + BigInt::from(EDWARDS_COEFF_D) * xx * yy;
+    reduce_field(&(left - right)) == BigInt::from(0)
+}
+
+/// Returns the inverse of a point of the curve.
+pub fn negate_group((x, y): &GroupPoint) -> GroupPoint {
+    (reduce_field(&-x), y.clone())
+}
+
+/// Returns the sum of two points of the curve.
+pub fn add_group((x1, y1): &GroupPoint, (x2, y2): &GroupPoint) -> Option<GroupPoint> {
+    let product = reduce_field(&(BigInt::from(EDWARDS_COEFF_D) * x1 * x2 * y1 * y2));
+    // The curve is complete, so neither denominator is zero for points on it.
+    let x_denominator = invert_field(&reduce_field(&(BigInt::from(1) + &product)))?;
+    let y_denominator = invert_field(&reduce_field(&(BigInt::from(1) - &product)))?;
+    let x = reduce_field(&((x1 * y2 + y1 * x2) * x_denominator));
+    let y = reduce_field(&((y1 * y2 - BigInt::from(EDWARDS_COEFF_A) * x1 * x2) * y_denominator));
+    Some((x, y))
+}
@@ -14,15 +14,89 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{cell::Cell, unimplemented};
+use std::{
+    cell::{Cell, RefCell},
+    collections::hash_map::DefaultHasher,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+    unimplemented,
+};
 
+use indexmap::IndexMap;
 use typed_arena::Arena;
 
-use crate::{Alias, ArenaNode, Circuit, Expression, Function, Scope, Statement, Variable};
+use crate::{Alias, ArenaNode, Circuit, Expression, Function, ProgressReporter, Scope, Statement, Variable};
+use leo_ast::Edition;
+use leo_errors::Span;
+
+/// A problem in the program that does not stop it from compiling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsgWarning {
+    pub message: String,
+    pub span: Span,
+}
+
+impl fmt::Display for AsgWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.span.path, self.span)
+    }
+}
+
+impl AsgWarning {
+    /// Identifies the warning by its message and where it is, including the source text it points at,
+    /// so that the same location in edited source is told apart.
+    fn key(&self) -> WarningKey {
+        let hash = |value: &str| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        WarningKey {
+            path: self.span.path.to_string(),
+            lines: (self.span.line_start, self.span.line_stop),
+            columns: (self.span.col_start, self.span.col_stop),
+            message: hash(&self.message),
+            content: hash(&self.span.content),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct WarningKey {
+    path: String,
+    lines: (usize, usize),
+    columns: (usize, usize),
+    message: u64,
+    content: u64,
+}
+
+/// A distinct warning reported while deduplicating, with how often and by which compiles it was reported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WarningTally {
+    pub warning: AsgWarning,
+    /// The number of times the warning was reported, including the first.
+    pub reports: usize,
+    /// The named compiles that reported the warning, in the order they first did.
+    pub sources: Vec<String>,
+}
 
 pub struct AsgContextInner<'a> {
     pub arena: &'a Arena<ArenaNode<'a>>,
     pub next_id: Cell<u32>,
+    /// Reject field literals outside of the field instead of reducing them.
+    pub strict_field_literals: Cell<bool>,
+    /// Casts the narrower operand of an operation on integers of one signedness but different widths.
+    pub integer_widening: Cell<bool>,
+    /// The edition the features of the program are checked against.
+    pub edition: Cell<Edition>,
+    pub warnings: RefCell<Vec<AsgWarning>>,
+    /// Drop warnings that were already taken once, for compiles that share source.
+    pub deduplicate_warnings: Cell<bool>,
+    warning_tally: RefCell<IndexMap<WarningKey, WarningTally>>,
+    /// Told about every function whose body is checked, numbered in order.
+    pub progress: RefCell<Option<Arc<dyn ProgressReporter>>>,
+    pub progress_items: Cell<usize>,
 }
 
 impl<'a> AsgContextInner<'a> {
@@ -30,6 +104,14 @@ impl<'a> AsgContextInner<'a> {
         match arena.alloc(ArenaNode::Inner(AsgContextInner {
             arena,
             next_id: Cell::new(0),
+            strict_field_literals: Cell::new(false),
+            integer_widening: Cell::new(false),
+            edition: Cell::new(Edition::LATEST),
+            warnings: RefCell::new(vec![]),
+            deduplicate_warnings: Cell::new(false),
+            warning_tally: RefCell::new(IndexMap::new()),
+            progress: RefCell::new(None),
+            progress_items: Cell::new(0),
         })) {
             ArenaNode::Inner(x) => x,
             _ => unimplemented!(),
@@ -42,6 +124,96 @@ impl<'a> AsgContextInner<'a> {
         next_id
     }
 
+    pub fn set_strict_field_literals(&self, strict: bool) {
+        self.strict_field_literals.set(strict);
+    }
+
+    pub fn set_integer_widening(&self, widening: bool) {
+        self.integer_widening.set(widening);
+    }
+
+    pub fn set_edition(&self, edition: Edition) {
+        self.edition.set(edition);
+    }
+
+    /// Reports the functions checked from now on to the given reporter, numbering them from zero.
+    pub fn set_progress(&self, progress: Option<Arc<dyn ProgressReporter>>) {
+        self.progress.replace(progress);
+        self.progress_items.set(0);
+    }
+
+    /// Reports that the body of the named function is being checked.
+    pub fn report_item(&self, name: &str) {
+        if let Some(progress) = &*self.progress.borrow() {
+            let index = self.progress_items.get();
+            self.progress_items.set(index + 1);
+            progress.on_item(index, name);
+        }
+    }
+
+    pub fn warn(&self, message: String, span: &Span) {
+        self.warnings.borrow_mut().push(AsgWarning {
+            message,
+            span: span.clone(),
+        });
+    }
+
+    ///
+    /// Makes every warning be taken only once, however many of the compiles sharing this context report it.
+    ///
+    /// Off by default, since compiles of different versions of the same file can report warnings that look alike.
+    /// Warnings are only told apart by their message, location and the source text at that location.
+    ///
+    pub fn set_deduplicate_warnings(&self, deduplicate: bool) {
+        self.deduplicate_warnings.set(deduplicate);
+    }
+
+    /// Returns the warnings reported so far, in order, and clears them.
+    pub fn take_warnings(&self) -> Vec<AsgWarning> {
+        self.take_warnings_of(None)
+    }
+
+    ///
+    /// Returns the warnings reported so far, in order, and clears them, crediting them to the named compile.
+    ///
+    /// When deduplicating, warnings that were taken before are left out, and only counted in the tally.
+    ///
+    pub fn take_warnings_from(&self, source: &str) -> Vec<AsgWarning> {
+        self.take_warnings_of(Some(source))
+    }
+
+    fn take_warnings_of(&self, source: Option<&str>) -> Vec<AsgWarning> {
+        let warnings = std::mem::take(&mut *self.warnings.borrow_mut());
+        if !self.deduplicate_warnings.get() {
+            return warnings;
+        }
+
+        let mut tally = self.warning_tally.borrow_mut();
+        let mut new = vec![];
+        for warning in warnings {
+            let entry = tally.entry(warning.key()).or_insert_with(|| {
+                new.push(warning.clone());
+                WarningTally {
+                    warning,
+                    reports: 0,
+                    sources: vec![],
+                }
+            });
+            entry.reports += 1;
+            if let Some(source) = source {
+                if !entry.sources.iter().any(|known| known == source) {
+                    entry.sources.push(source.to_string());
+                }
+            }
+        }
+        new
+    }
+
+    /// Returns every distinct warning taken while deduplicating, in the order they were first reported.
+    pub fn warning_tally(&self) -> Vec<WarningTally> {
+        self.warning_tally.borrow().values().cloned().collect()
+    }
+
     #[allow(clippy::mut_from_ref)]
     pub fn alloc_expression(&'a self, expr: Expression<'a>) -> &'a Expression<'a> {
         match self.arena.alloc(ArenaNode::Expression(expr)) {
@@ -0,0 +1,517 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Graphviz rendering of the dataflow of a function, for debugging the compiler.
+
+use crate::{
+    AssignAccess, ConsoleFunction, Expression, ExpressionNode, Function, Node, Statement, Variable, VariableDeclaration,
+};
+use leo_ast::ArtifactFormat;
+use leo_errors::Span;
+
+use indexmap::IndexMap;
+use std::fmt::Write;
+
+/// The role of a node in a [`Dataflow`] graph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DataflowNodeKind {
+    /// A function argument.
+    Input,
+    /// The value returned by the function.
+    Output,
+    /// A variable defined in the function body.
+    Variable,
+    /// An expression computing a value from its operands.
+    Operation,
+    /// A constant consumed by a statement rather than by an operation.
+    Constant,
+    /// A statement consuming values without producing one, such as a branch or an assertion.
+    Sink,
+}
+
+/// A value or operation in a [`Dataflow`] graph.
+#[derive(Clone, Debug)]
+pub struct DataflowNode {
+    pub id: String,
+    pub label: String,
+    pub kind: DataflowNodeKind,
+    /// The source code the node was built from.
+    pub span: Option<Span>,
+    /// The index of the statement the node belongs to, if it is not a variable.
+    pub statement: Option<usize>,
+}
+
+/// An operand flowing into a [`DataflowNode`].
+#[derive(Clone, Debug)]
+pub struct DataflowEdge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+}
+
+/// The format of dataflow graphs written as artifacts, bumped whenever their DOT changes in a way tools rely on.
+pub const DATAFLOW_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "dataflow",
+    version: 1,
+    json: false,
+};
+
+/// The dataflow of a function: variables and operations as nodes, operands as edges.
+///
+/// Constant operands are folded into the label of the operation using them.
+#[derive(Clone, Debug)]
+pub struct Dataflow {
+    pub name: String,
+    pub nodes: Vec<DataflowNode>,
+    pub edges: Vec<DataflowEdge>,
+    /// The span of every statement that owns nodes, by statement index.
+    pub statements: Vec<Option<Span>>,
+}
+
+/// An operand of an operation: either a node, or a constant rendered inline.
+enum Operand {
+    Node(String),
+    Inline(String),
+}
+
+/// Walks a function body, adding nodes and edges in the order their values are computed.
+struct DataflowBuilder {
+    graph: Dataflow,
+    /// DOT identifiers of the variables seen so far, by variable id.
+    variables: IndexMap<u32, String>,
+    operations: usize,
+    statement: Option<usize>,
+}
+
+impl Dataflow {
+    /// Builds the dataflow graph of the given function.
+    pub fn new(function: &Function) -> Self {
+        let mut builder = DataflowBuilder {
+            graph: Dataflow {
+                name: function.name.borrow().name.to_string(),
+                nodes: Vec::new(),
+                edges: Vec::new(),
+                statements: Vec::new(),
+            },
+            variables: IndexMap::new(),
+            operations: 0,
+            statement: None,
+        };
+
+        for argument in function.arguments.values() {
+            builder.variable(argument.get());
+        }
+        builder.add_node(
+            "output".to_string(),
+            format!("return: {}", function.output),
+            DataflowNodeKind::Output,
+            function.span.clone(),
+        );
+        if let Some(body) = function.body.get() {
+            builder.statement(body);
+        }
+
+        builder.graph
+    }
+
+    /// Renders the graph in the Graphviz DOT language.
+    ///
+    /// With `cluster_statements`, the nodes of each statement are grouped in a box labeled with its line,
+    /// which keeps large functions readable.
+    pub fn to_dot(&self, cluster_statements: bool) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph {} {{", quote(&self.name)).unwrap();
+        writeln!(dot, "    node [fontname=\"monospace\"];").unwrap();
+
+        if cluster_statements {
+            for node in self.nodes.iter().filter(|node| node.statement.is_none()) {
+                write_node(&mut dot, node, "    ");
+            }
+            for (index, span) in self.statements.iter().enumerate() {
+                writeln!(dot, "    subgraph cluster_{} {{", index).unwrap();
+                if let Some(span) = span {
+                    writeln!(dot, "        label={};", quote(&format!("line {}", span.line_start))).unwrap();
+                }
+                for node in self.nodes.iter().filter(|node| node.statement == Some(index)) {
+                    write_node(&mut dot, node, "        ");
+                }
+                writeln!(dot, "    }}").unwrap();
+            }
+        } else {
+            for node in self.nodes.iter() {
+                write_node(&mut dot, node, "    ");
+            }
+        }
+
+        for edge in self.edges.iter() {
+            match &edge.label {
+                Some(label) => writeln!(dot, "    {} -> {} [label={}];", edge.from, edge.to, quote(label)).unwrap(),
+                None => writeln!(dot, "    {} -> {};", edge.from, edge.to).unwrap(),
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<'a> Function<'a> {
+    /// Renders the dataflow of the function in the Graphviz DOT language. See [`Dataflow`].
+    pub fn to_dot(&self) -> String {
+        Dataflow::new(self).to_dot(false)
+    }
+}
+
+impl DataflowBuilder {
+    fn add_node(&mut self, id: String, label: String, kind: DataflowNodeKind, span: Option<Span>) -> String {
+        let statement = match kind {
+            DataflowNodeKind::Input | DataflowNodeKind::Output | DataflowNodeKind::Variable => None,
+            _ => self.statement,
+        };
+        self.graph.nodes.push(DataflowNode {
+            id: id.clone(),
+            label,
+            kind,
+            span,
+            statement,
+        });
+        id
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, label: Option<String>) {
+        self.graph.edges.push(DataflowEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label,
+        });
+    }
+
+    /// Adds a node for an operation or sink, with an edge from every node operand and the constants in its label.
+    fn operation(
+        &mut self,
+        label: String,
+        kind: DataflowNodeKind,
+        span: Option<&Span>,
+        operands: Vec<(String, Operand)>,
+    ) -> String {
+        let id = format!("e{}", self.operations);
+        self.operations += 1;
+
+        let mut full_label = label;
+        for (position, operand) in operands.iter() {
+            if let Operand::Inline(value) = operand {
+                write!(full_label, "\n#{} = {}", position, value).unwrap();
+            }
+        }
+        let id = self.add_node(id, full_label, kind, span.cloned());
+        for (position, operand) in operands {
+            if let Operand::Node(from) = operand {
+                self.add_edge(&from, &id, Some(position));
+            }
+        }
+        id
+    }
+
+    /// Returns the node of a variable, adding it the first time the variable is seen.
+    fn variable(&mut self, variable: &Variable) -> String {
+        let variable = variable.borrow();
+        if let Some(id) = self.variables.get(&variable.id) {
+            return id.clone();
+        }
+
+        let id = format!("v{}", self.variables.len());
+        self.variables.insert(variable.id, id.clone());
+        let kind = match variable.declaration {
+            VariableDeclaration::Parameter | VariableDeclaration::Input => DataflowNodeKind::Input,
+            _ => DataflowNodeKind::Variable,
+        };
+        let label = format!("{}: {}", variable.name.name, variable.type_);
+        self.add_node(id, label, kind, Some(variable.name.span.clone()))
+    }
+
+    /// Returns a node for the operand, adding a constant node if it is inline.
+    fn materialize(&mut self, operand: Operand, span: Option<&Span>) -> String {
+        match operand {
+            Operand::Node(id) => id,
+            Operand::Inline(value) => {
+                let id = format!("e{}", self.operations);
+                self.operations += 1;
+                self.add_node(id, value, DataflowNodeKind::Constant, span.cloned())
+            }
+        }
+    }
+
+    fn expression<'b>(&mut self, expression: &'b Expression<'b>) -> Operand {
+        if let Expression::VariableRef(reference) = expression {
+            return Operand::Node(self.variable(reference.variable));
+        }
+        if let Expression::Constant(_) = expression {
+            let constant: leo_ast::Expression = expression.into();
+            return Operand::Inline(constant.to_string());
+        }
+
+        let mut operands = Vec::new();
+        let mut operand = |builder: &mut Self, label: String, operand: &'b Expression<'b>| {
+            let operand = builder.expression(operand);
+            operands.push((label, operand));
+        };
+        let label = match expression {
+            Expression::VariableRef(_) | Expression::Constant(_) => unreachable!(),
+            Expression::Binary(binary) => {
+                operand(self, "0".to_string(), binary.left.get());
+                operand(self, "1".to_string(), binary.right.get());
+                binary.operation.as_ref().to_string()
+            }
+            Expression::Unary(unary) => {
+                operand(self, "0".to_string(), unary.inner.get());
+                unary.operation.as_ref().to_string()
+            }
+            Expression::Ternary(ternary) => {
+                operand(self, "0".to_string(), ternary.condition.get());
+                operand(self, "1".to_string(), ternary.if_true.get());
+                operand(self, "2".to_string(), ternary.if_false.get());
+                "?:".to_string()
+            }
+            Expression::Cast(cast) => {
+                operand(self, "0".to_string(), cast.inner.get());
+                format!("as {}", cast.target_type)
+            }
+            Expression::LengthOf(length_of) => {
+                operand(self, "0".to_string(), length_of.inner.get());
+                "len".to_string()
+            }
+            Expression::Bits(bits) => {
+                operand(self, "0".to_string(), bits.inner.get());
+                bits.direction.name().to_string()
+            }
+            Expression::ArrayInline(array_inline) => {
+                for (position, (element, spread)) in array_inline.elements.iter().enumerate() {
+                    let label = if *spread {
+                        format!("...{}", position)
+                    } else {
+                        position.to_string()
+                    };
+                    operand(self, label, element.get());
+                }
+                "[..]".to_string()
+            }
+            Expression::ArrayInit(array_init) => {
+                operand(self, "0".to_string(), array_init.element.get());
+                format!("[_; {}]", array_init.len)
+            }
+            Expression::ArrayAccess(array_access) => {
+                operand(self, "0".to_string(), array_access.array.get());
+                operand(self, "1".to_string(), array_access.index.get());
+                "[]".to_string()
+            }
+            Expression::ArrayRangeAccess(array_range_access) => {
+                operand(self, "0".to_string(), array_range_access.array.get());
+                if let Some(left) = array_range_access.left.get() {
+                    operand(self, "1".to_string(), left);
+                }
+                if let Some(right) = array_range_access.right.get() {
+                    operand(self, "2".to_string(), right);
+                }
+                "[..]".to_string()
+            }
+            Expression::TupleInit(tuple_init) => {
+                for (position, element) in tuple_init.elements.iter().enumerate() {
+                    operand(self, position.to_string(), element.get());
+                }
+                "(..)".to_string()
+            }
+            Expression::TupleAccess(tuple_access) => {
+                operand(self, "0".to_string(), tuple_access.tuple_ref.get());
+                format!(".{}", tuple_access.index)
+            }
+            Expression::CircuitInit(circuit_init) => {
+                for (name, value) in circuit_init.values.iter() {
+                    operand(self, name.name.to_string(), value.get());
+                }
+                format!("{} {{..}}", circuit_init.circuit.get().name.borrow().name)
+            }
+            Expression::CircuitAccess(circuit_access) => match circuit_access.target.get() {
+                Some(target) => {
+                    operand(self, "0".to_string(), target);
+                    format!(".{}", circuit_access.member.name)
+                }
+                None => format!(
+                    "{}::{}",
+                    circuit_access.circuit.get().name.borrow().name,
+                    circuit_access.member.name
+                ),
+            },
+            Expression::Call(call) => {
+                if let Some(target) = call.target.get() {
+                    operand(self, "self".to_string(), target);
+                }
+                for (position, argument) in call.arguments.iter().enumerate() {
+                    operand(self, position.to_string(), argument.get());
+                }
+                format!("call {}", call.function.get().name.borrow().name)
+            }
+        };
+
+        let label = match expression.get_type() {
+            Some(type_) => format!("{}\n: {}", label, type_),
+            None => label,
+        };
+        let span = expression.span().cloned();
+        Operand::Node(self.operation(label, DataflowNodeKind::Operation, span.as_ref(), operands))
+    }
+
+    /// Starts a new statement cluster for the nodes added until the next one.
+    fn begin_statement(&mut self, statement: &Statement) {
+        self.statement = Some(self.graph.statements.len());
+        self.graph.statements.push(statement.span().cloned());
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => {
+                for statement in block.statements.iter() {
+                    self.statement(statement.get());
+                }
+                return;
+            }
+            Statement::Empty(_) => return,
+            _ => self.begin_statement(statement),
+        }
+
+        let span = statement.span();
+        match statement {
+            Statement::Block(_) | Statement::Empty(_) => unreachable!(),
+            Statement::Return(return_) => {
+                let value = self.expression(return_.expression.get());
+                let value = self.materialize(value, span);
+                self.add_edge(&value, "output", None);
+            }
+            Statement::Definition(definition) => {
+                let value = self.expression(definition.value.get());
+                let value = self.materialize(value, span);
+                let destructured = definition.variables.len() > 1;
+                for (position, variable) in definition.variables.iter().enumerate() {
+                    let variable = self.variable(variable);
+                    let label = if destructured {
+                        format!("={}", position)
+                    } else {
+                        "=".to_string()
+                    };
+                    self.add_edge(&value, &variable, Some(label));
+                }
+            }
+            Statement::Assign(assign) => {
+                let mut operands = Vec::new();
+                let mut path = String::new();
+                for access in assign.target_accesses.iter() {
+                    match access {
+                        AssignAccess::ArrayIndex(index) => {
+                            path.push_str("[]");
+                            let index = self.expression(index.get());
+                            operands.push((format!("index {}", operands.len()), index));
+                        }
+                        AssignAccess::ArrayRange(left, right) => {
+                            path.push_str("[..]");
+                            for bound in [left.get(), right.get()].iter().flatten() {
+                                let bound = self.expression(bound);
+                                operands.push((format!("index {}", operands.len()), bound));
+                            }
+                        }
+                        AssignAccess::Tuple(index) => write!(path, ".{}", index).unwrap(),
+                        AssignAccess::Member(member) => write!(path, ".{}", member.name).unwrap(),
+                    }
+                }
+                let value = self.expression(assign.value.get());
+                let value = self.materialize(value, span);
+                let variable = self.variable(assign.target_variable.get());
+                for (label, operand) in operands {
+                    let operand = self.materialize(operand, span);
+                    self.add_edge(&operand, &variable, Some(label));
+                }
+                self.add_edge(
+                    &value,
+                    &variable,
+                    Some(format!("{}{}", path, assign.operation.as_ref())),
+                );
+            }
+            Statement::Conditional(conditional) => {
+                let condition = self.expression(conditional.condition.get());
+                self.operation(
+                    "if".to_string(),
+                    DataflowNodeKind::Sink,
+                    span,
+                    vec![("0".to_string(), condition)],
+                );
+                self.statement(conditional.result.get());
+                if let Some(next) = conditional.next.get() {
+                    self.statement(next);
+                }
+            }
+            Statement::Iteration(iteration) => {
+                let start = self.expression(iteration.start.get());
+                let start = self.materialize(start, span);
+                let stop = self.expression(iteration.stop.get());
+                let stop = self.materialize(stop, span);
+                let variable = self.variable(iteration.variable);
+                self.add_edge(&start, &variable, Some("start".to_string()));
+                self.add_edge(&stop, &variable, Some("stop".to_string()));
+                if let Some(step) = iteration.step.get() {
+                    let step = self.expression(step);
+                    let step = self.materialize(step, span);
+                    self.add_edge(&step, &variable, Some("step".to_string()));
+                }
+                self.statement(iteration.body.get());
+            }
+            Statement::Console(console) => {
+                let (label, arguments) = match &console.function {
+                    ConsoleFunction::Assert(condition) => ("assert", vec![condition]),
+                    ConsoleFunction::AssertEq(args) => ("assert_eq", vec![&args.left, &args.right]),
+                    ConsoleFunction::AssertNe(args) => ("assert_ne", vec![&args.left, &args.right]),
+                    ConsoleFunction::Error(args) => ("error", args.parameters.iter().collect()),
+                    ConsoleFunction::Log(args) => ("log", args.parameters.iter().collect()),
+                };
+                let operands = arguments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, argument)| (position.to_string(), self.expression(argument.get())))
+                    .collect();
+                self.operation(label.to_string(), DataflowNodeKind::Sink, span, operands);
+            }
+            Statement::Expression(expression) => {
+                let value = self.expression(expression.expression.get());
+                self.materialize(value, span);
+            }
+        }
+    }
+}
+
+fn write_node(dot: &mut String, node: &DataflowNode, indent: &str) {
+    let style = match node.kind {
+        DataflowNodeKind::Input => ", shape=box, style=filled, fillcolor=lightblue",
+        DataflowNodeKind::Output => ", shape=box, style=filled, fillcolor=lightpink",
+        DataflowNodeKind::Variable => ", shape=box",
+        DataflowNodeKind::Operation => "",
+        DataflowNodeKind::Constant => ", shape=plaintext",
+        DataflowNodeKind::Sink => ", shape=diamond",
+    };
+    writeln!(dot, "{}{} [label={}{}];", indent, node.id, quote(&node.label), style).unwrap();
+}
+
+/// Quotes a DOT identifier or label, escaping quotes, backslashes and newlines.
+fn quote(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
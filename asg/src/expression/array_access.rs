@@ -14,12 +14,33 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
+use crate::{ConstInt, ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
 use leo_ast::IntegerType;
 use leo_errors::{AsgError, Result, Span};
 
 use std::cell::Cell;
 
+/// Resolves an array index, which may be of any unsigned integer type and defaults to u32.
+///
+/// An index of a signed integer type is only accepted when it is a constant that is not negative.
+pub(crate) fn index_from_ast<'a>(scope: &'a Scope<'a>, index: &leo_ast::Expression) -> Result<&'a Expression<'a>> {
+    let index = <&Expression<'a>>::from_ast(scope, index, Some(PartialType::Integer(None, Some(IntegerType::U32))))?;
+    let is_position = || {
+        index
+            .const_value()
+            .as_ref()
+            .and_then(ConstValue::int)
+            .and_then(ConstInt::to_usize)
+            .is_some()
+    };
+    match index.get_type() {
+        Some(Type::Integer(type_)) if type_.is_signed() && !is_position() => {
+            Err(AsgError::signed_array_index(type_, &index.span().cloned().unwrap_or_default()).into())
+        }
+        _ => Ok(index),
+    }
+}
+
 #[derive(Clone)]
 pub struct ArrayAccessExpression<'a> {
     pub parent: Cell<Option<&'a Expression<'a>>>,
@@ -103,11 +124,7 @@ impl<'a> FromAst<'a, leo_ast::ArrayAccessExpression> for ArrayAccessExpression<'
             }
         };
 
-        let index = <&Expression<'a>>::from_ast(
-            scope,
-            &*value.index,
-            Some(PartialType::Integer(None, Some(IntegerType::U32))),
-        )?;
+        let index = index_from_ast(scope, &*value.index)?;
 
         if let Some(index) = index
             .const_value()
@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
-use leo_ast::SpreadOrExpression;
+use leo_ast::{Node as AstNode, SpreadOrExpression};
 use leo_errors::{AsgError, Result, Span};
 
 use std::cell::Cell;
@@ -134,56 +134,78 @@ impl<'a> FromAst<'a, leo_ast::ArrayInlineExpression> for ArrayInlineExpression<'
             }
         }
 
-        let mut len = 0;
+        if value.elements.is_empty() && expected_item.is_none() {
+            return Err(AsgError::empty_array_without_type(&value.span).into());
+        }
 
-        let output = ArrayInlineExpression {
-            parent: Cell::new(None),
-            span: Some(value.span.clone()),
-            elements: value
-                .elements
-                .iter()
-                .map(|e| match e {
-                    SpreadOrExpression::Expression(e) => {
-                        let expr = <&Expression<'a>>::from_ast(scope, e, expected_item.clone())?;
-                        if expected_item.is_none() {
-                            expected_item = expr.get_type().map(Type::partial);
+        let mut len = 0;
+        let mut elements = vec![];
+        // Elements of another type are reported together, once every element has been typed.
+        let mut mismatches = vec![];
+        for element in value.elements.iter() {
+            match element {
+                SpreadOrExpression::Expression(e) => {
+                    let expr = match <&Expression<'a>>::from_ast(scope, e, expected_item.clone()) {
+                        Ok(expr) => expr,
+                        Err(error) => {
+                            mismatches.push(element_mismatch(scope, e, expected_item.as_ref(), false).ok_or(error)?);
+                            continue;
                         }
-                        len += 1;
-                        Ok((Cell::new(expr), false))
+                    };
+                    if expected_item.is_none() {
+                        expected_item = expr.get_type().map(Type::partial);
                     }
-                    SpreadOrExpression::Spread(e) => {
-                        let expr = <&Expression<'a>>::from_ast(
-                            scope,
-                            e,
-                            Some(PartialType::Array(expected_item.clone().map(Box::new), None)),
-                        )?;
-
-                        match expr.get_type() {
-                            Some(Type::Array(item, spread_len)) => {
-                                if expected_item.is_none() {
-                                    expected_item = Some((*item).partial());
-                                }
-
-                                len += spread_len;
-                            }
-                            type_ => {
-                                return Err(AsgError::unexpected_type(
-                                    expected_item
-                                        .as_ref()
-                                        .map(|x| x.to_string())
-                                        .as_deref()
-                                        .unwrap_or("unknown"),
-                                    type_.map(|x| x.to_string()).unwrap_or_else(|| "unknown".to_string()),
-                                    &value.span,
-                                )
-                                .into());
+                    len += 1;
+                    elements.push((Cell::new(expr), false));
+                }
+                SpreadOrExpression::Spread(e) => {
+                    let expr = match <&Expression<'a>>::from_ast(
+                        scope,
+                        e,
+                        Some(PartialType::Array(expected_item.clone().map(Box::new), None)),
+                    ) {
+                        Ok(expr) => expr,
+                        Err(error) => {
+                            mismatches.push(element_mismatch(scope, e, expected_item.as_ref(), true).ok_or(error)?);
+                            continue;
+                        }
+                    };
+
+                    match expr.get_type() {
+                        Some(Type::Array(item, spread_len)) => {
+                            if expected_item.is_none() {
+                                expected_item = Some((*item).partial());
                             }
+
+                            len += spread_len;
+                        }
+                        type_ => {
+                            return Err(AsgError::unexpected_type(
+                                expected_item
+                                    .as_ref()
+                                    .map(|x| x.to_string())
+                                    .as_deref()
+                                    .unwrap_or("unknown"),
+                                type_.map(|x| x.to_string()).unwrap_or_else(|| "unknown".to_string()),
+                                &value.span,
+                            )
+                            .into());
                         }
-                        Ok((Cell::new(expr), true))
                     }
-                })
-                .collect::<Result<Vec<_>>>()?,
-        };
+                    elements.push((Cell::new(expr), true));
+                }
+            }
+        }
+
+        if !mismatches.is_empty() {
+            let expected = expected_item.map(|x| x.to_string()).unwrap_or_default();
+            let error = mismatches.iter().fold(
+                AsgError::mixed_array_elements(expected, mismatches.len(), &value.span),
+                |error, (span, label)| error.with_label(span, label),
+            );
+            return Err(error.into());
+        }
+
         if let Some(expected_len) = expected_len {
             if len != expected_len {
                 return Err(AsgError::unexpected_type(
@@ -194,7 +216,38 @@ impl<'a> FromAst<'a, leo_ast::ArrayInlineExpression> for ArrayInlineExpression<'
                 .into());
             }
         }
-        Ok(output)
+        Ok(ArrayInlineExpression {
+            parent: Cell::new(None),
+            span: Some(value.span.clone()),
+            elements,
+        })
+    }
+}
+
+///
+/// Returns the span of an element that failed to type as `expected` and a label naming its own type,
+/// if the element has a type and it is not the expected one.
+///
+/// Spread elements are compared by the type of the elements they spread.
+///
+fn element_mismatch<'a>(
+    scope: &'a Scope<'a>,
+    element: &leo_ast::Expression,
+    expected: Option<&PartialType<'a>>,
+    spread: bool,
+) -> Option<(Span, String)> {
+    let expected = expected?;
+    let type_ = <&Expression<'a>>::from_ast(scope, element, None).ok()?.get_type()?;
+    let (type_, label) = match type_ {
+        Type::Array(item, _) if spread => (*item, "spreads elements of type"),
+        _ if spread => return None,
+        type_ => (type_, "has type"),
+    };
+
+    if expected.matches(&type_) {
+        None
+    } else {
+        Some((element.span().clone(), format!("{} `{}`", label, type_)))
     }
 }
 
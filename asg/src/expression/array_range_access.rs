@@ -14,8 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
-use leo_ast::IntegerType;
+use crate::{index_from_ast, ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
 use leo_errors::{AsgError, Result, Span};
 
 use std::cell::Cell;
@@ -128,57 +127,57 @@ impl<'a> FromAst<'a, leo_ast::ArrayRangeAccessExpression> for ArrayRangeAccessEx
         let left = value
             .left
             .as_deref()
-            .map(|left| {
-                <&Expression<'a>>::from_ast(scope, left, Some(PartialType::Integer(None, Some(IntegerType::U32))))
-            })
+            .map(|left| index_from_ast(scope, left))
             .transpose()?;
         let right = value
             .right
             .as_deref()
-            .map(|right| {
-                <&Expression<'a>>::from_ast(scope, right, Some(PartialType::Integer(None, Some(IntegerType::U32))))
-            })
+            .map(|right| index_from_ast(scope, right))
             .transpose()?;
 
+        // Missing bounds default to the start and the end of the array.
         let const_left = match left.map(|x| x.const_value()) {
             Some(Some(ConstValue::Int(x))) => x.to_usize(),
             None => Some(0),
             _ => None,
         };
         let const_right = match right.map(|x| x.const_value()) {
-            Some(Some(ConstValue::Int(inner_value))) => {
-                let usize_value = inner_value.to_usize();
-                if let Some(inner_value) = usize_value {
-                    if inner_value > parent_size {
-                        let error_span = if let Some(right) = right {
-                            right.span().cloned().unwrap_or_default()
-                        } else {
-                            value.span.clone()
-                        };
-                        return Err(AsgError::array_index_out_of_bounds(inner_value, &error_span).into());
-                    } else if let Some(left) = const_left {
-                        if left > inner_value {
-                            let error_span = if let Some(right) = right {
-                                right.span().cloned().unwrap_or_default()
-                            } else {
-                                value.span.clone()
-                            };
-                            return Err(AsgError::array_index_out_of_bounds(inner_value, &error_span).into());
-                        }
-                    }
-                }
-                usize_value
-            }
+            Some(Some(ConstValue::Int(x))) => x.to_usize(),
             None => Some(parent_size),
             _ => None,
         };
+        let bound_span = |bound: Option<&'a Expression<'a>>| {
+            bound
+                .and_then(|bound| bound.span().cloned())
+                .unwrap_or_else(|| value.span.clone())
+        };
+
+        if let Some(right_value) = const_right {
+            if right_value > parent_size {
+                return Err(
+                    AsgError::array_range_out_of_bounds("stop", right_value, parent_size, &bound_span(right)).into(),
+                );
+            }
+        }
+        if let Some(left_value) = const_left {
+            if left_value > parent_size {
+                return Err(
+                    AsgError::array_range_out_of_bounds("start", left_value, parent_size, &bound_span(left)).into(),
+                );
+            }
+        }
 
-        let mut length = if let (Some(left), Some(right)) = (const_left, const_right) {
-            Some(right - left)
-        } else {
-            None
+        let mut length = match (const_left, const_right) {
+            (Some(left_value), Some(right_value)) if left_value > right_value => {
+                return Err(
+                    AsgError::array_range_reversed(left_value, right_value, parent_size, &bound_span(left)).into(),
+                );
+            }
+            (Some(left_value), Some(right_value)) => Some(right_value - left_value),
+            _ => None,
         };
 
+        // A declared result type fixes the length even when the start is only known at runtime.
         if let Some(expected_len) = expected_len {
             if let Some(length) = length {
                 if length != expected_len {
@@ -190,19 +189,18 @@ impl<'a> FromAst<'a, leo_ast::ArrayRangeAccessExpression> for ArrayRangeAccessEx
             }
             if let Some(left_value) = const_left {
                 if left_value + expected_len > parent_size {
-                    let error_span = if let Some(left) = left {
-                        left.span().cloned().unwrap_or_default()
-                    } else {
-                        value.span.clone()
-                    };
-                    return Err(AsgError::array_index_out_of_bounds(left_value, &error_span).into());
+                    return Err(AsgError::array_range_out_of_bounds(
+                        "stop",
+                        left_value + expected_len,
+                        parent_size,
+                        &bound_span(right),
+                    )
+                    .into());
                 }
             }
             length = Some(expected_len);
         }
-        if length.is_none() {
-            return Err(AsgError::unknown_array_size(&value.span).into());
-        }
+        let length = length.ok_or_else(|| AsgError::array_range_length_unknown(parent_size, &value.span))?;
 
         Ok(ArrayRangeAccessExpression {
             parent: Cell::new(None),
@@ -210,7 +208,7 @@ impl<'a> FromAst<'a, leo_ast::ArrayRangeAccessExpression> for ArrayRangeAccessEx
             array: Cell::new(array),
             left: Cell::new(left),
             right: Cell::new(right),
-            length: length.unwrap(),
+            length,
         })
     }
 }
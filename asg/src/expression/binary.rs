@@ -14,10 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
+use crate::{
+    add_group, invert_field, negate_group, pow_field, reduce_field, CastExpression, ConstValue, Expression,
+    ExpressionNode, FromAst, GroupValue, Node, PartialType, Scope, Type,
+};
+use leo_ast::{Feature, Node as AstNode};
 pub use leo_ast::{BinaryOperation, BinaryOperationClass};
 use leo_errors::{AsgError, Result, Span};
 
+use num_bigint::BigInt;
+
 use std::cell::Cell;
 
 #[derive(Clone)]
@@ -29,6 +35,280 @@ pub struct BinaryExpression<'a> {
     pub right: Cell<&'a Expression<'a>>,
 }
 
+impl<'a> BinaryExpression<'a> {
+    ///
+    /// Applies the operation to constant operands.
+    ///
+    /// Returns `None` if the operation is not defined for the operands or overflows.
+    ///
+    pub fn evaluate<'b>(
+        operation: &BinaryOperation,
+        left: ConstValue<'b>,
+        right: ConstValue<'b>,
+    ) -> Option<ConstValue<'b>> {
+        use BinaryOperation::*;
+        match (left, right) {
+            (ConstValue::Int(left), ConstValue::Int(right)) => Some(match operation {
+                Add => ConstValue::Int(left.value_add(&right)?),
+                Sub => ConstValue::Int(left.value_sub(&right)?),
+                Mul => ConstValue::Int(left.value_mul(&right)?),
+                Div => ConstValue::Int(left.value_div(&right)?),
+                Pow => ConstValue::Int(left.value_pow(&right)?),
+                Eq => ConstValue::Boolean(left == right),
+                Ne => ConstValue::Boolean(left != right),
+                Ge => ConstValue::Boolean(left.value_ge(&right)?),
+                Gt => ConstValue::Boolean(left.value_gt(&right)?),
+                Le => ConstValue::Boolean(left.value_le(&right)?),
+                Lt => ConstValue::Boolean(left.value_lt(&right)?),
+                _ => return None,
+            }),
+            // Field elements are canonical, so arithmetic folds modulo the field prime.
+            // Ordering comparisons on field elements must never be folded, even under `@allow_field_ordering`.
+            (ConstValue::Field(left), ConstValue::Field(right)) => Some(match operation {
+                Add => ConstValue::Field(reduce_field(&(left + right))),
+                Sub => ConstValue::Field(reduce_field(&(left - right))),
+                Mul => ConstValue::Field(reduce_field(&(left * right))),
+                Div => ConstValue::Field(reduce_field(&(left * invert_field(&right)?))),
+                Eq => ConstValue::Boolean(left == right),
+                Ne => ConstValue::Boolean(left != right),
+                _ => return None,
+            }),
+            (ConstValue::Field(left), ConstValue::Int(right)) => match operation {
+                Pow => Some(ConstValue::Field(pow_field(&left, &right.raw_value().parse().ok()?)?)),
+                _ => None,
+            },
+            // Points the circuit would have to recover from a coordinate are left to it.
+            (ConstValue::Group(left), ConstValue::Group(right)) => match (left.to_point(), right.to_point()) {
+                (Some(left), Some(right)) => Some(match operation {
+                    Add => ConstValue::Group(GroupValue::from_point(add_group(&left, &right)?)),
+                    Sub => ConstValue::Group(GroupValue::from_point(add_group(&left, &negate_group(&right))?)),
+                    Eq => ConstValue::Boolean(left == right),
+                    Ne => ConstValue::Boolean(left != right),
+                    _ => return None,
+                }),
+                // The same literal is the same point, whichever it is.
+                _ if left == right => match operation {
+                    Eq => Some(ConstValue::Boolean(true)),
+                    Ne => Some(ConstValue::Boolean(false)),
+                    _ => None,
+                },
+                _ => None,
+            },
+            (ConstValue::Boolean(left), ConstValue::Boolean(right)) => Some(match operation {
+                Eq => ConstValue::Boolean(left == right),
+                Ne => ConstValue::Boolean(left != right),
+                And => ConstValue::Boolean(left && right),
+                Or => ConstValue::Boolean(left || right),
+                _ => return None,
+            }),
+            (left, right) => Some(match operation {
+                Eq => ConstValue::Boolean(left == right),
+                Ne => ConstValue::Boolean(left != right),
+                _ => return None,
+            }),
+        }
+    }
+
+    ///
+    /// Returns the result of a comparison of strings of different lengths, or of a concatenation of strings.
+    ///
+    /// Both are decided at compile time, so the expression is replaced by its result.
+    ///
+    pub fn string_value(&self) -> Option<ConstValue<'a>> {
+        let left = self.left.get();
+        let right = self.right.get();
+        match (left.get_type()?, right.get_type()?) {
+            (Type::Array(left_element, left_len), Type::Array(right_element, right_len))
+                if *left_element == Type::Char && *right_element == Type::Char =>
+            {
+                match self.operation {
+                    BinaryOperation::Eq | BinaryOperation::Ne if left_len != right_len => {
+                        Some(ConstValue::Boolean(self.operation == BinaryOperation::Ne))
+                    }
+                    BinaryOperation::Add => match (left.const_value()?, right.const_value()?) {
+                        (ConstValue::Array(mut left), ConstValue::Array(right)) => {
+                            left.extend(right);
+                            Some(ConstValue::Array(left))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    ///
+    /// Checks an operation on two strings, warning about comparisons that are decided by the lengths alone.
+    ///
+    fn check_strings(
+        scope: &'a Scope<'a>,
+        value: &leo_ast::BinaryExpression,
+        left: &'a Expression<'a>,
+        right: &'a Expression<'a>,
+        left_type: &Type<'a>,
+        right_type: &Type<'a>,
+        concatenation_type: Option<PartialType<'a>>,
+    ) -> Result<()> {
+        let (left_len, right_len) = match (left_type, right_type) {
+            (Type::Array(_, left_len), Type::Array(_, right_len)) => (*left_len, *right_len),
+            _ => return Ok(()),
+        };
+        match value.op {
+            BinaryOperation::Eq | BinaryOperation::Ne if left_len != right_len => {
+                scope.context.warn(
+                    format!(
+                        "strings of different lengths are never equal — this comparison is always {}",
+                        value.op == BinaryOperation::Ne
+                    ),
+                    &value.span,
+                );
+            }
+            BinaryOperation::Add => {
+                if left.const_value().is_none() || right.const_value().is_none() {
+                    return Err(AsgError::string_concatenation_not_constant(&value.span).into());
+                }
+                let type_ = Type::Array(Box::new(Type::Char), left_len + right_len);
+                if let Some(expected) = concatenation_type {
+                    if !expected.matches(&type_) {
+                        return Err(AsgError::unexpected_type(expected, type_, &value.span).into());
+                    }
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    ///
+    /// Converts the operands, giving an untyped literal on either side the type of the other operand.
+    ///
+    fn operands(
+        scope: &'a Scope<'a>,
+        value: &leo_ast::BinaryExpression,
+        expected_type: Option<PartialType<'a>>,
+    ) -> Result<(&'a Expression<'a>, &'a Expression<'a>)> {
+        // left
+        let operands = match <&Expression<'a>>::from_ast(scope, &*value.left, expected_type.clone()) {
+            Ok(left) => {
+                if let Some(left_type) = left.get_type() {
+                    // Strings of any length can be compared and concatenated.
+                    let right_type = if is_string(&left_type)
+                        && matches!(
+                            value.op,
+                            BinaryOperation::Add | BinaryOperation::Eq | BinaryOperation::Ne
+                        ) {
+                        PartialType::Array(Some(Box::new(PartialType::Type(Type::Char))), None)
+                    } else {
+                        left_type.partial()
+                    };
+                    let right = <&Expression<'a>>::from_ast(scope, &*value.right, Some(right_type))?;
+                    (left, right)
+                } else {
+                    let right = <&Expression<'a>>::from_ast(scope, &*value.right, expected_type)?;
+                    if let Some(right_type) = right.get_type() {
+                        (
+                            <&Expression<'a>>::from_ast(scope, &*value.left, Some(right_type.partial()))?,
+                            right,
+                        )
+                    } else {
+                        (left, right)
+                    }
+                }
+            }
+            Err(e) => {
+                // An untyped literal on the left adopts the type of the right operand.
+                // If the right operand fails as well, the left error is the one to report.
+                let right = match <&Expression<'a>>::from_ast(scope, &*value.right, expected_type) {
+                    Ok(right) => right,
+                    Err(_) => return Err(e),
+                };
+                if let Some(right_type) = right.get_type() {
+                    (
+                        <&Expression<'a>>::from_ast(scope, &*value.left, Some(right_type.partial()))?,
+                        right,
+                    )
+                } else {
+                    return Err(e);
+                }
+            }
+        };
+        Ok(operands)
+    }
+
+    ///
+    /// Converts the operands of an arithmetic operation or comparison on integers of one signedness but different
+    /// widths, casting the narrower operand to the type of the wider one.
+    ///
+    /// Returns `None` when the operands fail to type check for any other reason.
+    ///
+    fn widen_operands(
+        scope: &'a Scope<'a>,
+        value: &leo_ast::BinaryExpression,
+        expected_type: Option<&PartialType<'a>>,
+    ) -> Result<Option<(&'a Expression<'a>, &'a Expression<'a>)>> {
+        use BinaryOperation::*;
+        if !matches!(value.op, Add | Sub | Mul | Div | Eq | Ne | Lt | Le | Gt | Ge) {
+            return Ok(None);
+        }
+        let (left, right) = match (
+            <&Expression<'a>>::from_ast(scope, &*value.left, None),
+            <&Expression<'a>>::from_ast(scope, &*value.right, None),
+        ) {
+            (Ok(left), Ok(right)) => (left, right),
+            _ => return Ok(None),
+        };
+        let (left_type, right_type) = match (left.get_type(), right.get_type()) {
+            (Some(Type::Integer(left_type)), Some(Type::Integer(right_type)))
+                if left_type.is_signed() == right_type.is_signed() && left_type != right_type =>
+            {
+                (left_type, right_type)
+            }
+            _ => return Ok(None),
+        };
+        if !scope.context.integer_widening.get() {
+            return Err(
+                AsgError::mixed_width_integer_operation(value.op.as_ref(), left_type, right_type, &value.span).into(),
+            );
+        }
+        Feature::IntegerWidening.check(scope.context.edition.get(), &value.span)?;
+
+        let wider = if left_type.bit_width() > right_type.bit_width() {
+            left_type
+        } else {
+            right_type
+        };
+        if let Some(expected_type) = expected_type {
+            if !expected_type.matches(&Type::Integer(wider.clone())) {
+                return Err(AsgError::unexpected_type(expected_type, Type::Integer(wider), &value.span).into());
+            }
+        }
+        let widen = |operand: &'a Expression<'a>| -> &'a Expression<'a> {
+            if operand.get_type() == Some(Type::Integer(wider.clone())) {
+                return operand;
+            }
+            let cast = scope.context.alloc_expression(Expression::Cast(CastExpression {
+                parent: Cell::new(None),
+                span: operand.span().cloned(),
+                inner: Cell::new(operand),
+                target_type: Type::Integer(wider.clone()),
+                implicit: true,
+            }));
+            cast.enforce_parents(cast);
+            cast
+        };
+        Ok(Some((widen(left), widen(right))))
+    }
+}
+
+///
+/// Returns `true` if the type is a string, that is an array of chars.
+///
+fn is_string(type_: &Type) -> bool {
+    matches!(type_, Type::Array(element, _) if **element == Type::Char)
+}
+
 impl<'a> Node for BinaryExpression<'a> {
     fn span(&self) -> Option<&Span> {
         self.span.as_ref()
@@ -60,51 +340,10 @@ impl<'a> ExpressionNode<'a> for BinaryExpression<'a> {
         false
     }
 
-    fn const_value(&self) -> Option<ConstValue> {
-        use BinaryOperation::*;
+    fn const_value(&'a self) -> Option<ConstValue<'a>> {
         let left = self.left.get().const_value()?;
         let right = self.right.get().const_value()?;
-
-        match (left, right) {
-            (ConstValue::Int(left), ConstValue::Int(right)) => Some(match self.operation {
-                Add => ConstValue::Int(left.value_add(&right)?),
-                Sub => ConstValue::Int(left.value_sub(&right)?),
-                Mul => ConstValue::Int(left.value_mul(&right)?),
-                Div => ConstValue::Int(left.value_div(&right)?),
-                Pow => ConstValue::Int(left.value_pow(&right)?),
-                Eq => ConstValue::Boolean(left == right),
-                Ne => ConstValue::Boolean(left != right),
-                Ge => ConstValue::Boolean(left.value_ge(&right)?),
-                Gt => ConstValue::Boolean(left.value_gt(&right)?),
-                Le => ConstValue::Boolean(left.value_le(&right)?),
-                Lt => ConstValue::Boolean(left.value_lt(&right)?),
-                _ => return None,
-            }),
-            // (ConstValue::Field(left), ConstValue::Field(right)) => {
-            //     Some(match self.operation {
-            //         Add => ConstValue::Field(left.checked_add(&right)?),
-            //         Sub => ConstValue::Field(left.checked_sub(&right)?),
-            //         Mul => ConstValue::Field(left.checked_mul(&right)?),
-            //         Div => ConstValue::Field(left.checked_div(&right)?),
-            //         Eq => ConstValue::Boolean(left == right),
-            //         Ne => ConstValue::Boolean(left != right),
-            //         _ => return None,
-            //     })
-            // },
-            (ConstValue::Boolean(left), ConstValue::Boolean(right)) => Some(match self.operation {
-                Eq => ConstValue::Boolean(left == right),
-                Ne => ConstValue::Boolean(left != right),
-                And => ConstValue::Boolean(left && right),
-                Or => ConstValue::Boolean(left || right),
-                _ => return None,
-            }),
-            //todo: group?
-            (left, right) => Some(match self.operation {
-                Eq => ConstValue::Boolean(left == right),
-                Ne => ConstValue::Boolean(left != right),
-                _ => return None,
-            }),
-        }
+        Self::evaluate(&self.operation, left, right)
     }
 
     fn is_consty(&self) -> bool {
@@ -119,6 +358,8 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
         expected_type: Option<PartialType<'a>>,
     ) -> Result<BinaryExpression<'a>> {
         let class = value.op.class();
+        // A concatenation of strings is checked against the expected type once its length is known.
+        let mut concatenation_type = None;
         let expected_type = match class {
             BinaryOperationClass::Boolean => match expected_type {
                 Some(PartialType::Type(Type::Boolean)) | None => None,
@@ -130,6 +371,10 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
                 Some(x @ PartialType::Integer(_, _)) => Some(x),
                 Some(x @ PartialType::Type(Type::Field)) => Some(x),
                 Some(x @ PartialType::Type(Type::Group)) => Some(x),
+                Some(x @ PartialType::Array(_, _)) if value.op == BinaryOperation::Add => {
+                    concatenation_type = Some(x);
+                    None
+                }
                 Some(x) => {
                     return Err(AsgError::unexpected_type("integer, field, or group", x, &value.span).into());
                 }
@@ -137,35 +382,12 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
             },
         };
 
-        // left
-        let (left, right) = match <&Expression<'a>>::from_ast(scope, &*value.left, expected_type.clone()) {
-            Ok(left) => {
-                if let Some(left_type) = left.get_type() {
-                    let right = <&Expression<'a>>::from_ast(scope, &*value.right, Some(left_type.partial()))?;
-                    (left, right)
-                } else {
-                    let right = <&Expression<'a>>::from_ast(scope, &*value.right, expected_type)?;
-                    if let Some(right_type) = right.get_type() {
-                        (
-                            <&Expression<'a>>::from_ast(scope, &*value.left, Some(right_type.partial()))?,
-                            right,
-                        )
-                    } else {
-                        (left, right)
-                    }
-                }
-            }
-            Err(e) => {
-                let right = <&Expression<'a>>::from_ast(scope, &*value.right, expected_type)?;
-                if let Some(right_type) = right.get_type() {
-                    (
-                        <&Expression<'a>>::from_ast(scope, &*value.left, Some(right_type.partial()))?,
-                        right,
-                    )
-                } else {
-                    return Err(e);
-                }
-            }
+        let (left, right) = match Self::operands(scope, value, expected_type.clone()) {
+            Ok(operands) => operands,
+            Err(e) => match Self::widen_operands(scope, value, expected_type.as_ref())? {
+                Some(operands) => operands,
+                None => return Err(e),
+            },
         };
 
         let left_type = left.get_type();
@@ -173,6 +395,7 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
         match class {
             BinaryOperationClass::Numeric => match left_type {
                 Some(Type::Integer(_)) => (),
+                Some(ref type_) if value.op == BinaryOperation::Add && is_string(type_) => (),
                 Some(Type::Group) | Some(Type::Field)
                     if value.op == BinaryOperation::Add || value.op == BinaryOperation::Sub =>
                 {
@@ -198,6 +421,14 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
                 BinaryOperation::Eq | BinaryOperation::Ne => (), // all types allowed
                 op => match left_type {
                     Some(Type::Integer(_)) | None => (),
+                    Some(Type::Field)
+                        if scope
+                            .resolve_current_function()
+                            .map(|function| function.allows_field_ordering())
+                            .unwrap_or(false) => {}
+                    Some(x @ Type::Field) | Some(x @ Type::Group) => {
+                        return Err(AsgError::field_ordering_not_allowed(op.as_ref(), x, &value.span).into());
+                    }
                     Some(x) => {
                         return Err(
                             AsgError::operator_allowed_only_for_type(op.as_ref(), "integer", x, &value.span).into(),
@@ -210,6 +441,9 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
         let right_type = right.get_type();
 
         match (left_type, right_type) {
+            (Some(left_type), Some(right_type)) if is_string(&left_type) && is_string(&right_type) => {
+                Self::check_strings(scope, value, left, right, &left_type, &right_type, concatenation_type)?;
+            }
             (Some(left_type), Some(right_type)) => {
                 if !left_type.is_assignable_from(&right_type) {
                     return Err(AsgError::unexpected_type(left_type, right_type, &value.span).into());
@@ -220,6 +454,14 @@ impl<'a> FromAst<'a, leo_ast::BinaryExpression> for BinaryExpression<'a> {
             }
             (_, _) => (),
         }
+
+        // Zero has no inverse, so a division by a constant zero field element can never succeed.
+        if value.op == BinaryOperation::Div
+            && matches!(right.const_value(), Some(ConstValue::Field(divisor)) if divisor == BigInt::from(0))
+        {
+            return Err(AsgError::field_division_by_zero(value.right.span()).into());
+        }
+
         Ok(BinaryExpression {
             parent: Cell::new(None),
             span: Some(value.span.clone()),
@@ -0,0 +1,209 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    ConstValue, Expression, ExpressionNode, FromAst, IntegerType, Node, PartialType, Scope, Type, FIELD_BIT_WIDTH,
+};
+use leo_errors::{AsgError, Result, Span};
+
+use std::cell::Cell;
+
+/// The names of the core functions, which a user function of the same name shadows.
+pub const CORE_FUNCTION_NAMES: &[&str] = &["to_bits_le", "from_bits_le"];
+
+/// The direction of a bit decomposition intrinsic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitsDirection {
+    /// `to_bits_le(x)`: decomposes an integer or field element into `[bool; N]`, least significant bit first.
+    ToBitsLe,
+    /// `from_bits_le(bits)`: recomposes an integer or field element from `[bool; N]`, least significant bit first.
+    FromBitsLe,
+}
+
+impl BitsDirection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BitsDirection::ToBitsLe => "to_bits_le",
+            BitsDirection::FromBitsLe => "from_bits_le",
+        }
+    }
+
+    /// Returns the intrinsic called by the given ast call, unless a user function shadows it.
+    pub fn resolve(scope: &Scope, value: &leo_ast::CallExpression) -> Option<BitsDirection> {
+        let name = match &*value.function {
+            leo_ast::Expression::Identifier(name) => name,
+            _ => return None,
+        };
+        if scope.resolve_function(&name.name).is_some() {
+            return None;
+        }
+        match name.name.as_ref() {
+            "to_bits_le" => Some(BitsDirection::ToBitsLe),
+            "from_bits_le" => Some(BitsDirection::FromBitsLe),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the number of bits in the value type of a bit decomposition, if it has one.
+pub fn bit_width(type_: &Type) -> Option<usize> {
+    match type_ {
+        Type::Integer(integer_type) => Some(integer_type.bit_width()),
+        Type::Field => Some(FIELD_BIT_WIDTH),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+pub struct BitsExpression<'a> {
+    pub parent: Cell<Option<&'a Expression<'a>>>,
+    pub span: Option<Span>,
+    pub direction: BitsDirection,
+    pub inner: Cell<&'a Expression<'a>>,
+    pub target_type: Type<'a>,
+}
+
+impl<'a> Node for BitsExpression<'a> {
+    fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
+
+impl<'a> ExpressionNode<'a> for BitsExpression<'a> {
+    fn set_parent(&self, parent: &'a Expression<'a>) {
+        self.parent.replace(Some(parent));
+    }
+
+    fn get_parent(&self) -> Option<&'a Expression<'a>> {
+        self.parent.get()
+    }
+
+    fn enforce_parents(&self, expr: &'a Expression<'a>) {
+        self.inner.get().set_parent(expr);
+    }
+
+    fn get_type(&self) -> Option<Type<'a>> {
+        Some(self.target_type.clone())
+    }
+
+    fn is_mut_ref(&self) -> bool {
+        false
+    }
+
+    fn const_value(&self) -> Option<ConstValue> {
+        None
+    }
+
+    fn is_consty(&self) -> bool {
+        self.inner.get().is_consty()
+    }
+}
+
+impl<'a> BitsExpression<'a> {
+    pub fn from_ast(
+        scope: &'a Scope<'a>,
+        value: &leo_ast::CallExpression,
+        direction: BitsDirection,
+        expected_type: Option<PartialType<'a>>,
+    ) -> Result<BitsExpression<'a>> {
+        if value.arguments.len() != 1 {
+            return Err(AsgError::unexpected_call_argument_count(1, value.arguments.len(), &value.span).into());
+        }
+
+        let (inner, target_type) = match direction {
+            BitsDirection::ToBitsLe => {
+                let inner = <&Expression<'a>>::from_ast(scope, &value.arguments[0], None)?;
+                let width = match inner.get_type() {
+                    Some(type_) => bit_width(&type_)
+                        .ok_or_else(|| AsgError::unexpected_type("integer or field", type_, &value.span))?,
+                    None => return Err(AsgError::unexpected_type("integer or field", "unknown", &value.span).into()),
+                };
+                (inner, Type::Array(Box::new(Type::Boolean), width))
+            }
+            BitsDirection::FromBitsLe => {
+                let bits_type = PartialType::Array(Some(Box::new(Type::Boolean.partial())), None);
+                let inner = <&Expression<'a>>::from_ast(scope, &value.arguments[0], Some(bits_type))?;
+                let length = match inner.get_type() {
+                    Some(Type::Array(element, length)) if *element == Type::Boolean => length,
+                    type_ => {
+                        return Err(AsgError::unexpected_type(
+                            "[bool; _]",
+                            type_.map(|x| x.to_string()).unwrap_or_else(|| "unknown".to_string()),
+                            &value.span,
+                        )
+                        .into());
+                    }
+                };
+
+                let target_type = match expected_type.as_ref().and_then(|expected| expected.clone().full()) {
+                    Some(type_) => match bit_width(&type_) {
+                        Some(width) if width == length => type_,
+                        Some(width) => {
+                            return Err(AsgError::from_bits_invalid_width(
+                                length,
+                                format!("{} bits for `{}`", width, type_),
+                                &value.span,
+                            )
+                            .into());
+                        }
+                        None => {
+                            return Err(AsgError::unexpected_type("integer or field", type_, &value.span).into());
+                        }
+                    },
+                    None if length == FIELD_BIT_WIDTH => Type::Field,
+                    None => IntegerType::unsigned_with_bit_width(length)
+                        .map(Type::Integer)
+                        .ok_or_else(|| {
+                            AsgError::from_bits_invalid_width(
+                                length,
+                                format!("one of 8, 16, 32, 64, 128, or {} bits", FIELD_BIT_WIDTH),
+                                &value.span,
+                            )
+                        })?,
+                };
+                (inner, target_type)
+            }
+        };
+
+        if let Some(expected) = expected_type {
+            if !expected.matches(&target_type) {
+                return Err(AsgError::unexpected_type(expected, target_type, &value.span).into());
+            }
+        }
+
+        Ok(BitsExpression {
+            parent: Cell::new(None),
+            span: Some(value.span.clone()),
+            direction,
+            inner: Cell::new(inner),
+            target_type,
+        })
+    }
+}
+
+impl<'a> Into<leo_ast::CallExpression> for &BitsExpression<'a> {
+    fn into(self) -> leo_ast::CallExpression {
+        let span = self.span.clone().unwrap_or_default();
+        leo_ast::CallExpression {
+            function: Box::new(leo_ast::Expression::Identifier(leo_ast::Identifier {
+                name: self.direction.name().into(),
+                span: span.clone(),
+            })),
+            arguments: vec![self.inner.get().into()],
+            span,
+        }
+    }
+}
@@ -15,8 +15,8 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    CircuitMember, ConstValue, Expression, ExpressionNode, FromAst, Function, FunctionQualifier, Node, PartialType,
-    Scope, Type,
+    static_member_unresolved, Circuit, CircuitMember, ConstValue, Expression, ExpressionNode, FromAst, Function,
+    FunctionQualifier, Node, PartialType, Scope, Type,
 };
 pub use leo_ast::{BinaryOperation, Node as AstNode};
 use leo_errors::{AsgError, Result, Span};
@@ -80,21 +80,24 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
         value: &leo_ast::CallExpression,
         expected_type: Option<PartialType<'a>>,
     ) -> Result<CallExpression<'a>> {
-        let (target, function) = match &*value.function {
-            leo_ast::Expression::Identifier(name) => (
-                None,
-                scope
-                    .resolve_function(&name.name)
-                    .ok_or_else(|| AsgError::unresolved_function(&name.name, &name.span))?,
-            ),
+        // A member function is called on an instance either as its target, for receiver methods, or as its
+        // first argument, for functions whose first parameter has the type of the circuit.
+        let mut target = None;
+        let mut leading_argument = None;
+        let mut ast_arguments = &value.arguments[..];
+        let mut receiver_call = false;
+        let function = match value.function.without_parentheses() {
+            leo_ast::Expression::Identifier(name) => scope
+                .resolve_function(&name.name)
+                .ok_or_else(|| AsgError::unresolved_function(&name.name, &name.span))?,
             leo_ast::Expression::CircuitMemberAccess(leo_ast::CircuitMemberAccessExpression {
                 circuit: ast_circuit,
                 name,
                 span,
                 ..
             }) => {
-                let target = <&Expression<'a>>::from_ast(scope, &**ast_circuit, None)?;
-                let circuit = match target.get_type() {
+                let instance = <&Expression<'a>>::from_ast(scope, &**ast_circuit, None)?;
+                let circuit = match instance.get_type() {
                     Some(Type::Circuit(circuit)) => circuit,
                     type_ => {
                         return Err(AsgError::unexpected_type(
@@ -113,15 +116,24 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
                 match member {
                     CircuitMember::Function(body) => {
                         if body.qualifier == FunctionQualifier::Static {
-                            return Err(AsgError::circuit_static_call_invalid(&circuit_name, &name.name, span).into());
-                        } else if body.qualifier == FunctionQualifier::MutSelfRef && !target.is_mut_ref() {
+                            if !takes_instance(body, circuit) {
+                                return Err(
+                                    AsgError::circuit_static_call_invalid(&circuit_name, &name.name, span).into()
+                                );
+                            }
+                            // `value.f(a)` is `Circuit::f(value, a)`.
+                            leading_argument = Some(instance);
+                        } else if body.qualifier == FunctionQualifier::MutSelfRef && !instance.is_mut_ref() {
                             return Err(
                                 AsgError::circuit_member_mut_call_invalid(circuit_name, &name.name, span).into(),
                             );
+                        } else {
+                            target = Some(instance);
                         }
-                        (Some(target), *body)
+                        receiver_call = true;
+                        *body
                     }
-                    CircuitMember::Variable(_) => {
+                    CircuitMember::Variable(_) | CircuitMember::Static(..) => {
                         return Err(AsgError::circuit_variable_call(circuit_name, &name.name, span).into());
                     }
                 }
@@ -131,10 +143,8 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
                 name,
                 span,
             }) => {
-                let circuit = if let leo_ast::Expression::Identifier(circuit_name) = &**ast_circuit {
-                    scope
-                        .resolve_circuit(&circuit_name.name)
-                        .ok_or_else(|| AsgError::unresolved_circuit(&circuit_name.name, &circuit_name.span))?
+                let (circuit, alias) = if let leo_ast::Expression::Identifier(circuit_name) = &**ast_circuit {
+                    scope.resolve_static_circuit(circuit_name)?
                 } else {
                     return Err(AsgError::unexpected_type("circuit", "unknown", span).into());
                 };
@@ -143,15 +153,32 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
                 let member = circuit.members.borrow();
                 let member = member
                     .get(name.name.as_ref())
-                    .ok_or_else(|| AsgError::unresolved_circuit_member(&circuit_name, &name.name, span))?;
+                    .ok_or_else(|| static_member_unresolved(circuit, alias, &name.name, span))?;
                 match member {
                     CircuitMember::Function(body) => {
                         if body.qualifier != FunctionQualifier::Static {
-                            return Err(AsgError::circuit_member_call_invalid(circuit_name, &name.name, span).into());
+                            // `Circuit::f(value, a)` is `value.f(a)`.
+                            let (receiver, rest) = value.arguments.split_first().ok_or_else(|| {
+                                AsgError::unexpected_receiver_call_argument_count(
+                                    body.arguments.len(),
+                                    "no receiver",
+                                    &value.span,
+                                )
+                            })?;
+                            let receiver =
+                                <&Expression<'a>>::from_ast(scope, receiver, Some(Type::Circuit(circuit).partial()))?;
+                            if body.qualifier == FunctionQualifier::MutSelfRef && !receiver.is_mut_ref() {
+                                return Err(
+                                    AsgError::circuit_member_mut_call_invalid(circuit_name, &name.name, span).into(),
+                                );
+                            }
+                            target = Some(receiver);
+                            ast_arguments = rest;
+                            receiver_call = true;
                         }
-                        (None, *body)
+                        *body
                     }
-                    CircuitMember::Variable(_) => {
+                    CircuitMember::Variable(_) | CircuitMember::Static(..) => {
                         return Err(AsgError::circuit_variable_call(circuit_name, &name.name, span).into());
                     }
                 }
@@ -170,28 +197,41 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
                 return Err(AsgError::unexpected_type(expected, output, &value.span).into());
             }
         }
-        if value.arguments.len() != function.arguments.len() {
-            return Err(AsgError::unexpected_call_argument_count(
-                function.arguments.len(),
-                value.arguments.len(),
-                &value.span,
-            )
-            .into());
+        let parameters = function.arguments.values().skip(leading_argument.is_some() as usize);
+        if ast_arguments.len() != parameters.len() {
+            if receiver_call {
+                return Err(AsgError::unexpected_receiver_call_argument_count(
+                    parameters.len(),
+                    ast_arguments.len(),
+                    &value.span,
+                )
+                .into());
+            }
+            return Err(
+                AsgError::unexpected_call_argument_count(parameters.len(), ast_arguments.len(), &value.span).into(),
+            );
         }
 
-        let arguments = value
-            .arguments
-            .iter()
-            .zip(function.arguments.iter())
-            .map(|(expr, (_, argument))| {
+        let mut arguments = vec![];
+        if let Some(instance) = leading_argument {
+            let parameter = function.arguments.values().next().unwrap().get().borrow();
+            if parameter.const_ && !instance.is_consty() {
+                return Err(AsgError::unexpected_nonconst(&value.span).into());
+            }
+            arguments.push(Cell::new(instance));
+        }
+        for (expr, argument) in ast_arguments.iter().zip(parameters) {
+            // The parameter is not borrowed while its argument is converted, as a recursive call may refer to it.
+            let (type_, const_) = {
                 let argument = argument.get().borrow();
-                let converted = <&Expression<'a>>::from_ast(scope, expr, Some(argument.type_.clone().partial()))?;
-                if argument.const_ && !converted.is_consty() {
-                    return Err(AsgError::unexpected_nonconst(expr.span()).into());
-                }
-                Ok(Cell::new(converted))
-            })
-            .collect::<Result<Vec<_>>>()?;
+                (argument.type_.clone(), argument.const_)
+            };
+            let converted = <&Expression<'a>>::from_ast(scope, expr, Some(type_.partial()))?;
+            if const_ && !converted.is_consty() {
+                return Err(AsgError::unexpected_nonconst(expr.span()).into());
+            }
+            arguments.push(Cell::new(converted));
+        }
 
         if function.is_test() {
             return Err(AsgError::call_test_function(&value.span).into());
@@ -206,6 +246,15 @@ impl<'a> FromAst<'a, leo_ast::CallExpression> for CallExpression<'a> {
     }
 }
 
+/// Returns `true` if the first parameter of `function` has the type of `circuit`, so the function can be called
+/// on an instance of the circuit.
+fn takes_instance<'a>(function: &Function<'a>, circuit: &Circuit<'a>) -> bool {
+    match function.arguments.values().next() {
+        Some(argument) => matches!(&argument.get().borrow().type_, Type::Circuit(type_) if *type_ == circuit),
+        None => false,
+    }
+}
+
 impl<'a> Into<leo_ast::CallExpression> for &CallExpression<'a> {
     fn into(self) -> leo_ast::CallExpression {
         let target_function = if let Some(target) = self.target.get() {
@@ -26,6 +26,8 @@ pub struct CastExpression<'a> {
     pub span: Option<Span>,
     pub inner: Cell<&'a Expression<'a>>,
     pub target_type: Type<'a>,
+    /// Inserted by type checking rather than written in the source, like the casts of integer widening.
+    pub implicit: bool,
 }
 
 impl<'a> Node for CastExpression<'a> {
@@ -91,6 +93,7 @@ impl<'a> FromAst<'a, leo_ast::CastExpression> for CastExpression<'a> {
             span: Some(value.span.clone()),
             inner: Cell::new(inner),
             target_type,
+            implicit: false,
         })
     }
 }
@@ -15,10 +15,11 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    Circuit, CircuitMember, ConstValue, Expression, ExpressionNode, FromAst, Identifier, Node, PartialType, Scope, Type,
+    Alias, Circuit, CircuitMember, ConstValue, Expression, ExpressionNode, FromAst, Identifier, InputFieldType, Node,
+    PartialType, Scope, Type,
 };
 
-use leo_errors::{AsgError, Result, Span};
+use leo_errors::{AsgError, LeoError, Result, Span};
 use std::cell::Cell;
 
 #[derive(Clone)]
@@ -52,15 +53,11 @@ impl<'a> ExpressionNode<'a> for CircuitAccessExpression<'a> {
     }
 
     fn get_type(&self) -> Option<Type<'a>> {
-        if self.target.get().is_none() {
-            None // function target only for static
-        } else {
-            let members = self.circuit.get().members.borrow();
-            let member = members.get(self.member.name.as_ref())?;
-            match member {
-                CircuitMember::Variable(type_) => Some(type_.clone()),
-                CircuitMember::Function(_) => None,
-            }
+        let members = self.circuit.get().members.borrow();
+        let member = members.get(self.member.name.as_ref())?;
+        match (member, self.target.get()) {
+            (CircuitMember::Variable(type_), Some(_)) | (CircuitMember::Static(type_, _), None) => Some(type_.clone()),
+            _ => None,
         }
     }
 
@@ -73,7 +70,11 @@ impl<'a> ExpressionNode<'a> for CircuitAccessExpression<'a> {
     }
 
     fn const_value(&self) -> Option<ConstValue<'a>> {
-        match self.target.get()?.const_value()? {
+        let target = match self.target.get() {
+            Some(target) => target,
+            None => return self.static_value()?.const_value(),
+        };
+        match target.const_value()? {
             ConstValue::Circuit(_, members) => {
                 let (_, const_value) = members.get(&self.member.name.to_string())?.clone();
                 Some(const_value)
@@ -87,6 +88,19 @@ impl<'a> ExpressionNode<'a> for CircuitAccessExpression<'a> {
     }
 }
 
+impl<'a> CircuitAccessExpression<'a> {
+    /// Returns the value of the accessed static member, if this is a static access to one.
+    pub fn static_value(&self) -> Option<&'a Expression<'a>> {
+        if self.target.get().is_some() {
+            return None;
+        }
+        match self.circuit.get().members.borrow().get(self.member.name.as_ref())? {
+            CircuitMember::Static(_, value) => value.get(),
+            _ => None,
+        }
+    }
+}
+
 impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessExpression<'a> {
     fn from_ast(
         scope: &'a Scope<'a>,
@@ -97,6 +111,9 @@ impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessEx
         let circuit = match target.get_type() {
             Some(Type::Circuit(circuit)) => circuit,
             x => {
+                if let Some(error) = shadowed_input_section(scope, value) {
+                    return Err(error.into());
+                }
                 return Err(AsgError::unexpected_type(
                     "circuit",
                     x.map(|x| x.to_string()).unwrap_or_else(|| "unknown".to_string()),
@@ -109,6 +126,14 @@ impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessEx
         // scoping refcell reference
         let found_member = {
             if let Some(member) = circuit.members.borrow().get(value.name.name.as_ref()) {
+                if let CircuitMember::Static(..) = member {
+                    return Err(AsgError::static_member_accessed_through_instance(
+                        &circuit.name.borrow().name,
+                        &value.name.name,
+                        &value.span,
+                    )
+                    .into());
+                }
                 if let Some(expected_type) = &expected_type {
                     if let CircuitMember::Variable(type_) = &member {
                         let type_: Type = type_.clone();
@@ -125,6 +150,40 @@ impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessEx
 
         if found_member {
             // skip
+        } else if circuit.is_input_state_circuit() {
+            // add a schema field to implicit input
+            let type_ = match circuit.input_field_type(scope, &value.name.name) {
+                Some(InputFieldType::Type(type_)) => type_,
+                Some(InputFieldType::Bytes) => match expected_type.clone().map(PartialType::full).flatten() {
+                    Some(type_) if InputFieldType::Bytes.accepts(&type_) => type_,
+                    Some(type_) => return Err(AsgError::unexpected_type("[u8; _]", type_, &value.span).into()),
+                    None => {
+                        return Err(AsgError::input_ref_needs_type(
+                            circuit.input_section_name(),
+                            &value.name.name,
+                            &value.span,
+                        )
+                        .into());
+                    }
+                },
+                None => {
+                    return Err(AsgError::unknown_input_field(
+                        circuit.input_section_name(),
+                        &value.name.name,
+                        &value.span,
+                    )
+                    .into());
+                }
+            };
+            if let Some(expected_type) = &expected_type {
+                if !expected_type.matches(&type_) {
+                    return Err(AsgError::unexpected_type(expected_type, type_, &value.span).into());
+                }
+            }
+            circuit
+                .members
+                .borrow_mut()
+                .insert(value.name.name.to_string(), CircuitMember::Variable(type_));
         } else if circuit.is_input_pseudo_circuit() {
             // add new member to implicit input
             if let Some(expected_type) = expected_type.map(PartialType::full).flatten() {
@@ -137,6 +196,8 @@ impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessEx
                     AsgError::input_ref_needs_type(&circuit.name.borrow().name, &value.name.name, &value.span).into(),
                 );
             }
+        } else if let Some(error) = shadowed_input_section(scope, value) {
+            return Err(error.into());
         } else {
             return Err(AsgError::unresolved_circuit_member(
                 &circuit.name.borrow().name,
@@ -156,34 +217,59 @@ impl<'a> FromAst<'a, leo_ast::CircuitMemberAccessExpression> for CircuitAccessEx
     }
 }
 
+/// Returns the error for an access like `input.registers` on a function's own variable named `input`,
+/// which hides the program input.
+fn shadowed_input_section(scope: &Scope, value: &leo_ast::CircuitMemberAccessExpression) -> Option<AsgError> {
+    match value.circuit.without_parentheses() {
+        leo_ast::Expression::Identifier(identifier) if identifier.name.as_ref() == "input" => (),
+        _ => return None,
+    }
+    if !matches!(
+        value.name.name.as_ref(),
+        "registers" | "record" | "state" | "state_leaf"
+    ) {
+        return None;
+    }
+    let variable = scope.resolve_variable("input")?;
+    let binding = variable.borrow().name.span.clone();
+    Some(AsgError::program_input_shadowed(&value.span).with_label(&binding, "`input` is declared here"))
+}
+
+/// The error for a static access to a member the circuit does not have, naming the alias the access went through.
+pub(crate) fn static_member_unresolved(circuit: &Circuit, alias: Option<&Alias>, name: &str, span: &Span) -> LeoError {
+    let circuit_name = &circuit.name.borrow().name;
+    match alias {
+        Some(alias) => AsgError::aliased_circuit_member_unresolved(&alias.name.borrow().name, circuit_name, name, span),
+        None => AsgError::unresolved_circuit_member(circuit_name, name, span),
+    }
+    .into()
+}
+
 impl<'a> FromAst<'a, leo_ast::CircuitStaticFunctionAccessExpression> for CircuitAccessExpression<'a> {
     fn from_ast(
         scope: &Scope<'a>,
         value: &leo_ast::CircuitStaticFunctionAccessExpression,
-        expected_type: Option<PartialType>,
+        expected_type: Option<PartialType<'a>>,
     ) -> Result<CircuitAccessExpression<'a>> {
-        let circuit = match &*value.circuit {
-            leo_ast::Expression::Identifier(name) => scope
-                .resolve_circuit(&name.name)
-                .ok_or_else(|| AsgError::unresolved_circuit(&name.name, &name.span))?,
+        let (circuit, alias) = match &*value.circuit {
+            leo_ast::Expression::Identifier(name) => scope.resolve_static_circuit(name)?,
             _ => {
                 return Err(AsgError::unexpected_type("circuit", "unknown", &value.span).into());
             }
         };
 
-        if let Some(expected_type) = expected_type {
+        if let Some(CircuitMember::Static(type_, _)) = circuit.members.borrow().get(value.name.name.as_ref()) {
+            if let Some(expected_type) = &expected_type {
+                if !expected_type.matches(type_) {
+                    return Err(AsgError::unexpected_type(expected_type, type_, &value.span).into());
+                }
+            }
+        } else if let Some(expected_type) = expected_type {
             return Err(AsgError::unexpected_type("none", expected_type, &value.span).into());
-        }
-
-        if let Some(CircuitMember::Function(_)) = circuit.members.borrow().get(value.name.name.as_ref()) {
+        } else if let Some(CircuitMember::Function(_)) = circuit.members.borrow().get(value.name.name.as_ref()) {
             // okay
         } else {
-            return Err(AsgError::unresolved_circuit_member(
-                &circuit.name.borrow().name,
-                &value.name.name,
-                &value.span,
-            )
-            .into());
+            return Err(static_member_unresolved(circuit, alias, &value.name.name, &value.span));
         }
 
         Ok(CircuitAccessExpression {
@@ -20,7 +20,7 @@ use crate::{
 
 use leo_errors::{AsgError, Result, Span};
 
-use indexmap::{IndexMap, IndexSet};
+use indexmap::IndexMap;
 use std::cell::Cell;
 
 #[derive(Clone)]
@@ -84,9 +84,18 @@ impl<'a> FromAst<'a, leo_ast::CircuitInitExpression> for CircuitInitExpression<'
         value: &leo_ast::CircuitInitExpression,
         expected_type: Option<PartialType<'a>>,
     ) -> Result<CircuitInitExpression<'a>> {
-        let circuit = scope
-            .resolve_circuit(&value.name.name)
-            .ok_or_else(|| AsgError::unresolved_circuit(&value.name.name, &value.name.span))?;
+        let circuit = match scope.resolve_circuit(&value.name.name) {
+            Some(circuit) => circuit,
+            None => {
+                return Err(match scope.suggest_circuit(&value.name.name) {
+                    Some(suggestion) => {
+                        AsgError::unresolved_circuit_with_suggestion(&value.name.name, suggestion, &value.name.span)
+                    }
+                    None => AsgError::unresolved_circuit(&value.name.name, &value.name.span),
+                }
+                .into());
+            }
+        };
         match expected_type {
             Some(PartialType::Type(Type::Circuit(expected_circuit))) if expected_circuit == circuit => (),
             None => (),
@@ -94,24 +103,35 @@ impl<'a> FromAst<'a, leo_ast::CircuitInitExpression> for CircuitInitExpression<'
                 return Err(AsgError::unexpected_type(x, circuit.name.borrow().name.to_string(), &value.span).into());
             }
         }
-        let members: IndexMap<&str, (&Identifier, Option<&leo_ast::Expression>)> = value
-            .members
-            .iter()
-            .map(|x| (x.identifier.name.as_ref(), (&x.identifier, x.expression.as_ref())))
-            .collect();
+
+        let circuit_name = circuit.name.borrow().name.clone();
+        let definition = circuit
+            .span
+            .as_ref()
+            .map(|span| format!("{}:{}:{}", span.path, span.line_start, span.col_start))
+            .unwrap_or_else(|| "an unknown location".to_string());
+
+        // Each member may only be initialized once.
+        let mut members: IndexMap<&str, (&Identifier, Option<&leo_ast::Expression>)> = IndexMap::new();
+        for member in value.members.iter() {
+            let name = member.identifier.name.as_ref();
+            if members.contains_key(name) {
+                return Err(AsgError::overridden_circuit_member(
+                    &circuit_name,
+                    name,
+                    &definition,
+                    &member.identifier.span,
+                )
+                .into());
+            }
+            members.insert(name, (&member.identifier, member.expression.as_ref()));
+        }
 
         let mut values: Vec<(Identifier, Cell<&'a Expression<'a>>)> = vec![];
-        let mut defined_variables = IndexSet::<String>::new();
 
         {
             let circuit_members = circuit.members.borrow();
             for (name, member) in circuit_members.iter() {
-                if defined_variables.contains(name) {
-                    return Err(
-                        AsgError::overridden_circuit_member(&circuit.name.borrow().name, name, &value.span).into(),
-                    );
-                }
-                defined_variables.insert(name.clone());
                 let type_: Type = if let CircuitMember::Variable(type_) = &member {
                     type_.clone()
                 } else {
@@ -129,16 +149,15 @@ impl<'a> FromAst<'a, leo_ast::CircuitInitExpression> for CircuitInitExpression<'
                     };
                     values.push(((*identifier).clone(), Cell::new(received)));
                 } else {
-                    return Err(
-                        AsgError::missing_circuit_member(&circuit.name.borrow().name, name, &value.span).into(),
-                    );
+                    return Err(AsgError::missing_circuit_member(&circuit_name, name, &definition, &value.span).into());
                 }
             }
 
+            // Only data members can be initialized, functions are not values of the circuit.
             for (name, (identifier, _expression)) in members.iter() {
-                if circuit_members.get(*name).is_none() {
+                if !matches!(circuit_members.get(*name), Some(CircuitMember::Variable(_))) {
                     return Err(
-                        AsgError::extra_circuit_member(&circuit.name.borrow().name, name, &identifier.span).into(),
+                        AsgError::extra_circuit_member(&circuit_name, name, &definition, &identifier.span).into(),
                     );
                 }
             }
@@ -15,10 +15,12 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    CharValue, ConstInt, ConstValue, Expression, ExpressionNode, FromAst, GroupValue, Node, PartialType, Scope, Type,
+    field_modulus, reduce_field, CharValue, ConstInt, ConstValue, Expression, ExpressionNode, FromAst, GroupValue,
+    Node, PartialType, Scope, Type, FIELD_MODULUS,
 };
 
 use leo_errors::{AsgError, Result, Span};
+use num_bigint::BigInt;
 
 use std::cell::Cell;
 
@@ -65,7 +67,7 @@ impl<'a> ExpressionNode<'a> for Constant<'a> {
 
 impl<'a> FromAst<'a, leo_ast::ValueExpression> for Constant<'a> {
     fn from_ast(
-        _scope: &'a Scope<'a>,
+        scope: &'a Scope<'a>,
         value: &leo_ast::ValueExpression,
         expected_type: Option<PartialType<'a>>,
     ) -> Result<Constant<'a>> {
@@ -125,7 +127,7 @@ impl<'a> FromAst<'a, leo_ast::ValueExpression> for Constant<'a> {
                 Constant {
                     parent: Cell::new(None),
                     span: Some(span.clone()),
-                    value: ConstValue::Field(value.parse().map_err(|_| AsgError::invalid_int(value, span))?),
+                    value: ConstValue::Field(parse_field(scope, value, span)?),
                 }
             }
             Group(value) => {
@@ -147,7 +149,7 @@ impl<'a> FromAst<'a, leo_ast::ValueExpression> for Constant<'a> {
                 }
             }
             Implicit(value, span) => match expected_type {
-                None => return Err(AsgError::unresolved_type("unknown", span).into()),
+                None => return Err(AsgError::ambiguous_literal_type(value, span).into()),
                 Some(PartialType::Integer(Some(sub_type), _)) | Some(PartialType::Integer(None, Some(sub_type))) => {
                     Constant {
                         parent: Cell::new(None),
@@ -158,7 +160,7 @@ impl<'a> FromAst<'a, leo_ast::ValueExpression> for Constant<'a> {
                 Some(PartialType::Type(Type::Field)) => Constant {
                     parent: Cell::new(None),
                     span: Some(span.clone()),
-                    value: ConstValue::Field(value.parse().map_err(|_| AsgError::invalid_int(value, span))?),
+                    value: ConstValue::Field(parse_field(scope, value, span)?),
                 },
                 Some(PartialType::Type(Type::Group)) => Constant {
                     parent: Cell::new(None),
@@ -196,6 +198,49 @@ impl<'a> FromAst<'a, leo_ast::ValueExpression> for Constant<'a> {
     }
 }
 
+impl<'a> Constant<'a> {
+    /// Writes a value nested in this constant as an expression at the span of this constant.
+    fn element(&self, value: &ConstValue<'a>) -> leo_ast::Expression {
+        let element = Constant {
+            parent: Cell::new(None),
+            span: self.span.clone(),
+            value: value.clone(),
+        };
+        (&element).into()
+    }
+}
+
+impl<'a> Into<leo_ast::Expression> for &Constant<'a> {
+    fn into(self) -> leo_ast::Expression {
+        match &self.value {
+            // Compound values have no literal form, so they are written as the expressions building them.
+            ConstValue::Array(values) => leo_ast::Expression::ArrayInline(leo_ast::ArrayInlineExpression {
+                elements: values
+                    .iter()
+                    .map(|value| leo_ast::SpreadOrExpression::Expression(self.element(value)))
+                    .collect(),
+                span: self.span.clone().unwrap_or_default(),
+            }),
+            ConstValue::Tuple(values) => leo_ast::Expression::TupleInit(leo_ast::TupleInitExpression {
+                elements: values.iter().map(|value| self.element(value)).collect(),
+                span: self.span.clone().unwrap_or_default(),
+            }),
+            ConstValue::Circuit(circuit, members) => leo_ast::Expression::CircuitInit(leo_ast::CircuitInitExpression {
+                name: circuit.name.borrow().clone(),
+                members: members
+                    .values()
+                    .map(|(identifier, value)| leo_ast::CircuitImpliedVariableDefinition {
+                        identifier: identifier.clone(),
+                        expression: Some(self.element(value)),
+                    })
+                    .collect(),
+                span: self.span.clone().unwrap_or_default(),
+            }),
+            _ => leo_ast::Expression::Value(self.into()),
+        }
+    }
+}
+
 impl<'a> Into<leo_ast::ValueExpression> for &Constant<'a> {
     fn into(self) -> leo_ast::ValueExpression {
         match &self.value {
@@ -239,3 +284,26 @@ impl<'a> Into<leo_ast::ValueExpression> for &Constant<'a> {
         }
     }
 }
+
+/// Parses a field literal into its canonical representative, reducing it modulo the field prime.
+///
+/// Literals at or above the modulus are an error under strict field literals, and a warning otherwise.
+fn parse_field(scope: &Scope, value: &str, span: &Span) -> Result<BigInt> {
+    let parsed: BigInt = value.parse().map_err(|_| AsgError::invalid_int(value, span))?;
+    let reduced = reduce_field(&parsed);
+
+    if parsed >= field_modulus() {
+        if scope.context.strict_field_literals.get() {
+            return Err(AsgError::field_literal_out_of_range(value, FIELD_MODULUS, span).into());
+        }
+        scope.context.warn(
+            format!(
+                "field literal `{}` is not less than the field modulus and was reduced to `{}`",
+                value, reduced
+            ),
+            span,
+        );
+    }
+
+    Ok(reduced)
+}
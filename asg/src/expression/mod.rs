@@ -68,8 +68,14 @@ pub use cast::*;
 mod lengthof;
 pub use lengthof::*;
 
+mod bits;
+pub use bits::*;
+
 use crate::{ConstValue, FromAst, Node, PartialType, Scope, Type};
-use leo_errors::{Result, Span};
+use leo_ast::ensure_stack;
+use leo_errors::{AsgError, Result, Span};
+
+use std::cell::Cell;
 
 #[derive(Clone)]
 pub enum Expression<'a> {
@@ -80,6 +86,7 @@ pub enum Expression<'a> {
     Ternary(TernaryExpression<'a>),
     Cast(CastExpression<'a>),
     LengthOf(LengthOfExpression<'a>),
+    Bits(BitsExpression<'a>),
 
     ArrayInline(ArrayInlineExpression<'a>),
     ArrayInit(ArrayInitExpression<'a>),
@@ -112,6 +119,7 @@ impl<'a> Node for Expression<'a> {
             Ternary(x) => x.span(),
             Cast(x) => x.span(),
             LengthOf(x) => x.span(),
+            Bits(x) => x.span(),
             ArrayInline(x) => x.span(),
             ArrayInit(x) => x.span(),
             ArrayAccess(x) => x.span(),
@@ -147,6 +155,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.set_parent(parent),
             Cast(x) => x.set_parent(parent),
             LengthOf(x) => x.set_parent(parent),
+            Bits(x) => x.set_parent(parent),
             ArrayInline(x) => x.set_parent(parent),
             ArrayInit(x) => x.set_parent(parent),
             ArrayAccess(x) => x.set_parent(parent),
@@ -169,6 +178,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.get_parent(),
             Cast(x) => x.get_parent(),
             LengthOf(x) => x.get_parent(),
+            Bits(x) => x.get_parent(),
             ArrayInline(x) => x.get_parent(),
             ArrayInit(x) => x.get_parent(),
             ArrayAccess(x) => x.get_parent(),
@@ -191,6 +201,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.enforce_parents(expr),
             Cast(x) => x.enforce_parents(expr),
             LengthOf(x) => x.enforce_parents(expr),
+            Bits(x) => x.enforce_parents(expr),
             ArrayInline(x) => x.enforce_parents(expr),
             ArrayInit(x) => x.enforce_parents(expr),
             ArrayAccess(x) => x.enforce_parents(expr),
@@ -205,7 +216,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
 
     fn get_type(&'a self) -> Option<Type<'a>> {
         use Expression::*;
-        match self {
+        ensure_stack(|| match self {
             VariableRef(x) => x.get_type(),
             Constant(x) => x.get_type(),
             Binary(x) => x.get_type(),
@@ -213,6 +224,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.get_type(),
             Cast(x) => x.get_type(),
             LengthOf(x) => x.get_type(),
+            Bits(x) => x.get_type(),
             ArrayInline(x) => x.get_type(),
             ArrayInit(x) => x.get_type(),
             ArrayAccess(x) => x.get_type(),
@@ -222,7 +234,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             CircuitInit(x) => x.get_type(),
             CircuitAccess(x) => x.get_type(),
             Call(x) => x.get_type(),
-        }
+        })
     }
 
     fn is_mut_ref(&self) -> bool {
@@ -235,6 +247,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.is_mut_ref(),
             Cast(x) => x.is_mut_ref(),
             LengthOf(x) => x.is_mut_ref(),
+            Bits(x) => x.is_mut_ref(),
             ArrayInline(x) => x.is_mut_ref(),
             ArrayInit(x) => x.is_mut_ref(),
             ArrayAccess(x) => x.is_mut_ref(),
@@ -249,7 +262,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
 
     fn const_value(&'a self) -> Option<ConstValue<'a>> {
         use Expression::*;
-        match self {
+        ensure_stack(|| match self {
             VariableRef(x) => x.const_value(),
             Constant(x) => x.const_value(),
             Binary(x) => x.const_value(),
@@ -257,6 +270,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.const_value(),
             Cast(x) => x.const_value(),
             LengthOf(x) => x.const_value(),
+            Bits(x) => x.const_value(),
             ArrayInline(x) => x.const_value(),
             ArrayInit(x) => x.const_value(),
             ArrayAccess(x) => x.const_value(),
@@ -266,12 +280,12 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             CircuitInit(x) => x.const_value(),
             CircuitAccess(x) => x.const_value(),
             Call(x) => x.const_value(),
-        }
+        })
     }
 
     fn is_consty(&self) -> bool {
         use Expression::*;
-        match self {
+        ensure_stack(|| match self {
             VariableRef(x) => x.is_consty(),
             Constant(x) => x.is_consty(),
             Binary(x) => x.is_consty(),
@@ -279,6 +293,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             Ternary(x) => x.is_consty(),
             Cast(x) => x.is_consty(),
             LengthOf(x) => x.is_consty(),
+            Bits(x) => x.is_consty(),
             ArrayInline(x) => x.is_consty(),
             ArrayInit(x) => x.is_consty(),
             ArrayAccess(x) => x.is_consty(),
@@ -288,7 +303,7 @@ impl<'a> ExpressionNode<'a> for Expression<'a> {
             CircuitInit(x) => x.is_consty(),
             CircuitAccess(x) => x.is_consty(),
             Call(x) => x.is_consty(),
-        }
+        })
     }
 }
 
@@ -298,66 +313,100 @@ impl<'a> FromAst<'a, leo_ast::Expression> for &'a Expression<'a> {
         value: &leo_ast::Expression,
         expected_type: Option<PartialType<'a>>,
     ) -> Result<Self> {
-        use leo_ast::Expression::*;
-        let expression = match value {
-            Identifier(identifier) => Self::from_ast(scope, identifier, expected_type)?,
-            Value(value) => scope
-                .context
-                .alloc_expression(Constant::from_ast(scope, value, expected_type).map(Expression::Constant)?),
-            Binary(binary) => scope
-                .context
-                .alloc_expression(BinaryExpression::from_ast(scope, binary, expected_type).map(Expression::Binary)?),
-            Unary(unary) => scope
-                .context
-                .alloc_expression(UnaryExpression::from_ast(scope, unary, expected_type).map(Expression::Unary)?),
-            Ternary(conditional) => scope.context.alloc_expression(
-                TernaryExpression::from_ast(scope, conditional, expected_type).map(Expression::Ternary)?,
-            ),
-            Cast(cast) => scope
-                .context
-                .alloc_expression(CastExpression::from_ast(scope, cast, expected_type).map(Expression::Cast)?),
-
-            LengthOf(lengthof) => scope.context.alloc_expression(
-                LengthOfExpression::from_ast(scope, lengthof, expected_type).map(Expression::LengthOf)?,
-            ),
-
-            ArrayInline(array_inline) => scope.context.alloc_expression(
-                ArrayInlineExpression::from_ast(scope, array_inline, expected_type).map(Expression::ArrayInline)?,
-            ),
-            ArrayInit(array_init) => scope.context.alloc_expression(
-                ArrayInitExpression::from_ast(scope, array_init, expected_type).map(Expression::ArrayInit)?,
-            ),
-            ArrayAccess(array_access) => scope.context.alloc_expression(
-                ArrayAccessExpression::from_ast(scope, array_access, expected_type).map(Expression::ArrayAccess)?,
-            ),
-            ArrayRangeAccess(array_range_access) => scope.context.alloc_expression(
-                ArrayRangeAccessExpression::from_ast(scope, array_range_access, expected_type)
-                    .map(Expression::ArrayRangeAccess)?,
-            ),
-
-            TupleInit(tuple_init) => scope.context.alloc_expression(
-                TupleInitExpression::from_ast(scope, tuple_init, expected_type).map(Expression::TupleInit)?,
-            ),
-            TupleAccess(tuple_access) => scope.context.alloc_expression(
-                TupleAccessExpression::from_ast(scope, tuple_access, expected_type).map(Expression::TupleAccess)?,
-            ),
-
-            CircuitInit(circuit_init) => scope.context.alloc_expression(
-                CircuitInitExpression::from_ast(scope, circuit_init, expected_type).map(Expression::CircuitInit)?,
-            ),
-            CircuitMemberAccess(circuit_member) => scope.context.alloc_expression(
-                CircuitAccessExpression::from_ast(scope, circuit_member, expected_type)
-                    .map(Expression::CircuitAccess)?,
-            ),
-            CircuitStaticFunctionAccess(circuit_member) => scope.context.alloc_expression(
-                CircuitAccessExpression::from_ast(scope, circuit_member, expected_type)
-                    .map(Expression::CircuitAccess)?,
-            ),
-
-            Call(call) => scope
-                .context
-                .alloc_expression(CallExpression::from_ast(scope, call, expected_type).map(Expression::Call)?),
+        // `Err` is left out, so that it still names the variant of `Result`.
+        use leo_ast::Expression::{
+            ArrayAccess, ArrayComprehension, ArrayInit, ArrayInline, ArrayRangeAccess, Binary, Call, Cast, CircuitInit,
+            CircuitMemberAccess, CircuitStaticFunctionAccess, Identifier, LengthOf, Parenthesized, Ternary,
+            TupleAccess, TupleInit, Unary, Value,
         };
+        let expression = ensure_stack(|| -> Result<Self> {
+            Ok(match value {
+                Identifier(identifier) => Self::from_ast(scope, identifier, expected_type)?,
+                Value(value) => scope
+                    .context
+                    .alloc_expression(Constant::from_ast(scope, value, expected_type).map(Expression::Constant)?),
+                // Parentheses only group, so the asg has the expression inside them.
+                Parenthesized(parenthesized) => Self::from_ast(scope, &*parenthesized.inner, expected_type)?,
+                Binary(binary) => {
+                    let binary = BinaryExpression::from_ast(scope, binary, expected_type)?;
+                    let expression = match binary.string_value() {
+                        Some(value) => Expression::Constant(Constant {
+                            parent: Cell::new(None),
+                            span: binary.span.clone(),
+                            value,
+                        }),
+                        None => Expression::Binary(binary),
+                    };
+                    scope.context.alloc_expression(expression)
+                }
+                Unary(unary) => scope
+                    .context
+                    .alloc_expression(UnaryExpression::from_ast(scope, unary, expected_type).map(Expression::Unary)?),
+                Ternary(conditional) => scope.context.alloc_expression(
+                    TernaryExpression::from_ast(scope, conditional, expected_type).map(Expression::Ternary)?,
+                ),
+                Cast(cast) => scope
+                    .context
+                    .alloc_expression(CastExpression::from_ast(scope, cast, expected_type).map(Expression::Cast)?),
+
+                LengthOf(lengthof) => scope.context.alloc_expression(
+                    LengthOfExpression::from_ast(scope, lengthof, expected_type).map(Expression::LengthOf)?,
+                ),
+
+                ArrayInline(array_inline) => scope.context.alloc_expression(
+                    ArrayInlineExpression::from_ast(scope, array_inline, expected_type).map(Expression::ArrayInline)?,
+                ),
+                ArrayInit(array_init) => scope.context.alloc_expression(
+                    ArrayInitExpression::from_ast(scope, array_init, expected_type).map(Expression::ArrayInit)?,
+                ),
+                ArrayComprehension(array_comprehension) => {
+                    return Err(AsgError::illegal_ast_structure(
+                        "array comprehensions must be lowered during canonicalization",
+                        &array_comprehension.span,
+                    )
+                    .into());
+                }
+                ArrayAccess(array_access) => scope.context.alloc_expression(
+                    ArrayAccessExpression::from_ast(scope, array_access, expected_type).map(Expression::ArrayAccess)?,
+                ),
+                ArrayRangeAccess(array_range_access) => scope.context.alloc_expression(
+                    ArrayRangeAccessExpression::from_ast(scope, array_range_access, expected_type)
+                        .map(Expression::ArrayRangeAccess)?,
+                ),
+
+                TupleInit(tuple_init) => scope.context.alloc_expression(
+                    TupleInitExpression::from_ast(scope, tuple_init, expected_type).map(Expression::TupleInit)?,
+                ),
+                TupleAccess(tuple_access) => scope.context.alloc_expression(
+                    TupleAccessExpression::from_ast(scope, tuple_access, expected_type).map(Expression::TupleAccess)?,
+                ),
+
+                CircuitInit(circuit_init) => scope.context.alloc_expression(
+                    CircuitInitExpression::from_ast(scope, circuit_init, expected_type)
+                        .map(Expression::CircuitInit)?,
+                ),
+                CircuitMemberAccess(circuit_member) => scope.context.alloc_expression(
+                    CircuitAccessExpression::from_ast(scope, circuit_member, expected_type)
+                        .map(Expression::CircuitAccess)?,
+                ),
+                CircuitStaticFunctionAccess(circuit_member) => scope.context.alloc_expression(
+                    CircuitAccessExpression::from_ast(scope, circuit_member, expected_type)
+                        .map(Expression::CircuitAccess)?,
+                ),
+
+                Call(call) => match BitsDirection::resolve(scope, call) {
+                    Some(direction) => scope.context.alloc_expression(
+                        BitsExpression::from_ast(scope, call, direction, expected_type).map(Expression::Bits)?,
+                    ),
+                    None => scope.context.alloc_expression(
+                        CallExpression::from_ast(scope, call, expected_type).map(Expression::Call)?,
+                    ),
+                },
+                leo_ast::Expression::Err(err) => {
+                    return Err(AsgError::unresolved_placeholder(&err.span).into());
+                }
+            })
+        })?;
         expression.enforce_parents(expression);
         Ok(expression)
     }
@@ -366,14 +415,15 @@ impl<'a> FromAst<'a, leo_ast::Expression> for &'a Expression<'a> {
 impl<'a> Into<leo_ast::Expression> for &Expression<'a> {
     fn into(self) -> leo_ast::Expression {
         use Expression::*;
-        match self {
+        ensure_stack(|| match self {
             VariableRef(x) => leo_ast::Expression::Identifier(x.into()),
-            Constant(x) => leo_ast::Expression::Value(x.into()),
+            Constant(x) => x.into(),
             Binary(x) => leo_ast::Expression::Binary(x.into()),
             Unary(x) => leo_ast::Expression::Unary(x.into()),
             Ternary(x) => leo_ast::Expression::Ternary(x.into()),
             Cast(x) => leo_ast::Expression::Cast(x.into()),
             LengthOf(x) => leo_ast::Expression::LengthOf(x.into()),
+            Bits(x) => leo_ast::Expression::Call(x.into()),
             ArrayInline(x) => leo_ast::Expression::ArrayInline(x.into()),
             ArrayInit(x) => leo_ast::Expression::ArrayInit(x.into()),
             ArrayAccess(x) => leo_ast::Expression::ArrayAccess(x.into()),
@@ -383,6 +433,6 @@ impl<'a> Into<leo_ast::Expression> for &Expression<'a> {
             CircuitInit(x) => leo_ast::Expression::CircuitInit(x.into()),
             CircuitAccess(x) => x.into(),
             Call(x) => leo_ast::Expression::Call(x.into()),
-        }
+        })
     }
 }
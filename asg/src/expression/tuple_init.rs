@@ -121,6 +121,13 @@ impl<'a> FromAst<'a, leo_ast::TupleInitExpression> for TupleInitExpression<'a> {
                 .map(Cell::new)
             })
             .collect::<Result<Vec<_>>>()?;
+        if let Some(element) = elements
+            .iter()
+            .find(|element| matches!(element.get().get_type(), Some(type_) if type_.is_unit()))
+        {
+            let span = element.get().span().cloned().unwrap_or_else(|| value.span.clone());
+            return Err(AsgError::unit_in_tuple(&span).into());
+        }
 
         Ok(TupleInitExpression {
             parent: Cell::new(None),
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConstValue, Expression, ExpressionNode, FromAst, Node, PartialType, Scope, Type};
+use crate::{
+    negate_group, reduce_field, ConstValue, Expression, ExpressionNode, FromAst, GroupValue, Node, PartialType, Scope,
+    Type,
+};
 pub use leo_ast::UnaryOperation;
 use leo_errors::{AsgError, Result, Span};
 
@@ -28,6 +31,34 @@ pub struct UnaryExpression<'a> {
     pub inner: Cell<&'a Expression<'a>>,
 }
 
+impl<'a> UnaryExpression<'a> {
+    ///
+    /// Applies the operation to a constant operand.
+    ///
+    /// Returns `None` if the operation is not defined for the operand or overflows.
+    ///
+    pub fn evaluate<'b>(operation: &UnaryOperation, inner: ConstValue<'b>) -> Option<ConstValue<'b>> {
+        match operation {
+            UnaryOperation::Not => match inner {
+                ConstValue::Boolean(value) => Some(ConstValue::Boolean(!value)),
+                _ => None,
+            },
+            UnaryOperation::Negate => match inner {
+                ConstValue::Int(value) => Some(ConstValue::Int(value.value_negate()?)),
+                ConstValue::Group(value) => Some(ConstValue::Group(GroupValue::from_point(negate_group(
+                    &value.to_point()?,
+                )))),
+                ConstValue::Field(value) => Some(ConstValue::Field(reduce_field(&-value))),
+                _ => None,
+            },
+            UnaryOperation::BitNot => match inner {
+                ConstValue::Int(value) => Some(ConstValue::Int(value.value_bit_negate()?)),
+                _ => None,
+            },
+        }
+    }
+}
+
 impl<'a> Node for UnaryExpression<'a> {
     fn span(&self) -> Option<&Span> {
         self.span.as_ref()
@@ -55,29 +86,8 @@ impl<'a> ExpressionNode<'a> for UnaryExpression<'a> {
         false
     }
 
-    fn const_value(&self) -> Option<ConstValue> {
-        if let Some(inner) = self.inner.get().const_value() {
-            match self.operation {
-                UnaryOperation::Not => match inner {
-                    ConstValue::Boolean(value) => Some(ConstValue::Boolean(!value)),
-                    _ => None,
-                },
-                UnaryOperation::Negate => {
-                    match inner {
-                        ConstValue::Int(value) => Some(ConstValue::Int(value.value_negate()?)),
-                        // ConstValue::Group(value) => Some(ConstValue::Group(value)), TODO: groups
-                        // ConstValue::Field(value) => Some(ConstValue::Field(-value)),
-                        _ => None,
-                    }
-                }
-                UnaryOperation::BitNot => match inner {
-                    ConstValue::Int(value) => Some(ConstValue::Int(value.value_bit_negate()?)),
-                    _ => None,
-                },
-            }
-        } else {
-            None
-        }
+    fn const_value(&'a self) -> Option<ConstValue<'a>> {
+        Self::evaluate(&self.operation, self.inner.get().const_value()?)
     }
 
     fn is_consty(&self) -> bool {
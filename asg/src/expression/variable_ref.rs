@@ -127,11 +127,7 @@ impl<'a> FromAst<'a, leo_ast::Identifier> for &'a Expression<'a> {
         expected_type: Option<PartialType<'a>>,
     ) -> Result<&'a Expression<'a>> {
         let variable = if value.name.as_ref() == "input" {
-            if let Some(input) = scope.resolve_input() {
-                input.container
-            } else {
-                return Err(AsgError::illegal_input_variable_reference(&value.span).into());
-            }
+            scope.resolve_input_variable(&value.span)?
         } else if let Some(gc) = scope.resolve_global_const(&value.name) {
             gc.variables
                 .iter()
@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Circuit, CircuitMember, Identifier, Scope, Type, Variable};
-use leo_errors::Span;
+use crate::{Circuit, CircuitMember, Identifier, IntegerType, Scope, Type, Variable};
+use leo_errors::{AsgError, Result, Span};
 
 use indexmap::IndexMap;
 use std::cell::RefCell;
@@ -37,12 +37,73 @@ pub const RECORD_PSEUDO_CIRCUIT: &str = "$InputRecord";
 pub const STATE_PSEUDO_CIRCUIT: &str = "$InputState";
 pub const STATE_LEAF_PSEUDO_CIRCUIT: &str = "$InputStateLeaf";
 
+/// The circuit whose members declare the custom data fields of the `[record]` input section.
+pub const RECORD_DATA_CIRCUIT: &str = "Record";
+
+/// The type of a field of the `[record]`, `[state]` or `[state_leaf]` input sections.
+#[derive(Clone, PartialEq)]
+pub enum InputFieldType<'a> {
+    /// A value of the given type.
+    Type(Type<'a>),
+    /// A `u8` array, whose length is given by the input file.
+    Bytes,
+}
+
+impl<'a> InputFieldType<'a> {
+    /// Returns `true` if a value of the given type can be passed for this field.
+    pub fn accepts(&self, type_: &Type<'a>) -> bool {
+        match self {
+            InputFieldType::Type(expected) => expected.is_assignable_from(type_),
+            InputFieldType::Bytes => {
+                matches!(type_, Type::Array(element, _) if **element == Type::Integer(IntegerType::U8))
+            }
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for InputFieldType<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputFieldType::Type(type_) => type_.fmt(f),
+            InputFieldType::Bytes => write!(f, "[u8; _]"),
+        }
+    }
+}
+
+/// Returns the type of a built-in field of an input section, given the name of its pseudo circuit.
+pub fn builtin_input_field<'a>(section: &str, field: &str) -> Option<InputFieldType<'a>> {
+    let type_ = match (section, field) {
+        (RECORD_PSEUDO_CIRCUIT, "owner") => Type::Address,
+        (RECORD_PSEUDO_CIRCUIT, "gates") | (RECORD_PSEUDO_CIRCUIT, "value") => Type::Integer(IntegerType::U64),
+        (RECORD_PSEUDO_CIRCUIT, "is_dummy") => Type::Boolean,
+        (RECORD_PSEUDO_CIRCUIT, "serial_number")
+        | (RECORD_PSEUDO_CIRCUIT, "commitment")
+        | (RECORD_PSEUDO_CIRCUIT, "payload")
+        | (RECORD_PSEUDO_CIRCUIT, "birth_program_id")
+        | (RECORD_PSEUDO_CIRCUIT, "death_program_id")
+        | (RECORD_PSEUDO_CIRCUIT, "serial_number_nonce")
+        | (RECORD_PSEUDO_CIRCUIT, "commitment_randomness") => return Some(InputFieldType::Bytes),
+
+        (STATE_PSEUDO_CIRCUIT, "leaf_index") => Type::Integer(IntegerType::U32),
+        (STATE_PSEUDO_CIRCUIT, "root") => return Some(InputFieldType::Bytes),
+
+        (STATE_LEAF_PSEUDO_CIRCUIT, "network_id") => Type::Integer(IntegerType::U8),
+        (STATE_LEAF_PSEUDO_CIRCUIT, "path")
+        | (STATE_LEAF_PSEUDO_CIRCUIT, "memo")
+        | (STATE_LEAF_PSEUDO_CIRCUIT, "leaf_randomness") => return Some(InputFieldType::Bytes),
+
+        _ => return None,
+    };
+    Some(InputFieldType::Type(type_))
+}
+
 impl<'a> Input<'a> {
     fn make_header(scope: &'a Scope<'a>, name: &str) -> &'a Circuit<'a> {
         scope.context.alloc_circuit(Circuit {
             id: scope.context.get_id(),
             name: RefCell::new(Identifier::new(name.into())),
             members: RefCell::new(IndexMap::new()),
+            member_spans: IndexMap::new(),
             core_mapping: RefCell::new(None),
             scope,
             span: Some(Span::default()),
@@ -72,6 +133,7 @@ impl<'a> Input<'a> {
             id: scope.context.get_id(),
             name: RefCell::new(Identifier::new(CONTAINER_PSEUDO_CIRCUIT.into())),
             members: RefCell::new(container_members),
+            member_spans: IndexMap::new(),
             core_mapping: RefCell::new(None),
             scope: input_scope,
             span: Some(Span::default()),
@@ -104,4 +166,62 @@ impl<'a> Circuit<'a> {
             REGISTERS_PSEUDO_CIRCUIT | RECORD_PSEUDO_CIRCUIT | STATE_PSEUDO_CIRCUIT | STATE_LEAF_PSEUDO_CIRCUIT
         )
     }
+
+    /// Returns `true` for the `[record]`, `[state]` and `[state_leaf]` input sections, whose fields are fixed.
+    pub fn is_input_state_circuit(&self) -> bool {
+        matches!(
+            &*self.name.borrow().name,
+            RECORD_PSEUDO_CIRCUIT | STATE_PSEUDO_CIRCUIT | STATE_LEAF_PSEUDO_CIRCUIT
+        )
+    }
+
+    /// Returns the name of the input section this pseudo circuit stands for, as written in input files.
+    pub fn input_section_name(&self) -> &'static str {
+        match &*self.name.borrow().name {
+            REGISTERS_PSEUDO_CIRCUIT => "registers",
+            RECORD_PSEUDO_CIRCUIT => "record",
+            STATE_PSEUDO_CIRCUIT => "state",
+            STATE_LEAF_PSEUDO_CIRCUIT => "state_leaf",
+            _ => "main",
+        }
+    }
+
+    /// Returns the type of a field of an input state section: a built-in field, or for `[record]`,
+    /// a member of the program's `Record` circuit resolved from `scope`.
+    pub fn input_field_type(&self, scope: &Scope<'a>, field: &str) -> Option<InputFieldType<'a>> {
+        let section = self.name.borrow().name.clone();
+        if let Some(type_) = builtin_input_field(&section, field) {
+            return Some(type_);
+        }
+        if &*section != RECORD_PSEUDO_CIRCUIT {
+            return None;
+        }
+        let record = scope.resolve_circuit(RECORD_DATA_CIRCUIT)?;
+        let members = record.members.borrow();
+        match members.get(field)? {
+            CircuitMember::Variable(type_) => Some(InputFieldType::Type(type_.clone())),
+            CircuitMember::Static(..) | CircuitMember::Function(_) => None,
+        }
+    }
+
+    /// Checks that the members of the program's `Record` circuit agree with the built-in record fields.
+    pub fn check_record_data_fields(&self) -> Result<()> {
+        for (name, member) in self.members.borrow().iter() {
+            if let CircuitMember::Variable(type_) = member {
+                match builtin_input_field(RECORD_PSEUDO_CIRCUIT, name) {
+                    Some(builtin) if !builtin.accepts(type_) => {
+                        return Err(AsgError::record_field_redefined(
+                            name,
+                            builtin,
+                            type_,
+                            &self.span.clone().unwrap_or_default(),
+                        )
+                        .into());
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
 }
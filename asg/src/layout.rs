@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flattened layouts of composite types.
+//!
+//! A [`Layout`] orders every scalar value held by a type into slots, each named by its access path
+//! from the root (e.g. `points[2].x`). Arrays are laid out element by element, tuples field by field,
+//! and circuits member variable by member variable in declaration order.
+
+use crate::{Circuit, CircuitMember, Type};
+use leo_errors::{AsgError, Result, Span};
+
+/// The maximum nesting depth of arrays, tuples, and circuits in a single layout.
+pub const MAX_LAYOUT_DEPTH: usize = 32;
+
+/// A scalar value within a flattened type.
+#[derive(Clone, PartialEq)]
+pub struct LayoutSlot<'a> {
+    pub path: String,
+    pub type_: Type<'a>,
+}
+
+/// The ordered scalar slots of a type.
+#[derive(Clone, PartialEq)]
+pub struct Layout<'a> {
+    pub slots: Vec<LayoutSlot<'a>>,
+}
+
+impl<'a> Layout<'a> {
+    /// Flattens `type_` into its scalar slots, with paths starting at `root`.
+    pub fn flatten(type_: &Type<'a>, root: &str, span: &Span) -> Result<Self> {
+        Self::check(type_, span)?;
+
+        let mut layout = Layout { slots: vec![] };
+        layout.push(type_, root.to_string());
+        Ok(layout)
+    }
+
+    /// Checks that `type_` has a finite layout within the nesting limit, without expanding arrays.
+    pub fn check(type_: &Type<'a>, span: &Span) -> Result<()> {
+        Self::check_nested(type_, &mut vec![], 0, span)
+    }
+
+    fn check_nested(type_: &Type<'a>, circuits: &mut Vec<&'a Circuit<'a>>, depth: usize, span: &Span) -> Result<()> {
+        if depth > MAX_LAYOUT_DEPTH {
+            return Err(AsgError::type_layout_too_deep(type_, MAX_LAYOUT_DEPTH, span).into());
+        }
+
+        match type_ {
+            Type::Array(element, _) | Type::ArrayWithoutSize(element) => {
+                Self::check_nested(element, circuits, depth + 1, span)
+            }
+            Type::Tuple(elements) => elements
+                .iter()
+                .try_for_each(|element| Self::check_nested(element, circuits, depth + 1, span)),
+            Type::Circuit(circuit) => {
+                if circuits.contains(circuit) {
+                    return Err(AsgError::circuit_contains_itself(&circuit.name.borrow().name, span).into());
+                }
+
+                circuits.push(circuit);
+                for member in circuit.members.borrow().values() {
+                    if let CircuitMember::Variable(member_type) = member {
+                        Self::check_nested(member_type, circuits, depth + 1, span)?;
+                    }
+                }
+                circuits.pop();
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn push(&mut self, type_: &Type<'a>, path: String) {
+        match type_ {
+            Type::Array(element, len) => {
+                for i in 0..*len {
+                    self.push(element, format!("{}[{}]", path, i));
+                }
+            }
+            Type::Tuple(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    self.push(element, format!("{}.{}", path, i));
+                }
+            }
+            Type::Circuit(circuit) => {
+                for (name, member) in circuit.members.borrow().iter() {
+                    if let CircuitMember::Variable(member_type) = member {
+                        self.push(member_type, format!("{}.{}", path, name));
+                    }
+                }
+            }
+            scalar => self.slots.push(LayoutSlot {
+                path,
+                type_: scalar.clone(),
+            }),
+        }
+    }
+
+    /// Returns the number of scalar slots `type_` flattens to, without expanding arrays.
+    ///
+    /// Saturates at `usize::MAX`. The type must already have passed [`Layout::check`].
+    pub fn slot_count(type_: &Type<'a>) -> usize {
+        match type_ {
+            Type::Array(element, len) => Self::slot_count(element).saturating_mul(*len),
+            Type::Tuple(elements) => elements
+                .iter()
+                .fold(0usize, |count, element| count.saturating_add(Self::slot_count(element))),
+            Type::Circuit(circuit) => circuit
+                .members
+                .borrow()
+                .values()
+                .fold(0usize, |count, member| match member {
+                    CircuitMember::Variable(member_type) => count.saturating_add(Self::slot_count(member_type)),
+                    CircuitMember::Static(..) | CircuitMember::Function(_) => count,
+                }),
+            _ => 1,
+        }
+    }
+
+    /// Returns the number of scalar slots.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the index of the slot with the given access path, if the path names a scalar.
+    pub fn slot_index(&self, path: &str) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.path == path)
+    }
+
+    /// Returns the range of slots held by the value at the given access path, which may be composite.
+    pub fn slot_range(&self, path: &str) -> Option<std::ops::Range<usize>> {
+        let is_within = |slot: &LayoutSlot| {
+            slot.path == path
+                || (slot.path.starts_with(path)
+                    && matches!(slot.path[path.len()..].chars().next(), Some('[') | Some('.')))
+        };
+
+        let start = self.slots.iter().position(|slot| is_within(slot))?;
+        let len = self.slots[start..].iter().take_while(|slot| is_within(slot)).count();
+        Some(start..start + len)
+    }
+}
@@ -32,30 +32,54 @@ pub use checks::*;
 pub mod const_value;
 pub use const_value::*;
 
+pub mod constants;
+pub use constants::*;
+
+pub mod dot;
+pub use dot::*;
+
 pub mod expression;
 pub use expression::*;
 
 mod input;
 pub use input::*;
 
+mod layout;
+pub use layout::*;
+
+pub mod navigation;
+pub use navigation::*;
+
 pub mod node;
 pub use node::*;
 
 pub mod program;
 pub use program::*;
 
+pub mod progress;
+pub use progress::*;
+
 pub mod reducer;
 pub use reducer::*;
 
 pub mod scope;
 pub use scope::*;
 
+pub mod semantic_tokens;
+pub use semantic_tokens::*;
+
 pub mod statement;
 pub use statement::*;
 
+pub mod symbol_table;
+pub use symbol_table::*;
+
 pub mod type_;
 pub use type_::*;
 
+pub mod type_context;
+pub use type_context::*;
+
 pub mod variable;
 use typed_arena::Arena;
 pub use variable::*;
@@ -0,0 +1,676 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The queries behind go to definition, find references and hover, for tools like language servers.
+
+use crate::{
+    CallExpression, CircuitAccessExpression, CircuitMember, Constness, ExpressionSymbol, ExpressionVisitor, Program,
+    ProgramVisitor, StatementVisitor, SymbolTable, Type, VisitResult, VisitorDirector,
+};
+use leo_ast::{
+    is_generated_name, AssigneeAccess, AstNode, Block, ConsoleFunction, Expression, FunctionInput, Identifier,
+    Statement,
+};
+use leo_errors::Span;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// A position in a source file, with the line and column counted from 1 as in a [`Span`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// What is known about the expression or name at a position.
+#[derive(Clone)]
+pub struct HoverInfo<'a> {
+    /// The innermost expression or name under the position.
+    pub span: Span,
+    /// The inferred type of the expression, if the program type checks.
+    pub type_: Option<Type<'a>>,
+    /// How much of the expression's value is known at compile time, if the program type checks.
+    pub constness: Option<Constness<'a>>,
+    /// The `///` comment of the declaration the name refers to, if it has one.
+    pub doc: Option<String>,
+}
+
+/// Returns `true` if `span` is in `file` and covers `position`.
+fn covers(span: &Span, file: &str, position: Position) -> bool {
+    *span.path == file
+        && (span.line_start, span.col_start) <= (position.line, position.column)
+        && (position.line, position.column) < (span.line_stop, span.col_stop)
+}
+
+/// Maps the spans of member accesses and calls in the asg to the declarations they resolve to.
+#[derive(Default)]
+struct MemberCollector {
+    members: IndexMap<Span, Span>,
+}
+
+impl<'a> ExpressionVisitor<'a> for MemberCollector {
+    fn visit_call(&mut self, input: &CallExpression<'a>) -> VisitResult {
+        if let Some(span) = &input.span {
+            let declaration = input.function.get().name.borrow().span.clone();
+            self.members.insert(span.clone(), declaration);
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_access(&mut self, input: &CircuitAccessExpression<'a>) -> VisitResult {
+        let circuit = input.circuit.get();
+        let declaration = match circuit.members.borrow().get(input.member.name.as_ref()) {
+            Some(CircuitMember::Function(function)) => Some(function.name.borrow().span.clone()),
+            Some(_) => circuit.member_spans.get(input.member.name.as_ref()).cloned(),
+            None => None,
+        };
+        if let (Some(span), Some(declaration)) = (&input.span, declaration) {
+            self.members.insert(span.clone(), declaration);
+        }
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for MemberCollector {}
+
+impl<'a> ProgramVisitor<'a> for MemberCollector {}
+
+/// A name in scope: where it is declared, and the circuit of its type if the ast tells.
+#[derive(Clone)]
+struct Binding {
+    declaration: Span,
+    circuit: Option<String>,
+}
+
+/// The members of a circuit, each with the circuit of its type or output if the ast tells.
+struct CircuitItem {
+    declaration: Span,
+    members: IndexMap<String, Binding>,
+}
+
+/// Walks the ast, resolving every name against the enclosing scopes and the program's items.
+///
+/// Member accesses resolve through the types of the asg where there is one, and otherwise through
+/// the circuit types the ast spells out, such as those of annotated variables and parameters.
+struct Resolver {
+    asg_members: IndexMap<Span, Span>,
+    functions: IndexMap<String, Binding>,
+    circuits: IndexMap<String, CircuitItem>,
+    aliases: IndexMap<String, Binding>,
+    scopes: Vec<IndexMap<String, Binding>>,
+    circuit: Option<String>,
+    declarations: Vec<Span>,
+    /// The declarations of functions, circuits, aliases, members and global constants.
+    items: Vec<Span>,
+    references: Vec<(Span, Span)>,
+}
+
+impl Resolver {
+    fn collect_items(&mut self, program: &leo_ast::Program) {
+        for import in program.imports.values() {
+            self.collect_items(import);
+        }
+        for name in program.aliases.keys() {
+            let binding = Binding {
+                declaration: name.span.clone(),
+                circuit: None,
+            };
+            self.aliases.insert(name.name.to_string(), binding);
+        }
+        // Aliases may name circuits, so types are only resolved once every name is known.
+        for name in program.circuits.keys() {
+            let item = CircuitItem {
+                declaration: name.span.clone(),
+                members: IndexMap::new(),
+            };
+            self.circuits.insert(name.name.to_string(), item);
+        }
+        for (name, alias) in program.aliases.iter() {
+            let circuit = self.circuit_of_type(&alias.represents);
+            self.aliases[name.name.as_ref()].circuit = circuit;
+        }
+        for (name, circuit) in program.circuits.iter() {
+            self.circuit = Some(name.name.to_string());
+            let mut members = IndexMap::new();
+            for member in circuit.members.iter() {
+                let (identifier, type_) = match member {
+                    leo_ast::CircuitMember::CircuitVariable(identifier, type_)
+                    | leo_ast::CircuitMember::CircuitStatic(identifier, type_, _) => (identifier, Some(type_)),
+                    leo_ast::CircuitMember::CircuitFunction(function) => {
+                        (&function.identifier, function.output.as_ref())
+                    }
+                };
+                let binding = Binding {
+                    declaration: identifier.span.clone(),
+                    circuit: type_.and_then(|type_| self.circuit_of_type(type_)),
+                };
+                members.insert(identifier.name.to_string(), binding);
+            }
+            self.circuits[name.name.as_ref()].members = members;
+        }
+        self.circuit = None;
+        for (name, function) in program.functions.iter() {
+            let binding = Binding {
+                declaration: name.span.clone(),
+                circuit: function.output.as_ref().and_then(|type_| self.circuit_of_type(type_)),
+            };
+            self.functions.insert(name.name.to_string(), binding);
+        }
+    }
+
+    /// Returns the circuit a type names, directly or through an alias.
+    fn circuit_of_type(&self, type_: &leo_ast::Type) -> Option<String> {
+        match type_ {
+            leo_ast::Type::SelfType => self.circuit.clone(),
+            leo_ast::Type::Identifier(identifier) => self.circuit_named(identifier),
+            _ => None,
+        }
+    }
+
+    /// Returns the circuit a name refers to in a type or a static access, directly or through an alias.
+    fn circuit_named(&self, identifier: &Identifier) -> Option<String> {
+        let name = identifier.name.as_ref();
+        if name == "Self" {
+            self.circuit.clone()
+        } else if self.circuits.contains_key(name) {
+            Some(name.to_string())
+        } else {
+            self.aliases.get(name).and_then(|alias| alias.circuit.clone())
+        }
+    }
+
+    /// Returns the circuit of the type of an expression, as far as the ast tells.
+    fn circuit_of_expression(&self, expression: &Expression) -> Option<String> {
+        match expression {
+            Expression::Identifier(identifier) if identifier.name.as_ref() == "self" => self.circuit.clone(),
+            Expression::Identifier(identifier) => self.lookup(identifier.name.as_ref())?.circuit.clone(),
+            Expression::CircuitInit(init) => self.circuit_named(&init.name),
+            Expression::Parenthesized(parenthesized) => self.circuit_of_expression(&parenthesized.inner),
+            Expression::CircuitMemberAccess(access) => {
+                let circuit = self.circuit_of_expression(&access.circuit)?;
+                self.circuits[&circuit]
+                    .members
+                    .get(access.name.name.as_ref())?
+                    .circuit
+                    .clone()
+            }
+            Expression::Call(call) => match &*call.function {
+                Expression::Identifier(identifier) => self.functions.get(identifier.name.as_ref())?.circuit.clone(),
+                Expression::CircuitMemberAccess(access) => {
+                    let circuit = self.circuit_of_expression(&access.circuit)?;
+                    self.circuits[&circuit]
+                        .members
+                        .get(access.name.name.as_ref())?
+                        .circuit
+                        .clone()
+                }
+                Expression::CircuitStaticFunctionAccess(access) => match &*access.circuit {
+                    Expression::Identifier(identifier) => {
+                        let circuit = self.circuit_named(identifier)?;
+                        self.circuits[&circuit]
+                            .members
+                            .get(access.name.name.as_ref())?
+                            .circuit
+                            .clone()
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the variable or constant of the given name, with global constants taking precedence as in the asg.
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes[0]
+            .get(name)
+            .or_else(|| self.scopes[1..].iter().rev().find_map(|scope| scope.get(name)))
+    }
+
+    fn refer(&mut self, span: &Span, declaration: &Span) {
+        self.references.push((span.clone(), declaration.clone()));
+    }
+
+    fn declare(&mut self, identifier: &Identifier, circuit: Option<String>) {
+        if is_generated_name(&identifier.name) {
+            return;
+        }
+        self.declarations.push(identifier.span.clone());
+        let binding = Binding {
+            declaration: identifier.span.clone(),
+            circuit,
+        };
+        self.scopes
+            .last_mut()
+            .expect("no scope to declare in")
+            .insert(identifier.name.to_string(), binding);
+    }
+
+    fn resolve(&mut self, identifier: &Identifier) {
+        let name = identifier.name.as_ref();
+        if name == "self" || name.starts_with("aleo1") || is_generated_name(name) {
+            return;
+        }
+        if name == "Self" {
+            if let Some(circuit) = &self.circuit {
+                let declaration = self.circuits[circuit].declaration.clone();
+                self.refer(&identifier.span, &declaration);
+            }
+            return;
+        }
+
+        let declaration = match self.lookup(name) {
+            Some(binding) => Some(&binding.declaration),
+            None => self
+                .functions
+                .get(name)
+                .map(|function| &function.declaration)
+                .or_else(|| self.circuits.get(name).map(|circuit| &circuit.declaration))
+                .or_else(|| self.aliases.get(name).map(|alias| &alias.declaration)),
+        };
+        if let Some(declaration) = declaration.cloned() {
+            self.refer(&identifier.span, &declaration);
+        }
+    }
+
+    /// Resolves the name of a member of the given circuit.
+    fn resolve_member(&mut self, circuit: Option<String>, name: &Identifier) {
+        let declaration = circuit.and_then(|circuit| {
+            let member = self.circuits[&circuit].members.get(name.name.as_ref())?;
+            Some(member.declaration.clone())
+        });
+        if let Some(declaration) = declaration {
+            self.refer(&name.span, &declaration);
+        }
+    }
+
+    fn visit_type(&mut self, type_: &leo_ast::Type) {
+        match type_ {
+            leo_ast::Type::Identifier(identifier) => self.resolve(identifier),
+            leo_ast::Type::Array(element, _) => self.visit_type(element),
+            leo_ast::Type::Tuple(elements) => elements.iter().for_each(|element| self.visit_type(element)),
+            _ => (),
+        }
+    }
+
+    fn declare_item(&mut self, identifier: &Identifier) {
+        self.declarations.push(identifier.span.clone());
+        self.items.push(identifier.span.clone());
+    }
+
+    fn visit_function(&mut self, function: &leo_ast::Function) {
+        self.declare_item(&function.identifier);
+
+        self.scopes.push(IndexMap::new());
+        for input in function.input.iter() {
+            if let FunctionInput::Variable(variable) = input {
+                let circuit = self.circuit_of_type(&variable.type_);
+                self.declare(&variable.identifier, circuit);
+                self.visit_type(&variable.type_);
+            }
+        }
+        if let Some(output) = &function.output {
+            self.visit_type(output);
+        }
+        self.visit_block(&function.block);
+        self.scopes.pop();
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.scopes.push(IndexMap::new());
+        block
+            .statements
+            .iter()
+            .for_each(|statement| self.visit_statement(statement));
+        self.scopes.pop();
+    }
+
+    fn visit_definition(&mut self, definition: &leo_ast::DefinitionStatement) {
+        if let Some(value) = &definition.value {
+            self.visit_expression(value);
+        }
+        if let Some(type_) = &definition.type_ {
+            self.visit_type(type_);
+        }
+
+        // Only a single variable takes the type of the value; tuples are only known from their annotation.
+        let circuit = match (&definition.type_, &definition.variable_names[..]) {
+            (Some(type_), [_]) => self.circuit_of_type(type_),
+            (None, [_]) => definition
+                .value
+                .as_ref()
+                .and_then(|value| self.circuit_of_expression(value)),
+            _ => None,
+        };
+        for variable in definition.variable_names.iter() {
+            self.declare(&variable.identifier, circuit.clone());
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Definition(definition) => self.visit_definition(definition),
+            Statement::Assign(assign) => {
+                self.resolve(&assign.assignee.identifier);
+                let mut circuit =
+                    self.circuit_of_expression(&Expression::Identifier(assign.assignee.identifier.clone()));
+                for access in assign.assignee.accesses.iter() {
+                    match access {
+                        AssigneeAccess::ArrayRange(left, right) => {
+                            left.iter()
+                                .chain(right.iter())
+                                .for_each(|index| self.visit_expression(index));
+                            circuit = None;
+                        }
+                        AssigneeAccess::ArrayIndex(index) => {
+                            self.visit_expression(index);
+                            circuit = None;
+                        }
+                        AssigneeAccess::Member(member) => {
+                            let next = circuit.as_ref().and_then(|circuit| {
+                                self.circuits[circuit]
+                                    .members
+                                    .get(member.name.as_ref())?
+                                    .circuit
+                                    .clone()
+                            });
+                            self.resolve_member(circuit, member);
+                            circuit = next;
+                        }
+                        AssigneeAccess::Tuple(..) => circuit = None,
+                    }
+                }
+                self.visit_expression(&assign.value);
+            }
+            Statement::Conditional(conditional) => {
+                self.visit_expression(&conditional.condition);
+                self.visit_block(&conditional.block);
+                if let Some(next) = &conditional.next {
+                    self.visit_statement(next);
+                }
+            }
+            Statement::Iteration(iteration) => {
+                self.visit_expression(&iteration.start);
+                self.visit_expression(&iteration.stop);
+                if let Some(step) = &iteration.step {
+                    self.visit_expression(step);
+                }
+                self.scopes.push(IndexMap::new());
+                self.declare(&iteration.variable, None);
+                self.visit_block(&iteration.block);
+                self.scopes.pop();
+            }
+            Statement::Console(console) => match &console.function {
+                ConsoleFunction::Assert(expression) => self.visit_expression(expression),
+                ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                    self.visit_expression(&args.left);
+                    self.visit_expression(&args.right);
+                }
+                ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => args
+                    .parameters
+                    .iter()
+                    .for_each(|parameter| self.visit_expression(parameter)),
+            },
+            Statement::Return(return_) => self.visit_expression(&return_.expression),
+            Statement::Expression(expression) => self.visit_expression(&expression.expression),
+            Statement::Block(block) => self.visit_block(block),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.resolve(identifier),
+            Expression::CircuitMemberAccess(access) => {
+                self.visit_expression(&access.circuit);
+                match self.asg_members.get(&access.span).cloned() {
+                    Some(declaration) => self.refer(&access.name.span, &declaration),
+                    None => {
+                        let circuit = self.circuit_of_expression(&access.circuit);
+                        self.resolve_member(circuit, &access.name);
+                    }
+                }
+            }
+            Expression::CircuitStaticFunctionAccess(access) => {
+                self.visit_expression(&access.circuit);
+                let circuit = match &*access.circuit {
+                    Expression::Identifier(identifier) => self.circuit_named(identifier),
+                    _ => None,
+                };
+                self.resolve_member(circuit, &access.name);
+            }
+            Expression::Call(call) => {
+                // The asg knows which function a call on an instance resolves to, whatever the type of the instance.
+                let callee = match &*call.function {
+                    Expression::CircuitMemberAccess(access) => Some((&access.circuit, &access.name)),
+                    _ => None,
+                };
+                match (callee, self.asg_members.get(&call.span).cloned()) {
+                    (Some((instance, name)), Some(declaration)) => {
+                        self.visit_expression(instance);
+                        self.refer(&name.span, &declaration);
+                    }
+                    _ => self.visit_expression(&call.function),
+                }
+                call.arguments
+                    .iter()
+                    .for_each(|argument| self.visit_expression(argument));
+            }
+            Expression::CircuitInit(init) => {
+                self.resolve(&init.name);
+                let circuit = self.circuit_named(&init.name);
+                for member in init.members.iter() {
+                    self.resolve_member(circuit.clone(), &member.identifier);
+                    match &member.expression {
+                        Some(expression) => self.visit_expression(expression),
+                        // A shorthand member names both the member and the variable it is initialized from.
+                        None => self.resolve(&member.identifier),
+                    }
+                }
+            }
+            Expression::Cast(cast) => {
+                self.visit_expression(&cast.inner);
+                self.visit_type(&cast.target_type);
+            }
+            Expression::ArrayComprehension(comprehension) => {
+                self.visit_expression(&comprehension.start);
+                self.visit_expression(&comprehension.stop);
+                self.scopes.push(IndexMap::new());
+                self.declare(&comprehension.variable, None);
+                self.visit_expression(&comprehension.element);
+                self.scopes.pop();
+            }
+            expression => {
+                for child in AstNode::Expression(expression).children() {
+                    if let AstNode::Expression(child) = child {
+                        self.visit_expression(child);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Where every name of a program is declared and referenced, and the type of every expression.
+///
+/// Built from the ast alone, such as when the program does not type check, member accesses only resolve
+/// where the ast spells out the circuit of the value accessed, and expressions have no types.
+#[derive(Clone, Default)]
+pub struct Navigation<'a> {
+    declarations: Vec<Span>,
+    /// The declarations that can be documented, see [`doc_comment`].
+    items: Vec<Span>,
+    /// Every reference, with the declaration it resolves to, in the order of the ast.
+    references: Vec<(Span, Span)>,
+    expressions: Vec<ExpressionSymbol<'a>>,
+}
+
+impl<'a> Navigation<'a> {
+    /// Indexes `ast`, using the types of `asg` if there is one, which must have been built from `ast`.
+    pub fn new(ast: &leo_ast::Program, asg: Option<&Program<'a>>) -> Self {
+        let mut director = VisitorDirector::new(MemberCollector::default());
+        if let Some(asg) = asg {
+            director.visit_program(asg).ok();
+        }
+
+        let mut resolver = Resolver {
+            asg_members: director.visitor().members,
+            functions: IndexMap::new(),
+            circuits: IndexMap::new(),
+            aliases: IndexMap::new(),
+            scopes: vec![IndexMap::new()],
+            circuit: None,
+            declarations: vec![],
+            items: vec![],
+            references: vec![],
+        };
+        resolver.collect_items(ast);
+
+        for definition in ast.global_consts.values() {
+            resolver.visit_definition(definition);
+            for variable in definition.variable_names.iter() {
+                resolver.items.push(variable.identifier.span.clone());
+            }
+        }
+        for alias in ast.aliases.values() {
+            resolver.declare_item(&alias.name);
+            resolver.visit_type(&alias.represents);
+        }
+        for (name, circuit) in ast.circuits.iter() {
+            resolver.declare_item(name);
+            resolver.circuit = Some(name.name.to_string());
+            for member in circuit.members.iter() {
+                match member {
+                    leo_ast::CircuitMember::CircuitVariable(name, type_) => {
+                        resolver.declare_item(name);
+                        resolver.visit_type(type_);
+                    }
+                    leo_ast::CircuitMember::CircuitStatic(name, type_, value) => {
+                        resolver.declare_item(name);
+                        resolver.visit_type(type_);
+                        resolver.visit_expression(value);
+                    }
+                    leo_ast::CircuitMember::CircuitFunction(function) => resolver.visit_function(function),
+                }
+            }
+            resolver.circuit = None;
+        }
+        for function in ast.functions.values() {
+            resolver.visit_function(function);
+        }
+
+        // Canonicalization can copy names, such as the target of a compound assignment, so keep each once.
+        let references: IndexSet<(Span, Span)> = resolver
+            .references
+            .into_iter()
+            .filter(|(reference, _)| reference.line_start > 0)
+            .collect();
+
+        Navigation {
+            declarations: resolver.declarations,
+            items: resolver.items,
+            references: references.into_iter().collect(),
+            expressions: asg
+                .map(|asg| SymbolTable::new(asg).all_expressions().cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the declaration of the name at the given position, which is the name itself at a declaration.
+    pub fn definition_at(&self, file: &str, position: Position) -> Option<&Span> {
+        self.references
+            .iter()
+            .find(|(reference, _)| covers(reference, file, position))
+            .map(|(_, declaration)| declaration)
+            .or_else(|| self.declarations.iter().find(|span| covers(span, file, position)))
+    }
+
+    /// Returns every reference to the given declaration, in the order of the program.
+    pub fn references_of(&self, declaration: &Span) -> Vec<Span> {
+        let mut references: Vec<Span> = self
+            .references
+            .iter()
+            .filter(|(_, target)| target == declaration)
+            .map(|(reference, _)| reference.clone())
+            .collect();
+        references.sort_by_key(|span| (span.line_start, span.col_start));
+        references
+    }
+
+    /// Returns the type and constness of the innermost expression at the given position, and the documentation
+    /// of the declaration of the name there, taken from `source`, the text of `file`.
+    pub fn hover_at(&self, file: &str, position: Position, source: Option<&str>) -> Option<HoverInfo<'a>> {
+        // Symbols are ordered outermost first, so the last one covering the position is the innermost.
+        let expression = self
+            .expressions
+            .iter()
+            .filter(|symbol| covers(&symbol.span, file, position))
+            .last();
+        let name = self
+            .references
+            .iter()
+            .find(|(reference, _)| covers(reference, file, position))
+            .map(|(reference, declaration)| (reference, declaration))
+            .or_else(|| {
+                self.declarations
+                    .iter()
+                    .find(|span| covers(span, file, position))
+                    .map(|span| (span, span))
+            });
+        let doc = match (name, source) {
+            (Some((_, declaration)), Some(source)) if *declaration.path == file && self.items.contains(declaration) => {
+                doc_comment(source, declaration.line_start)
+            }
+            _ => None,
+        };
+
+        match (expression, name) {
+            (None, None) => None,
+            (expression, name) => Some(HoverInfo {
+                span: expression
+                    .map(|symbol| symbol.span.clone())
+                    .or_else(|| name.map(|(span, _)| span.clone()))
+                    .unwrap(),
+                type_: expression.and_then(|symbol| symbol.type_.clone()),
+                constness: expression.map(|symbol| symbol.constness.clone()),
+                doc,
+            }),
+        }
+    }
+}
+
+/// Returns the `///` comment on the lines right before the given line, past any annotations, without the slashes.
+pub fn doc_comment(source: &str, line: usize) -> Option<String> {
+    let mut lines = source
+        .lines()
+        .take(line.saturating_sub(1))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .map(str::trim)
+        .skip_while(|line| line.starts_with('@'))
+        .take_while(|line| line.starts_with("///"))
+        .map(|line| {
+            let line = &line[3..];
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
@@ -45,7 +45,7 @@ impl<'a> Node for Alias<'a> {
 }
 
 impl<'a> Alias<'a> {
-    pub(super) fn init(scope: &'a Scope<'a>, value: &leo_ast::Alias) -> Result<&'a Alias<'a>> {
+    pub(crate) fn init(scope: &'a Scope<'a>, value: &leo_ast::Alias) -> Result<&'a Alias<'a>> {
         let alias = scope.context.alloc_alias(Alias {
             id: scope.context.get_id(),
             name: RefCell::new(value.name.clone()),
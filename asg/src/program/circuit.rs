@@ -14,15 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Function, Identifier, Node, Scope, Type};
+use crate::{Expression, ExpressionNode, FromAst, Function, Identifier, Node, Scope, Type};
+use leo_ast::is_generated_name;
 use leo_errors::{AsgError, Result, Span};
 
 use indexmap::IndexMap;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 #[derive(Clone)]
 pub enum CircuitMember<'a> {
     Variable(Type<'a>),
+    /// A member shared by every instance, with its constant value once the circuit's statics are filled.
+    Static(Type<'a>, Cell<Option<&'a Expression<'a>>>),
     Function(&'a Function<'a>),
 }
 
@@ -34,6 +37,8 @@ pub struct Circuit<'a> {
     pub scope: &'a Scope<'a>,
     pub span: Option<Span>,
     pub members: RefCell<IndexMap<String, CircuitMember<'a>>>,
+    /// Where the variable and static members are declared, by name.
+    pub member_spans: IndexMap<String, Span>,
 }
 
 impl<'a> PartialEq for Circuit<'a> {
@@ -55,12 +60,24 @@ impl<'a> Node for Circuit<'a> {
 
 impl<'a> Circuit<'a> {
     pub(super) fn init(scope: &'a Scope<'a>, value: &leo_ast::Circuit) -> Result<&'a Circuit<'a>> {
+        Self::check_member_names(value)?;
         let new_scope = scope.make_subscope();
+        let member_spans = value
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                leo_ast::CircuitMember::CircuitVariable(name, _) | leo_ast::CircuitMember::CircuitStatic(name, ..) => {
+                    Some((name.name.to_string(), name.span.clone()))
+                }
+                leo_ast::CircuitMember::CircuitFunction(_) => None,
+            })
+            .collect();
 
         let circuit = scope.context.alloc_circuit(Circuit {
             id: scope.context.get_id(),
             name: RefCell::new(value.circuit_name.clone()),
             members: RefCell::new(IndexMap::new()),
+            member_spans,
             core_mapping: value.core_mapping.clone(),
             span: Some(value.circuit_name.span.clone()),
             scope: new_scope,
@@ -68,22 +85,61 @@ impl<'a> Circuit<'a> {
 
         let mut members = circuit.members.borrow_mut();
         for member in value.members.iter() {
-            if let leo_ast::CircuitMember::CircuitVariable(name, type_) = member {
-                if members.contains_key(name.name.as_ref()) {
-                    return Err(
-                        AsgError::redefined_circuit_member(&value.circuit_name.name, &name.name, &name.span).into(),
-                    );
-                }
-                members.insert(
-                    name.name.to_string(),
+            let (name, member) = match member {
+                leo_ast::CircuitMember::CircuitVariable(name, type_) => (
+                    name,
                     CircuitMember::Variable(new_scope.resolve_ast_type(type_, &name.span)?),
-                );
-            }
+                ),
+                leo_ast::CircuitMember::CircuitStatic(name, type_, _) => (
+                    name,
+                    CircuitMember::Static(new_scope.resolve_ast_type(type_, &name.span)?, Cell::new(None)),
+                ),
+                leo_ast::CircuitMember::CircuitFunction(_) => continue,
+            };
+            members.insert(name.name.to_string(), member);
         }
 
         Ok(circuit)
     }
 
+    ///
+    /// Checks that every member of the circuit has its own name, and that none is a name the compiler reserves.
+    ///
+    /// Variables, statics and functions share one namespace, since all of them are accessed as `Circuit::name`
+    /// or `value.name`. A duplicate is reported at its second declaration, with the first one as a label.
+    ///
+    fn check_member_names(value: &leo_ast::Circuit) -> Result<()> {
+        let mut declared: IndexMap<&str, (&'static str, &Span)> = IndexMap::new();
+        for member in value.members.iter() {
+            let (name, kind) = match member {
+                leo_ast::CircuitMember::CircuitVariable(name, _) => (name, "variable"),
+                leo_ast::CircuitMember::CircuitStatic(name, ..) => (name, "static"),
+                leo_ast::CircuitMember::CircuitFunction(function) => (&function.identifier, "function"),
+            };
+            if is_reserved_member_name(&name.name) {
+                return Err(
+                    AsgError::reserved_circuit_member_name(&value.circuit_name.name, &name.name, &name.span).into(),
+                );
+            }
+            if let Some((previous_kind, previous_span)) = declared.get(name.name.as_ref()) {
+                return Err(AsgError::duplicate_circuit_member(
+                    &value.circuit_name.name,
+                    &name.name,
+                    kind,
+                    previous_kind,
+                    &name.span,
+                )
+                .with_label(
+                    previous_span,
+                    format!("{} `{}` is declared here", previous_kind, name.name),
+                )
+                .into());
+            }
+            declared.insert(name.name.as_ref(), (kind, &name.span));
+        }
+        Ok(())
+    }
+
     pub(super) fn init_member(scope: &'a Scope<'a>, value: &leo_ast::Circuit) -> Result<&'a Circuit<'a>> {
         let new_scope = scope.make_subscope();
         let circuits = scope.circuits.borrow();
@@ -93,14 +149,6 @@ impl<'a> Circuit<'a> {
         let mut members = circuit.members.borrow_mut();
         for member in value.members.iter() {
             if let leo_ast::CircuitMember::CircuitFunction(function) = member {
-                if members.contains_key(function.identifier.name.as_ref()) {
-                    return Err(AsgError::redefined_circuit_member(
-                        &value.circuit_name.name,
-                        &function.identifier.name,
-                        &function.identifier.span,
-                    )
-                    .into());
-                }
                 let asg_function = Function::init(new_scope, function)?;
                 asg_function.circuit.replace(Some(circuit));
                 if asg_function.is_test() {
@@ -116,10 +164,45 @@ impl<'a> Circuit<'a> {
         Ok(circuit)
     }
 
+    ///
+    /// Resolves the values of static members, which must be constant.
+    ///
+    /// Statics are filled after global constants and before function bodies, so that they can use the former
+    /// and the latter can use them.
+    ///
+    pub(super) fn fill_statics(self: &'a Circuit<'a>, value: &leo_ast::Circuit) -> Result<()> {
+        for member in value.members.iter() {
+            if let leo_ast::CircuitMember::CircuitStatic(name, _, initializer) = member {
+                let type_ = match self
+                    .members
+                    .borrow()
+                    .get(name.name.as_ref())
+                    .expect("missing header for defined circuit static")
+                {
+                    CircuitMember::Static(type_, _) => type_.clone(),
+                    _ => unimplemented!(),
+                };
+                let expression = <&Expression<'a>>::from_ast(self.scope, initializer, Some(type_.partial()))?;
+                if expression.const_value().is_none() {
+                    return Err(AsgError::static_member_not_const(
+                        &value.circuit_name.name,
+                        &name.name,
+                        &expression.span().cloned().unwrap_or_else(|| name.span.clone()),
+                    )
+                    .into());
+                }
+                if let Some(CircuitMember::Static(_, value)) = self.members.borrow().get(name.name.as_ref()) {
+                    value.set(Some(expression));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(super) fn fill_from_ast(self: &'a Circuit<'a>, value: &leo_ast::Circuit) -> Result<()> {
         for member in value.members.iter() {
             match member {
-                leo_ast::CircuitMember::CircuitVariable(..) => {}
+                leo_ast::CircuitMember::CircuitVariable(..) | leo_ast::CircuitMember::CircuitStatic(..) => {}
                 leo_ast::CircuitMember::CircuitFunction(function) => {
                     let asg_function = match *self
                         .members
@@ -138,6 +221,12 @@ impl<'a> Circuit<'a> {
     }
 }
 
+/// Returns `true` for names the compiler gives to members of its own: the receiver of member functions,
+/// the circuit itself, and the variables that passes generate.
+fn is_reserved_member_name(name: &str) -> bool {
+    name == "self" || name == "Self" || is_generated_name(name)
+}
+
 impl<'a> Into<leo_ast::Circuit> for &Circuit<'a> {
     fn into(self) -> leo_ast::Circuit {
         let members = self
@@ -148,6 +237,11 @@ impl<'a> Into<leo_ast::Circuit> for &Circuit<'a> {
                 CircuitMember::Variable(type_) => {
                     leo_ast::CircuitMember::CircuitVariable(Identifier::new((&**name).into()), type_.into())
                 }
+                CircuitMember::Static(type_, value) => leo_ast::CircuitMember::CircuitStatic(
+                    Identifier::new((&**name).into()),
+                    type_.into(),
+                    value.get().expect("static member without a value").into(),
+                ),
                 CircuitMember::Function(func) => leo_ast::CircuitMember::CircuitFunction((*func).into()),
             })
             .collect();
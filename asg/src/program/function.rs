@@ -15,14 +15,18 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    BlockStatement, Circuit, FromAst, Identifier, MonoidalDirector, ReturnPathReducer, Scope, Statement, Type, Variable,
+    BlockStatement, Circuit, DefiniteAssignment, FromAst, Identifier, MonoidalDirector, ReturnPathReducer, Scope,
+    Statement, Type, Variable,
 };
 use indexmap::IndexMap;
 pub use leo_ast::Annotation;
-use leo_ast::FunctionInput;
+use leo_ast::{FunctionInput, Traversal};
 use leo_errors::{AsgError, Result, Span};
 
-use std::cell::{Cell, RefCell};
+use std::{
+    cell::{Cell, RefCell},
+    ops::RangeInclusive,
+};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum FunctionQualifier {
@@ -32,6 +36,25 @@ pub enum FunctionQualifier {
     Static,
 }
 
+/// How a function asks to be compiled where it is called, by its `@inline` annotation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlineRequest {
+    /// `@inline(always)`
+    Always,
+    /// `@inline(never)`
+    Never,
+}
+
+impl InlineRequest {
+    fn parse(argument: &str) -> Option<Self> {
+        match argument {
+            "always" => Some(InlineRequest::Always),
+            "never" => Some(InlineRequest::Never),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Function<'a> {
     pub id: u32,
@@ -44,6 +67,9 @@ pub struct Function<'a> {
     pub scope: &'a Scope<'a>,
     pub qualifier: FunctionQualifier,
     pub annotations: Vec<Annotation>,
+    /// Where the function declares its own variable named `input`, which hides the program input
+    /// in the whole function.
+    pub input_binding: Option<Span>,
 }
 
 impl<'a> PartialEq for Function<'a> {
@@ -105,6 +131,8 @@ impl<'a> Function<'a> {
                 }
             }
         }
+        check_annotations(&value.annotations)?;
+
         let function = scope.context.alloc_function(Function {
             id: scope.context.get_id(),
             name: RefCell::new(value.identifier.clone()),
@@ -116,6 +144,7 @@ impl<'a> Function<'a> {
             scope: new_scope,
             span: Some(value.span.clone()),
             annotations: value.annotations.clone(),
+            input_binding: input_binding(value),
         });
         function.scope.function.replace(Some(function));
 
@@ -152,6 +181,11 @@ impl<'a> Function<'a> {
             self.scope.variables.borrow_mut().insert(name.clone(), argument.get());
         }
 
+        // The body is left out, so that the function is refused when it is compiled while the others still are.
+        if value.has_errors() {
+            return Ok(());
+        }
+
         let main_block = BlockStatement::from_ast(self.scope, &value.block, None)?;
         let mut director = MonoidalDirector::new(ReturnPathReducer::new());
         if !director.reduce_block(&main_block).0 && !self.output.is_unit() {
@@ -163,15 +197,154 @@ impl<'a> Function<'a> {
             return Err(AsgError::function_return_validation(&self.name.borrow().name, error, &span).into());
         }
 
+        let mut definite_assignment = DefiniteAssignment::new();
+        definite_assignment.check_block(&main_block);
+        if let Some((span, name, notes)) = definite_assignment.errors.into_iter().next() {
+            return Err(AsgError::variable_maybe_unassigned(name, notes, &span).into());
+        }
+
         self.body
             .replace(Some(self.scope.context.alloc_statement(Statement::Block(main_block))));
 
         Ok(())
     }
 
+    /// Returns the output types of the function: none for unit, one per element for a tuple, or the single output type.
+    pub fn outputs(&self) -> Vec<Type<'a>> {
+        match &self.output {
+            Type::Tuple(types) => types.clone(),
+            type_ => vec![type_.clone()],
+        }
+    }
+
     pub fn is_test(&self) -> bool {
         self.annotations.iter().any(|x| x.name.name.as_ref() == "test")
     }
+
+    /// Returns `true` if the function opts into ordering comparisons on field elements.
+    pub fn allows_field_ordering(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|x| x.name.name.as_ref() == "allow_field_ordering")
+    }
+
+    /// Returns `true` if the function opts out of warnings about parameters whose circuits other programs cannot construct.
+    pub fn allows_unconstructible_parameters(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|x| x.name.name.as_ref() == "allow_unconstructible_parameters")
+    }
+
+    /// Returns `true` if the function opts out of warnings about the case of its name and of its variables' names.
+    pub fn allows_naming(&self) -> bool {
+        self.annotations.iter().any(|x| x.name.name.as_ref() == "allow_naming")
+    }
+
+    /// Returns how the function asks to be compiled where it is called, if it is annotated with `@inline`.
+    pub fn inline_request(&self) -> Option<InlineRequest> {
+        self.annotations
+            .iter()
+            .find(|x| x.name.name.as_ref() == "inline")
+            .and_then(|x| x.arguments.first())
+            .and_then(|argument| InlineRequest::parse(argument))
+    }
+
+    /// Returns the number of constraints the function may synthesize, if it is annotated with `@budget`.
+    pub fn budget(&self) -> Option<usize> {
+        self.annotations
+            .iter()
+            .find(|x| x.name.name.as_ref() == "budget")
+            .and_then(|x| x.arguments.first())
+            .and_then(|argument| parse_budget(argument))
+    }
+}
+
+/// The annotations a function may carry, with the numbers of arguments each accepts.
+const FUNCTION_ANNOTATIONS: &[(&str, RangeInclusive<usize>)] = &[
+    ("test", 0..=1),
+    ("allow_field_ordering", 0..=0),
+    ("allow_unconstructible_parameters", 0..=0),
+    ("allow_naming", 0..=0),
+    ("budget", 1..=1),
+    ("inline", 1..=1),
+];
+
+/// Parses the `instructions=N` argument of a `@budget` annotation.
+fn parse_budget(argument: &str) -> Option<usize> {
+    let (key, value) = argument.split_once('=')?;
+    if key != "instructions" {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Checks that every annotation is known, is applied at most once, and is given an accepted number of arguments.
+fn check_annotations(annotations: &[Annotation]) -> Result<()> {
+    let mut seen: IndexMap<&str, &Annotation> = IndexMap::new();
+    for annotation in annotations.iter() {
+        let name = annotation.name.name.as_ref();
+        let arity = match FUNCTION_ANNOTATIONS.iter().find(|(known, _)| *known == name) {
+            Some((_, arity)) => arity,
+            None => return Err(AsgError::unknown_annotation(name, &annotation.span).into()),
+        };
+        if let Some(first) = seen.insert(name, annotation) {
+            let request = |annotation: &Annotation| match annotation.arguments.as_slice() {
+                [argument] => InlineRequest::parse(argument),
+                _ => None,
+            };
+            if name == "inline" && request(annotation).is_some() && request(first) != request(annotation) {
+                return Err(AsgError::conflicting_inline_annotations(
+                    &first.arguments[0],
+                    first.span.line_start,
+                    &annotation.span,
+                )
+                .into());
+            }
+            return Err(AsgError::duplicate_annotation(name, first.span.line_start, &annotation.span).into());
+        }
+        if !arity.contains(&annotation.arguments.len()) {
+            let expected = if arity.start() == arity.end() {
+                arity.start().to_string()
+            } else {
+                format!("{} to {}", arity.start(), arity.end())
+            };
+            return Err(AsgError::annotation_argument_count(
+                name,
+                expected,
+                annotation.arguments.len(),
+                &annotation.span,
+            )
+            .into());
+        }
+        if name == "budget" && parse_budget(&annotation.arguments[0]).is_none() {
+            return Err(AsgError::invalid_budget_annotation(&annotation.arguments[0], &annotation.span).into());
+        }
+        if name == "inline" && InlineRequest::parse(&annotation.arguments[0]).is_none() {
+            return Err(AsgError::invalid_inline_annotation(&annotation.arguments[0], &annotation.span).into());
+        }
+    }
+    Ok(())
+}
+
+/// Returns the span of the first parameter or variable of the function named `input`.
+fn input_binding(function: &leo_ast::Function) -> Option<Span> {
+    let parameters = function.input.iter().filter_map(|input| match input {
+        FunctionInput::Variable(variable) => Some(&variable.identifier),
+        _ => None,
+    });
+    let variables = function
+        .iter_statements(Traversal::PreOrder)
+        .flat_map(|statement| match statement {
+            leo_ast::Statement::Definition(definition) => {
+                definition.variable_names.iter().map(|name| &name.identifier).collect()
+            }
+            leo_ast::Statement::Iteration(iteration) => vec![&iteration.variable],
+            _ => vec![],
+        });
+    parameters
+        .chain(variables)
+        .find(|identifier| identifier.name.as_ref() == "input")
+        .map(|identifier| identifier.span.clone())
 }
 
 impl<'a> Into<leo_ast::Function> for &Function<'a> {
@@ -27,12 +27,18 @@ pub use circuit::*;
 mod function;
 pub use function::*;
 
-use crate::{node::FromAst, ArenaNode, AsgContext, DefinitionStatement, Input, Scope, Statement};
+use crate::{
+    node::FromAst, ArenaNode, AsgContext, DefinitionStatement, Input, Layout, Scope, Statement, Type, TypeContext,
+    CORE_FUNCTION_NAMES, RECORD_DATA_CIRCUIT,
+};
 use leo_ast::{PackageAccess, PackageOrPackages};
 use leo_errors::{AsgError, Result, Span};
 
 use indexmap::IndexMap;
-use std::cell::{Cell, RefCell};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 /// Stores the Leo program abstract semantic graph (ASG).
 #[derive(Clone)]
@@ -130,6 +136,46 @@ fn resolve_import_package_access(
     }
 }
 
+/// The names of the primitive types, which are keywords.
+const PRIMITIVE_TYPE_NAMES: &[&str] = &[
+    "address", "bool", "char", "field", "group", "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128",
+];
+
+/// The name of the function a program is run from.
+const ENTRYPOINT_NAME: &str = "main";
+
+///
+/// Warns if the name of a top-level circuit, alias or function is easily confused with a name the language
+/// gives a meaning to: a primitive type, a core function or the entrypoint, compared regardless of case.
+///
+/// Names that are exactly a keyword never get here, since the parser rejects them.
+///
+fn warn_confusable_name(context: AsgContext, kind: &str, name: &leo_ast::Identifier) {
+    let name_str = name.name.as_ref();
+    let confused = PRIMITIVE_TYPE_NAMES
+        .iter()
+        .map(|target| ("primitive type", *target))
+        .chain(CORE_FUNCTION_NAMES.iter().map(|target| ("core function", *target)))
+        .chain(std::iter::once(("entrypoint", ENTRYPOINT_NAME)))
+        .find(|(_, target)| target.eq_ignore_ascii_case(name_str));
+
+    let message = match confused {
+        None => return,
+        Some(("entrypoint", _)) if kind == "function" && name_str == ENTRYPOINT_NAME => return,
+        Some(("core function", target)) if kind == "function" && name_str == target => {
+            format!("function `{}` shadows the core function of the same name", name_str)
+        }
+        Some((what, target)) if name_str == target => {
+            format!("{} `{}` has the same name as the {}", kind, name_str, what)
+        }
+        Some((what, target)) => format!(
+            "{} `{}` differs only in case from the {} `{}`",
+            kind, name_str, what, target
+        ),
+    };
+    context.warn(message, &name.span);
+}
+
 /// Checks whether a given string is found in any other global namespaces.
 /// If it is found it returns an error.
 fn check_top_level_namespaces<'a>(
@@ -238,6 +284,7 @@ impl<'a> Program<'a> {
             }
         }
 
+        let types = Rc::new(TypeContext::default());
         let import_scope = match context.arena.alloc(ArenaNode::Scope(Box::new(Scope {
             context,
             id: context.get_id(),
@@ -249,6 +296,7 @@ impl<'a> Program<'a> {
             circuits: RefCell::new(imported_circuits),
             function: Cell::new(None),
             input: Cell::new(None),
+            types: types.clone(),
         }))) {
             ArenaNode::Scope(c) => c,
             _ => unimplemented!(),
@@ -265,15 +313,21 @@ impl<'a> Program<'a> {
             global_consts: RefCell::new(IndexMap::new()),
             circuits: RefCell::new(IndexMap::new()),
             function: Cell::new(None),
+            types,
         });
 
         // Prepare header-like scope entries.
-        // Have to do aliases first.
+        // Have to do aliases first, except for those naming circuits of this program,
+        // which wait until the circuits are declared. An alias naming another alias
+        // declares that one first, whatever their order.
+        scope.types.begin_aliases(scope, program.aliases.values());
+        let mut deferred_aliases = vec![];
         for (name, alias) in program.aliases.iter() {
             assert_eq!(name.name, alias.name.name);
 
-            let asg_alias = Alias::init(scope, alias)?;
-            scope.aliases.borrow_mut().insert(name.name.to_string(), asg_alias);
+            if scope.types.declare_alias(&name.name).is_err() {
+                deferred_aliases.push(name);
+            }
         }
 
         for (name, circuit) in program.circuits.iter() {
@@ -283,6 +337,10 @@ impl<'a> Program<'a> {
             scope.circuits.borrow_mut().insert(name.name.to_string(), asg_circuit);
         }
 
+        for name in deferred_aliases {
+            scope.types.declare_alias(&name.name)?;
+        }
+
         // Second pass for circuit members.
         for (name, circuit) in program.circuits.iter() {
             assert_eq!(name.name, circuit.circuit_name.name);
@@ -291,6 +349,20 @@ impl<'a> Program<'a> {
             scope.circuits.borrow_mut().insert(name.name.to_string(), asg_circuit);
         }
 
+        scope.types.seal();
+
+        // Every circuit must have a finite layout.
+        for circuit in scope.circuits.borrow().values() {
+            let span = circuit.span.clone().unwrap_or_default();
+            scope.types.slot_count(&Type::Circuit(*circuit), &span)?;
+            Layout::check(&Type::Circuit(*circuit), &span)?;
+        }
+
+        // The `Record` circuit declares the custom data fields of the input record.
+        if let Some(record) = scope.circuits.borrow().get(RECORD_DATA_CIRCUIT) {
+            record.check_record_data_fields()?;
+        }
+
         for (name, function) in program.functions.iter() {
             assert_eq!(name.name, function.identifier.name);
             let function = Function::init(scope, function)?;
@@ -315,6 +387,11 @@ impl<'a> Program<'a> {
             }
         }
 
+        for (name, circuit) in program.circuits.iter() {
+            let asg_circuit = *scope.circuits.borrow().get(name.name.as_ref()).unwrap();
+            asg_circuit.fill_statics(circuit)?;
+        }
+
         // Load concrete definitions.
         let mut aliases = IndexMap::new();
         let mut functions = IndexMap::new();
@@ -325,6 +402,7 @@ impl<'a> Program<'a> {
             assert_eq!(name.name, alias.name.name);
             let asg_alias = *scope.aliases.borrow().get(name.name.as_ref()).unwrap();
 
+            warn_confusable_name(context, "alias", &alias.name);
             let name = name.name.to_string();
 
             check_top_level_namespaces(&name, &alias.span, &aliases, &functions, &circuits, &global_consts)?;
@@ -336,8 +414,10 @@ impl<'a> Program<'a> {
             assert_eq!(name.name, function.identifier.name);
             let asg_function = *scope.functions.borrow().get(name.name.as_ref()).unwrap();
 
+            context.report_item(name.name.as_ref());
             asg_function.fill_from_ast(function)?;
 
+            warn_confusable_name(context, "function", &function.identifier);
             let name = name.name.to_string();
 
             check_top_level_namespaces(&name, &function.span, &aliases, &functions, &circuits, &global_consts)?;
@@ -351,6 +431,7 @@ impl<'a> Program<'a> {
 
             asg_circuit.fill_from_ast(circuit)?;
 
+            warn_confusable_name(context, "circuit", &circuit.circuit_name);
             let name = name.name.to_string();
 
             check_top_level_namespaces(
@@ -0,0 +1,29 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+/// Receives the progress of a compilation, for progress bars in the CLI and other tools.
+///
+/// Phases may nest: a phase started within another one always ends before it.
+pub trait ProgressReporter {
+    /// A phase starts, with the number of items it reports if that is known in advance.
+    fn on_phase_start(&self, phase: &str, items: Option<usize>);
+
+    /// The innermost running phase moves on to its item at `index`.
+    fn on_item(&self, index: usize, name: &str);
+
+    /// A phase ends, whether it succeeded or not.
+    fn on_phase_end(&self, phase: &str);
+}
@@ -16,6 +16,7 @@
 
 use super::*;
 use crate::{expression::*, program::*, statement::*};
+use leo_ast::ensure_stack;
 
 use std::marker::PhantomData;
 
@@ -37,6 +38,10 @@ impl<'a, T: Monoid, R: MonoidalReducerExpression<'a, T>> MonoidalDirector<'a, T,
     }
 
     pub fn reduce_expression(&mut self, input: &'a Expression<'a>) -> T {
+        ensure_stack(|| self.reduce_expression_inner(input))
+    }
+
+    fn reduce_expression_inner(&mut self, input: &'a Expression<'a>) -> T {
         let value = match input {
             Expression::ArrayAccess(e) => self.reduce_array_access(e),
             Expression::ArrayInit(e) => self.reduce_array_init(e),
@@ -49,6 +54,7 @@ impl<'a, T: Monoid, R: MonoidalReducerExpression<'a, T>> MonoidalDirector<'a, T,
             Expression::Ternary(e) => self.reduce_ternary_expression(e),
             Expression::Cast(e) => self.reduce_cast_expression(e),
             Expression::LengthOf(e) => self.reduce_lengthof_expression(e),
+            Expression::Bits(e) => self.reduce_bits_expression(e),
             Expression::Constant(e) => self.reduce_constant(e),
             Expression::TupleAccess(e) => self.reduce_tuple_access(e),
             Expression::TupleInit(e) => self.reduce_tuple_init(e),
@@ -145,6 +151,12 @@ impl<'a, T: Monoid, R: MonoidalReducerExpression<'a, T>> MonoidalDirector<'a, T,
         self.reducer.reduce_lengthof_expression(input, inner)
     }
 
+    pub fn reduce_bits_expression(&mut self, input: &BitsExpression<'a>) -> T {
+        let inner = self.reduce_expression(input.inner.get());
+
+        self.reducer.reduce_bits_expression(input, inner)
+    }
+
     pub fn reduce_constant(&mut self, input: &Constant<'a>) -> T {
         self.reducer.reduce_constant(input)
     }
@@ -245,6 +257,10 @@ impl<'a, T: Monoid, R: MonoidalReducerStatement<'a, T>> MonoidalDirector<'a, T,
     pub fn reduce_console(&mut self, input: &ConsoleStatement<'a>) -> T {
         let argument = match &input.function {
             ConsoleFunction::Assert(e) => self.reduce_expression(e.get()),
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                let left = self.reduce_expression(args.left.get());
+                left.append(self.reduce_expression(args.right.get()))
+            }
             ConsoleFunction::Error(f) | ConsoleFunction::Log(f) => self.reduce_formatted_string(f),
         };
 
@@ -266,9 +282,10 @@ impl<'a, T: Monoid, R: MonoidalReducerStatement<'a, T>> MonoidalDirector<'a, T,
     pub fn reduce_iteration(&mut self, input: &IterationStatement<'a>) -> T {
         let start = self.reduce_expression(input.start.get());
         let stop = self.reduce_expression(input.stop.get());
+        let step = input.step.get().map(|e| self.reduce_expression(e));
         let body = self.reduce_statement(input.body.get());
 
-        self.reducer.reduce_iteration(input, start, stop, body)
+        self.reducer.reduce_iteration(input, start, stop, step, body)
     }
 
     pub fn reduce_return(&mut self, input: &ReturnStatement<'a>) -> T {
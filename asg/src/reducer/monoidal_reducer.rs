@@ -72,6 +72,10 @@ pub trait MonoidalReducerExpression<'a, T: Monoid> {
         inner
     }
 
+    fn reduce_bits_expression(&mut self, input: &BitsExpression<'a>, inner: T) -> T {
+        inner
+    }
+
     fn reduce_constant(&mut self, input: &Constant<'a>) -> T {
         T::default()
     }
@@ -138,8 +142,8 @@ pub trait MonoidalReducerStatement<'a, T: Monoid>: MonoidalReducerExpression<'a,
         expression
     }
 
-    fn reduce_iteration(&mut self, input: &IterationStatement<'a>, start: T, stop: T, body: T) -> T {
-        start.append(stop).append(body)
+    fn reduce_iteration(&mut self, input: &IterationStatement<'a>, start: T, stop: T, step: Option<T>, body: T) -> T {
+        start.append(stop).append_option(step).append(body)
     }
 
     fn reduce_return(&mut self, input: &ReturnStatement<'a>, value: T) -> T {
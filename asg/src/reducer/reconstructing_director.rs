@@ -16,6 +16,7 @@
 
 use super::*;
 use crate::{expression::*, program::*, statement::*, AsgContext};
+use leo_ast::ensure_stack;
 
 /*
 reconstructing director tries to maintain a normalized ASG but may require renormalization under the following circumstances:
@@ -37,6 +38,10 @@ impl<'a, R: ReconstructingReducerExpression<'a>> ReconstructingDirector<'a, R> {
     }
 
     pub fn reduce_expression(&mut self, input: &'a Expression<'a>) -> &'a Expression<'a> {
+        ensure_stack(|| self.reduce_expression_inner(input))
+    }
+
+    fn reduce_expression_inner(&mut self, input: &'a Expression<'a>) -> &'a Expression<'a> {
         let value = match input.clone() {
             Expression::ArrayAccess(e) => self.reduce_array_access(e),
             Expression::ArrayInit(e) => self.reduce_array_init(e),
@@ -49,6 +54,7 @@ impl<'a, R: ReconstructingReducerExpression<'a>> ReconstructingDirector<'a, R> {
             Expression::Ternary(e) => self.reduce_ternary_expression(e),
             Expression::Cast(e) => self.reduce_cast_expression(e),
             Expression::LengthOf(e) => Expression::LengthOf(e), // TODO: implement REDUCER
+            Expression::Bits(e) => self.reduce_bits_expression(e),
             Expression::Constant(e) => self.reduce_constant(e),
             Expression::TupleAccess(e) => self.reduce_tuple_access(e),
             Expression::TupleInit(e) => self.reduce_tuple_init(e),
@@ -155,6 +161,12 @@ impl<'a, R: ReconstructingReducerExpression<'a>> ReconstructingDirector<'a, R> {
         self.reducer.reduce_cast_expression(input, inner)
     }
 
+    pub fn reduce_bits_expression(&mut self, input: BitsExpression<'a>) -> Expression<'a> {
+        let inner = self.reduce_expression(input.inner.get());
+
+        self.reducer.reduce_bits_expression(input, inner)
+    }
+
     pub fn reduce_constant(&mut self, input: Constant<'a>) -> Expression<'a> {
         self.reducer.reduce_constant(input)
     }
@@ -260,6 +272,11 @@ impl<'a, R: ReconstructingReducerStatement<'a>> ReconstructingDirector<'a, R> {
                 let argument = self.reduce_expression(argument.get());
                 self.reducer.reduce_console_assert(input, argument)
             }
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                let left = self.reduce_expression(args.left.get());
+                let right = self.reduce_expression(args.right.get());
+                self.reducer.reduce_console_assert_comparison(input, left, right)
+            }
             ConsoleFunction::Error(f) | ConsoleFunction::Log(f) => {
                 let formatted = self.reduce_formatted_string(f.clone());
                 self.reducer.reduce_console_log(input, formatted)
@@ -282,9 +299,10 @@ impl<'a, R: ReconstructingReducerStatement<'a>> ReconstructingDirector<'a, R> {
     pub fn reduce_iteration(&mut self, input: IterationStatement<'a>) -> Statement<'a> {
         let start = self.reduce_expression(input.start.get());
         let stop = self.reduce_expression(input.stop.get());
+        let step = input.step.get().map(|e| self.reduce_expression(e));
         let body = self.reduce_statement(input.body.get());
 
-        self.reducer.reduce_iteration(input, start, stop, body)
+        self.reducer.reduce_iteration(input, start, stop, step, body)
     }
 
     pub fn reduce_return(&mut self, input: ReturnStatement<'a>) -> Statement<'a> {
@@ -308,6 +326,10 @@ impl<'a, R: ReconstructingReducerProgram<'a>> ReconstructingDirector<'a, R> {
                 let function = self.reduce_function(function);
                 self.reducer.reduce_circuit_member_function(input, function)
             }
+            CircuitMember::Static(_, ref value) => {
+                let value = value.get().map(|value| self.reduce_expression(value));
+                self.reducer.reduce_circuit_member_static(input, value)
+            }
             CircuitMember::Variable(_) => self.reducer.reduce_circuit_member_variable(input),
         }
     }
@@ -156,10 +156,21 @@ pub trait ReconstructingReducerExpression<'a> {
             parent: input.parent,
             inner: Cell::new(inner),
             target_type: input.target_type,
+            implicit: input.implicit,
             span: input.span,
         })
     }
 
+    fn reduce_bits_expression(&mut self, input: BitsExpression<'a>, inner: &'a Expression<'a>) -> Expression<'a> {
+        Expression::Bits(BitsExpression {
+            parent: input.parent,
+            span: input.span,
+            direction: input.direction,
+            inner: Cell::new(inner),
+            target_type: input.target_type,
+        })
+    }
+
     fn reduce_constant(&mut self, input: Constant<'a>) -> Expression<'a> {
         Expression::Constant(input)
     }
@@ -293,13 +304,40 @@ pub trait ReconstructingReducerStatement<'a>: ReconstructingReducerExpression<'a
         })
     }
 
+    fn reduce_console_assert_comparison(
+        &mut self,
+        input: ConsoleStatement<'a>,
+        left: &'a Expression<'a>,
+        right: &'a Expression<'a>,
+    ) -> Statement<'a> {
+        let compared = |args: ConsoleAssertArgs<'a>| ConsoleAssertArgs {
+            left: Cell::new(left),
+            right: Cell::new(right),
+            span: args.span,
+        };
+        Statement::Console(ConsoleStatement {
+            parent: input.parent,
+            span: input.span,
+            function: match input.function {
+                ConsoleFunction::AssertEq(args) => ConsoleFunction::AssertEq(compared(args)),
+                ConsoleFunction::AssertNe(args) => ConsoleFunction::AssertNe(compared(args)),
+                _ => unimplemented!(),
+            },
+        })
+    }
+
     fn reduce_console_log(&mut self, input: ConsoleStatement<'a>, argument: ConsoleArgs<'a>) -> Statement<'a> {
-        assert!(!matches!(input.function, ConsoleFunction::Assert(_)));
+        assert!(matches!(
+            input.function,
+            ConsoleFunction::Error(_) | ConsoleFunction::Log(_)
+        ));
         Statement::Console(ConsoleStatement {
             parent: input.parent,
             span: input.span,
             function: match input.function {
-                ConsoleFunction::Assert(_) => unimplemented!(),
+                ConsoleFunction::Assert(_) | ConsoleFunction::AssertEq(_) | ConsoleFunction::AssertNe(_) => {
+                    unimplemented!()
+                }
                 ConsoleFunction::Error(_) => ConsoleFunction::Error(argument),
                 ConsoleFunction::Log(_) => ConsoleFunction::Log(argument),
             },
@@ -312,6 +350,7 @@ pub trait ReconstructingReducerStatement<'a>: ReconstructingReducerExpression<'a
             span: input.span,
             variables: input.variables,
             value: Cell::new(value),
+            initialized: input.initialized,
         })
     }
 
@@ -332,6 +371,7 @@ pub trait ReconstructingReducerStatement<'a>: ReconstructingReducerExpression<'a
         input: IterationStatement<'a>,
         start: &'a Expression<'a>,
         stop: &'a Expression<'a>,
+        step: Option<&'a Expression<'a>>,
         body: &'a Statement<'a>,
     ) -> Statement<'a> {
         Statement::Iteration(IterationStatement {
@@ -341,6 +381,7 @@ pub trait ReconstructingReducerStatement<'a>: ReconstructingReducerExpression<'a
             start: Cell::new(start),
             stop: Cell::new(stop),
             inclusive: input.inclusive,
+            step: Cell::new(step),
             body: Cell::new(body),
         })
     }
@@ -366,6 +407,17 @@ pub trait ReconstructingReducerProgram<'a>: ReconstructingReducerStatement<'a> {
         input
     }
 
+    fn reduce_circuit_member_static(
+        &mut self,
+        input: CircuitMember<'a>,
+        value: Option<&'a Expression<'a>>,
+    ) -> CircuitMember<'a> {
+        if let CircuitMember::Static(_, cell) = &input {
+            cell.set(value);
+        }
+        input
+    }
+
     fn reduce_circuit_member_function(
         &mut self,
         input: CircuitMember<'a>,
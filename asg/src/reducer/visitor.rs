@@ -80,6 +80,10 @@ pub trait ExpressionVisitor<'a> {
         Default::default()
     }
 
+    fn visit_bits_expression(&mut self, input: &BitsExpression<'a>) -> VisitResult {
+        Default::default()
+    }
+
     fn visit_constant(&mut self, input: &Constant<'a>) -> VisitResult {
         Default::default()
     }
@@ -16,6 +16,7 @@
 
 use super::*;
 use crate::{expression::*, program::*, statement::*};
+use leo_ast::ensure_stack;
 
 use std::{cell::Cell, marker::PhantomData};
 
@@ -49,6 +50,10 @@ impl<'a, R: ExpressionVisitor<'a>> VisitorDirector<'a, R> {
     }
 
     pub fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> ConcreteVisitResult {
+        ensure_stack(|| self.visit_expression_inner(input))
+    }
+
+    fn visit_expression_inner(&mut self, input: &Cell<&'a Expression<'a>>) -> ConcreteVisitResult {
         match self.visitor.visit_expression(input) {
             VisitResult::VisitChildren => match input.get() {
                 Expression::ArrayAccess(e) => self.visit_array_access(e),
@@ -62,6 +67,7 @@ impl<'a, R: ExpressionVisitor<'a>> VisitorDirector<'a, R> {
                 Expression::Ternary(e) => self.visit_ternary_expression(e),
                 Expression::Cast(e) => self.visit_cast_expression(e),
                 Expression::LengthOf(e) => self.visit_lengthof_expression(e),
+                Expression::Bits(e) => self.visit_bits_expression(e),
                 Expression::Constant(e) => self.visit_constant(e),
                 Expression::TupleAccess(e) => self.visit_tuple_access(e),
                 Expression::TupleInit(e) => self.visit_tuple_init(e),
@@ -206,6 +212,16 @@ impl<'a, R: ExpressionVisitor<'a>> VisitorDirector<'a, R> {
         }
     }
 
+    pub fn visit_bits_expression(&mut self, input: &BitsExpression<'a>) -> ConcreteVisitResult {
+        match self.visitor.visit_bits_expression(input) {
+            VisitResult::VisitChildren => {
+                self.visit_expression(&input.inner)?;
+                Ok(())
+            }
+            x => x.into(),
+        }
+    }
+
     pub fn visit_constant(&mut self, input: &Constant<'a>) -> ConcreteVisitResult {
         self.visitor.visit_constant(input).into()
     }
@@ -347,6 +363,10 @@ impl<'a, R: StatementVisitor<'a>> VisitorDirector<'a, R> {
             VisitResult::VisitChildren => {
                 match &input.function {
                     ConsoleFunction::Assert(e) => self.visit_expression(e)?,
+                    ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                        self.visit_expression(&args.left)?;
+                        self.visit_expression(&args.right)?;
+                    }
                     ConsoleFunction::Error(f) | ConsoleFunction::Log(f) => self.visit_formatted_string(f)?,
                 }
                 Ok(())
@@ -380,6 +400,7 @@ impl<'a, R: StatementVisitor<'a>> VisitorDirector<'a, R> {
             VisitResult::VisitChildren => {
                 self.visit_expression(&input.start)?;
                 self.visit_expression(&input.stop)?;
+                self.visit_opt_expression(&input.step)?;
                 self.visit_statement(&input.body)?;
                 Ok(())
             }
@@ -412,8 +433,14 @@ impl<'a, R: ProgramVisitor<'a>> VisitorDirector<'a, R> {
     pub fn visit_circuit_member(&mut self, input: &CircuitMember<'a>) -> ConcreteVisitResult {
         match self.visitor.visit_circuit_member(input) {
             VisitResult::VisitChildren => {
-                if let CircuitMember::Function(f) = input {
-                    self.visit_function(f)?;
+                match input {
+                    CircuitMember::Function(f) => self.visit_function(f)?,
+                    CircuitMember::Static(_, value) => {
+                        if let Some(value) = value.get() {
+                            self.visit_expression(&Cell::new(value))?;
+                        }
+                    }
+                    CircuitMember::Variable(_) => {}
                 }
                 Ok(())
             }
@@ -14,11 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Alias, AsgContext, Circuit, DefinitionStatement, Function, Input, Type, Variable};
+use crate::{
+    Alias, AsgContext, Circuit, DefinitionStatement, Function, Identifier, Input, Type, TypeContext, Variable,
+};
 use leo_errors::{AsgError, Result, Span};
 
 use indexmap::IndexMap;
-use std::cell::{Cell, RefCell};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 /// An abstract data type that track the current bindings for variables, functions, and circuits.
 #[derive(Clone)]
@@ -51,6 +56,9 @@ pub struct Scope<'a> {
 
     /// The main input to the program.
     pub input: Cell<Option<Input<'a>>>,
+
+    /// The resolved type names and type sizes of the program, shared by all of its scopes.
+    pub types: Rc<TypeContext<'a>>,
 }
 
 #[allow(clippy::mut_from_ref)]
@@ -103,6 +111,29 @@ impl<'a> Scope<'a> {
         }
     }
 
+    ///
+    /// Returns the variable that `input` refers to: the function's own variable of that name if one
+    /// is in scope, and otherwise the program input.
+    ///
+    /// Using the program input in a function that declares its own `input` is an error.
+    ///
+    pub fn resolve_input_variable(&'a self, span: &Span) -> Result<&'a Variable<'a>> {
+        if let Some(variable) = self.resolve_variable("input") {
+            return Ok(variable);
+        }
+        if let Some(function) = self.resolve_current_function() {
+            if let Some(binding) = &function.input_binding {
+                return Err(AsgError::program_input_shadowed(span)
+                    .with_label(binding, "`input` is declared here")
+                    .into());
+            }
+        }
+        match self.resolve_input() {
+            Some(input) => Ok(input.container),
+            None => Err(AsgError::illegal_input_variable_reference(span).into()),
+        }
+    }
+
     ///
     /// Returns a reference to the alias corresponding to the name.
     ///
@@ -151,6 +182,46 @@ impl<'a> Scope<'a> {
         }
     }
 
+    ///
+    /// Returns the circuit named on the left of a static access such as `Foo::new`, along with the alias
+    /// the name went through if it is not the name of the circuit itself.
+    ///
+    /// Aliases are resolved when they are declared, so an alias of an alias leads straight to the circuit.
+    ///
+    pub fn resolve_static_circuit(&self, name: &Identifier) -> Result<(&'a Circuit<'a>, Option<&'a Alias<'a>>)> {
+        if let Some(circuit) = self.resolve_circuit(&name.name) {
+            return Ok((circuit, None));
+        }
+        match self.resolve_alias(&name.name) {
+            Some(alias) => match &alias.represents {
+                Type::Circuit(circuit) => Ok((circuit, Some(alias))),
+                represents => Err(AsgError::unexpected_type("circuit", represents, &name.span).into()),
+            },
+            None => Err(AsgError::unresolved_circuit(&name.name, &name.span).into()),
+        }
+    }
+
+    ///
+    /// Returns the name of a visible circuit that is spelled similarly to the given name.
+    ///
+    /// Used to suggest a correction when a circuit cannot be resolved.
+    ///
+    pub fn suggest_circuit(&self, name: &str) -> Option<String> {
+        let max_distance = std::cmp::max(1, name.len() / 3);
+        let mut best: Option<(usize, String)> = None;
+        let mut scope = Some(self);
+        while let Some(current) = scope {
+            for candidate in current.circuits.borrow().keys() {
+                let distance = edit_distance(name, candidate);
+                if distance <= max_distance && best.as_ref().map(|(best, _)| distance < *best).unwrap_or(true) {
+                    best = Some((distance, candidate.clone()));
+                }
+            }
+            scope = current.parent_scope.get();
+        }
+        best.map(|(_, candidate)| candidate)
+    }
+
     ///
     /// Returns a reference to the global const definition statement corresponding to the name.
     ///
@@ -182,6 +253,7 @@ impl<'a> Scope<'a> {
             global_consts: RefCell::new(IndexMap::new()),
             function: Cell::new(None),
             input: Cell::new(None),
+            types: self.types.clone(),
         })
     }
 
@@ -189,7 +261,8 @@ impl<'a> Scope<'a> {
     /// Returns the type returned by the current scope.
     ///
     pub fn resolve_ast_type(&self, type_: &leo_ast::Type, span: &Span) -> Result<Type<'a>> {
-        use leo_ast::Type::*;
+        // `Err` is left out, so that it still names the variant of `Result`.
+        use leo_ast::Type::{Address, Array, Boolean, Char, Field, Group, Identifier, IntegerType, SelfType, Tuple};
         Ok(match type_ {
             Address => Type::Address,
             Boolean => Type::Boolean,
@@ -214,22 +287,34 @@ impl<'a> Scope<'a> {
 
                 *item
             }
-            Tuple(sub_types) => Type::Tuple(
-                sub_types
+            Tuple(sub_types) => {
+                let sub_types = sub_types
                     .iter()
                     .map(|x| self.resolve_ast_type(x, span))
-                    .collect::<Result<Vec<_>>>()?,
-            ),
-            SelfType => return Err(AsgError::unexpected_big_self(span).into()),
-            Identifier(name) => {
-                if let Some(circuit) = self.resolve_circuit(&name.name) {
-                    Type::Circuit(circuit)
-                } else if let Some(alias) = self.resolve_alias(&name.name) {
-                    alias.represents.clone()
-                } else {
-                    return Err(AsgError::unresolved_circuit(&name.name, &name.span).into());
+                    .collect::<Result<Vec<_>>>()?;
+                if sub_types.iter().any(Type::is_unit) {
+                    return Err(AsgError::unit_in_tuple(span).into());
                 }
+                Type::Tuple(sub_types)
             }
+            SelfType => return Err(AsgError::unexpected_big_self(span).into()),
+            Identifier(name) => self.types.resolve_identifier(self, name)?,
+            leo_ast::Type::Err(err) => return Err(AsgError::unresolved_placeholder(&err.span).into()),
         })
     }
 }
+
+/// Returns the Levenshtein distance between two names.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + if left_char == *right_char { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
@@ -0,0 +1,413 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Classification of the identifiers of a program by what they resolve to, for editor highlighting.
+
+use crate::{
+    DefinitionStatement, ExpressionVisitor, Function, IterationStatement, Program, ProgramVisitor, StatementVisitor,
+    Variable, VisitResult, VisitorDirector,
+};
+use leo_ast::{
+    is_generated_name, AssigneeAccess, AstNode, Block, CircuitMember, ConsoleFunction, Declare, Expression,
+    FunctionInput, Statement, Type,
+};
+use leo_errors::Span;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// What an identifier refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SemanticKind {
+    Function,
+    Parameter,
+    Local,
+    /// A global constant or a `const` definition.
+    Constant,
+    Circuit,
+    Alias,
+    /// A member variable or member function of a circuit.
+    CircuitMember,
+    LoopVariable,
+    /// The identifier does not resolve to anything in the program.
+    Unknown,
+}
+
+/// Properties of the variable an identifier refers to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SemanticModifiers {
+    pub mutable: bool,
+    pub constant: bool,
+    /// The variable is declared but never read.
+    pub unused: bool,
+}
+
+/// The classification of one identifier or member name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SemanticToken {
+    pub span: Span,
+    pub kind: SemanticKind,
+    pub modifiers: SemanticModifiers,
+}
+
+/// Collects whether each variable of the asg is mutable and read, keyed by the span of its declaration.
+#[derive(Default)]
+struct VariableCollector {
+    variables: IndexMap<Span, (bool, bool)>,
+}
+
+impl VariableCollector {
+    fn add(&mut self, variable: &Variable) {
+        let variable = variable.borrow();
+        self.variables.insert(
+            variable.name.span.clone(),
+            (variable.mutable, variable.references.is_empty()),
+        );
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for VariableCollector {}
+
+impl<'a> StatementVisitor<'a> for VariableCollector {
+    fn visit_definition(&mut self, input: &DefinitionStatement<'a>) -> VisitResult {
+        input.variables.iter().for_each(|variable| self.add(variable));
+        VisitResult::VisitChildren
+    }
+
+    fn visit_iteration(&mut self, input: &IterationStatement<'a>) -> VisitResult {
+        self.add(input.variable);
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for VariableCollector {
+    fn visit_function(&mut self, input: &'a Function<'a>) -> VisitResult {
+        input.arguments.values().for_each(|variable| self.add(variable.get()));
+        VisitResult::VisitChildren
+    }
+
+    fn visit_global_const(&mut self, input: &'a DefinitionStatement<'a>) -> VisitResult {
+        input.variables.iter().for_each(|variable| self.add(variable));
+        VisitResult::VisitChildren
+    }
+}
+
+/// Walks the ast, resolving identifiers against the enclosing scopes and the program's items.
+struct Classifier {
+    variables: IndexMap<Span, (bool, bool)>,
+    functions: IndexSet<String>,
+    circuits: IndexSet<String>,
+    aliases: IndexSet<String>,
+    scopes: Vec<IndexMap<String, (SemanticKind, SemanticModifiers)>>,
+    tokens: Vec<SemanticToken>,
+}
+
+impl Classifier {
+    fn collect_items(&mut self, program: &leo_ast::Program) {
+        self.functions
+            .extend(program.functions.keys().map(|name| name.name.to_string()));
+        self.circuits
+            .extend(program.circuits.keys().map(|name| name.name.to_string()));
+        self.aliases
+            .extend(program.aliases.keys().map(|name| name.name.to_string()));
+        for import in program.imports.values() {
+            self.collect_items(import);
+        }
+    }
+
+    fn push(&mut self, span: &Span, kind: SemanticKind, modifiers: SemanticModifiers) {
+        self.tokens.push(SemanticToken {
+            span: span.clone(),
+            kind,
+            modifiers,
+        });
+    }
+
+    /// Declares a variable in the innermost scope, taking its mutability and use from the asg.
+    fn declare(&mut self, identifier: &leo_ast::Identifier, kind: SemanticKind, constant: bool) {
+        let (mutable, unused) = self.variables.get(&identifier.span).copied().unwrap_or_default();
+        let modifiers = SemanticModifiers {
+            mutable,
+            constant,
+            unused,
+        };
+        // Variables added by passes are not the user's to highlight, used or not.
+        if !is_generated_name(&identifier.name) {
+            self.push(&identifier.span, kind, modifiers);
+        }
+        // References to the variable carry its modifiers, except that they are uses themselves.
+        let modifiers = SemanticModifiers {
+            unused: false,
+            ..modifiers
+        };
+        self.scopes
+            .last_mut()
+            .expect("no scope to declare in")
+            .insert(identifier.name.to_string(), (kind, modifiers));
+    }
+
+    fn resolve(&mut self, identifier: &leo_ast::Identifier) {
+        let name = identifier.name.as_ref();
+        // Keywords are highlighted lexically, address literals parse as identifiers, and generated names
+        // belong to passes rather than the user.
+        if name == "self" || name == "Self" || name.starts_with("aleo1") || is_generated_name(name) {
+            return;
+        }
+        // The program input is a keyword too, unless the function declares its own variable named `input`.
+        if name == "input" && !self.scopes[1..].iter().any(|scope| scope.contains_key(name)) {
+            return;
+        }
+
+        // Global constants take precedence over local variables, as in the asg.
+        let binding = self.scopes[0]
+            .get(name)
+            .or_else(|| self.scopes[1..].iter().rev().find_map(|scope| scope.get(name)));
+        let (kind, modifiers) = match binding {
+            Some(binding) => *binding,
+            None if self.functions.contains(name) => (SemanticKind::Function, Default::default()),
+            None if self.circuits.contains(name) => (SemanticKind::Circuit, Default::default()),
+            None if self.aliases.contains(name) => (SemanticKind::Alias, Default::default()),
+            None => (SemanticKind::Unknown, Default::default()),
+        };
+        self.push(&identifier.span, kind, modifiers);
+    }
+
+    fn visit_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Identifier(identifier) => self.resolve(identifier),
+            Type::Array(element, _) => self.visit_type(element),
+            Type::Tuple(elements) => elements.iter().for_each(|element| self.visit_type(element)),
+            _ => (),
+        }
+    }
+
+    fn visit_function(&mut self, function: &leo_ast::Function, kind: SemanticKind) {
+        self.push(&function.identifier.span, kind, Default::default());
+
+        self.scopes.push(IndexMap::new());
+        for input in function.input.iter() {
+            if let FunctionInput::Variable(variable) = input {
+                self.declare(&variable.identifier, SemanticKind::Parameter, variable.const_);
+                self.visit_type(&variable.type_);
+            }
+        }
+        if let Some(output) = &function.output {
+            self.visit_type(output);
+        }
+        self.visit_block(&function.block);
+        self.scopes.pop();
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.scopes.push(IndexMap::new());
+        block
+            .statements
+            .iter()
+            .for_each(|statement| self.visit_statement(statement));
+        self.scopes.pop();
+    }
+
+    fn visit_definition(&mut self, definition: &leo_ast::DefinitionStatement, global: bool) {
+        if let Some(value) = &definition.value {
+            self.visit_expression(value);
+        }
+        if let Some(type_) = &definition.type_ {
+            self.visit_type(type_);
+        }
+
+        let constant = global || definition.declaration_type == Declare::Const;
+        let kind = if constant {
+            SemanticKind::Constant
+        } else {
+            SemanticKind::Local
+        };
+        for variable in definition.variable_names.iter() {
+            self.declare(&variable.identifier, kind, constant);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Definition(definition) => self.visit_definition(definition, false),
+            Statement::Assign(assign) => {
+                self.resolve(&assign.assignee.identifier);
+                for access in assign.assignee.accesses.iter() {
+                    match access {
+                        AssigneeAccess::ArrayRange(left, right) => left
+                            .iter()
+                            .chain(right.iter())
+                            .for_each(|index| self.visit_expression(index)),
+                        AssigneeAccess::ArrayIndex(index) => self.visit_expression(index),
+                        AssigneeAccess::Member(member) => {
+                            self.push(&member.span, SemanticKind::CircuitMember, Default::default())
+                        }
+                        AssigneeAccess::Tuple(..) => (),
+                    }
+                }
+                self.visit_expression(&assign.value);
+            }
+            Statement::Conditional(conditional) => {
+                self.visit_expression(&conditional.condition);
+                self.visit_block(&conditional.block);
+                if let Some(next) = &conditional.next {
+                    self.visit_statement(next);
+                }
+            }
+            Statement::Iteration(iteration) => {
+                self.visit_expression(&iteration.start);
+                self.visit_expression(&iteration.stop);
+                if let Some(step) = &iteration.step {
+                    self.visit_expression(step);
+                }
+                self.scopes.push(IndexMap::new());
+                self.declare(&iteration.variable, SemanticKind::LoopVariable, true);
+                self.visit_block(&iteration.block);
+                self.scopes.pop();
+            }
+            Statement::Console(console) => match &console.function {
+                ConsoleFunction::Assert(expression) => self.visit_expression(expression),
+                ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                    self.visit_expression(&args.left);
+                    self.visit_expression(&args.right);
+                }
+                ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => args
+                    .parameters
+                    .iter()
+                    .for_each(|parameter| self.visit_expression(parameter)),
+            },
+            Statement::Return(return_) => self.visit_expression(&return_.expression),
+            Statement::Expression(expression) => self.visit_expression(&expression.expression),
+            Statement::Block(block) => self.visit_block(block),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.resolve(identifier),
+            Expression::CircuitMemberAccess(access) => {
+                self.visit_expression(&access.circuit);
+                self.push(&access.name.span, SemanticKind::CircuitMember, Default::default());
+            }
+            Expression::CircuitStaticFunctionAccess(access) => {
+                self.visit_expression(&access.circuit);
+                self.push(&access.name.span, SemanticKind::CircuitMember, Default::default());
+            }
+            Expression::CircuitInit(init) => {
+                self.resolve(&init.name);
+                for member in init.members.iter() {
+                    // A shorthand member names both the member and the variable it is initialized from.
+                    self.push(&member.identifier.span, SemanticKind::CircuitMember, Default::default());
+                    if let Some(expression) = &member.expression {
+                        self.visit_expression(expression);
+                    }
+                }
+            }
+            Expression::Cast(cast) => {
+                self.visit_expression(&cast.inner);
+                self.visit_type(&cast.target_type);
+            }
+            Expression::ArrayComprehension(comprehension) => {
+                self.visit_expression(&comprehension.start);
+                self.visit_expression(&comprehension.stop);
+                self.scopes.push(IndexMap::new());
+                self.declare(&comprehension.variable, SemanticKind::LoopVariable, true);
+                self.visit_expression(&comprehension.element);
+                self.scopes.pop();
+            }
+            expression => {
+                for child in AstNode::Expression(expression).children() {
+                    if let AstNode::Expression(child) = child {
+                        self.visit_expression(child);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Classifies every identifier and member name of `ast`.
+///
+/// The variable modifiers `mutable` and `unused` come from `asg`, which must have been built from `ast`.
+/// Without one, such as when the program does not type check, they are never set.
+///
+/// Tokens are ordered by span and do not overlap. Identifiers that do not resolve are kept with
+/// [`SemanticKind::Unknown`], while the `self`, `Self`, and `input` keywords and the names generated
+/// by passes are left out.
+pub fn semantic_tokens<'a>(ast: &leo_ast::Program, asg: Option<&Program<'a>>) -> Vec<SemanticToken> {
+    let mut director = VisitorDirector::new(VariableCollector::default());
+    if let Some(asg) = asg {
+        director.visit_program(asg).ok();
+    }
+
+    let mut classifier = Classifier {
+        variables: director.visitor().variables,
+        functions: IndexSet::new(),
+        circuits: IndexSet::new(),
+        aliases: IndexSet::new(),
+        scopes: vec![IndexMap::new()],
+        tokens: vec![],
+    };
+    classifier.collect_items(ast);
+
+    for definition in ast.global_consts.values() {
+        classifier.visit_definition(definition, true);
+    }
+    for alias in ast.aliases.values() {
+        classifier.push(&alias.name.span, SemanticKind::Alias, Default::default());
+        classifier.visit_type(&alias.represents);
+    }
+    for circuit in ast.circuits.values() {
+        classifier.push(&circuit.circuit_name.span, SemanticKind::Circuit, Default::default());
+        for member in circuit.members.iter() {
+            match member {
+                CircuitMember::CircuitVariable(name, type_) => {
+                    classifier.push(&name.span, SemanticKind::CircuitMember, Default::default());
+                    classifier.visit_type(type_);
+                }
+                CircuitMember::CircuitStatic(name, type_, value) => {
+                    let modifiers = SemanticModifiers {
+                        constant: true,
+                        ..Default::default()
+                    };
+                    classifier.push(&name.span, SemanticKind::CircuitMember, modifiers);
+                    classifier.visit_type(type_);
+                    classifier.visit_expression(value);
+                }
+                CircuitMember::CircuitFunction(function) => {
+                    classifier.visit_function(function, SemanticKind::CircuitMember)
+                }
+            }
+        }
+    }
+    for function in ast.functions.values() {
+        classifier.visit_function(function, SemanticKind::Function);
+    }
+
+    // Canonicalization can copy identifiers, such as the target of a compound assignment, so keep the
+    // first token at each position and drop tokens without a source location.
+    let mut tokens = classifier.tokens;
+    tokens.retain(|token| token.span.line_start > 0);
+    tokens.sort_by_key(|token| (token.span.line_start, token.span.col_start));
+    let mut classified: Vec<SemanticToken> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if let Some(last) = classified.last() {
+            if (token.span.line_start, token.span.col_start) < (last.span.line_stop, last.span.col_stop) {
+                continue;
+            }
+        }
+        classified.push(token);
+    }
+    classified
+}
@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    CircuitMember, ConstInt, ConstValue, Expression, ExpressionNode, FromAst, Identifier, IntegerType, Node,
+    index_from_ast, CircuitMember, ConstInt, ConstValue, Expression, ExpressionNode, FromAst, Identifier, Node,
     PartialType, Scope, Statement, Type, Variable,
 };
 pub use leo_ast::AssignOperation;
@@ -60,17 +60,16 @@ impl<'a> FromAst<'a, leo_ast::AssignStatement> for &'a Statement<'a> {
         );
 
         let variable = if name.as_ref() == "input" {
-            if let Some(input) = scope.resolve_input() {
-                input.container
-            } else {
-                return Err(AsgError::illegal_input_variable_reference(&statement.span).into());
-            }
+            scope.resolve_input_variable(&statement.span)?
         } else {
             scope
                 .resolve_variable(name)
                 .ok_or_else(|| AsgError::unresolved_reference(name, span))?
         };
 
+        if variable.borrow().const_ {
+            return Err(AsgError::const_parameter_assignment(name, &statement.span).into());
+        }
         if !variable.borrow().mutable {
             return Err(AsgError::immutable_assignment(name, &statement.span).into());
         }
@@ -80,19 +79,8 @@ impl<'a> FromAst<'a, leo_ast::AssignStatement> for &'a Statement<'a> {
         for access in statement.assignee.accesses.iter() {
             target_accesses.push(match access {
                 AstAssigneeAccess::ArrayRange(left, right) => {
-                    let index_type = Some(PartialType::Integer(None, Some(IntegerType::U32)));
-                    let left = left
-                        .as_ref()
-                        .map(|left: &leo_ast::Expression| -> Result<&'a Expression<'a>> {
-                            <&Expression<'a>>::from_ast(scope, left, index_type.clone())
-                        })
-                        .transpose()?;
-                    let right = right
-                        .as_ref()
-                        .map(|right: &leo_ast::Expression| -> Result<&'a Expression<'a>> {
-                            <&Expression<'a>>::from_ast(scope, right, index_type)
-                        })
-                        .transpose()?;
+                    let left = left.as_ref().map(|left| index_from_ast(scope, left)).transpose()?;
+                    let right = right.as_ref().map(|right| index_from_ast(scope, right)).transpose()?;
 
                     match &target_type {
                         Some(PartialType::Array(item, len)) => {
@@ -140,11 +128,7 @@ impl<'a> FromAst<'a, leo_ast::AssignStatement> for &'a Statement<'a> {
                         Some(PartialType::Array(item, _)) => item.map(|x| *x),
                         _ => return Err(AsgError::index_into_non_array(name, &statement.span).into()),
                     };
-                    AssignAccess::ArrayIndex(Cell::new(<&Expression<'a>>::from_ast(
-                        scope,
-                        index,
-                        Some(PartialType::Integer(None, Some(IntegerType::U32))),
-                    )?))
+                    AssignAccess::ArrayIndex(Cell::new(index_from_ast(scope, index)?))
                 }
                 AstAssigneeAccess::Tuple(index, span) => {
                     let index = index
@@ -176,6 +160,14 @@ impl<'a> FromAst<'a, leo_ast::AssignStatement> for &'a Statement<'a> {
 
                             let x = match &member {
                                 CircuitMember::Variable(type_) => type_.clone(),
+                                CircuitMember::Static(..) => {
+                                    return Err(AsgError::static_member_accessed_through_instance(
+                                        &circuit.name.borrow().name,
+                                        &name.name,
+                                        &statement.span,
+                                    )
+                                    .into());
+                                }
                                 CircuitMember::Function(_) => {
                                     return Err(AsgError::illegal_function_assign(&name.name, &statement.span).into());
                                 }
@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{CharValue, Expression, FromAst, Node, PartialType, Scope, Statement, Type};
-use leo_ast::ConsoleFunction as AstConsoleFunction;
+use crate::{BinaryExpression, CharValue, Expression, FromAst, Node, PartialType, Scope, Statement, Type};
+use leo_ast::{BinaryOperation, ConsoleFunction as AstConsoleFunction};
 use leo_errors::{Result, Span};
 
 use std::cell::Cell;
@@ -28,9 +28,19 @@ pub struct ConsoleArgs<'a> {
     pub span: Span,
 }
 
+/// The operands of `console.assert_eq` and `console.assert_ne`, which are typed as the operands of `==`.
+#[derive(Clone)]
+pub struct ConsoleAssertArgs<'a> {
+    pub left: Cell<&'a Expression<'a>>,
+    pub right: Cell<&'a Expression<'a>>,
+    pub span: Span,
+}
+
 #[derive(Clone)]
 pub enum ConsoleFunction<'a> {
     Assert(Cell<&'a Expression<'a>>),
+    AssertEq(ConsoleAssertArgs<'a>),
+    AssertNe(ConsoleAssertArgs<'a>),
     Error(ConsoleArgs<'a>),
     Log(ConsoleArgs<'a>),
 }
@@ -76,6 +86,40 @@ impl<'a> Into<leo_ast::ConsoleArgs> for &ConsoleArgs<'a> {
     }
 }
 
+impl<'a> FromAst<'a, leo_ast::ConsoleAssertArgs> for ConsoleAssertArgs<'a> {
+    fn from_ast(
+        scope: &'a Scope<'a>,
+        value: &leo_ast::ConsoleAssertArgs,
+        _expected_type: Option<PartialType<'a>>,
+    ) -> Result<Self> {
+        let comparison = BinaryExpression::from_ast(
+            scope,
+            &leo_ast::BinaryExpression {
+                left: Box::new(value.left.clone()),
+                right: Box::new(value.right.clone()),
+                op: BinaryOperation::Eq,
+                span: value.span.clone(),
+            },
+            None,
+        )?;
+        Ok(ConsoleAssertArgs {
+            left: comparison.left,
+            right: comparison.right,
+            span: value.span.clone(),
+        })
+    }
+}
+
+impl<'a> Into<leo_ast::ConsoleAssertArgs> for &ConsoleAssertArgs<'a> {
+    fn into(self) -> leo_ast::ConsoleAssertArgs {
+        leo_ast::ConsoleAssertArgs {
+            left: self.left.get().into(),
+            right: self.right.get().into(),
+            span: self.span.clone(),
+        }
+    }
+}
+
 impl<'a> FromAst<'a, leo_ast::ConsoleStatement> for ConsoleStatement<'a> {
     fn from_ast(
         scope: &'a Scope<'a>,
@@ -89,6 +133,12 @@ impl<'a> FromAst<'a, leo_ast::ConsoleStatement> for ConsoleStatement<'a> {
                 AstConsoleFunction::Assert(expression) => ConsoleFunction::Assert(Cell::new(
                     <&Expression<'a>>::from_ast(scope, expression, Some(Type::Boolean.into()))?,
                 )),
+                AstConsoleFunction::AssertEq(args) => {
+                    ConsoleFunction::AssertEq(ConsoleAssertArgs::from_ast(scope, args, None)?)
+                }
+                AstConsoleFunction::AssertNe(args) => {
+                    ConsoleFunction::AssertNe(ConsoleAssertArgs::from_ast(scope, args, None)?)
+                }
                 AstConsoleFunction::Error(args) => ConsoleFunction::Error(ConsoleArgs::from_ast(scope, args, None)?),
                 AstConsoleFunction::Log(args) => ConsoleFunction::Log(ConsoleArgs::from_ast(scope, args, None)?),
             },
@@ -102,6 +152,8 @@ impl<'a> Into<leo_ast::ConsoleStatement> for &ConsoleStatement<'a> {
         leo_ast::ConsoleStatement {
             function: match &self.function {
                 Assert(e) => AstConsoleFunction::Assert(e.get().into()),
+                AssertEq(args) => AstConsoleFunction::AssertEq(args.into()),
+                AssertNe(args) => AstConsoleFunction::AssertNe(args.into()),
                 Error(args) => AstConsoleFunction::Error(args.into()),
                 Log(args) => AstConsoleFunction::Log(args.into()),
             },
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, ExpressionNode, FromAst, InnerVariable, Node, PartialType, Scope, Statement, Type, Variable};
+use crate::{
+    ConstValue, Constant, Expression, ExpressionNode, FromAst, InnerVariable, Node, PartialType, Scope, Statement,
+    Type, Variable,
+};
 use leo_errors::{AsgError, Result, Span};
 
 use std::cell::{Cell, RefCell};
@@ -25,6 +28,8 @@ pub struct DefinitionStatement<'a> {
     pub span: Option<Span>,
     pub variables: Vec<&'a Variable<'a>>,
     pub value: Cell<&'a Expression<'a>>,
+    /// `false` for `let a: u32;`, whose value is the default of its type until it is assigned.
+    pub initialized: bool,
 }
 
 impl<'a> DefinitionStatement<'a> {
@@ -39,6 +44,7 @@ impl<'a> DefinitionStatement<'a> {
                         span: self.span.clone(),
                         variables: vec![variable],
                         value: self.value.clone(),
+                        initialized: self.initialized,
                     },
                 )
             })
@@ -64,7 +70,25 @@ impl<'a> FromAst<'a, leo_ast::DefinitionStatement> for &'a Statement<'a> {
             .map(|x| scope.resolve_ast_type(x, &statement.span))
             .transpose()?;
 
-        let value = <&Expression<'a>>::from_ast(scope, &statement.value, type_.clone().map(Into::into))?;
+        let value = match (&statement.value, &statement.declaration_type, &type_) {
+            (Some(value), _, _) => <&Expression<'a>>::from_ast(scope, value, type_.clone().map(Into::into))?,
+            (None, leo_ast::Declare::Let, Some(type_)) => {
+                let value = ConstValue::default_of(type_)
+                    .ok_or_else(|| AsgError::uninitialized_declaration_type(type_, &statement.span))?;
+                scope.context.alloc_expression(Expression::Constant(Constant {
+                    parent: Cell::new(None),
+                    span: Some(statement.span.clone()),
+                    value,
+                }))
+            }
+            (None, _, _) => {
+                return Err(AsgError::illegal_ast_structure(
+                    "only a `let` with a type can be declared without a value",
+                    &statement.span,
+                )
+                .into());
+            }
+        };
 
         if matches!(statement.declaration_type, leo_ast::Declare::Const) && !value.is_consty() {
             let var_names = statement
@@ -98,6 +122,26 @@ impl<'a> FromAst<'a, leo_ast::DefinitionStatement> for &'a Statement<'a> {
                 Some(Type::Tuple(sub_types)) if sub_types.len() == statement.variable_names.len() => {
                     output_types.extend(sub_types.clone().into_iter().map(Some).collect::<Vec<_>>());
                 }
+                Some(type_) => {
+                    let (source, found) = match value {
+                        Expression::Call(call) => {
+                            let function = call.function.get();
+                            let name = function.name.borrow().name.to_string();
+                            (format!("call to '{}'", name), function.outputs().len())
+                        }
+                        _ => match type_ {
+                            Type::Tuple(sub_types) => ("tuple value".to_string(), sub_types.len()),
+                            _ => (format!("value of type '{}'", type_), 1),
+                        },
+                    };
+                    return Err(AsgError::destructuring_arity_mismatch(
+                        source,
+                        found,
+                        statement.variable_names.len(),
+                        &statement.span,
+                    )
+                    .into());
+                }
                 type_ => {
                     return Err(AsgError::unexpected_type(
                         format!("{}-ary tuple", statement.variable_names.len()),
@@ -152,6 +196,7 @@ impl<'a> FromAst<'a, leo_ast::DefinitionStatement> for &'a Statement<'a> {
                 span: Some(statement.span.clone()),
                 variables: variables.clone(),
                 value: Cell::new(value),
+                initialized: statement.value.is_some(),
             }));
 
         for variable in variables {
@@ -184,7 +229,11 @@ impl<'a> Into<leo_ast::DefinitionStatement> for &DefinitionStatement<'a> {
             declaration_type: leo_ast::Declare::Let,
             variable_names,
             type_,
-            value: self.value.get().into(),
+            value: if self.initialized {
+                Some(self.value.get().into())
+            } else {
+                None
+            },
             span: self.span.clone().unwrap_or_default(),
         }
     }
@@ -16,7 +16,9 @@
 
 use leo_ast::IntegerType;
 
-use crate::{Expression, ExpressionNode, FromAst, InnerVariable, Node, PartialType, Scope, Statement, Variable};
+use crate::{
+    ConstValue, Expression, ExpressionNode, FromAst, InnerVariable, Node, PartialType, Scope, Statement, Type, Variable,
+};
 use leo_errors::{AsgError, Result, Span};
 
 use std::cell::{Cell, RefCell};
@@ -29,6 +31,8 @@ pub struct IterationStatement<'a> {
     pub start: Cell<&'a Expression<'a>>,
     pub stop: Cell<&'a Expression<'a>>,
     pub inclusive: bool,
+    /// The amount added to the counter after every iteration, a nonzero constant of the type of the counter.
+    pub step: Cell<Option<&'a Expression<'a>>>,
     pub body: Cell<&'a Statement<'a>>,
 }
 
@@ -38,15 +42,81 @@ impl<'a> Node for IterationStatement<'a> {
     }
 }
 
+impl<'a> IterationStatement<'a> {
+    ///
+    /// Returns the values the counter takes, given the values of the bounds and of the step.
+    ///
+    /// The counter starts at `from` and the step is added to it for as long as it is within the bounds,
+    /// so it does not have to reach `to`. Without a step, the counter counts by one towards `to`.
+    ///
+    pub fn counters(&self, from: i128, to: i128, step: Option<i128>) -> Result<Box<dyn Iterator<Item = i128>>> {
+        let step = match step {
+            Some(step) => {
+                let span = self
+                    .step
+                    .get()
+                    .and_then(|step| step.span().cloned())
+                    .or_else(|| self.span.clone());
+                check_step(from, to, step, &span.unwrap_or_default())?;
+                step
+            }
+            None if from <= to => 1,
+            None => -1,
+        };
+        let inclusive = self.inclusive;
+        let within = move |i: &i128| match (step > 0, inclusive) {
+            (true, true) => *i <= to,
+            (true, false) => *i < to,
+            (false, true) => *i >= to,
+            (false, false) => *i > to,
+        };
+        Ok(Box::new(
+            std::iter::successors(Some(from), move |i| i.checked_add(step)).take_while(within),
+        ))
+    }
+}
+
+/// Checks that the step of a loop from `from` to `to` is not zero and heads towards `to`.
+fn check_step(from: i128, to: i128, step: i128, span: &Span) -> Result<()> {
+    if step == 0 {
+        return Err(AsgError::loop_step_zero(span).into());
+    }
+    if (step > 0 && from > to) || (step < 0 && from < to) {
+        return Err(AsgError::loop_step_wrong_sign(step, from, to, span).into());
+    }
+    Ok(())
+}
+
+/// Returns the value of a constant integer, if it fits in an `i128`.
+fn const_i128<'a>(expression: &'a Expression<'a>) -> Option<i128> {
+    match expression.const_value()? {
+        ConstValue::Int(value) => value.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
 impl<'a> FromAst<'a, leo_ast::IterationStatement> for &'a Statement<'a> {
     fn from_ast(
         scope: &'a Scope<'a>,
         statement: &leo_ast::IterationStatement,
         _expected_type: Option<PartialType<'a>>,
     ) -> Result<Self> {
-        let expected_index_type = Some(PartialType::Integer(Some(IntegerType::U32), None));
-        let start = <&Expression<'a>>::from_ast(scope, &statement.start, expected_index_type.clone())?;
-        let stop = <&Expression<'a>>::from_ast(scope, &statement.stop, expected_index_type)?;
+        // The loop variable takes the integer type of the start bound, defaulting to u32.
+        // The stop bound may be of another integer type; the unroller checks that every counter value fits.
+        let start = <&Expression<'a>>::from_ast(
+            scope,
+            &statement.start,
+            Some(PartialType::Integer(None, Some(IntegerType::U32))),
+        )?;
+        let index_type = match start.get_type() {
+            Some(Type::Integer(index_type)) => index_type,
+            _ => IntegerType::U32,
+        };
+        let stop = <&Expression<'a>>::from_ast(
+            scope,
+            &statement.stop,
+            Some(PartialType::Integer(None, Some(index_type.clone()))),
+        )?;
 
         // Return an error if start or stop is not constant.
         if !start.is_consty() {
@@ -56,6 +126,29 @@ impl<'a> FromAst<'a, leo_ast::IterationStatement> for &'a Statement<'a> {
             return Err(AsgError::unexpected_nonconst(&stop.span().cloned().unwrap_or_default()).into());
         }
 
+        // The step is a constant of the type of the counter, heading towards the stop bound.
+        let step = match &statement.step {
+            Some(step) => {
+                let step = <&Expression<'a>>::from_ast(
+                    scope,
+                    step,
+                    Some(PartialType::Integer(Some(index_type.clone()), None)),
+                )?;
+                let span = step.span().cloned().unwrap_or_default();
+                if !step.is_consty() {
+                    return Err(AsgError::unexpected_nonconst(&span).into());
+                }
+                // Bounds that depend on an enclosing loop are only known when it is unrolled.
+                match (const_i128(start), const_i128(stop), const_i128(step)) {
+                    (Some(from), Some(to), Some(value)) => check_step(from, to, value, &span)?,
+                    (_, _, Some(0)) => return Err(AsgError::loop_step_zero(&span).into()),
+                    _ => (),
+                }
+                Some(step)
+            }
+            None => None,
+        };
+
         let variable = scope.context.alloc_variable(RefCell::new(InnerVariable {
             id: scope.context.get_id(),
             name: statement.variable.clone(),
@@ -80,6 +173,7 @@ impl<'a> FromAst<'a, leo_ast::IterationStatement> for &'a Statement<'a> {
             stop: Cell::new(stop),
             start: Cell::new(start),
             inclusive: statement.inclusive,
+            step: Cell::new(step),
             body: Cell::new(
                 scope
                     .context
@@ -102,6 +196,7 @@ impl<'a> Into<leo_ast::IterationStatement> for &IterationStatement<'a> {
             start: self.start.get().into(),
             stop: self.stop.get().into(),
             inclusive: self.inclusive,
+            step: self.step.get().map(|step| step.into()),
             block: match self.body.get() {
                 Statement::Block(block) => block.into(),
                 _ => unimplemented!(),
@@ -125,7 +125,11 @@ impl<'a> Into<leo_ast::Statement> for &Statement<'a> {
             Console(statement) => leo_ast::Statement::Console(statement.into()),
             Expression(statement) => leo_ast::Statement::Expression(statement.into()),
             Block(statement) => leo_ast::Statement::Block(statement.into()),
-            Empty(_) => unimplemented!(),
+            // Passes leave an empty statement where they remove one, which is written as an empty block.
+            Empty(span) => leo_ast::Statement::Block(leo_ast::Block {
+                statements: vec![],
+                span: span.clone().unwrap_or_default(),
+            }),
         }
     }
 }
@@ -0,0 +1,173 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A per-function index of the type and constness of every expression, for tools like editors.
+
+use crate::{
+    CircuitMember, ConstValue, Expression, ExpressionNode, ExpressionVisitor, Function, Identifier, Node, Program,
+    ProgramVisitor, StatementVisitor, Type, VisitResult, VisitorDirector,
+};
+use leo_errors::Span;
+
+use indexmap::IndexMap;
+use std::{cell::Cell, mem::size_of};
+
+/// How much of an expression's value is known at compile time.
+#[derive(Clone, PartialEq)]
+pub enum Constness<'a> {
+    /// The value is known.
+    Const(ConstValue<'a>),
+    /// The value only depends on constants, but is not known until the function is called,
+    /// such as an expression over a const parameter or a loop variable.
+    ConstFoldable,
+    /// The value depends on values that are only known when the circuit is synthesized.
+    Runtime,
+}
+
+/// The inferred type and constness of an expression.
+#[derive(Clone)]
+pub struct ExpressionSymbol<'a> {
+    /// The type of the expression, if it has one.
+    pub type_: Option<Type<'a>>,
+    pub constness: Constness<'a>,
+    pub span: Span,
+}
+
+impl<'a> ExpressionSymbol<'a> {
+    fn new(expression: &'a Expression<'a>, span: &Span) -> Self {
+        let constness = match expression.const_value() {
+            Some(value) => Constness::Const(value),
+            None if expression.is_consty() => Constness::ConstFoldable,
+            None => Constness::Runtime,
+        };
+
+        ExpressionSymbol {
+            type_: expression.get_type(),
+            constness,
+            span: span.clone(),
+        }
+    }
+}
+
+/// Collects a symbol for every expression with a span, outermost expressions first.
+#[derive(Default)]
+struct ExpressionSymbolCollector<'a> {
+    symbols: Vec<ExpressionSymbol<'a>>,
+}
+
+impl<'a> ExpressionVisitor<'a> for ExpressionSymbolCollector<'a> {
+    fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        let expression = input.get();
+        if let Some(span) = expression.span() {
+            self.symbols.push(ExpressionSymbol::new(expression, span));
+        }
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for ExpressionSymbolCollector<'a> {}
+
+impl<'a> ProgramVisitor<'a> for ExpressionSymbolCollector<'a> {}
+
+/// The expression symbols of every function of a program.
+///
+/// Circuit member functions are keyed as `Circuit::function`.
+pub struct SymbolTable<'a> {
+    functions: IndexMap<String, Vec<ExpressionSymbol<'a>>>,
+}
+
+impl<'a> SymbolTable<'a> {
+    pub fn new(program: &Program<'a>) -> Self {
+        let mut functions = IndexMap::new();
+
+        for (name, function) in program.functions.iter() {
+            functions.insert(name.clone(), Self::collect(function));
+        }
+        for (circuit_name, circuit) in program.circuits.iter() {
+            for (name, member) in circuit.members.borrow().iter() {
+                if let CircuitMember::Function(function) = member {
+                    functions.insert(format!("{}::{}", circuit_name, name), Self::collect(function));
+                }
+            }
+        }
+
+        SymbolTable { functions }
+    }
+
+    fn collect(function: &'a Function<'a>) -> Vec<ExpressionSymbol<'a>> {
+        let mut director = VisitorDirector::new(ExpressionSymbolCollector::default());
+        director.visit_function(function).ok();
+        director.visitor().symbols
+    }
+
+    /// Returns the symbols of every expression in the given function, outermost expressions first.
+    pub fn expressions(&self, function: &str) -> &[ExpressionSymbol<'a>] {
+        self.functions.get(function).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the symbols of every expression of the program, function by function.
+    pub fn all_expressions(&self) -> impl Iterator<Item = &ExpressionSymbol<'a>> {
+        self.functions.values().flatten()
+    }
+
+    /// Returns the symbol of the outermost expression in the given function that covers exactly the given span.
+    pub fn expr_at(&self, function: &str, span: &Span) -> Option<&ExpressionSymbol<'a>> {
+        self.expressions(function).iter().find(|symbol| {
+            symbol.span.line_start == span.line_start
+                && symbol.span.col_start == span.col_start
+                && symbol.span.line_stop == span.line_stop
+                && symbol.span.col_stop == span.col_stop
+        })
+    }
+
+    /// Returns the approximate number of bytes held by the table.
+    ///
+    /// Symbols are counted at their inline size plus the elements of compound constants;
+    /// types and spans are shared with the ASG and not counted again.
+    pub fn deep_size(&self) -> usize {
+        self.functions
+            .iter()
+            .map(|(name, symbols)| {
+                name.len()
+                    + symbols
+                        .iter()
+                        .map(|symbol| {
+                            size_of::<ExpressionSymbol>()
+                                + match &symbol.constness {
+                                    Constness::Const(value) => const_value_size(value),
+                                    _ => 0,
+                                }
+                        })
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+/// Returns the heap size of the elements of a compound constant.
+fn const_value_size(value: &ConstValue) -> usize {
+    match value {
+        ConstValue::Tuple(values) | ConstValue::Array(values) => values
+            .iter()
+            .map(|value| size_of::<ConstValue>() + const_value_size(value))
+            .sum(),
+        ConstValue::Circuit(_, members) => members
+            .iter()
+            .map(|(name, (_, value))| name.len() + size_of::<(Identifier, ConstValue)>() + const_value_size(value))
+            .sum(),
+        _ => 0,
+    }
+}
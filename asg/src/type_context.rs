@@ -0,0 +1,287 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resolution of type names and sizes of types, computed once per program.
+
+use crate::{Alias, Circuit, CircuitMember, Identifier, Scope, Type};
+use leo_errors::{AsgError, Result, Span};
+
+use indexmap::IndexMap;
+use std::cell::{Cell, RefCell};
+
+/// Counts the queries of a [`TypeContext`], and how many were answered from its caches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TypeCacheStatistics {
+    pub identifier_hits: usize,
+    pub identifier_misses: usize,
+    pub size_hits: usize,
+    pub size_misses: usize,
+}
+
+impl TypeCacheStatistics {
+    /// Returns the number of queries answered from the caches.
+    pub fn hits(&self) -> usize {
+        self.identifier_hits + self.size_hits
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum CircuitSize {
+    Known(usize),
+    /// The circuit contains itself. The cycle was reported when it was found.
+    Poisoned,
+}
+
+///
+/// Resolves the type names of a program and computes the sizes of its types, remembering both.
+///
+/// Resolved names are only cached once every alias and circuit of the program is declared, see
+/// [`TypeContext::seal`]. Until then, the aliases of the program are declared as they are first
+/// named, so an alias may name another alias declared after it.
+///
+#[derive(Default)]
+pub struct TypeContext<'a> {
+    /// The scope the aliases of the program are declared in.
+    program_scope: Cell<Option<&'a Scope<'a>>>,
+    /// The aliases of the program that are not yet declared.
+    pending_aliases: RefCell<IndexMap<String, leo_ast::Alias>>,
+    sealed: Cell<bool>,
+    identifiers: RefCell<IndexMap<String, Type<'a>>>,
+    /// Maps circuit id => flattened size.
+    sizes: RefCell<IndexMap<u32, CircuitSize>>,
+    statistics: Cell<TypeCacheStatistics>,
+}
+
+impl<'a> TypeContext<'a> {
+    ///
+    /// Prepares the aliases of the program to be declared in `scope`, each when it is first named.
+    ///
+    pub(crate) fn begin_aliases<'b>(
+        &self,
+        scope: &'a Scope<'a>,
+        aliases: impl IntoIterator<Item = &'b leo_ast::Alias>,
+    ) {
+        self.program_scope.set(Some(scope));
+        self.pending_aliases.borrow_mut().extend(
+            aliases
+                .into_iter()
+                .map(|alias| (alias.name.name.to_string(), alias.clone())),
+        );
+    }
+
+    ///
+    /// Returns the alias of the program with the given name, declaring it first if it was not yet.
+    ///
+    /// Returns `None` if the program has no such alias. If the type the alias represents cannot be
+    /// resolved yet, the error is returned and the alias stays pending.
+    ///
+    pub(crate) fn declare_alias(&self, name: &str) -> Result<Option<&'a Alias<'a>>> {
+        let scope = match self.program_scope.get() {
+            Some(scope) => scope,
+            None => return Ok(None),
+        };
+        if let Some(alias) = scope.aliases.borrow().get(name) {
+            return Ok(Some(*alias));
+        }
+        let value = match self.pending_aliases.borrow_mut().remove(name) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        match Alias::init(scope, &value) {
+            Ok(alias) => {
+                scope.aliases.borrow_mut().insert(name.to_string(), alias);
+                Ok(Some(alias))
+            }
+            Err(error) => {
+                self.pending_aliases.borrow_mut().insert(name.to_string(), value);
+                Err(error)
+            }
+        }
+    }
+
+    ///
+    /// Marks every alias and circuit of the program as declared, so resolved names are cached from now on.
+    ///
+    pub(crate) fn seal(&self) {
+        self.sealed.set(true);
+        self.pending_aliases.borrow_mut().clear();
+    }
+
+    ///
+    /// Returns the circuit or aliased type that `name` refers to in `scope`.
+    ///
+    pub fn resolve_identifier(&self, scope: &Scope<'a>, name: &Identifier) -> Result<Type<'a>> {
+        if self.sealed.get() {
+            if let Some(type_) = self.identifiers.borrow().get(name.name.as_ref()) {
+                self.count(|statistics| statistics.identifier_hits += 1);
+                return Ok(type_.clone());
+            }
+        }
+        self.count(|statistics| statistics.identifier_misses += 1);
+
+        let type_ = if let Some(circuit) = scope.resolve_circuit(&name.name) {
+            Type::Circuit(circuit)
+        } else if let Some(alias) = scope.resolve_alias(&name.name) {
+            alias.represents.clone()
+        } else if let Some(alias) = self.declare_alias(&name.name)? {
+            alias.represents.clone()
+        } else {
+            return Err(AsgError::unresolved_circuit(&name.name, &name.span).into());
+        };
+
+        if self.sealed.get() {
+            self.identifiers
+                .borrow_mut()
+                .insert(name.name.to_string(), type_.clone());
+        }
+        Ok(type_)
+    }
+
+    ///
+    /// Returns the number of scalar slots `type_` flattens to, as [`crate::Layout::slot_count`] does,
+    /// remembering the size of every circuit it contains.
+    ///
+    /// The first query that finds a circuit containing itself returns an error naming the circuits of
+    /// the cycle. Every later query of a type containing one of them returns `None`.
+    ///
+    pub fn slot_count(&self, type_: &Type<'a>, span: &Span) -> Result<Option<usize>> {
+        let mut circuits = vec![];
+        contained_circuits(type_, &mut circuits);
+        for circuit in circuits {
+            if self.circuit_size(circuit, span)?.is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(self.known_size(type_))
+    }
+
+    /// Returns the number of queries so far, and how many were answered from the caches.
+    pub fn statistics(&self) -> TypeCacheStatistics {
+        self.statistics.get()
+    }
+
+    fn count(&self, update: impl FnOnce(&mut TypeCacheStatistics)) {
+        let mut statistics = self.statistics.get();
+        update(&mut statistics);
+        self.statistics.set(statistics);
+    }
+
+    ///
+    /// Computes the size of `root` and of every circuit it contains, depth first with an explicit stack.
+    ///
+    fn circuit_size(&self, root: &'a Circuit<'a>, span: &Span) -> Result<Option<usize>> {
+        if let Some(size) = self.sizes.borrow().get(&root.id) {
+            self.count(|statistics| statistics.size_hits += 1);
+            return Ok(match size {
+                CircuitSize::Known(size) => Some(*size),
+                CircuitSize::Poisoned => None,
+            });
+        }
+        self.count(|statistics| statistics.size_misses += 1);
+
+        // Each circuit on the stack contains the next one.
+        let mut stack = vec![root];
+        while let Some(&circuit) = stack.last() {
+            let mut members = vec![];
+            for member in circuit.members.borrow().values() {
+                if let CircuitMember::Variable(member_type) = member {
+                    contained_circuits(member_type, &mut members);
+                }
+            }
+
+            let unsized_member = members
+                .into_iter()
+                .find(|member| !self.sizes.borrow().contains_key(&member.id));
+            match unsized_member {
+                Some(member) => {
+                    if let Some(start) = stack.iter().position(|circuit| circuit.id == member.id) {
+                        let path = stack[start..]
+                            .iter()
+                            .chain(std::iter::once(&member))
+                            .map(|circuit| format!("`{}`", circuit.name.borrow().name))
+                            .collect::<Vec<_>>()
+                            .join(" -> ");
+                        self.poison(&stack);
+                        return Err(AsgError::circuit_cycle(&member.name.borrow().name, path, span).into());
+                    }
+                    stack.push(member);
+                }
+                None => match self.members_size(circuit) {
+                    Some(size) => {
+                        self.sizes.borrow_mut().insert(circuit.id, CircuitSize::Known(size));
+                        stack.pop();
+                    }
+                    None => {
+                        // A member contains a cycle that was already reported.
+                        self.poison(&stack);
+                        return Ok(None);
+                    }
+                },
+            }
+        }
+
+        Ok(self.known_size(&Type::Circuit(root)))
+    }
+
+    fn poison(&self, circuits: &[&'a Circuit<'a>]) {
+        let mut sizes = self.sizes.borrow_mut();
+        for circuit in circuits {
+            sizes.insert(circuit.id, CircuitSize::Poisoned);
+        }
+    }
+
+    /// Returns the size of the member variables of a circuit whose contained circuits are all sized.
+    fn members_size(&self, circuit: &'a Circuit<'a>) -> Option<usize> {
+        circuit
+            .members
+            .borrow()
+            .values()
+            .try_fold(0usize, |count, member| match member {
+                CircuitMember::Variable(member_type) => Some(count.saturating_add(self.known_size(member_type)?)),
+                CircuitMember::Static(..) | CircuitMember::Function(_) => Some(count),
+            })
+    }
+
+    /// Returns the size of a type whose contained circuits are all sized, or `None` if one is poisoned.
+    fn known_size(&self, type_: &Type<'a>) -> Option<usize> {
+        match type_ {
+            Type::Array(element, len) => Some(self.known_size(element)?.saturating_mul(*len)),
+            Type::Tuple(elements) => elements.iter().try_fold(0usize, |count, element| {
+                Some(count.saturating_add(self.known_size(element)?))
+            }),
+            Type::Circuit(circuit) => match self.sizes.borrow().get(&circuit.id) {
+                Some(CircuitSize::Known(size)) => Some(*size),
+                _ => None,
+            },
+            _ => Some(1),
+        }
+    }
+}
+
+/// Collects the circuits that `type_` holds directly or through arrays and tuples.
+fn contained_circuits<'a>(type_: &Type<'a>, circuits: &mut Vec<&'a Circuit<'a>>) {
+    match type_ {
+        Type::Array(element, _) | Type::ArrayWithoutSize(element) => contained_circuits(element, circuits),
+        Type::Tuple(elements) => {
+            for element in elements {
+                contained_circuits(element, circuits);
+            }
+        }
+        Type::Circuit(circuit) => circuits.push(circuit),
+        _ => {}
+    }
+}
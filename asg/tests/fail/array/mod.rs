@@ -14,7 +14,20 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg};
+use leo_errors::{expect_errors, AsgError, LeoError};
+
+/// Returns the line, column and message of every secondary label of an ASG error.
+fn labels(error: &LeoError) -> Vec<(usize, usize, &str)> {
+    match error {
+        LeoError::AsgError(AsgError::FormattedError(error)) => error
+            .labels
+            .iter()
+            .map(|label| (label.span.line_start, label.span.col_start, label.message.as_str()))
+            .collect(),
+        _ => vec![],
+    }
+}
 
 // Expressions
 
@@ -133,3 +146,114 @@ fn test_type_tuple_value_tuple_4x3x2_fail() {
     let program_string = include_str!("type_tuple_value_tuple_4x3x2_fail.leo");
     load_asg(program_string).err().unwrap();
 }
+
+#[test]
+fn test_from_bits_wrong_width() {
+    let program_string = r#"
+function main() {
+    let x: u8 = from_bits_le([true, false, true, false]);
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373056", 3, 17)]);
+}
+
+#[test]
+fn test_range_reversed_bounds() {
+    let program_string = r#"
+function main() {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[3..1];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373062", 4, 15)]);
+}
+
+#[test]
+fn test_range_stop_out_of_bounds() {
+    let program_string = r#"
+function main() {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[..10];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373061", 4, 17)]);
+}
+
+#[test]
+fn test_range_start_out_of_bounds() {
+    let program_string = r#"
+function main() {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[5..];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373061", 4, 15)]);
+}
+
+#[test]
+fn test_range_runtime_bound() {
+    let program_string = r#"
+function main(i: u32) {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[i..];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373063", 4, 13)]);
+}
+
+#[test]
+fn test_signed_index() {
+    let program_string = r#"
+function main(i: i32) {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[i];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373069", 4, 15)]);
+}
+
+#[test]
+fn test_mixed_inline_elements() {
+    let program_string = r#"
+function main() {
+    let a = [1u8, 2u16, true, 3u8, 4i32];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373071", 3, 13)]);
+
+    let error = load_asg(program_string).err().unwrap();
+    assert!(error
+        .to_string()
+        .contains("array elements must all have type `u8`, but 3 element(s) do not"));
+    assert_eq!(
+        labels(&error),
+        vec![
+            (3, 19, "has type `u16`"),
+            (3, 25, "has type `bool`"),
+            (3, 36, "has type `i32`"),
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_inline_spread() {
+    let program_string = r#"
+function main() {
+    let b = [1u16, 2u16];
+    let a = [0u8, ...b, 3u8];
+}
+"#;
+    let error = load_asg(program_string).err().unwrap();
+    assert_eq!(error.error_code(), "EASG0373071");
+    assert_eq!(labels(&error), vec![(4, 22, "spreads elements of type `u16`")]);
+}
+
+#[test]
+fn test_empty_inline_without_type() {
+    let program_string = r#"
+function main() {
+    let a = [];
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373072", 3, 13)]);
+}
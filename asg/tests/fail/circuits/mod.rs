@@ -14,7 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg, make_test_context, TESTING_FILEPATH};
+use leo_asg::{CircuitMember, Program, Type, TypeContext};
+use leo_errors::{expect_errors, Span};
 
 // Expressions
 
@@ -79,6 +81,79 @@ fn test_member_static_function_undefined() {
     load_asg(program_string).err().unwrap();
 }
 
+#[test]
+fn test_member_static_function_alias_undefined() {
+    let program_string = r#"
+circuit Foo {
+    function echo(x: u32) -> u32 {
+        return x;
+    }
+}
+
+type Bar = Foo;
+type Baz = Bar;
+
+function main() {
+    const a = Baz::missing(1u32);
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373075", 12, 15)]);
+    let error = load_asg(program_string).err().unwrap();
+    assert!(error
+        .to_string()
+        .contains("`Baz` resolves to `Foo`, which has no member `missing`"));
+}
+
+#[test]
+fn test_static_member_through_instance() {
+    let program_string = r#"
+circuit Point {
+    static SIZE: u32 = 8;
+    x: u32,
+}
+
+function main() {
+    let p = Point { x: 1 };
+    let size = p.SIZE;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373077", 9, 16)]);
+    let error = load_asg(program_string).err().unwrap();
+    assert!(error.to_string().contains("use `Point::SIZE` instead"));
+}
+
+#[test]
+fn test_static_member_not_const() {
+    let program_string = r#"
+function square(x: u32) -> u32 {
+    return x * x;
+}
+
+circuit Point {
+    static SIZE: u32 = square(3);
+    x: u32,
+}
+
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373078", 7, 24)]);
+}
+
+#[test]
+fn test_static_member_initialized() {
+    let program_string = r#"
+circuit Point {
+    static SIZE: u32 = 8;
+    x: u32,
+}
+
+function main() {
+    let p = Point { x: 1, SIZE: 2 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373005", 8, 27)]);
+}
+
 // Mutability
 
 #[test]
@@ -136,3 +211,272 @@ fn test_self_member_undefined() {
     let program_string = include_str!("self_member_undefined.leo");
     load_asg(program_string).err().unwrap();
 }
+
+#[test]
+fn test_self_containing_circuit() {
+    let program_string = r#"
+    circuit Node {
+        children: [Node; 2],
+    }
+    function main () {}
+    "#;
+    load_asg(program_string).err().unwrap();
+}
+
+#[test]
+fn test_init_duplicate_member() {
+    let program_string = r#"
+circuit Point { x: u8, y: u8 }
+function main() {
+    let p = Point { x: 1u8, x: 2u8, y: 3u8 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373003", 4, 29)]);
+}
+
+#[test]
+fn test_init_unknown_member() {
+    let program_string = r#"
+circuit Point { x: u8, y: u8 }
+function main() {
+    let p = Point { x: 1u8, y: 2u8, z: 3u8 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373005", 4, 37)]);
+}
+
+#[test]
+fn test_init_function_member() {
+    let program_string = r#"
+circuit Point {
+    x: u8,
+    function origin() -> u8 { return 0u8; }
+}
+function main() {
+    let p = Point { x: 1u8, origin: 2u8 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373005", 7, 29)]);
+}
+
+#[test]
+fn test_init_missing_member() {
+    let program_string = r#"
+circuit Point { x: u8, y: u8 }
+function main() {
+    let p = Point { x: 1u8 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373002", 4, 13)]);
+}
+
+#[test]
+fn test_init_wrong_member_type() {
+    let program_string = r#"
+circuit Point { x: u8, y: u8 }
+function main() {
+    let p = Point { x: 1u8, y: true };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 4, 32)]);
+}
+
+#[test]
+fn test_init_unknown_circuit_suggestion() {
+    let program_string = r#"
+circuit Point { x: u8, y: u8 }
+function main() {
+    let p = Pointt { x: 1u8, y: 2u8 };
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373057", 4, 13)]);
+    let error = load_asg(program_string).err().unwrap();
+    assert!(error.to_string().contains("did you mean 'Point'?"));
+}
+
+#[test]
+fn test_member_variable_function_collision() {
+    let program_string = r#"
+circuit Foo {
+    x: u8,
+    function x() -> u8 { return 0u8; }
+}
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373080", 4, 14)]);
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(error.contains("function `x` of circuit `Foo` has the same name as a variable declared before it"));
+    assert!(error.contains("variable `x` is declared here"));
+}
+
+#[test]
+fn test_duplicate_member_variables() {
+    let program_string = r#"
+circuit Foo {
+    x: u8,
+    y: u8,
+    x: u32,
+}
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373080", 5, 5)]);
+}
+
+#[test]
+fn test_member_static_variable_collision() {
+    let program_string = r#"
+circuit Foo {
+    static SIZE: u32 = 8;
+    SIZE: u32,
+}
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373080", 4, 5)]);
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(error.contains("variable `SIZE` of circuit `Foo` has the same name as a static declared before it"));
+}
+
+#[test]
+fn test_reserved_member_name() {
+    let program_string = r#"
+circuit Foo {
+    x: u8,
+}
+function main() {}
+"#;
+    // The parser never accepts `self` as a member name, so the member is renamed in the AST.
+    let mut program = leo_parser::parse_ast(&TESTING_FILEPATH, program_string)
+        .unwrap()
+        .into_repr();
+    for circuit in program.circuits.values_mut() {
+        if let leo_ast::CircuitMember::CircuitVariable(name, _) = &mut circuit.members[0] {
+            name.name = "self".into();
+        }
+    }
+    let error = Program::new(make_test_context(), &program).err().unwrap();
+    assert_eq!(error.error_code(), "EASG0373081");
+}
+
+#[test]
+fn test_circuit_cycle() {
+    // A circuit only names circuits declared before it, so the cycle is closed on the ASG.
+    let program_string = r#"
+circuit A {
+    x: u8,
+}
+circuit B {
+    a: (u8, A),
+}
+function main() {}
+"#;
+    let program = load_asg(program_string).unwrap();
+    let a = program.circuits["A"];
+    let b = program.circuits["B"];
+    a.members.borrow_mut().insert(
+        "b".to_string(),
+        CircuitMember::Variable(Type::Array(Box::new(Type::Circuit(b)), 2)),
+    );
+
+    let error = TypeContext::default()
+        .slot_count(&Type::Circuit(a), &Span::default())
+        .err()
+        .unwrap();
+    assert_eq!(error.error_code(), "EASG0373083");
+    assert!(error.to_string().contains("`A` -> `B` -> `A`"));
+}
+
+#[test]
+fn test_circuit_cycle_reported_once() {
+    let program_string = r#"
+circuit B {
+    x: u8,
+}
+circuit A {
+    b: B,
+}
+function main() {}
+"#;
+    let program = load_asg(program_string).unwrap();
+    let a = program.circuits["A"];
+    let b = program.circuits["B"];
+    b.members
+        .borrow_mut()
+        .insert("a".to_string(), CircuitMember::Variable(Type::Circuit(a)));
+
+    let types = TypeContext::default();
+    let span = Span::default();
+    let error = types.slot_count(&Type::Circuit(b), &span).err().unwrap();
+    assert_eq!(error.error_code(), "EASG0373083");
+    assert!(error.to_string().contains("`B` -> `A` -> `B`"));
+    for _ in 0..100 {
+        assert!(types.slot_count(&Type::Circuit(a), &span).unwrap().is_none());
+        assert!(types.slot_count(&Type::Circuit(b), &span).unwrap().is_none());
+        let tuple = Type::Tuple(vec![Type::Boolean, Type::Array(Box::new(Type::Circuit(a)), 3)]);
+        assert!(types.slot_count(&tuple, &span).unwrap().is_none());
+    }
+    assert_eq!(types.statistics().size_misses, 1);
+    assert_eq!(types.statistics().size_hits, 300);
+    assert_eq!(types.slot_count(&Type::Boolean, &span).unwrap(), Some(1));
+}
+
+#[test]
+fn test_receiver_call_argument_count() {
+    let program_string = r#"
+circuit Point {
+    x: u32,
+
+    function shifted(self, d: u32) -> u32 {
+        return self.x + d;
+    }
+
+    function scaled(p: Point, k: u32) -> u32 {
+        return p.x * k;
+    }
+}
+
+function main() {
+    const p = Point { x: 1u32 };
+    const a = p.shifted();
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373084", 16, 15)]);
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(error.contains("function call expected 1 arguments plus receiver, got 0"));
+
+    let program_string = program_string.replace("p.shifted()", "Point::shifted(p, 1u32, 2u32)");
+    let error = load_asg(&program_string).err().unwrap().to_string();
+    assert!(error.contains("function call expected 1 arguments plus receiver, got 2"));
+
+    let program_string = program_string.replace("Point::shifted(p, 1u32, 2u32)", "Point::shifted()");
+    let error = load_asg(&program_string).err().unwrap().to_string();
+    assert!(error.contains("function call expected 1 arguments plus receiver, got no receiver"));
+
+    let program_string = program_string.replace("Point::shifted()", "p.scaled(1u32, 2u32)");
+    let error = load_asg(&program_string).err().unwrap().to_string();
+    assert!(error.contains("function call expected 1 arguments plus receiver, got 2"));
+
+    // Called statically, the function takes the instance as an ordinary argument.
+    let program_string = program_string.replace("p.scaled(1u32, 2u32)", "Point::scaled(p)");
+    let error = load_asg(&program_string).err().unwrap().to_string();
+    assert!(error.contains("function call expected 2 arguments, got 1"));
+}
+
+#[test]
+fn test_static_call_of_mut_receiver_needs_mut_instance() {
+    let program_string = r#"
+circuit Point {
+    x: u32,
+
+    function clear(mut self) {
+        self.x = 0u32;
+    }
+}
+
+function main() {
+    const p = Point { x: 1u32 };
+    Point::clear(p);
+}
+"#;
+    let error = load_asg(program_string).err().unwrap();
+    assert_eq!(error.error_code(), "EASG0373009");
+}
@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg};
+use leo_errors::{expect_errors, DiagnosticSnapshot};
 
 #[test]
 fn test_log_fail() {
@@ -27,3 +28,23 @@ fn test_log_parameter_fail_unknown() {
     let program_string = include_str!("log_parameter_fail_unknown.leo");
     load_asg(program_string).err().unwrap();
 }
+
+#[test]
+fn test_assert_eq_type_mismatch() {
+    let program_string = r#"
+function main(a: [u8; 3], b: [u16; 3]) {
+    console.assert_eq(a, b);
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 3, 26)]);
+}
+
+#[test]
+fn test_assert_ne_type_mismatch() {
+    let program_string = r#"
+function main(a: u8) {
+    console.assert_ne(a, true);
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 3, 26)]);
+}
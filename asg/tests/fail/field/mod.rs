@@ -13,3 +13,47 @@
 
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{compile_fail, load_asg_imports, make_test_context};
+use leo_errors::{expect_errors, DiagnosticSnapshot};
+
+#[test]
+fn test_ordering_rejected() {
+    let program_string = r#"
+function main(a: field, b: field) {
+    let c = a < b;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373055", 3, 13)]);
+}
+
+#[test]
+fn test_strict_literal_rejected() {
+    let program_string = r#"
+function main() -> field {
+    return 8444461749428370424248824938781546531375899335154063827935233455917409239041field;
+}
+"#;
+    let context = make_test_context();
+    context.set_strict_field_literals(true);
+    let error = load_asg_imports(context, program_string).err().unwrap();
+    expect_errors!([DiagnosticSnapshot::from(&error)], [("EASG0373058", 3, 12)]);
+}
+
+#[test]
+fn test_division_by_zero() {
+    let program_string = r#"
+function main(a: field) -> field {
+    return a / 0field;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373079", 3, 16)]);
+
+    let program_string = r#"
+function main(a: field) -> field {
+    const ZERO: field = 2field - 2field;
+    return a / ZERO;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373079", 4, 16)]);
+}
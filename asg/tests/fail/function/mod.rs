@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg};
+use leo_errors::expect_errors;
 
 #[test]
 fn test_multiple_returns_fail() {
@@ -37,13 +38,13 @@ fn test_multiple_returns_fail_conditional() {
 #[test]
 fn test_scope_fail() {
     let program_string = include_str!("scope_fail.leo");
-    load_asg(program_string).err().unwrap();
+    expect_errors!(compile_fail(program_string), [("EASG0373027", 2, 12)]);
 }
 
 #[test]
 fn test_undefined() {
     let program_string = include_str!("undefined.leo");
-    load_asg(program_string).err().unwrap();
+    expect_errors!(compile_fail(program_string), [("EASG0373023", 2, 5)]);
 }
 
 #[test]
@@ -65,3 +66,108 @@ fn test_return_array_tuple_fail() {
     let program_string = include_str!("return_array_tuple_fail.leo");
     load_asg(program_string).err().unwrap();
 }
+
+#[test]
+fn test_unknown_annotation() {
+    let program_string = r#"
+@deprecated
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373066", 2, 1)]);
+}
+
+#[test]
+fn test_duplicate_annotation() {
+    let program_string = r#"
+@test
+@test(other)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373067", 3, 1)]);
+}
+
+#[test]
+fn test_annotation_unexpected_argument() {
+    let program_string = r#"
+@allow_field_ordering(always)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373068", 2, 1)]);
+}
+
+#[test]
+fn test_test_annotation_too_many_arguments() {
+    let program_string = r#"
+@test(first, second)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373068", 2, 1)]);
+}
+
+#[test]
+fn test_budget_annotation_without_value() {
+    let program_string = r#"
+@budget(instructions)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373076", 2, 1)]);
+}
+
+#[test]
+fn test_budget_annotation_unknown_measure() {
+    let program_string = r#"
+@budget(gates = 100)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373076", 2, 1)]);
+}
+
+#[test]
+fn test_inline_annotation_without_argument() {
+    let program_string = r#"
+@inline
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373068", 2, 1)]);
+}
+
+#[test]
+fn test_inline_annotation_bad_argument() {
+    let program_string = r#"
+@inline(sometimes)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373088", 2, 1)]);
+}
+
+#[test]
+fn test_inline_annotations_conflict() {
+    let program_string = r#"
+@inline(always)
+@inline(never)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373089", 3, 1)]);
+}
+
+#[test]
+fn test_inline_annotation_repeated() {
+    let program_string = r#"
+@inline(never)
+@inline(never)
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373067", 3, 1)]);
+}
+
+#[test]
+fn test_circuit_member_annotation() {
+    let program_string = r#"
+circuit Foo {
+    @allow_field_ordering
+    @allow_field_ordering
+    function bar() {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373067", 4, 5)]);
+}
@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg};
+use leo_errors::expect_errors;
 
 #[test]
 fn test_both_sign_high() {
@@ -33,3 +34,13 @@ fn test_both_sign_inferred() {
     let program_string = include_str!("both_sign_inferred.leo");
     load_asg(program_string).err().unwrap();
 }
+
+#[test]
+fn test_ordering_rejected() {
+    let program_string = r#"
+function main(a: group, b: group) {
+    let c = a >= b;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373055", 3, 13)]);
+}
@@ -0,0 +1,50 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{compile_fail, load_asg};
+use leo_errors::{expect_errors, DiagnosticSnapshot};
+
+#[test]
+fn test_ambiguous_literal() {
+    let program_string = r#"
+function main() {
+    let a = 1 + 2;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373059", 3, 13)]);
+}
+
+#[test]
+fn test_ambiguous_literal_lists_candidates() {
+    let program_string = r#"
+function main() {
+    let a = 7;
+}
+"#;
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(error.contains("cannot infer the type of literal `7`"));
+    assert!(error.contains("candidates are u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, field and group"));
+}
+
+#[test]
+fn test_range_checked_after_inference() {
+    let program_string = r#"
+function main(x: u8) {
+    let y = x + 300;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373030", 3, 17)]);
+}
@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{compile_fail, load_asg};
+use leo_errors::{expect_errors, AsgError, LeoError};
+
+#[test]
+fn test_unknown_record_field() {
+    let program_string = r#"
+function main() -> u64 {
+    return input.record.colour;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373064", 3, 12)]);
+}
+
+#[test]
+fn test_record_field_type_mismatch() {
+    let program_string = r#"
+function main() -> u8 {
+    let owner: u8 = input.record.owner;
+    return owner;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 3, 21)]);
+}
+
+#[test]
+fn test_record_circuit_redefines_builtin_field() {
+    let program_string = r#"
+circuit Record {
+    gates: u8,
+}
+
+function main() {}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373065", 2, 9)]);
+}
+
+#[test]
+fn test_program_input_shadowed_by_variable() {
+    let program_string = r#"
+function main(a: bool) -> bool {
+    let input = true;
+    return a == input.registers.b;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373073", 4, 17)]);
+}
+
+#[test]
+fn test_program_input_shadowed_by_later_variable() {
+    let program_string = r#"
+function main(a: u8) -> u8 {
+    let b: u8 = input.registers.r;
+    let input = a;
+    return b + input;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373073", 3, 17)]);
+
+    match load_asg(program_string) {
+        Err(LeoError::AsgError(AsgError::FormattedError(error))) => {
+            let label = &error.labels[0];
+            assert_eq!((label.span.line_start, label.span.col_start), (4, 9));
+            assert_eq!(label.message, "`input` is declared here");
+        }
+        _ => panic!("expected a formatted error"),
+    }
+}
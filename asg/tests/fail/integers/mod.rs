@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{compile_fail, load_asg, load_asg_imports, make_test_context};
+use leo_errors::{expect_errors, DiagnosticSnapshot};
+
 #[macro_use]
 pub mod int_macro;
 
@@ -35,3 +38,45 @@ pub mod i16;
 pub mod i32;
 pub mod i64;
 pub mod i8;
+
+#[test]
+fn test_mixed_width_integers_rejected() {
+    let program_string = r#"
+function main(a: u8, b: u16) -> u16 {
+    return a + b;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373085", 3, 12)]);
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(error.contains("operator `+` cannot be applied to `u8` and `u16`, which differ in width"));
+    assert!(error.contains("set the compiler option `integer_widening = \"lossless\"`"));
+}
+
+#[test]
+fn test_mixed_signedness_rejected_in_both_modes() {
+    let program_string = r#"
+function main(a: u8, b: i16) -> bool {
+    return a < b;
+}
+"#;
+    for &widening in &[false, true] {
+        let context = make_test_context();
+        context.set_integer_widening(widening);
+        let error = load_asg_imports(context, program_string).err().unwrap();
+        expect_errors!([DiagnosticSnapshot::from(&error)], [("EASG0373025", 3, 16)]);
+        assert!(!error.to_string().contains("integer_widening"));
+    }
+}
+
+#[test]
+fn test_lossy_widening_rejected() {
+    let program_string = r#"
+function main(a: u8, b: u16) {
+    let c: u8 = a + b;
+}
+"#;
+    let context = make_test_context();
+    context.set_integer_widening(true);
+    let error = load_asg_imports(context, program_string).err().unwrap();
+    expect_errors!([DiagnosticSnapshot::from(&error)], [("EASG0373025", 3, 17)]);
+}
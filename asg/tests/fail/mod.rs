@@ -23,8 +23,11 @@ pub mod core;
 pub mod definition;
 pub mod field;
 pub mod function;
+pub mod input_files;
 // pub mod group; // we dont do context-specific type checking for groups
+pub mod inference;
 pub mod integers;
 pub mod mutability;
 pub mod statements;
+pub mod strings;
 pub mod tuples;
@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_fail, load_asg};
+use leo_errors::expect_errors;
 
 #[test]
 fn test_num_returns_fail() {
@@ -25,5 +26,112 @@ fn test_num_returns_fail() {
 #[test]
 fn test_let_mut_declaration_fail() {
     let program_string = include_str!("let_mut_declaration_fail.leo");
-    load_asg(program_string).err().unwrap();
+    expect_errors!(compile_fail(program_string), [("EPAR0370015", 2, 5)]);
+}
+
+#[test]
+fn test_assignment_in_one_branch() {
+    let program_string = r#"
+function main(a: bool) -> u32 {
+    let x: u32;
+    if a {
+        x = 1;
+    }
+    return x;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373052", 7, 12)]);
+
+    let error = load_asg(program_string).err().unwrap().to_string();
+    assert!(
+        error.contains("variable `x` is read before it is definitely assigned"),
+        "{}",
+        error
+    );
+    assert!(error.contains("not assigned on the path through"), "{}", error);
+}
+
+#[test]
+fn test_assignment_in_loop_body() {
+    let program_string = r#"
+function main() -> u32 {
+    let x: u32;
+    for i in 0u32..2u32 {
+        x = i;
+    }
+    return x;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373052", 7, 12)]);
+}
+
+#[test]
+fn test_assignment_in_branch_folded_away() {
+    let program_string = r#"
+function main() -> u32 {
+    let x: u32;
+    if false {
+        x = 1;
+    }
+    return x;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373052", 7, 12)]);
+}
+
+#[test]
+fn test_uninitialized_declaration_without_default() {
+    let program_string = r#"
+function main() {
+    let a: address;
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373094", 3, 5)]);
+}
+
+#[test]
+fn test_iteration_step_zero() {
+    let program_string = r#"
+function main() {
+    for i in 0u32..8u32 step 0u32 {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373091", 3, 30)]);
+}
+
+#[test]
+fn test_iteration_step_wrong_sign() {
+    let program_string = r#"
+function main() {
+    for i in 0i32..8i32 step -2i32 {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373092", 3, 30)]);
+
+    let program_string = r#"
+function main() {
+    for i in 8i32..0i32 step 2i32 {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373092", 3, 30)]);
+}
+
+#[test]
+fn test_iteration_step_nonconst() {
+    let program_string = r#"
+function main(n: u32) {
+    for i in 0u32..8u32 step n {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373026", 3, 30)]);
+}
+
+#[test]
+fn test_iteration_step_type_mismatch() {
+    let program_string = r#"
+function main() {
+    for i in 0u32..8u32 step 2u8 {}
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 3, 30)]);
 }
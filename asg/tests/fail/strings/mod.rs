@@ -0,0 +1,38 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::compile_fail;
+use leo_errors::expect_errors;
+
+#[test]
+fn test_runtime_concatenation() {
+    let program_string = r#"
+function main(a: [char; 2]) -> [char; 4] {
+    return a + "cd";
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373074", 3, 12)]);
+}
+
+#[test]
+fn test_concatenation_length_mismatch() {
+    let program_string = r#"
+function main() -> [char; 5] {
+    return "ab" + "cd";
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373025", 3, 12)]);
+}
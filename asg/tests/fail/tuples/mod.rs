@@ -13,3 +13,66 @@
 
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::compile_fail;
+use leo_errors::{expect_errors, DiagnosticSnapshot};
+
+#[test]
+fn test_destructure_call_too_few_variables() {
+    let program_string = r#"
+function three() -> (u8, u8, u8) {
+    return (1, 2, 3);
+}
+
+function main() {
+    let (a, b) = three();
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373060", 7, 5)]);
+}
+
+#[test]
+fn test_destructure_scalar_call() {
+    let program_string = r#"
+function one() -> u8 {
+    return 1;
+}
+
+function main() {
+    let (a, b) = one();
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373060", 7, 5)]);
+}
+
+#[test]
+fn test_destructure_tuple_too_many_variables() {
+    let program_string = r#"
+function main() {
+    let (a, b, c) = (1u8, 2u8);
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373060", 3, 5)]);
+}
+
+#[test]
+fn test_unit_in_tuple_type() {
+    let program_string = r#"
+function main() {
+    let a: (u8, ()) = (1u8, ());
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373082", 3, 5)]);
+}
+
+#[test]
+fn test_unit_in_tuple_value() {
+    let program_string = r#"
+function nothing() {}
+
+function main() {
+    let a = (1u8, nothing());
+}
+"#;
+    expect_errors!(compile_fail(program_string), [("EASG0373082", 5, 19)]);
+}
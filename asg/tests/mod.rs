@@ -15,7 +15,9 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_asg::*;
-use leo_errors::LeoError;
+use leo_ast::AstPass;
+use leo_ast_passes::Canonicalizer;
+use leo_errors::{DiagnosticSnapshot, LeoError};
 use leo_parser::parse_ast;
 
 mod fail;
@@ -28,10 +30,28 @@ fn load_asg(program_string: &str) -> Result<Program<'static>, LeoError> {
 }
 
 fn load_asg_imports<'a>(context: AsgContext<'a>, program_string: &str) -> Result<Program<'a>, LeoError> {
+    // Programs are canonicalized before the ASG is built, as they are by the compiler.
     let ast = parse_ast(&TESTING_FILEPATH, program_string)?;
+    let ast = Canonicalizer::do_pass(ast.into_repr())?;
     Program::new(context, &ast.as_repr())
 }
 
+/// Loads the program, asserting that it has no errors.
+fn compile_pass(program_string: &str) -> Program<'static> {
+    match load_asg(program_string) {
+        Ok(program) => program,
+        Err(error) => panic!("expected no errors, got: {}", DiagnosticSnapshot::from(&error)),
+    }
+}
+
+/// Loads the program, asserting that it fails, and returns its errors.
+fn compile_fail(program_string: &str) -> Vec<DiagnosticSnapshot> {
+    match load_asg(program_string) {
+        Ok(_) => panic!("expected errors, but the program loaded"),
+        Err(error) => vec![DiagnosticSnapshot::from(&error)],
+    }
+}
+
 //convenience function for tests, leaks memory
 pub(crate) fn make_test_context() -> AsgContext<'static> {
     let allocator = Box::leak(Box::new(new_alloc_context()));
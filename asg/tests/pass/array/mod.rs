@@ -205,3 +205,37 @@ fn test_input_type_tuple_value_tuple_4x3x2() {
     let program_string = include_str!("type_input_4x3x2.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_bits() {
+    let program_string = include_str!("bits.leo");
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_range_defaulted_bounds() {
+    let program_string = r#"
+    function main(i: u32) {
+        let a: [u8; 4] = [1, 2, 3, 4];
+        let b: [u8; 4] = a[..];
+        let c: [u8; 3] = a[1..];
+        let d: [u8; 2] = a[..2];
+        let e = a[1..3];
+        let f: [u8; 2] = e;
+        let h: [u8; 2] = a[i..];
+    }
+    "#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_u64_index() {
+    let program_string = r#"
+function main(i: u64) {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[i];
+    let c: [u8; 2] = a[i..];
+}
+"#;
+    load_asg(program_string).unwrap();
+}
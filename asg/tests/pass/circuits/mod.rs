@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{load_asg, load_asg_imports, make_test_context};
+use leo_asg::ExpressionNode;
 
 // Expressions
 
@@ -73,6 +74,47 @@ fn test_member_static_function() {
     load_asg(program_string).unwrap();
 }
 
+#[test]
+fn test_member_static_function_alias() {
+    let program_string = include_str!("member_static_function_alias.leo");
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_static_member() {
+    let program_string = r#"
+circuit Point {
+    static SIZE: u32 = 8;
+    static AREA: u32 = Point::SIZE * Point::SIZE;
+    x: u32,
+    y: u32,
+
+    function area() -> u32 {
+        return Point::AREA;
+    }
+}
+
+function main() {
+    const size: u32 = Point::SIZE;
+    let p = Point { x: 1, y: 2 };
+    console.assert(p.x + Point::SIZE == 9u32);
+    console.assert(Point::area() == 64u32);
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+    let point = program.circuits.get("Point").unwrap();
+    let members = point.members.borrow();
+    match members.get("AREA") {
+        Some(leo_asg::CircuitMember::Static(_, value)) => assert!(matches!(
+            value.get().unwrap().const_value(),
+            Some(leo_asg::ConstValue::Int(leo_asg::ConstInt::U32(64)))
+        )),
+        _ => panic!("`AREA` is not a static member"),
+    }
+    // Statics are not part of the data of an instance.
+    assert_eq!(leo_asg::Layout::slot_count(&leo_asg::Type::Circuit(*point)), 2);
+}
+
 // Mutability
 
 #[test]
@@ -121,3 +163,149 @@ fn test_circuit_explicit_define() {
     "#;
     load_asg(program_string).unwrap();
 }
+
+// Layouts
+
+#[test]
+fn test_nested_layout() {
+    let program_string = r#"
+    circuit Point {
+        x: u8,
+        y: u8,
+    }
+    circuit Bag {
+        values: [u8; 2],
+        origin: Point,
+    }
+    function first(points: [Point; 2], bag: Bag) -> u8 {
+        return points[1].y + bag.values[0];
+    }
+    function main () {}
+    "#;
+    let program = load_asg(program_string).unwrap();
+    let function = program.functions.get("first").unwrap();
+    let span = leo_errors::Span::default();
+
+    let points = function.arguments.get("points").unwrap().get().borrow().type_.clone();
+    let layout = leo_asg::Layout::flatten(&points, "points", &span).unwrap();
+    let paths: Vec<_> = layout.slots.iter().map(|slot| slot.path.as_str()).collect();
+    assert_eq!(paths, vec!["points[0].x", "points[0].y", "points[1].x", "points[1].y"]);
+    assert_eq!(layout.slot_index("points[1].y"), Some(3));
+    assert_eq!(layout.slot_range("points[1]"), Some(2..4));
+    assert_eq!(leo_asg::Layout::slot_count(&points), layout.len());
+
+    let bag = function.arguments.get("bag").unwrap().get().borrow().type_.clone();
+    let layout = leo_asg::Layout::flatten(&bag, "bag", &span).unwrap();
+    let paths: Vec<_> = layout.slots.iter().map(|slot| slot.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["bag.values[0]", "bag.values[1]", "bag.origin.x", "bag.origin.y"]
+    );
+    assert_eq!(leo_asg::Layout::slot_count(&bag), layout.len());
+}
+
+#[test]
+fn test_distinct_member_names() {
+    let program_string = r#"
+    circuit Foo {
+        static SIZE: u32 = 8;
+        x: u32,
+        y: u32,
+
+        function new(x: u32) -> Self { return Self { x: x, y: Self::SIZE }; }
+        function sum(self) -> u32 { return self.x + self.y; }
+    }
+    function main () {}
+    "#;
+    let program = load_asg(program_string).unwrap();
+    let circuit = program.circuits.get("Foo").unwrap();
+    let names: Vec<_> = circuit.members.borrow().keys().cloned().collect();
+    assert_eq!(names, vec!["SIZE", "x", "y", "new", "sum"]);
+}
+
+#[test]
+fn test_primitive_like_name_warns() {
+    let program_string = r#"
+circuit Field {
+    x: u8,
+}
+type U8 = Field;
+function main() {}
+"#;
+    let context = make_test_context();
+    load_asg_imports(context, program_string).unwrap();
+
+    let warnings = context.take_warnings();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(
+        warnings[0].message,
+        "alias `U8` differs only in case from the primitive type `u8`"
+    );
+    assert_eq!((warnings[0].span.line_start, warnings[0].span.col_start), (5, 6));
+    assert_eq!(
+        warnings[1].message,
+        "circuit `Field` differs only in case from the primitive type `field`"
+    );
+    assert_eq!((warnings[1].span.line_start, warnings[1].span.col_start), (2, 9));
+}
+
+#[test]
+fn test_entrypoint_name_warns_only_at_top_level() {
+    let program_string = r#"
+circuit Runner {
+    function main() -> u8 {
+        return 1u8;
+    }
+}
+function main() -> u8 {
+    return Runner::main();
+}
+"#;
+    let context = make_test_context();
+    load_asg_imports(context, program_string).unwrap();
+    assert!(context.take_warnings().is_empty());
+
+    let context = make_test_context();
+    load_asg_imports(context, "circuit Main {}\nfunction main() {}\n").unwrap();
+    let warnings = context.take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].message,
+        "circuit `Main` differs only in case from the entrypoint `main`"
+    );
+}
+
+#[test]
+fn test_member_functions_called_both_ways() {
+    let program_string = r#"
+type P = Point;
+
+circuit Point {
+    x: u32,
+    y: u32,
+
+    function magnitude(self) -> u32 {
+        return self.x * self.x + self.y * self.y;
+    }
+
+    function scaled(p: P, k: u32) -> Point {
+        return Point { x: p.x * k, y: p.y * k };
+    }
+
+    function mirror(mut self) {
+        self.x = self.y;
+    }
+}
+
+function main() {
+    let p = Point { x: 1u32, y: 2u32 };
+    let a = p.magnitude();
+    let b = Point::magnitude(p);
+    let c = P::magnitude(p.scaled(2u32));
+    let d = Point::scaled(p, 2u32).magnitude();
+    let e = P::scaled(p, 2u32);
+    Point::mirror(p);
+}
+"#;
+    load_asg(program_string).unwrap();
+}
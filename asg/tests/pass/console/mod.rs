@@ -61,3 +61,9 @@ fn test_conditional_assert() {
     let program_string = include_str!("conditional_assert.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_assert_eq() {
+    let program_string = include_str!("assert_eq.leo");
+    load_asg(program_string).unwrap();
+}
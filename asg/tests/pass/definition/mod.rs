@@ -28,3 +28,20 @@ fn test_out_of_order() {
 //     let program_string = include_str!("out_of_order_with_import.leo");
 //     load_asg(program_string).unwrap();
 // }
+
+#[test]
+fn test_alias_chain_declared_in_reverse() {
+    // `T0` names `T1`, which names `T2`, and so on, each alias declared before the one it names.
+    let mut program_string = String::new();
+    for i in 0..49 {
+        program_string.push_str(&format!("type T{} = T{};\n", i, i + 1));
+    }
+    program_string.push_str("type T49 = u8;\n\nfunction main() {\n    let a: T0 = 1u8;\n    let b: T0 = a;\n}\n");
+
+    let program = load_asg(&program_string).unwrap();
+    assert_eq!(program.aliases["T0"].represents.to_string(), "u8");
+    // Every alias is resolved once while the chain is declared, and `T0` once more for `main`.
+    let statistics = program.scope.types.statistics();
+    assert_eq!(statistics.identifier_misses, 50);
+    assert!(statistics.identifier_hits >= 1);
+}
@@ -0,0 +1,85 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::load_asg;
+use leo_asg::{Dataflow, DataflowNodeKind};
+
+const PROGRAM: &str = r#"
+function main(a: u8, b: u8) -> u8 {
+    let c = a + b * 2u8;
+    return c;
+}
+"#;
+
+const EXPECTED: &str = r#"digraph "main" {
+    node [fontname="monospace"];
+    v0 [label="a: u8", shape=box, style=filled, fillcolor=lightblue];
+    v1 [label="b: u8", shape=box, style=filled, fillcolor=lightblue];
+    output [label="return: u8", shape=box, style=filled, fillcolor=lightpink];
+    e0 [label="*\n: u8\n#1 = 2u8"];
+    e1 [label="+\n: u8"];
+    v2 [label="c: u8", shape=box];
+    v1 -> e0 [label="0"];
+    v0 -> e1 [label="0"];
+    e0 -> e1 [label="1"];
+    e1 -> v2 [label="="];
+    v2 -> output;
+}
+"#;
+
+#[test]
+fn test_dot_golden() {
+    let program = load_asg(PROGRAM).unwrap();
+    let main = program.functions.get("main").unwrap();
+    assert_eq!(main.to_dot(), EXPECTED);
+}
+
+#[test]
+fn test_dot_clusters() {
+    let program = load_asg(PROGRAM).unwrap();
+    let main = program.functions.get("main").unwrap();
+    let dot = Dataflow::new(main).to_dot(true);
+    assert!(dot.contains("subgraph cluster_0 {\n        label=\"line 3\";\n        e0"));
+    assert!(dot.contains("subgraph cluster_1 {\n        label=\"line 4\";\n    }"));
+}
+
+#[test]
+fn test_dot_provenance() {
+    let program = load_asg(
+        r#"
+        function main(a: [u8; 4], flag: bool) -> u8 {
+            let b = a;
+            let x = b[1] + b[2];
+            for i in 0..4 {
+                b[i] += x;
+            }
+            if flag {
+                console.assert(b[0] == x);
+            }
+            return b[3];
+        }
+        "#,
+    )
+    .unwrap();
+    let graph = Dataflow::new(program.functions.get("main").unwrap());
+    for node in graph.nodes.iter() {
+        if let DataflowNodeKind::Operation | DataflowNodeKind::Sink | DataflowNodeKind::Constant = node.kind {
+            assert!(node.span.is_some(), "node {} has no span", node.id);
+            assert!(node.statement.is_some(), "node {} has no statement", node.id);
+        }
+    }
+    assert!(graph.statements.iter().all(Option::is_some));
+}
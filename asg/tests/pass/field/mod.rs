@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{load_asg, load_asg_imports, make_test_context};
+use leo_asg::{
+    BinaryExpression, BinaryOperation, ConstInt, ConstValue, ExpressionNode, Program, Statement, FIELD_MODULUS,
+};
+use num_bigint::BigInt;
 
 #[test]
 fn test_negate() {
@@ -73,3 +77,105 @@ fn test_ternary() {
     let program_string = include_str!("ternary.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_ordering_allowed() {
+    let program_string = include_str!("ordering_allowed.leo");
+    load_asg(program_string).unwrap();
+}
+
+/// Returns the constant value of the expression returned by `main`, as a decimal string.
+fn main_return_value(program: &Program) -> String {
+    let main = program.functions.get("main").unwrap();
+    let value = match main.body.get() {
+        Some(Statement::Block(block)) => match block.statements[0].get() {
+            Statement::Return(statement) => statement.expression.get().const_value(),
+            _ => None,
+        },
+        _ => None,
+    };
+    match value {
+        Some(ConstValue::Field(value)) => value.to_string(),
+        _ => panic!("main does not return a constant field"),
+    }
+}
+
+fn modulus_minus(value: u32) -> String {
+    (FIELD_MODULUS.parse::<BigInt>().unwrap() - BigInt::from(value)).to_string()
+}
+
+#[test]
+fn test_literal_reduced_with_warning() {
+    let program_string = r#"
+function main() -> field {
+    return 8444461749428370424248824938781546531375899335154063827935233455917409239043field;
+}
+"#;
+    let context = make_test_context();
+    let program = load_asg_imports(context, program_string).unwrap();
+    assert_eq!(main_return_value(&program), "2");
+
+    let warnings = context.take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("reduced to `2`"));
+}
+
+#[test]
+fn test_negative_literal_canonical() {
+    let program_string = r#"
+function main() -> field {
+    return -1field;
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+    assert_eq!(main_return_value(&program), modulus_minus(1));
+
+    let program_string = r#"
+function main() -> field {
+    let a: field = -3;
+    return a;
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_arithmetic_folded() {
+    let program_string = r#"
+function main() -> field {
+    return 3field - 5field * 2field;
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+    assert_eq!(main_return_value(&program), modulus_minus(7));
+
+    let program_string = r#"
+function main() -> field {
+    return 6field / 3field + 1field / 2field * 2field;
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+    assert_eq!(main_return_value(&program), "3");
+}
+
+#[test]
+fn test_pow_folded() {
+    let pow = |base: u32, exponent: ConstInt| {
+        BinaryExpression::evaluate(
+            &BinaryOperation::Pow,
+            ConstValue::Field(BigInt::from(base)),
+            ConstValue::Int(exponent),
+        )
+    };
+
+    assert!(matches!(pow(3, ConstInt::U32(4)), Some(ConstValue::Field(x)) if x == BigInt::from(81)));
+    assert!(matches!(pow(0, ConstInt::U8(0)), Some(ConstValue::Field(x)) if x == BigInt::from(1)));
+    // A negative power is a power of the inverse.
+    let half = BinaryExpression::evaluate(
+        &BinaryOperation::Div,
+        ConstValue::Field(BigInt::from(1)),
+        ConstValue::Field(BigInt::from(2)),
+    );
+    assert!(pow(2, ConstInt::I8(-1)) == half);
+    assert!(pow(0, ConstInt::I8(-1)).is_none());
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{load_asg, load_asg_imports, make_test_context};
 
 #[test]
 fn test_empty() {
@@ -169,3 +169,85 @@ fn test_return_tuple_conditional() {
     let program_string = include_str!("return_tuple_conditional.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_outputs() {
+    let program_string = r#"
+    function unit() {}
+
+    function scalar() -> u8 {
+        return 1;
+    }
+
+    function triple() -> (u8, bool, field) {
+        return (1, true, 2);
+    }
+
+    function main() {
+        let (a, b, c) = triple();
+    }
+    "#;
+    let program = load_asg(program_string).unwrap();
+    let outputs = |name: &str| {
+        program.functions[name]
+            .outputs()
+            .iter()
+            .map(|type_| type_.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert!(outputs("unit").is_empty());
+    assert_eq!(outputs("scalar"), vec!["u8"]);
+    assert_eq!(outputs("triple"), vec!["u8", "bool", "field"]);
+}
+
+#[test]
+fn test_annotations() {
+    let program_string = r#"
+    @test(input_file)
+    @allow_field_ordering
+    function annotated() {}
+
+    @test
+    function tested() {}
+
+    function main() {}
+    "#;
+    let program = load_asg(program_string).unwrap();
+
+    assert!(program.functions["annotated"].is_test());
+    assert!(program.functions["annotated"].allows_field_ordering());
+    assert!(program.functions["tested"].is_test());
+    assert!(!program.functions["tested"].allows_field_ordering());
+    assert!(!program.functions["main"].is_test());
+}
+
+#[test]
+fn test_core_function_name_warns() {
+    let program_string = r#"
+function to_bits_le(a: u8) -> u8 {
+    return a;
+}
+function From_Bits_LE(a: u8) -> u8 {
+    return a;
+}
+function main() -> u8 {
+    return to_bits_le(From_Bits_LE(1u8));
+}
+"#;
+    let context = make_test_context();
+    load_asg_imports(context, program_string).unwrap();
+
+    let messages = context
+        .take_warnings()
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "function `to_bits_le` shadows the core function of the same name",
+            "function `From_Bits_LE` differs only in case from the core function `from_bits_le`",
+        ]
+    );
+}
@@ -15,6 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::load_asg;
+use leo_asg::{group_generator, ConstValue, ExpressionNode, GroupCoordinate, GroupValue, Statement};
 
 #[test]
 fn test_one() {
@@ -137,3 +138,61 @@ fn test_ternary() {
     let program_string = include_str!("ternary.leo");
     load_asg(program_string).unwrap();
 }
+
+/// Returns the constant value of the expression returned by `main`, if it is known at compile time.
+fn main_return_value(program_string: &str) -> Option<GroupValue> {
+    let program = load_asg(program_string).unwrap();
+    let main = program.functions.get("main").unwrap();
+    let value = match main.body.get() {
+        Some(Statement::Block(block)) => match block.statements[0].get() {
+            Statement::Return(statement) => statement.expression.get().const_value(),
+            _ => None,
+        },
+        _ => None,
+    };
+    match value {
+        Some(ConstValue::Group(value)) => Some(value),
+        None => None,
+        Some(_) => panic!("main does not return a group element"),
+    }
+}
+
+fn returning(expression: &str) -> String {
+    format!("function main() -> group {{\n    return {};\n}}\n", expression)
+}
+
+#[test]
+fn test_identity_and_generator_folded() {
+    let one = Some(GroupValue::Single("1".into()));
+    let zero = Some(GroupValue::Single("0".into()));
+
+    assert_eq!(main_return_value(&returning("1group + 0group")), one);
+    assert_eq!(main_return_value(&returning("0group - 1group + 1group")), zero);
+    assert_eq!(main_return_value(&returning("-1group + 1group")), zero);
+    assert_eq!(main_return_value(&returning("-(-1group)")), one);
+
+    let (x, y) = group_generator();
+    let generator = format!("({}, {})group", x, y);
+    assert_eq!(main_return_value(&returning(&format!("{} + 0group", generator))), one);
+}
+
+#[test]
+fn test_addition_folded() {
+    let double = Some(GroupValue::Tuple(
+        GroupCoordinate::Number("7671246526200950761769666614018517695911487176117270528693021891980979118135".into()),
+        GroupCoordinate::Number("6783221422172629105438050778057806742915153182459831994944409593185417235674".into()),
+    ));
+    assert_eq!(main_return_value(&returning("1group + 1group")), double);
+    assert_eq!(
+        main_return_value(&returning("1group + 1group + 1group - 1group")),
+        double
+    );
+}
+
+#[test]
+fn test_unknown_points_not_folded() {
+    // Points recovered from a single coordinate, and multiples of the generator, are left to the circuit.
+    assert_eq!(main_return_value(&returning("(0, _)group + 1group")), None);
+    assert_eq!(main_return_value(&returning("2group + 1group")), None);
+    assert_eq!(main_return_value(&returning("-(1, _)group")), None);
+}
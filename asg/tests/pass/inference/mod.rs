@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::load_asg;
+
+#[test]
+fn test_other_operand() {
+    let program_string = r#"
+function main(x: u32) -> u32 {
+    let a = x + 1;
+    let b = 1 + x;
+    let c: u32 = a * b;
+    return c;
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_chained_operands() {
+    let program_string = r#"
+function main(x: u16) {
+    let a = x + 1 + 2;
+    let b = 1 + 2 + x;
+    let c: u16 = a - b;
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_declared_type() {
+    let program_string = r#"
+function main() {
+    let a: u8 = 255;
+    const b: i64 = -5;
+    let c: u128 = 1 + 2;
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_return_type() {
+    let program_string = r#"
+function negative() -> i16 {
+    return -5;
+}
+
+function main() {
+    let a: i16 = negative();
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_index_position() {
+    let program_string = r#"
+function main(i: u32) -> u8 {
+    let a: [u8; 4] = [1, 2, 3, 4];
+    let b = a[1];
+    let c = a[i + 1];
+    let d = a[1..3];
+    return b + c + d[0];
+}
+"#;
+    load_asg(program_string).unwrap();
+}
+
+#[test]
+fn test_call_argument() {
+    let program_string = r#"
+function scale(x: u64, factor: u64) -> u64 {
+    return x * factor;
+}
+
+function main() {
+    let a = scale(3, 4);
+    for i in 0u8..10 {
+        let b = i + 1;
+    }
+}
+"#;
+    load_asg(program_string).unwrap();
+}
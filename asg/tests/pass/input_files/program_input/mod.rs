@@ -27,3 +27,23 @@ fn test_input_multiple() {
     let program_string = include_str!("main_multiple.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_parameter_named_input() {
+    let program_string = r#"
+function scale(input: u8) -> u8 {
+    return input * 2;
+}
+
+function main(a: bool) -> bool {
+    let doubled = scale(3);
+    return a == input.registers.b;
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+
+    let scale = program.functions.get("scale").unwrap();
+    assert_eq!(scale.arguments["input"].get().borrow().references.len(), 1);
+    let program_input = program.scope.resolve_input().unwrap().container;
+    assert_eq!(program_input.borrow().references.len(), 1);
+}
@@ -15,6 +15,12 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::load_asg;
+use leo_asg::SymbolTable;
+use leo_errors::Span;
+
+fn span(line: usize, col_start: usize, col_stop: usize) -> Span {
+    Span::new(line, line, col_start, col_stop, "test".to_string().into(), "".into())
+}
 
 #[test]
 fn test_access_state() {
@@ -27,3 +33,37 @@ fn test_access_all() {
     let program_string = include_str!("access_all.leo");
     load_asg(program_string).unwrap();
 }
+
+#[test]
+fn test_record_fields_from_schema() {
+    let program_string = r#"
+function main() -> u64 {
+    let owner = input.record.owner;
+    let expected: address = aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8sta57j8;
+    console.assert(owner == expected);
+    return input.record.gates + input.record.value;
+}
+"#;
+    let program = load_asg(program_string).unwrap();
+    let table = SymbolTable::new(&program);
+
+    let owner = table.expr_at("main", &span(3, 17, 35)).unwrap();
+    assert_eq!(owner.type_.as_ref().unwrap().to_string(), "address");
+    let gates = table.expr_at("main", &span(6, 12, 30)).unwrap();
+    assert_eq!(gates.type_.as_ref().unwrap().to_string(), "u64");
+}
+
+#[test]
+fn test_record_custom_data_field() {
+    let program_string = r#"
+circuit Record {
+    owner: address,
+    amount: u32,
+}
+
+function main() -> u32 {
+    return input.record.amount + 1;
+}
+"#;
+    load_asg(program_string).unwrap();
+}
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{load_asg_imports, make_test_context};
+
 #[macro_use]
 pub mod int_macro;
 
@@ -35,3 +37,18 @@ pub mod i16;
 pub mod i32;
 pub mod i64;
 pub mod i8;
+
+#[test]
+fn test_mixed_width_integers_widened() {
+    let program_string = r#"
+function main(a: u8, b: u16, c: i8, d: i64) -> bool {
+    let x: u16 = a + b;
+    let y = b * a;
+    let z = c - d;
+    return x == y && a < b && z >= c;
+}
+"#;
+    let context = make_test_context();
+    context.set_integer_widening(true);
+    load_asg_imports(context, program_string).unwrap();
+}
@@ -21,11 +21,16 @@ pub mod circuits;
 pub mod console;
 pub mod core;
 pub mod definition;
+pub mod dot;
 pub mod field;
 pub mod function;
 pub mod group;
+pub mod inference;
 pub mod input_files;
 pub mod integers;
 pub mod mutability;
+pub mod semantic_tokens;
 pub mod statements;
+pub mod strings;
+pub mod symbol_table;
 pub mod tuples;
@@ -0,0 +1,239 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::load_asg;
+use leo_asg::{semantic_tokens, CircuitMember, SemanticKind, SemanticModifiers, SemanticToken};
+use leo_parser::parse_ast;
+
+const PROGRAM: &str = r#"
+const LIMIT: u8 = 10u8;
+
+circuit Point {
+    x: u8,
+
+    function scale(self, factor: u8) -> u8 {
+        return self.x * factor;
+    }
+}
+
+function helper(a: u8) -> u8 {
+    return a + LIMIT;
+}
+
+function main(const n: u8, unused_arg: u8) -> u8 {
+    let total = 0u8;
+    const step = 1u8;
+    let p = Point { x: n };
+    let spare = 5u8;
+    for i in 0u8..3u8 {
+        total += helper(i) + step + p.scale(2u8);
+    }
+    return total;
+}
+"#;
+
+fn tokens(program_string: &str) -> Vec<SemanticToken> {
+    let ast = parse_ast("input.leo", program_string).unwrap();
+    let asg = load_asg(program_string).unwrap();
+    semantic_tokens(ast.as_repr(), Some(&asg))
+}
+
+fn token_at(tokens: &[SemanticToken], line: usize, col: usize) -> &SemanticToken {
+    tokens
+        .iter()
+        .find(|token| token.span.line_start == line && token.span.col_start == col)
+        .unwrap_or_else(|| panic!("no token at {}:{}", line, col))
+}
+
+#[test]
+fn test_kinds() {
+    let tokens = tokens(PROGRAM);
+    let kind_at = |line, col| token_at(&tokens, line, col).kind;
+
+    assert_eq!(kind_at(2, 7), SemanticKind::Constant);
+    assert_eq!(kind_at(4, 9), SemanticKind::Circuit);
+    assert_eq!(kind_at(5, 5), SemanticKind::CircuitMember);
+    assert_eq!(kind_at(7, 14), SemanticKind::CircuitMember);
+    assert_eq!(kind_at(7, 26), SemanticKind::Parameter);
+    assert_eq!(kind_at(8, 21), SemanticKind::CircuitMember);
+    assert_eq!(kind_at(8, 25), SemanticKind::Parameter);
+    assert_eq!(kind_at(12, 10), SemanticKind::Function);
+    assert_eq!(kind_at(13, 12), SemanticKind::Parameter);
+    assert_eq!(kind_at(13, 16), SemanticKind::Constant);
+    assert_eq!(kind_at(17, 9), SemanticKind::Local);
+    assert_eq!(kind_at(18, 11), SemanticKind::Constant);
+    assert_eq!(kind_at(19, 13), SemanticKind::Circuit);
+    assert_eq!(kind_at(19, 21), SemanticKind::CircuitMember);
+    assert_eq!(kind_at(21, 9), SemanticKind::LoopVariable);
+    assert_eq!(kind_at(22, 18), SemanticKind::Function);
+    assert_eq!(kind_at(22, 25), SemanticKind::LoopVariable);
+    assert_eq!(kind_at(22, 30), SemanticKind::Constant);
+    assert_eq!(kind_at(22, 37), SemanticKind::Local);
+    assert_eq!(kind_at(22, 39), SemanticKind::CircuitMember);
+
+    // `self` is a keyword, not a token.
+    assert!(tokens
+        .iter()
+        .all(|token| !(token.span.line_start == 8 && token.span.col_start == 16)));
+}
+
+#[test]
+fn test_modifiers() {
+    let tokens = tokens(PROGRAM);
+    let modifiers_at = |line, col| token_at(&tokens, line, col).modifiers;
+
+    // The const parameter `n`, and its use in the circuit initializer.
+    assert!(modifiers_at(16, 21).constant);
+    assert!(modifiers_at(19, 24).constant);
+    assert!(modifiers_at(17, 9).mutable);
+    assert!(modifiers_at(22, 9).mutable);
+    assert_eq!(
+        modifiers_at(18, 11),
+        SemanticModifiers {
+            mutable: false,
+            constant: true,
+            unused: false,
+        }
+    );
+}
+
+#[test]
+fn test_unused_matches_references() {
+    let ast = parse_ast("input.leo", PROGRAM).unwrap();
+    let asg = load_asg(PROGRAM).unwrap();
+    let tokens = semantic_tokens(ast.as_repr(), Some(&asg));
+
+    let unused: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.modifiers.unused)
+        .map(|token| (token.span.line_start, token.span.col_start))
+        .collect();
+    assert_eq!(unused, vec![(16, 28), (20, 9)]);
+
+    // Parameters agree with the references recorded in the asg, including those of member functions.
+    let mut functions: Vec<_> = asg.functions.values().copied().collect();
+    for circuit in asg.circuits.values() {
+        for member in circuit.members.borrow().values() {
+            if let CircuitMember::Function(function) = member {
+                functions.push(*function);
+            }
+        }
+    }
+    for function in functions {
+        for argument in function.arguments.values() {
+            let argument = argument.get().borrow();
+            if argument.name.name.as_ref() == "self" {
+                continue;
+            }
+            let token = token_at(&tokens, argument.name.span.line_start, argument.name.span.col_start);
+            assert_eq!(token.modifiers.unused, argument.references.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_ordered_and_disjoint() {
+    let tokens = tokens(PROGRAM);
+
+    for pair in tokens.windows(2) {
+        let (first, second) = (&pair[0].span, &pair[1].span);
+        assert!((first.line_stop, first.col_stop) <= (second.line_start, second.col_start));
+    }
+}
+
+#[test]
+fn test_unresolved_identifier() {
+    let program_string = r#"
+function main() -> u8 {
+    let a = missing;
+    return a;
+}
+"#;
+    let ast = parse_ast("input.leo", program_string).unwrap();
+    let tokens = semantic_tokens(ast.as_repr(), None);
+
+    assert_eq!(token_at(&tokens, 3, 13).kind, SemanticKind::Unknown);
+    assert_eq!(token_at(&tokens, 4, 12).kind, SemanticKind::Local);
+}
+
+#[test]
+fn test_generated_names_left_out() {
+    use leo_ast::{builder::*, IntegerType, NameGenerator, Type};
+
+    let u32_type = || Type::IntegerType(IntegerType::U32);
+    let mut generator = NameGenerator::new(&program().build()).unwrap();
+    let temporary = generator.fresh("tmp");
+    let ast = program()
+        .function(func::new("main").input("a", u32_type()).returns(u32_type()).body(vec![
+            stmt::let_(&temporary, None, expr::ident("a")),
+            stmt::let_("unused", None, expr::int(1, IntegerType::U32)),
+            stmt::return_(expr::ident(&temporary)),
+        ]))
+        .build();
+
+    let tokens = semantic_tokens(&ast, None);
+    let kinds = tokens.iter().map(|token| token.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            SemanticKind::Function,
+            SemanticKind::Parameter,
+            SemanticKind::Parameter,
+            SemanticKind::Local,
+        ]
+    );
+}
+
+#[test]
+fn test_variable_named_input() {
+    let tokens = tokens(
+        r#"
+function scale(input: u8) -> u8 {
+    return input * 2;
+}
+
+function main(a: bool) -> bool {
+    return a == input.registers.b;
+}
+"#,
+    );
+
+    assert_eq!(token_at(&tokens, 2, 16).kind, SemanticKind::Parameter);
+    assert_eq!(token_at(&tokens, 3, 12).kind, SemanticKind::Parameter);
+    assert!(tokens
+        .iter()
+        .all(|token| !(token.span.line_start == 7 && token.span.col_start == 17)));
+}
+
+#[test]
+fn test_static_access_through_alias() {
+    let program_string = r#"
+circuit Point {
+    function origin() -> u8 {
+        return 0u8;
+    }
+}
+
+type Vector = Point;
+
+function main() -> u8 {
+    return Vector::origin();
+}
+"#;
+    let tokens = tokens(program_string);
+    assert_eq!(token_at(&tokens, 11, 12).kind, SemanticKind::Alias);
+    assert_eq!(token_at(&tokens, 11, 20).kind, SemanticKind::CircuitMember);
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::load_asg;
+use crate::{compile_pass, load_asg};
 
 pub mod conditional;
 
@@ -34,8 +34,26 @@ fn test_iteration_basic() {
     load_asg(program_string).unwrap();
 }
 
+#[test]
+fn test_iteration_step() {
+    let program_string = include_str!("iteration_step.leo");
+    compile_pass(program_string);
+}
+
 #[test]
 fn test_block() {
     let program_string = include_str!("block.leo");
-    load_asg(program_string).unwrap();
+    compile_pass(program_string);
+}
+
+#[test]
+fn test_assignment_in_branches() {
+    let program_string = include_str!("assignment_in_branches.leo");
+    compile_pass(program_string);
+}
+
+#[test]
+fn test_uninitialized_declarations() {
+    let program_string = include_str!("uninitialized_declarations.leo");
+    compile_pass(program_string);
 }
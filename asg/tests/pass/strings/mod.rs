@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{load_asg_imports, make_test_context};
+use leo_asg::{CharValue, ConstValue, Expression, ExpressionNode, Program, Statement};
+
+/// Returns the expression returned by the last statement of `main`.
+fn main_return<'a>(program: &Program<'a>) -> &'a Expression<'a> {
+    let main = program.functions.get("main").unwrap();
+    match main.body.get() {
+        Some(Statement::Block(block)) => match block.statements.last().unwrap().get() {
+            Statement::Return(statement) => statement.expression.get(),
+            _ => panic!("main does not end with a return"),
+        },
+        _ => panic!("main has no body"),
+    }
+}
+
+/// Returns the text of a constant string.
+fn string_text(value: ConstValue) -> String {
+    match value {
+        ConstValue::Array(chars) => chars
+            .into_iter()
+            .map(|value| match value {
+                ConstValue::Char(CharValue::Scalar(scalar)) => scalar,
+                _ => panic!("string contains a value that is not a scalar char"),
+            })
+            .collect(),
+        _ => panic!("value is not a string"),
+    }
+}
+
+#[test]
+fn test_equal_length_comparison() {
+    let program_string = r#"
+function main(a: [char; 2]) -> bool {
+    return a == "ab";
+}
+"#;
+    let context = make_test_context();
+    let program = load_asg_imports(context, program_string).unwrap();
+
+    assert!(matches!(main_return(&program), Expression::Binary(_)));
+    assert!(context.take_warnings().is_empty());
+}
+
+#[test]
+fn test_different_length_comparison_folded() {
+    let program_string = r#"
+function main(a: [char; 2]) -> bool {
+    let equal = a == "abc";
+    return "abc" != a;
+}
+"#;
+    let context = make_test_context();
+    let program = load_asg_imports(context, program_string).unwrap();
+
+    assert!(matches!(
+        main_return(&program).const_value(),
+        Some(ConstValue::Boolean(true))
+    ));
+    let warnings = context.take_warnings();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(
+        warnings[0].message,
+        "strings of different lengths are never equal — this comparison is always false"
+    );
+    assert_eq!(warnings[0].span.line_start, 3);
+    assert!(warnings[1].message.ends_with("this comparison is always true"));
+}
+
+#[test]
+fn test_constant_concatenation() {
+    let program_string = r#"
+function main() -> [char; 9] {
+    const greeting = "hi" + ", ";
+    const name: [char; 3] = "leo";
+    return greeting + name + "!!";
+}
+"#;
+    let context = make_test_context();
+    let program = load_asg_imports(context, program_string).unwrap();
+
+    let value = main_return(&program).const_value().unwrap();
+    assert_eq!(string_text(value), "hi, leo!!");
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::load_asg;
+use leo_asg::{ConstInt, ConstValue, Constness, SymbolTable};
+use leo_errors::Span;
+
+const PROGRAM: &str = r#"
+function helper(a: u32, const n: u32) -> u32 {
+    let b = a * 2 + 1;
+    let c = 3u32 + 4;
+    let d = n + 1;
+    return b + c + d;
+}
+
+circuit Counter {
+    count: u8,
+
+    function next(self) -> u8 {
+        return self.count + 1;
+    }
+}
+
+function main() {}
+"#;
+
+fn span(line: usize, col_start: usize, col_stop: usize) -> Span {
+    Span::new(line, line, col_start, col_stop, "test".to_string().into(), "".into())
+}
+
+#[test]
+fn test_nested_binary_type() {
+    let program = load_asg(PROGRAM).unwrap();
+    let table = SymbolTable::new(&program);
+
+    let sum = table.expr_at("helper", &span(3, 13, 22)).unwrap();
+    assert_eq!(sum.type_.as_ref().unwrap().to_string(), "u32");
+    assert!(sum.constness == Constness::Runtime);
+
+    let product = table.expr_at("helper", &span(3, 13, 18)).unwrap();
+    assert_eq!(product.type_.as_ref().unwrap().to_string(), "u32");
+
+    let literal = table.expr_at("helper", &span(3, 17, 18)).unwrap();
+    assert_eq!(literal.type_.as_ref().unwrap().to_string(), "u32");
+    assert!(literal.constness == Constness::Const(ConstValue::Int(ConstInt::U32(2))));
+}
+
+#[test]
+fn test_constness() {
+    let program = load_asg(PROGRAM).unwrap();
+    let table = SymbolTable::new(&program);
+
+    let folded = table.expr_at("helper", &span(4, 13, 21)).unwrap();
+    assert!(folded.constness == Constness::Const(ConstValue::Int(ConstInt::U32(7))));
+
+    let const_parameter = table.expr_at("helper", &span(5, 13, 18)).unwrap();
+    assert!(const_parameter.constness == Constness::ConstFoldable);
+
+    let parameter = table.expr_at("helper", &span(3, 13, 14)).unwrap();
+    assert!(parameter.constness == Constness::Runtime);
+}
+
+#[test]
+fn test_lookup_by_span() {
+    let program = load_asg(PROGRAM).unwrap();
+    let table = SymbolTable::new(&program);
+
+    assert!(table.expr_at("helper", &span(3, 13, 19)).is_none());
+    assert!(table.expr_at("main", &span(3, 13, 22)).is_none());
+    assert!(table.expr_at("missing", &span(3, 13, 22)).is_none());
+    assert!(table.expressions("main").is_empty());
+
+    let member = table.expr_at("Counter::next", &span(13, 16, 30)).unwrap();
+    assert_eq!(member.type_.as_ref().unwrap().to_string(), "u8");
+}
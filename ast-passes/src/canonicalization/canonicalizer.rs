@@ -17,24 +17,40 @@
 use leo_ast::*;
 use leo_errors::{AstError, Result, Span};
 
+use indexmap::IndexMap;
+
 /// Replace Self when it is in a enclosing circuit type.
 /// Error when Self is outside an enclosing circuit type.
 /// Tuple array types and expressions expand to nested arrays.
 /// Tuple array types and expressions error if a size of 0 is given.
-/// Compound operators become simple assignments.
+/// Compound operators become simple assignments, with computed indices bound to new variables beforehand.
 /// Functions missing output type return a empty tuple.
+/// The expected input of the program becomes the parameters of `main`.
+/// Array comprehensions over constant ranges become inline arrays.
 #[derive(Default)]
 pub struct Canonicalizer {
-    // If we are in a circuit keep track of the circuit name.
-    circuit_name: Option<Identifier>,
+    // The names of the enclosing circuits, innermost last.
+    circuit_names: Vec<Identifier>,
     in_circuit: bool,
+    names: NameGenerator,
+    // The definitions of the indices bound by the compound assignment being reduced.
+    bindings: Vec<Statement>,
 }
 
 impl AstPass for Canonicalizer {
     fn do_pass(ast: Program) -> Result<Ast> {
-        Ok(Ast::new(
-            ReconstructingDirector::new(Self::default()).reduce_program(&ast)?,
-        ))
+        Self::do_pass_with_max_depth(ast, DEFAULT_MAX_EXPRESSION_DEPTH)
+    }
+}
+
+impl Canonicalizer {
+    /// Canonicalizes the program, failing with a diagnostic if expressions nest more than `max_depth` levels deep.
+    pub fn do_pass_with_max_depth(ast: Program, max_depth: usize) -> Result<Ast> {
+        let canonicalizer = Canonicalizer {
+            names: NameGenerator::new(&ast)?,
+            ..Default::default()
+        };
+        Ok(ReducerPass::new(canonicalizer).with_max_depth(max_depth).run(&ast)?.0)
     }
 }
 
@@ -48,7 +64,7 @@ impl Canonicalizer {
         let mut left = Box::new(start);
 
         for access in accesses.iter() {
-            match self.canonicalize_assignee_access(access) {
+            match self.canonicalize_assignee_access(access)? {
                 AssigneeAccess::ArrayIndex(index) => {
                     left = Box::new(Expression::ArrayAccess(ArrayAccessExpression {
                         array: left,
@@ -85,6 +101,37 @@ impl Canonicalizer {
         Ok(left)
     }
 
+    ///
+    /// Binds every array index of the assignee that is computed from more than a single identifier or literals
+    /// to a new variable, so that the read and the write of a compound assignment compute it once, in order.
+    ///
+    fn bind_indices(&mut self, assignee: Assignee) -> Assignee {
+        let mut accesses = vec![];
+        for access in assignee.accesses.into_iter() {
+            match access {
+                AssigneeAccess::ArrayIndex(index) if !is_trivial(&index) => {
+                    let span = index.span().clone();
+                    let identifier = self.names.fresh_identifier("index", span.clone());
+                    self.bindings.push(Statement::Definition(DefinitionStatement {
+                        declaration_type: Declare::Let,
+                        variable_names: vec![VariableName {
+                            mutable: false,
+                            identifier: identifier.clone(),
+                            span: span.clone(),
+                        }],
+                        type_: None,
+                        value: Some(index),
+                        span,
+                    }));
+                    accesses.push(AssigneeAccess::ArrayIndex(Expression::Identifier(identifier)));
+                }
+                access => accesses.push(access),
+            }
+        }
+
+        Assignee { accesses, ..assignee }
+    }
+
     pub fn compound_operation_converstion(&mut self, operation: &AssignOperation) -> Result<BinaryOperation> {
         match operation {
             AssignOperation::Assign => unreachable!(),
@@ -105,83 +152,107 @@ impl Canonicalizer {
         }
     }
 
-    fn canonicalize_self_type(&self, type_option: Option<&Type>) -> Option<Type> {
+    /// Returns the name of the innermost enclosing circuit, which `Self` refers to.
+    fn resolve_self(&self, span: &Span) -> Result<Identifier> {
+        match self.circuit_names.last() {
+            Some(circuit_name) => Ok(circuit_name.clone()),
+            None => Err(AstError::big_self_outside_of_circuit(span).into()),
+        }
+    }
+
+    fn canonicalize_self_type(&self, type_option: Option<&Type>, span: &Span) -> Result<Option<Type>> {
         match type_option {
-            Some(type_) => match type_ {
-                Type::SelfType => Some(Type::Identifier(self.circuit_name.as_ref().unwrap().clone())),
-                Type::Array(type_, dimensions) => Some(Type::Array(
-                    Box::new(self.canonicalize_self_type(Some(type_)).unwrap()),
-                    dimensions.clone(),
-                )),
-                Type::Tuple(types) => Some(Type::Tuple(
-                    types
-                        .iter()
-                        .map(|type_| self.canonicalize_self_type(Some(type_)).unwrap())
-                        .collect(),
-                )),
-                _ => Some(type_.clone()),
-            },
-            None => None,
+            Some(type_) => self.canonicalize_type(type_, span).map(Some),
+            None => Ok(None),
         }
     }
 
+    fn canonicalize_type(&self, type_: &Type, span: &Span) -> Result<Type> {
+        Ok(match type_ {
+            Type::SelfType => Type::Identifier(self.resolve_self(span)?),
+            Type::Array(type_, dimensions) => {
+                Type::Array(Box::new(self.canonicalize_type(type_, span)?), dimensions.clone())
+            }
+            Type::Tuple(types) => Type::Tuple(
+                types
+                    .iter()
+                    .map(|type_| self.canonicalize_type(type_, span))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            _ => type_.clone(),
+        })
+    }
+
     fn canonicalize_circuit_implied_variable_definition(
         &mut self,
         member: &CircuitImpliedVariableDefinition,
-    ) -> CircuitImpliedVariableDefinition {
-        CircuitImpliedVariableDefinition {
+    ) -> Result<CircuitImpliedVariableDefinition> {
+        Ok(CircuitImpliedVariableDefinition {
             identifier: member.identifier.clone(),
             expression: member
                 .expression
                 .as_ref()
-                .map(|expr| self.canonicalize_expression(expr)),
-        }
+                .map(|expr| self.canonicalize_expression(expr))
+                .transpose()?,
+        })
     }
 
-    fn canonicalize_expression(&mut self, expression: &Expression) -> Expression {
+    fn canonicalize_console_args(&mut self, args: &ConsoleArgs) -> Result<ConsoleArgs> {
+        Ok(ConsoleArgs {
+            string: args.string.clone(),
+            parameters: args
+                .parameters
+                .iter()
+                .map(|parameter| self.canonicalize_expression(parameter))
+                .collect::<Result<Vec<_>>>()?,
+            span: args.span.clone(),
+        })
+    }
+
+    fn canonicalize_expression(&mut self, expression: &Expression) -> Result<Expression> {
         match expression {
             Expression::Unary(unary) => {
-                let inner = Box::new(self.canonicalize_expression(&unary.inner));
+                let inner = Box::new(self.canonicalize_expression(&unary.inner)?);
 
-                return Expression::Unary(UnaryExpression {
+                return Ok(Expression::Unary(UnaryExpression {
                     inner,
                     op: unary.op.clone(),
                     span: unary.span.clone(),
-                });
+                }));
             }
             Expression::Binary(binary) => {
-                let left = Box::new(self.canonicalize_expression(&binary.left));
-                let right = Box::new(self.canonicalize_expression(&binary.right));
+                let left = Box::new(self.canonicalize_expression(&binary.left)?);
+                let right = Box::new(self.canonicalize_expression(&binary.right)?);
 
-                return Expression::Binary(BinaryExpression {
+                return Ok(Expression::Binary(BinaryExpression {
                     left,
                     right,
                     op: binary.op.clone(),
                     span: binary.span.clone(),
-                });
+                }));
             }
             Expression::Ternary(ternary) => {
-                let condition = Box::new(self.canonicalize_expression(&ternary.condition));
-                let if_true = Box::new(self.canonicalize_expression(&ternary.if_true));
-                let if_false = Box::new(self.canonicalize_expression(&ternary.if_false));
+                let condition = Box::new(self.canonicalize_expression(&ternary.condition)?);
+                let if_true = Box::new(self.canonicalize_expression(&ternary.if_true)?);
+                let if_false = Box::new(self.canonicalize_expression(&ternary.if_false)?);
 
-                return Expression::Ternary(TernaryExpression {
+                return Ok(Expression::Ternary(TernaryExpression {
                     condition,
                     if_true,
                     if_false,
                     span: ternary.span.clone(),
-                });
+                }));
             }
 
             Expression::Cast(cast) => {
-                let inner = Box::new(self.canonicalize_expression(&cast.inner));
-                let target_type = self.canonicalize_self_type(Some(&cast.target_type)).unwrap();
+                let inner = Box::new(self.canonicalize_expression(&cast.inner)?);
+                let target_type = self.canonicalize_type(&cast.target_type, &cast.span)?;
 
-                return Expression::Cast(CastExpression {
+                return Ok(Expression::Cast(CastExpression {
                     inner,
                     target_type,
                     span: cast.span.clone(),
-                });
+                }));
             }
 
             Expression::ArrayInline(array_inline) => {
@@ -189,59 +260,61 @@ impl Canonicalizer {
                     .elements
                     .iter()
                     .map(|element| match element {
-                        SpreadOrExpression::Expression(expression) => {
-                            SpreadOrExpression::Expression(self.canonicalize_expression(expression))
-                        }
+                        SpreadOrExpression::Expression(expression) => Ok(SpreadOrExpression::Expression(
+                            self.canonicalize_expression(expression)?,
+                        )),
                         SpreadOrExpression::Spread(expression) => {
-                            SpreadOrExpression::Spread(self.canonicalize_expression(expression))
+                            Ok(SpreadOrExpression::Spread(self.canonicalize_expression(expression)?))
                         }
                     })
-                    .collect();
+                    .collect::<Result<Vec<_>>>()?;
 
-                return Expression::ArrayInline(ArrayInlineExpression {
+                return Ok(Expression::ArrayInline(ArrayInlineExpression {
                     elements,
                     span: array_inline.span.clone(),
-                });
+                }));
             }
 
             Expression::ArrayInit(array_init) => {
-                let element = Box::new(self.canonicalize_expression(&array_init.element));
+                let element = Box::new(self.canonicalize_expression(&array_init.element)?);
 
-                return Expression::ArrayInit(ArrayInitExpression {
+                return Ok(Expression::ArrayInit(ArrayInitExpression {
                     dimensions: array_init.dimensions.clone(),
                     element,
                     span: array_init.span.clone(),
-                });
+                }));
             }
 
             Expression::ArrayAccess(array_access) => {
-                let array = Box::new(self.canonicalize_expression(&array_access.array));
-                let index = Box::new(self.canonicalize_expression(&array_access.index));
+                let array = Box::new(self.canonicalize_expression(&array_access.array)?);
+                let index = Box::new(self.canonicalize_expression(&array_access.index)?);
 
-                return Expression::ArrayAccess(ArrayAccessExpression {
+                return Ok(Expression::ArrayAccess(ArrayAccessExpression {
                     array,
                     index,
                     span: array_access.span.clone(),
-                });
+                }));
             }
 
             Expression::ArrayRangeAccess(array_range_access) => {
-                let array = Box::new(self.canonicalize_expression(&array_range_access.array));
+                let array = Box::new(self.canonicalize_expression(&array_range_access.array)?);
                 let left = array_range_access
                     .left
                     .as_ref()
-                    .map(|left| Box::new(self.canonicalize_expression(left)));
+                    .map(|left| self.canonicalize_expression(left).map(Box::new))
+                    .transpose()?;
                 let right = array_range_access
                     .right
                     .as_ref()
-                    .map(|right| Box::new(self.canonicalize_expression(right)));
+                    .map(|right| self.canonicalize_expression(right).map(Box::new))
+                    .transpose()?;
 
-                return Expression::ArrayRangeAccess(ArrayRangeAccessExpression {
+                return Ok(Expression::ArrayRangeAccess(ArrayRangeAccessExpression {
                     array,
                     left,
                     right,
                     span: array_range_access.span.clone(),
-                });
+                }));
             }
 
             Expression::TupleInit(tuple_init) => {
@@ -249,129 +322,245 @@ impl Canonicalizer {
                     .elements
                     .iter()
                     .map(|element| self.canonicalize_expression(element))
-                    .collect();
+                    .collect::<Result<Vec<_>>>()?;
 
-                return Expression::TupleInit(TupleInitExpression {
+                return Ok(Expression::TupleInit(TupleInitExpression {
                     elements,
                     span: tuple_init.span.clone(),
-                });
+                }));
             }
 
             Expression::TupleAccess(tuple_access) => {
-                let tuple = Box::new(self.canonicalize_expression(&tuple_access.tuple));
+                let tuple = Box::new(self.canonicalize_expression(&tuple_access.tuple)?);
 
-                return Expression::TupleAccess(TupleAccessExpression {
+                return Ok(Expression::TupleAccess(TupleAccessExpression {
                     tuple,
                     index: tuple_access.index.clone(),
                     span: tuple_access.span.clone(),
-                });
+                }));
             }
 
             Expression::CircuitInit(circuit_init) => {
                 let mut name = circuit_init.name.clone();
-                if name.name.as_ref() == "Self" && self.circuit_name.is_some() {
-                    name = self.circuit_name.as_ref().unwrap().clone();
+                if name.name.as_ref() == "Self" {
+                    name = self.resolve_self(&name.span)?;
                 }
 
-                return Expression::CircuitInit(CircuitInitExpression {
+                return Ok(Expression::CircuitInit(CircuitInitExpression {
                     name,
                     members: circuit_init
                         .members
                         .iter()
                         .map(|member| self.canonicalize_circuit_implied_variable_definition(member))
-                        .collect(),
+                        .collect::<Result<Vec<_>>>()?,
                     span: circuit_init.span.clone(),
-                });
+                }));
             }
             Expression::CircuitMemberAccess(circuit_member_access) => {
-                return Expression::CircuitMemberAccess(CircuitMemberAccessExpression {
-                    circuit: Box::new(self.canonicalize_expression(&circuit_member_access.circuit)),
+                return Ok(Expression::CircuitMemberAccess(CircuitMemberAccessExpression {
+                    circuit: Box::new(self.canonicalize_expression(&circuit_member_access.circuit)?),
                     name: circuit_member_access.name.clone(),
                     span: circuit_member_access.span.clone(),
                     type_: None,
-                });
+                }));
             }
             Expression::CircuitStaticFunctionAccess(circuit_static_func_access) => {
-                return Expression::CircuitStaticFunctionAccess(CircuitStaticFunctionAccessExpression {
-                    circuit: Box::new(self.canonicalize_expression(&circuit_static_func_access.circuit)),
-                    name: circuit_static_func_access.name.clone(),
-                    span: circuit_static_func_access.span.clone(),
-                });
+                return Ok(Expression::CircuitStaticFunctionAccess(
+                    CircuitStaticFunctionAccessExpression {
+                        circuit: Box::new(self.canonicalize_expression(&circuit_static_func_access.circuit)?),
+                        name: circuit_static_func_access.name.clone(),
+                        span: circuit_static_func_access.span.clone(),
+                    },
+                ));
             }
             Expression::Call(call) => {
-                return Expression::Call(CallExpression {
-                    function: Box::new(self.canonicalize_expression(&call.function)),
+                return Ok(Expression::Call(CallExpression {
+                    function: Box::new(self.canonicalize_expression(&call.function)?),
                     arguments: call
                         .arguments
                         .iter()
                         .map(|arg| self.canonicalize_expression(arg))
-                        .collect(),
+                        .collect::<Result<Vec<_>>>()?,
                     span: call.span.clone(),
-                });
+                }));
+            }
+            Expression::LengthOf(lengthof) => {
+                return Ok(Expression::LengthOf(LengthOfExpression {
+                    inner: Box::new(self.canonicalize_expression(&lengthof.inner)?),
+                    span: lengthof.span.clone(),
+                }));
+            }
+            Expression::ArrayComprehension(comprehension) => {
+                return self.lower_array_comprehension(comprehension.clone());
+            }
+            Expression::Parenthesized(parenthesized) => {
+                return Ok(Expression::Parenthesized(ParenthesizedExpression {
+                    inner: Box::new(self.canonicalize_expression(&parenthesized.inner)?),
+                    span: parenthesized.span.clone(),
+                }));
             }
             Expression::Identifier(identifier) => {
-                if identifier.name.as_ref() == "Self" && self.circuit_name.is_some() {
-                    return Expression::Identifier(self.circuit_name.as_ref().unwrap().clone());
+                if identifier.name.as_ref() == "Self" {
+                    return Ok(Expression::Identifier(self.resolve_self(&identifier.span)?));
                 }
             }
-            _ => (),
+            Expression::Value(_) | Expression::Err(_) => (),
         }
 
-        expression.clone()
+        Ok(expression.clone())
     }
 
-    fn canonicalize_assignee_access(&mut self, access: &AssigneeAccess) -> AssigneeAccess {
-        match access {
+    /// Evaluates a bound of an array comprehension range, along with its integer type if it has one.
+    fn const_range_bound(&self, bound: &Expression) -> Result<(i128, Option<IntegerType>)> {
+        let error = || AstError::array_comprehension_non_const_range(bound.span());
+        match bound {
+            Expression::Value(ValueExpression::Implicit(value, _)) => {
+                Ok((value.parse::<i128>().map_err(|_| error())?, None))
+            }
+            Expression::Value(ValueExpression::Integer(type_, value, _)) => {
+                Ok((value.parse::<i128>().map_err(|_| error())?, Some(type_.clone())))
+            }
+            Expression::Unary(unary) if unary.op == UnaryOperation::Negate => {
+                let (value, type_) = self.const_range_bound(&unary.inner)?;
+                Ok((value.checked_neg().ok_or_else(error)?, type_))
+            }
+            Expression::Binary(binary) => {
+                let (left, left_type) = self.const_range_bound(&binary.left)?;
+                let (right, right_type) = self.const_range_bound(&binary.right)?;
+                let value = match binary.op {
+                    BinaryOperation::Add => left.checked_add(right),
+                    BinaryOperation::Sub => left.checked_sub(right),
+                    BinaryOperation::Mul => left.checked_mul(right),
+                    _ => None,
+                };
+                Ok((value.ok_or_else(error)?, left_type.or(right_type)))
+            }
+            Expression::Parenthesized(parenthesized) => self.const_range_bound(&parenthesized.inner),
+            _ => Err(error().into()),
+        }
+    }
+
+    /// Unrolls an array comprehension into an inline array with one element per value of its range.
+    fn lower_array_comprehension(&self, comprehension: ArrayComprehensionExpression) -> Result<Expression> {
+        let (start, start_type) = self.const_range_bound(&comprehension.start)?;
+        let (stop, stop_type) = self.const_range_bound(&comprehension.stop)?;
+        let type_ = start_type.or(stop_type);
+
+        let end = if comprehension.inclusive {
+            stop.checked_add(1)
+                .ok_or_else(|| AstError::array_comprehension_non_const_range(comprehension.stop.span()))?
+        } else {
+            stop
+        };
+        if start >= end {
+            return Err(AstError::array_comprehension_empty_range(
+                &comprehension.start,
+                format!(
+                    "{}{}",
+                    if comprehension.inclusive { "=" } else { "" },
+                    comprehension.stop
+                ),
+                &comprehension.span,
+            )
+            .into());
+        }
+
+        let elements = (start..end)
+            .map(|index| {
+                let substitution = Substitution {
+                    variable: comprehension.variable.name.to_string(),
+                    value: index.to_string(),
+                    type_: type_.clone(),
+                    in_circuit: self.in_circuit,
+                };
+                ReconstructingDirector::new(substitution)
+                    .reduce_expression(&comprehension.element)
+                    .map(SpreadOrExpression::Expression)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Expression::ArrayInline(ArrayInlineExpression {
+            elements,
+            span: comprehension.span,
+        }))
+    }
+
+    /// Returns how many bytes of source text, starting at `offset`, spell the next character of a string literal.
+    fn char_source_width(content: &str, offset: usize) -> Option<usize> {
+        let rest = content.get(offset..)?;
+        let mut chars = rest.chars();
+        match chars.next()? {
+            '\\' => match chars.next()? {
+                'x' => Some(4),
+                'u' => Some(rest.find('}')? + 1),
+                _ => Some(2),
+            },
+            character => Some(character.len_utf8()),
+        }
+    }
+
+    fn canonicalize_assignee_access(&mut self, access: &AssigneeAccess) -> Result<AssigneeAccess> {
+        Ok(match access {
             AssigneeAccess::ArrayRange(left, right) => {
-                let left = left.as_ref().map(|left| self.canonicalize_expression(left));
-                let right = right.as_ref().map(|right| self.canonicalize_expression(right));
+                let left = left
+                    .as_ref()
+                    .map(|left| self.canonicalize_expression(left))
+                    .transpose()?;
+                let right = right
+                    .as_ref()
+                    .map(|right| self.canonicalize_expression(right))
+                    .transpose()?;
 
                 AssigneeAccess::ArrayRange(left, right)
             }
-            AssigneeAccess::ArrayIndex(index) => AssigneeAccess::ArrayIndex(self.canonicalize_expression(index)),
+            AssigneeAccess::ArrayIndex(index) => AssigneeAccess::ArrayIndex(self.canonicalize_expression(index)?),
             _ => access.clone(),
-        }
+        })
     }
 
-    fn canonicalize_assignee(&mut self, assignee: &Assignee) -> Assignee {
+    fn canonicalize_assignee(&mut self, assignee: &Assignee) -> Result<Assignee> {
         let accesses = assignee
             .accesses
             .iter()
             .map(|access| self.canonicalize_assignee_access(access))
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        Assignee {
+        Ok(Assignee {
             identifier: assignee.identifier.clone(),
             accesses,
             span: assignee.span.clone(),
-        }
+        })
     }
 
-    fn canonicalize_block(&mut self, block: &Block) -> Block {
+    fn canonicalize_block(&mut self, block: &Block) -> Result<Block> {
         let statements = block
             .statements
             .iter()
             .map(|block_statement| self.canonicalize_statement(block_statement))
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        Block {
+        Ok(Block {
             statements,
             span: block.span.clone(),
-        }
+        })
     }
 
-    fn canonicalize_statement(&mut self, statement: &Statement) -> Statement {
-        match statement {
+    fn canonicalize_statement(&mut self, statement: &Statement) -> Result<Statement> {
+        Ok(match statement {
             Statement::Return(return_statement) => {
-                let expression = self.canonicalize_expression(&return_statement.expression);
+                let expression = self.canonicalize_expression(&return_statement.expression)?;
                 Statement::Return(ReturnStatement {
                     expression,
                     span: return_statement.span.clone(),
                 })
             }
             Statement::Definition(definition) => {
-                let value = self.canonicalize_expression(&definition.value);
-                let type_ = self.canonicalize_self_type(definition.type_.as_ref());
+                let value = definition
+                    .value
+                    .as_ref()
+                    .map(|value| self.canonicalize_expression(value))
+                    .transpose()?;
+                let type_ = self.canonicalize_self_type(definition.type_.as_ref(), &definition.span)?;
 
                 Statement::Definition(DefinitionStatement {
                     declaration_type: definition.declaration_type.clone(),
@@ -382,8 +571,8 @@ impl Canonicalizer {
                 })
             }
             Statement::Assign(assign) => {
-                let assignee = self.canonicalize_assignee(&assign.assignee);
-                let value = self.canonicalize_expression(&assign.value);
+                let assignee = self.canonicalize_assignee(&assign.assignee)?;
+                let value = self.canonicalize_expression(&assign.value)?;
 
                 Statement::Assign(Box::new(AssignStatement {
                     assignee,
@@ -393,12 +582,13 @@ impl Canonicalizer {
                 }))
             }
             Statement::Conditional(conditional) => {
-                let condition = self.canonicalize_expression(&conditional.condition);
-                let block = self.canonicalize_block(&conditional.block);
+                let condition = self.canonicalize_expression(&conditional.condition)?;
+                let block = self.canonicalize_block(&conditional.block)?;
                 let next = conditional
                     .next
                     .as_ref()
-                    .map(|condition| Box::new(self.canonicalize_statement(condition)));
+                    .map(|condition| self.canonicalize_statement(condition).map(Box::new))
+                    .transpose()?;
 
                 Statement::Conditional(ConditionalStatement {
                     condition,
@@ -408,15 +598,21 @@ impl Canonicalizer {
                 })
             }
             Statement::Iteration(iteration) => {
-                let start = self.canonicalize_expression(&iteration.start);
-                let stop = self.canonicalize_expression(&iteration.stop);
-                let block = self.canonicalize_block(&iteration.block);
+                let start = self.canonicalize_expression(&iteration.start)?;
+                let stop = self.canonicalize_expression(&iteration.stop)?;
+                let step = iteration
+                    .step
+                    .as_ref()
+                    .map(|step| self.canonicalize_expression(step))
+                    .transpose()?;
+                let block = self.canonicalize_block(&iteration.block)?;
 
                 Statement::Iteration(Box::new(IterationStatement {
                     variable: iteration.variable.clone(),
                     start,
                     stop,
                     inclusive: iteration.inclusive,
+                    step,
                     block,
                     span: iteration.span.clone(),
                 }))
@@ -424,27 +620,22 @@ impl Canonicalizer {
             Statement::Console(console_function_call) => {
                 let function = match &console_function_call.function {
                     ConsoleFunction::Assert(expression) => {
-                        ConsoleFunction::Assert(self.canonicalize_expression(expression))
+                        ConsoleFunction::Assert(self.canonicalize_expression(expression)?)
                     }
-                    ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => {
-                        let parameters = args
-                            .parameters
-                            .iter()
-                            .map(|parameter| self.canonicalize_expression(parameter))
-                            .collect();
-
-                        let console_args = ConsoleArgs {
-                            string: args.string.clone(),
-                            parameters,
+                    ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                        let console_args = ConsoleAssertArgs {
+                            left: self.canonicalize_expression(&args.left)?,
+                            right: self.canonicalize_expression(&args.right)?,
                             span: args.span.clone(),
                         };
 
                         match &console_function_call.function {
-                            ConsoleFunction::Error(_) => ConsoleFunction::Error(console_args),
-                            ConsoleFunction::Log(_) => ConsoleFunction::Log(console_args),
-                            _ => unimplemented!(), // impossible
+                            ConsoleFunction::AssertEq(_) => ConsoleFunction::AssertEq(console_args),
+                            _ => ConsoleFunction::AssertNe(console_args),
                         }
                     }
+                    ConsoleFunction::Error(args) => ConsoleFunction::Error(self.canonicalize_console_args(args)?),
+                    ConsoleFunction::Log(args) => ConsoleFunction::Log(self.canonicalize_console_args(args)?),
                 };
 
                 Statement::Console(ConsoleStatement {
@@ -453,53 +644,60 @@ impl Canonicalizer {
                 })
             }
             Statement::Expression(expression) => Statement::Expression(ExpressionStatement {
-                expression: self.canonicalize_expression(&expression.expression),
+                expression: self.canonicalize_expression(&expression.expression)?,
                 span: expression.span.clone(),
             }),
-            Statement::Block(block) => Statement::Block(self.canonicalize_block(block)),
-        }
+            Statement::Block(block) => Statement::Block(self.canonicalize_block(block)?),
+        })
     }
 
-    fn canonicalize_function_input(&mut self, input: &FunctionInput) -> FunctionInput {
+    fn canonicalize_function_input(&mut self, input: &FunctionInput) -> Result<FunctionInput> {
         if let FunctionInput::Variable(variable) = input {
-            let type_ = self.canonicalize_self_type(Some(&variable.type_)).unwrap();
+            let type_ = self.canonicalize_type(&variable.type_, &variable.span)?;
 
-            return FunctionInput::Variable(FunctionInputVariable {
+            return Ok(FunctionInput::Variable(FunctionInputVariable {
                 identifier: variable.identifier.clone(),
                 const_: variable.const_,
                 mutable: variable.mutable,
                 type_,
                 span: variable.span.clone(),
-            });
+            }));
         }
 
-        input.clone()
+        Ok(input.clone())
     }
 
-    fn canonicalize_circuit_member(&mut self, circuit_member: &CircuitMember) -> CircuitMember {
+    fn canonicalize_circuit_member(&mut self, circuit_member: &CircuitMember) -> Result<CircuitMember> {
         match circuit_member {
             CircuitMember::CircuitVariable(_, _) => {}
+            CircuitMember::CircuitStatic(identifier, type_, value) => {
+                return Ok(CircuitMember::CircuitStatic(
+                    identifier.clone(),
+                    self.canonicalize_type(type_, &identifier.span)?,
+                    self.canonicalize_expression(value)?,
+                ));
+            }
             CircuitMember::CircuitFunction(function) => {
                 let input = function
                     .input
                     .iter()
                     .map(|input| self.canonicalize_function_input(input))
-                    .collect();
-                let output = self.canonicalize_self_type(function.output.as_ref());
-                let block = self.canonicalize_block(&function.block);
+                    .collect::<Result<Vec<_>>>()?;
+                let output = self.canonicalize_self_type(function.output.as_ref(), &function.span)?;
+                let block = self.canonicalize_block(&function.block)?;
 
-                return CircuitMember::CircuitFunction(Function {
+                return Ok(CircuitMember::CircuitFunction(Function {
                     annotations: function.annotations.clone(),
                     identifier: function.identifier.clone(),
                     input,
                     output,
                     block,
                     span: function.span.clone(),
-                });
+                }));
             }
         }
 
-        circuit_member.clone()
+        Ok(circuit_member.clone())
     }
 }
 
@@ -545,51 +743,45 @@ impl ReconstructingReducer for Canonicalizer {
         }
     }
 
+    fn reduce_expression(&mut self, _expression: &Expression, new: Expression) -> Result<Expression> {
+        match new {
+            Expression::ArrayComprehension(comprehension) => self.lower_array_comprehension(comprehension),
+            new => Ok(new),
+        }
+    }
+
     fn reduce_string(&mut self, string: &[Char], span: &Span) -> Result<Expression> {
         if string.is_empty() {
             return Err(AstError::empty_string(span).into());
         }
 
         let mut elements = Vec::new();
-        let mut col_adder = 0;
-        for (index, character) in string.iter().enumerate() {
-            let col_start = span.col_start + index + 1 + col_adder; // account for open quote
-            let bytes = span.content.clone().into_bytes();
-            let col_stop: usize;
-
-            if bytes[col_start - 1] == b'\\' {
-                let mut width = 0;
-
-                match bytes[col_start] {
-                    b'x' => width += 3,
-                    b'u' => {
-                        width += 1;
-                        let mut index = 1;
-                        while bytes[col_start + index] != b'}' {
-                            width += 1;
-                            index += 1;
-                        }
-                        width += 1;
-                    }
-                    _ => width += 1,
-                }
-                col_adder += width;
-                col_stop = col_start + 1 + width;
+        // The byte offset of the next character in the line, starting just past the open quote.
+        let mut offset = span.col_start;
+        for character in string.iter() {
+            let width = if span.line_start == span.line_stop {
+                Self::char_source_width(&span.content, offset)
             } else {
-                col_stop = col_start + 1;
-            }
+                None
+            };
+            let char_span = match width {
+                Some(width) => Span::new(
+                    span.line_start,
+                    span.line_stop,
+                    offset + 1,
+                    offset + 1 + width,
+                    span.path.clone(),
+                    span.content.clone(),
+                ),
+                // Fall back to the whole string when the source text cannot be matched up with the characters.
+                None => span.clone(),
+            };
+            offset += width.unwrap_or(1);
 
             elements.push(SpreadOrExpression::Expression(Expression::Value(
                 ValueExpression::Char(CharValue {
                     character: character.clone(),
-                    span: Span::new(
-                        span.line_start,
-                        span.line_stop,
-                        col_start,
-                        col_stop,
-                        span.path.clone(),
-                        span.content.clone(),
-                    ),
+                    span: char_span,
                 }),
             )));
         }
@@ -656,6 +848,7 @@ impl ReconstructingReducer for Canonicalizer {
     ) -> Result<AssignStatement> {
         match value {
             value if assign.operation != AssignOperation::Assign => {
+                let assignee = self.bind_indices(assignee);
                 let left = self.canonicalize_accesses(
                     Expression::Identifier(assignee.identifier.clone()),
                     &assignee.accesses,
@@ -687,6 +880,20 @@ impl ReconstructingReducer for Canonicalizer {
         }
     }
 
+    fn reduce_statement(&mut self, statement: &Statement, new: Statement) -> Result<Statement> {
+        if self.bindings.is_empty() {
+            return Ok(new);
+        }
+
+        // The indices bound by a compound assignment are defined in a block around it.
+        let mut statements = std::mem::take(&mut self.bindings);
+        statements.push(new);
+        Ok(Statement::Block(Block {
+            statements,
+            span: statement.span().clone(),
+        }))
+    }
+
     fn reduce_function(
         &mut self,
         function: &Function,
@@ -717,16 +924,111 @@ impl ReconstructingReducer for Canonicalizer {
         circuit_name: Identifier,
         members: Vec<CircuitMember>,
     ) -> Result<Circuit> {
-        self.circuit_name = Some(circuit_name.clone());
-        let circ = Circuit {
+        self.circuit_names.push(circuit_name.clone());
+        let members = members
+            .iter()
+            .map(|member| self.canonicalize_circuit_member(member))
+            .collect::<Result<Vec<_>>>();
+        self.circuit_names.pop();
+
+        Ok(Circuit {
             circuit_name,
             core_mapping: circuit.core_mapping.clone(),
-            members: members
-                .iter()
-                .map(|member| self.canonicalize_circuit_member(member))
-                .collect(),
+            members: members?,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_program(
+        &mut self,
+        program: &Program,
+        expected_input: Vec<FunctionInput>,
+        import_statements: Vec<ImportStatement>,
+        imports: IndexMap<Vec<String>, Program>,
+        aliases: IndexMap<Identifier, Alias>,
+        circuits: IndexMap<Identifier, Circuit>,
+        functions: IndexMap<Identifier, Function>,
+        global_consts: IndexMap<Vec<Identifier>, DefinitionStatement>,
+    ) -> Result<Program> {
+        let mut program = Program {
+            name: program.name.clone(),
+            expected_input,
+            import_statements,
+            imports,
+            aliases,
+            circuits,
+            functions,
+            global_consts,
+            recovered: program.recovered.clone(),
+        };
+        program.sync_expected_input();
+        Ok(program)
+    }
+}
+
+/// Replaces every use of an array comprehension variable with one value of its range.
+struct Substitution {
+    variable: String,
+    value: String,
+    type_: Option<IntegerType>,
+    in_circuit: bool,
+}
+
+impl Substitution {
+    fn value(&self, span: &Span) -> Expression {
+        Expression::Value(match &self.type_ {
+            Some(type_) => ValueExpression::Integer(type_.clone(), self.value.as_str().into(), span.clone()),
+            None => ValueExpression::Implicit(self.value.as_str().into(), span.clone()),
+        })
+    }
+}
+
+impl ReconstructingReducer for Substitution {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_expression(&mut self, _expression: &Expression, new: Expression) -> Result<Expression> {
+        match new {
+            Expression::Identifier(identifier) if identifier.name.as_ref() == self.variable => {
+                Ok(self.value(&identifier.span))
+            }
+            new => Ok(new),
+        }
+    }
+
+    fn reduce_circuit_implied_variable_definition(
+        &mut self,
+        _variable: &CircuitImpliedVariableDefinition,
+        identifier: Identifier,
+        expression: Option<Expression>,
+    ) -> Result<CircuitImpliedVariableDefinition> {
+        // `Foo { i }` is shorthand for `Foo { i: i }`, so the member takes the value too.
+        let expression = match expression {
+            None if identifier.name.as_ref() == self.variable => Some(self.value(&identifier.span)),
+            expression => expression,
         };
-        self.circuit_name = None;
-        Ok(circ)
+        Ok(CircuitImpliedVariableDefinition { identifier, expression })
+    }
+}
+
+/// Returns `true` if the expression is a single identifier or made of literals alone, so computing it again costs nothing.
+fn is_trivial(expression: &Expression) -> bool {
+    matches!(expression.without_parentheses(), Expression::Identifier(_)) || is_literal(expression)
+}
+
+/// Returns `true` if the expression is made of literals alone.
+fn is_literal(expression: &Expression) -> bool {
+    match expression {
+        Expression::Value(_) => true,
+        Expression::Unary(unary) => is_literal(&unary.inner),
+        Expression::Binary(binary) => is_literal(&binary.left) && is_literal(&binary.right),
+        Expression::Cast(cast) => is_literal(&cast.inner),
+        Expression::Parenthesized(parenthesized) => is_literal(&parenthesized.inner),
+        _ => false,
     }
 }
@@ -16,3 +16,22 @@
 
 pub mod canonicalizer;
 pub use canonicalizer::*;
+
+use leo_ast::{AstPass, Program};
+use leo_errors::{panic_message, CompilerError, LeoError};
+
+use std::panic::{self, AssertUnwindSafe};
+
+/// Canonicalizes a program of unknown origin, such as the output of [`leo_parser::parse_fallible`], without ever panicking.
+///
+/// Returns the canonicalized program, if canonicalization succeeds, along with its diagnostics.
+pub fn canonicalize_fallible(program: Program) -> (Option<Program>, Vec<LeoError>) {
+    match panic::catch_unwind(AssertUnwindSafe(|| Canonicalizer::do_pass(program))) {
+        Ok(Ok(ast)) => (Some(ast.into_repr()), Vec::new()),
+        Ok(Err(error)) => (None, vec![error]),
+        Err(payload) => (
+            None,
+            vec![CompilerError::compiler_panicked(panic_message(payload.as_ref())).into()],
+        ),
+    }
+}
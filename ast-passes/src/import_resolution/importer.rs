@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::resolver::*;
+use crate::{normalization::*, resolver::*};
 
 use leo_ast::*;
 use leo_errors::{AstError, Result, Span};
@@ -28,21 +28,40 @@ impl Importer {
     where
         T: ImportResolver,
     {
-        let mut ast = program.clone();
+        Ok(Self::do_pass_with_diagnostics(program, importer)?.0)
+    }
+
+    ///
+    /// Resolves the imports of the program like [`Importer::do_pass`], also returning its repeated imports.
+    ///
+    /// The packages are named as the resolver normalizes them, in the import statements of the program and
+    /// in its `imports`, which lists the prelude and then every package in the order it is first imported.
+    ///
+    pub fn do_pass_with_diagnostics<T>(program: Program, importer: &mut T) -> Result<(Ast, Vec<ImportDiagnostic>)>
+    where
+        T: ImportResolver,
+    {
+        let mut ast = program;
         ast.imports.extend(leo_stdlib::resolve_prelude_modules()?);
 
-        let mut imported_symbols: Vec<(Vec<String>, ImportSymbol, Span)> = vec![];
-        for import_statement in program.import_statements.iter() {
-            resolve_import_package(&mut imported_symbols, vec![], &import_statement.package_or_packages);
-        }
+        let mut wrapped_resolver = CoreImportResolver::new(importer);
 
+        ast.import_statements = ast
+            .import_statements
+            .iter()
+            .map(|import_statement| normalize_import_statement(import_statement, &wrapped_resolver))
+            .collect();
+        let imported_symbols = flatten_imports(&ast.import_statements);
+        let diagnostics = find_repeated_imports(&imported_symbols);
+
+        // A package is resolved once, at its first import.
         let mut deduplicated_imports: IndexMap<Vec<String>, Span> = IndexMap::new();
-        for (package, _symbol, span) in imported_symbols.iter() {
-            deduplicated_imports.insert(package.clone(), span.clone());
+        for import in imported_symbols.iter() {
+            deduplicated_imports
+                .entry(import.package.clone())
+                .or_insert_with(|| import.span.clone());
         }
 
-        let mut wrapped_resolver = CoreImportResolver::new(importer);
-
         let mut resolved_packages: IndexMap<Vec<String>, Program> = IndexMap::new();
         for (package, span) in deduplicated_imports {
             let pretty_package = package.join(".");
@@ -58,72 +77,6 @@ impl Importer {
 
         ast.imports.extend(resolved_packages);
 
-        Ok(Ast::new(ast))
-    }
-}
-
-/// Enumerates what names are imported from a package.
-#[derive(Clone)]
-enum ImportSymbol {
-    /// Import the symbol by name.
-    Direct(String),
-
-    /// Import the symbol by name and store it under an alias.
-    Alias(String, String), // from remote -> to local
-
-    /// Import all symbols from the package.
-    All,
-}
-
-fn resolve_import_package(
-    output: &mut Vec<(Vec<String>, ImportSymbol, Span)>,
-    mut package_segments: Vec<String>,
-    package_or_packages: &PackageOrPackages,
-) {
-    match package_or_packages {
-        PackageOrPackages::Package(package) => {
-            package_segments.push(package.name.name.to_string());
-            resolve_import_package_access(output, package_segments, &package.access);
-        }
-        PackageOrPackages::Packages(packages) => {
-            package_segments.push(packages.name.name.to_string());
-            for access in packages.accesses.clone() {
-                resolve_import_package_access(output, package_segments.clone(), &access);
-            }
-        }
-    }
-}
-
-fn resolve_import_package_access(
-    output: &mut Vec<(Vec<String>, ImportSymbol, Span)>,
-    mut package_segments: Vec<String>,
-    package: &PackageAccess,
-) {
-    match package {
-        PackageAccess::Star { span } => {
-            output.push((package_segments, ImportSymbol::All, span.clone()));
-        }
-        PackageAccess::SubPackage(subpackage) => {
-            resolve_import_package(
-                output,
-                package_segments,
-                &PackageOrPackages::Package(*(*subpackage).clone()),
-            );
-        }
-        PackageAccess::Symbol(symbol) => {
-            let span = symbol.symbol.span.clone();
-            let symbol = if let Some(alias) = symbol.alias.as_ref() {
-                ImportSymbol::Alias(symbol.symbol.name.to_string(), alias.name.to_string())
-            } else {
-                ImportSymbol::Direct(symbol.symbol.name.to_string())
-            };
-            output.push((package_segments, symbol, span));
-        }
-        PackageAccess::Multiple(packages) => {
-            package_segments.push(packages.name.name.to_string());
-            for subaccess in packages.accesses.iter() {
-                resolve_import_package_access(output, package_segments.clone(), subaccess);
-            }
-        }
+        Ok((Ast::new(ast), diagnostics))
     }
 }
@@ -17,5 +17,8 @@
 pub mod importer;
 pub use self::importer::*;
 
+pub mod normalization;
+pub use self::normalization::*;
+
 pub mod resolver;
 pub use self::resolver::*;
@@ -0,0 +1,304 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flattens the import statements of a program into the symbols they import, and finds repeated imports.
+
+use crate::resolver::ImportResolver;
+
+use leo_ast::*;
+use leo_errors::Span;
+
+use indexmap::IndexMap;
+use std::fmt;
+
+/// Enumerates what names are imported from a package.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImportedSymbol {
+    /// Import the symbol by name.
+    Direct(String),
+
+    /// Import the symbol by name and store it under an alias.
+    Alias(String, String), // from remote -> to local
+
+    /// Import all symbols from the package, with `.*`.
+    All,
+}
+
+/// One symbol imported by an import statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizedImport {
+    /// The path of the package, as normalized by the resolver.
+    pub package: Vec<String>,
+    pub symbol: ImportedSymbol,
+    /// The span of the symbol, or of the `*` of a wildcard.
+    pub span: Span,
+}
+
+impl fmt::Display for NormalizedImport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.", self.package.join("."))?;
+        match &self.symbol {
+            ImportedSymbol::Direct(name) => write!(f, "{}", name),
+            ImportedSymbol::Alias(name, alias) => write!(f, "{} as {}", name, alias),
+            ImportedSymbol::All => write!(f, "*"),
+        }
+    }
+}
+
+///
+/// Returns the import statement with every package named as the resolver normalizes it.
+///
+/// The spans are kept, so that diagnostics still point at the import as written.
+///
+pub fn normalize_import_statement<R: ImportResolver + ?Sized>(
+    statement: &ImportStatement,
+    resolver: &R,
+) -> ImportStatement {
+    ImportStatement {
+        package_or_packages: normalize_package_or_packages(&statement.package_or_packages, &[], resolver),
+        span: statement.span.clone(),
+    }
+}
+
+fn normalize_package_or_packages<R: ImportResolver + ?Sized>(
+    package_or_packages: &PackageOrPackages,
+    package_segments: &[String],
+    resolver: &R,
+) -> PackageOrPackages {
+    match package_or_packages {
+        PackageOrPackages::Package(package) => {
+            PackageOrPackages::Package(normalize_package(package, package_segments, resolver))
+        }
+        PackageOrPackages::Packages(packages) => {
+            let (name, package_segments) = normalize_segment(&packages.name, package_segments, resolver);
+            PackageOrPackages::Packages(Packages {
+                name,
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| normalize_package_access(access, &package_segments, resolver))
+                    .collect(),
+                span: packages.span.clone(),
+            })
+        }
+    }
+}
+
+fn normalize_package<R: ImportResolver + ?Sized>(
+    package: &Package,
+    package_segments: &[String],
+    resolver: &R,
+) -> Package {
+    let (name, package_segments) = normalize_segment(&package.name, package_segments, resolver);
+    Package {
+        name,
+        access: normalize_package_access(&package.access, &package_segments, resolver),
+        span: package.span.clone(),
+    }
+}
+
+fn normalize_package_access<R: ImportResolver + ?Sized>(
+    access: &PackageAccess,
+    package_segments: &[String],
+    resolver: &R,
+) -> PackageAccess {
+    match access {
+        PackageAccess::Star { .. } | PackageAccess::Symbol(_) => access.clone(),
+        PackageAccess::SubPackage(package) => {
+            PackageAccess::SubPackage(Box::new(normalize_package(package, package_segments, resolver)))
+        }
+        PackageAccess::Multiple(packages) => {
+            let (name, package_segments) = normalize_segment(&packages.name, package_segments, resolver);
+            PackageAccess::Multiple(Packages {
+                name,
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| normalize_package_access(access, &package_segments, resolver))
+                    .collect(),
+                span: packages.span.clone(),
+            })
+        }
+    }
+}
+
+/// Normalizes the last segment of the package path ending with `name`, returning it and the path as written.
+fn normalize_segment<R: ImportResolver + ?Sized>(
+    name: &Identifier,
+    package_segments: &[String],
+    resolver: &R,
+) -> (Identifier, Vec<String>) {
+    let mut package_segments = package_segments.to_vec();
+    package_segments.push(name.name.to_string());
+    let segments = package_segments.iter().map(|segment| &**segment).collect::<Vec<_>>();
+    let normalized = resolver
+        .normalize_package(&segments)
+        .pop()
+        .unwrap_or_else(|| name.name.to_string());
+    (
+        Identifier::new_with_span(&normalized, name.span.clone()),
+        package_segments,
+    )
+}
+
+///
+/// Returns every symbol imported by the import statements, in the order they are written.
+///
+pub fn flatten_imports(import_statements: &[ImportStatement]) -> Vec<NormalizedImport> {
+    let mut output = vec![];
+    for import_statement in import_statements.iter() {
+        resolve_import_package(&mut output, vec![], &import_statement.package_or_packages);
+    }
+    output
+}
+
+fn resolve_import_package(
+    output: &mut Vec<NormalizedImport>,
+    mut package_segments: Vec<String>,
+    package_or_packages: &PackageOrPackages,
+) {
+    match package_or_packages {
+        PackageOrPackages::Package(package) => {
+            package_segments.push(package.name.name.to_string());
+            resolve_import_package_access(output, package_segments, &package.access);
+        }
+        PackageOrPackages::Packages(packages) => {
+            package_segments.push(packages.name.name.to_string());
+            for access in packages.accesses.clone() {
+                resolve_import_package_access(output, package_segments.clone(), &access);
+            }
+        }
+    }
+}
+
+fn resolve_import_package_access(
+    output: &mut Vec<NormalizedImport>,
+    mut package_segments: Vec<String>,
+    package: &PackageAccess,
+) {
+    match package {
+        PackageAccess::Star { span } => {
+            output.push(NormalizedImport {
+                package: package_segments,
+                symbol: ImportedSymbol::All,
+                span: span.clone(),
+            });
+        }
+        PackageAccess::SubPackage(subpackage) => {
+            resolve_import_package(
+                output,
+                package_segments,
+                &PackageOrPackages::Package(*(*subpackage).clone()),
+            );
+        }
+        PackageAccess::Symbol(symbol) => {
+            let span = symbol.symbol.span.clone();
+            let symbol = if let Some(alias) = symbol.alias.as_ref() {
+                ImportedSymbol::Alias(symbol.symbol.name.to_string(), alias.name.to_string())
+            } else {
+                ImportedSymbol::Direct(symbol.symbol.name.to_string())
+            };
+            output.push(NormalizedImport {
+                package: package_segments,
+                symbol,
+                span,
+            });
+        }
+        PackageAccess::Multiple(packages) => {
+            package_segments.push(packages.name.name.to_string());
+            for subaccess in packages.accesses.iter() {
+                resolve_import_package_access(output, package_segments.clone(), subaccess);
+            }
+        }
+    }
+}
+
+/// How much an [`ImportDiagnostic`] matters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportSeverity {
+    Warning,
+    Note,
+}
+
+/// A repeated import, which does not stop the program from compiling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDiagnostic {
+    pub severity: ImportSeverity,
+    /// The message, which says where the other import is.
+    pub message: String,
+    /// The span of the repeated import.
+    pub span: Span,
+    /// The span of the import it repeats: the earlier import, or the wildcard that covers it.
+    pub other: Span,
+}
+
+impl fmt::Display for ImportDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.severity {
+            ImportSeverity::Warning => write!(f, "{}", self.message),
+            ImportSeverity::Note => write!(f, "note: {}", self.message),
+        }
+    }
+}
+
+///
+/// Returns the imports that repeat another import of the program.
+///
+/// Importing exactly the same symbol, alias or wildcard again is a warning, in the order the repeats are written.
+/// They are followed by a note for each symbol that is imported by name and by a wildcard of its package.
+///
+pub fn find_repeated_imports(imports: &[NormalizedImport]) -> Vec<ImportDiagnostic> {
+    let mut diagnostics = vec![];
+    let mut seen: IndexMap<(&[String], &ImportedSymbol), &NormalizedImport> = IndexMap::new();
+    for import in imports.iter() {
+        let key = (&import.package[..], &import.symbol);
+        match seen.get(&key) {
+            Some(first) => diagnostics.push(ImportDiagnostic {
+                severity: ImportSeverity::Warning,
+                message: format!(
+                    "`{}` is imported twice, first at {}:{}",
+                    import, first.span.path, first.span
+                ),
+                span: import.span.clone(),
+                other: first.span.clone(),
+            }),
+            None => {
+                seen.insert(key, import);
+            }
+        }
+    }
+
+    // The name binds the same definition either way, so the import by name only repeats the wildcard.
+    for import in seen.values() {
+        let name = match &import.symbol {
+            ImportedSymbol::Direct(name) => name,
+            ImportedSymbol::Alias(name, alias) if name == alias => name,
+            _ => continue,
+        };
+        if let Some(wildcard) = seen.get(&(&import.package[..], &ImportedSymbol::All)) {
+            diagnostics.push(ImportDiagnostic {
+                severity: ImportSeverity::Note,
+                message: format!(
+                    "`{}` is also imported by `{}` at {}:{}, which binds `{}` to the same definition",
+                    import, wildcard, wildcard.span.path, wildcard.span, name
+                ),
+                span: import.span.clone(),
+                other: wildcard.span.clone(),
+            });
+        }
+    }
+    diagnostics
+}
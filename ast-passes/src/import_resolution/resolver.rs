@@ -22,6 +22,16 @@ use indexmap::IndexMap;
 
 pub trait ImportResolver {
     fn resolve_package(&mut self, package_segments: &[&str], span: &Span) -> Result<Option<Program>>;
+
+    ///
+    /// Returns the path the resolver looks the package up by, with one segment for each given segment.
+    ///
+    /// Imports of packages with the same normalized path import the same package. Package names are
+    /// case sensitive unless the resolver says otherwise.
+    ///
+    fn normalize_package(&self, package_segments: &[&str]) -> Vec<String> {
+        package_segments.iter().map(|segment| segment.to_string()).collect()
+    }
 }
 
 pub struct NullImportResolver;
@@ -50,6 +60,14 @@ impl<'a, T: ImportResolver> ImportResolver for CoreImportResolver<'a, T> {
             self.inner.resolve_package(package_segments, span)
         }
     }
+
+    fn normalize_package(&self, package_segments: &[&str]) -> Vec<String> {
+        if !package_segments.is_empty() && package_segments[0] == "std" {
+            package_segments.iter().map(|segment| segment.to_string()).collect()
+        } else {
+            self.inner.normalize_package(package_segments)
+        }
+    }
 }
 
 pub struct MockedImportResolver {
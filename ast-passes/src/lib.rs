@@ -21,3 +21,6 @@ pub use canonicalization::*;
 
 pub mod import_resolution;
 pub use import_resolution::*;
+
+pub mod renaming;
+pub use renaming::*;
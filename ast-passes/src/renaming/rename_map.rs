@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{ArtifactFormat, STAMP_KEY};
+use leo_errors::{AstError, Result};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The format of rename maps, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const RENAME_MAP_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "rename map",
+    version: 1,
+    json: true,
+};
+
+/// The mapping from original identifier names to the short names produced by the [`Renamer`](crate::Renamer).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RenameMap {
+    /// Short names keyed by the original name.
+    names: IndexMap<String, String>,
+}
+
+impl RenameMap {
+    /// Records that `original` is renamed to `renamed`.
+    pub fn insert(&mut self, original: String, renamed: String) {
+        self.names.insert(original, renamed);
+    }
+
+    /// Returns the short name for the given original name, if it was renamed.
+    pub fn get(&self, original: &str) -> Option<&str> {
+        self.names.get(original).map(|renamed| renamed.as_str())
+    }
+
+    /// Returns the original name for the given short name, if it is one.
+    pub fn original(&self, renamed: &str) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|(_, name)| name.as_str() == renamed)
+            .map(|(original, _)| original.as_str())
+    }
+
+    /// Returns the original names with their short names, in the order they were recorded.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.names
+            .iter()
+            .map(|(original, renamed)| (original.as_str(), renamed.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Replaces every short name appearing as a whole word in `text` with its original name.
+    pub fn demangle(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        for c in text.chars().chain(std::iter::once('\0')) {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                output.push_str(self.original(&word).unwrap_or(&word));
+                word.clear();
+            }
+            if c != '\0' {
+                output.push(c);
+            }
+        }
+
+        output
+    }
+
+    /// Serializes the map into a JSON string keyed by the short names.
+    pub fn to_json_string(&self) -> Result<String> {
+        let reversed: IndexMap<&str, &str> = self
+            .names
+            .iter()
+            .map(|(original, renamed)| (renamed.as_str(), original.as_str()))
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&reversed)
+            .map_err(|e| AstError::failed_to_convert_rename_map_to_json_string(&e))?)
+    }
+
+    /// Deserializes a JSON string produced by [`RenameMap::to_json_string`], checking its version stamp if it has one.
+    pub fn from_json_string(json: &str) -> Result<Self> {
+        let mut reversed: IndexMap<String, serde_json::Value> =
+            serde_json::from_str(json).map_err(|e| AstError::failed_to_read_json_string_to_rename_map(&e))?;
+        RENAME_MAP_FORMAT.check(reversed.shift_remove(STAMP_KEY))?;
+
+        let names = reversed
+            .into_iter()
+            .map(|(renamed, original)| {
+                let original = serde_json::from_value(original)
+                    .map_err(|e| AstError::failed_to_read_json_string_to_rename_map(&e))?;
+                Ok((original, renamed))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { names })
+    }
+
+    /// Writes the map as JSON to the given file in the given directory.
+    pub fn to_json_file(&self, mut path: PathBuf, file_name: &str) -> Result<()> {
+        path.push(file_name);
+        let json = self.to_json_string()?;
+        std::fs::write(&path, json).map_err(|e| AstError::failed_to_write_rename_map_file(&path, &e))?;
+        Ok(())
+    }
+}
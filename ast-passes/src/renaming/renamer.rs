@@ -0,0 +1,449 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::RenameMap;
+
+use leo_ast::*;
+use leo_errors::Result;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// The kind of declaration a name was first seen in, which picks the prefix of its short name.
+#[derive(Clone, Copy)]
+enum NameKind {
+    Function,
+    Circuit,
+    Member,
+    Variable,
+}
+
+impl NameKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            NameKind::Function => "f",
+            NameKind::Circuit => "c",
+            NameKind::Member => "m",
+            NameKind::Variable => "v",
+        }
+    }
+}
+
+/// Collects every name declared by the program and every name that must keep its spelling.
+#[derive(Default)]
+struct NameCollector {
+    declared: IndexMap<String, NameKind>,
+    reserved: IndexSet<String>,
+    seen: IndexSet<String>,
+    /// The functions whose names and parameters are bound by the input file.
+    entrypoints: IndexSet<String>,
+    in_circuit: bool,
+}
+
+impl NameCollector {
+    fn declare(&mut self, identifier: &Identifier, kind: NameKind) {
+        self.declared.entry(identifier.name.to_string()).or_insert(kind);
+    }
+
+    fn reserve(&mut self, identifier: &Identifier) {
+        self.reserved.insert(identifier.name.to_string());
+    }
+
+    fn reserve_access(&mut self, access: &PackageAccess) {
+        match access {
+            PackageAccess::Star { .. } | PackageAccess::Symbol(_) => (),
+            PackageAccess::SubPackage(package) => {
+                self.reserve(&package.name);
+                self.reserve_access(&package.access);
+            }
+            PackageAccess::Multiple(packages) => {
+                self.reserve(&packages.name);
+                for access in packages.accesses.iter() {
+                    self.reserve_access(access);
+                }
+            }
+        }
+    }
+
+    /// Reserves `input` and the members of it accessed by the program and its imports,
+    /// since they are bound by the input file.
+    ///
+    /// A function's own variable named `input` keeps its name too, since renaming is global
+    /// and the other functions may still use the program input.
+    fn reserve_input_members(&mut self, program: &Program) {
+        self.reserved.insert("input".to_string());
+        for expression in program.iter_expressions(Traversal::PreOrder) {
+            if let Expression::CircuitMemberAccess(access) = expression {
+                if is_input_access(&access.circuit) {
+                    self.reserve(&access.name);
+                }
+            }
+        }
+        for import in program.imports.values() {
+            self.reserve_input_members(import);
+        }
+    }
+
+    /// Builds the rename map, skipping reserved names and never producing a name that is already in use.
+    fn into_map(self) -> RenameMap {
+        let mut map = RenameMap::default();
+        let mut counters: IndexMap<&'static str, usize> = IndexMap::new();
+
+        for (name, kind) in self.declared.iter() {
+            if self.reserved.contains(name) {
+                continue;
+            }
+
+            let counter = counters.entry(kind.prefix()).or_insert(0);
+            let renamed = loop {
+                let candidate = format!("{}{}", kind.prefix(), counter);
+                *counter += 1;
+                let in_use = self.reserved.contains(&candidate)
+                    || (self.seen.contains(&candidate) && !self.declared.contains_key(&candidate));
+                if !in_use {
+                    break candidate;
+                }
+            };
+
+            map.insert(name.clone(), renamed);
+        }
+
+        map
+    }
+}
+
+/// Returns `true` if the expression is `input` or a member access rooted at `input`.
+fn is_input_access(expression: &Expression) -> bool {
+    match expression.without_parentheses() {
+        Expression::Identifier(identifier) => identifier.name.as_ref() == "input",
+        Expression::CircuitMemberAccess(access) => is_input_access(&access.circuit),
+        _ => false,
+    }
+}
+
+impl ReconstructingReducer for NameCollector {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        self.seen.insert(identifier.name.to_string());
+        Ok(identifier.clone())
+    }
+
+    fn reduce_variable_name(&mut self, variable_name: &VariableName, identifier: Identifier) -> Result<VariableName> {
+        self.declare(&identifier, NameKind::Variable);
+
+        Ok(VariableName {
+            mutable: variable_name.mutable,
+            identifier,
+            span: variable_name.span.clone(),
+        })
+    }
+
+    fn reduce_iteration(
+        &mut self,
+        iteration: &IterationStatement,
+        variable: Identifier,
+        start: Expression,
+        stop: Expression,
+        step: Option<Expression>,
+        block: Block,
+    ) -> Result<IterationStatement> {
+        self.declare(&variable, NameKind::Variable);
+
+        Ok(IterationStatement {
+            variable,
+            start,
+            stop,
+            inclusive: iteration.inclusive,
+            step,
+            block,
+            span: iteration.span.clone(),
+        })
+    }
+
+    fn reduce_package_or_packages(
+        &mut self,
+        package_or_packages: &PackageOrPackages,
+        new: PackageOrPackages,
+    ) -> Result<PackageOrPackages> {
+        match package_or_packages {
+            PackageOrPackages::Package(package) => {
+                self.reserve(&package.name);
+                self.reserve_access(&package.access);
+            }
+            PackageOrPackages::Packages(packages) => {
+                self.reserve(&packages.name);
+                for access in packages.accesses.iter() {
+                    self.reserve_access(access);
+                }
+            }
+        }
+
+        Ok(new)
+    }
+
+    fn reduce_function_input_variable(
+        &mut self,
+        variable: &FunctionInputVariable,
+        identifier: Identifier,
+        type_: Type,
+    ) -> Result<FunctionInputVariable> {
+        self.declare(&identifier, NameKind::Variable);
+
+        Ok(FunctionInputVariable {
+            identifier,
+            const_: variable.const_,
+            mutable: variable.mutable,
+            type_,
+            span: variable.span.clone(),
+        })
+    }
+
+    fn reduce_circuit_member(&mut self, _circuit_member: &CircuitMember, new: CircuitMember) -> Result<CircuitMember> {
+        match &new {
+            CircuitMember::CircuitVariable(identifier, _) | CircuitMember::CircuitStatic(identifier, ..) => {
+                self.declare(identifier, NameKind::Member)
+            }
+            CircuitMember::CircuitFunction(function) => self.declare(&function.identifier, NameKind::Member),
+        }
+
+        Ok(new)
+    }
+
+    fn reduce_circuit(
+        &mut self,
+        circuit: &Circuit,
+        circuit_name: Identifier,
+        members: Vec<CircuitMember>,
+    ) -> Result<Circuit> {
+        // Core circuits are resolved by name.
+        if circuit.core_mapping.borrow().is_some() {
+            self.reserve(&circuit_name);
+            for member in members.iter() {
+                match member {
+                    CircuitMember::CircuitVariable(identifier, _) | CircuitMember::CircuitStatic(identifier, ..) => {
+                        self.reserve(identifier)
+                    }
+                    CircuitMember::CircuitFunction(function) => self.reserve(&function.identifier),
+                }
+            }
+        } else {
+            self.declare(&circuit_name, NameKind::Circuit);
+        }
+
+        Ok(Circuit {
+            circuit_name,
+            core_mapping: circuit.core_mapping.clone(),
+            members,
+        })
+    }
+
+    fn reduce_annotation(&mut self, annotation: &Annotation, name: Identifier) -> Result<Annotation> {
+        self.reserve(&name);
+
+        Ok(Annotation {
+            span: annotation.span.clone(),
+            name,
+            arguments: annotation.arguments.clone(),
+        })
+    }
+
+    fn reduce_function(
+        &mut self,
+        function: &Function,
+        identifier: Identifier,
+        annotations: Vec<Annotation>,
+        input: Vec<FunctionInput>,
+        output: Option<Type>,
+        block: Block,
+    ) -> Result<Function> {
+        if self.entrypoints.contains(identifier.name.as_ref()) {
+            // The entrypoints and their parameters are bound by the input file.
+            self.reserve(&identifier);
+            for input in input.iter() {
+                if let FunctionInput::Variable(variable) = input {
+                    self.reserve(&variable.identifier);
+                }
+            }
+        } else if !self.in_circuit {
+            self.declare(&identifier, NameKind::Function);
+        }
+
+        Ok(Function {
+            identifier,
+            annotations,
+            input,
+            output,
+            block,
+            span: function.span.clone(),
+        })
+    }
+}
+
+/// Rewrites every name declared by the program to a short deterministic name.
+///
+/// The entrypoints, their parameters, `input` and its members, import paths, and core circuits keep their names.
+/// Names are mapped globally rather than per scope, so every reference to a name is rewritten the same way
+/// as its declaration and name resolution in the ASG is unaffected.
+pub struct Renamer {
+    map: RenameMap,
+    in_circuit: bool,
+}
+
+impl AstPass for Renamer {
+    fn do_pass(ast: Program) -> Result<Ast> {
+        Ok(Self::do_pass_with_map(ast)?.0)
+    }
+}
+
+impl Renamer {
+    /// Renames the program, returning it along with the map from original to short names.
+    pub fn do_pass_with_map(ast: Program) -> Result<(Ast, RenameMap)> {
+        Self::do_pass_with_entrypoints(ast, &["main".to_string()])
+    }
+
+    /// Renames the program like `do_pass_with_map()`, keeping the names and parameters of the given entrypoints.
+    pub fn do_pass_with_entrypoints(ast: Program, entrypoints: &[String]) -> Result<(Ast, RenameMap)> {
+        let mut collector = NameCollector {
+            entrypoints: entrypoints.iter().cloned().collect(),
+            ..Default::default()
+        };
+        collector.reserve_input_members(&ast);
+        let (_, collector) = ReducerPass::new(collector).run(&ast)?;
+        let map = collector.into_map();
+
+        let (ast, renamer) = ReducerPass::new(Renamer { map, in_circuit: false }).run(&ast)?;
+
+        Ok((ast, renamer.map))
+    }
+
+    fn rename(&self, identifier: &Identifier) -> Identifier {
+        match self.map.get(&identifier.name) {
+            Some(renamed) => Identifier::new_with_span(renamed, identifier.span.clone()),
+            None => identifier.clone(),
+        }
+    }
+
+    fn rename_access(&self, access: &PackageAccess) -> PackageAccess {
+        match access {
+            PackageAccess::Star { .. } => access.clone(),
+            PackageAccess::SubPackage(package) => PackageAccess::SubPackage(Box::new(Package {
+                name: package.name.clone(),
+                access: self.rename_access(&package.access),
+                span: package.span.clone(),
+            })),
+            PackageAccess::Symbol(symbol) => PackageAccess::Symbol(ImportSymbol {
+                symbol: self.rename(&symbol.symbol),
+                alias: symbol.alias.as_ref().map(|alias| self.rename(alias)),
+                span: symbol.span.clone(),
+            }),
+            PackageAccess::Multiple(packages) => PackageAccess::Multiple(Packages {
+                name: packages.name.clone(),
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| self.rename_access(access))
+                    .collect(),
+                span: packages.span.clone(),
+            }),
+        }
+    }
+}
+
+impl ReconstructingReducer for Renamer {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        Ok(self.rename(identifier))
+    }
+
+    fn reduce_package_or_packages(
+        &mut self,
+        _package_or_packages: &PackageOrPackages,
+        new: PackageOrPackages,
+    ) -> Result<PackageOrPackages> {
+        Ok(match new {
+            PackageOrPackages::Package(package) => PackageOrPackages::Package(Package {
+                access: self.rename_access(&package.access),
+                ..package
+            }),
+            PackageOrPackages::Packages(packages) => PackageOrPackages::Packages(Packages {
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| self.rename_access(access))
+                    .collect(),
+                ..packages
+            }),
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_program(
+        &mut self,
+        program: &Program,
+        expected_input: Vec<FunctionInput>,
+        import_statements: Vec<ImportStatement>,
+        imports: IndexMap<Vec<String>, Program>,
+        aliases: IndexMap<Identifier, Alias>,
+        circuits: IndexMap<Identifier, Circuit>,
+        functions: IndexMap<Identifier, Function>,
+        global_consts: IndexMap<Vec<Identifier>, DefinitionStatement>,
+    ) -> Result<Program> {
+        // The director keeps the original keys, so rebuild them from the renamed declarations.
+        let mut program = Program {
+            name: program.name.clone(),
+            expected_input,
+            import_statements,
+            imports,
+            aliases,
+            circuits: circuits
+                .into_iter()
+                .map(|(_, circuit)| (circuit.circuit_name.clone(), circuit))
+                .collect(),
+            functions: functions
+                .into_iter()
+                .map(|(_, function)| (function.identifier.clone(), function))
+                .collect(),
+            global_consts: global_consts
+                .into_iter()
+                .map(|(_, definition)| {
+                    let names = definition
+                        .variable_names
+                        .iter()
+                        .map(|name| name.identifier.clone())
+                        .collect();
+                    (names, definition)
+                })
+                .collect(),
+            recovered: program.recovered.clone(),
+        };
+        // Types of the parameters of `main` may name renamed circuits.
+        program.sync_expected_input();
+        Ok(program)
+    }
+}
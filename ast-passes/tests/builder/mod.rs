@@ -0,0 +1,181 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    builder::*, validate, AssignOperation, AstPass, BinaryOperation, IntegerType, Node, Program, Stage, Traversal, Type,
+};
+use leo_ast_passes::Canonicalizer;
+
+use std::collections::HashSet;
+
+fn u32_type() -> Type {
+    Type::IntegerType(IntegerType::U32)
+}
+
+/// Builds the equivalent of:
+///
+/// ```leo
+/// circuit Point {
+///     x: u32,
+///
+///     function get(self) -> u32 {
+///         return self.x;
+///     }
+/// }
+///
+/// function main(a: u32) -> u32 {
+///     let p = Point { x: a };
+///     let total: u32 = p.get();
+///     total += 1u32;
+///     return total;
+/// }
+/// ```
+fn point_program() -> Program {
+    program()
+        .circuit(
+            circuit::new("Point").variable("x", u32_type()).function(
+                func::new("get")
+                    .self_input()
+                    .returns(u32_type())
+                    .body(vec![stmt::return_(expr::member(expr::ident("self"), "x"))]),
+            ),
+        )
+        .function(func::new("main").input("a", u32_type()).returns(u32_type()).body(vec![
+            stmt::let_("p", None, expr::circuit("Point", vec![("x", expr::ident("a"))])),
+            stmt::let_(
+                "total",
+                Some(u32_type()),
+                expr::call(expr::member(expr::ident("p"), "get"), vec![]),
+            ),
+            stmt::assign("total", AssignOperation::Add, expr::int(1, IntegerType::U32)),
+            stmt::return_(expr::ident("total")),
+        ]))
+        .build()
+}
+
+#[test]
+fn test_built_program_validates() {
+    let program = point_program();
+    assert!(validate(&program, Stage::Parsed).is_empty());
+
+    let canonicalized = Canonicalizer::do_pass(program).unwrap().into_repr();
+    assert!(validate(&canonicalized, Stage::Canonicalized).is_empty());
+}
+
+#[test]
+fn test_built_program_prints_as_source() {
+    let main = program()
+        .function(
+            func::new("main")
+                .input("a", u32_type())
+                .returns(u32_type())
+                .body(vec![stmt::return_(expr::binary(
+                    expr::ident("a"),
+                    BinaryOperation::Add,
+                    expr::int(5, IntegerType::U32),
+                ))]),
+        )
+        .build();
+
+    assert!(main.to_string().contains("return a + 5u32"));
+}
+
+#[test]
+fn test_built_nodes_have_distinct_spans() {
+    let program = point_program();
+
+    let lines = program
+        .iter_expressions(Traversal::PreOrder)
+        .map(|expression| expression.span().clone())
+        .chain(
+            program
+                .iter_statements(Traversal::PreOrder)
+                .map(|statement| statement.span().clone()),
+        )
+        .inspect(|span| assert_eq!(span.path.as_str(), BUILDER_PATH))
+        .map(|span| span.line_start)
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), lines.iter().collect::<HashSet<_>>().len());
+}
+
+#[test]
+fn test_built_expressions_print_the_parentheses_they_need() {
+    let (a, b, c) = (|| expr::ident("a"), || expr::ident("b"), || expr::ident("c"));
+    let printed = |expression: leo_ast::Expression| expression.to_string();
+
+    assert_eq!(
+        printed(expr::binary(
+            expr::binary(a(), BinaryOperation::Add, b()),
+            BinaryOperation::Mul,
+            c()
+        )),
+        "(a + b) * c"
+    );
+    assert_eq!(
+        printed(expr::binary(
+            a(),
+            BinaryOperation::Sub,
+            expr::binary(b(), BinaryOperation::Sub, c())
+        )),
+        "a - (b - c)"
+    );
+    assert_eq!(
+        printed(expr::binary(
+            expr::binary(a(), BinaryOperation::Sub, b()),
+            BinaryOperation::Sub,
+            c()
+        )),
+        "a - b - c"
+    );
+    assert_eq!(
+        printed(expr::binary(
+            expr::binary(a(), BinaryOperation::Pow, b()),
+            BinaryOperation::Pow,
+            c()
+        )),
+        "(a ** b) ** c"
+    );
+    assert_eq!(
+        printed(expr::binary(
+            a(),
+            BinaryOperation::Pow,
+            expr::binary(b(), BinaryOperation::Pow, c())
+        )),
+        "a ** b ** c"
+    );
+    assert_eq!(
+        printed(expr::cast(expr::binary(a(), BinaryOperation::Add, b()), u32_type())),
+        "(a + b) as u32"
+    );
+    assert_eq!(
+        printed(expr::member(expr::ternary(a(), b(), c()), "x")),
+        "(if a ? b : c).x"
+    );
+    // Parentheses the builder asks for are printed as well, without doubling the ones that are needed.
+    assert_eq!(
+        printed(expr::binary(
+            expr::parenthesized(expr::binary(a(), BinaryOperation::Add, b())),
+            BinaryOperation::Mul,
+            c()
+        )),
+        "(a + b) * c"
+    );
+    assert_eq!(
+        printed(expr::binary(expr::parenthesized(a()), BinaryOperation::Add, b())),
+        "(a) + b"
+    );
+}
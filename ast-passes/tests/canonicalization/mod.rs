@@ -0,0 +1,313 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    is_generated_name, AssignOperation, Ast, AstPass, FunctionInput, IntegerType, Program, ReconstructingDirector,
+    ReducerPass, Statement, Type,
+};
+use leo_ast_passes::Canonicalizer;
+use leo_errors::Result;
+
+/// Drives the canonicalizer directly, without going through the pass adapter.
+fn canonicalize_with_director(program: &Program) -> Result<Ast> {
+    Ok(Ast::new(
+        ReconstructingDirector::new(Canonicalizer::default()).reduce_program(program)?,
+    ))
+}
+
+fn canonicalize(program_string: &str) -> Result<Program> {
+    let ast = leo_parser::parse_ast("input.leo", program_string)?;
+    Ok(Canonicalizer::do_pass(ast.into_repr())?.into_repr())
+}
+
+#[test]
+fn test_self_in_const_initializer() {
+    let program_string = r#"
+    circuit Foo {
+        x: u32,
+
+        function new() -> Self {
+            const foo = Self { x: 1u32 };
+            return foo;
+        }
+    }
+    "#;
+    let program = canonicalize(program_string).unwrap();
+    let json = serde_json::to_string(&program).unwrap();
+    assert!(!json.contains("SelfType"));
+}
+
+#[test]
+fn test_self_in_static_access() {
+    let program_string = r#"
+    circuit Foo {
+        x: u32,
+
+        function new() -> Self {
+            return Self { x: 1u32 };
+        }
+
+        function double() -> [Self; 2] {
+            return [Self::new(), Self::new()];
+        }
+    }
+    "#;
+    let program = canonicalize(program_string).unwrap();
+    let json = serde_json::to_string(&program).unwrap();
+    assert!(!json.contains("SelfType"));
+}
+
+#[test]
+fn test_self_at_file_scope() {
+    let program_string = r#"
+    function main() -> Self {
+        return Self { x: 1u32 };
+    }
+    "#;
+    canonicalize(program_string).err().unwrap();
+}
+
+#[test]
+fn test_reducer_pass_matches_canonicalizer() {
+    let program_string = include_str!("../renaming/program.leo");
+    let ast = leo_parser::parse_ast("input.leo", program_string).unwrap();
+
+    let direct = canonicalize_with_director(ast.as_repr()).unwrap();
+    let adapted = ReducerPass::<Canonicalizer>::do_pass(ast.into_repr()).unwrap();
+
+    assert_eq!(direct.to_json_string().unwrap(), adapted.to_json_string().unwrap());
+}
+
+#[test]
+fn test_reducer_pass_preserves_error_spans() {
+    let program_string = r#"
+    function main() -> Self {
+        return Self { x: 1u32 };
+    }
+    "#;
+    let ast = leo_parser::parse_ast("input.leo", program_string).unwrap();
+
+    let direct = canonicalize_with_director(ast.as_repr()).unwrap_err();
+    let adapted = ReducerPass::<Canonicalizer>::do_pass(ast.into_repr()).unwrap_err();
+
+    assert_eq!(direct.error_code(), adapted.error_code());
+    assert_eq!(direct.span(), adapted.span());
+}
+
+#[test]
+fn test_array_comprehension_round_trips() {
+    let program_string = r#"
+    function main() {
+        const squares = [i * i for i in 0u8..=3];
+    }
+    "#;
+    let program = leo_parser::parse_ast("input.leo", program_string).unwrap().into_repr();
+    assert!(program.to_string().contains("[i * i for i in 0u8..=3]"));
+}
+
+#[test]
+fn test_array_comprehension_lowers_to_inline_array() {
+    let program_string = r#"
+    function main() {
+        const squares = [i * i for i in 0u8..4];
+    }
+    "#;
+    let program = canonicalize(program_string).unwrap();
+    let json = serde_json::to_string(&program).unwrap();
+    assert!(!json.contains("ArrayComprehension"));
+    assert!(program
+        .to_string()
+        .contains("[0u8 * 0u8, 1u8 * 1u8, 2u8 * 2u8, 3u8 * 3u8]"));
+}
+
+#[test]
+fn test_nested_array_comprehension() {
+    let program_string = r#"
+    function main() {
+        const grid = [[i + j for j in 0..2] for i in 0..2];
+    }
+    "#;
+    let program = canonicalize(program_string).unwrap();
+    assert!(program.to_string().contains("[[0 + 0, 0 + 1], [1 + 0, 1 + 1]]"));
+}
+
+#[test]
+fn test_array_comprehension_non_const_range() {
+    let program_string = r#"
+    function main(n: u8) {
+        const values = [i for i in 0u8..n];
+    }
+    "#;
+    let error = canonicalize(program_string).unwrap_err();
+    assert!(error.to_string().contains("must be an integer constant"));
+}
+
+#[test]
+fn test_array_comprehension_empty_range() {
+    let program_string = r#"
+    function main() {
+        const values = [i for i in 4u8..4];
+    }
+    "#;
+    let error = canonicalize(program_string).unwrap_err();
+    assert!(error.to_string().contains("array comprehension range 4u8..4 is empty"));
+}
+
+const ENTRYPOINT_PROGRAM: &str = r#"
+    type Amount = u64;
+
+    function main(a: Amount, const b: [Amount; 2], c: bool) {}
+    "#;
+
+#[test]
+fn test_expected_input_from_main() {
+    let program = canonicalize(ENTRYPOINT_PROGRAM).unwrap();
+    let parameters = program
+        .expected_input
+        .iter()
+        .map(|input| match input {
+            FunctionInput::Variable(variable) => (variable.identifier.name.to_string(), variable.type_.clone()),
+            input => panic!("unexpected input {}", input),
+        })
+        .collect::<Vec<_>>();
+
+    // In declaration order, with the alias resolved.
+    let amount = Type::IntegerType(IntegerType::U64);
+    assert_eq!(parameters.len(), 3);
+    assert_eq!(parameters[0], ("a".to_string(), amount.clone()));
+    assert_eq!(parameters[1].0, "b");
+    assert!(matches!(&parameters[1].1, Type::Array(element, _) if **element == amount));
+    assert_eq!(parameters[2], ("c".to_string(), Type::Boolean));
+}
+
+#[test]
+fn test_expected_input_round_trips() {
+    let program = canonicalize(ENTRYPOINT_PROGRAM).unwrap();
+    let json = Ast::new(program.clone()).to_json_string().unwrap();
+    let loaded = Ast::from_json_string(&json).unwrap();
+    assert_eq!(loaded.as_repr().expected_input, program.expected_input);
+}
+
+#[test]
+fn test_stale_expected_input() {
+    let mut program = canonicalize(ENTRYPOINT_PROGRAM).unwrap();
+    program.expected_input.swap(0, 2);
+    let json = Ast::new(program).to_json_string().unwrap();
+
+    let error = Ast::from_json_string(&json).unwrap_err();
+    assert_eq!(error.error_code(), "EAST0372022");
+}
+
+fn main_statements(program_string: &str) -> Vec<Statement> {
+    let program = canonicalize(program_string).unwrap();
+    program.functions.values().next().unwrap().block.statements.clone()
+}
+
+#[test]
+fn test_compound_assignment_binds_computed_index() {
+    let statements = main_statements(
+        r#"
+    function main(a: [u32; 4], i: u32) {
+        let b = a;
+        b[i + 1] += 5;
+    }
+    "#,
+    );
+
+    let block = match &statements[1] {
+        Statement::Block(block) => block,
+        statement => panic!("unexpected statement {}", statement),
+    };
+    assert_eq!(block.statements.len(), 2);
+    let index = match &block.statements[0] {
+        Statement::Definition(definition) => {
+            assert_eq!(definition.value.as_ref().unwrap().to_string(), "i + 1");
+            definition.variable_names[0].identifier.name.to_string()
+        }
+        statement => panic!("unexpected statement {}", statement),
+    };
+    assert!(is_generated_name(&index));
+
+    // The index is read and written back through the bound variable.
+    match &block.statements[1] {
+        Statement::Assign(assign) => {
+            assert_eq!(assign.operation, AssignOperation::Assign);
+            assert_eq!(assign.to_string(), format!("b[{0}] = b[{0}] + 5;", index));
+        }
+        statement => panic!("unexpected statement {}", statement),
+    }
+}
+
+#[test]
+fn test_compound_assignment_keeps_trivial_index() {
+    let statements = main_statements(
+        r#"
+    function main(a: [u32; 4], i: u32) {
+        let b = a;
+        let c = 1u32;
+        b[i] += 5;
+        b[0] *= 2;
+        b[1 + 2] -= 1;
+        c += 1;
+    }
+    "#,
+    );
+
+    let assignments = statements[2..]
+        .iter()
+        .map(|statement| match statement {
+            Statement::Assign(assign) => assign.to_string(),
+            statement => panic!("unexpected statement {}", statement),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        assignments,
+        vec![
+            "b[i] = b[i] + 5;",
+            "b[0] = b[0] * 2;",
+            "b[1 + 2] = b[1 + 2] - 1;",
+            "c = c + 1;",
+        ]
+    );
+}
+
+#[test]
+fn test_canonicalization_keeps_grouping() {
+    let statements = main_statements(
+        r#"
+    function main(a: u32, b: u32) {
+        let c = (a + b) * 2;
+        c -= 1 + a;
+        c *= (b);
+        c **= a ** 2;
+    }
+    "#,
+    );
+
+    let printed = statements
+        .iter()
+        .map(|statement| statement.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        printed,
+        vec![
+            "let mut c = (a + b) * 2;",
+            "c = c - (1 + a);",
+            "c = c * (b);",
+            "c = c ** a ** 2;",
+        ]
+    );
+}
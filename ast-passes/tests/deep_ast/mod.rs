@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{builder::*, Ast, AstPass, BinaryOperation, IntegerType, Type};
+use leo_ast_passes::Canonicalizer;
+
+/// Returns a program returning the sum of `terms` ones, which parses into a left-nested chain `terms - 1` deep.
+fn deep_chain(terms: usize) -> String {
+    let mut program_string = String::from("function main() -> u32 {\n    return 1u32");
+    for _ in 1..terms {
+        program_string.push_str(" + 1u32");
+    }
+    program_string.push_str(";\n}\n");
+    program_string
+}
+
+#[test]
+fn test_deep_chain_does_not_overflow() {
+    // Spawned threads get the default stack size, unlike the main thread.
+    std::thread::spawn(|| {
+        let ast = leo_parser::parse_ast("input.leo", &deep_chain(50_000)).unwrap();
+
+        let canonicalized = Canonicalizer::do_pass(ast.into_repr()).unwrap();
+        assert!(canonicalized.as_repr().to_string().contains("1u32 + 1u32"));
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_deep_chain_json_does_not_overflow() {
+    // The JSON grows with the square of the depth, as every span holds its source and every level is indented,
+    // so the chain is only as deep as it takes to overflow without guarded recursion.
+    std::thread::spawn(|| {
+        let ast = leo_parser::parse_ast("input.leo", &deep_chain(1_000)).unwrap();
+
+        let json = ast.to_json_string().unwrap();
+        let reparsed = Ast::from_json_string(&json).unwrap();
+        assert_eq!(json, reparsed.to_json_string().unwrap());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_expression_depth_limit() {
+    let mut chain = expr::int(1, IntegerType::U32);
+    for _ in 1..1_000 {
+        chain = expr::binary(chain, BinaryOperation::Add, expr::int(1, IntegerType::U32));
+    }
+    let program = program()
+        .function(
+            func::new("main")
+                .returns(Type::IntegerType(IntegerType::U32))
+                .body(vec![stmt::return_(chain)]),
+        )
+        .build();
+
+    let error = Canonicalizer::do_pass_with_max_depth(program, 100).unwrap_err();
+    assert!(error.to_string().contains("expression nests more than 100 levels deep"));
+}
@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast_passes::canonicalize_fallible;
+use leo_errors::LeoError;
+
+/// Inputs that used to panic or overflow the stack in the parser or the canonicalizer.
+fn corpus() -> Vec<Vec<u8>> {
+    vec![
+        // A multi-byte character the tokenizer does not recognize.
+        "function main() { let x = é; }".into(),
+        // An unrecognized character on the last line of a file.
+        "function main() {}\n§".into(),
+        // A unicode escape longer than 255 bytes.
+        format!("function main() {{ let s = \"\\u{{{}}}\"; }}", "0".repeat(300)).into(),
+        // A character literal ending in an unfinished unicode escape.
+        "function main() { let c = '\\u".into(),
+        // A unicode escape in a character literal ending in a multi-byte character.
+        "function main() { let c = '\\u{é'; }".into(),
+        // A circuit member variable cut off after its separator.
+        "circuit A { x: u8,".into(),
+        // A string literal spanning two lines.
+        "function main() { let s = \"a\nbcdefghijklmnopqrstuvwxyz\"; }".into(),
+        // Deeply nested blocks.
+        format!("function main() {}{}", "{".repeat(100_000), "}".repeat(100_000)).into(),
+        // A long else-if chain.
+        format!("function main() {{ {} }}", "if true {} else ".repeat(100_000) + "{}").into(),
+        // Deeply nested array types.
+        format!(
+            "function main(a: {}u8{}) {{}}",
+            "[".repeat(100_000),
+            "; 1]".repeat(100_000)
+        )
+        .into(),
+        // A long chain of unary operators.
+        format!("function main() {{ let x = {}true; }}", "!".repeat(100_000)).into(),
+        // A long chain of ternary expressions.
+        format!("function main() {{ let x = {}1u8; }}", "true ? 1u8 : ".repeat(100_000)).into(),
+        // Invalid UTF-8.
+        b"function main() { let x = \xff; }".to_vec(),
+    ]
+}
+
+/// Parses and canonicalizes the source, returning every diagnostic produced on the way.
+fn diagnostics(source: &[u8]) -> Vec<LeoError> {
+    let (program, mut diagnostics) = leo_parser::parse_fallible(source);
+    if let Some(program) = program {
+        diagnostics.extend(canonicalize_fallible(program).1);
+    }
+    diagnostics
+}
+
+#[test]
+fn test_corpus_does_not_panic() {
+    // Spawned threads get the default stack size, unlike the main thread.
+    std::thread::spawn(|| {
+        for source in corpus() {
+            for diagnostic in diagnostics(&source) {
+                assert!(
+                    !diagnostic.to_string().contains("panicked"),
+                    "{}",
+                    String::from_utf8_lossy(&source)
+                );
+            }
+        }
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let errors = diagnostics(b"function main() { let x = \xff; }");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("invalid byte at offset 26"));
+}
+
+#[test]
+fn test_nesting_limit() {
+    std::thread::spawn(|| {
+        let source = format!("function main() {}{}", "{".repeat(100_000), "}".repeat(100_000));
+        let errors = diagnostics(source.as_bytes());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("nests more than 1000 levels deep"));
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_multi_line_string_canonicalizes() {
+    let (program, errors) = leo_parser::parse_fallible("function main() { let s = \"a\nbcdefghijklmnop\"; }");
+    assert!(errors.is_empty());
+    let (program, errors) = canonicalize_fallible(program.unwrap());
+    assert!(errors.is_empty());
+    assert!(program.is_some());
+}
@@ -0,0 +1,186 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::Program;
+use leo_ast_passes::{ImportDiagnostic, ImportResolver, ImportSeverity, Importer, MockedImportResolver};
+use leo_errors::{Result, Span};
+
+use indexmap::IndexMap;
+
+fn parse(path: &str, source: &str) -> Program {
+    leo_parser::parse(path, source).unwrap()
+}
+
+fn packages() -> MockedImportResolver {
+    let mut packages = IndexMap::new();
+    packages.insert(
+        "foo".to_string(),
+        parse("foo.leo", "function bar() {}\nfunction baz() {}\n"),
+    );
+    packages.insert("lib.nested".to_string(), parse("nested.leo", "function qux() {}\n"));
+    MockedImportResolver { packages }
+}
+
+/// Resolves packages regardless of whether their names are written with dashes or underscores.
+struct DashInsensitiveResolver(MockedImportResolver);
+
+impl ImportResolver for DashInsensitiveResolver {
+    fn resolve_package(&mut self, package_segments: &[&str], span: &Span) -> Result<Option<Program>> {
+        self.0.resolve_package(package_segments, span)
+    }
+
+    fn normalize_package(&self, package_segments: &[&str]) -> Vec<String> {
+        package_segments
+            .iter()
+            .map(|segment| segment.replace('-', "_"))
+            .collect()
+    }
+}
+
+fn resolve(source: &str, resolver: &mut impl ImportResolver) -> (Program, Vec<ImportDiagnostic>) {
+    let program = parse("main.leo", source);
+    let (ast, diagnostics) = Importer::do_pass_with_diagnostics(program, resolver).unwrap();
+    (ast.into_repr(), diagnostics)
+}
+
+/// Returns the packages imported by the program, without the prelude.
+fn imported_packages(program: &Program) -> Vec<String> {
+    program
+        .imports
+        .keys()
+        .map(|package| package.join("."))
+        .filter(|package| !package.starts_with("std."))
+        .collect()
+}
+
+#[test]
+fn test_exact_duplicate_is_a_warning() {
+    let (program, diagnostics) = resolve(
+        "import foo.bar;\nimport foo.bar;\nfunction main() {}\n",
+        &mut packages(),
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.severity, ImportSeverity::Warning);
+    assert_eq!(
+        diagnostic.to_string(),
+        "`foo.bar` is imported twice, first at main.leo:1:12-15"
+    );
+    // Both imports are pointed at.
+    assert_eq!(diagnostic.other.line_start, 1);
+    assert_eq!(diagnostic.span.line_start, 2);
+    assert_eq!(diagnostic.span.col_start, 12);
+
+    // The package is still resolved once.
+    assert_eq!(imported_packages(&program), vec!["foo"]);
+}
+
+#[test]
+fn test_distinct_imports_are_not_repeats() {
+    let (_, diagnostics) = resolve(
+        "import foo.bar;\nimport foo.bar as other;\nimport foo.baz;\nfunction main() {}\n",
+        &mut packages(),
+    );
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_wildcard_and_name_overlap_is_a_note() {
+    let (_, diagnostics) = resolve(
+        "import foo.*;\nimport foo.(bar, baz as other);\nfunction main() {}\n",
+        &mut packages(),
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.severity, ImportSeverity::Note);
+    assert_eq!(
+        diagnostic.to_string(),
+        "note: `foo.bar` is also imported by `foo.*` at main.leo:1:12-13, which binds `bar` to the same definition"
+    );
+    assert_eq!((diagnostic.span.line_start, diagnostic.other.line_start), (2, 1));
+}
+
+#[test]
+fn test_duplicates_are_found_after_normalization() {
+    let mut packages = packages();
+    packages
+        .packages
+        .insert("my_lib".to_string(), parse("my_lib.leo", "function bar() {}\n"));
+    let mut resolver = DashInsensitiveResolver(packages);
+    let (program, diagnostics) = resolve(
+        "import my-lib.bar;\nimport my_lib.bar;\nimport lib.nested.qux;\nfunction main() {}\n",
+        &mut resolver,
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].to_string().starts_with("`my_lib.bar` is imported twice"));
+    assert_eq!(imported_packages(&program), vec!["my_lib", "lib.nested"]);
+
+    // The import statements name the packages as they were resolved, at the spans they were written at.
+    let statements = program
+        .import_statements
+        .iter()
+        .map(|statement| statement.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statements,
+        vec!["import my_lib.bar;", "import my_lib.bar;", "import lib.nested.qux;"]
+    );
+    assert_eq!(program.import_statements[0].get_file_name(), "my_lib");
+}
+
+#[test]
+fn test_imports_are_deterministic() {
+    let source = "import lib.nested.*;\nimport foo.baz;\nimport lib.nested.qux;\nimport foo.*;\nfunction main() {}\n";
+    let runs = (0..4)
+        .map(|_| {
+            let (program, diagnostics) = resolve(source, &mut packages());
+            let packages = program
+                .imports
+                .keys()
+                .map(|package| package.join("."))
+                .collect::<Vec<_>>();
+            let diagnostics = diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.to_string())
+                .collect::<Vec<_>>();
+            (packages, diagnostics)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(runs.iter().all(|run| *run == runs[0]));
+    // The prelude comes first, then the packages in the order they are first imported.
+    let (packages, diagnostics) = &runs[0];
+    assert!(
+        packages
+            .iter()
+            .take_while(|package| package.starts_with("std."))
+            .count()
+            > 0
+    );
+    assert_eq!(
+        packages
+            .iter()
+            .filter(|package| !package.starts_with("std."))
+            .collect::<Vec<_>>(),
+        vec!["lib.nested", "foo"]
+    );
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.starts_with("note: ")));
+}
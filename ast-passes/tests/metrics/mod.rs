@@ -0,0 +1,172 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{metrics, AstMetrics, FunctionMetrics, Program};
+
+const PROGRAM: &str = r#"
+const LIMIT: u8 = 1u8 + 2u8;
+
+circuit Point {
+    x: u8,
+
+    function double(self) -> u8 {
+        return self.x * 2u8;
+    }
+}
+
+function main(a: u8) -> u8 {
+    let b = a + 1u8;
+    if b > 2u8 {
+        console.log("{}", b);
+    }
+    return b;
+}
+"#;
+
+const BODY: &str = r#"
+    let b = a + 1u8;
+    if b > 2u8 {
+        console.log("{}", b);
+    }
+    return b;
+"#;
+
+fn parse(program: &str) -> Program {
+    leo_parser::parse_ast("input.leo", program).unwrap().into_repr()
+}
+
+/// Returns the metrics of a program whose `main` function runs [`BODY`] the given number of times.
+fn repeated(times: usize) -> AstMetrics {
+    let program = format!("function main(a: u8) -> u8 {{{}}}\n", BODY.repeat(times));
+    metrics::collect(&parse(&program))
+}
+
+#[test]
+fn test_metrics_of_fixture() {
+    let metrics = metrics::collect(&parse(PROGRAM));
+
+    let expressions = metrics.expressions.iter().map(|(kind, count)| (*kind, *count));
+    let mut expressions = expressions.collect::<Vec<_>>();
+    expressions.sort_unstable();
+    assert_eq!(
+        expressions,
+        vec![
+            ("Binary", 4),
+            ("CircuitMemberAccess", 1),
+            ("Identifier", 5),
+            ("Value", 5)
+        ]
+    );
+    let mut statements = metrics
+        .statements
+        .iter()
+        .map(|(kind, count)| (*kind, *count))
+        .collect::<Vec<_>>();
+    statements.sort_unstable();
+    assert_eq!(
+        statements,
+        vec![("Conditional", 1), ("Console", 1), ("Definition", 1), ("Return", 2)]
+    );
+    assert_eq!(metrics.expression_count(), 15);
+    assert_eq!(metrics.statement_count(), 5);
+
+    assert_eq!(metrics.functions.len(), 2);
+    assert_eq!(
+        metrics.functions["main"],
+        FunctionMetrics {
+            statements: 4,
+            expressions: 8,
+        }
+    );
+    assert_eq!(
+        metrics.functions["Point::double"],
+        FunctionMetrics {
+            statements: 1,
+            expressions: 4,
+        }
+    );
+
+    // `self` in `self.x * 2u8`.
+    assert_eq!(metrics.max_expression_depth, 3);
+    // `console.log` in the `if` of `main`.
+    assert_eq!(metrics.max_block_depth, 2);
+    assert_eq!(metrics.identifiers, 5);
+}
+
+#[test]
+fn test_doubled_function_doubles_counts() {
+    let single = repeated(1);
+    let double = repeated(2);
+
+    for (kind, count) in single.expressions.iter() {
+        assert_eq!(double.expressions[kind], 2 * count, "{}", kind);
+    }
+    for (kind, count) in single.statements.iter() {
+        assert_eq!(double.statements[kind], 2 * count, "{}", kind);
+    }
+    assert_eq!(double.expressions.len(), single.expressions.len());
+    assert_eq!(double.statements.len(), single.statements.len());
+    assert_eq!(
+        double.functions["main"].statements,
+        2 * single.functions["main"].statements
+    );
+    assert_eq!(
+        double.functions["main"].expressions,
+        2 * single.functions["main"].expressions
+    );
+    assert_eq!(double.identifiers, 2 * single.identifiers);
+
+    assert_eq!(double.max_expression_depth, single.max_expression_depth);
+    assert_eq!(double.max_block_depth, single.max_block_depth);
+}
+
+#[test]
+fn test_nesting_depth_of_deep_expression() {
+    // `((a + 1u8) + 1u8) + ...` nests every addition in the next one, with the operands of the innermost below it.
+    let program = format!(
+        "function main(a: u8) -> u8 {{\n    return a{};\n}}\n",
+        " + 1u8".repeat(40)
+    );
+    let metrics = metrics::collect(&parse(&program));
+
+    assert_eq!(metrics.max_expression_depth, 41);
+    assert_eq!(metrics.max_block_depth, 1);
+    assert_eq!(metrics.expressions["Binary"], 40);
+}
+
+#[test]
+fn test_nesting_depth_of_blocks() {
+    let program = r#"
+function main(a: u8) -> u8 {
+    for i in 0..2 {
+        if a > 1u8 {
+            console.log("{}", a);
+        } else {
+            if a > 0u8 {
+                console.log("{}", a);
+            } else if a == 0u8 {
+                console.log("{}", a);
+            }
+        }
+    }
+    return a;
+}
+"#;
+    let metrics = metrics::collect(&parse(program));
+
+    // The `console.log` statements of the `else` branch are in its block, inside the `if` of the loop.
+    assert_eq!(metrics.max_block_depth, 4);
+}
@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{builder::*, is_generated_name, IntegerType, NameGenerator, Program, Type};
+
+const PROGRAM: &str = r#"
+circuit Flag {
+    cond_1: bool,
+}
+
+function main(a: u32) -> u32 {
+    let cond = a > 1u32;
+    return a;
+}
+"#;
+
+fn parse() -> Program {
+    leo_parser::parse_ast("input.leo", PROGRAM).unwrap().into_repr()
+}
+
+#[test]
+fn test_fresh_names_count_per_prefix() {
+    let mut generator = NameGenerator::new(&parse()).unwrap();
+
+    assert_eq!(generator.fresh("cond"), "$cond_1");
+    assert_eq!(generator.fresh("cond"), "$cond_2");
+    assert_eq!(generator.fresh("flag"), "$flag_1");
+    assert!(is_generated_name("$cond_1"));
+    assert!(!is_generated_name("cond_1"));
+}
+
+#[test]
+fn test_fresh_names_avoid_program_identifiers() {
+    let u32_type = || Type::IntegerType(IntegerType::U32);
+    let program = program()
+        .function(
+            func::new("main")
+                .input("$cond_1", u32_type())
+                .returns(u32_type())
+                .body(vec![
+                    stmt::let_("$cond_3", None, expr::ident("$cond_1")),
+                    stmt::return_(expr::ident("$cond_3")),
+                ]),
+        )
+        .build();
+    let mut generator = NameGenerator::new(&program).unwrap();
+    generator.reserve("$cond_4");
+
+    assert_eq!(generator.fresh("cond"), "$cond_2");
+    assert_eq!(generator.fresh("cond"), "$cond_5");
+}
+
+#[test]
+fn test_fresh_names_are_deterministic() {
+    let names = || {
+        let mut generator = NameGenerator::new(&parse()).unwrap();
+        vec![generator.fresh("cond"), generator.fresh("tmp"), generator.fresh("cond")]
+    };
+
+    assert_eq!(names(), names());
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{ArtifactFormat, AstPass};
+use leo_ast_passes::{Canonicalizer, RenameMap, Renamer, RENAME_MAP_FORMAT};
+
+fn rename(program_string: &str) -> (leo_ast::Program, RenameMap) {
+    let ast = leo_parser::parse_ast("input.leo", program_string).unwrap();
+    let ast = Canonicalizer::do_pass(ast.into_repr()).unwrap();
+    let (ast, map) = Renamer::do_pass_with_map(ast.into_repr()).unwrap();
+    (ast.into_repr(), map)
+}
+
+#[test]
+fn test_entrypoint_keeps_name() {
+    let (program, map) = rename(include_str!("program.leo"));
+
+    assert!(program.functions.keys().any(|name| name.name.as_ref() == "main"));
+    assert_eq!(map.get("main"), None);
+    assert_eq!(map.get("amount"), None);
+}
+
+#[test]
+fn test_references_are_renamed() {
+    let (program, map) = rename(include_str!("program.leo"));
+
+    let double = map.get("double").unwrap();
+    let point = map.get("Point").unwrap();
+    assert!(double.starts_with('f'));
+    assert!(point.starts_with('c'));
+    assert!(map.get("x").unwrap().starts_with('m'));
+
+    assert!(program.functions.keys().any(|name| name.name.as_ref() == double));
+    assert!(program.circuits.keys().any(|name| name.name.as_ref() == point));
+    assert!(program
+        .functions
+        .keys()
+        .chain(program.circuits.keys())
+        .all(|name| name.name.as_ref() != "double" && name.name.as_ref() != "Point"));
+}
+
+#[test]
+fn test_rename_map_round_trips() {
+    let (_, map) = rename(include_str!("program.leo"));
+
+    let json = map.to_json_string().unwrap();
+    assert_eq!(RenameMap::from_json_string(&json).unwrap(), map);
+}
+
+#[test]
+fn test_stamped_rename_map_round_trips() {
+    let (_, map) = rename(include_str!("program.leo"));
+
+    let json = RENAME_MAP_FORMAT.stamp(map.to_json_string().unwrap().as_bytes());
+    assert_eq!(
+        RenameMap::from_json_string(std::str::from_utf8(&json).unwrap()).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn test_incompatible_rename_map_format() {
+    let (_, map) = rename(include_str!("program.leo"));
+    let bumped = ArtifactFormat {
+        version: RENAME_MAP_FORMAT.version + 1,
+        ..RENAME_MAP_FORMAT
+    };
+
+    let json = bumped.stamp(map.to_json_string().unwrap().as_bytes());
+    let error = RenameMap::from_json_string(std::str::from_utf8(&json).unwrap()).unwrap_err();
+    assert_eq!(error.error_code(), "EAST0372020");
+}
+
+#[test]
+fn test_demangle() {
+    let (_, map) = rename(include_str!("program.leo"));
+
+    let message = format!("function `{}` not found", map.get("double").unwrap());
+    assert_eq!(map.demangle(&message), "function `double` not found");
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{AstNode, Expression, Program, Statement, Traversal};
+
+const PROGRAM: &str = r#"
+const LIMIT: u8 = 1u8 + 2u8;
+
+circuit Point {
+    x: u8,
+
+    function double(self) -> u8 {
+        return self.x * 2u8;
+    }
+}
+
+function main(a: u8) -> u8 {
+    let b = a + 1u8;
+    if b > 2u8 {
+        console.log("{}", b);
+    }
+    return b;
+}
+"#;
+
+fn parse() -> Program {
+    leo_parser::parse_ast("input.leo", PROGRAM).unwrap().into_repr()
+}
+
+fn is_same(left: AstNode, right: AstNode) -> bool {
+    match (left, right) {
+        (AstNode::Statement(left), AstNode::Statement(right)) => std::ptr::eq(left, right),
+        (AstNode::Expression(left), AstNode::Expression(right)) => std::ptr::eq(left, right),
+        _ => false,
+    }
+}
+
+#[test]
+fn test_counts_nodes() {
+    let program = parse();
+    let main = program.functions.values().find(|f| f.get_name() == "main").unwrap();
+
+    assert_eq!(program.iter_statements(Traversal::PreOrder).count(), 5);
+    assert_eq!(program.iter_expressions(Traversal::PreOrder).count(), 15);
+    assert_eq!(main.iter_statements(Traversal::PreOrder).count(), 4);
+    assert_eq!(main.iter_expressions(Traversal::PreOrder).count(), 8);
+    assert_eq!(program.iter_nodes(Traversal::PostOrder).count(), 20);
+}
+
+#[test]
+fn test_paths_resolve_to_nodes() {
+    let program = parse();
+
+    for order in [Traversal::PreOrder, Traversal::PostOrder].iter() {
+        for (node, path) in program.iter_nodes(*order) {
+            assert!(is_same(path.resolve(&program).unwrap(), node));
+        }
+        for (expression, path) in program.iter_expressions_with_path(*order) {
+            assert!(is_same(
+                path.resolve(&program).unwrap(),
+                AstNode::Expression(expression)
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_children_order() {
+    let program = parse();
+    let pre_order: Vec<_> = program.iter_nodes(Traversal::PreOrder).map(|(node, _)| node).collect();
+    let post_order: Vec<_> = program.iter_nodes(Traversal::PostOrder).map(|(node, _)| node).collect();
+
+    let position = |nodes: &[AstNode], target: AstNode| nodes.iter().position(|node| is_same(*node, target)).unwrap();
+    for node in pre_order.iter() {
+        for child in node.children() {
+            assert!(position(&pre_order, *node) < position(&pre_order, child));
+            assert!(position(&post_order, child) < position(&post_order, *node));
+        }
+    }
+
+    assert!(matches!(pre_order[0], AstNode::Statement(Statement::Definition(_))));
+    assert!(matches!(pre_order[1], AstNode::Expression(Expression::Binary(_))));
+    assert!(matches!(post_order[0], AstNode::Expression(Expression::Identifier(_))));
+}
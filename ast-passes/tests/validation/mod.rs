@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    builder, validate, validate_pass_output, Annotation, AstPass, Block, Function, FunctionInput, Identifier,
+    InvariantViolation, Program, ReconstructingReducer, ReducerPass, Stage, Statement, Type,
+};
+use leo_ast_passes::{Canonicalizer, Renamer};
+use leo_errors::{Result, Span};
+
+const HEALTHY: &str = r#"
+circuit Point {
+    x: u32,
+
+    function new(x: u32) -> Self {
+        return Self { x };
+    }
+}
+
+function main(a: u32) -> u32 {
+    let p = Point::new(a);
+    let total = p.x;
+    total += 1u32;
+    return total;
+}
+"#;
+
+fn parse(program_string: &str) -> Program {
+    leo_parser::parse_ast("input.leo", program_string).unwrap().into_repr()
+}
+
+fn main_function(program: &mut Program) -> &mut Function {
+    program
+        .functions
+        .values_mut()
+        .find(|function| function.identifier.name.as_ref() == "main")
+        .unwrap()
+}
+
+fn messages(program: &Program, stage: Stage) -> Vec<String> {
+    validate(program, stage)
+        .into_iter()
+        .map(|violation| violation.message)
+        .collect()
+}
+
+#[test]
+fn test_healthy_program_passes_every_stage() {
+    let parsed = parse(HEALTHY);
+    assert!(validate(&parsed, Stage::Parsed).is_empty());
+
+    let canonicalized = Canonicalizer::do_pass(parsed).unwrap().into_repr();
+    assert!(validate(&canonicalized, Stage::Parsed).is_empty());
+    assert!(validate(&canonicalized, Stage::Canonicalized).is_empty());
+
+    let renamed = Renamer::do_pass(canonicalized).unwrap().into_repr();
+    assert!(validate(&renamed, Stage::Canonicalized).is_empty());
+}
+
+#[test]
+fn test_self_type_only_valid_before_canonicalization() {
+    let parsed = parse(HEALTHY);
+
+    assert!(validate(&parsed, Stage::Parsed).is_empty());
+    assert!(messages(&parsed, Stage::Canonicalized).contains(&"`Self` type survived canonicalization".to_string()));
+}
+
+#[test]
+fn test_compound_assignment_after_canonicalization() {
+    let parsed = parse(HEALTHY);
+
+    assert!(messages(&parsed, Stage::Canonicalized)
+        .contains(&"compound assignment `+=` survived canonicalization".to_string()));
+}
+
+#[test]
+fn test_missing_output_after_canonicalization() {
+    let mut program = Canonicalizer::do_pass(parse(HEALTHY)).unwrap().into_repr();
+    main_function(&mut program).output = None;
+
+    assert_eq!(
+        messages(&program, Stage::Canonicalized),
+        vec!["function 'main' has no output type after canonicalization"]
+    );
+}
+
+#[test]
+fn test_empty_variable_names() {
+    let mut program = builder::program()
+        .function(builder::func::new("main").body(vec![builder::stmt::let_("a", None, builder::expr::boolean(true))]))
+        .build();
+    match &mut main_function(&mut program).block.statements[0] {
+        Statement::Definition(definition) => definition.variable_names.clear(),
+        _ => panic!("expected a definition"),
+    }
+
+    assert_eq!(
+        messages(&program, Stage::Parsed),
+        vec!["definition declares no variables"]
+    );
+}
+
+#[test]
+fn test_reversed_span() {
+    let mut program = parse(HEALTHY);
+    let function = main_function(&mut program);
+    function.span.line_stop = function.span.line_start - 1;
+
+    let violations = validate(&program, Stage::Parsed);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].message, "function span ends before it starts");
+    assert_eq!(violations[0].span, main_function(&mut program).span);
+}
+
+/// A deliberately broken pass that reintroduces `Self` as the output type of every function.
+#[derive(Default)]
+struct CorruptingReducer {
+    in_circuit: bool,
+}
+
+impl ReconstructingReducer for CorruptingReducer {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_function(
+        &mut self,
+        function: &Function,
+        identifier: Identifier,
+        annotations: Vec<Annotation>,
+        input: Vec<FunctionInput>,
+        _output: Option<Type>,
+        block: Block,
+    ) -> Result<Function> {
+        Ok(Function {
+            identifier,
+            annotations,
+            input,
+            output: Some(Type::SelfType),
+            block,
+            span: function.span.clone(),
+        })
+    }
+}
+
+#[test]
+fn test_violation_blames_pass() {
+    let canonicalized = Canonicalizer::do_pass(parse(HEALTHY)).unwrap().into_repr();
+    validate_pass_output("canonicalization", &canonicalized, Stage::Canonicalized).unwrap();
+
+    let corrupted = ReducerPass::<CorruptingReducer>::do_pass(canonicalized)
+        .unwrap()
+        .into_repr();
+    let error = validate_pass_output("corrupting pass", &corrupted, Stage::Canonicalized)
+        .err()
+        .unwrap()
+        .to_string();
+
+    assert!(error.contains("pass 'corrupting pass' produced an invalid canonicalized program"));
+    assert!(error.contains("`Self` type survived canonicalization"));
+}
+
+#[test]
+fn test_violation_display() {
+    let violation = InvariantViolation {
+        message: "definition declares no variables".to_string(),
+        span: Span::new(2, 2, 5, 9, "input.leo".to_string().into(), "".into()),
+    };
+
+    assert_eq!(
+        violation.to_string(),
+        "definition declares no variables at input.leo:2:5"
+    );
+}
+
+#[test]
+fn test_placeholders_only_valid_before_type_checking() {
+    let source = "function main(a: u32) -> u32 {\n    let b: u32 = a * / 3;\n    return b;\n}\n";
+    let (recovered, errors) = leo_parser::parse_recovering("input.leo", source);
+    assert_eq!(errors.len(), 1);
+    let canonicalized = Canonicalizer::do_pass(recovered).unwrap().into_repr();
+
+    // The parser already reported the error the placeholder stands for.
+    assert!(validate(&canonicalized, Stage::TypeChecked).is_empty());
+
+    let mut unreported = canonicalized;
+    unreported.recovered.clear();
+    assert!(validate(&unreported, Stage::Canonicalized).is_empty());
+    assert_eq!(
+        messages(&unreported, Stage::TypeChecked),
+        vec!["error placeholder survived type checking"]
+    );
+}
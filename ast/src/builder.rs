@@ -0,0 +1,471 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fluent constructors for programs built in code rather than parsed, by tests and code generators.
+//!
+//! Nothing is read from a file, so every node gets a synthesized span in [`BUILDER_PATH`]
+//! on a line of its own. Errors about a built node then still tell it apart from its neighbours.
+//!
+//! ```
+//! use leo_ast::{builder::*, BinaryOperation, IntegerType, Type};
+//!
+//! let program = program()
+//!     .function(
+//!         func::new("main")
+//!             .input("a", Type::IntegerType(IntegerType::U8))
+//!             .returns(Type::IntegerType(IntegerType::U8))
+//!             .body(vec![stmt::return_(expr::binary(
+//!                 expr::ident("a"),
+//!                 BinaryOperation::Add,
+//!                 expr::int(5, IntegerType::U8),
+//!             ))]),
+//!     )
+//!     .build();
+//! ```
+
+use crate::*;
+use leo_errors::Span;
+
+use std::{cell::Cell, sync::Arc};
+
+/// The path of the spans given to built nodes.
+pub const BUILDER_PATH: &str = "<builder>";
+
+thread_local! {
+    static NEXT_LINE: Cell<usize> = Cell::new(1);
+}
+
+///
+/// Returns a span on a line that no other built node uses.
+///
+pub fn span() -> Span {
+    let line = NEXT_LINE.with(|next| {
+        let line = next.get();
+        next.set(line + 1);
+        line
+    });
+    Span::new(line, line, 1, 1, Arc::new(BUILDER_PATH.to_string()), "".into())
+}
+
+///
+/// Returns an identifier with a fresh span.
+///
+pub fn ident(name: &str) -> Identifier {
+    Identifier::new_with_span(name, span())
+}
+
+/// Expressions.
+pub mod expr {
+    use super::*;
+
+    pub fn int(value: u128, type_: IntegerType) -> Expression {
+        Expression::Value(ValueExpression::Integer(type_, value.to_string().into(), span()))
+    }
+
+    /// An integer literal without a type suffix, typed by its context.
+    pub fn implicit(value: u128) -> Expression {
+        Expression::Value(ValueExpression::Implicit(value.to_string().into(), span()))
+    }
+
+    pub fn field(value: u128) -> Expression {
+        Expression::Value(ValueExpression::Field(value.to_string().into(), span()))
+    }
+
+    pub fn boolean(value: bool) -> Expression {
+        Expression::Value(ValueExpression::Boolean(value.to_string().into(), span()))
+    }
+
+    pub fn address(value: &str) -> Expression {
+        Expression::Value(ValueExpression::Address(value.into(), span()))
+    }
+
+    pub fn ident(name: &str) -> Expression {
+        Expression::Identifier(super::ident(name))
+    }
+
+    pub fn binary(left: Expression, op: BinaryOperation, right: Expression) -> Expression {
+        Expression::Binary(BinaryExpression {
+            left: Box::new(left),
+            right: Box::new(right),
+            op,
+            span: span(),
+        })
+    }
+
+    pub fn unary(op: UnaryOperation, inner: Expression) -> Expression {
+        Expression::Unary(UnaryExpression {
+            inner: Box::new(inner),
+            op,
+            span: span(),
+        })
+    }
+
+    pub fn ternary(condition: Expression, if_true: Expression, if_false: Expression) -> Expression {
+        Expression::Ternary(TernaryExpression {
+            condition: Box::new(condition),
+            if_true: Box::new(if_true),
+            if_false: Box::new(if_false),
+            span: span(),
+        })
+    }
+
+    pub fn cast(inner: Expression, target_type: Type) -> Expression {
+        Expression::Cast(CastExpression {
+            inner: Box::new(inner),
+            target_type,
+            span: span(),
+        })
+    }
+
+    /// `(inner)`. Built expressions need no parentheses: the printer adds those that precedence requires.
+    pub fn parenthesized(inner: Expression) -> Expression {
+        Expression::Parenthesized(ParenthesizedExpression {
+            inner: Box::new(inner),
+            span: span(),
+        })
+    }
+
+    /// A call of `function`, such as `expr::ident("foo")` or `expr::member(expr::ident("p"), "get")`.
+    pub fn call(function: Expression, arguments: Vec<Expression>) -> Expression {
+        Expression::Call(CallExpression {
+            function: Box::new(function),
+            arguments,
+            span: span(),
+        })
+    }
+
+    pub fn array(elements: Vec<Expression>) -> Expression {
+        Expression::ArrayInline(ArrayInlineExpression {
+            elements: elements.into_iter().map(SpreadOrExpression::Expression).collect(),
+            span: span(),
+        })
+    }
+
+    pub fn array_access(array: Expression, index: Expression) -> Expression {
+        Expression::ArrayAccess(ArrayAccessExpression {
+            array: Box::new(array),
+            index: Box::new(index),
+            span: span(),
+        })
+    }
+
+    pub fn tuple(elements: Vec<Expression>) -> Expression {
+        Expression::TupleInit(TupleInitExpression { elements, span: span() })
+    }
+
+    /// An initialization of the named circuit, with its members in the given order.
+    pub fn circuit(name: &str, members: Vec<(&str, Expression)>) -> Expression {
+        Expression::CircuitInit(CircuitInitExpression {
+            name: super::ident(name),
+            members: members
+                .into_iter()
+                .map(|(name, expression)| CircuitImpliedVariableDefinition {
+                    identifier: super::ident(name),
+                    expression: Some(expression),
+                })
+                .collect(),
+            span: span(),
+        })
+    }
+
+    pub fn member(circuit: Expression, name: &str) -> Expression {
+        Expression::CircuitMemberAccess(CircuitMemberAccessExpression {
+            circuit: Box::new(circuit),
+            name: super::ident(name),
+            span: span(),
+            type_: None,
+        })
+    }
+}
+
+/// Statements.
+pub mod stmt {
+    use super::*;
+
+    fn define(declaration_type: Declare, name: &str, type_: Option<Type>, value: Expression) -> Statement {
+        let mutable = declaration_type == Declare::Let;
+        Statement::Definition(DefinitionStatement {
+            declaration_type,
+            variable_names: vec![VariableName {
+                mutable,
+                identifier: ident(name),
+                span: span(),
+            }],
+            type_,
+            value: Some(value),
+            span: span(),
+        })
+    }
+
+    /// `let name: type_ = value;`, without the type annotation if `type_` is `None`.
+    pub fn let_(name: &str, type_: Option<Type>, value: Expression) -> Statement {
+        define(Declare::Let, name, type_, value)
+    }
+
+    /// `const name: type_ = value;`, without the type annotation if `type_` is `None`.
+    pub fn const_(name: &str, type_: Option<Type>, value: Expression) -> Statement {
+        define(Declare::Const, name, type_, value)
+    }
+
+    /// `name op value;`, where `op` is `=` or a compound assignment.
+    pub fn assign(name: &str, operation: AssignOperation, value: Expression) -> Statement {
+        Statement::Assign(Box::new(AssignStatement {
+            operation,
+            assignee: Assignee {
+                identifier: ident(name),
+                accesses: vec![],
+                span: span(),
+            },
+            value,
+            span: span(),
+        }))
+    }
+
+    pub fn return_(expression: Expression) -> Statement {
+        Statement::Return(ReturnStatement {
+            expression,
+            span: span(),
+        })
+    }
+
+    pub fn expression(expression: Expression) -> Statement {
+        Statement::Expression(ExpressionStatement {
+            expression,
+            span: span(),
+        })
+    }
+
+    /// `if condition { .. } else { .. }`, without the `else` block if `otherwise` is `None`.
+    pub fn if_(condition: Expression, then: Vec<Statement>, otherwise: Option<Vec<Statement>>) -> Statement {
+        Statement::Conditional(ConditionalStatement {
+            condition,
+            block: block(then),
+            next: otherwise.map(|statements| Box::new(Statement::Block(block(statements)))),
+            span: span(),
+        })
+    }
+
+    /// `for variable in start..stop { .. }`.
+    pub fn for_(variable: &str, start: Expression, stop: Expression, statements: Vec<Statement>) -> Statement {
+        Statement::Iteration(Box::new(IterationStatement {
+            variable: ident(variable),
+            start,
+            stop,
+            inclusive: false,
+            step: None,
+            block: block(statements),
+            span: span(),
+        }))
+    }
+
+    pub fn block(statements: Vec<Statement>) -> Block {
+        Block {
+            statements,
+            span: span(),
+        }
+    }
+}
+
+/// Functions.
+pub mod func {
+    use super::*;
+
+    /// Builds a [`Function`] one part at a time.
+    #[derive(Clone, Debug)]
+    pub struct FunctionBuilder {
+        function: Function,
+    }
+
+    /// Starts a function with the given name, no inputs, no output type and an empty body.
+    pub fn new(name: &str) -> FunctionBuilder {
+        FunctionBuilder {
+            function: Function {
+                annotations: vec![],
+                identifier: ident(name),
+                input: vec![],
+                output: None,
+                block: stmt::block(vec![]),
+                span: span(),
+            },
+        }
+    }
+
+    impl FunctionBuilder {
+        fn variable(mut self, name: &str, type_: Type, const_: bool) -> Self {
+            self.function.input.push(FunctionInput::Variable(FunctionInputVariable {
+                identifier: ident(name),
+                const_,
+                mutable: !const_,
+                type_,
+                span: span(),
+            }));
+            self
+        }
+
+        /// Appends an input.
+        pub fn input(self, name: &str, type_: Type) -> Self {
+            self.variable(name, type_, false)
+        }
+
+        /// Appends a `const` input.
+        pub fn const_input(self, name: &str, type_: Type) -> Self {
+            self.variable(name, type_, true)
+        }
+
+        /// Appends the `self` input, which makes the function a circuit member function.
+        pub fn self_input(mut self) -> Self {
+            self.function.input.push(FunctionInput::SelfKeyword(SelfKeyword {
+                identifier: ident("self"),
+            }));
+            self
+        }
+
+        /// Adds an annotation without arguments, such as `@test`.
+        pub fn annotation(mut self, name: &str) -> Self {
+            self.function.annotations.push(Annotation {
+                span: span(),
+                name: ident(name),
+                arguments: vec![],
+            });
+            self
+        }
+
+        pub fn returns(mut self, type_: Type) -> Self {
+            self.function.output = Some(type_);
+            self
+        }
+
+        /// Replaces the body of the function.
+        pub fn body(mut self, statements: Vec<Statement>) -> Self {
+            self.function.block = stmt::block(statements);
+            self
+        }
+
+        pub fn build(self) -> Function {
+            self.function
+        }
+    }
+}
+
+/// Circuits.
+pub mod circuit {
+    use super::*;
+
+    /// Builds a [`Circuit`] one member at a time.
+    #[derive(Clone, Debug)]
+    pub struct CircuitBuilder {
+        circuit: Circuit,
+    }
+
+    /// Starts a circuit with the given name and no members.
+    pub fn new(name: &str) -> CircuitBuilder {
+        CircuitBuilder {
+            circuit: Circuit {
+                circuit_name: ident(name),
+                core_mapping: std::cell::RefCell::new(None),
+                members: vec![],
+            },
+        }
+    }
+
+    impl CircuitBuilder {
+        /// Appends a member variable.
+        pub fn variable(mut self, name: &str, type_: Type) -> Self {
+            self.circuit
+                .members
+                .push(CircuitMember::CircuitVariable(ident(name), type_));
+            self
+        }
+
+        /// Appends a static member with the given value.
+        pub fn static_member(mut self, name: &str, type_: Type, value: Expression) -> Self {
+            self.circuit
+                .members
+                .push(CircuitMember::CircuitStatic(ident(name), type_, value));
+            self
+        }
+
+        /// Appends a member function.
+        pub fn function(mut self, function: func::FunctionBuilder) -> Self {
+            self.circuit
+                .members
+                .push(CircuitMember::CircuitFunction(function.build()));
+            self
+        }
+
+        pub fn build(self) -> Circuit {
+            self.circuit
+        }
+    }
+}
+
+/// Builds a [`Program`] one definition at a time.
+#[derive(Clone, Debug)]
+pub struct ProgramBuilder {
+    program: Program,
+}
+
+/// Starts a program named `main` without any definitions.
+pub fn program() -> ProgramBuilder {
+    ProgramBuilder {
+        program: Program::new("main".to_string()),
+    }
+}
+
+impl ProgramBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.program.name = name.to_string();
+        self
+    }
+
+    /// Adds a function, replacing any function of the same name.
+    pub fn function(mut self, function: func::FunctionBuilder) -> Self {
+        let function = function.build();
+        self.program.functions.insert(function.identifier.clone(), function);
+        self
+    }
+
+    /// Adds a circuit, replacing any circuit of the same name.
+    pub fn circuit(mut self, circuit: circuit::CircuitBuilder) -> Self {
+        let circuit = circuit.build();
+        self.program.circuits.insert(circuit.circuit_name.clone(), circuit);
+        self
+    }
+
+    /// Adds a global constant `const name: type_ = value;`.
+    pub fn global_const(mut self, name: &str, type_: Option<Type>, value: Expression) -> Self {
+        let definition = match stmt::const_(name, type_, value) {
+            Statement::Definition(definition) => definition,
+            _ => unreachable!("const_ builds a definition"),
+        };
+        let names = definition
+            .variable_names
+            .iter()
+            .map(|variable| variable.identifier.clone())
+            .collect();
+        self.program.global_consts.insert(names, definition);
+        self
+    }
+
+    pub fn build(self) -> Program {
+        self.program
+    }
+
+    /// Builds the program and wraps it in an [`Ast`].
+    pub fn into_ast(self) -> Ast {
+        Ast::new(self.program)
+    }
+}
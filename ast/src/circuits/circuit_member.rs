@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Function, Identifier, Type};
+use crate::{Expression, Function, Identifier, Type};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -23,6 +23,8 @@ use std::fmt;
 pub enum CircuitMember {
     // (variable_name, variable_type)
     CircuitVariable(Identifier, Type),
+    // (static_name, static_type, value), shared by every instance of the circuit
+    CircuitStatic(Identifier, Type, Expression),
     // (function)
     CircuitFunction(Function),
 }
@@ -31,6 +33,9 @@ impl fmt::Display for CircuitMember {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             CircuitMember::CircuitVariable(ref identifier, ref type_) => write!(f, "{}: {}", identifier, type_),
+            CircuitMember::CircuitStatic(ref identifier, ref type_, ref value) => {
+                write!(f, "static {}: {} = {};", identifier, type_, value)
+            }
             CircuitMember::CircuitFunction(ref function) => write!(f, "{}", function),
         }
     }
@@ -0,0 +1,53 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Node;
+use leo_errors::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+///
+/// A placeholder for an expression or type the parser could not make sense of, made when recovering
+/// from syntax errors so that the rest of the program can still be processed.
+///
+/// It is printed as `/* error */`. A program with placeholders in the bodies of its functions is compiled
+/// except for those functions, which are refused with an error of their own. A placeholder anywhere else,
+/// like the signature of a function, leaves a type unknown and stops the program at type checking.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ErrNode {
+    pub span: Span,
+    /// The source the placeholder stands for, if it was captured when recovering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl fmt::Display for ErrNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "/* error */")
+    }
+}
+
+impl Node for ErrNode {
+    fn span(&self) -> &Span {
+        &self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -20,6 +20,9 @@ pub use array_dimensions::*;
 pub mod const_self_keyword;
 pub use const_self_keyword::*;
 
+pub mod err_node;
+pub use err_node::*;
+
 pub mod global_consts_json;
 
 pub mod identifier;
@@ -40,4 +43,7 @@ pub use self_keyword::*;
 pub mod spread_or_expression;
 pub use spread_or_expression::*;
 
+pub mod stack;
+pub use stack::*;
+
 pub mod vec_tendril_json;
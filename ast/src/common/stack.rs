@@ -0,0 +1,35 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Guarded recursion for walking arbitrarily deep ASTs.
+
+/// The minimum amount of stack space, in bytes, that must remain before a recursive step.
+pub const STACK_RED_ZONE: usize = 128 * 1024;
+
+/// The size, in bytes, of each new stack segment allocated when the red zone is reached.
+pub const STACK_GROWTH: usize = 4 * 1024 * 1024;
+
+/// The default limit on how deeply expressions may nest before compilation is aborted with a diagnostic.
+pub const DEFAULT_MAX_EXPRESSION_DEPTH: usize = 100_000;
+
+/// Runs `f`, first moving onto a freshly allocated stack segment if the current one is nearly exhausted.
+///
+/// Every recursive walk over expressions or statements should enter through this function,
+/// so that deeply nested programs do not overflow the stack.
+#[inline]
+pub fn ensure_stack<R, F: FnOnce() -> R>(f: F) -> R {
+    stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, f)
+}
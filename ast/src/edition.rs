@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The editions of the language, which projects opt into to use newer syntax and checks.
+//!
+//! Every gated feature is registered in [`FEATURE_GATES`] with the first edition it is available in.
+//! The parser and the type checker look the feature up where they meet the construct, and fail with
+//! an error naming it if the program is compiled against an older edition.
+
+use leo_errors::{AstError, Result, Span};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An edition of the language, ordered from oldest to newest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Edition {
+    /// The language before any feature was gated.
+    #[serde(rename = "2021")]
+    E2021,
+    /// Adds array comprehensions, loop steps, mixed-width integer operations and declarations without an
+    /// initializer.
+    #[serde(rename = "2022")]
+    E2022,
+}
+
+impl Edition {
+    /// The newest edition, which programs are compiled against unless they declare another.
+    pub const LATEST: Edition = Edition::E2022;
+
+    /// Every edition, oldest first.
+    pub const ALL: [Edition; 2] = [Edition::E2021, Edition::E2022];
+
+    /// Returns the edition written as `name`, e.g. `2021`, if there is one.
+    pub fn from_name(name: &str) -> Option<Edition> {
+        Self::ALL.iter().copied().find(|edition| edition.to_string() == name)
+    }
+}
+
+impl Default for Edition {
+    fn default() -> Self {
+        Edition::LATEST
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Edition::E2021 => write!(f, "2021"),
+            Edition::E2022 => write!(f, "2022"),
+        }
+    }
+}
+
+/// A construct that is only available from some edition on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// `[element for i in start..stop]`.
+    ArrayComprehensions,
+    /// `for i in start..stop step n`.
+    LoopSteps,
+    /// Operations on integers of one signedness but different widths, when widening is enabled.
+    IntegerWidening,
+    /// `let a: u32;`, assigned before it is read.
+    UninitializedDeclarations,
+}
+
+impl Feature {
+    /// Every gated feature.
+    pub const ALL: [Feature; 4] = [
+        Feature::ArrayComprehensions,
+        Feature::LoopSteps,
+        Feature::IntegerWidening,
+        Feature::UninitializedDeclarations,
+    ];
+
+    /// Returns the gate of the feature in [`FEATURE_GATES`].
+    pub fn gate(self) -> &'static FeatureGate {
+        FEATURE_GATES
+            .iter()
+            .find(|gate| gate.feature == self)
+            .expect("every feature has a gate")
+    }
+
+    ///
+    /// Returns an error naming the feature and the edition it requires if it is not available in `edition`.
+    ///
+    pub fn check(self, edition: Edition, span: &Span) -> Result<()> {
+        let gate = self.gate();
+        if edition < gate.since {
+            return Err(AstError::feature_requires_edition(gate.name, gate.since, span).into());
+        }
+        Ok(())
+    }
+}
+
+/// The first edition a feature is available in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeatureGate {
+    pub feature: Feature,
+    /// The name of the feature in diagnostics, in the plural.
+    pub name: &'static str,
+    pub since: Edition,
+}
+
+/// The gate of every feature.
+pub const FEATURE_GATES: &[FeatureGate] = &[
+    FeatureGate {
+        feature: Feature::ArrayComprehensions,
+        name: "array comprehensions",
+        since: Edition::E2022,
+    },
+    FeatureGate {
+        feature: Feature::LoopSteps,
+        name: "loop steps",
+        since: Edition::E2022,
+    },
+    FeatureGate {
+        feature: Feature::IntegerWidening,
+        name: "mixed-width integer operations",
+        since: Edition::E2022,
+    },
+    FeatureGate {
+        feature: Feature::UninitializedDeclarations,
+        name: "declarations without an initializer",
+        since: Edition::E2022,
+    },
+];
@@ -25,7 +25,8 @@ pub struct ArrayAccessExpression {
 
 impl fmt::Display for ArrayAccessExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}[{}]", self.array, self.index)
+        self.array.fmt_operand(f, Precedence::Postfix, false)?;
+        write!(f, "[{}]", self.index)
     }
 }
 
@@ -0,0 +1,53 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// An array built by evaluating `element` for every value of `variable` in a constant range,
+/// such as `[i * i for i in 0..8]`. Lowered to an inline array during canonicalization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArrayComprehensionExpression {
+    pub element: Box<Expression>,
+    pub variable: Identifier,
+    pub start: Box<Expression>,
+    pub stop: Box<Expression>,
+    pub inclusive: bool,
+    pub span: Span,
+}
+
+impl fmt::Display for ArrayComprehensionExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{} for {} in {}..{}{}]",
+            self.element,
+            self.variable,
+            self.start,
+            if self.inclusive { "=" } else { "" },
+            self.stop
+        )
+    }
+}
+
+impl Node for ArrayComprehensionExpression {
+    fn span(&self) -> &Span {
+        &self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -26,10 +26,10 @@ pub struct ArrayRangeAccessExpression {
 
 impl fmt::Display for ArrayRangeAccessExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.array.fmt_operand(f, Precedence::Postfix, false)?;
         write!(
             f,
-            "{}[{}..{}]",
-            self.array,
+            "[{}..{}]",
             self.left.as_ref().map(|e| e.to_string()).unwrap_or_default(),
             self.right.as_ref().map(|e| e.to_string()).unwrap_or_default()
         )
@@ -16,7 +16,9 @@
 
 use super::*;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+use tendril::StrTendril;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BinaryOperation {
     Add,
     Sub,
@@ -98,6 +100,25 @@ impl BinaryOperation {
             | BinaryOperation::Lt => BinaryOperationClass::Boolean,
         }
     }
+
+    /// Returns how tightly the operator holds its operands, following the parser.
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            BinaryOperation::Or => Precedence::Or,
+            BinaryOperation::And => Precedence::And,
+            BinaryOperation::Eq | BinaryOperation::Ne => Precedence::Equality,
+            BinaryOperation::Ge | BinaryOperation::Gt | BinaryOperation::Le | BinaryOperation::Lt => {
+                Precedence::Ordering
+            }
+            BinaryOperation::BitOr => Precedence::BitOr,
+            BinaryOperation::BitXor => Precedence::BitXor,
+            BinaryOperation::BitAnd => Precedence::BitAnd,
+            BinaryOperation::Shr | BinaryOperation::ShrSigned | BinaryOperation::Shl => Precedence::Shift,
+            BinaryOperation::Add | BinaryOperation::Sub => Precedence::Additive,
+            BinaryOperation::Mul | BinaryOperation::Div | BinaryOperation::Mod => Precedence::Multiplicative,
+            BinaryOperation::Pow => Precedence::Exponential,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -110,7 +131,41 @@ pub struct BinaryExpression {
 
 impl fmt::Display for BinaryExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", self.left, self.op.as_ref(), self.right)
+        // `**` groups to the right and `==` and `!=` do not chain, the other operators group to the left.
+        let precedence = self.op.precedence();
+        let (left_strict, right_strict) = match self.op {
+            BinaryOperation::Pow => (true, false),
+            BinaryOperation::Eq | BinaryOperation::Ne => (true, true),
+            _ => (false, true),
+        };
+        self.left.fmt_operand(f, precedence, left_strict)?;
+        write!(f, " {} ", self.op.as_ref())?;
+        self.right.fmt_operand(f, precedence, right_strict)
+    }
+}
+
+impl Drop for BinaryExpression {
+    /// Drops nested binary operands iteratively, so that long operator chains do not overflow the stack.
+    fn drop(&mut self) {
+        let mut pending = Vec::new();
+        self.take_binary_operands(&mut pending);
+        while let Some(mut expression) = pending.pop() {
+            if let Expression::Binary(binary) = &mut *expression {
+                binary.take_binary_operands(&mut pending);
+            }
+        }
+    }
+}
+
+impl BinaryExpression {
+    /// Moves operands which are themselves binary expressions into `pending`, leaving empty values in their place.
+    fn take_binary_operands(&mut self, pending: &mut Vec<Box<Expression>>) {
+        for operand in [&mut self.left, &mut self.right] {
+            if let Expression::Binary(_) = **operand {
+                let empty = Expression::Value(ValueExpression::Implicit(StrTendril::default(), Span::default()));
+                pending.push(std::mem::replace(operand, Box::new(empty)));
+            }
+        }
     }
 }
 
@@ -25,7 +25,8 @@ pub struct CallExpression {
 
 impl fmt::Display for CallExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}(", self.function)?;
+        self.function.fmt_operand(f, Precedence::Postfix, false)?;
+        write!(f, "(")?;
         for (i, param) in self.arguments.iter().enumerate() {
             write!(f, "{}", param)?;
             if i < self.arguments.len() - 1 {
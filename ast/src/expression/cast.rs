@@ -27,7 +27,8 @@ pub struct CastExpression {
 
 impl fmt::Display for CastExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} as {}", self.inner, self.target_type)
+        self.inner.fmt_operand(f, Precedence::Cast, false)?;
+        write!(f, " as {}", self.target_type)
     }
 }
 
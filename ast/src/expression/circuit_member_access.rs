@@ -26,7 +26,8 @@ pub struct CircuitMemberAccessExpression {
 
 impl fmt::Display for CircuitMemberAccessExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}.{}", self.circuit, self.name)
+        self.circuit.fmt_operand(f, Precedence::Postfix, false)?;
+        write!(f, ".{}", self.name)
     }
 }
 
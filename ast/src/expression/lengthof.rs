@@ -26,7 +26,8 @@ pub struct LengthOfExpression {
 
 impl fmt::Display for LengthOfExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}.len()", self.inner)
+        self.inner.fmt_operand(f, Precedence::Postfix, false)?;
+        write!(f, ".len()")
     }
 }
 
@@ -15,13 +15,13 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    ArrayDimensions, CircuitImpliedVariableDefinition, GroupValue, Identifier, IntegerType, Node, PositiveNumber,
-    SpreadOrExpression,
+    ensure_stack, ArrayDimensions, CircuitImpliedVariableDefinition, ErrNode, GroupValue, Identifier, IntegerType,
+    Node, PositiveNumber, SpreadOrExpression,
 };
 
 use leo_errors::Span;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
 mod binary;
@@ -38,6 +38,8 @@ mod array_inline;
 pub use array_inline::*;
 mod array_init;
 pub use array_init::*;
+mod array_comprehension;
+pub use array_comprehension::*;
 mod tuple_access;
 pub use tuple_access::*;
 mod tuple_init;
@@ -56,9 +58,12 @@ mod cast;
 pub use cast::*;
 mod lengthof;
 pub use lengthof::*;
+mod parenthesized;
+pub use parenthesized::*;
 
 /// Expression that evaluates to a value
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum Expression {
     Identifier(Identifier),
     Value(ValueExpression),
@@ -70,6 +75,7 @@ pub enum Expression {
 
     ArrayInline(ArrayInlineExpression),
     ArrayInit(ArrayInitExpression),
+    ArrayComprehension(ArrayComprehensionExpression),
     ArrayAccess(ArrayAccessExpression),
     ArrayRangeAccess(ArrayRangeAccessExpression),
 
@@ -81,6 +87,86 @@ pub enum Expression {
     CircuitStaticFunctionAccess(CircuitStaticFunctionAccessExpression),
 
     Call(CallExpression),
+
+    /// Serialized as the expression in parentheses, see [`ParenthesizedExpression`].
+    #[serde(skip)]
+    Parenthesized(ParenthesizedExpression),
+
+    /// An expression the parser could not make sense of, see [`ErrNode`].
+    Err(ErrNode),
+}
+
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // `serde_stacker` only grows the stack around sequences and maps, not the fields of an expression.
+        ensure_stack(|| match self {
+            Expression::Parenthesized(parenthesized) => parenthesized.inner.serialize(serializer),
+            expression => Expression::serialize(expression, serializer),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Expression::deserialize(deserializer)
+    }
+}
+
+impl Expression {
+    ///
+    /// Returns the expression inside the parentheses around it, if any.
+    ///
+    pub fn without_parentheses(&self) -> &Expression {
+        let mut expression = self;
+        while let Expression::Parenthesized(parenthesized) = expression {
+            expression = &parenthesized.inner;
+        }
+        expression
+    }
+
+    ///
+    /// Returns how tightly the expression holds together when printed as an operand.
+    ///
+    pub fn precedence(&self) -> Precedence {
+        use Expression::*;
+        match self {
+            Ternary(_) => Precedence::Ternary,
+            Binary(binary) => binary.op.precedence(),
+            Cast(_) => Precedence::Cast,
+            Unary(_) => Precedence::Unary,
+            ArrayAccess(_)
+            | ArrayRangeAccess(_)
+            | TupleAccess(_)
+            | CircuitMemberAccess(_)
+            | CircuitStaticFunctionAccess(_)
+            | Call(_)
+            | LengthOf(_) => Precedence::Postfix,
+            Identifier(_)
+            | Value(_)
+            | ArrayInline(_)
+            | ArrayInit(_)
+            | ArrayComprehension(_)
+            | TupleInit(_)
+            | CircuitInit(_)
+            | Parenthesized(_)
+            | Err(_) => Precedence::Primary,
+        }
+    }
+
+    ///
+    /// Writes the expression as an operand that must hold together at `precedence`, or tighter if `strict`.
+    ///
+    /// An operand that does not is written in parentheses, so that an expression printed
+    /// without the parentheses of the source, such as one built by a pass, parses back the same.
+    ///
+    pub(crate) fn fmt_operand(&self, f: &mut fmt::Formatter, precedence: Precedence, strict: bool) -> fmt::Result {
+        let own = self.precedence();
+        if own < precedence || (strict && own == precedence) {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
 }
 
 impl Node for Expression {
@@ -94,6 +180,7 @@ impl Node for Expression {
             Ternary(n) => n.span(),
             ArrayInline(n) => n.span(),
             ArrayInit(n) => n.span(),
+            ArrayComprehension(n) => n.span(),
             ArrayAccess(n) => n.span(),
             ArrayRangeAccess(n) => n.span(),
             TupleInit(n) => n.span(),
@@ -104,6 +191,8 @@ impl Node for Expression {
             Call(n) => n.span(),
             Cast(n) => n.span(),
             LengthOf(n) => n.span(),
+            Parenthesized(n) => n.span(),
+            Err(n) => n.span(),
         }
     }
 
@@ -117,6 +206,7 @@ impl Node for Expression {
             Ternary(n) => n.set_span(span),
             ArrayInline(n) => n.set_span(span),
             ArrayInit(n) => n.set_span(span),
+            ArrayComprehension(n) => n.set_span(span),
             ArrayAccess(n) => n.set_span(span),
             ArrayRangeAccess(n) => n.set_span(span),
             TupleInit(n) => n.set_span(span),
@@ -127,6 +217,8 @@ impl Node for Expression {
             Call(n) => n.set_span(span),
             Cast(n) => n.set_span(span),
             LengthOf(n) => n.set_span(span),
+            Parenthesized(n) => n.set_span(span),
+            Err(n) => n.set_span(span),
         }
     }
 }
@@ -134,7 +226,7 @@ impl Node for Expression {
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use Expression::*;
-        match &self {
+        ensure_stack(|| match &self {
             Identifier(n) => n.fmt(f),
             Value(n) => n.fmt(f),
             Binary(n) => n.fmt(f),
@@ -142,6 +234,7 @@ impl fmt::Display for Expression {
             Ternary(n) => n.fmt(f),
             ArrayInline(n) => n.fmt(f),
             ArrayInit(n) => n.fmt(f),
+            ArrayComprehension(n) => n.fmt(f),
             ArrayAccess(n) => n.fmt(f),
             ArrayRangeAccess(n) => n.fmt(f),
             TupleInit(n) => n.fmt(f),
@@ -152,6 +245,8 @@ impl fmt::Display for Expression {
             Call(n) => n.fmt(f),
             Cast(n) => n.fmt(f),
             LengthOf(n) => n.fmt(f),
-        }
+            Parenthesized(n) => n.fmt(f),
+            Err(n) => n.fmt(f),
+        })
     }
 }
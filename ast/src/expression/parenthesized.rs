@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+///
+/// An expression in parentheses as written in the source, such as `(a)` in `(a) + b`.
+///
+/// Parentheses only group, so they are kept for printing the program as it was written and are
+/// otherwise seen through: by equality ignoring spans, by type checking, and by the JSON of the AST.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParenthesizedExpression {
+    pub inner: Box<Expression>,
+    pub span: Span,
+}
+
+impl fmt::Display for ParenthesizedExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.inner)
+    }
+}
+
+impl Node for ParenthesizedExpression {
+    fn span(&self) -> &Span {
+        &self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+///
+/// How tightly an expression holds together when it is printed as an operand, from loosest to tightest.
+///
+/// The levels follow the parser, so an operand looser than its operator is printed in parentheses.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Ternary,
+    Or,
+    And,
+    Equality,
+    Ordering,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
+    Additive,
+    Multiplicative,
+    Exponential,
+    Cast,
+    Unary,
+    /// Accesses, calls and `.len()`, which apply to the expression before them.
+    Postfix,
+    /// Names, literals, and expressions that are delimited on both sides.
+    Primary,
+}
@@ -26,7 +26,9 @@ pub struct TernaryExpression {
 
 impl fmt::Display for TernaryExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "if {} ? {} : {}", self.condition, self.if_true, self.if_false)
+        write!(f, "if ")?;
+        self.condition.fmt_operand(f, Precedence::Or, false)?;
+        write!(f, " ? {} : {}", self.if_true, self.if_false)
     }
 }
 
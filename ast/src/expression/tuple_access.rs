@@ -25,7 +25,8 @@ pub struct TupleAccessExpression {
 
 impl fmt::Display for TupleAccessExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}.{}", self.tuple, self.index)
+        self.tuple.fmt_operand(f, Precedence::Postfix, false)?;
+        write!(f, ".{}", self.index)
     }
 }
 
@@ -16,7 +16,7 @@
 
 use super::*;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnaryOperation {
     Not,
     Negate,
@@ -42,7 +42,8 @@ pub struct UnaryExpression {
 
 impl fmt::Display for UnaryExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", self.op.as_ref(), self.inner)
+        write!(f, "{}", self.op.as_ref())?;
+        self.inner.fmt_operand(f, Precedence::Unary, false)
     }
 }
 
@@ -69,7 +69,7 @@ impl Function {
     fn format(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "function {}", self.identifier)?;
 
-        let parameters = self.input.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",");
+        let parameters = self.input.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ");
         let returns = self.output.as_ref().map(|type_| type_.to_string());
         if returns.is_none() {
             write!(f, "({}) {}", parameters, self.block)
@@ -0,0 +1,501 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Equality and hashing of AST nodes that disregard where the nodes were written.
+//!
+//! The derived `PartialEq` of AST nodes compares spans, so the same code parsed from two files,
+//! or from two places in one file, compares unequal. [`IgnoreSpan`] compares and hashes only the
+//! program text. Every struct is destructured in full and every enum matched without a wildcard,
+//! so a field or variant added to the AST fails to compile here until it is handled.
+
+use crate::*;
+use leo_errors::Span;
+
+use std::hash::{Hash, Hasher};
+use tendril::StrTendril;
+
+/// Equality and hashing of AST nodes that leave out their spans.
+pub trait IgnoreSpan {
+    /// Returns `true` if the two nodes are the same code, wherever each of them was written.
+    fn eq_ignore_span(&self, other: &Self) -> bool;
+
+    /// Feeds the node to `state` without its spans.
+    /// Nodes that are equal by [`IgnoreSpan::eq_ignore_span`] hash the same.
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H);
+}
+
+///
+/// A node used as a key by its code alone, so that copies of the same code written in different places
+/// share one entry of a map or set.
+///
+#[derive(Debug)]
+pub struct IgnoringSpans<'a, T>(pub &'a T);
+
+impl<'a, T: IgnoreSpan> PartialEq for IgnoringSpans<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_span(other.0)
+    }
+}
+
+impl<'a, T: IgnoreSpan> Eq for IgnoringSpans<'a, T> {}
+
+impl<'a, T: IgnoreSpan> Hash for IgnoringSpans<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash_ignore_span(state);
+    }
+}
+
+impl IgnoreSpan for Span {
+    fn eq_ignore_span(&self, _other: &Self) -> bool {
+        true
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<T: IgnoreSpan> IgnoreSpan for Box<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        (**self).eq_ignore_span(&**other)
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        (**self).hash_ignore_span(state);
+    }
+}
+
+impl<T: IgnoreSpan> IgnoreSpan for Option<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(left), Some(right)) => left.eq_ignore_span(right),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        self.is_some().hash(state);
+        if let Some(value) = self {
+            value.hash_ignore_span(state);
+        }
+    }
+}
+
+impl<T: IgnoreSpan> IgnoreSpan for Vec<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(left, right)| left.eq_ignore_span(right))
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for value in self.iter() {
+            value.hash_ignore_span(state);
+        }
+    }
+}
+
+/// Implements [`IgnoreSpan`] through `PartialEq` and `Hash`, for types without spans.
+macro_rules! ignore_span_by_value {
+    ($($type_:ty),* $(,)?) => {
+        $(
+            impl IgnoreSpan for $type_ {
+                fn eq_ignore_span(&self, other: &Self) -> bool {
+                    self == other
+                }
+
+                fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+                    self.hash(state);
+                }
+            }
+        )*
+    };
+}
+
+ignore_span_by_value!(
+    bool,
+    String,
+    StrTendril,
+    ArrayDimensions,
+    AssignOperation,
+    BinaryOperation,
+    Char,
+    Declare,
+    IntegerType,
+    PositiveNumber,
+    UnaryOperation,
+);
+
+/// Implements [`IgnoreSpan`] for a struct field by field. All fields must be listed, spans included.
+macro_rules! ignore_span_by_fields {
+    ($($type_:ident { $($field:ident),* $(,)? })*) => {
+        $(
+            impl IgnoreSpan for $type_ {
+                fn eq_ignore_span(&self, other: &Self) -> bool {
+                    let $type_ { $($field),* } = self;
+                    $($field.eq_ignore_span(&other.$field))&&*
+                }
+
+                fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+                    let $type_ { $($field),* } = self;
+                    $($field.hash_ignore_span(state);)*
+                }
+            }
+        )*
+    };
+}
+
+ignore_span_by_fields! {
+    Identifier { name, span }
+    ErrNode { span, text }
+    CharValue { character, span }
+    GroupTuple { x, y, span }
+    SelfKeyword { identifier }
+    ConstSelfKeyword { identifier }
+    MutSelfKeyword { identifier }
+
+    BinaryExpression { left, right, op, span }
+    UnaryExpression { inner, op, span }
+    TernaryExpression { condition, if_true, if_false, span }
+    CastExpression { inner, target_type, span }
+    LengthOfExpression { inner, span }
+    ArrayInlineExpression { elements, span }
+    ArrayInitExpression { element, dimensions, span }
+    ArrayComprehensionExpression { element, variable, start, stop, inclusive, span }
+    ArrayAccessExpression { array, index, span }
+    ArrayRangeAccessExpression { array, left, right, span }
+    TupleInitExpression { elements, span }
+    TupleAccessExpression { tuple, index, span }
+    CircuitImpliedVariableDefinition { identifier, expression }
+    CircuitInitExpression { name, members, span }
+    CircuitMemberAccessExpression { circuit, name, span, type_ }
+    CircuitStaticFunctionAccessExpression { circuit, name, span }
+    CallExpression { function, arguments, span }
+
+    ReturnStatement { expression, span }
+    VariableName { mutable, identifier, span }
+    DefinitionStatement { declaration_type, variable_names, type_, value, span }
+    Assignee { identifier, accesses, span }
+    AssignStatement { operation, assignee, value, span }
+    ConditionalStatement { condition, block, next, span }
+    IterationStatement { variable, start, stop, inclusive, step, block, span }
+    ConsoleArgs { string, parameters, span }
+    ConsoleAssertArgs { left, right, span }
+    ConsoleStatement { function, span }
+    ExpressionStatement { expression, span }
+    Block { statements, span }
+
+    Annotation { span, name, arguments }
+    FunctionInputVariable { identifier, const_, mutable, type_, span }
+    Function { annotations, identifier, input, output, block, span }
+}
+
+impl IgnoreSpan for Expression {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use Expression::*;
+        // Parentheses only group, so an expression equals itself in parentheses.
+        let (this, other) = (self.without_parentheses(), other.without_parentheses());
+        ensure_stack(|| match this {
+            Identifier(left) => matches!(other, Identifier(right) if left.eq_ignore_span(right)),
+            Value(left) => matches!(other, Value(right) if left.eq_ignore_span(right)),
+            Binary(left) => matches!(other, Binary(right) if left.eq_ignore_span(right)),
+            Unary(left) => matches!(other, Unary(right) if left.eq_ignore_span(right)),
+            Ternary(left) => matches!(other, Ternary(right) if left.eq_ignore_span(right)),
+            Cast(left) => matches!(other, Cast(right) if left.eq_ignore_span(right)),
+            LengthOf(left) => matches!(other, LengthOf(right) if left.eq_ignore_span(right)),
+            ArrayInline(left) => matches!(other, ArrayInline(right) if left.eq_ignore_span(right)),
+            ArrayInit(left) => matches!(other, ArrayInit(right) if left.eq_ignore_span(right)),
+            ArrayComprehension(left) => matches!(other, ArrayComprehension(right) if left.eq_ignore_span(right)),
+            ArrayAccess(left) => matches!(other, ArrayAccess(right) if left.eq_ignore_span(right)),
+            ArrayRangeAccess(left) => matches!(other, ArrayRangeAccess(right) if left.eq_ignore_span(right)),
+            TupleInit(left) => matches!(other, TupleInit(right) if left.eq_ignore_span(right)),
+            TupleAccess(left) => matches!(other, TupleAccess(right) if left.eq_ignore_span(right)),
+            CircuitInit(left) => matches!(other, CircuitInit(right) if left.eq_ignore_span(right)),
+            CircuitMemberAccess(left) => matches!(other, CircuitMemberAccess(right) if left.eq_ignore_span(right)),
+            CircuitStaticFunctionAccess(left) => {
+                matches!(other, CircuitStaticFunctionAccess(right) if left.eq_ignore_span(right))
+            }
+            Call(left) => matches!(other, Call(right) if left.eq_ignore_span(right)),
+            Parenthesized(_) => unreachable!("parentheses are seen through"),
+            Err(left) => matches!(other, Err(right) if left.eq_ignore_span(right)),
+        })
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use Expression::*;
+        let this = self.without_parentheses();
+        std::mem::discriminant(this).hash(state);
+        ensure_stack(|| match this {
+            Identifier(expression) => expression.hash_ignore_span(state),
+            Value(expression) => expression.hash_ignore_span(state),
+            Binary(expression) => expression.hash_ignore_span(state),
+            Unary(expression) => expression.hash_ignore_span(state),
+            Ternary(expression) => expression.hash_ignore_span(state),
+            Cast(expression) => expression.hash_ignore_span(state),
+            LengthOf(expression) => expression.hash_ignore_span(state),
+            ArrayInline(expression) => expression.hash_ignore_span(state),
+            ArrayInit(expression) => expression.hash_ignore_span(state),
+            ArrayComprehension(expression) => expression.hash_ignore_span(state),
+            ArrayAccess(expression) => expression.hash_ignore_span(state),
+            ArrayRangeAccess(expression) => expression.hash_ignore_span(state),
+            TupleInit(expression) => expression.hash_ignore_span(state),
+            TupleAccess(expression) => expression.hash_ignore_span(state),
+            CircuitInit(expression) => expression.hash_ignore_span(state),
+            CircuitMemberAccess(expression) => expression.hash_ignore_span(state),
+            CircuitStaticFunctionAccess(expression) => expression.hash_ignore_span(state),
+            Call(expression) => expression.hash_ignore_span(state),
+            Parenthesized(_) => unreachable!("parentheses are seen through"),
+            Err(expression) => expression.hash_ignore_span(state),
+        })
+    }
+}
+
+impl IgnoreSpan for ValueExpression {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use ValueExpression::*;
+        match self {
+            Address(left, _) => matches!(other, Address(right, _) if left == right),
+            Boolean(left, _) => matches!(other, Boolean(right, _) if left == right),
+            Char(left) => matches!(other, Char(right) if left.eq_ignore_span(right)),
+            Field(left, _) => matches!(other, Field(right, _) if left == right),
+            Group(left) => matches!(other, Group(right) if left.eq_ignore_span(right)),
+            Implicit(left, _) => matches!(other, Implicit(right, _) if left == right),
+            Integer(left_type, left, _) => {
+                matches!(other, Integer(right_type, right, _) if left_type == right_type && left == right)
+            }
+            String(left, _) => matches!(other, String(right, _) if left == right),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use ValueExpression::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Address(value, _) | Boolean(value, _) | Field(value, _) | Implicit(value, _) => value.hash(state),
+            Char(value) => value.hash_ignore_span(state),
+            Group(value) => value.hash_ignore_span(state),
+            Integer(type_, value, _) => {
+                type_.hash(state);
+                value.hash(state);
+            }
+            String(value, _) => value.hash(state),
+        }
+    }
+}
+
+impl IgnoreSpan for GroupValue {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match self {
+            GroupValue::Single(left, _) => matches!(other, GroupValue::Single(right, _) if left == right),
+            GroupValue::Tuple(left) => matches!(other, GroupValue::Tuple(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            GroupValue::Single(value, _) => value.hash(state),
+            GroupValue::Tuple(tuple) => tuple.hash_ignore_span(state),
+        }
+    }
+}
+
+impl IgnoreSpan for GroupCoordinate {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use GroupCoordinate::*;
+        match self {
+            Number(left, _) => matches!(other, Number(right, _) if left == right),
+            SignHigh => matches!(other, SignHigh),
+            SignLow => matches!(other, SignLow),
+            Inferred => matches!(other, Inferred),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            GroupCoordinate::Number(value, _) => value.hash(state),
+            GroupCoordinate::SignHigh | GroupCoordinate::SignLow | GroupCoordinate::Inferred => (),
+        }
+    }
+}
+
+impl IgnoreSpan for SpreadOrExpression {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use SpreadOrExpression::*;
+        match self {
+            Spread(left) => matches!(other, Spread(right) if left.eq_ignore_span(right)),
+            Expression(left) => matches!(other, Expression(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            SpreadOrExpression::Spread(expression) | SpreadOrExpression::Expression(expression) => {
+                expression.hash_ignore_span(state)
+            }
+        }
+    }
+}
+
+impl IgnoreSpan for Statement {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use Statement::*;
+        ensure_stack(|| match self {
+            Return(left) => matches!(other, Return(right) if left.eq_ignore_span(right)),
+            Definition(left) => matches!(other, Definition(right) if left.eq_ignore_span(right)),
+            Assign(left) => matches!(other, Assign(right) if left.eq_ignore_span(right)),
+            Conditional(left) => matches!(other, Conditional(right) if left.eq_ignore_span(right)),
+            Iteration(left) => matches!(other, Iteration(right) if left.eq_ignore_span(right)),
+            Console(left) => matches!(other, Console(right) if left.eq_ignore_span(right)),
+            Expression(left) => matches!(other, Expression(right) if left.eq_ignore_span(right)),
+            Block(left) => matches!(other, Block(right) if left.eq_ignore_span(right)),
+        })
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use Statement::*;
+        std::mem::discriminant(self).hash(state);
+        ensure_stack(|| match self {
+            Return(statement) => statement.hash_ignore_span(state),
+            Definition(statement) => statement.hash_ignore_span(state),
+            Assign(statement) => statement.hash_ignore_span(state),
+            Conditional(statement) => statement.hash_ignore_span(state),
+            Iteration(statement) => statement.hash_ignore_span(state),
+            Console(statement) => statement.hash_ignore_span(state),
+            Expression(statement) => statement.hash_ignore_span(state),
+            Block(statement) => statement.hash_ignore_span(state),
+        })
+    }
+}
+
+impl IgnoreSpan for AssigneeAccess {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use AssigneeAccess::*;
+        match self {
+            ArrayRange(left_start, left_stop) => matches!(
+                other,
+                ArrayRange(right_start, right_stop)
+                    if left_start.eq_ignore_span(right_start) && left_stop.eq_ignore_span(right_stop)
+            ),
+            ArrayIndex(left) => matches!(other, ArrayIndex(right) if left.eq_ignore_span(right)),
+            Tuple(left, _) => matches!(other, Tuple(right, _) if left == right),
+            Member(left) => matches!(other, Member(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use AssigneeAccess::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ArrayRange(start, stop) => {
+                start.hash_ignore_span(state);
+                stop.hash_ignore_span(state);
+            }
+            ArrayIndex(index) => index.hash_ignore_span(state),
+            Tuple(index, _) => index.hash(state),
+            Member(name) => name.hash_ignore_span(state),
+        }
+    }
+}
+
+impl IgnoreSpan for ConsoleFunction {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use ConsoleFunction::*;
+        match self {
+            Assert(left) => matches!(other, Assert(right) if left.eq_ignore_span(right)),
+            AssertEq(left) => matches!(other, AssertEq(right) if left.eq_ignore_span(right)),
+            AssertNe(left) => matches!(other, AssertNe(right) if left.eq_ignore_span(right)),
+            Error(left) => matches!(other, Error(right) if left.eq_ignore_span(right)),
+            Log(left) => matches!(other, Log(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use ConsoleFunction::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Assert(expression) => expression.hash_ignore_span(state),
+            AssertEq(args) | AssertNe(args) => args.hash_ignore_span(state),
+            Error(args) | Log(args) => args.hash_ignore_span(state),
+        }
+    }
+}
+
+impl IgnoreSpan for Type {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use Type::*;
+        match self {
+            Address => matches!(other, Address),
+            Boolean => matches!(other, Boolean),
+            Char => matches!(other, Char),
+            Field => matches!(other, Field),
+            Group => matches!(other, Group),
+            IntegerType(left) => matches!(other, IntegerType(right) if left == right),
+            Array(left, left_dimensions) => matches!(
+                other,
+                Array(right, right_dimensions)
+                    if left.eq_ignore_span(right) && left_dimensions == right_dimensions
+            ),
+            Tuple(left) => matches!(other, Tuple(right) if left.eq_ignore_span(right)),
+            Identifier(left) => matches!(other, Identifier(right) if left.eq_ignore_span(right)),
+            SelfType => matches!(other, SelfType),
+            Err(left) => matches!(other, Err(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use Type::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Address | Boolean | Char | Field | Group | SelfType => (),
+            IntegerType(type_) => type_.hash(state),
+            Array(type_, dimensions) => {
+                type_.hash_ignore_span(state);
+                dimensions.hash(state);
+            }
+            Tuple(types) => types.hash_ignore_span(state),
+            Identifier(name) => name.hash_ignore_span(state),
+            Err(err) => err.hash_ignore_span(state),
+        }
+    }
+}
+
+impl IgnoreSpan for FunctionInput {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        use FunctionInput::*;
+        match self {
+            SelfKeyword(left) => matches!(other, SelfKeyword(right) if left.eq_ignore_span(right)),
+            ConstSelfKeyword(left) => matches!(other, ConstSelfKeyword(right) if left.eq_ignore_span(right)),
+            MutSelfKeyword(left) => matches!(other, MutSelfKeyword(right) if left.eq_ignore_span(right)),
+            Variable(left) => matches!(other, Variable(right) if left.eq_ignore_span(right)),
+        }
+    }
+
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        use FunctionInput::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            SelfKeyword(keyword) => keyword.hash_ignore_span(state),
+            ConstSelfKeyword(keyword) => keyword.hash_ignore_span(state),
+            MutSelfKeyword(keyword) => keyword.hash_ignore_span(state),
+            Variable(variable) => variable.hash_ignore_span(state),
+        }
+    }
+}
@@ -20,11 +20,15 @@ use leo_input::{
     InputParserError,
 };
 
+use indexmap::IndexSet;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Input {
     name: String,
     program_input: ProgramInput,
     program_state: ProgramState,
+    /// The names of the entries that are in the input file but failed to parse.
+    malformed: IndexSet<String>,
 }
 
 impl Default for Input {
@@ -33,6 +37,7 @@ impl Default for Input {
             name: "default".to_owned(),
             program_input: ProgramInput::new(),
             program_state: ProgramState::new(),
+            malformed: IndexSet::new(),
         }
     }
 }
@@ -53,6 +58,7 @@ impl Input {
             name: self.name.clone(),
             program_input: input,
             program_state: state,
+            malformed: self.malformed.clone(),
         }
     }
 
@@ -80,6 +86,55 @@ impl Input {
         Ok(())
     }
 
+    ///
+    /// Parses all input variables included in a file and stores them in `self`, recovering from errors.
+    ///
+    /// Malformed entries, and every entry of a section that is not valid, are left out and marked as
+    /// malformed. Returns the errors of all of them.
+    ///
+    pub fn parse_input_recovering(&mut self, file: File) -> Vec<InputParserError> {
+        let mut errors = vec![];
+        for entry in file.entries.into_iter() {
+            let section = match entry {
+                TableOrSection::Section(section) => section,
+                TableOrSection::Table(table) => {
+                    errors.push(InputParserError::table(table));
+                    continue;
+                }
+            };
+            let names = section
+                .definitions
+                .iter()
+                .map(|definition| definition.parameter.variable.value.clone())
+                .collect::<Vec<_>>();
+
+            match self.program_input.parse_recovering(section) {
+                Ok(failed) => {
+                    for (name, error) in failed {
+                        self.mark_malformed(name);
+                        errors.push(error);
+                    }
+                }
+                Err(error) => {
+                    names.into_iter().for_each(|name| self.mark_malformed(name));
+                    errors.push(error);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Marks the entry with the given `name` as present in the input file but malformed.
+    pub fn mark_malformed(&mut self, name: String) {
+        self.malformed.insert(name);
+    }
+
+    /// Returns `true` if the input file has an entry with the given `name` that failed to parse.
+    pub fn is_malformed(&self, name: &str) -> bool {
+        self.malformed.contains(name)
+    }
+
     /// Parse all state variables included in a file and store them in `self`.
     pub fn parse_state(&mut self, file: File) -> Result<(), InputParserError> {
         for entry in file.entries.into_iter() {
@@ -0,0 +1,218 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The entries of an input file as written, with their documentation, for tools that read or generate input files.
+
+use crate::{write_json_pretty, ArtifactFormat};
+use leo_errors::{AstError, Result};
+use leo_input::{definitions::Definition, files::TableOrSection, sections::Section, tables::Table, LeoInputParser};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The format of input AST snapshots, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const INPUT_AST_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "input ast",
+    version: 1,
+    json: true,
+};
+
+///
+/// The sections and tables of an input file, in the order they are written.
+///
+/// Types and values are kept as written. A `///` comment on the lines right before a table, a section,
+/// or an entry is its description. Other comments are left out.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAst {
+    pub items: Vec<InputItem>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputItem {
+    Section(InputSection),
+    Table(InputTable),
+}
+
+/// A `[[public]]` or `[[private]]` table and its sections.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputTable {
+    pub description: Option<String>,
+    pub visibility: String,
+    pub sections: Vec<InputSection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputSection {
+    pub description: Option<String>,
+    pub header: String,
+    pub entries: Vec<InputEntry>,
+}
+
+/// A `name: type = value;` entry of a section.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputEntry {
+    pub description: Option<String>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: String,
+}
+
+impl InputAst {
+    /// Parses the input file, keeping the description of each of its tables, sections, and entries.
+    pub fn parse(source: &str) -> Result<Self> {
+        let file = LeoInputParser::parse_file(source)?;
+        let items = file
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                TableOrSection::Section(section) => InputItem::Section(InputSection::new(section, source)),
+                TableOrSection::Table(table) => InputItem::Table(InputTable::new(table, source)),
+            })
+            .collect();
+
+        Ok(Self { items })
+    }
+
+    ///
+    /// Returns the text of an input file with these tables, sections, and entries.
+    ///
+    /// Parsing the text gives back an equal input AST.
+    ///
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            match item {
+                InputItem::Section(section) => section.write_text(&mut text),
+                InputItem::Table(table) => table.write_text(&mut text),
+            }
+        }
+        text
+    }
+
+    /// Serializes the input AST into JSON bytes.
+    pub fn to_json_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        write_json_pretty(&mut bytes, self).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?;
+        Ok(bytes)
+    }
+}
+
+impl InputTable {
+    fn new(table: &Table, source: &str) -> Self {
+        Self {
+            description: description(source, table.span.start()),
+            visibility: table.visibility.to_string(),
+            sections: table
+                .sections
+                .iter()
+                .map(|section| InputSection::new(section, source))
+                .collect(),
+        }
+    }
+
+    fn write_text(&self, text: &mut String) {
+        write_description(text, &self.description);
+        writeln!(text, "[[{}]]", self.visibility).unwrap();
+        for section in self.sections.iter() {
+            text.push('\n');
+            section.write_text(text);
+        }
+    }
+}
+
+impl InputSection {
+    fn new(section: &Section, source: &str) -> Self {
+        Self {
+            description: description(source, section.span.start()),
+            header: section.header.to_string(),
+            entries: section
+                .definitions
+                .iter()
+                .map(|definition| InputEntry::new(definition, source))
+                .collect(),
+        }
+    }
+
+    fn write_text(&self, text: &mut String) {
+        write_description(text, &self.description);
+        writeln!(text, "[{}]", self.header).unwrap();
+        for entry in self.entries.iter() {
+            write_description(text, &entry.description);
+            writeln!(text, "{}: {} = {};", entry.name, entry.type_, entry.value).unwrap();
+        }
+    }
+}
+
+impl InputEntry {
+    fn new(definition: &Definition, source: &str) -> Self {
+        let parameter = &definition.parameter;
+        // The type is written after the `:` that follows the name.
+        let type_ = &source[parameter.variable.span.end()..parameter.span.end()];
+        let type_ = type_.trim_start().trim_start_matches(':').trim();
+
+        Self {
+            description: description(source, definition.span.start()),
+            name: parameter.variable.value.clone(),
+            type_: type_.to_string(),
+            value: definition.expression.span().as_str().to_string(),
+        }
+    }
+}
+
+///
+/// Returns the `///` comment on the lines right before the node starting at `start`, without the slashes.
+///
+/// A node that does not start its line, such as the second entry of a line, has no description.
+///
+fn description(source: &str, start: usize) -> Option<String> {
+    let line_start = source[..start].rfind('\n').map(|newline| newline + 1).unwrap_or(0);
+    if line_start == 0 || !source[line_start..start].trim().is_empty() {
+        return None;
+    }
+
+    let mut lines = source[..line_start - 1]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .take_while(|line| line.starts_with("///"))
+        .map(|line| {
+            let line = &line[3..];
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+fn write_description(text: &mut String, description: &Option<String>) {
+    if let Some(description) = description {
+        for line in description.split('\n') {
+            if line.is_empty() {
+                text.push_str("///\n");
+            } else {
+                writeln!(text, "/// {}", line).unwrap();
+            }
+        }
+    }
+}
@@ -56,15 +56,24 @@ macro_rules! record_input_section {
                 self.is_present = true;
 
                 for definition in definitions {
-                    let value = InputValue::from_expression(definition.parameter.type_.clone(), definition.expression)?;
-                    let parameter = Parameter::from(definition.parameter);
-
-                    self.values.insert(parameter, Some(value));
+                    self.parse_definition(definition)?;
                 }
 
                 Ok(())
             }
 
+            /// Parses a single definition of the section and stores it in `self`.
+            pub fn parse_definition(&mut self, definition: Definition) -> Result<(), InputParserError> {
+                self.is_present = true;
+
+                let value = InputValue::from_expression(definition.parameter.type_.clone(), definition.expression)?;
+                let parameter = Parameter::from(definition.parameter);
+
+                self.values.insert(parameter, Some(value));
+
+                Ok(())
+            }
+
             /// Returns this section's [IndexMap] of values.
             pub fn values(&self) -> IndexMap<Parameter, Option<InputValue>> {
                 self.values.clone()
@@ -114,15 +123,22 @@ macro_rules! main_input_section {
             /// Parses main input definitions and stores them in `self`.
             pub fn parse(&mut self, definitions: Vec<Definition>) -> Result<(), InputParserError> {
                 for definition in definitions {
-                    let name = definition.parameter.variable.value;
-                    let value = InputValue::from_expression(definition.parameter.type_, definition.expression)?;
-
-                    self.insert(name, Some(value));
+                    self.parse_definition(definition)?;
                 }
 
                 Ok(())
             }
 
+            /// Parses a single main input definition and stores it in `self`.
+            pub fn parse_definition(&mut self, definition: Definition) -> Result<(), InputParserError> {
+                let name = definition.parameter.variable.value;
+                let value = InputValue::from_expression(definition.parameter.type_, definition.expression)?;
+
+                self.insert(name, Some(value));
+
+                Ok(())
+            }
+
             /// Returns an `Option` of the main function input at `name`.
             pub fn get(&self, name: &str) -> Option<Option<InputValue>> {
                 self.input.get(name).cloned()
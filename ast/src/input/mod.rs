@@ -23,6 +23,9 @@ pub use macros::*;
 pub mod input;
 pub use input::*;
 
+pub mod input_ast;
+pub use input_ast::*;
+
 pub mod input_value;
 pub use input_value::*;
 
@@ -16,6 +16,7 @@
 
 use crate::{ConstantInput, InputValue, MainInput, Registers};
 use leo_input::{
+    definitions::Definition,
     sections::{Header, Section},
     InputParserError,
 };
@@ -72,6 +73,34 @@ impl ProgramInput {
         }
     }
 
+    ///
+    /// Parses each definition of a section and stores it in `self`, recovering from malformed definitions.
+    ///
+    /// Returns the name and error of every definition that failed to parse.
+    /// Sections that are not valid in an input file fail as a whole.
+    ///
+    pub fn parse_recovering(&mut self, section: Section) -> Result<Vec<(String, InputParserError)>, InputParserError> {
+        let parse: fn(&mut Self, Definition) -> Result<(), InputParserError> = match section.header {
+            Header::Constants(_constants) => |input, definition| input.constants.parse_definition(definition),
+            Header::Main(_main) => |input, definition| input.main.parse_definition(definition),
+            Header::Registers(_registers) => {
+                self.registers.parse(vec![])?;
+                |input, definition| input.registers.parse_definition(definition)
+            }
+            header => return Err(InputParserError::input_section_header(header)),
+        };
+
+        let mut failed = vec![];
+        for definition in section.definitions {
+            let name = definition.parameter.variable.value.clone();
+            if let Err(error) = parse(self, definition) {
+                failed.push((name, error));
+            }
+        }
+
+        Ok(failed)
+    }
+
     /// Returns the main function input value with the given `name`
     #[allow(clippy::ptr_arg)]
     pub fn get(&self, name: &str) -> Option<Option<InputValue>> {
@@ -28,6 +28,8 @@ pub use self::aliases::*;
 pub mod annotation;
 pub use self::annotation::*;
 
+pub mod builder;
+
 pub mod circuits;
 pub use self::circuits::*;
 
@@ -37,6 +39,9 @@ pub use self::chars::*;
 pub mod common;
 pub use self::common::*;
 
+pub mod edition;
+pub use self::edition::*;
+
 pub mod expression;
 pub use self::expression::*;
 
@@ -46,12 +51,24 @@ pub use self::functions::*;
 pub mod groups;
 pub use self::groups::*;
 
+pub mod ignore_span;
+pub use self::ignore_span::*;
+
 pub mod imports;
 pub use self::imports::*;
 
 pub mod input;
 pub use self::input::*;
 
+pub mod memory;
+pub use self::memory::*;
+
+pub mod metrics;
+pub use self::metrics::{AstMetrics, FunctionMetrics};
+
+pub mod name_generator;
+pub use self::name_generator::*;
+
 pub mod pass;
 pub use self::pass::*;
 
@@ -64,14 +81,32 @@ pub use self::reducer::*;
 pub mod statements;
 pub use self::statements::*;
 
+pub mod traversal;
+pub use self::traversal::*;
+
 pub mod types;
 pub use self::types::*;
 
+pub mod validate;
+pub use self::validate::*;
+
+pub mod version;
+pub use self::version::*;
+
 mod node;
 pub use node::*;
 
 use leo_errors::{AstError, Result};
 
+use serde::{Deserialize, Serialize};
+
+/// The format of AST snapshots, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const AST_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "ast",
+    version: 1,
+    json: true,
+};
+
 /// The abstract syntax tree (AST) for a Leo program.
 ///
 /// The [`Ast`] type represents a Leo program as a series of recursive data types.
@@ -100,7 +135,8 @@ impl Ast {
 
     /// Serializes the ast into a JSON string.
     pub fn to_json_string(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(&self.ast).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?)
+        let bytes = self.to_json_bytes()?;
+        Ok(String::from_utf8(bytes).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?)
     }
 
     // Converts the ast into a JSON value.
@@ -108,19 +144,44 @@ impl Ast {
     // since we modify JSON values leaving them unable to be converted
     // back into Programs.
     pub fn to_json_value(&self) -> Result<serde_json::Value> {
-        Ok(serde_json::to_value(&self.ast).map_err(|e| AstError::failed_to_convert_ast_to_json_value(&e))?)
+        Ok(self
+            .ast
+            .serialize(serde_stacker::Serializer::new(serde_json::value::Serializer))
+            .map_err(|e| AstError::failed_to_convert_ast_to_json_value(&e))?)
     }
 
     /// Serializes the ast into a JSON file.
+    #[cfg(feature = "std")]
     pub fn to_json_file(&self, mut path: std::path::PathBuf, file_name: &str) -> Result<()> {
         path.push(file_name);
         let file = std::fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
         let writer = std::io::BufWriter::new(file);
-        Ok(serde_json::to_writer_pretty(writer, &self.ast)
-            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+        Ok(write_json_pretty(writer, &self.ast).map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+    }
+
+    /// Serializes the ast into JSON bytes, removing keys from object mappings.
+    pub fn to_json_bytes_without_keys(&self, excluded_keys: &[&str]) -> Result<Vec<u8>> {
+        let mut value = self.to_json_value()?;
+        for key in excluded_keys {
+            value = remove_key_from_json(value, key);
+        }
+        value = normalize_json_value(value);
+
+        let mut bytes = Vec::new();
+        write_json_pretty(&mut bytes, &value).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?;
+        drop_json_value(value);
+        Ok(bytes)
+    }
+
+    /// Serializes the ast into JSON bytes.
+    pub fn to_json_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        write_json_pretty(&mut bytes, &self.ast).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?;
+        Ok(bytes)
     }
 
     /// Serializes the ast into a JSON value and removes keys from object mappings before writing to a file.
+    #[cfg(feature = "std")]
     pub fn to_json_file_without_keys(
         &self,
         mut path: std::path::PathBuf,
@@ -128,26 +189,25 @@ impl Ast {
         excluded_keys: &[&str],
     ) -> Result<()> {
         path.push(file_name);
-        let file = std::fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
-        let writer = std::io::BufWriter::new(file);
-
-        let mut value = self.to_json_value().unwrap();
-        for key in excluded_keys {
-            value = remove_key_from_json(value, key);
-        }
-        value = normalize_json_value(value);
-
-        Ok(serde_json::to_writer_pretty(writer, &value)
-            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+        let bytes = self.to_json_bytes_without_keys(excluded_keys)?;
+        std::fs::write(&path, bytes).map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?;
+        Ok(())
     }
 
-    /// Deserializes the JSON string into a ast.
+    /// Deserializes the JSON string into a ast, checking its version stamp if it has one
+    /// and that its expected input matches the parameters of `main`.
     pub fn from_json_string(json: &str) -> Result<Self> {
-        let ast: Program = serde_json::from_str(json).map_err(|e| AstError::failed_to_read_json_string_to_ast(&e))?;
+        AST_FORMAT.check_json(json)?;
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        deserializer.disable_recursion_limit();
+        let ast = Program::deserialize(serde_stacker::Deserializer::new(&mut deserializer))
+            .map_err(|e| AstError::failed_to_read_json_string_to_ast(&e))?;
+        ast.check_expected_input()?;
         Ok(Self { ast })
     }
 
     /// Deserializes the JSON string into a ast from a file.
+    #[cfg(feature = "std")]
     pub fn from_json_file(path: std::path::PathBuf) -> Result<Self> {
         let data = std::fs::read_to_string(&path).map_err(|e| AstError::failed_to_read_json_file(&path, &e))?;
         Self::from_json_string(&data)
@@ -160,9 +220,27 @@ impl AsRef<Program> for Ast {
     }
 }
 
+/// Helper function to pretty print JSON without overflowing the stack on deeply nested values.
+fn write_json_pretty<W: std::io::Write, T: Serialize>(writer: W, value: &T) -> serde_json::Result<()> {
+    let mut serializer = serde_json::Serializer::pretty(writer);
+    value.serialize(serde_stacker::Serializer::new(&mut serializer))
+}
+
+/// Helper function to drop a deeply nested JSON value without overflowing the stack.
+fn drop_json_value(value: serde_json::Value) {
+    let mut pending = vec![value];
+    while let Some(value) = pending.pop() {
+        match value {
+            serde_json::Value::Object(map) => pending.extend(map.into_iter().map(|(_, v)| v)),
+            serde_json::Value::Array(values) => pending.extend(values),
+            _ => (),
+        }
+    }
+}
+
 /// Helper function to recursively filter keys from AST JSON
 fn remove_key_from_json(value: serde_json::Value, key: &str) -> serde_json::Value {
-    match value {
+    ensure_stack(|| match value {
         serde_json::Value::Object(map) => serde_json::Value::Object(
             map.into_iter()
                 .filter(|(k, _)| k != key)
@@ -173,7 +251,7 @@ fn remove_key_from_json(value: serde_json::Value, key: &str) -> serde_json::Valu
             serde_json::Value::Array(values.into_iter().map(|v| remove_key_from_json(v, key)).collect())
         }
         _ => value,
-    }
+    })
 }
 
 /// Helper function to normalize AST JSON into a form compatible with tgc.
@@ -183,7 +261,7 @@ fn remove_key_from_json(value: serde_json::Value, key: &str) -> serde_json::Valu
 /// 2. If there are two elements in a JSON array and one is an empty object
 ///     mapping and the other is not, then lift up the one that isn't
 fn normalize_json_value(value: serde_json::Value) -> serde_json::Value {
-    match value {
+    ensure_stack(|| match value {
         serde_json::Value::Array(vec) => {
             let orig_length = vec.len();
             let mut new_vec: Vec<serde_json::Value> = vec
@@ -202,5 +280,5 @@ fn normalize_json_value(value: serde_json::Value) -> serde_json::Value {
             serde_json::Value::Object(map.into_iter().map(|(k, v)| (k, normalize_json_value(v))).collect())
         }
         _ => value,
-    }
+    })
 }
@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Approximate accounting of the memory held by an AST.
+//!
+//! The numbers are meant to show which part of a program is large, not to be byte-perfect:
+//! every node is counted at the size of its enum, heap allocations are counted at their length
+//! rather than their capacity, and span text, which nodes share with the source, is not counted.
+
+use crate::*;
+use leo_errors::Result;
+
+use indexmap::IndexSet;
+use std::mem::size_of;
+
+/// The distinct identifier names of a program.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SymbolStatistics {
+    /// The number of distinct names.
+    pub count: usize,
+    /// The total length of the distinct names, in bytes.
+    pub bytes: usize,
+}
+
+impl Ast {
+    ///
+    /// Returns the approximate number of bytes held by the program and its imports.
+    ///
+    pub fn deep_size(&self) -> Result<usize> {
+        Ok(MemoryCounter::count(self.as_repr())?.bytes)
+    }
+
+    ///
+    /// Returns the number and total length of the distinct identifier names of the program and its imports.
+    ///
+    pub fn symbol_statistics(&self) -> Result<SymbolStatistics> {
+        let names = MemoryCounter::count(self.as_repr())?.names;
+        Ok(SymbolStatistics {
+            count: names.len(),
+            bytes: names.iter().map(String::len).sum(),
+        })
+    }
+}
+
+/// Sums the sizes of the nodes of a program while reconstructing it unchanged.
+#[derive(Default)]
+struct MemoryCounter {
+    bytes: usize,
+    names: IndexSet<String>,
+    in_circuit: bool,
+}
+
+impl MemoryCounter {
+    fn count(program: &Program) -> Result<Self> {
+        let mut director = ReconstructingDirector::new(MemoryCounter::default()).with_max_depth(usize::MAX);
+        director.reduce_program(program)?;
+        Ok(director.reducer())
+    }
+}
+
+impl ReconstructingReducer for MemoryCounter {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_expression(&mut self, _expression: &Expression, new: Expression) -> Result<Expression> {
+        self.bytes += size_of::<Expression>();
+        Ok(new)
+    }
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        self.bytes += size_of::<Identifier>() + identifier.name.len();
+        self.names.insert(identifier.name.to_string());
+        Ok(identifier.clone())
+    }
+
+    fn reduce_value(&mut self, value: &ValueExpression, new: Expression) -> Result<Expression> {
+        self.bytes += match value {
+            ValueExpression::Address(text, _)
+            | ValueExpression::Boolean(text, _)
+            | ValueExpression::Field(text, _)
+            | ValueExpression::Implicit(text, _)
+            | ValueExpression::Integer(_, text, _) => text.len(),
+            ValueExpression::Group(_) => size_of::<GroupValue>(),
+            ValueExpression::String(chars, _) => chars.len() * size_of::<Char>(),
+            ValueExpression::Char(_) => 0,
+        };
+        Ok(new)
+    }
+
+    fn reduce_statement(&mut self, _statement: &Statement, new: Statement) -> Result<Statement> {
+        self.bytes += size_of::<Statement>();
+        Ok(new)
+    }
+
+    fn reduce_function_input(&mut self, _input: &FunctionInput, new: FunctionInput) -> Result<FunctionInput> {
+        self.bytes += size_of::<FunctionInput>();
+        Ok(new)
+    }
+
+    fn reduce_circuit_member(&mut self, _circuit_member: &CircuitMember, new: CircuitMember) -> Result<CircuitMember> {
+        self.bytes += size_of::<CircuitMember>();
+        Ok(new)
+    }
+
+    fn reduce_function(
+        &mut self,
+        function: &Function,
+        identifier: Identifier,
+        annotations: Vec<Annotation>,
+        input: Vec<FunctionInput>,
+        output: Option<Type>,
+        block: Block,
+    ) -> Result<Function> {
+        self.bytes += size_of::<Function>() + annotations.len() * size_of::<Annotation>();
+        Ok(Function {
+            identifier,
+            annotations,
+            input,
+            output,
+            block,
+            span: function.span.clone(),
+        })
+    }
+}
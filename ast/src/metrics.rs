@@ -0,0 +1,261 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Structural numbers about a program, gathered in a single walk for limits, statistics and reporting.
+
+use crate::{
+    AstIter, AstNode, AstRoot, CircuitMember, Expression, Function, FunctionInput, Program, Statement, Traversal, Type,
+};
+
+use indexmap::IndexMap;
+
+/// The size of the body of a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FunctionMetrics {
+    pub statements: usize,
+    pub expressions: usize,
+}
+
+///
+/// Counts and depths of the statements and expressions of a program.
+///
+/// Only the program itself is measured, not its imports.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AstMetrics {
+    /// The number of expressions of each kind, keyed by variant name such as `Binary`.
+    pub expressions: IndexMap<&'static str, usize>,
+    /// The number of statements of each kind, keyed by variant name such as `Definition`.
+    pub statements: IndexMap<&'static str, usize>,
+    /// The size of every function, keyed by its name, or `Circuit::function` for circuit member functions.
+    pub functions: IndexMap<String, FunctionMetrics>,
+    /// The most expressions nested in one another, counting an expression that is not an operand as depth 1.
+    pub max_expression_depth: usize,
+    /// The most blocks nested in one another, counting the statements of a function body as depth 1.
+    pub max_block_depth: usize,
+    /// The number of identifiers referred to by expressions.
+    pub identifiers: usize,
+    /// The number of placeholders for the expressions and types the parser could not make sense of,
+    /// which are not counted as expressions.
+    pub error_nodes: usize,
+}
+
+impl AstMetrics {
+    /// Returns the number of expressions of the program.
+    pub fn expression_count(&self) -> usize {
+        self.expressions.values().sum()
+    }
+
+    /// Returns the number of statements of the program.
+    pub fn statement_count(&self) -> usize {
+        self.statements.values().sum()
+    }
+}
+
+/// A node on the path to the node being measured, with the depth it is at.
+struct Visited<'a> {
+    node: AstNode<'a>,
+    expression_depth: usize,
+    block_depth: usize,
+}
+
+/// Measures the program in one walk over its statements and expressions.
+pub fn collect(program: &Program) -> AstMetrics {
+    let mut metrics = AstMetrics::default();
+    for (name, function) in program.functions.iter() {
+        metrics
+            .functions
+            .insert(name.name.to_string(), FunctionMetrics::default());
+        metrics.error_nodes += signature_errors(function);
+    }
+    for (name, circuit) in program.circuits.iter() {
+        for member in circuit.members.iter() {
+            match member {
+                CircuitMember::CircuitFunction(function) => {
+                    metrics.functions.insert(
+                        member_function(&name.name, &function.identifier.name),
+                        FunctionMetrics::default(),
+                    );
+                    metrics.error_nodes += signature_errors(function);
+                }
+                CircuitMember::CircuitVariable(_, type_) | CircuitMember::CircuitStatic(_, type_, _) => {
+                    metrics.error_nodes += type_errors(type_);
+                }
+            }
+        }
+    }
+
+    let mut root = None;
+    let mut function = None;
+    let mut ancestors: Vec<Visited> = vec![];
+    for (node, path) in AstIter::program(program, Traversal::PreOrder) {
+        if root.as_ref() != Some(&path.root) {
+            function = match &path.root {
+                AstRoot::Function(name) => Some(name.clone()),
+                AstRoot::CircuitFunction(circuit, name) => Some(member_function(circuit, name)),
+                AstRoot::CircuitStatic(..) | AstRoot::GlobalConst(_) => None,
+            };
+            root = Some(path.root.clone());
+            ancestors.clear();
+        }
+        // The walk is depth first, so the nodes left on the stack are the ancestors of this one.
+        ancestors.truncate(path.indices.len() - 1);
+        let parent = ancestors.last();
+        let sizes = function.as_ref().and_then(|name| metrics.functions.get_mut(name));
+
+        let visited = match node {
+            // Placeholders stand for no expression in particular, so they are only counted as errors.
+            AstNode::Expression(Expression::Err(_)) => {
+                metrics.error_nodes += 1;
+                Visited {
+                    node,
+                    expression_depth: 0,
+                    block_depth: parent.map(|parent| parent.block_depth).unwrap_or_default(),
+                }
+            }
+            // Parentheses only group, so they are neither counted nor nest the expression inside them.
+            AstNode::Expression(Expression::Parenthesized(_)) => Visited {
+                node,
+                expression_depth: match parent {
+                    Some(Visited {
+                        node: AstNode::Expression(_),
+                        expression_depth,
+                        ..
+                    }) => *expression_depth,
+                    _ => 0,
+                },
+                block_depth: parent.map(|parent| parent.block_depth).unwrap_or_default(),
+            },
+            AstNode::Expression(expression) => {
+                if let Some(sizes) = sizes {
+                    sizes.expressions += 1;
+                }
+                *metrics.expressions.entry(expression_kind(expression)).or_insert(0) += 1;
+                match expression {
+                    Expression::Identifier(_) => metrics.identifiers += 1,
+                    Expression::Cast(cast) => metrics.error_nodes += type_errors(&cast.target_type),
+                    _ => (),
+                }
+                let expression_depth = match parent {
+                    Some(Visited {
+                        node: AstNode::Expression(_),
+                        expression_depth,
+                        ..
+                    }) => expression_depth + 1,
+                    _ => 1,
+                };
+                metrics.max_expression_depth = metrics.max_expression_depth.max(expression_depth);
+                Visited {
+                    node,
+                    expression_depth,
+                    block_depth: parent.map(|parent| parent.block_depth).unwrap_or_default(),
+                }
+            }
+            AstNode::Statement(statement) => {
+                if let Some(sizes) = sizes {
+                    sizes.statements += 1;
+                }
+                *metrics.statements.entry(statement_kind(statement)).or_insert(0) += 1;
+                if let Statement::Definition(definition) = statement {
+                    metrics.error_nodes += definition.type_.as_ref().map(type_errors).unwrap_or_default();
+                }
+                let index = *path.indices.last().unwrap();
+                let block_depth = match parent.map(|parent| (parent.node, parent.block_depth)) {
+                    None => 1,
+                    // The `else` branch of a conditional is at the depth of the conditional.
+                    Some((AstNode::Statement(Statement::Conditional(conditional)), depth))
+                        if index == conditional.block.statements.len() + 1 =>
+                    {
+                        depth
+                    }
+                    Some((AstNode::Statement(Statement::Conditional(_)), depth))
+                    | Some((AstNode::Statement(Statement::Iteration(_)), depth))
+                    | Some((AstNode::Statement(Statement::Block(_)), depth)) => depth + 1,
+                    Some((_, depth)) => depth,
+                };
+                metrics.max_block_depth = metrics.max_block_depth.max(block_depth);
+                Visited {
+                    node,
+                    expression_depth: 0,
+                    block_depth,
+                }
+            }
+        };
+        ancestors.push(visited);
+    }
+    metrics
+}
+
+fn member_function(circuit: &str, function: &str) -> String {
+    format!("{}::{}", circuit, function)
+}
+
+/// Returns the number of placeholders in the parameter and output types of the function.
+fn signature_errors(function: &Function) -> usize {
+    let inputs = function.input.iter().map(|input| match input {
+        FunctionInput::Variable(variable) => type_errors(&variable.type_),
+        _ => 0,
+    });
+    inputs.sum::<usize>() + function.output.as_ref().map(type_errors).unwrap_or_default()
+}
+
+/// Returns the number of placeholders in the type.
+fn type_errors(type_: &Type) -> usize {
+    match type_ {
+        Type::Err(_) => 1,
+        Type::Array(element, _) => type_errors(element),
+        Type::Tuple(elements) => elements.iter().map(type_errors).sum(),
+        _ => 0,
+    }
+}
+
+fn expression_kind(expression: &Expression) -> &'static str {
+    match expression {
+        Expression::Identifier(_) => "Identifier",
+        Expression::Value(_) => "Value",
+        Expression::Binary(_) => "Binary",
+        Expression::Unary(_) => "Unary",
+        Expression::Ternary(_) => "Ternary",
+        Expression::Cast(_) => "Cast",
+        Expression::LengthOf(_) => "LengthOf",
+        Expression::ArrayInline(_) => "ArrayInline",
+        Expression::ArrayInit(_) => "ArrayInit",
+        Expression::ArrayComprehension(_) => "ArrayComprehension",
+        Expression::ArrayAccess(_) => "ArrayAccess",
+        Expression::ArrayRangeAccess(_) => "ArrayRangeAccess",
+        Expression::TupleInit(_) => "TupleInit",
+        Expression::TupleAccess(_) => "TupleAccess",
+        Expression::CircuitInit(_) => "CircuitInit",
+        Expression::CircuitMemberAccess(_) => "CircuitMemberAccess",
+        Expression::CircuitStaticFunctionAccess(_) => "CircuitStaticFunctionAccess",
+        Expression::Call(_) => "Call",
+        Expression::Parenthesized(_) => "Parenthesized",
+        Expression::Err(_) => "Err",
+    }
+}
+
+fn statement_kind(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::Return(_) => "Return",
+        Statement::Definition(_) => "Definition",
+        Statement::Assign(_) => "Assign",
+        Statement::Conditional(_) => "Conditional",
+        Statement::Iteration(_) => "Iteration",
+        Statement::Console(_) => "Console",
+        Statement::Expression(_) => "Expression",
+        Statement::Block(_) => "Block",
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Generation of names for variables that passes introduce into a program.
+
+use crate::*;
+use leo_errors::{Result, Span};
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Starts every generated name. The parser never accepts it in an identifier,
+/// so generated names are told apart from user names by their first character alone.
+pub const GENERATED_NAME_MARKER: char = '$';
+
+///
+/// Returns `true` if the name was made by a [`NameGenerator`].
+///
+/// Analyses that report on the user's variables, such as unused variable highlighting, skip these names.
+///
+pub fn is_generated_name(name: &str) -> bool {
+    name.starts_with(GENERATED_NAME_MARKER)
+}
+
+/// Makes names that no identifier of a program, and no name made before, already uses.
+///
+/// Names are `$<prefix>_<n>`, counting from 1 for every prefix, so the same program always gets the same names.
+#[derive(Clone, Debug, Default)]
+pub struct NameGenerator {
+    taken: IndexSet<String>,
+    counters: IndexMap<String, usize>,
+}
+
+impl NameGenerator {
+    ///
+    /// Returns a generator that avoids every identifier of the program and its imports.
+    ///
+    pub fn new(program: &Program) -> Result<Self> {
+        let mut collector = ReconstructingDirector::new(IdentifierCollector::default()).with_max_depth(usize::MAX);
+        collector.reduce_program(program)?;
+
+        let mut generator = NameGenerator {
+            taken: collector.reducer().names,
+            counters: IndexMap::new(),
+        };
+        generator.reserve_aliases(program);
+        Ok(generator)
+    }
+
+    // Alias names are not passed to the reducer, since aliases are not reconstructed.
+    fn reserve_aliases(&mut self, program: &Program) {
+        for name in program.aliases.keys() {
+            self.reserve(&name.name);
+        }
+        for import in program.imports.values() {
+            self.reserve_aliases(import);
+        }
+    }
+
+    ///
+    /// Keeps the generator from making the given name.
+    ///
+    pub fn reserve(&mut self, name: &str) {
+        self.taken.insert(name.to_string());
+    }
+
+    ///
+    /// Returns a new name starting with `$<prefix>_`.
+    ///
+    pub fn fresh(&mut self, prefix: &str) -> String {
+        let counter = self.counters.entry(prefix.to_string()).or_insert(0);
+        loop {
+            *counter += 1;
+            let name = format!("{}{}_{}", GENERATED_NAME_MARKER, prefix, counter);
+            if self.taken.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    ///
+    /// Returns an identifier with a new name starting with `$<prefix>_`.
+    ///
+    pub fn fresh_identifier(&mut self, prefix: &str, span: Span) -> Identifier {
+        Identifier::new_with_span(&self.fresh(prefix), span)
+    }
+}
+
+/// Records the name of every identifier while reconstructing an unchanged program.
+#[derive(Default)]
+struct IdentifierCollector {
+    names: IndexSet<String>,
+    in_circuit: bool,
+}
+
+impl ReconstructingReducer for IdentifierCollector {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        self.names.insert(identifier.name.to_string());
+        Ok(identifier.clone())
+    }
+}
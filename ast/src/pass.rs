@@ -14,9 +14,44 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Ast, Program};
+use crate::{Ast, Program, ReconstructingDirector, ReconstructingReducer, DEFAULT_MAX_EXPRESSION_DEPTH};
 use leo_errors::Result;
 
 pub trait AstPass {
     fn do_pass(asg: Program) -> Result<Ast>;
 }
+
+/// Runs a [`ReconstructingReducer`] over a whole program, so that any reducer can be used as an [`AstPass`].
+pub struct ReducerPass<R: ReconstructingReducer> {
+    reducer: R,
+    max_depth: usize,
+}
+
+impl<R: ReconstructingReducer> ReducerPass<R> {
+    pub fn new(reducer: R) -> Self {
+        Self {
+            reducer,
+            max_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+        }
+    }
+
+    /// Sets how deeply expressions may nest before the pass fails with a diagnostic.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Reduces the program, returning the reconstructed AST along with the reducer and any state it collected.
+    pub fn run(self, program: &Program) -> Result<(Ast, R)> {
+        let mut director = ReconstructingDirector::new(self.reducer).with_max_depth(self.max_depth);
+        let program = director.reduce_program(program)?;
+
+        Ok((Ast::new(program), director.reducer()))
+    }
+}
+
+impl<R: ReconstructingReducer + Default> AstPass for ReducerPass<R> {
+    fn do_pass(ast: Program) -> Result<Ast> {
+        Ok(Self::new(R::default()).run(&ast)?.0)
+    }
+}
@@ -17,9 +17,13 @@
 //! A Leo program consists of import, circuit, and function definitions.
 //! Each defined type consists of ast statements and expressions.
 
-use crate::{Alias, Circuit, DefinitionStatement, Function, FunctionInput, Identifier, ImportStatement};
+use crate::{
+    Alias, Circuit, DefinitionStatement, Function, FunctionInput, FunctionInputVariable, Identifier, IgnoreSpan,
+    ImportStatement, Type,
+};
 
 use indexmap::IndexMap;
+use leo_errors::{AstError, LeoError, Result, Span};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -27,6 +31,8 @@ use std::fmt;
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Program {
     pub name: String,
+    /// The parameters of `main`, in declaration order and with aliases resolved.
+    /// Populated by canonicalization; empty before it.
     pub expected_input: Vec<FunctionInput>,
     pub import_statements: Vec<ImportStatement>,
     #[serde(with = "crate::common::imported_modules")]
@@ -36,6 +42,9 @@ pub struct Program {
     #[serde(with = "crate::common::global_consts_json")]
     pub global_consts: IndexMap<Vec<Identifier>, DefinitionStatement>,
     pub functions: IndexMap<Identifier, Function>,
+    /// The regions of source the parser made up while recovering from syntax errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recovered: Vec<Span>,
 }
 
 impl AsRef<Program> for Program {
@@ -85,6 +94,103 @@ impl Program {
             circuits: IndexMap::new(),
             global_consts: IndexMap::new(),
             functions: IndexMap::new(),
+            recovered: vec![],
+        }
+    }
+
+    ///
+    /// Returns `true` if the span of `error` overlaps a region the parser made up while recovering,
+    /// in which case the error is likely a consequence of the recovery rather than a mistake of its own.
+    ///
+    pub fn is_recovered(&self, error: &LeoError) -> bool {
+        let span = match error.span() {
+            Some(span) => span,
+            None => return false,
+        };
+        self.recovered.iter().any(|region| {
+            (region.line_start, region.col_start) < (span.line_stop, span.col_stop)
+                && (span.line_start, span.col_start) < (region.line_stop, region.col_stop)
+        })
+    }
+
+    ///
+    /// Returns the parameters of `main` in declaration order, with their alias types resolved.
+    ///
+    /// Returns no parameters if the program has no `main` function.
+    ///
+    pub fn entrypoint_input(&self) -> Vec<FunctionInput> {
+        self.function_input("main")
+    }
+
+    ///
+    /// Returns the parameters of the top-level function with the given name in declaration order,
+    /// with their alias types resolved.
+    ///
+    /// Returns no parameters if the program has no such function.
+    ///
+    pub fn function_input(&self, name: &str) -> Vec<FunctionInput> {
+        let function = match self.functions.values().find(|f| f.identifier.name.as_ref() == name) {
+            Some(function) => function,
+            None => return vec![],
+        };
+        function
+            .input
+            .iter()
+            .map(|input| match input {
+                FunctionInput::Variable(variable) => FunctionInput::Variable(FunctionInputVariable {
+                    type_: self.resolve_aliases(&variable.type_, 0),
+                    ..variable.clone()
+                }),
+                input => input.clone(),
+            })
+            .collect()
+    }
+
+    ///
+    /// Sets `expected_input` to the parameters of `main`.
+    /// Passes that change the signature of `main` call this to keep the two in sync.
+    ///
+    pub fn sync_expected_input(&mut self) {
+        self.expected_input = self.entrypoint_input();
+    }
+
+    ///
+    /// Returns an error if `expected_input` has been populated but no longer matches the parameters of `main`,
+    /// as in a stale or edited serialized program.
+    ///
+    pub fn check_expected_input(&self) -> Result<()> {
+        if self.expected_input.is_empty() || self.expected_input.eq_ignore_span(&self.entrypoint_input()) {
+            return Ok(());
+        }
+        let format_inputs = |inputs: &[FunctionInput]| {
+            inputs
+                .iter()
+                .map(|input| input.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        Err(AstError::expected_input_mismatch(
+            format_inputs(&self.expected_input),
+            format_inputs(&self.entrypoint_input()),
+        )
+        .into())
+    }
+
+    /// Replaces every alias in `type_` with the type it represents.
+    /// Stops after as many substitutions as there are aliases, so that cyclic aliases terminate.
+    fn resolve_aliases(&self, type_: &Type, depth: usize) -> Type {
+        match type_ {
+            Type::Identifier(name) if depth < self.aliases.len() => {
+                match self.aliases.values().find(|alias| alias.name.name == name.name) {
+                    Some(alias) => self.resolve_aliases(&alias.represents, depth + 1),
+                    None => type_.clone(),
+                }
+            }
+            Type::Array(element, dimensions) => {
+                Type::Array(Box::new(self.resolve_aliases(element, depth)), dimensions.clone())
+            }
+            Type::Tuple(types) => Type::Tuple(types.iter().map(|type_| self.resolve_aliases(type_, depth)).collect()),
+            type_ => type_.clone(),
         }
     }
 
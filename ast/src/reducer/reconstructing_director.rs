@@ -23,11 +23,27 @@ use leo_errors::{AstError, Result, Span};
 
 pub struct ReconstructingDirector<R: ReconstructingReducer> {
     reducer: R,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl<R: ReconstructingReducer> ReconstructingDirector<R> {
     pub fn new(reducer: R) -> Self {
-        Self { reducer }
+        Self {
+            reducer,
+            depth: 0,
+            max_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+        }
+    }
+
+    /// Sets how deeply expressions may nest before reducing them fails with a diagnostic.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn reducer(self) -> R {
+        self.reducer
     }
 
     pub fn reduce_type(&mut self, type_: &Type, span: &Span) -> Result<Type> {
@@ -50,6 +66,19 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
 
     // Expressions
     pub fn reduce_expression(&mut self, expression: &Expression) -> Result<Expression> {
+        if self.depth >= self.max_depth {
+            return Err(AstError::expression_too_deep(self.max_depth, expression.span()).into());
+        }
+
+        // Parentheses only group, so they do not count towards the depth.
+        let nesting = !matches!(expression, Expression::Parenthesized(_)) as usize;
+        self.depth += nesting;
+        let reduced = ensure_stack(|| self.reduce_expression_inner(expression));
+        self.depth -= nesting;
+        reduced
+    }
+
+    fn reduce_expression_inner(&mut self, expression: &Expression) -> Result<Expression> {
         let new = match expression {
             Expression::Identifier(identifier) => Expression::Identifier(self.reduce_identifier(identifier)?),
             Expression::Value(value) => self.reduce_value(value)?,
@@ -61,6 +90,9 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
 
             Expression::ArrayInline(array_inline) => Expression::ArrayInline(self.reduce_array_inline(array_inline)?),
             Expression::ArrayInit(array_init) => Expression::ArrayInit(self.reduce_array_init(array_init)?),
+            Expression::ArrayComprehension(array_comprehension) => {
+                Expression::ArrayComprehension(self.reduce_array_comprehension(array_comprehension)?)
+            }
             Expression::ArrayAccess(array_access) => Expression::ArrayAccess(self.reduce_array_access(array_access)?),
             Expression::ArrayRangeAccess(array_range_access) => {
                 Expression::ArrayRangeAccess(self.reduce_array_range_access(array_range_access)?)
@@ -78,6 +110,10 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
             }
 
             Expression::Call(call) => Expression::Call(self.reduce_call(call)?),
+            Expression::Parenthesized(parenthesized) => {
+                Expression::Parenthesized(self.reduce_parenthesized(parenthesized)?)
+            }
+            Expression::Err(err) => Expression::Err(err.clone()),
         };
 
         self.reducer.reduce_expression(expression, new)
@@ -168,6 +204,19 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
         self.reducer.reduce_array_init(array_init, element)
     }
 
+    pub fn reduce_array_comprehension(
+        &mut self,
+        array_comprehension: &ArrayComprehensionExpression,
+    ) -> Result<ArrayComprehensionExpression> {
+        let element = self.reduce_expression(&array_comprehension.element)?;
+        let variable = self.reduce_identifier(&array_comprehension.variable)?;
+        let start = self.reduce_expression(&array_comprehension.start)?;
+        let stop = self.reduce_expression(&array_comprehension.stop)?;
+
+        self.reducer
+            .reduce_array_comprehension(array_comprehension, element, variable, start, stop)
+    }
+
     pub fn reduce_array_access(&mut self, array_access: &ArrayAccessExpression) -> Result<ArrayAccessExpression> {
         let array = self.reduce_expression(&array_access.array)?;
         let index = self.reduce_expression(&array_access.index)?;
@@ -274,8 +323,18 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
         self.reducer.reduce_call(call, function, arguments)
     }
 
+    pub fn reduce_parenthesized(&mut self, parenthesized: &ParenthesizedExpression) -> Result<ParenthesizedExpression> {
+        let inner = self.reduce_expression(&parenthesized.inner)?;
+
+        self.reducer.reduce_parenthesized(parenthesized, inner)
+    }
+
     // Statements
     pub fn reduce_statement(&mut self, statement: &Statement) -> Result<Statement> {
+        ensure_stack(|| self.reduce_statement_inner(statement))
+    }
+
+    fn reduce_statement_inner(&mut self, statement: &Statement) -> Result<Statement> {
         let new = match statement {
             Statement::Return(return_statement) => Statement::Return(self.reduce_return(return_statement)?),
             Statement::Definition(definition) => Statement::Definition(self.reduce_definition(definition)?),
@@ -314,7 +373,11 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
             .map(|type_| self.reduce_type(type_, &definition.span))
             .transpose()?;
 
-        let value = self.reduce_expression(&definition.value)?;
+        let value = definition
+            .value
+            .as_ref()
+            .map(|value| self.reduce_expression(value))
+            .transpose()?;
 
         self.reducer.reduce_definition(definition, variable_names, type_, value)
     }
@@ -369,14 +432,32 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
         let variable = self.reduce_identifier(&iteration.variable)?;
         let start = self.reduce_expression(&iteration.start)?;
         let stop = self.reduce_expression(&iteration.stop)?;
+        let step = iteration
+            .step
+            .as_ref()
+            .map(|step| self.reduce_expression(step))
+            .transpose()?;
         let block = self.reduce_block(&iteration.block)?;
 
-        self.reducer.reduce_iteration(iteration, variable, start, stop, block)
+        self.reducer
+            .reduce_iteration(iteration, variable, start, stop, step, block)
     }
 
     pub fn reduce_console(&mut self, console_function_call: &ConsoleStatement) -> Result<ConsoleStatement> {
         let function = match &console_function_call.function {
             ConsoleFunction::Assert(expression) => ConsoleFunction::Assert(self.reduce_expression(expression)?),
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                let compared = ConsoleAssertArgs {
+                    left: self.reduce_expression(&args.left)?,
+                    right: self.reduce_expression(&args.right)?,
+                    span: args.span.clone(),
+                };
+
+                match &console_function_call.function {
+                    ConsoleFunction::AssertEq(_) => ConsoleFunction::AssertEq(compared),
+                    _ => ConsoleFunction::AssertNe(compared),
+                }
+            }
             ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => {
                 let mut parameters = vec![];
                 for parameter in args.parameters.iter() {
@@ -530,6 +611,11 @@ impl<R: ReconstructingReducer> ReconstructingDirector<R> {
                 self.reduce_identifier(identifier)?,
                 self.reduce_type(type_, &identifier.span)?,
             ),
+            CircuitMember::CircuitStatic(identifier, type_, value) => CircuitMember::CircuitStatic(
+                self.reduce_identifier(identifier)?,
+                self.reduce_type(type_, &identifier.span)?,
+                self.reduce_expression(value)?,
+            ),
             CircuitMember::CircuitFunction(function) => CircuitMember::CircuitFunction(self.reduce_function(function)?),
         };
 
@@ -137,6 +137,24 @@ pub trait ReconstructingReducer {
         })
     }
 
+    fn reduce_array_comprehension(
+        &mut self,
+        array_comprehension: &ArrayComprehensionExpression,
+        element: Expression,
+        variable: Identifier,
+        start: Expression,
+        stop: Expression,
+    ) -> Result<ArrayComprehensionExpression> {
+        Ok(ArrayComprehensionExpression {
+            element: Box::new(element),
+            variable,
+            start: Box::new(start),
+            stop: Box::new(stop),
+            inclusive: array_comprehension.inclusive,
+            span: array_comprehension.span.clone(),
+        })
+    }
+
     fn reduce_array_access(
         &mut self,
         array_access: &ArrayAccessExpression,
@@ -251,6 +269,17 @@ pub trait ReconstructingReducer {
         })
     }
 
+    fn reduce_parenthesized(
+        &mut self,
+        parenthesized: &ParenthesizedExpression,
+        inner: Expression,
+    ) -> Result<ParenthesizedExpression> {
+        Ok(ParenthesizedExpression {
+            inner: Box::new(inner),
+            span: parenthesized.span.clone(),
+        })
+    }
+
     // Statements
     fn reduce_statement(&mut self, _statement: &Statement, new: Statement) -> Result<Statement> {
         Ok(new)
@@ -276,7 +305,7 @@ pub trait ReconstructingReducer {
         definition: &DefinitionStatement,
         variable_names: Vec<VariableName>,
         type_: Option<Type>,
-        value: Expression,
+        value: Option<Expression>,
     ) -> Result<DefinitionStatement> {
         Ok(DefinitionStatement {
             declaration_type: definition.declaration_type.clone(),
@@ -339,6 +368,7 @@ pub trait ReconstructingReducer {
         variable: Identifier,
         start: Expression,
         stop: Expression,
+        step: Option<Expression>,
         block: Block,
     ) -> Result<IterationStatement> {
         Ok(IterationStatement {
@@ -346,6 +376,7 @@ pub trait ReconstructingReducer {
             start,
             stop,
             inclusive: iteration.inclusive,
+            step,
             block,
             span: iteration.span.clone(),
         })
@@ -398,6 +429,7 @@ pub trait ReconstructingReducer {
             circuits,
             functions,
             global_consts,
+            recovered: program.recovered.clone(),
         })
     }
 
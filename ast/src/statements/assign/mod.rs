@@ -24,7 +24,7 @@ use std::fmt;
 mod assignee;
 pub use assignee::*;
 
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub enum AssignOperation {
     Assign,
     Add,
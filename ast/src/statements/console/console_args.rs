@@ -31,14 +31,13 @@ impl fmt::Display for ConsoleArgs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "\"{}\", {}",
-            self.string.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(""),
-            self.parameters
-                .iter()
-                .map(|p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(",")
-        )
+            "\"{}\"",
+            self.string.iter().map(|x| x.to_string()).collect::<Vec<_>>().join("")
+        )?;
+        for parameter in self.parameters.iter() {
+            write!(f, ", {}", parameter)?;
+        }
+        Ok(())
     }
 }
 
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConsoleArgs, Expression, Node};
+use crate::{ConsoleArgs, ConsoleAssertArgs, Expression, Node};
 use leo_errors::Span;
 
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,8 @@ use std::fmt;
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ConsoleFunction {
     Assert(Expression),
+    AssertEq(ConsoleAssertArgs),
+    AssertNe(ConsoleAssertArgs),
     Error(ConsoleArgs),
     Log(ConsoleArgs),
 }
@@ -31,6 +33,8 @@ impl fmt::Display for ConsoleFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ConsoleFunction::Assert(assert) => write!(f, "assert({})", assert),
+            ConsoleFunction::AssertEq(args) => write!(f, "assert_eq({})", args),
+            ConsoleFunction::AssertNe(args) => write!(f, "assert_ne({})", args),
             ConsoleFunction::Error(error) => write!(f, "error{})", error),
             ConsoleFunction::Log(log) => write!(f, "log({})", log),
         }
@@ -41,6 +45,7 @@ impl Node for ConsoleFunction {
     fn span(&self) -> &Span {
         match self {
             ConsoleFunction::Assert(assert) => assert.span(),
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => &args.span,
             ConsoleFunction::Error(formatted) | ConsoleFunction::Log(formatted) => &formatted.span,
         }
     }
@@ -48,6 +53,7 @@ impl Node for ConsoleFunction {
     fn set_span(&mut self, span: Span) {
         match self {
             ConsoleFunction::Assert(assert) => assert.set_span(span),
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => args.set_span(span),
             ConsoleFunction::Error(formatted) | ConsoleFunction::Log(formatted) => formatted.set_span(span),
         }
     }
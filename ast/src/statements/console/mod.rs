@@ -20,5 +20,8 @@ pub use console_function::*;
 pub mod console_args;
 pub use console_args::*;
 
+pub mod console_assert_args;
+pub use console_assert_args::*;
+
 pub mod console_statement;
 pub use console_statement::*;
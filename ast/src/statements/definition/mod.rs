@@ -31,7 +31,7 @@ pub struct DefinitionStatement {
     pub declaration_type: Declare,
     pub variable_names: Vec<VariableName>,
     pub type_: Option<Type>,
-    pub value: Expression,
+    pub value: Option<Expression>,
     pub span: Span,
 }
 
@@ -48,7 +48,7 @@ impl fmt::Display for DefinitionStatement {
                 .iter()
                 .map(|x| x.to_string())
                 .collect::<Vec<_>>()
-                .join(",");
+                .join(", ");
 
             write!(f, "({})", names)?;
         }
@@ -56,7 +56,10 @@ impl fmt::Display for DefinitionStatement {
         if self.type_.is_some() {
             write!(f, ": {}", self.type_.as_ref().unwrap())?;
         }
-        write!(f, " = {};", self.value)
+        match &self.value {
+            Some(value) => write!(f, " = {};", value),
+            None => write!(f, ";"),
+        }
     }
 }
 
@@ -26,6 +26,9 @@ pub struct IterationStatement {
     pub start: Expression,
     pub stop: Expression,
     pub inclusive: bool,
+    /// The amount added to the counter after every iteration, `1` or `-1` towards `stop` when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<Expression>,
     pub block: Block,
     pub span: Span,
 }
@@ -33,11 +36,11 @@ pub struct IterationStatement {
 impl fmt::Display for IterationStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let eq = if self.inclusive { "=" } else { "" };
-        write!(
-            f,
-            "for {} in {}..{}{} {}",
-            self.variable, self.start, eq, self.stop, self.block
-        )
+        write!(f, "for {} in {}..{}{} ", self.variable, self.start, eq, self.stop)?;
+        if let Some(step) = &self.step {
+            write!(f, "step {} ", step)?;
+        }
+        write!(f, "{}", self.block)
     }
 }
 
@@ -0,0 +1,367 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Iterators over the statements and expressions of a program, for analyses that do not need a full reducer.
+//!
+//! The iterators keep an explicit stack rather than recursing, so arbitrarily deep programs can be walked.
+
+use crate::{
+    AssigneeAccess, CircuitMember, ConsoleFunction, Expression, Function, FunctionInput, Program, SpreadOrExpression,
+    Statement, Type,
+};
+
+/// The order in which an [`AstIter`] yields nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Traversal {
+    /// Every node before its children.
+    PreOrder,
+    /// Every node after its children.
+    PostOrder,
+}
+
+/// A statement or expression of a program.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AstNode<'a> {
+    Statement(&'a Statement),
+    Expression(&'a Expression),
+}
+
+impl<'a> AstNode<'a> {
+    /// Returns the statements and expressions directly nested in the node, in source order.
+    pub fn children(&self) -> Vec<AstNode<'a>> {
+        match self {
+            AstNode::Statement(statement) => statement_children(statement),
+            AstNode::Expression(expression) => expression_children(expression),
+        }
+    }
+}
+
+fn statement_children(statement: &Statement) -> Vec<AstNode<'_>> {
+    let mut children = vec![];
+    match statement {
+        Statement::Return(return_) => children.push(AstNode::Expression(&return_.expression)),
+        Statement::Definition(definition) => children.extend(definition.value.iter().map(AstNode::Expression)),
+        Statement::Assign(assign) => {
+            for access in assign.assignee.accesses.iter() {
+                match access {
+                    AssigneeAccess::ArrayRange(left, right) => {
+                        children.extend(left.iter().chain(right.iter()).map(AstNode::Expression))
+                    }
+                    AssigneeAccess::ArrayIndex(index) => children.push(AstNode::Expression(index)),
+                    AssigneeAccess::Tuple(..) | AssigneeAccess::Member(_) => (),
+                }
+            }
+            children.push(AstNode::Expression(&assign.value));
+        }
+        Statement::Conditional(conditional) => {
+            children.push(AstNode::Expression(&conditional.condition));
+            children.extend(conditional.block.statements.iter().map(AstNode::Statement));
+            children.extend(conditional.next.iter().map(|next| AstNode::Statement(next)));
+        }
+        Statement::Iteration(iteration) => {
+            children.push(AstNode::Expression(&iteration.start));
+            children.push(AstNode::Expression(&iteration.stop));
+            children.extend(iteration.step.iter().map(AstNode::Expression));
+            children.extend(iteration.block.statements.iter().map(AstNode::Statement));
+        }
+        Statement::Console(console) => match &console.function {
+            ConsoleFunction::Assert(expression) => children.push(AstNode::Expression(expression)),
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                children.push(AstNode::Expression(&args.left));
+                children.push(AstNode::Expression(&args.right));
+            }
+            ConsoleFunction::Error(args) | ConsoleFunction::Log(args) => {
+                children.extend(args.parameters.iter().map(AstNode::Expression))
+            }
+        },
+        Statement::Expression(expression) => children.push(AstNode::Expression(&expression.expression)),
+        Statement::Block(block) => children.extend(block.statements.iter().map(AstNode::Statement)),
+    }
+    children
+}
+
+fn expression_children(expression: &Expression) -> Vec<AstNode<'_>> {
+    let expressions: Vec<&Expression> = match expression {
+        Expression::Identifier(_) | Expression::Value(_) | Expression::Err(_) => vec![],
+        Expression::Binary(binary) => vec![&*binary.left, &*binary.right],
+        Expression::Unary(unary) => vec![&*unary.inner],
+        Expression::Ternary(ternary) => vec![&*ternary.condition, &*ternary.if_true, &*ternary.if_false],
+        Expression::Cast(cast) => vec![&*cast.inner],
+        Expression::LengthOf(lengthof) => vec![&*lengthof.inner],
+        Expression::ArrayInline(array) => array
+            .elements
+            .iter()
+            .map(|element| match element {
+                SpreadOrExpression::Spread(expression) | SpreadOrExpression::Expression(expression) => expression,
+            })
+            .collect(),
+        Expression::ArrayInit(array) => vec![&*array.element],
+        Expression::ArrayComprehension(comprehension) => {
+            vec![&*comprehension.element, &*comprehension.start, &*comprehension.stop]
+        }
+        Expression::ArrayAccess(access) => vec![&*access.array, &*access.index],
+        Expression::ArrayRangeAccess(access) => std::iter::once(&*access.array)
+            .chain(access.left.as_deref())
+            .chain(access.right.as_deref())
+            .collect(),
+        Expression::TupleInit(tuple) => tuple.elements.iter().collect(),
+        Expression::TupleAccess(access) => vec![&*access.tuple],
+        Expression::CircuitInit(init) => init
+            .members
+            .iter()
+            .filter_map(|member| member.expression.as_ref())
+            .collect(),
+        Expression::CircuitMemberAccess(access) => vec![&*access.circuit],
+        Expression::CircuitStaticFunctionAccess(access) => vec![&*access.circuit],
+        Expression::Call(call) => std::iter::once(&*call.function).chain(call.arguments.iter()).collect(),
+        Expression::Parenthesized(parenthesized) => vec![&*parenthesized.inner],
+    };
+    expressions.into_iter().map(AstNode::Expression).collect()
+}
+
+/// Where an [`AstPath`] starts.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AstRoot {
+    /// The body of the function with the given name.
+    Function(String),
+    /// The body of the member function of a circuit, given by circuit and function name.
+    CircuitFunction(String, String),
+    /// The value of the static member of a circuit, given by circuit and member name.
+    CircuitStatic(String, String),
+    /// The initializer of the global constant at the given index.
+    GlobalConst(usize),
+}
+
+/// The position of a statement or expression in a program.
+///
+/// The first index picks a node at the root: a statement of a function body or the initializer of a global constant.
+/// Every following index picks a node among the [children](AstNode::children) of the previous one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AstPath {
+    pub root: AstRoot,
+    pub indices: Vec<usize>,
+}
+
+impl AstPath {
+    /// Returns the node at this path in the program, if there is one.
+    pub fn resolve<'a>(&self, program: &'a Program) -> Option<AstNode<'a>> {
+        let (first, rest) = self.indices.split_first()?;
+        let mut node = *root_nodes(program, &self.root)?.get(*first)?;
+        for index in rest {
+            node = *node.children().get(*index)?;
+        }
+        Some(node)
+    }
+}
+
+fn function_nodes(function: &Function) -> Vec<AstNode<'_>> {
+    function.block.statements.iter().map(AstNode::Statement).collect()
+}
+
+fn root_nodes<'a>(program: &'a Program, root: &AstRoot) -> Option<Vec<AstNode<'a>>> {
+    match root {
+        AstRoot::Function(name) => program
+            .functions
+            .values()
+            .find(|function| function.identifier.name.as_ref() == name)
+            .map(function_nodes),
+        AstRoot::CircuitFunction(circuit, name) => program
+            .circuits
+            .values()
+            .find(|candidate| candidate.circuit_name.name.as_ref() == circuit)?
+            .members
+            .iter()
+            .find_map(|member| match member {
+                CircuitMember::CircuitFunction(function) if function.identifier.name.as_ref() == name => {
+                    Some(function_nodes(function))
+                }
+                _ => None,
+            }),
+        AstRoot::CircuitStatic(circuit, name) => program
+            .circuits
+            .values()
+            .find(|candidate| candidate.circuit_name.name.as_ref() == circuit)?
+            .members
+            .iter()
+            .find_map(|member| match member {
+                CircuitMember::CircuitStatic(identifier, _, value) if identifier.name.as_ref() == name => {
+                    Some(vec![AstNode::Expression(value)])
+                }
+                _ => None,
+            }),
+        AstRoot::GlobalConst(index) => program
+            .global_consts
+            .get_index(*index)
+            .map(|(_, definition)| definition.value.iter().map(AstNode::Expression).collect()),
+    }
+}
+
+/// An iterator over statements and expressions, along with their paths.
+pub struct AstIter<'a> {
+    order: Traversal,
+    /// The nodes left to visit with their paths, and whether their children were pushed already.
+    stack: Vec<(AstNode<'a>, AstPath, bool)>,
+}
+
+impl<'a> AstIter<'a> {
+    fn new(order: Traversal, roots: Vec<(AstRoot, Vec<AstNode<'a>>)>) -> Self {
+        let mut stack = vec![];
+        for (root, nodes) in roots.into_iter().rev() {
+            for (index, node) in nodes.into_iter().enumerate().rev() {
+                let path = AstPath {
+                    root: root.clone(),
+                    indices: vec![index],
+                };
+                stack.push((node, path, false));
+            }
+        }
+        Self { order, stack }
+    }
+
+    /// Iterates over the bodies of every function and circuit member function and the values of static members,
+    /// then every global constant initializer.
+    pub fn program(program: &'a Program, order: Traversal) -> Self {
+        let mut roots = vec![];
+        for function in program.functions.values() {
+            roots.push((
+                AstRoot::Function(function.identifier.name.to_string()),
+                function_nodes(function),
+            ));
+        }
+        for circuit in program.circuits.values() {
+            for member in circuit.members.iter() {
+                match member {
+                    CircuitMember::CircuitFunction(function) => {
+                        let root = AstRoot::CircuitFunction(
+                            circuit.circuit_name.name.to_string(),
+                            function.identifier.name.to_string(),
+                        );
+                        roots.push((root, function_nodes(function)));
+                    }
+                    CircuitMember::CircuitStatic(identifier, _, value) => {
+                        let root =
+                            AstRoot::CircuitStatic(circuit.circuit_name.name.to_string(), identifier.name.to_string());
+                        roots.push((root, vec![AstNode::Expression(value)]));
+                    }
+                    CircuitMember::CircuitVariable(..) => {}
+                }
+            }
+        }
+        for (index, definition) in program.global_consts.values().enumerate() {
+            roots.push((
+                AstRoot::GlobalConst(index),
+                definition.value.iter().map(AstNode::Expression).collect(),
+            ));
+        }
+        Self::new(order, roots)
+    }
+
+    /// Iterates over the body of a function, with paths as if it were a function of a program.
+    pub fn function(function: &'a Function, order: Traversal) -> Self {
+        let root = AstRoot::Function(function.identifier.name.to_string());
+        Self::new(order, vec![(root, function_nodes(function))])
+    }
+}
+
+impl<'a> Iterator for AstIter<'a> {
+    type Item = (AstNode<'a>, AstPath);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, path, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some((node, path));
+            }
+            if self.order == Traversal::PostOrder {
+                self.stack.push((node, path.clone(), true));
+            }
+            for (index, child) in node.children().into_iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.indices.push(index);
+                self.stack.push((child, child_path, false));
+            }
+            if self.order == Traversal::PreOrder {
+                return Some((node, path));
+            }
+        }
+    }
+}
+
+fn expressions<'a>(iter: AstIter<'a>) -> impl Iterator<Item = (&'a Expression, AstPath)> {
+    iter.filter_map(|(node, path)| match node {
+        AstNode::Expression(expression) => Some((expression, path)),
+        AstNode::Statement(_) => None,
+    })
+}
+
+fn statements<'a>(iter: AstIter<'a>) -> impl Iterator<Item = &'a Statement> {
+    iter.filter_map(|(node, _)| match node {
+        AstNode::Statement(statement) => Some(statement),
+        AstNode::Expression(_) => None,
+    })
+}
+
+impl Program {
+    /// Returns every statement and expression of the program with its path. See [`AstIter::program`].
+    pub fn iter_nodes(&self, order: Traversal) -> AstIter<'_> {
+        AstIter::program(self, order)
+    }
+
+    /// Returns every expression of the program, including global constant initializers.
+    pub fn iter_expressions(&self, order: Traversal) -> impl Iterator<Item = &Expression> {
+        expressions(self.iter_nodes(order)).map(|(expression, _)| expression)
+    }
+
+    /// Returns every expression of the program with its path.
+    pub fn iter_expressions_with_path(&self, order: Traversal) -> impl Iterator<Item = (&Expression, AstPath)> {
+        expressions(self.iter_nodes(order))
+    }
+
+    /// Returns every statement of the program, including those nested in blocks.
+    pub fn iter_statements(&self, order: Traversal) -> impl Iterator<Item = &Statement> {
+        statements(self.iter_nodes(order))
+    }
+}
+
+impl Function {
+    /// Returns every statement of the function body, including those nested in blocks.
+    pub fn iter_statements(&self, order: Traversal) -> impl Iterator<Item = &Statement> {
+        statements(AstIter::function(self, order))
+    }
+
+    /// Returns every expression of the function body.
+    pub fn iter_expressions(&self, order: Traversal) -> impl Iterator<Item = &Expression> {
+        expressions(AstIter::function(self, order)).map(|(expression, _)| expression)
+    }
+
+    /// Returns `true` if the signature or the body of the function has a placeholder for code the parser
+    /// could not make sense of, see [`crate::ErrNode`].
+    pub fn has_errors(&self) -> bool {
+        let signature = self.input.iter().any(|input| match input {
+            FunctionInput::Variable(variable) => variable.type_.has_errors(),
+            _ => false,
+        }) || self.output.as_ref().map(Type::has_errors).unwrap_or_default();
+        signature
+            || AstIter::function(self, Traversal::PreOrder).any(|(node, _)| match node {
+                AstNode::Expression(Expression::Err(_)) => true,
+                AstNode::Expression(Expression::Cast(cast)) => cast.target_type.has_errors(),
+                AstNode::Statement(Statement::Definition(definition)) => {
+                    definition.type_.as_ref().map(Type::has_errors).unwrap_or_default()
+                }
+                _ => false,
+            })
+    }
+}
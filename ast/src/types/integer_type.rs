@@ -43,6 +43,31 @@ impl IntegerType {
         use IntegerType::*;
         matches!(self, I8 | I16 | I32 | I64 | I128)
     }
+
+    /// Returns the number of bits in the integer type.
+    pub fn bit_width(&self) -> usize {
+        use IntegerType::*;
+        match self {
+            U8 | I8 => 8,
+            U16 | I16 => 16,
+            U32 | I32 => 32,
+            U64 | I64 => 64,
+            U128 | I128 => 128,
+        }
+    }
+
+    /// Returns the unsigned integer type with the given number of bits, if there is one.
+    pub fn unsigned_with_bit_width(bit_width: usize) -> Option<Self> {
+        use IntegerType::*;
+        match bit_width {
+            8 => Some(U8),
+            16 => Some(U16),
+            32 => Some(U32),
+            64 => Some(U64),
+            128 => Some(U128),
+            _ => None,
+        }
+    }
 }
 
 impl From<InputIntegerType> for IntegerType {
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ArrayDimensions, Identifier, IntegerType, PositiveNumber};
+use crate::{ArrayDimensions, ErrNode, Identifier, IntegerType, PositiveNumber};
 use leo_input::types::{
     ArrayType as InputArrayType, DataType as InputDataType, TupleType as InputTupleType, Type as InputType,
 };
@@ -39,6 +39,9 @@ pub enum Type {
     Tuple(Vec<Type>),
     Identifier(Identifier), // ex Circuit or Alias
     SelfType,
+
+    /// A type the parser could not make sense of, see [`ErrNode`].
+    Err(ErrNode),
 }
 
 impl Type {
@@ -49,6 +52,18 @@ impl Type {
         matches!(self, Type::SelfType)
     }
 
+    ///
+    /// Returns `true` if the self `Type` is or contains a type the parser could not make sense of.
+    ///
+    pub fn has_errors(&self) -> bool {
+        match self {
+            Type::Err(_) => true,
+            Type::Array(element, _) => element.has_errors(),
+            Type::Tuple(elements) => elements.iter().any(Type::has_errors),
+            _ => false,
+        }
+    }
+
     ///
     /// Returns `true` if the self `Type` is a `Circuit`.
     ///
@@ -163,6 +178,7 @@ impl fmt::Display for Type {
             Type::IntegerType(ref integer_type) => write!(f, "{}", integer_type),
             Type::Identifier(ref variable) => write!(f, "circuit {}", variable),
             Type::SelfType => write!(f, "SelfType"),
+            Type::Err(ref err) => write!(f, "{}", err),
             Type::Array(ref array, ref dimensions) => {
                 if let Some(dimensions) = dimensions {
                     write!(f, "[{}; {}]", *array, dimensions)
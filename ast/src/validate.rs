@@ -0,0 +1,285 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use leo_errors::{AstError, Result, Span};
+
+use indexmap::IndexMap;
+
+use std::fmt;
+
+/// The point of the pipeline a program is validated at.
+///
+/// Every stage expects the invariants of the stages before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Directly after parsing or import resolution:
+    /// - every span ends at or after its start,
+    /// - every definition declares at least one variable.
+    Parsed,
+    /// After canonicalization:
+    /// - no `Self` type remains,
+    /// - no compound assignment remains,
+    /// - every function has an explicit output type,
+    /// - the expected input of the program lists the parameters of `main`.
+    Canonicalized,
+    /// After type checking:
+    /// - no placeholder left by error recovery remains, unless the parser recovered from errors.
+    TypeChecked,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parsed => write!(f, "parsed"),
+            Stage::Canonicalized => write!(f, "canonicalized"),
+            Stage::TypeChecked => write!(f, "type checked"),
+        }
+    }
+}
+
+/// A structural invariant that does not hold for a program at a given [`Stage`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvariantViolation {
+    pub message: String,
+    pub span: Span,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}:{}",
+            self.message, self.span.path, self.span.line_start, self.span.col_start
+        )
+    }
+}
+
+/// Returns every structural invariant of the given stage that the program violates.
+pub fn validate(program: &Program, stage: Stage) -> Vec<InvariantViolation> {
+    let mut director = ReconstructingDirector::new(InvariantChecker::new(stage, !program.recovered.is_empty()))
+        .with_max_depth(usize::MAX);
+    match director.reduce_program(program) {
+        Ok(_) => director.reducer().violations,
+        Err(e) => vec![InvariantViolation {
+            message: format!("failed to traverse program: {}", e),
+            span: Span::default(),
+        }],
+    }
+}
+
+/// Validates the program produced by the named pass, failing with an internal error that blames the pass.
+pub fn validate_pass_output(pass: &str, program: &Program, stage: Stage) -> Result<()> {
+    let violations = validate(program, stage);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let violations = violations
+        .iter()
+        .map(|violation| violation.to_string())
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(AstError::invariant_violated(pass, stage, violations).into())
+}
+
+/// Records invariant violations while reconstructing an unchanged program.
+struct InvariantChecker {
+    stage: Stage,
+    /// Whether the parser recovered from errors, which are reported already.
+    recovered: bool,
+    violations: Vec<InvariantViolation>,
+    in_circuit: bool,
+}
+
+impl InvariantChecker {
+    fn new(stage: Stage, recovered: bool) -> Self {
+        Self {
+            stage,
+            recovered,
+            violations: vec![],
+            in_circuit: false,
+        }
+    }
+
+    fn violation(&mut self, message: impl Into<String>, span: &Span) {
+        self.violations.push(InvariantViolation {
+            message: message.into(),
+            span: span.clone(),
+        });
+    }
+
+    fn check_span(&mut self, node: &str, span: &Span) {
+        if span.line_stop < span.line_start || (span.line_stop == span.line_start && span.col_stop < span.col_start) {
+            self.violation(format!("{} span ends before it starts", node), span);
+        }
+    }
+}
+
+impl ReconstructingReducer for InvariantChecker {
+    fn in_circuit(&self) -> bool {
+        self.in_circuit
+    }
+
+    fn swap_in_circuit(&mut self) {
+        self.in_circuit = !self.in_circuit;
+    }
+
+    fn reduce_type(&mut self, _type_: &Type, new: Type, span: &Span) -> Result<Type> {
+        if self.stage >= Stage::Canonicalized && new.is_self() {
+            self.violation("`Self` type survived canonicalization", span);
+        }
+        if self.stage >= Stage::TypeChecked && !self.recovered {
+            if let Type::Err(err) = &new {
+                self.violation("error placeholder survived type checking", &err.span);
+            }
+        }
+        Ok(new)
+    }
+
+    fn reduce_expression(&mut self, _expression: &Expression, new: Expression) -> Result<Expression> {
+        self.check_span("expression", new.span());
+        if self.stage >= Stage::Canonicalized && matches!(new, Expression::ArrayComprehension(_)) {
+            self.violation("array comprehension survived canonicalization", new.span());
+        }
+        if self.stage >= Stage::TypeChecked && !self.recovered && matches!(new, Expression::Err(_)) {
+            self.violation("error placeholder survived type checking", new.span());
+        }
+        Ok(new)
+    }
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        self.check_span("identifier", &identifier.span);
+        Ok(identifier.clone())
+    }
+
+    fn reduce_statement(&mut self, _statement: &Statement, new: Statement) -> Result<Statement> {
+        self.check_span("statement", new.span());
+        Ok(new)
+    }
+
+    fn reduce_definition(
+        &mut self,
+        definition: &DefinitionStatement,
+        variable_names: Vec<VariableName>,
+        type_: Option<Type>,
+        value: Option<Expression>,
+    ) -> Result<DefinitionStatement> {
+        if variable_names.is_empty() {
+            self.violation("definition declares no variables", &definition.span);
+        }
+        if value.is_none() && (definition.declaration_type == Declare::Const || type_.is_none()) {
+            self.violation(
+                "definition without a value is not a `let` with a type",
+                &definition.span,
+            );
+        }
+
+        Ok(DefinitionStatement {
+            declaration_type: definition.declaration_type.clone(),
+            variable_names,
+            type_,
+            value,
+            span: definition.span.clone(),
+        })
+    }
+
+    fn reduce_assign(
+        &mut self,
+        assign: &AssignStatement,
+        assignee: Assignee,
+        value: Expression,
+    ) -> Result<AssignStatement> {
+        if self.stage >= Stage::Canonicalized && assign.operation != AssignOperation::Assign {
+            self.violation(
+                format!(
+                    "compound assignment `{}` survived canonicalization",
+                    assign.operation.as_ref()
+                ),
+                &assign.span,
+            );
+        }
+
+        Ok(AssignStatement {
+            operation: assign.operation,
+            assignee,
+            value,
+            span: assign.span.clone(),
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_program(
+        &mut self,
+        program: &Program,
+        expected_input: Vec<FunctionInput>,
+        import_statements: Vec<ImportStatement>,
+        imports: IndexMap<Vec<String>, Program>,
+        aliases: IndexMap<Identifier, Alias>,
+        circuits: IndexMap<Identifier, Circuit>,
+        functions: IndexMap<Identifier, Function>,
+        global_consts: IndexMap<Vec<Identifier>, DefinitionStatement>,
+    ) -> Result<Program> {
+        if self.stage >= Stage::Canonicalized && !program.expected_input.eq_ignore_span(&program.entrypoint_input()) {
+            self.violation(
+                "expected input does not match the parameters of `main`",
+                &Span::default(),
+            );
+        }
+
+        Ok(Program {
+            name: program.name.clone(),
+            expected_input,
+            import_statements,
+            imports,
+            aliases,
+            circuits,
+            functions,
+            global_consts,
+            recovered: program.recovered.clone(),
+        })
+    }
+
+    fn reduce_function(
+        &mut self,
+        function: &Function,
+        identifier: Identifier,
+        annotations: Vec<Annotation>,
+        input: Vec<FunctionInput>,
+        output: Option<Type>,
+        block: Block,
+    ) -> Result<Function> {
+        self.check_span("function", &function.span);
+        if self.stage >= Stage::Canonicalized && output.is_none() {
+            self.violation(
+                format!(
+                    "function '{}' has no output type after canonicalization",
+                    identifier.name
+                ),
+                &function.span,
+            );
+        }
+
+        Ok(Function {
+            identifier,
+            annotations,
+            input,
+            output,
+            block,
+            span: function.span.clone(),
+        })
+    }
+}
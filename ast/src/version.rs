@@ -0,0 +1,198 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Versions stamped into the artifacts written by the compiler, and checked when they are read back.
+//!
+//! Every artifact records the version of the compiler that produced it and the version of its format.
+//! Each kind of artifact declares its [`ArtifactFormat`] next to its serializer.
+
+use leo_errors::{AstError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The version of the compiler, stamped into every artifact.
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The key of the stamp in JSON artifacts, and its label in text artifacts.
+pub const STAMP_KEY: &str = "leo_artifact";
+
+/// The format of a kind of artifact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArtifactFormat {
+    /// The name of the kind of artifact, e.g. `ast`.
+    pub kind: &'static str,
+    /// The version of the format, bumped whenever the artifact changes in a way older readers cannot handle.
+    pub version: u32,
+    /// Whether the artifact is a JSON object, rather than text that allows `//` comments.
+    pub json: bool,
+}
+
+/// The stamp embedded in an artifact.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactStamp {
+    pub kind: String,
+    pub format: u32,
+    pub compiler: String,
+    /// The digest of the sources the artifact was compiled from, if they were known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// The digest of the compiler options the artifact was compiled with, if they were known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<String>,
+}
+
+impl ArtifactStamp {
+    ///
+    /// Reads the stamp embedded in an artifact of any format, returning `None` if it is not stamped.
+    ///
+    pub fn read(bytes: &[u8]) -> Result<Option<ArtifactStamp>> {
+        let prefix = format!("// {}: ", STAMP_KEY);
+        let stamp = if bytes.starts_with(prefix.as_bytes()) {
+            let line_end = bytes.iter().position(|b| *b == b'\n').unwrap_or_else(|| bytes.len());
+            serde_json::from_slice(&bytes[prefix.len()..line_end]).map(Some)
+        } else {
+            let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+            deserializer.disable_recursion_limit();
+            match Stamped::deserialize(serde_stacker::Deserializer::new(&mut deserializer)) {
+                Ok(Stamped { stamp: Some(stamp) }) => serde_json::from_value(stamp).map(Some),
+                _ => Ok(None),
+            }
+        };
+        Ok(stamp.map_err(|e| AstError::invalid_artifact_stamp("unknown", e))?)
+    }
+}
+
+impl fmt::Display for ArtifactStamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "leo {} ({} format {})", self.compiler, self.kind, self.format)
+    }
+}
+
+#[derive(Deserialize)]
+struct Stamped {
+    #[serde(rename = "leo_artifact")]
+    stamp: Option<serde_json::Value>,
+}
+
+impl ArtifactFormat {
+    /// Returns the stamp of the artifacts of this format written by this compiler.
+    pub fn current(&self) -> ArtifactStamp {
+        ArtifactStamp {
+            kind: self.kind.to_string(),
+            format: self.version,
+            compiler: COMPILER_VERSION.to_string(),
+            source: None,
+            options: None,
+        }
+    }
+
+    ///
+    /// Returns the artifact with the current stamp embedded.
+    ///
+    /// JSON objects get the stamp as their first member, on a line of its own.
+    /// Text artifacts get it as a leading `//` comment line.
+    ///
+    pub fn stamp(&self, bytes: &[u8]) -> Vec<u8> {
+        self.stamp_with(&self.current(), bytes)
+    }
+
+    ///
+    /// Returns the artifact with the given stamp embedded, as [`ArtifactFormat::stamp`] does.
+    ///
+    pub fn stamp_with(&self, stamp: &ArtifactStamp, bytes: &[u8]) -> Vec<u8> {
+        let stamp = serde_json::to_string(stamp).expect("failed to serialize artifact stamp");
+        if !self.json {
+            let mut stamped = format!("// {}: {}\n", STAMP_KEY, stamp).into_bytes();
+            stamped.extend_from_slice(bytes);
+            return stamped;
+        }
+
+        let open = match bytes.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(open) if bytes[open] == b'{' => open,
+            _ => return bytes.to_vec(),
+        };
+        let rest = &bytes[open + 1..];
+        let mut stamped = bytes[..=open].to_vec();
+        if rest.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'}') {
+            stamped.extend(format!("\n  \"{}\": {}\n}}", STAMP_KEY, stamp).bytes());
+        } else {
+            stamped.extend(format!("\n  \"{}\": {},", STAMP_KEY, stamp).bytes());
+            stamped.extend_from_slice(rest);
+        }
+        stamped
+    }
+
+    ///
+    /// Returns the artifact without the stamp embedded by [`ArtifactFormat::stamp`], as it was serialized.
+    /// Artifacts that were not stamped, or were reformatted since, are returned as is.
+    ///
+    pub fn unstamp(&self, bytes: &[u8]) -> Vec<u8> {
+        if !self.json {
+            let prefix = format!("// {}: ", STAMP_KEY);
+            return match bytes.iter().position(|b| *b == b'\n') {
+                Some(line_end) if bytes.starts_with(prefix.as_bytes()) => bytes[line_end + 1..].to_vec(),
+                _ => bytes.to_vec(),
+            };
+        }
+
+        let prefix = format!("{{\n  \"{}\": ", STAMP_KEY);
+        if !bytes.starts_with(prefix.as_bytes()) {
+            return bytes.to_vec();
+        }
+        match bytes[prefix.len()..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|x| x + prefix.len())
+        {
+            // The stamp is followed by the other members of the object.
+            Some(line_end) if bytes[line_end - 1] == b',' => [&bytes[..1], &bytes[line_end..]].concat(),
+            // The stamp is the only member.
+            Some(_) => b"{}".to_vec(),
+            None => bytes.to_vec(),
+        }
+    }
+
+    ///
+    /// Checks the stamp read from an artifact of this format.
+    ///
+    /// Unstamped artifacts, such as those serialized directly rather than written by the compiler, are accepted.
+    ///
+    pub fn check(&self, stamp: Option<serde_json::Value>) -> Result<()> {
+        let stamp: ArtifactStamp = match stamp {
+            Some(stamp) => serde_json::from_value(stamp).map_err(|e| AstError::invalid_artifact_stamp(self.kind, e))?,
+            None => return Ok(()),
+        };
+        if stamp.kind != self.kind || stamp.format != self.version {
+            return Err(AstError::incompatible_artifact_version(self.kind, stamp, self.current()).into());
+        }
+        Ok(())
+    }
+
+    ///
+    /// Checks the stamp of a JSON artifact of this format.
+    ///
+    /// Malformed JSON is left for the deserializer of the artifact to report.
+    ///
+    pub fn check_json(&self, json: &str) -> Result<()> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        deserializer.disable_recursion_limit();
+        match Stamped::deserialize(serde_stacker::Deserializer::new(&mut deserializer)) {
+            Ok(stamped) => self.check(stamped.stamp),
+            Err(_) => Ok(()),
+        }
+    }
+}
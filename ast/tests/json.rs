@@ -0,0 +1,30 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::Ast;
+
+// Round trips through strings only, so that it also runs without the `std` feature.
+#[test]
+fn test_json_string_round_trip() {
+    let json = include_str!("../../parser/tests/serialization/expected_leo_ast/one_plus_one.json");
+
+    let ast = Ast::from_json_string(json).unwrap();
+    let serialized = ast.to_json_string().unwrap();
+    let deserialized = Ast::from_json_string(&serialized).unwrap();
+
+    assert_eq!(ast.as_repr(), deserialized.as_repr());
+    assert_eq!(serialized, deserialized.to_json_string().unwrap());
+}
@@ -0,0 +1,201 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Destinations for the artifacts produced by the compiler.
+
+use leo_ast::{ArtifactFormat, ArtifactStamp};
+use leo_errors::{CompilerError, Result};
+
+use indexmap::IndexMap;
+use std::{cell::RefCell, fs, path::PathBuf, rc::Rc};
+
+/// A destination for the artifacts produced by the compiler, such as AST snapshots.
+pub trait ArtifactSink {
+    /// Writes the artifact with the given file name, which may contain `/`-separated directories.
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Writes the artifact of the given format with the stamp embedded.
+    fn write_stamped(
+        &mut self,
+        format: &ArtifactFormat,
+        stamp: &ArtifactStamp,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<()> {
+        self.write(name, &format.stamp_with(stamp, bytes))
+    }
+
+    /// Returns the problems the sink ran into that did not stop the compilation.
+    fn warnings(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+///
+/// Writes artifacts as files in a directory, which is created along with its parents on the first write.
+///
+/// Artifacts are a by-product of the compilation, so a directory that cannot be written does not fail it.
+/// The first failure is reported once as a warning and every later artifact is skipped.
+///
+#[derive(Clone, Debug)]
+pub struct DirectorySink {
+    directory: PathBuf,
+    disabled: Option<String>,
+}
+
+impl DirectorySink {
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            disabled: None,
+        }
+    }
+
+    /// Returns why artifacts are no longer written, if a write failed.
+    pub fn disabled(&self) -> Option<&str> {
+        self.disabled.as_deref()
+    }
+
+    fn try_write(&self, name: &str, bytes: &[u8]) -> std::io::Result<()> {
+        let path = self.directory.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, bytes)
+    }
+}
+
+impl ArtifactSink for DirectorySink {
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<()> {
+        if self.disabled.is_some() {
+            return Ok(());
+        }
+        if let Err(error) = self.try_write(name, bytes) {
+            let reason = format!("failed to write `{}` to {}: {}", name, self.directory.display(), error);
+            tracing::warn!("artifacts disabled: {}", reason);
+            self.disabled = Some(reason);
+        }
+        Ok(())
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.disabled
+            .iter()
+            .map(|reason| format!("artifacts disabled: {}", reason))
+            .collect()
+    }
+}
+
+/// Keeps artifacts in memory, keyed by file name. Clones share the same storage.
+#[derive(Clone, Debug, Default)]
+pub struct MemorySink {
+    artifacts: Rc<RefCell<IndexMap<String, Vec<u8>>>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the contents of the artifact with the given file name.
+    pub fn get(&self, name: &str) -> Option<Vec<u8>> {
+        self.artifacts.borrow().get(name).cloned()
+    }
+
+    /// Returns every artifact written so far, in the order they were first written.
+    pub fn artifacts(&self) -> IndexMap<String, Vec<u8>> {
+        self.artifacts.borrow().clone()
+    }
+}
+
+impl ArtifactSink for MemorySink {
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<()> {
+        self.artifacts.borrow_mut().insert(name.to_string(), bytes.to_vec());
+        Ok(())
+    }
+}
+
+///
+/// Writes the artifacts of a compilation unit under `<unit>/` in another sink.
+///
+pub(crate) struct PrefixedSink<'s> {
+    unit: &'s str,
+    sink: &'s mut dyn ArtifactSink,
+}
+
+impl<'s> PrefixedSink<'s> {
+    pub(crate) fn new(unit: &'s str, sink: &'s mut dyn ArtifactSink) -> Self {
+        Self { unit, sink }
+    }
+}
+
+impl ArtifactSink for PrefixedSink<'_> {
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<()> {
+        self.sink.write(&format!("{}/{}", self.unit, name), bytes)
+    }
+
+    fn write_stamped(
+        &mut self,
+        format: &ArtifactFormat,
+        stamp: &ArtifactStamp,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<()> {
+        self.sink
+            .write_stamped(format, stamp, &format!("{}/{}", self.unit, name), bytes)
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.sink.warnings()
+    }
+}
+
+///
+/// The compilation units of a session whose programs share an output directory. Clones share the same units.
+///
+/// Every compiler joining the session writes its artifacts under `<unit>/`, so the programs do not overwrite
+/// each other's artifacts. The unit is [`crate::CompilerOptions::unit_name`], or the stem of the main file.
+///
+#[derive(Clone, Debug, Default)]
+pub struct CompilationUnits {
+    /// Maps the units => the main file of the program compiled as the unit.
+    units: Rc<RefCell<IndexMap<String, String>>>,
+}
+
+impl CompilationUnits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Adds the unit compiled from `source` to the session.
+    ///
+    /// Returns an error naming both sources if another program of the session is compiled as the same unit.
+    ///
+    pub fn register(&self, unit: &str, source: &str) -> Result<()> {
+        let mut units = self.units.borrow_mut();
+        if let Some(first) = units.get(unit) {
+            return Err(CompilerError::duplicate_compilation_unit(unit, first, source).into());
+        }
+        units.insert(unit.to_string(), source.to_string());
+        Ok(())
+    }
+
+    /// Returns every unit of the session with its main file, in the order they joined.
+    pub fn units(&self) -> IndexMap<String, String> {
+        self.units.borrow().clone()
+    }
+}
@@ -0,0 +1,643 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Summarizes a checked program on one page, for the audit of its circuit.
+
+use crate::{CostEstimate, Interface};
+use leo_asg::{Circuit, CircuitMember, DefinitionStatement, ExpressionNode, Function, Layout};
+use leo_ast::{
+    ArtifactFormat, AstIter, AstNode, AstRoot, ConsoleFunction, Program as AstProgram, Statement, Traversal,
+};
+use leo_errors::{CompilerError, Result};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// The format of audit reports, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const AUDIT_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "audit",
+    version: 1,
+    json: true,
+};
+
+/// A section of an audit report, which gives the reason it is left out when what it summarizes is not known.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditSection<T> {
+    Available { content: T },
+    Unavailable { reason: String },
+}
+
+impl<T> AuditSection<T> {
+    /// Returns the content of the section, if it is available.
+    pub fn content(&self) -> Option<&T> {
+        match self {
+            AuditSection::Available { content } => Some(content),
+            AuditSection::Unavailable { .. } => None,
+        }
+    }
+}
+
+/// An entrypoint, with the interface its input file and output registers follow.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntrypoint {
+    /// The declaration of the entrypoint, e.g. `function main(a: u32) -> u32`.
+    pub signature: String,
+    pub interface: Interface,
+}
+
+impl AuditEntrypoint {
+    pub fn new(function: &Function) -> Self {
+        let interface = Interface::new(function);
+        let parameters = interface
+            .parameters
+            .iter()
+            .map(|parameter| match parameter.const_ {
+                true => format!("const {}: {}", parameter.name, parameter.type_),
+                false => format!("{}: {}", parameter.name, parameter.type_),
+            })
+            .collect::<Vec<_>>();
+        AuditEntrypoint {
+            signature: format!(
+                "function {}({}) -> {}",
+                interface.entrypoint,
+                parameters.join(", "),
+                function.output
+            ),
+            interface,
+        }
+    }
+}
+
+/// The size of a function of the program after all AST passes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditFunction {
+    /// The name of the function, or `Circuit::function` for circuit member functions.
+    pub name: String,
+    pub statements: usize,
+    pub expressions: usize,
+    /// The number of places the function is called from.
+    pub calls: usize,
+    pub entrypoint: bool,
+}
+
+/// The estimated size of the circuit of a function, see [`CostEstimate`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEstimate {
+    pub function: String,
+    /// The body of the function.
+    pub own: CostEstimate,
+    /// The body of the function with every call inlined.
+    pub total: CostEstimate,
+}
+
+/// A member variable of a circuit, with the scalar slots it flattens to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditMember {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub slots: usize,
+}
+
+/// The layout of a circuit type.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCircuit {
+    pub name: String,
+    /// The member variables, in the order of their slots.
+    pub members: Vec<AuditMember>,
+    /// The scalar slots of a value of the circuit.
+    pub slots: usize,
+    pub functions: Vec<String>,
+}
+
+impl AuditCircuit {
+    pub fn new(circuit: &Circuit) -> Self {
+        let mut members = vec![];
+        let mut functions = vec![];
+        for (name, member) in circuit.members.borrow().iter() {
+            match member {
+                CircuitMember::Variable(type_) => members.push(AuditMember {
+                    name: name.clone(),
+                    type_: type_.to_string(),
+                    slots: Layout::slot_count(type_),
+                }),
+                CircuitMember::Function(_) => functions.push(name.clone()),
+                CircuitMember::Static(..) => (),
+            }
+        }
+        AuditCircuit {
+            name: circuit.name.borrow().name.to_string(),
+            slots: members
+                .iter()
+                .fold(0usize, |slots, member| slots.saturating_add(member.slots)),
+            members,
+            functions,
+        }
+    }
+}
+
+/// A global constant, with the value it folded to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditConstant {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub value: Option<String>,
+}
+
+impl AuditConstant {
+    pub fn new(name: &str, definition: &DefinitionStatement) -> Self {
+        let value = definition.value.get();
+        AuditConstant {
+            name: name.to_string(),
+            type_: value.get_type().map(|type_| type_.to_string()),
+            value: value.const_value().map(|value| value.to_string()),
+        }
+    }
+}
+
+/// What constant folding computed at compile time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditConstants {
+    pub global_consts: Vec<AuditConstant>,
+    /// The calls evaluated by constant folding.
+    pub calls_evaluated: usize,
+    /// The calls whose value was reused from an earlier evaluation.
+    pub calls_reused: usize,
+}
+
+/// A `console.assert` of the program as written, before the asserts that always hold are removed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssertSite {
+    /// The function of the assert, or `Circuit::function` for circuit member functions.
+    pub function: String,
+    /// The statement, e.g. `console.assert(a == b);`.
+    pub statement: String,
+    /// The span of the statement, as `path:line:columns`.
+    pub location: String,
+}
+
+///
+/// Returns every assert of the program, in source order.
+///
+/// Only the program itself is searched, not its imports.
+///
+pub fn collect_asserts(program: &AstProgram) -> Vec<AssertSite> {
+    AstIter::program(program, Traversal::PreOrder)
+        .filter_map(|(node, path)| {
+            let console = match node {
+                AstNode::Statement(Statement::Console(console)) => console,
+                _ => return None,
+            };
+            match &console.function {
+                ConsoleFunction::Assert(_) | ConsoleFunction::AssertEq(_) | ConsoleFunction::AssertNe(_) => (),
+                ConsoleFunction::Error(_) | ConsoleFunction::Log(_) => return None,
+            }
+            let function = match &path.root {
+                AstRoot::Function(name) => name.clone(),
+                AstRoot::CircuitFunction(circuit, name) => format!("{}::{}", circuit, name),
+                AstRoot::CircuitStatic(..) | AstRoot::GlobalConst(_) => return None,
+            };
+            Some(AssertSite {
+                function,
+                statement: console.to_string(),
+                location: format!("{}:{}", console.span.path, console.span),
+            })
+        })
+        .collect()
+}
+
+/// The asserts of the program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditAsserts {
+    pub sites: Vec<AssertSite>,
+    /// The asserts removed because their condition folded to `true`.
+    pub removed_as_constant: usize,
+    /// The asserts removed because the ranges of the types of their operands decide them.
+    pub removed_from_types: usize,
+}
+
+/// The build the report is of, as stamped into its artifacts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditBuild {
+    /// The version of the compiler.
+    pub compiler: String,
+    /// The SHA256 checksum of the main program file, if its source is known.
+    pub source_checksum: Option<String>,
+    /// The digest of the main program file and every file it imports, if the source of the main file is known.
+    pub source_digest: Option<String>,
+    pub options_digest: String,
+    /// The compiler options that differ from their defaults, by dotted name.
+    pub options: BTreeMap<String, String>,
+}
+
+///
+/// A summary of a program for auditors: its entrypoints and their interface, the size of its functions,
+/// the layout of its circuits, what was computed at compile time, its asserts, and the build it is of.
+///
+/// Written as `audit.md` and `audit.json` by [`crate::Compiler::write_audit_report`].
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub program: String,
+    pub entrypoints: AuditSection<Vec<AuditEntrypoint>>,
+    pub functions: AuditSection<Vec<AuditFunction>>,
+    pub estimates: AuditSection<Vec<AuditEstimate>>,
+    pub circuits: AuditSection<Vec<AuditCircuit>>,
+    pub constants: AuditSection<AuditConstants>,
+    pub asserts: AuditSection<AuditAsserts>,
+    pub build: AuditBuild,
+}
+
+impl AuditReport {
+    /// Serializes the report into a JSON string, which follows [`AuditReport::json_schema`].
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(
+            serde_json::to_string_pretty(self)
+                .map_err(|e| CompilerError::failed_to_convert_audit_report_to_json(&e))?,
+        )
+    }
+
+    ///
+    /// Returns the JSON schema of the report.
+    ///
+    /// Objects have no members besides those of the schema, except for the stamp of the `audit.json` artifact.
+    ///
+    pub fn json_schema() -> Value {
+        let string = json!({ "type": "string" });
+        let count = json!({ "type": "integer", "minimum": 0 });
+        let optional_string = json!({ "type": ["string", "null"] });
+        let estimate = object(&[("instructions", count.clone()), ("constraints", count.clone())]);
+        let interface = object(&[
+            ("entrypoint", string.clone()),
+            (
+                "parameters",
+                array(object(&[
+                    ("name", string.clone()),
+                    ("type", string.clone()),
+                    ("const", json!({ "type": "boolean" })),
+                    ("slots", count.clone()),
+                ])),
+            ),
+            ("outputs", array(string.clone())),
+            (
+                "output_slots",
+                array(object(&[("path", string.clone()), ("type", string.clone())])),
+            ),
+        ]);
+
+        let mut schema = object(&[
+            ("program", string.clone()),
+            (
+                "entrypoints",
+                section(array(object(&[
+                    ("signature", string.clone()),
+                    ("interface", interface),
+                ]))),
+            ),
+            (
+                "functions",
+                section(array(object(&[
+                    ("name", string.clone()),
+                    ("statements", count.clone()),
+                    ("expressions", count.clone()),
+                    ("calls", count.clone()),
+                    ("entrypoint", json!({ "type": "boolean" })),
+                ]))),
+            ),
+            (
+                "estimates",
+                section(array(object(&[
+                    ("function", string.clone()),
+                    ("own", estimate.clone()),
+                    ("total", estimate),
+                ]))),
+            ),
+            (
+                "circuits",
+                section(array(object(&[
+                    ("name", string.clone()),
+                    (
+                        "members",
+                        array(object(&[
+                            ("name", string.clone()),
+                            ("type", string.clone()),
+                            ("slots", count.clone()),
+                        ])),
+                    ),
+                    ("slots", count.clone()),
+                    ("functions", array(string.clone())),
+                ]))),
+            ),
+            (
+                "constants",
+                section(object(&[
+                    (
+                        "global_consts",
+                        array(object(&[
+                            ("name", string.clone()),
+                            ("type", optional_string.clone()),
+                            ("value", optional_string.clone()),
+                        ])),
+                    ),
+                    ("calls_evaluated", count.clone()),
+                    ("calls_reused", count.clone()),
+                ])),
+            ),
+            (
+                "asserts",
+                section(object(&[
+                    (
+                        "sites",
+                        array(object(&[
+                            ("function", string.clone()),
+                            ("statement", string.clone()),
+                            ("location", string.clone()),
+                        ])),
+                    ),
+                    ("removed_as_constant", count.clone()),
+                    ("removed_from_types", count.clone()),
+                ])),
+            ),
+            (
+                "build",
+                object(&[
+                    ("compiler", string.clone()),
+                    ("source_checksum", optional_string.clone()),
+                    ("source_digest", optional_string),
+                    ("options_digest", string.clone()),
+                    ("options", json!({ "type": "object", "additionalProperties": string })),
+                ]),
+            ),
+        ]);
+        schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+        schema["title"] = json!("Leo audit report");
+        schema["properties"][leo_ast::STAMP_KEY] = json!({ "type": "object" });
+        schema
+    }
+
+    ///
+    /// Renders the report as a Markdown page, with a section for each part of the report.
+    ///
+    /// A section that is not available says why instead.
+    ///
+    pub fn to_markdown(&self) -> String {
+        let mut page = String::new();
+        // Writing to a string does not fail.
+        self.write_markdown(&mut page).unwrap();
+        page
+    }
+
+    fn write_markdown(&self, page: &mut String) -> std::fmt::Result {
+        writeln!(page, "# Audit of `{}`", self.program)?;
+
+        writeln!(page, "\n## Entrypoints\n")?;
+        if let Some(entrypoints) = write_section(page, &self.entrypoints)? {
+            for entrypoint in entrypoints.iter() {
+                let interface = &entrypoint.interface;
+                writeln!(page, "### `{}`\n", interface.entrypoint)?;
+                writeln!(page, "`{}`\n", entrypoint.signature)?;
+                writeln!(page, "| Input | Type | Const | Slots |\n| --- | --- | --- | --- |")?;
+                for parameter in interface.parameters.iter() {
+                    writeln!(
+                        page,
+                        "| {} | {} | {} | {} |",
+                        code(&parameter.name),
+                        code(&parameter.type_),
+                        if parameter.const_ { "yes" } else { "no" },
+                        parameter.slots
+                    )?;
+                }
+                writeln!(page, "\n| Output | Type |\n| --- | --- |")?;
+                for slot in interface.output_slots.iter() {
+                    writeln!(page, "| {} | {} |", code(&slot.path), code(&slot.type_))?;
+                }
+                writeln!(page)?;
+            }
+        }
+
+        writeln!(page, "## Functions\n")?;
+        if let Some(functions) = write_section(page, &self.functions)? {
+            writeln!(page, "{} functions.\n", functions.len())?;
+            writeln!(
+                page,
+                "| Function | Statements | Expressions | Calls | Entrypoint |\n| --- | --- | --- | --- | --- |"
+            )?;
+            for function in functions.iter() {
+                writeln!(
+                    page,
+                    "| {} | {} | {} | {} | {} |",
+                    code(&function.name),
+                    function.statements,
+                    function.expressions,
+                    function.calls,
+                    if function.entrypoint { "yes" } else { "no" }
+                )?;
+            }
+            writeln!(page)?;
+        }
+
+        writeln!(page, "## Estimates\n")?;
+        if let Some(estimates) = write_section(page, &self.estimates)? {
+            writeln!(
+                page,
+                "Operations on constants cost nothing. Every other operation is an instruction costing a constraint \
+                 per bit of the integers it operates on, so constraints are a rough estimate.\n"
+            )?;
+            writeln!(
+                page,
+                "| Function | Instructions | Constraints | With calls: instructions | With calls: constraints |\n\
+                 | --- | --- | --- | --- | --- |"
+            )?;
+            for estimate in estimates.iter() {
+                writeln!(
+                    page,
+                    "| {} | {} | {} | {} | {} |",
+                    code(&estimate.function),
+                    estimate.own.instructions,
+                    estimate.own.constraints,
+                    estimate.total.instructions,
+                    estimate.total.constraints
+                )?;
+            }
+            writeln!(page)?;
+        }
+
+        writeln!(page, "## Circuits\n")?;
+        if let Some(circuits) = write_section(page, &self.circuits)? {
+            if circuits.is_empty() {
+                writeln!(page, "No circuits.\n")?;
+            }
+            for circuit in circuits.iter() {
+                writeln!(page, "### `{}`\n", circuit.name)?;
+                writeln!(page, "{} slots.\n", circuit.slots)?;
+                writeln!(page, "| Member | Type | Slots |\n| --- | --- | --- |")?;
+                for member in circuit.members.iter() {
+                    writeln!(
+                        page,
+                        "| {} | {} | {} |",
+                        code(&member.name),
+                        code(&member.type_),
+                        member.slots
+                    )?;
+                }
+                if !circuit.functions.is_empty() {
+                    let functions = circuit
+                        .functions
+                        .iter()
+                        .map(String::as_str)
+                        .map(code)
+                        .collect::<Vec<_>>();
+                    writeln!(page, "\nFunctions: {}.", functions.join(", "))?;
+                }
+                writeln!(page)?;
+            }
+        }
+
+        writeln!(page, "## Constants\n")?;
+        if let Some(constants) = write_section(page, &self.constants)? {
+            if !constants.global_consts.is_empty() {
+                writeln!(page, "| Constant | Type | Value |\n| --- | --- | --- |")?;
+                for constant in constants.global_consts.iter() {
+                    writeln!(
+                        page,
+                        "| {} | {} | {} |",
+                        code(&constant.name),
+                        constant.type_.as_deref().map(code).unwrap_or_default(),
+                        constant
+                            .value
+                            .as_deref()
+                            .map(code)
+                            .unwrap_or_else(|| "not constant".to_string())
+                    )?;
+                }
+                writeln!(page)?;
+            }
+            writeln!(
+                page,
+                "{} calls evaluated at compile time, {} of them reused from an earlier evaluation.\n",
+                constants.calls_evaluated + constants.calls_reused,
+                constants.calls_reused
+            )?;
+        }
+
+        writeln!(page, "## Asserts\n")?;
+        if let Some(asserts) = write_section(page, &self.asserts)? {
+            if asserts.sites.is_empty() {
+                writeln!(page, "No asserts.\n")?;
+            } else {
+                writeln!(page, "| Function | Assert | Location |\n| --- | --- | --- |")?;
+                for site in asserts.sites.iter() {
+                    writeln!(
+                        page,
+                        "| {} | {} | {} |",
+                        code(&site.function),
+                        code(&site.statement),
+                        code(&site.location)
+                    )?;
+                }
+                writeln!(page)?;
+            }
+            writeln!(
+                page,
+                "{} asserts removed because they always hold: {} folded to `true`, {} decided by the types of their operands.\n",
+                asserts.removed_as_constant + asserts.removed_from_types,
+                asserts.removed_as_constant,
+                asserts.removed_from_types
+            )?;
+        }
+
+        let build = &self.build;
+        let unknown = "unknown, the source of the main file is not known".to_string();
+        writeln!(page, "## Build\n")?;
+        writeln!(page, "- Compiler: leo {}", build.compiler)?;
+        writeln!(
+            page,
+            "- Source checksum: {}",
+            build
+                .source_checksum
+                .as_deref()
+                .map(code)
+                .unwrap_or_else(|| unknown.clone())
+        )?;
+        writeln!(
+            page,
+            "- Source digest, with imports: {}",
+            build.source_digest.as_deref().map(code).unwrap_or(unknown)
+        )?;
+        writeln!(page, "- Options digest: {}", code(&build.options_digest))?;
+        if build.options.is_empty() {
+            writeln!(page, "- Options: the defaults")?;
+        } else {
+            writeln!(page, "- Options changed from the defaults:")?;
+            for (name, value) in build.options.iter() {
+                writeln!(page, "  - {}", code(&format!("{} = {}", name, value)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes why the section is not available, or returns its content.
+fn write_section<'a, T>(page: &mut String, section: &'a AuditSection<T>) -> Result<Option<&'a T>, std::fmt::Error> {
+    match section {
+        AuditSection::Available { content } => Ok(Some(content)),
+        AuditSection::Unavailable { reason } => {
+            writeln!(page, "_Not available: {}._\n", reason)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the text as inline code, which may be in a table cell.
+fn code(text: &str) -> String {
+    format!("`{}`", text.replace('|', "\\|"))
+}
+
+/// Returns the schema of an object with exactly the given members.
+fn object(members: &[(&str, Value)]) -> Value {
+    let properties = members
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect::<serde_json::Map<_, _>>();
+    let required = members.iter().map(|(name, _)| json!(name)).collect::<Vec<_>>();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// Returns the schema of an [`AuditSection`] with the given content.
+fn section(content: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "status": { "enum": ["available", "unavailable"] },
+            "content": content,
+            "reason": { "type": "string" },
+        },
+        "required": ["status"],
+        "additionalProperties": false,
+    })
+}
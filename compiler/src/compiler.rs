@@ -16,28 +16,49 @@
 
 //! Compiles a Leo program from a file path.
 use crate::{
-    constraints::{generate_constraints, generate_test_constraints},
-    AstSnapshotOptions, CompilerOptions, GroupType, Output, OutputFile, TypeInferencePhase,
+    constraints::{generate_constraints, generate_entrypoint_constraints, generate_test_constraints, Synthesis},
+    reproducibility::source_digest,
+    ArtifactSink, AssertSite, AstSnapshotOptions, AuditAsserts, AuditBuild, AuditCircuit, AuditConstant,
+    AuditConstants, AuditEntrypoint, AuditEstimate, AuditFunction, AuditReport, AuditSection, CompilationUnits,
+    CompilerOptions, DirectorySink, FileName, FileSystemSources, FunctionCost, GroupType, InputSlots, IntegerWidening,
+    Interface, Output, OutputFile, PrefixedSink, SignatureManifest, SourceProvider, TypeInferencePhase,
+    VerificationReport, AUDIT_FORMAT, INTERFACE_FORMAT, OUTPUT_FORMAT, PROVENANCE_FORMAT,
 };
 pub use leo_asg::{new_context, AsgContext as Context, AsgContext};
-use leo_asg::{Asg, AsgPass, Program as AsgProgram};
-use leo_ast::{AstPass, Input, MainInput, Program as AstProgram};
-use leo_errors::{CompilerError, Result};
+use leo_asg::{
+    Asg, AsgPass, AsgWarning, CircuitMember, Dataflow, HoverInfo, Navigation, Position, Program as AsgProgram,
+    SemanticToken, SymbolTable, TypeCacheStatistics, DATAFLOW_FORMAT,
+};
+use leo_asg_passes::{AssertStatistics, BooleanSimplificationStatistics, EvaluationStatistics, InlineDecision};
+use leo_ast::{
+    metrics, validate_pass_output, ArtifactFormat, ArtifactStamp, Ast, AstMetrics, AstPass, Edition, FunctionInput,
+    Input, InputAst, MainInput, NameGenerator, Program as AstProgram, Stage, AST_FORMAT, COMPILER_VERSION,
+    INPUT_AST_FORMAT,
+};
+use leo_ast_passes::{RenameMap, RENAME_MAP_FORMAT};
+use leo_errors::{AsgError, CompilerError, LeoError, Result, Span};
 use leo_imports::ImportParser;
-use leo_input::LeoInputParser;
-use leo_package::inputs::InputPairs;
-use leo_parser::parse_ast;
+use leo_input::{InputParserError, LeoInputParser};
+use leo_package::{
+    inputs::InputPairs,
+    root::{Manifest, MANIFEST_FILENAME},
+};
+use leo_parser::parse_ast_for_edition;
 use leo_state::verify_local_data_commitment;
 
 use snarkvm_dpc::testnet1::{instantiated::Components, parameters::SystemParameters};
 use snarkvm_fields::PrimeField;
-use snarkvm_r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use snarkvm_r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError, TestConstraintSystem};
 
 use sha2::{Digest, Sha256};
 use std::{
+    cell::RefCell,
+    convert::TryFrom,
     fs,
     marker::PhantomData,
     path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
 };
 
 use indexmap::IndexMap;
@@ -54,19 +75,118 @@ pub fn thread_leaked_context() -> AsgContext<'static> {
     THREAD_GLOBAL_CONTEXT.with(|f| *f)
 }
 
+/// The optimization passes run on the ASG of a program, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Optimization {
+    RangeComparison,
+    Constructibility,
+    Naming,
+    ConstantFolding,
+    BooleanSimplification,
+    StaticAsserts,
+    DeadCodeElimination,
+    Usage,
+    Inlining,
+}
+
+const OPTIMIZATIONS: &[Optimization] = &[
+    Optimization::RangeComparison,
+    Optimization::Constructibility,
+    Optimization::Naming,
+    Optimization::ConstantFolding,
+    Optimization::BooleanSimplification,
+    Optimization::StaticAsserts,
+    Optimization::DeadCodeElimination,
+    Optimization::Usage,
+    Optimization::Inlining,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CheckPhase {
+    ImportResolution,
+    Canonicalization,
+    Renaming,
+    TypeChecking,
+    /// The optimization pass at this index of [`OPTIMIZATIONS`].
+    Optimization(usize),
+    Limits,
+    Done,
+}
+
+///
+/// A program part way through being checked by [`Compiler::check_step`].
+///
+pub struct ProgramCheck {
+    /// The syntax tree, until it is type checked.
+    ast: Option<Ast>,
+    next: CheckPhase,
+}
+
+impl ProgramCheck {
+    fn new(ast: Ast) -> Self {
+        Self {
+            ast: Some(ast),
+            next: CheckPhase::ImportResolution,
+        }
+    }
+
+    /// Returns `true` once every phase ran.
+    pub fn is_done(&self) -> bool {
+        self.next == CheckPhase::Done
+    }
+}
+
 /// Stores information to compile a Leo program.
 #[derive(Clone)]
 pub struct Compiler<'a, F: PrimeField, G: GroupType<F>> {
     program_name: String,
-    main_file_path: PathBuf,
+    /// The name of the main program file, which is only read through the source provider.
+    main_file: FileName,
     output_directory: PathBuf,
     program: AstProgram,
     program_input: Input,
+    /// Additional program inputs, keyed by label.
+    inputs: IndexMap<String, Input>,
+    /// The inputs of the entrypoints other than `main`, keyed by entrypoint.
+    entrypoint_inputs: IndexMap<String, Input>,
     context: AsgContext<'a>,
+    /// The name of this compile in the tally of a context that deduplicates warnings, the program name by default.
+    warning_source: String,
+    /// The warnings this compile reported.
+    warnings: Vec<AsgWarning>,
     asg: Option<AsgProgram<'a>>,
+    /// The declarations and references of the last program parsed, as far as it was checked.
+    navigation: Navigation<'a>,
     options: CompilerOptions,
+    /// Whether the edition of the options was chosen by the caller, rather than left to the project config.
+    edition_declared: bool,
     imports_map: IndexMap<String, String>,
     ast_snapshot_options: AstSnapshotOptions,
+    rename_map: Option<RenameMap>,
+    /// Names the variables that passes add to the program, avoiding every identifier of the program.
+    name_generator: NameGenerator,
+    /// The structure of the program after all AST passes.
+    metrics: Option<AstMetrics>,
+    input_slots: Option<InputSlots>,
+    evaluation_statistics: Option<EvaluationStatistics>,
+    boolean_simplification_statistics: Option<BooleanSimplificationStatistics>,
+    assert_statistics: Option<AssertStatistics>,
+    /// Maps the functions that are called => how their calls are compiled.
+    inline_decisions: Option<IndexMap<String, InlineDecision>>,
+    /// Maps every function => its estimated size, if costs are estimated.
+    cost_estimates: Option<IndexMap<String, FunctionCost>>,
+    /// The asserts of the program as written, found once it type checks.
+    asserts: Option<Vec<AssertSite>>,
+    /// The source of the main program file, if it was parsed from source rather than given as a syntax tree.
+    main_source: Option<String>,
+    /// The digest of the main program file and every file it imports, if the source of the main file is known.
+    source_digest: Option<String>,
+    output_writer: Rc<RefCell<dyn ArtifactSink>>,
+    /// The artifacts signed so far, if the options have a signer.
+    signatures: Rc<RefCell<SignatureManifest>>,
+    /// The compilation unit the artifacts are written under, if the compiler joined a session.
+    unit: Option<String>,
+    source_provider: Rc<dyn SourceProvider>,
     _engine: PhantomData<F>,
     _group: PhantomData<G>,
 }
@@ -86,15 +206,37 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
     ) -> Self {
         Self {
             program_name: package_name.clone(),
-            main_file_path,
+            warning_source: package_name.clone(),
+            warnings: vec![],
+            main_file: FileName::Real(main_file_path),
+            output_writer: Rc::new(RefCell::new(DirectorySink::new(output_directory.clone()))),
+            signatures: Rc::new(RefCell::new(SignatureManifest::default())),
+            unit: None,
             output_directory,
             program: AstProgram::new(package_name),
             program_input: Input::new(),
+            inputs: IndexMap::new(),
+            entrypoint_inputs: IndexMap::new(),
             asg: None,
+            navigation: Navigation::default(),
             context,
             options: options.unwrap_or_default(),
+            edition_declared: false,
             imports_map,
             ast_snapshot_options: ast_snapshot_options.unwrap_or_default(),
+            rename_map: None,
+            name_generator: NameGenerator::default(),
+            metrics: None,
+            input_slots: None,
+            evaluation_statistics: None,
+            boolean_simplification_statistics: None,
+            assert_statistics: None,
+            inline_decisions: None,
+            cost_estimates: None,
+            asserts: None,
+            main_source: None,
+            source_digest: None,
+            source_provider: Rc::new(FileSystemSources),
             _engine: PhantomData,
             _group: PhantomData,
         }
@@ -135,92 +277,469 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
         self.options = options;
     }
 
+    ///
+    /// Returns the options this compiler uses, with the defaults of every option that was not given.
+    ///
+    /// Writing them to a file with [`CompilerOptions::to_file`] and loading that file reproduces the build.
+    ///
+    pub fn effective_options(&self) -> &CompilerOptions {
+        &self.options
+    }
+
+    /// Returns `true` if the options ask for any artifact besides the outputs of the circuit.
+    fn artifacts_enabled(&self) -> bool {
+        let snapshots = &self.ast_snapshot_options;
+        snapshots.initial
+            || snapshots.imports_resolved
+            || snapshots.canonicalized
+            || snapshots.type_inferenced
+            || snapshots.input
+            || self.options.renaming_enabled
+            || self.options.emit_dot
+            || self.options.record_provenance
+    }
+
+    ///
+    /// Writes the effective options to the output sink as `effective_options.toml`, if artifacts are enabled.
+    ///
+    fn write_effective_options(&self) -> Result<()> {
+        if !self.artifacts_enabled() {
+            return Ok(());
+        }
+        let toml = format!(
+            "# The compiler options of this build, whose digest `{}` is stamped into its artifacts.\n{}",
+            self.options.digest(),
+            self.options.to_toml_string()?
+        );
+        self.write_output("effective_options.toml", toml.as_bytes())
+    }
+
+    ///
+    /// Returns the compiler with its artifacts redirected to the given sink instead of the output directory.
+    ///
+    pub fn with_output_writer(mut self, sink: impl ArtifactSink + 'static) -> Self {
+        self.output_writer = Rc::new(RefCell::new(sink));
+        self.signatures = Rc::new(RefCell::new(SignatureManifest::default()));
+        self
+    }
+
+    ///
+    /// Returns the compilation unit of the program: the `unit_name` option, or else the stem of the main file.
+    ///
+    pub fn unit_name(&self) -> String {
+        if let Some(unit) = &self.options.unit_name {
+            return unit.clone();
+        }
+        self.main_file
+            .path()
+            .and_then(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.program_name.clone())
+    }
+
+    ///
+    /// Returns the compiler as a unit of `units`, with its artifacts written under `<unit>/`.
+    ///
+    /// Returns an error naming both main files if another program of the session has the same unit.
+    ///
+    pub fn with_compilation_units(mut self, units: &CompilationUnits) -> Result<Self> {
+        let unit = self.unit_name();
+        units.register(&unit, &self.main_file.to_string())?;
+        self.unit = Some(unit);
+        Ok(self)
+    }
+
+    ///
+    /// Returns the compiler with its main program file named `main_file`, which need not be a file.
+    ///
+    /// Diagnostics show the name as given. The imports of a source that is not a file are resolved
+    /// from the current directory, as if the source were the main file of the package there.
+    ///
+    pub fn with_main_file(mut self, main_file: FileName) -> Self {
+        self.main_file = main_file;
+        self
+    }
+
+    ///
+    /// Returns the compiler with the program compiled against `edition`.
+    ///
+    /// Without it, [`Compiler::parse_program`] reads the edition from the `Leo.toml` of the package,
+    /// and warns if there is none.
+    ///
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.options.edition = edition;
+        self.edition_declared = true;
+        self
+    }
+
+    ///
+    /// Returns the compiler with the sources it is given by name read from `provider` instead of the filesystem.
+    ///
+    pub fn with_source_provider(mut self, provider: impl SourceProvider + 'static) -> Self {
+        self.source_provider = Rc::new(provider);
+        self
+    }
+
+    ///
+    /// Returns the compiler with its warnings credited to `source` when the context deduplicates them,
+    /// such as the entrypoint or test it is compiled for.
+    ///
+    pub fn with_warning_source(mut self, source: impl Into<String>) -> Self {
+        self.warning_source = source.into();
+        self
+    }
+
+    ///
+    /// Returns the name of the main program file.
+    ///
+    pub fn main_file(&self) -> &FileName {
+        &self.main_file
+    }
+
+    ///
+    /// Reads the main program file through the source provider.
+    ///
+    fn read_main_file(&self) -> Result<String> {
+        Ok(self
+            .source_provider
+            .read_source(&self.main_file)
+            .map_err(|e| CompilerError::file_read_error(self.main_file.to_string(), e))?)
+    }
+
+    ///
+    /// Returns the warnings about the program reported by this compile.
+    ///
+    /// When the context deduplicates warnings, those that another compile sharing it reported first are left out.
+    ///
+    pub fn warnings(&self) -> &[AsgWarning] {
+        &self.warnings
+    }
+
+    /// Logs the warnings reported to the context since the last call, and keeps them.
+    fn report_warnings(&mut self) {
+        for warning in self.context.take_warnings_from(&self.warning_source) {
+            tracing::warn!("{}", warning);
+            self.warnings.push(warning);
+        }
+    }
+
+    ///
+    /// Returns the problems writing artifacts that did not stop the compilation,
+    /// such as an output directory that cannot be written.
+    ///
+    pub fn artifact_warnings(&self) -> Vec<String> {
+        self.output_writer.borrow().warnings()
+    }
+
+    ///
+    /// Returns the stamp of the artifacts of the given format built by this compiler,
+    /// with the digests of the sources and the options they are built from.
+    ///
+    fn stamp(&self, format: &ArtifactFormat) -> ArtifactStamp {
+        ArtifactStamp {
+            source: self.source_digest.clone(),
+            options: Some(self.options.digest()),
+            ..format.current()
+        }
+    }
+
+    ///
+    /// Writes an artifact of the given format to the output sink, stamped with its version and digests.
+    ///
+    fn write_artifact(&self, format: &ArtifactFormat, name: &str, bytes: &[u8]) -> Result<()> {
+        let stamp = self.stamp(format);
+        self.with_sink(|sink| sink.write_stamped(format, &stamp, name, bytes))?;
+        self.sign_output(name, || format.stamp_with(&stamp, bytes))
+    }
+
+    ///
+    /// Writes bytes to the output sink, along with their signature if the options have a signer.
+    ///
+    fn write_output(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        self.with_sink(|sink| sink.write(name, bytes))?;
+        self.sign_output(name, || bytes.to_vec())
+    }
+
+    ///
+    /// Runs `write` on the output sink, under the directory of the compilation unit if there is one.
+    ///
+    /// The names of the artifacts, their signatures and the signature manifest are all relative to that directory.
+    ///
+    fn with_sink<T>(&self, write: impl FnOnce(&mut dyn ArtifactSink) -> T) -> T {
+        let mut sink = self.output_writer.borrow_mut();
+        match self.unit.as_deref().or_else(|| self.options.unit_name.as_deref()) {
+            Some(unit) => write(&mut PrefixedSink::new(unit, &mut *sink)),
+            None => write(&mut *sink),
+        }
+    }
+
+    ///
+    /// Signs the artifact written with the given name, if the options have a signer.
+    ///
+    fn sign_output(&self, name: &str, bytes: impl FnOnce() -> Vec<u8>) -> Result<()> {
+        match self.options.signer.signer() {
+            Some(signer) => {
+                self.with_sink(|sink| self.signatures.borrow_mut().sign(sink, signer.as_ref(), name, &bytes()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    ///
+    /// Checks that the artifact at the given path was built by this build of the program.
+    ///
+    /// The version of the compiler, the sources, and the options stamped into the artifact
+    /// are compared with those of this compiler, and each of them is reported as matching or changed.
+    ///
+    pub fn verify_artifact(&self, path: &Path) -> Result<VerificationReport> {
+        let bytes = fs::read(path).map_err(|e| CompilerError::file_read_error(path.to_path_buf(), e))?;
+        let stamped =
+            ArtifactStamp::read(&bytes)?.ok_or_else(|| CompilerError::artifact_not_stamped(path.display()))?;
+        let current = ArtifactStamp {
+            compiler: COMPILER_VERSION.to_string(),
+            source: self.source_digest.clone(),
+            options: Some(self.options.digest()),
+            ..stamped.clone()
+        };
+        Ok(VerificationReport::new(&stamped, &current))
+    }
+
+    ///
+    /// Writes the provenance of a synthesized circuit to the output sink, if it was recorded.
+    ///
+    fn write_provenance(&self, name: &str, output: &Output) -> Result<()> {
+        match &output.provenance {
+            Some(provenance) => self.write_artifact(&PROVENANCE_FORMAT, name, provenance.to_json_string()?.as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    ///
+    /// Writes an AST snapshot to the output sink, with spans if enabled.
+    ///
+    fn write_ast_snapshot(&self, ast: &Ast, name: &str) -> Result<()> {
+        let bytes = if self.ast_snapshot_options.spans_enabled {
+            ast.to_json_bytes()?
+        } else {
+            ast.to_json_bytes_without_keys(&["span"])?
+        };
+        self.write_artifact(&AST_FORMAT, name, &bytes)
+    }
+
+    ///
+    /// Checks the structural invariants of the AST produced by the given pass, if verification is enabled.
+    ///
+    fn verify_ast(&self, pass: &str, ast: &Ast, stage: Stage) -> Result<()> {
+        if self.options.verify_ast {
+            validate_pass_output(pass, ast.as_repr(), stage)?;
+        }
+        Ok(())
+    }
+
     ///
     /// Returns a new `Compiler` from the given main file path.
     ///
     /// Parses and stores program input from from the input file path and state file path
-    /// Parses and stores a program from the main file path.
-    /// Parses and stores all imported programs.
-    /// Performs type inference checking on the program, imported programs, and program input.
     ///
-    #[allow(clippy::too_many_arguments)]
-    pub fn parse_program_with_input(
-        package_name: String,
-        main_file_path: PathBuf,
-        output_directory: PathBuf,
+    /// Calls `set_path()` on compiler errors with the given input file name or state file name,
+    /// which need not name files, since the input and state are given as strings.
+    /// If the input has more than one error, they are all reported together.
+    ///
+    pub fn parse_input(
+        &mut self,
         input_string: &str,
-        input_path: &Path,
+        input_name: impl Into<FileName>,
         state_string: &str,
-        state_path: &Path,
-        context: AsgContext<'a>,
-        options: Option<CompilerOptions>,
-        imports_map: IndexMap<String, String>,
-        ast_snapshot_options: Option<AstSnapshotOptions>,
-    ) -> Result<Self> {
-        let mut compiler = Self::new(
-            package_name,
-            main_file_path,
-            output_directory,
-            context,
-            options,
-            imports_map,
-            ast_snapshot_options,
+        state_name: impl Into<FileName>,
+    ) -> Result<()> {
+        let errors = self.parse_input_recovering(input_string, input_name, state_string, state_name);
+        Self::input_result(errors)
+    }
+
+    ///
+    /// Parses and stores program input like `parse_input()`, returning every error instead of the first one.
+    ///
+    /// Malformed entries and sections of the input file are reported and left out.
+    /// Once the program is parsed, the parameters of `main` that the input leaves out are reported as well,
+    /// unless the input has a malformed entry for them.
+    ///
+    pub fn parse_input_recovering(
+        &mut self,
+        input_string: &str,
+        input_name: impl Into<FileName>,
+        state_string: &str,
+        state_name: impl Into<FileName>,
+    ) -> Vec<LeoError> {
+        let mut errors = Self::parse_input_into(
+            &mut self.program_input,
+            "main",
+            &self.program.expected_input,
+            input_string,
+            &input_name.into(),
+            state_string,
+            &state_name.into(),
         );
+        if self.ast_snapshot_options.input {
+            if let Err(e) = self.write_input_ast_snapshot(input_string) {
+                errors.push(e);
+            }
+        }
+        errors
+    }
 
-        compiler.parse_input(input_string, input_path, state_string, state_path)?;
+    ///
+    /// Writes the input file as an input AST snapshot, leaving out its malformed entries and sections.
+    ///
+    fn write_input_ast_snapshot(&self, input_string: &str) -> Result<()> {
+        let recovered = LeoInputParser::recover(input_string);
+        let input_ast = match InputAst::parse(&recovered.source) {
+            Ok(input_ast) => input_ast,
+            // The input did not recover, which is already reported.
+            Err(_) => return Ok(()),
+        };
+        self.write_artifact(&INPUT_AST_FORMAT, "input_ast.json", &input_ast.to_json_bytes()?)
+    }
 
-        compiler.parse_program()?;
+    ///
+    /// Parses and stores an additional program input and state under the given label.
+    ///
+    /// The program can then be synthesized against each labeled input with
+    /// `compile_constraints_with_input()`. Adding an input with an existing label replaces it.
+    ///
+    pub fn add_input(
+        &mut self,
+        label: &str,
+        input_string: &str,
+        input_name: impl Into<FileName>,
+        state_string: &str,
+        state_name: impl Into<FileName>,
+    ) -> Result<()> {
+        let mut input = Input::new();
+        let errors = Self::parse_input_into(
+            &mut input,
+            "main",
+            &self.program.expected_input,
+            input_string,
+            &input_name.into(),
+            state_string,
+            &state_name.into(),
+        );
+        Self::input_result(errors)?;
+        self.inputs.insert(label.to_string(), input);
 
-        Ok(compiler)
+        Ok(())
     }
 
     ///
-    /// Parses and stores program input from from the input file path and state file path
+    /// Parses and stores the input and state of an entrypoint other than `main`.
     ///
-    /// Calls `set_path()` on compiler errors with the given input file path or state file path
+    /// The parameters of the entrypoint are given in the `[main]` section of the input file.
+    /// Entrypoints without an input of their own are synthesized with the program input.
+    /// Errors are tagged with the entrypoint.
     ///
-    pub fn parse_input(
+    pub fn add_entrypoint_input(
         &mut self,
+        entrypoint: &str,
         input_string: &str,
-        input_path: &Path,
+        input_name: impl Into<FileName>,
         state_string: &str,
-        state_path: &Path,
+        state_name: impl Into<FileName>,
     ) -> Result<()> {
-        let input_syntax_tree = LeoInputParser::parse_file(input_string).map_err(|mut e| {
-            e.set_path(
-                input_path.to_str().unwrap_or_default(),
-                &input_string.lines().map(|x| x.to_string()).collect::<Vec<String>>()[..],
-            );
+        let mut input = Input::new();
+        let errors = Self::parse_input_into(
+            &mut input,
+            entrypoint,
+            &self.program.function_input(entrypoint),
+            input_string,
+            &input_name.into(),
+            state_string,
+            &state_name.into(),
+        );
+        Self::input_result(errors).map_err(|e| Self::tag_entrypoint(entrypoint, e))?;
+        self.entrypoint_inputs.insert(entrypoint.to_string(), input);
 
-            e
-        })?;
-        let state_syntax_tree = LeoInputParser::parse_file(state_string).map_err(|mut e| {
-            e.set_path(
-                state_path.to_str().unwrap_or_default(),
-                &state_string.lines().map(|x| x.to_string()).collect::<Vec<String>>()[..],
-            );
+        Ok(())
+    }
 
-            e
-        })?;
+    ///
+    /// Returns the labels of the inputs added with `add_input()`, in the order they were added.
+    ///
+    pub fn input_labels(&self) -> impl Iterator<Item = &str> {
+        self.inputs.keys().map(|label| label.as_str())
+    }
 
-        self.program_input.parse_input(input_syntax_tree).map_err(|mut e| {
-            e.set_path(
-                input_path.to_str().unwrap_or_default(),
-                &input_string.lines().map(|x| x.to_string()).collect::<Vec<String>>()[..],
-            );
+    /// Returns the only error of an input as is, and several errors together.
+    fn input_result(mut errors: Vec<LeoError>) -> Result<()> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            count => {
+                let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+                Err(CompilerError::input_errors(count, errors.join("\n")).into())
+            }
+        }
+    }
 
-            e
-        })?;
-        self.program_input.parse_state(state_syntax_tree).map_err(|mut e| {
-            e.set_path(
-                state_path.to_str().unwrap_or_default(),
-                &state_string.lines().map(|x| x.to_string()).collect::<Vec<String>>()[..],
+    fn parse_input_into(
+        program_input: &mut Input,
+        function: &str,
+        expected_input: &[FunctionInput],
+        input_string: &str,
+        input_name: &FileName,
+        state_string: &str,
+        state_name: &FileName,
+    ) -> Vec<LeoError> {
+        let with_path = |mut error: InputParserError, name: &FileName, string: &str| -> LeoError {
+            error.set_path(
+                &name.to_string(),
+                &string.lines().map(|x| x.to_string()).collect::<Vec<String>>()[..],
             );
 
-            e
-        })?;
+            error.into()
+        };
 
-        Ok(())
+        // Recover from every malformed entry of the input file, so that all of them are reported.
+        let recovered = LeoInputParser::recover(input_string);
+        let mut errors = recovered
+            .errors
+            .into_iter()
+            .map(|e| with_path(e, input_name, input_string))
+            .collect::<Vec<_>>();
+        for name in recovered.skipped {
+            program_input.mark_malformed(name);
+        }
+        match LeoInputParser::parse_file(&recovered.source) {
+            Ok(input_syntax_tree) => errors.extend(
+                program_input
+                    .parse_input_recovering(input_syntax_tree)
+                    .into_iter()
+                    .map(|e| with_path(e, input_name, input_string)),
+            ),
+            Err(e) => errors.push(with_path(e, input_name, input_string)),
+        }
+
+        let state = LeoInputParser::parse_file(state_string)
+            .and_then(|state_syntax_tree| program_input.parse_state(state_syntax_tree));
+        if let Err(e) = state {
+            errors.push(with_path(e, state_name, state_string));
+        }
+
+        // Parameters with a malformed entry are not missing, their entry was already reported.
+        for parameter in expected_input.iter() {
+            let parameter = match parameter {
+                FunctionInput::Variable(parameter) => parameter,
+                _ => continue,
+            };
+            let name = parameter.identifier.name.to_string();
+            if program_input.get(&name).is_none()
+                && program_input.get_constant(&name).is_none()
+                && !program_input.is_malformed(&name)
+            {
+                errors.push(CompilerError::function_input_not_found(function, name, &parameter.identifier.span).into());
+            }
+        }
+
+        errors
     }
 
     ///
@@ -228,109 +747,670 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
     ///
     /// Parses and stores all programs imported by the main program file.
     ///
+    /// Unless an edition was given with [`Compiler::with_edition`], the program is compiled against the edition
+    /// declared in the `Leo.toml` of its package, or else that of the options with a warning.
+    ///
     pub fn parse_program(&mut self) -> Result<()> {
+        if !self.edition_declared {
+            match self.project_edition()? {
+                Some(edition) => self.options.edition = edition,
+                None => {
+                    let span = Span {
+                        line_start: 1,
+                        line_stop: 1,
+                        col_start: 1,
+                        col_stop: 1,
+                        path: Arc::new(self.main_file.to_string()),
+                        ..Default::default()
+                    };
+                    self.context.warn(
+                        format!(
+                            "no edition is declared, so the program is compiled against edition {}; \
+                             declare it as `edition` in the `[project]` of `{}`",
+                            self.options.edition, MANIFEST_FILENAME
+                        ),
+                        &span,
+                    );
+                }
+            }
+        }
+
         // Load the program file.
-        let content = fs::read_to_string(&self.main_file_path)
-            .map_err(|e| CompilerError::file_read_error(self.main_file_path.clone(), e))?;
+        let content = self.read_main_file()?;
 
         self.parse_program_from_string(&content)
     }
 
+    ///
+    /// Returns the edition declared in the `Leo.toml` of the package of the main file, if it declares one.
+    ///
+    fn project_edition(&self) -> Result<Option<Edition>> {
+        // The main file is in the source directory of the package.
+        let package = match self.main_file.path().and_then(Path::parent).and_then(Path::parent) {
+            Some(package) if Manifest::exists_at(package) => package,
+            _ => return Ok(None),
+        };
+        let edition = match Manifest::try_from(package)?.get_package_edition() {
+            Some(edition) => edition,
+            None => return Ok(None),
+        };
+        match Edition::from_name(&edition) {
+            Some(edition) => Ok(Some(edition)),
+            None => {
+                let editions = Edition::ALL
+                    .iter()
+                    .map(|edition| format!("`{}`", edition))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let path = package.join(MANIFEST_FILENAME);
+                Err(CompilerError::unknown_edition(edition, path.display(), editions).into())
+            }
+        }
+    }
+
     ///
     /// Equivalent to parse_and_check_program but uses the given program_string instead of a main
     /// file path.
     ///
     pub fn parse_program_from_string(&mut self, program_string: &str) -> Result<()> {
         // Use the parser to construct the abstract syntax tree (ast).
+        let path = self.main_file.to_string();
+        let cancellation = &self.options.cancellation;
+        let edition = self.options.edition;
+        let ast: leo_ast::Ast = self.options.progress.checked_phase(cancellation, "parsing", None, || {
+            parse_ast_for_edition(&path, program_string, edition, cancellation)
+        })?;
 
-        let mut ast: leo_ast::Ast = parse_ast(self.main_file_path.to_str().unwrap_or_default(), program_string)?;
+        self.main_source = Some(program_string.to_string());
+        self.parse_ast(ast)
+    }
 
-        if self.ast_snapshot_options.initial {
-            if self.ast_snapshot_options.spans_enabled {
-                ast.to_json_file(self.output_directory.clone(), "initial_ast.json")?;
-            } else {
-                ast.to_json_file_without_keys(self.output_directory.clone(), "initial_ast.json", &["span"])?;
+    ///
+    /// Equivalent to parse_program_from_string but starts from an already parsed syntax tree.
+    ///
+    /// The source of the program is not known, so its artifacts are not stamped with a source digest.
+    ///
+    pub fn parse_program_from_ast(&mut self, ast: leo_ast::Ast) -> Result<()> {
+        self.main_source = None;
+        self.parse_ast(ast)
+    }
+
+    fn parse_ast(&mut self, ast: leo_ast::Ast) -> Result<()> {
+        let mut check = ProgramCheck::new(ast);
+        while !self.check_step(&mut check)? {}
+        Ok(())
+    }
+
+    ///
+    /// Starts checking the program of `ast` like [`Compiler::parse_program_from_ast`],
+    /// leaving its phases to [`Compiler::check_step`].
+    ///
+    pub fn start_program_from_ast(&mut self, ast: leo_ast::Ast) -> ProgramCheck {
+        self.main_source = None;
+        ProgramCheck::new(ast)
+    }
+
+    ///
+    /// Runs the next phase of checking and optimizing the program, or the next optimization pass.
+    ///
+    /// Returns `true` once the program is checked, after which the result is the same as that of
+    /// [`Compiler::parse_program_from_ast`], which runs every phase in one call.
+    ///
+    pub fn check_step(&mut self, check: &mut ProgramCheck) -> Result<bool> {
+        let progress = self.options.progress.clone();
+        let cancellation = self.options.cancellation.clone();
+
+        match check.next {
+            CheckPhase::ImportResolution => {
+                let ast = check.ast.take().unwrap();
+                // The sources are only known once imports are resolved.
+                self.source_digest = None;
+                // Answer queries from the syntax alone until the program type checks.
+                self.navigation = Navigation::new(ast.as_repr(), None);
+                self.verify_ast("parser", &ast, Stage::Parsed)?;
+                if self.options.inline_threshold == 0 {
+                    return Err(CompilerError::invalid_inline_threshold().into());
+                }
+                self.write_effective_options()?;
+
+                if self.ast_snapshot_options.initial {
+                    self.write_ast_snapshot(&ast, "initial_ast.json")?;
+                }
+
+                // Preform import resolution.
+                let program_path = match self.main_file.path() {
+                    Some(path) => path.to_path_buf(),
+                    None => PathBuf::from("."),
+                };
+                let mut import_parser =
+                    ImportParser::new(program_path, self.imports_map.clone()).with_edition(self.options.edition);
+                let (ast, diagnostics) = progress.checked_phase(&cancellation, "import resolution", None, || {
+                    leo_ast_passes::Importer::do_pass_with_diagnostics(ast.into_repr(), &mut import_parser)
+                })?;
+                self.verify_ast("import resolution", &ast, Stage::Parsed)?;
+                // Repeated imports are reported with the warnings of type checking.
+                for diagnostic in import_parser.diagnostics().iter().chain(diagnostics.iter()) {
+                    self.context.warn(diagnostic.to_string(), &diagnostic.span);
+                }
+
+                self.source_digest = self.main_source.as_ref().map(|main| {
+                    let mut sources = import_parser.sources().clone();
+                    sources.insert("main".to_string(), main.clone());
+                    source_digest(&sources)
+                });
+
+                if self.ast_snapshot_options.imports_resolved {
+                    self.write_ast_snapshot(&ast, "imports_resolved_ast.json")?;
+                }
+                check.ast = Some(ast);
+                check.next = CheckPhase::Canonicalization;
             }
-        }
+            CheckPhase::Canonicalization => {
+                // Preform canonicalization of AST always.
+                let ast = check.ast.take().unwrap();
+                let max_depth = self.options.max_expression_depth;
+                let ast = progress.checked_phase(&cancellation, "canonicalization", None, || {
+                    leo_ast_passes::Canonicalizer::do_pass_with_max_depth(ast.into_repr(), max_depth)
+                })?;
+                self.verify_ast("canonicalization", &ast, Stage::Canonicalized)?;
 
-        // Preform import resolution.
-        ast = leo_ast_passes::Importer::do_pass(
-            ast.into_repr(),
-            &mut ImportParser::new(self.main_file_path.clone(), self.imports_map.clone()),
-        )?;
+                if self.ast_snapshot_options.canonicalized {
+                    self.write_ast_snapshot(&ast, "canonicalization_ast.json")?;
+                }
+                check.ast = Some(ast);
+                check.next = match self.options.renaming_enabled {
+                    true => CheckPhase::Renaming,
+                    false => CheckPhase::TypeChecking,
+                };
+            }
+            CheckPhase::Renaming => {
+                // Rename internal identifiers if requested.
+                let ast = check.ast.take().unwrap();
+                let entrypoints = &self.options.entrypoints;
+                let (renamed, rename_map) = progress.checked_phase(&cancellation, "renaming", None, || {
+                    leo_ast_passes::Renamer::do_pass_with_entrypoints(ast.into_repr(), entrypoints)
+                })?;
+                self.write_artifact(
+                    &RENAME_MAP_FORMAT,
+                    "rename_map.json",
+                    rename_map.to_json_string()?.as_bytes(),
+                )?;
+                self.rename_map = Some(rename_map);
+                self.verify_ast("renaming", &renamed, Stage::Canonicalized)?;
+                check.ast = Some(renamed);
+                check.next = CheckPhase::TypeChecking;
+            }
+            CheckPhase::TypeChecking => {
+                // Store the main program file.
+                self.program = check.ast.take().unwrap().into_repr();
+                self.program.name = self.program_name.clone();
+                self.name_generator = NameGenerator::new(&self.program)?;
+                self.metrics = Some(metrics::collect(&self.program));
+                self.cost_estimates = None;
+                self.asserts = None;
+
+                tracing::debug!("Program parsing complete\n{:#?}", self.program);
+
+                // Create a new symbol table from the program, imported_programs, and program_input.
+                self.context
+                    .set_strict_field_literals(self.options.strict_field_literals);
+                self.context
+                    .set_integer_widening(self.options.integer_widening == IntegerWidening::Lossless);
+                self.context.set_edition(self.options.edition);
+                self.context.set_progress(progress.reporter());
+                let functions = count_functions(&self.program);
+                let asg = progress.checked_phase(&cancellation, "type checking", Some(functions), || {
+                    Asg::new(self.context, &self.program)
+                });
+                self.context.set_progress(None);
+                let asg = asg.map_err(|e| self.demangle_error(e))?;
+                if self.options.verify_ast {
+                    validate_pass_output("type checking", &self.program, Stage::TypeChecked)?;
+                }
+                self.navigation = Navigation::new(&self.program, Some(asg.as_repr()));
+                self.asserts = Some(crate::collect_asserts(&self.program));
+                self.report_warnings();
+
+                if self.ast_snapshot_options.type_inferenced {
+                    let new_ast = TypeInferencePhase::default()
+                        .phase_ast(&self.program, &asg.clone().into_repr())
+                        .expect("Failed to produce type inference ast.");
+
+                    self.write_ast_snapshot(&new_ast, "type_inferenced_ast.json")?;
+                }
 
-        if self.ast_snapshot_options.imports_resolved {
-            if self.ast_snapshot_options.spans_enabled {
-                ast.to_json_file(self.output_directory.clone(), "imports_resolved_ast.json")?;
-            } else {
-                ast.to_json_file_without_keys(self.output_directory.clone(), "imports_resolved_ast.json", &["span"])?;
+                tracing::debug!("ASG generation complete");
+
+                // Store the ASG.
+                self.asg = Some(asg.into_repr());
+
+                cancellation.check("optimization")?;
+                progress.start_phase("optimization", None);
+                check.next = self.next_optimization(0);
+            }
+            CheckPhase::Optimization(index) => {
+                let optimized = self.optimize(OPTIMIZATIONS[index]);
+                if let Err(error) = optimized {
+                    progress.end_phase("optimization");
+                    return Err(self.demangle_error(error));
+                }
+                check.next = self.next_optimization(index + 1);
+                if check.next == CheckPhase::Limits {
+                    progress.end_phase("optimization");
+                    cancellation.check("optimization")?;
+                }
+            }
+            CheckPhase::Limits => {
+                if self.options.emit_dot {
+                    self.write_dataflow_graphs()?;
+                }
+                if self.options.estimate_costs {
+                    self.cost_estimates = Some(crate::estimate_costs(self.asg.as_ref().unwrap()));
+                }
+
+                progress.checked_phase(&cancellation, "limits", None, || {
+                    self.check_definition_limits()?;
+                    self.check_input_slots()
+                })?;
+                check.next = CheckPhase::Done;
             }
+            CheckPhase::Done => (),
         }
 
-        // Preform canonicalization of AST always.
-        ast = leo_ast_passes::Canonicalizer::do_pass(ast.into_repr())?;
+        Ok(check.next == CheckPhase::Done)
+    }
 
-        if self.ast_snapshot_options.canonicalized {
-            if self.ast_snapshot_options.spans_enabled {
-                ast.to_json_file(self.output_directory.clone(), "canonicalization_ast.json")?;
-            } else {
-                ast.to_json_file_without_keys(self.output_directory.clone(), "canonicalization_ast.json", &["span"])?;
+    ///
+    /// Counts the input slots taken by the parameters of `main`, returning an error if they exceed the limit.
+    ///
+    fn check_input_slots(&mut self) -> Result<()> {
+        let main = match self.asg.as_ref().unwrap().functions.get("main") {
+            Some(main) => *main,
+            None => return Ok(()),
+        };
+
+        let input_slots = InputSlots::count(main);
+        let checked = input_slots.check(self.options.limits.max_input_slots);
+        self.input_slots = Some(input_slots);
+        checked
+    }
+
+    ///
+    /// Writes the dataflow of every function, including circuit member functions, as `<function>.dot`.
+    ///
+    fn write_dataflow_graphs(&self) -> Result<()> {
+        let program = self.asg.as_ref().unwrap();
+        let mut functions = vec![];
+        for (name, function) in program.functions.iter() {
+            functions.push((name.clone(), *function));
+        }
+        for (circuit_name, circuit) in program.circuits.iter() {
+            for (name, member) in circuit.members.borrow().iter() {
+                if let CircuitMember::Function(function) = member {
+                    functions.push((format!("{}.{}", circuit_name, name), *function));
+                }
             }
         }
 
-        // Store the main program file.
-        self.program = ast.into_repr();
-        self.program.name = self.program_name.clone();
+        let (progress, cancellation) = (&self.options.progress, &self.options.cancellation);
+        progress.checked_phase(cancellation, "dataflow graphs", Some(functions.len()), || {
+            for (index, (name, function)) in functions.iter().enumerate() {
+                progress.item(index, name);
+                let dot = Dataflow::new(function).to_dot(self.options.cluster_dot);
+                self.write_artifact(&DATAFLOW_FORMAT, &format!("{}.dot", name), dot.as_bytes())?;
+            }
+            Ok(())
+        })
+    }
 
-        tracing::debug!("Program parsing complete\n{:#?}", self.program);
+    ///
+    /// Returns an error if the program defines more functions or circuits than the limits allow.
+    ///
+    fn check_definition_limits(&self) -> Result<()> {
+        let asg = self.asg.as_ref().unwrap();
+        let limits = &self.options.limits;
 
-        // Create a new symbol table from the program, imported_programs, and program_input.
-        let asg = Asg::new(self.context, &self.program)?;
+        let circuits = asg.circuits.len();
+        if circuits > limits.max_circuits {
+            return Err(CompilerError::definition_limit_exceeded(
+                "circuits",
+                circuits,
+                limits.max_circuits,
+                "max_circuits",
+            )
+            .into());
+        }
 
-        if self.ast_snapshot_options.type_inferenced {
-            let new_ast = TypeInferencePhase::default()
-                .phase_ast(&self.program, &asg.clone().into_repr())
-                .expect("Failed to produce type inference ast.");
+        let functions = self.metrics.as_ref().unwrap().functions.len();
+        if functions > limits.max_functions {
+            return Err(CompilerError::definition_limit_exceeded(
+                "functions",
+                functions,
+                limits.max_functions,
+                "max_functions",
+            )
+            .into());
+        }
 
-            if self.ast_snapshot_options.spans_enabled {
-                new_ast.to_json_file(self.output_directory.clone(), "type_inferenced_ast.json")?;
-            } else {
-                new_ast.to_json_file_without_keys(
-                    self.output_directory.clone(),
-                    "type_inferenced_ast.json",
-                    &["span"],
-                )?;
-            }
+        Ok(())
+    }
+
+    ///
+    /// Returns the program AST after all AST passes have run.
+    ///
+    pub fn program(&self) -> &AstProgram {
+        &self.program
+    }
+
+    ///
+    /// Returns the parameters of `main` that the program expects as input, in declaration order.
+    ///
+    /// Returns no parameters if the program has not been parsed or has no `main` function.
+    ///
+    pub fn expected_input(&self) -> &[FunctionInput] {
+        &self.program.expected_input
+    }
+
+    ///
+    /// Returns the node counts, function sizes and nesting depths of the program after all AST passes.
+    ///
+    /// Returns `None` if the program has not been parsed.
+    ///
+    pub fn metrics(&self) -> Option<&AstMetrics> {
+        self.metrics.as_ref()
+    }
+
+    ///
+    /// Returns the input slots taken by the parameters of `main`.
+    ///
+    /// Returns `None` if the program has not been parsed or has no `main` function.
+    ///
+    pub fn input_slots(&self) -> Option<&InputSlots> {
+        self.input_slots.as_ref()
+    }
+
+    ///
+    /// Returns the counts of the lookups in the cache of calls evaluated by constant folding.
+    ///
+    /// Returns `None` if the program has not been parsed or constant folding is disabled.
+    ///
+    pub fn evaluation_statistics(&self) -> Option<EvaluationStatistics> {
+        self.evaluation_statistics
+    }
+
+    ///
+    /// Returns the counts of the rewrites applied by boolean simplification.
+    ///
+    /// Returns `None` if the program has not been parsed or boolean simplification is disabled.
+    ///
+    pub fn boolean_simplification_statistics(&self) -> Option<BooleanSimplificationStatistics> {
+        self.boolean_simplification_statistics
+    }
+
+    ///
+    /// Returns the counts of the asserts removed because they always hold.
+    ///
+    /// Returns `None` if the program has not been parsed.
+    ///
+    pub fn assert_statistics(&self) -> Option<AssertStatistics> {
+        self.assert_statistics
+    }
+
+    ///
+    /// Returns how the calls to every function that is called are compiled, keyed by function name,
+    /// or `Circuit::function` for circuit member functions.
+    ///
+    /// Returns `None` if the program has not been parsed.
+    ///
+    pub fn inline_decisions(&self) -> Option<&IndexMap<String, InlineDecision>> {
+        self.inline_decisions.as_ref()
+    }
+
+    ///
+    /// Returns the estimated size of every function, keyed by function name, or `Circuit::function`
+    /// for circuit member functions.
+    ///
+    /// Returns `None` if the program has not been checked or costs are not estimated.
+    ///
+    pub fn cost_estimates(&self) -> Option<&IndexMap<String, FunctionCost>> {
+        self.cost_estimates.as_ref()
+    }
+
+    ///
+    /// Returns the asserts of the program as written, before those that always hold are removed.
+    ///
+    /// Returns `None` if the program has not been type checked.
+    ///
+    pub fn asserts(&self) -> Option<&[AssertSite]> {
+        self.asserts.as_deref()
+    }
+
+    ///
+    /// Returns the counts of the queries of the type name and type size caches of the program.
+    ///
+    /// Returns `None` if the program has not been parsed.
+    ///
+    pub fn type_cache_statistics(&self) -> Option<TypeCacheStatistics> {
+        self.asg.as_ref().map(|asg| asg.scope.types.statistics())
+    }
+
+    ///
+    /// Returns the map from original to renamed identifiers, if renaming is enabled.
+    ///
+    pub fn rename_map(&self) -> Option<&RenameMap> {
+        self.rename_map.as_ref()
+    }
+
+    ///
+    /// Returns the generator for the names of variables that passes add to the program.
+    ///
+    /// It avoids every identifier of the program after all AST passes, and every name it made before.
+    ///
+    pub fn name_generator(&mut self) -> &mut NameGenerator {
+        &mut self.name_generator
+    }
+
+    ///
+    /// Returns the classification of every identifier of the program, ordered by span.
+    ///
+    /// Returns no tokens if the program has not been parsed.
+    ///
+    pub fn semantic_tokens(&self) -> Vec<SemanticToken> {
+        match &self.asg {
+            Some(asg) => leo_asg::semantic_tokens(&self.program, Some(asg)),
+            None => vec![],
         }
+    }
 
-        tracing::debug!("ASG generation complete");
+    ///
+    /// Returns the type and constness of every expression of the program.
+    ///
+    /// Returns `None` if the program has not been parsed.
+    ///
+    pub fn symbol_table(&self) -> Option<SymbolTable<'a>> {
+        self.asg.as_ref().map(SymbolTable::new)
+    }
 
-        // Store the ASG.
-        self.asg = Some(asg.into_repr());
+    ///
+    /// Returns the declaration of the name at the given position of the given file.
+    ///
+    /// A program with errors is answered from its syntax, as far as it was parsed.
+    ///
+    pub fn definition_at(&self, file: &str, position: Position) -> Option<Span> {
+        self.navigation.definition_at(file, position).cloned()
+    }
 
-        self.do_asg_passes()?;
+    ///
+    /// Returns every reference to the declaration with the given span, such as one returned by [`Self::definition_at`].
+    ///
+    pub fn references_of(&self, declaration: &Span) -> Vec<Span> {
+        self.navigation.references_of(declaration)
+    }
 
-        Ok(())
+    ///
+    /// Returns the type and constness of the expression at the given position of the given file,
+    /// and the documentation of the declaration of the name there.
+    ///
+    /// Types and constness are only known once the program type checks, and documentation only for
+    /// declarations in the main file when its source is known.
+    ///
+    pub fn hover_at(&self, file: &str, position: Position) -> Option<HoverInfo<'a>> {
+        let source = match &self.main_source {
+            Some(source) if file == self.main_file.to_string() => Some(source.as_str()),
+            _ => None,
+        };
+        self.navigation.hover_at(file, position, source)
     }
 
     ///
-    /// Run compiler optimization passes on the program in asg format.
+    /// Restores the original identifier names in the message of an error raised after renaming.
     ///
-    fn do_asg_passes(&mut self) -> Result<()> {
-        assert!(self.asg.is_some());
+    fn demangle_error(&self, error: LeoError) -> LeoError {
+        match &self.rename_map {
+            Some(rename_map) => map_message(error, |message| rename_map.demangle(message)),
+            None => error,
+        }
+    }
 
-        // Do constant folding.
-        if self.options.constant_folding_enabled {
-            let asg = self.asg.take().unwrap();
-            self.asg = Some(leo_asg_passes::ConstantFolding::do_pass(asg)?);
+    ///
+    /// Tags an error raised by an entrypoint-specific stage with the entrypoint.
+    ///
+    /// Errors of the ASG and of the compiler keep their span, other errors are wrapped.
+    ///
+    fn tag_entrypoint(entrypoint: &str, error: LeoError) -> LeoError {
+        match error {
+            LeoError::AsgError(_) | LeoError::CompilerError(_) => {
+                map_message(error, |message| format!("in entrypoint `{}`: {}", entrypoint, message))
+            }
+            error => CompilerError::entrypoint_error(entrypoint, error).into(),
         }
+    }
+
+    ///
+    /// Returns the phase of the first optimization pass at or after `index` that is enabled,
+    /// or the phase after optimization if there is none.
+    ///
+    fn next_optimization(&self, index: usize) -> CheckPhase {
+        (index..OPTIMIZATIONS.len())
+            .find(|index| self.is_enabled(OPTIMIZATIONS[*index]))
+            .map(CheckPhase::Optimization)
+            .unwrap_or(CheckPhase::Limits)
+    }
 
-        // Do dead code elimination.
-        if self.options.dead_code_elimination_enabled {
-            let asg = self.asg.take().unwrap();
-            self.asg = Some(leo_asg_passes::DeadCodeElimination::do_pass(asg)?);
+    fn is_enabled(&self, optimization: Optimization) -> bool {
+        match optimization {
+            Optimization::Naming => self.options.naming.rules().is_enabled(),
+            Optimization::ConstantFolding => self.options.constant_folding_enabled,
+            Optimization::BooleanSimplification => self.options.simplify_boolean_expressions,
+            Optimization::DeadCodeElimination => self.options.dead_code_elimination_enabled,
+            _ => true,
         }
+    }
+
+    ///
+    /// Runs a compiler optimization pass on the program in asg format.
+    ///
+    fn optimize(&mut self, optimization: Optimization) -> Result<()> {
+        assert!(self.asg.is_some());
+        let progress = self.options.progress.clone();
+        let cancellation = self.options.cancellation.clone();
+        let asg = self.asg.take().unwrap();
 
+        match optimization {
+            Optimization::RangeComparison => {
+                // Report comparisons decided by the ranges of their operand types, folding them if requested.
+                let fold = self.options.fold_range_comparisons;
+                self.asg = Some(progress.checked_phase(&cancellation, "range comparison", None, || {
+                    leo_asg_passes::RangeComparison::do_pass_with_folding(asg, fold)
+                })?);
+            }
+            Optimization::Constructibility => {
+                // Report functions taking circuits that the programs calling them cannot construct.
+                self.asg = Some(progress.checked_phase(&cancellation, "constructibility", None, || {
+                    leo_asg_passes::Constructibility::do_pass(asg)
+                })?);
+            }
+            Optimization::Naming => {
+                // Report declarations whose names break the naming conventions.
+                let rules = self.options.naming.rules();
+                let originals: IndexMap<String, String> = self
+                    .rename_map
+                    .as_ref()
+                    .map(|rename_map| {
+                        rename_map
+                            .iter()
+                            .map(|(original, renamed)| (renamed.to_string(), original.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                self.asg = Some(progress.checked_phase(&cancellation, "naming", None, || {
+                    leo_asg_passes::NamingConventions::do_pass(asg, &rules, &originals)
+                })?);
+            }
+            Optimization::ConstantFolding => {
+                // Do constant folding.
+                let (asg, statistics) = progress.checked_phase(&cancellation, "constant folding", None, || {
+                    leo_asg_passes::ConstantFolding::do_pass_with_cancellation(
+                        asg,
+                        leo_asg_passes::DEFAULT_CALL_FUEL,
+                        &cancellation,
+                    )
+                })?;
+                self.asg = Some(asg);
+                self.evaluation_statistics = Some(statistics);
+            }
+            Optimization::BooleanSimplification => {
+                // Simplify boolean expressions, if requested.
+                let (asg, statistics) =
+                    progress.checked_phase(&cancellation, "boolean simplification", None, || {
+                        leo_asg_passes::BooleanSimplifier::do_pass_with_statistics(asg)
+                    })?;
+                self.asg = Some(asg);
+                self.boolean_simplification_statistics = Some(statistics);
+            }
+            Optimization::StaticAsserts => {
+                // Fail on asserts that never hold, and remove those that always do.
+                let entrypoints = self.options.entrypoints.clone();
+                let keep_true = self.options.keep_true_asserts;
+                let (asg, statistics) = progress.checked_phase(&cancellation, "static asserts", None, || {
+                    leo_asg_passes::StaticAsserts::do_pass_with_options(asg, &entrypoints, keep_true)
+                })?;
+                self.asg = Some(asg);
+                self.assert_statistics = Some(statistics);
+            }
+            Optimization::DeadCodeElimination => {
+                // Do dead code elimination.
+                self.asg = Some(
+                    progress.checked_phase(&cancellation, "dead code elimination", None, || {
+                        leo_asg_passes::DeadCodeElimination::do_pass(asg)
+                    })?,
+                );
+            }
+            Optimization::Usage => {
+                // Report unused parameters and circuit members, once dead code no longer counts as using them.
+                let entrypoints = self.options.entrypoints.clone();
+                self.asg = Some(progress.checked_phase(&cancellation, "usage", None, || {
+                    leo_asg_passes::Usage::do_pass_with_entrypoints(asg, &entrypoints)
+                })?);
+            }
+            Optimization::Inlining => {
+                // Decide how the calls that are left are compiled, failing on those to functions that must not be inlined.
+                let threshold = self.options.inline_threshold;
+                let (asg, decisions) = progress.checked_phase(&cancellation, "inlining", None, || {
+                    leo_asg_passes::Inlining::do_pass_with_threshold(asg, threshold)
+                })?;
+                self.asg = Some(asg);
+                self.inline_decisions = Some(match &self.rename_map {
+                    Some(rename_map) => decisions
+                        .into_iter()
+                        .map(|(name, decision)| (rename_map.demangle(&name), decision))
+                        .collect(),
+                    None => decisions,
+                });
+            }
+        }
+
+        self.report_warnings();
         Ok(())
     }
 
@@ -338,23 +1418,202 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
     /// Synthesizes the circuit with program input to verify correctness.
     ///
     pub fn compile_constraints<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Result<Output> {
-        generate_constraints::<F, G, CS>(cs, self.asg.as_ref().unwrap(), &self.program_input)
+        let mut synthesis = self.start_constraints()?;
+        loop {
+            if let Some(output) = self.constraints_step(&mut synthesis, cs)? {
+                return Ok(output);
+            }
+        }
+    }
+
+    ///
+    /// Starts synthesizing the circuit with program input like [`Compiler::compile_constraints`],
+    /// leaving its statements to [`Compiler::constraints_step`].
+    ///
+    pub fn start_constraints(&self) -> Result<Synthesis<'a, F, G>> {
+        let asg = self.asg.as_ref().unwrap();
+        if !asg.functions.contains_key("main") {
+            return Err(CompilerError::no_main_function().into());
+        }
+        Synthesis::new(
+            asg,
+            "main",
+            &self.program.expected_input,
+            &self.program_input,
+            &self.options.limits,
+            &self.options.progress,
+            &self.options.cancellation,
+            self.options.record_provenance,
+        )
+        .map_err(|e| self.demangle_error(e))
+    }
+
+    ///
+    /// Does the next step of the synthesis, returning the output and writing its provenance once the circuit is complete.
+    ///
+    pub fn constraints_step<CS: ConstraintSystem<F>>(
+        &self,
+        synthesis: &mut Synthesis<'a, F, G>,
+        cs: &mut CS,
+    ) -> Result<Option<Output>> {
+        let output = match synthesis.step(cs).map_err(|e| self.demangle_error(e))? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+
+        self.write_provenance(&format!("{}.provenance.json", self.program_name), &output)?;
+
+        Ok(Some(output))
+    }
+
+    ///
+    /// Synthesizes the circuit with the program input added under the given label.
+    ///
+    /// The output registers are written to `inputs/<label>/<program name>.out` in the artifact sink.
+    ///
+    pub fn compile_constraints_with_input<CS: ConstraintSystem<F>>(&self, label: &str, cs: &mut CS) -> Result<Output> {
+        let input = self
+            .inputs
+            .get(label)
+            .ok_or_else(|| CompilerError::unknown_input_label(label))?;
+        let output = generate_constraints::<F, G, CS>(
+            cs,
+            self.asg.as_ref().unwrap(),
+            &self.program.expected_input,
+            input,
+            &self.options.limits,
+            &self.options.progress,
+            &self.options.cancellation,
+            self.options.record_provenance,
+        )
+        .map_err(|e| self.demangle_error(e))?;
+
+        self.write_provenance(
+            &format!("inputs/{}/{}.provenance.json", label, self.program_name),
+            &output,
+        )?;
+
+        self.write_artifact(
+            &OUTPUT_FORMAT,
+            &format!("inputs/{}/{}.out", label, self.program_name),
+            output.to_string().as_bytes(),
+        )?;
+
+        Ok(output)
+    }
+
+    ///
+    /// Synthesizes the circuit against every labeled input, returning the errors of the inputs that failed.
+    ///
+    pub fn validate_inputs(&self) -> Vec<(String, LeoError)> {
+        self.inputs
+            .keys()
+            .filter_map(|label| {
+                let mut cs = TestConstraintSystem::<F>::new();
+                self.compile_constraints_with_input(label, &mut cs)
+                    .err()
+                    .map(|error| (label.clone(), error))
+            })
+            .collect()
+    }
+
+    ///
+    /// Synthesizes the circuit with the given entrypoint, reusing the program checked and optimized once for all of them.
+    ///
+    /// The input slots of the entrypoint are checked against the limits, and its output registers, interface,
+    /// and provenance are written to `<entrypoint>/<program name>.*` in the artifact sink.
+    /// Errors are tagged with the entrypoint.
+    ///
+    pub fn compile_entrypoint_constraints<CS: ConstraintSystem<F>>(
+        &self,
+        entrypoint: &str,
+        cs: &mut CS,
+    ) -> Result<Output> {
+        self.synthesize_entrypoint(entrypoint, cs)
+            .map_err(|e| Self::tag_entrypoint(entrypoint, self.demangle_error(e)))
+    }
+
+    fn synthesize_entrypoint<CS: ConstraintSystem<F>>(&self, entrypoint: &str, cs: &mut CS) -> Result<Output> {
+        let asg = self.asg.as_ref().unwrap();
+        let function = *asg
+            .functions
+            .get(entrypoint)
+            .ok_or_else(|| CompilerError::unknown_entrypoint(entrypoint))?;
+        InputSlots::count(function).check(self.options.limits.max_input_slots)?;
+
+        let input = self.entrypoint_inputs.get(entrypoint).unwrap_or(&self.program_input);
+        let output = generate_entrypoint_constraints::<F, G, CS>(
+            cs,
+            asg,
+            entrypoint,
+            &self.program.function_input(entrypoint),
+            input,
+            &self.options.limits,
+            &self.options.progress,
+            &self.options.cancellation,
+            self.options.record_provenance,
+        )?;
+
+        self.write_artifact(
+            &INTERFACE_FORMAT,
+            &format!("{}/{}.interface.json", entrypoint, self.program_name),
+            Interface::new(function).to_json_string()?.as_bytes(),
+        )?;
+        self.write_provenance(
+            &format!("{}/{}.provenance.json", entrypoint, self.program_name),
+            &output,
+        )?;
+        self.write_artifact(
+            &OUTPUT_FORMAT,
+            &format!("{}/{}.out", entrypoint, self.program_name),
+            output.to_string().as_bytes(),
+        )?;
+
+        Ok(output)
+    }
+
+    ///
+    /// Synthesizes the circuit with every entrypoint of the compiler options, returning the result of each.
+    ///
+    /// An entrypoint that fails does not stop the others from being synthesized and writing their artifacts.
+    ///
+    pub fn compile_entrypoints(&self) -> Vec<(String, Result<Output>)> {
+        self.options
+            .entrypoints
+            .iter()
+            .map(|entrypoint| {
+                let mut cs = TestConstraintSystem::<F>::new();
+                (
+                    entrypoint.clone(),
+                    self.compile_entrypoint_constraints(entrypoint, &mut cs),
+                )
+            })
+            .collect()
     }
 
     ///
     /// Synthesizes the circuit for test functions with program input.
     ///
     pub fn compile_test_constraints(self, input_pairs: InputPairs) -> Result<(u32, u32)> {
-        generate_test_constraints::<F, G>(self.asg.as_ref().unwrap(), input_pairs, &self.output_directory)
+        generate_test_constraints::<F, G>(
+            self.asg.as_ref().unwrap(),
+            input_pairs,
+            &self.output_directory,
+            &self.options.limits,
+        )
     }
 
     ///
     /// Returns a SHA256 checksum of the program file.
     ///
+    /// The source the program was parsed from is hashed if it is known, so that sources which
+    /// cannot be read twice, such as the standard input or a named pipe, are only read once.
+    ///
     pub fn checksum(&self) -> Result<String> {
-        // Read in the main file as string
-        let unparsed_file = fs::read_to_string(&self.main_file_path)
-            .map_err(|e| CompilerError::file_read_error(self.main_file_path.clone(), e))?;
+        let unparsed_file = match &self.main_source {
+            Some(source) => source.clone(),
+            None => self.read_main_file()?,
+        };
 
         // Hash the file contents
         let mut hasher = Sha256::new();
@@ -364,6 +1623,156 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
         Ok(format!("{:x}", hash))
     }
 
+    ///
+    /// Returns a summary of the program for auditors, see [`AuditReport`].
+    ///
+    /// Sections that cannot be filled in, because the program has not been checked or the option
+    /// they depend on is disabled, say why instead.
+    ///
+    pub fn audit_report(&self) -> AuditReport {
+        let demangle = |name: &str| match &self.rename_map {
+            Some(rename_map) => rename_map.demangle(name),
+            None => name.to_string(),
+        };
+        let unchecked = || "the program has not been type checked".to_string();
+
+        let entrypoints = match &self.asg {
+            Some(asg) => AuditSection::Available {
+                content: self
+                    .options
+                    .entrypoints
+                    .iter()
+                    .filter_map(|entrypoint| asg.functions.get(entrypoint))
+                    .map(|function| AuditEntrypoint::new(function))
+                    .collect(),
+            },
+            None => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        let functions = match (&self.metrics, &self.asg) {
+            (Some(metrics), Some(_)) => AuditSection::Available {
+                content: metrics
+                    .functions
+                    .iter()
+                    .map(|(name, function)| {
+                        let name = demangle(name);
+                        AuditFunction {
+                            calls: self
+                                .inline_decisions
+                                .as_ref()
+                                .and_then(|decisions| decisions.get(&name))
+                                .map(|decision| decision.calls)
+                                .unwrap_or(0),
+                            entrypoint: self.options.entrypoints.contains(&name),
+                            statements: function.statements,
+                            expressions: function.expressions,
+                            name,
+                        }
+                    })
+                    .collect(),
+            },
+            _ => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        let estimates = match (&self.cost_estimates, &self.asg) {
+            (Some(estimates), _) => AuditSection::Available {
+                content: estimates
+                    .iter()
+                    .map(|(name, cost)| AuditEstimate {
+                        function: demangle(name),
+                        own: cost.own,
+                        total: cost.total,
+                    })
+                    .collect(),
+            },
+            (None, Some(_)) if !self.options.estimate_costs => AuditSection::Unavailable {
+                reason: "cost estimation is disabled by the `estimate_costs` option".to_string(),
+            },
+            (None, _) => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        let circuits = match &self.asg {
+            Some(asg) => AuditSection::Available {
+                content: asg
+                    .circuits
+                    .values()
+                    .map(|circuit| {
+                        let mut circuit = AuditCircuit::new(circuit);
+                        circuit.name = demangle(&circuit.name);
+                        for member in circuit.members.iter_mut() {
+                            member.name = demangle(&member.name);
+                            member.type_ = demangle(&member.type_);
+                        }
+                        for function in circuit.functions.iter_mut() {
+                            *function = demangle(function);
+                        }
+                        circuit
+                    })
+                    .collect(),
+            },
+            None => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        let constants = match (&self.asg, self.evaluation_statistics) {
+            (Some(asg), Some(statistics)) => AuditSection::Available {
+                content: AuditConstants {
+                    global_consts: asg
+                        .global_consts
+                        .iter()
+                        .map(|(name, definition)| AuditConstant::new(&demangle(name), definition))
+                        .collect(),
+                    calls_evaluated: statistics.misses,
+                    calls_reused: statistics.hits,
+                },
+            },
+            (Some(_), None) if !self.options.constant_folding_enabled => AuditSection::Unavailable {
+                reason: "constant folding is disabled by the `constant_folding_enabled` option".to_string(),
+            },
+            _ => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        let asserts = match (&self.asg, &self.asserts) {
+            (Some(_), Some(sites)) => {
+                let statistics = self.assert_statistics.unwrap_or_default();
+                AuditSection::Available {
+                    content: AuditAsserts {
+                        sites: sites.clone(),
+                        removed_as_constant: statistics.constant,
+                        removed_from_types: statistics.from_types,
+                    },
+                }
+            }
+            _ => AuditSection::Unavailable { reason: unchecked() },
+        };
+
+        AuditReport {
+            program: self.program_name.clone(),
+            entrypoints,
+            functions,
+            estimates,
+            circuits,
+            constants,
+            asserts,
+            build: AuditBuild {
+                compiler: AUDIT_FORMAT.current().compiler,
+                source_checksum: self.main_source.as_ref().and_then(|_| self.checksum().ok()),
+                source_digest: self.source_digest.clone(),
+                options_digest: self.options.digest(),
+                options: self.options.changed_options(),
+            },
+        }
+    }
+
+    ///
+    /// Writes the audit report of the program to the output sink, as `audit.md` and `audit.json`.
+    ///
+    pub fn write_audit_report(&self) -> Result<AuditReport> {
+        let report = self.audit_report();
+        self.write_output("audit.md", report.to_markdown().as_bytes())?;
+        self.write_artifact(&AUDIT_FORMAT, "audit.json", report.to_json_string()?.as_bytes())?;
+        Ok(report)
+    }
+
     /// TODO (howardwu): Incorporate this for real program executions and intentionally-real
     ///  test executions. Exclude it for test executions on dummy data.
     ///
@@ -410,3 +1819,31 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstraintSynthesizer<F> for Compiler<'
         Ok(())
     }
 }
+
+/// Rewrites the message of an error of the ASG or of the compiler, leaving other errors as they are.
+fn map_message(error: LeoError, map: impl Fn(&str) -> String) -> LeoError {
+    match error {
+        LeoError::AsgError(AsgError::FormattedError(mut e)) => {
+            e.backtrace.message = map(&e.backtrace.message);
+            AsgError::FormattedError(e).into()
+        }
+        LeoError::AsgError(AsgError::BacktracedError(mut e)) => {
+            e.message = map(&e.message);
+            AsgError::BacktracedError(e).into()
+        }
+        LeoError::CompilerError(CompilerError::FormattedError(mut e)) => {
+            e.backtrace.message = map(&e.backtrace.message);
+            CompilerError::FormattedError(e).into()
+        }
+        LeoError::CompilerError(CompilerError::BacktracedError(mut e)) => {
+            e.message = map(&e.message);
+            CompilerError::BacktracedError(e).into()
+        }
+        error => error,
+    }
+}
+
+/// Returns the number of functions of the program and its imports, which the ASG checks one by one.
+fn count_functions(program: &AstProgram) -> usize {
+    program.functions.len() + program.imports.values().map(count_functions).sum::<usize>()
+}
@@ -16,8 +16,11 @@
 
 //! Enforces an assert equals statement in a compiled Leo program.
 
-use crate::{get_indicator_value, program::ConstrainedProgram, value::ConstrainedValue, GroupType};
-use leo_asg::Expression;
+use crate::{
+    evaluate_eq, evaluate_not, get_indicator_value, program::ConstrainedProgram, render_log_value,
+    value::ConstrainedValue, GroupType,
+};
+use leo_asg::{BinaryOperation, ConsoleAssertArgs, Expression};
 use leo_errors::{CompilerError, Result, Span};
 
 use snarkvm_fields::PrimeField;
@@ -41,19 +44,62 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             return Ok(()); // Continue execution.
         }
 
-        // Unwrap assertion value and handle errors
-        let result_option = match assert_expression {
-            ConstrainedValue::Boolean(boolean) => boolean.get_value(),
-            _ => {
-                return Err(CompilerError::console_assertion_must_be_boolean(span).into());
-            }
+        if !assertion_holds(assert_expression, span)? {
+            return Err(CompilerError::console_assertion_failed(span).into());
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Evaluates `console.assert_eq` or `console.assert_ne` as `console.assert` on the `==` or `!=` of the operands,
+    /// which enforces the same constraints, but renders both operands if the assertion fails.
+    ///
+    pub fn evaluate_console_assert_comparison<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        indicator: &Boolean,
+        args: &ConsoleAssertArgs<'a>,
+        operation: BinaryOperation,
+        span: &Span,
+    ) -> Result<()> {
+        let (left, right) = self.enforce_binary_expression(cs, args.left.get(), args.right.get())?;
+        let operands = (left.clone(), right.clone());
+        let comparison = match operation {
+            BinaryOperation::Ne => evaluate_not(evaluate_eq(cs, left, right, &args.span)?, &args.span)?,
+            _ => evaluate_eq(cs, left, right, &args.span)?,
         };
-        let result_bool = result_option.ok_or_else(|| CompilerError::console_assertion_depends_on_input(span))?;
 
-        if !result_bool {
-            return Err(CompilerError::console_assertion_failed(span).into());
+        if !get_indicator_value(indicator) {
+            return Ok(());
+        }
+
+        if !assertion_holds(comparison, span)? {
+            let function = match operation {
+                BinaryOperation::Ne => "assert_ne",
+                _ => "assert_eq",
+            };
+            return Err(CompilerError::console_comparison_failed(
+                function,
+                render_log_value(&operands.0),
+                render_log_value(&operands.1),
+                span,
+            )
+            .into());
         }
 
         Ok(())
     }
 }
+
+/// Returns the value of an asserted boolean, which must be known while compiling.
+fn assertion_holds<'a, F: PrimeField, G: GroupType<F>>(value: ConstrainedValue<'a, F, G>, span: &Span) -> Result<bool> {
+    // Unwrap assertion value and handle errors
+    let result_option = match value {
+        ConstrainedValue::Boolean(boolean) => boolean.get_value(),
+        _ => {
+            return Err(CompilerError::console_assertion_must_be_boolean(span).into());
+        }
+    };
+    result_option.ok_or_else(|| CompilerError::console_assertion_depends_on_input(span).into())
+}
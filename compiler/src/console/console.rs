@@ -16,8 +16,13 @@
 
 //! Evaluates a macro in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, statement::get_indicator_value, GroupType};
-use leo_asg::{ConsoleFunction, ConsoleStatement};
+use crate::{
+    console::{LogLevel, LogLine},
+    program::ConstrainedProgram,
+    statement::get_indicator_value,
+    GroupType,
+};
+use leo_asg::{BinaryOperation, ConsoleFunction, ConsoleStatement};
 use leo_errors::Result;
 
 use snarkvm_fields::PrimeField;
@@ -40,18 +45,31 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                     &console.span.clone().unwrap_or_default(),
                 )?;
             }
+            ConsoleFunction::AssertEq(args) | ConsoleFunction::AssertNe(args) => {
+                let operation = match &console.function {
+                    ConsoleFunction::AssertNe(_) => BinaryOperation::Ne,
+                    _ => BinaryOperation::Eq,
+                };
+                self.evaluate_console_assert_comparison(
+                    cs,
+                    indicator,
+                    args,
+                    operation,
+                    &console.span.clone().unwrap_or_default(),
+                )?;
+            }
             ConsoleFunction::Error(string) => {
                 let string = self.format(cs, string)?;
 
                 if get_indicator_value(indicator) {
-                    tracing::error!("{}", string);
+                    self.push_log(LogLine::new(LogLevel::Error, string));
                 }
             }
             ConsoleFunction::Log(string) => {
                 let string = self.format(cs, string)?;
 
                 if get_indicator_value(indicator) {
-                    tracing::info!("{}", string);
+                    self.push_log(LogLine::new(LogLevel::Info, string));
                 }
             }
         }
@@ -16,7 +16,7 @@
 
 //! Evaluates a formatted string in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, GroupType};
+use crate::{console::render_placeholder_value, program::ConstrainedProgram, GroupType};
 use leo_asg::{CharValue, ConsoleArgs};
 use leo_errors::{CompilerError, Result};
 
@@ -59,7 +59,7 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                                 .into());
                             }
                         };
-                        out.push(self.enforce_expression(cs, parameter.get())?.to_string());
+                        out.push(render_placeholder_value(&self.enforce_expression(cs, parameter.get())?));
                         arg_index += 1;
                     }
                     '}' if !in_container => {
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Captured console output of a compiled Leo program.
+
+use crate::{CharType, ConstrainedValue, GroupType};
+
+use snarkvm_fields::PrimeField;
+use std::fmt;
+
+/// The level of a captured console line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevel {
+    /// Written by `console.log`.
+    Info,
+    /// Written by `console.error`.
+    Error,
+}
+
+/// A line written by `console.log` or `console.error` on a taken branch of the program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl LogLine {
+    pub fn new(level: LogLevel, message: String) -> Self {
+        Self { level, message }
+    }
+
+    /// Emits the line through `tracing` at its level.
+    pub fn emit(&self) {
+        match self.level {
+            LogLevel::Info => tracing::info!("{}", self.message),
+            LogLevel::Error => tracing::error!("{}", self.message),
+        }
+    }
+}
+
+impl fmt::Display for LogLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.level {
+            LogLevel::Info => write!(f, "{}", self.message),
+            LogLevel::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+/// Renders a value the way it appears in console output.
+///
+/// Chars and strings are quoted, arrays are bracketed, and circuits are written with their member names.
+pub fn render_log_value<'a, F: PrimeField, G: GroupType<F>>(value: &ConstrainedValue<'a, F, G>) -> String {
+    match value {
+        ConstrainedValue::Char(character) => format!("'{}'", render_char(&character.character)),
+        ConstrainedValue::Array(array)
+            if !array.is_empty() && array.iter().all(|x| matches!(x, ConstrainedValue::Char(_))) =>
+        {
+            let string = array
+                .iter()
+                .map(|x| match x {
+                    ConstrainedValue::Char(character) => render_char(&character.character),
+                    _ => String::new(),
+                })
+                .collect::<String>();
+            format!("\"{}\"", string)
+        }
+        ConstrainedValue::Array(array) => format!("[{}]", render_log_values(array)),
+        ConstrainedValue::Tuple(tuple) => format!("({})", render_log_values(tuple)),
+        ConstrainedValue::CircuitExpression(circuit, members) => {
+            let members = members
+                .iter()
+                .map(|member| format!("{}: {}", member.0, render_log_value(&member.1)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{} {{ {} }}", circuit.name.borrow(), members)
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Renders a value in place of a `{}` placeholder of a console format string.
+///
+/// Strings are written as their text; every other value is rendered as by [`render_log_value`].
+pub fn render_placeholder_value<'a, F: PrimeField, G: GroupType<F>>(value: &ConstrainedValue<'a, F, G>) -> String {
+    match value {
+        ConstrainedValue::Array(array)
+            if !array.is_empty() && array.iter().all(|x| matches!(x, ConstrainedValue::Char(_))) =>
+        {
+            array
+                .iter()
+                .map(|x| match x {
+                    ConstrainedValue::Char(character) => match &character.character {
+                        CharType::Scalar(scalar) => scalar.to_string(),
+                        CharType::NonScalar(non_scalar) => format!("\\u{{{:x}}}", non_scalar),
+                    },
+                    _ => String::new(),
+                })
+                .collect()
+        }
+        value => render_log_value(value),
+    }
+}
+
+fn render_log_values<'a, F: PrimeField, G: GroupType<F>>(values: &[ConstrainedValue<'a, F, G>]) -> String {
+    values.iter().map(render_log_value).collect::<Vec<_>>().join(", ")
+}
+
+fn render_char(character: &CharType) -> String {
+    match character {
+        CharType::Scalar(scalar) => scalar.escape_default().to_string(),
+        CharType::NonScalar(non_scalar) => format!("\\u{{{:x}}}", non_scalar),
+    }
+}
@@ -22,3 +22,6 @@ pub use self::console::*;
 
 pub mod format;
 pub use self::format::*;
+
+pub mod log;
+pub use self::log::*;
@@ -16,39 +16,318 @@
 
 //! Generates R1CS constraints for a compiled Leo program.
 
-use crate::{ConstrainedProgram, GroupType, Output, OutputFile};
-use leo_asg::Program;
-use leo_ast::Input;
-use leo_errors::{CompilerError, Result};
+use crate::{
+    ConstrainedProgram, FrameKind, GroupType, IndicatorAndConstrainedValue, Iterations, Output, OutputFile,
+    ProgramLimits, Progress, ITERATION_CHUNK,
+};
+use leo_asg::{Function, Program, Statement};
+use leo_ast::{FunctionInput, Input};
+use leo_errors::{CancellationToken, CompilerError, Result};
 use leo_input::LeoInputParser;
 use leo_package::inputs::InputPairs;
 
 use snarkvm_fields::PrimeField;
+use snarkvm_gadgets::boolean::Boolean;
 use snarkvm_r1cs::{ConstraintSystem, TestConstraintSystem};
-use std::path::Path;
+use std::{cell::Cell, path::Path};
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_constraints<'a, F: PrimeField, G: GroupType<F>, CS: ConstraintSystem<F>>(
     cs: &mut CS,
     program: &Program<'a>,
+    expected_input: &[FunctionInput],
     input: &Input,
+    limits: &ProgramLimits,
+    progress: &Progress,
+    cancellation: &CancellationToken,
+    record_provenance: bool,
 ) -> Result<Output> {
-    let mut resolved_program = ConstrainedProgram::<F, G>::new(program.clone());
+    match program.functions.get("main") {
+        Some(_) => generate_entrypoint_constraints::<F, G, CS>(
+            cs,
+            program,
+            "main",
+            expected_input,
+            input,
+            limits,
+            progress,
+            cancellation,
+            record_provenance,
+        ),
+        None => Err(CompilerError::no_main_function().into()),
+    }
+}
 
-    for (_, global_const) in program.global_consts.iter() {
-        resolved_program.enforce_definition_statement(cs, global_const)?;
+///
+/// Synthesizes the circuit of the program with the given function as its entrypoint.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn generate_entrypoint_constraints<'a, F: PrimeField, G: GroupType<F>, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    program: &Program<'a>,
+    entrypoint: &str,
+    expected_input: &[FunctionInput],
+    input: &Input,
+    limits: &ProgramLimits,
+    progress: &Progress,
+    cancellation: &CancellationToken,
+    record_provenance: bool,
+) -> Result<Output> {
+    let mut synthesis = Synthesis::<F, G>::new(
+        program,
+        entrypoint,
+        expected_input,
+        input,
+        limits,
+        progress,
+        cancellation,
+        record_provenance,
+    )?;
+    loop {
+        if let Some(output) = synthesis.step(cs)? {
+            return Ok(output);
+        }
     }
+}
+
+enum SynthesisStep {
+    /// Enforce the global constants and allocate the input of the entrypoint.
+    Start,
+    /// Enforce the whole body of the entrypoint, which is not a block of statements or has a budget.
+    Body,
+    /// Enforce the statement at this index of the body of the entrypoint.
+    Statement(usize),
+    /// Enforce the next chunk of iterations of the loop at this index of the body of the entrypoint.
+    Iterations(usize, Iterations),
+    Done,
+}
 
-    let main = {
-        let program = program;
-        program.functions.get("main").cloned()
-    };
+///
+/// Synthesizes the circuit of an entrypoint a bounded amount of work at a time, for hosts that cannot block.
+///
+/// Every [`Synthesis::step`] enforces one statement of the body of the entrypoint, or [`ITERATION_CHUNK`]
+/// iterations of a loop that is one of those statements. A loop nested in another statement is enforced
+/// in the step of that statement. The steps synthesize the same constraints, under the same names, as a
+/// single call would, which is how [`generate_entrypoint_constraints`] runs them.
+///
+pub struct Synthesis<'a, F: PrimeField, G: GroupType<F>> {
+    program: ConstrainedProgram<'a, F, G>,
+    function: &'a Function<'a>,
+    expected_input: Vec<FunctionInput>,
+    input: Input,
+    progress: Progress,
+    cancellation: CancellationToken,
+    next: SynthesisStep,
+    /// The values that the statements enforced so far may return.
+    results: Vec<IndicatorAndConstrainedValue<'a, F, G>>,
+    /// The phases started and not yet ended, innermost last.
+    phases: Vec<&'static str>,
+    /// The number of namespaces entered and not yet left.
+    namespaces: usize,
+}
 
-    match main {
-        Some(function) => {
-            let result = resolved_program.enforce_main_function(cs, function, input)?;
-            Ok(result)
+impl<'a, F: PrimeField, G: GroupType<F>> Synthesis<'a, F, G> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        program: &Program<'a>,
+        entrypoint: &str,
+        expected_input: &[FunctionInput],
+        input: &Input,
+        limits: &ProgramLimits,
+        progress: &Progress,
+        cancellation: &CancellationToken,
+        record_provenance: bool,
+    ) -> Result<Self> {
+        let function = match program.functions.get(entrypoint) {
+            Some(function) => *function,
+            None => return Err(CompilerError::unknown_entrypoint(entrypoint).into()),
+        };
+
+        let mut resolved_program = ConstrainedProgram::<F, G>::new(program.clone())
+            .with_limits(limits.clone())
+            .with_progress(progress.clone())
+            .with_cancellation(cancellation);
+        if record_provenance {
+            resolved_program = resolved_program.with_provenance();
         }
-        _ => Err(CompilerError::no_main_function().into()),
+
+        Ok(Self {
+            program: resolved_program,
+            function,
+            expected_input: expected_input.to_vec(),
+            input: input.clone(),
+            progress: progress.clone(),
+            cancellation: cancellation.clone(),
+            next: SynthesisStep::Start,
+            results: vec![],
+            phases: vec![],
+            namespaces: 0,
+        })
+    }
+
+    ///
+    /// Does the next step of the synthesis, returning the output once the circuit is complete.
+    ///
+    /// After an error or the output, there are no steps left.
+    ///
+    pub fn step<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Result<Option<Output>> {
+        let stepped = self.advance(cs);
+        if stepped.is_err() {
+            // Leave what was entered, as the unwinding of a single call would.
+            while let Some(phase) = self.phases.pop() {
+                self.progress.end_phase(phase);
+            }
+            for _ in 0..self.namespaces {
+                cs.get_root().pop_namespace();
+            }
+            self.namespaces = 0;
+            self.next = SynthesisStep::Done;
+        }
+        stepped
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self.next, SynthesisStep::Done)
+    }
+
+    fn advance<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Result<Option<Output>> {
+        let function = self.function;
+        let body = function.body.get().ok_or_else(|| {
+            CompilerError::function_has_unresolved_errors(
+                &function.name.borrow().name,
+                &function.span.clone().unwrap_or_default(),
+            )
+        })?;
+        let indicator = Boolean::constant(true);
+
+        match std::mem::replace(&mut self.next, SynthesisStep::Done) {
+            SynthesisStep::Start => {
+                let global_consts = self.program.asg.global_consts.values().copied().collect::<Vec<_>>();
+                for global_const in global_consts {
+                    self.program.enforce_definition_statement(cs, global_const)?;
+                }
+
+                self.cancellation.check("constraint synthesis")?;
+                self.start_phase("constraint synthesis", None);
+                let arguments = self
+                    .program
+                    .allocate_main_inputs(cs, function, &self.expected_input, &self.input)?;
+                self.program.bind_arguments(cs, function, &arguments)?;
+
+                self.next = match (function.budget(), body) {
+                    (None, Statement::Block(block)) => {
+                        // The namespaces that enforcing the body as a statement enters.
+                        let span = block.span.clone().unwrap_or_default();
+                        self.enter_namespace(cs, format!("statement {}:{}", span.line_start, span.col_start));
+                        self.enter_namespace(cs, format!("block {}:{}", span.line_start, span.col_start));
+                        SynthesisStep::Statement(0)
+                    }
+                    _ => SynthesisStep::Body,
+                };
+            }
+            SynthesisStep::Body => {
+                let results = match (function.budget(), body) {
+                    (Some(budget), Statement::Block(block)) => self
+                        .program
+                        .enforce_budgeted_block(cs, &indicator, function, block, budget)?,
+                    _ => self.program.enforce_statement(cs, &indicator, body)?,
+                };
+                self.results.extend(results);
+                return self.finish(cs).map(Some);
+            }
+            SynthesisStep::Statement(index) => match Self::statement(body, index) {
+                Some(Statement::Iteration(iteration)) => {
+                    // Enforce the loop like `enforce_statement` does, but a chunk of iterations per step.
+                    let span = iteration.span.clone().unwrap_or_default();
+                    self.program.unrolled_statements += 1;
+                    self.program.checkpoint.tick()?;
+                    self.enter_namespace(cs, format!("statement {}:{}", span.line_start, span.col_start));
+                    self.program.enter_frame(cs, FrameKind::Statement, &span);
+                    let iterations = self.program.start_iterations(cs, iteration)?;
+                    self.start_phase("loop unrolling", iterations.chunks);
+                    self.next = SynthesisStep::Iterations(index, iterations);
+                }
+                Some(statement) => {
+                    let results = self.program.enforce_statement(cs, &indicator, statement)?;
+                    self.results.extend(results);
+                    self.next = SynthesisStep::Statement(index + 1);
+                }
+                None => {
+                    self.leave_namespace(cs);
+                    self.leave_namespace(cs);
+                    return self.finish(cs).map(Some);
+                }
+            },
+            SynthesisStep::Iterations(index, mut iterations) => {
+                let iteration = match Self::statement(body, index) {
+                    Some(Statement::Iteration(iteration)) => iteration,
+                    _ => unreachable!("the loop is a statement of the body"),
+                };
+                let left = self.program.enforce_iterations(
+                    cs,
+                    &indicator,
+                    iteration,
+                    &mut iterations,
+                    ITERATION_CHUNK,
+                    &mut self.results,
+                )?;
+                if left {
+                    self.next = SynthesisStep::Iterations(index, iterations);
+                } else {
+                    self.end_phase();
+                    self.program.exit_frame(cs);
+                    self.leave_namespace(cs);
+                    self.next = SynthesisStep::Statement(index + 1);
+                }
+            }
+            SynthesisStep::Done => (),
+        }
+        Ok(None)
+    }
+
+    /// Selects the value the entrypoint returns, and writes the output.
+    fn finish<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Result<Output> {
+        let function = self.function;
+        let span = function.span.clone().unwrap_or_default();
+        let results = std::mem::take(&mut self.results);
+        let value = ConstrainedProgram::<F, G>::conditionally_select_result(cs, &function.output, results, &span)?;
+        self.program.check_circuit_limits(cs, &span)?;
+        let mut output = Output::new(&self.program.asg, self.input.get_registers(), value, &span)?;
+        self.end_phase();
+        self.cancellation.check("constraint synthesis")?;
+
+        output.logs = self.program.take_logs();
+        output.provenance = self.program.take_provenance(cs);
+        Ok(output)
+    }
+
+    fn statement(body: &'a Statement<'a>, index: usize) -> Option<&'a Statement<'a>> {
+        match body {
+            Statement::Block(block) => block.statements.get(index).map(Cell::get),
+            _ => None,
+        }
+    }
+
+    fn start_phase(&mut self, name: &'static str, items: Option<usize>) {
+        self.progress.start_phase(name, items);
+        self.phases.push(name);
+    }
+
+    fn end_phase(&mut self) {
+        if let Some(phase) = self.phases.pop() {
+            self.progress.end_phase(phase);
+        }
+    }
+
+    // A namespace is entered on the root constraint system directly, as it outlives the step that enters it.
+    fn enter_namespace<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, name: String) {
+        cs.get_root().push_namespace(|| name);
+        self.namespaces += 1;
+    }
+
+    fn leave_namespace<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        cs.get_root().pop_namespace();
+        self.namespaces -= 1;
     }
 }
 
@@ -56,8 +335,9 @@ pub fn generate_test_constraints<'a, F: PrimeField, G: GroupType<F>>(
     program: &Program<'a>,
     input: InputPairs,
     output_directory: &Path,
+    limits: &ProgramLimits,
 ) -> Result<(u32, u32)> {
-    let mut resolved_program = ConstrainedProgram::<F, G>::new(program.clone());
+    let mut resolved_program = ConstrainedProgram::<F, G>::new(program.clone()).with_limits(limits.clone());
     let program_name = program.name.clone();
 
     // Get default input
@@ -123,10 +403,19 @@ pub fn generate_test_constraints<'a, F: PrimeField, G: GroupType<F>>(
         input.parse_state(state_ast)?;
 
         // run test function on new program with input
+        let test_function: leo_ast::Function = (*function).into();
         let result = resolved_program.enforce_main_function(
-            cs, function, &input, // pass program input into every test
+            cs,
+            function,
+            &test_function.input,
+            &input, // pass program input into every test
         );
 
+        // replay the console output of this test only
+        for line in resolved_program.take_logs() {
+            line.emit();
+        }
+
         match (result.is_ok(), cs.is_satisfied()) {
             (true, true) => {
                 tracing::info!("{} ... ok\n", full_test_name);
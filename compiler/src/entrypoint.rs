@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Describes the interface of the entrypoints of a program.
+
+use leo_asg::{Function, Layout};
+use leo_ast::ArtifactFormat;
+use leo_errors::{CompilerError, Result};
+
+use serde::{Deserialize, Serialize};
+
+/// The format of interface files, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const INTERFACE_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "interface",
+    version: 1,
+    json: true,
+};
+
+/// A parameter of an entrypoint, which is given by the input file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Const parameters are compiled into the circuit rather than allocated as private variables.
+    #[serde(rename = "const")]
+    pub const_: bool,
+    /// The scalar slots the parameter flattens to. Interfaces written before it was recorded read as zero.
+    #[serde(default)]
+    pub slots: usize,
+}
+
+/// A scalar value of the output of an entrypoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceSlot {
+    /// The access path of the value from the output, e.g. `output.1.y`.
+    pub path: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+///
+/// The parameters and outputs of an entrypoint, written as `<entrypoint>/<program>.interface.json`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interface {
+    pub entrypoint: String,
+    pub parameters: Vec<InterfaceParameter>,
+    pub outputs: Vec<String>,
+    /// The scalar slots the output flattens to, which the `[registers]` section is compared with
+    /// whether its registers declare scalars or aggregates. Interfaces written before they were recorded read as empty.
+    #[serde(default)]
+    pub output_slots: Vec<InterfaceSlot>,
+}
+
+impl Interface {
+    pub fn new(function: &Function) -> Self {
+        let span = function.span.clone().unwrap_or_default();
+        let parameters = function
+            .arguments
+            .values()
+            .map(|variable| {
+                let variable = variable.get().borrow();
+                let slots = function.scope.types.slot_count(&variable.type_, &span);
+                InterfaceParameter {
+                    name: variable.name.name.to_string(),
+                    type_: variable.type_.to_string(),
+                    const_: variable.const_,
+                    slots: slots.ok().flatten().unwrap_or(usize::MAX),
+                }
+            })
+            .collect();
+        let outputs = function.outputs().iter().map(ToString::to_string).collect();
+        let output_slots = Layout::flatten(&function.output, "output", &span)
+            .map(|layout| {
+                layout
+                    .slots
+                    .into_iter()
+                    .map(|slot| InterfaceSlot {
+                        path: slot.path,
+                        type_: slot.type_.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Interface {
+            entrypoint: function.name.borrow().name.to_string(),
+            parameters,
+            outputs,
+            output_slots,
+        }
+    }
+
+    /// Serializes the interface into a JSON string.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(|e| CompilerError::failed_to_convert_interface_to_json(&e))?)
+    }
+}
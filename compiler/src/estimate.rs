@@ -0,0 +1,268 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimates the size of the circuit of every function from the checked and optimized program.
+
+use leo_asg::*;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    convert::TryFrom,
+    ops::{Add, Mul},
+};
+
+///
+/// The estimated size of the circuit of some code.
+///
+/// Operations whose value is constant are computed by the compiler and cost nothing. Every other
+/// operation is an instruction, costing a constraint per bit of the integers it operates on and one for
+/// other scalars. Gadgets differ in how many constraints they take per bit, so this is a rough measure.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub instructions: usize,
+    pub constraints: usize,
+}
+
+impl Add for CostEstimate {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        CostEstimate {
+            instructions: self.instructions.saturating_add(other.instructions),
+            constraints: self.constraints.saturating_add(other.constraints),
+        }
+    }
+}
+
+impl Mul<usize> for CostEstimate {
+    type Output = Self;
+
+    fn mul(self, times: usize) -> Self {
+        CostEstimate {
+            instructions: self.instructions.saturating_mul(times),
+            constraints: self.constraints.saturating_mul(times),
+        }
+    }
+}
+
+/// The estimated size of a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionCost {
+    /// The body of the function, with every loop counted once per iteration.
+    pub own: CostEstimate,
+    /// The body of the function and of every function it calls, each call being inlined.
+    pub total: CostEstimate,
+}
+
+///
+/// Returns the estimated size of every function of the program, keyed by name, or `Circuit::function`
+/// for circuit member functions.
+///
+/// Only the program itself is estimated, so calls to the functions of its imports count nothing.
+///
+pub fn estimate_costs(program: &Program) -> IndexMap<String, FunctionCost> {
+    let mut functions = vec![];
+    for (name, function) in program.functions.iter() {
+        functions.push((name.clone(), *function));
+    }
+    for (circuit_name, circuit) in program.circuits.iter() {
+        for (name, member) in circuit.members.borrow().iter() {
+            if let CircuitMember::Function(function) = member {
+                functions.push((format!("{}::{}", circuit_name, name), *function));
+            }
+        }
+    }
+
+    let estimated = functions
+        .into_iter()
+        .map(|(name, function)| {
+            let mut director = VisitorDirector::new(Estimator::new(1));
+            if let Some(body) = function.body.get() {
+                director.visit_statement(&Cell::new(body)).ok();
+            }
+            (name, director.visitor())
+        })
+        .collect::<IndexMap<_, _>>();
+
+    let mut totals = IndexMap::new();
+    for name in estimated.keys() {
+        total_cost(name, &estimated, &mut totals, &mut vec![]);
+    }
+    estimated
+        .iter()
+        .map(|(name, estimator)| {
+            let cost = FunctionCost {
+                own: estimator.cost,
+                total: totals.get(name.as_str()).copied().unwrap_or_default(),
+            };
+            (name.clone(), cost)
+        })
+        .collect()
+}
+
+/// Adds up the cost of the function and of the functions it calls, leaving out calls back into a function being added up.
+fn total_cost<'a>(
+    name: &'a str,
+    estimated: &'a IndexMap<String, Estimator>,
+    totals: &mut IndexMap<&'a str, CostEstimate>,
+    calling: &mut Vec<&'a str>,
+) -> CostEstimate {
+    if let Some(total) = totals.get(name) {
+        return *total;
+    }
+    let estimator = match estimated.get(name) {
+        Some(estimator) if !calling.contains(&name) => estimator,
+        _ => return CostEstimate::default(),
+    };
+
+    calling.push(name);
+    let mut total = estimator.cost;
+    for (callee, times) in estimator.calls.iter() {
+        total = total + total_cost(callee, estimated, totals, calling) * *times;
+    }
+    calling.pop();
+    totals.insert(name, total);
+    total
+}
+
+/// Adds up the cost of the code it visits, each operation counted `multiplier` times.
+struct Estimator {
+    multiplier: usize,
+    cost: CostEstimate,
+    /// Maps the functions called => the number of times they are called.
+    calls: IndexMap<String, usize>,
+}
+
+impl Estimator {
+    fn new(multiplier: usize) -> Self {
+        Estimator {
+            multiplier,
+            cost: CostEstimate::default(),
+            calls: IndexMap::new(),
+        }
+    }
+
+    fn add(&mut self, constraints: usize) {
+        let cost = CostEstimate {
+            instructions: 1,
+            constraints,
+        };
+        self.cost = self.cost + cost * self.multiplier;
+    }
+}
+
+/// Returns the constraints of an operation on values of the given type.
+fn operation_cost(type_: Option<Type>) -> usize {
+    match type_ {
+        Some(Type::Integer(integer_type)) => integer_type.bit_width(),
+        Some(type_ @ Type::Array(..)) | Some(type_ @ Type::Tuple(_)) | Some(type_ @ Type::Circuit(_)) => {
+            Layout::slot_count(&type_)
+        }
+        _ => 1,
+    }
+}
+
+/// Returns the value of a constant integer, if it fits in an `i128`.
+fn const_i128<'a>(expression: &'a Expression<'a>) -> Option<i128> {
+    match expression.const_value()? {
+        ConstValue::Int(value) => value.raw_value().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the number of iterations of a loop, or `None` if its bounds are not known.
+fn iterations(iteration: &IterationStatement) -> Option<usize> {
+    let from = const_i128(iteration.start.get())?;
+    let to = const_i128(iteration.stop.get())?;
+    let step = match iteration.step.get() {
+        Some(step) => const_i128(step)?,
+        None if from <= to => 1,
+        None => -1,
+    };
+
+    // The counter moves by the step for as long as it is within the bounds, see `IterationStatement::counters`.
+    let (distance, step) = match step {
+        0 => return Some(0),
+        step if step > 0 => (to.checked_sub(from)?, step),
+        step => (from.checked_sub(to)?, step.checked_neg()?),
+    };
+    let distance = match iteration.inclusive {
+        true => distance,
+        false => distance.saturating_sub(1),
+    };
+    if distance < 0 {
+        return Some(0);
+    }
+    Some(usize::try_from(distance / step + 1).unwrap_or(usize::MAX))
+}
+
+impl<'a> ExpressionVisitor<'a> for Estimator {
+    fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        let expression = input.get();
+        if expression.is_consty() {
+            return VisitResult::SkipChildren;
+        }
+        match expression {
+            Expression::Binary(binary) => self.add(operation_cost(binary.left.get().get_type())),
+            Expression::Unary(unary) => self.add(operation_cost(unary.inner.get().get_type())),
+            Expression::Ternary(ternary) => self.add(operation_cost(ternary.if_true.get().get_type())),
+            Expression::Cast(cast) => self.add(operation_cost(Some(cast.target_type.clone()))),
+            Expression::Bits(bits) => {
+                let width = bit_width(&bits.target_type).or_else(|| bit_width(&bits.inner.get().get_type()?));
+                self.add(width.unwrap_or(1))
+            }
+            Expression::Call(call) => {
+                let function = call.function.get();
+                let name = match function.circuit.get() {
+                    Some(circuit) => format!("{}::{}", circuit.name.borrow().name, function.name.borrow().name),
+                    None => function.name.borrow().name.to_string(),
+                };
+                let times = self.calls.entry(name).or_insert(0);
+                *times = times.saturating_add(self.multiplier);
+            }
+            _ => (),
+        }
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Estimator {
+    fn visit_console(&mut self, input: &ConsoleStatement<'a>) -> VisitResult {
+        match &input.function {
+            ConsoleFunction::Assert(_) | ConsoleFunction::AssertEq(_) | ConsoleFunction::AssertNe(_) => self.add(1),
+            ConsoleFunction::Error(_) | ConsoleFunction::Log(_) => (),
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_iteration(&mut self, input: &IterationStatement<'a>) -> VisitResult {
+        // A loop whose bounds are not known fails to unroll, so counting it once keeps the estimate a lower bound.
+        let times = iterations(input).unwrap_or(1);
+        let mut director = VisitorDirector::new(Estimator::new(self.multiplier.saturating_mul(times)));
+        director.visit_statement(&input.body).ok();
+        let body = director.visitor();
+
+        self.cost = self.cost + body.cost;
+        for (name, calls) in body.calls {
+            let times = self.calls.entry(name).or_insert(0);
+            *times = times.saturating_add(calls);
+        }
+        VisitResult::SkipChildren
+    }
+}
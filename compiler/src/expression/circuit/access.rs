@@ -53,6 +53,8 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                     );
                 }
             }
+        } else if let Some(value) = expr.static_value() {
+            self.enforce_expression(cs, value)
         } else {
             Err(CompilerError::invalid_circuit_static_member_access(&expr.member.name, &expr.member.span).into())
         }
@@ -26,7 +26,7 @@ use crate::{
     FieldType, GroupType,
 };
 use leo_asg::{expression::*, ConstValue, Expression, Node};
-use leo_errors::{Result, Span};
+use leo_errors::{CompilerError, Result, Span};
 
 use snarkvm_fields::PrimeField;
 use snarkvm_gadgets::boolean::Boolean;
@@ -36,7 +36,7 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
     pub(crate) fn enforce_const_value<CS: ConstraintSystem<F>>(
         &mut self,
         cs: &mut CS,
-        value: &'a ConstValue<'a>,
+        value: &ConstValue<'a>,
         span: &Span,
     ) -> Result<ConstrainedValue<'a, F, G>> {
         Ok(match value {
@@ -83,7 +83,7 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                     ));
                 }
 
-                ConstrainedValue::CircuitExpression(circuit, constrained_members)
+                ConstrainedValue::CircuitExpression(*circuit, constrained_members)
             }
         })
     }
@@ -96,11 +96,14 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         let span = &expression.span().cloned().unwrap_or_default();
         match expression {
             // Cast
-            Expression::Cast(_) => unimplemented!("casts not implemented"),
+            Expression::Cast(cast) => self.enforce_cast(cs, cast, span),
 
             // LengthOf
             Expression::LengthOf(lengthof) => self.enforce_lengthof(cs, lengthof, span),
 
+            // Bit decomposition
+            Expression::Bits(bits) => self.enforce_bits(cs, bits, span),
+
             // Variables
             Expression::VariableRef(variable_ref) => self.evaluate_ref(variable_ref),
 
@@ -127,7 +130,12 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                     BinaryOperation::Gt => evaluate_gt(cs, resolved_left, resolved_right, span),
                     BinaryOperation::Le => evaluate_le(cs, resolved_left, resolved_right, span),
                     BinaryOperation::Lt => evaluate_lt(cs, resolved_left, resolved_right, span),
-                    _ => unimplemented!("unimplemented binary operator"),
+                    operation => Err(CompilerError::unsupported_construct(
+                        format!("the `{}` operator", operation.as_ref()),
+                        "constraint synthesis",
+                        span,
+                    )
+                    .into()),
                 }
             }
 
@@ -138,7 +146,12 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
                     enforce_negate(cs, resolved_inner, span)
                 }
                 UnaryOperation::Not => Ok(evaluate_not(self.enforce_expression(cs, inner.get())?, span)?),
-                _ => unimplemented!("unimplemented unary operator"),
+                UnaryOperation::BitNot => Err(CompilerError::unsupported_construct(
+                    format!("the `{}` operator", operation.as_ref()),
+                    "constraint synthesis",
+                    span,
+                )
+                .into()),
             },
 
             Expression::Ternary(TernaryExpression {
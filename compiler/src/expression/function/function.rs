@@ -18,7 +18,7 @@
 
 use std::cell::Cell;
 
-use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use crate::{program::ConstrainedProgram, value::ConstrainedValue, FrameKind, GroupType};
 use leo_asg::{Expression, Function};
 use leo_errors::{Result, Span};
 
@@ -44,7 +44,9 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             )
         };
 
+        self.enter_frame(cs, FrameKind::Call, span);
         let return_value = self.enforce_function(&mut cs.ns(name_unique), function, target, arguments)?;
+        self.exit_frame(cs);
 
         Ok(return_value)
     }
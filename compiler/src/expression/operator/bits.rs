@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Enforces the bit decomposition intrinsics in a compiled Leo program.
+
+use crate::{
+    program::ConstrainedProgram,
+    value::{ConstrainedValue, FieldType, Integer},
+    GroupType,
+};
+use leo_asg::{BitsDirection, BitsExpression, Type};
+use leo_errors::{CompilerError, Result, Span};
+
+use snarkvm_fields::PrimeField;
+use snarkvm_r1cs::ConstraintSystem;
+
+impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
+    /// Enforce `to_bits_le` and `from_bits_le` expressions
+    pub fn enforce_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        bits: &'a BitsExpression<'a>,
+        span: &Span,
+    ) -> Result<ConstrainedValue<'a, F, G>> {
+        let value = self.enforce_expression(cs, bits.inner.get())?;
+        let namespace = cs.ns(|| format!("{} {}:{}", bits.direction.name(), span.line_start, span.col_start));
+
+        Ok(match (bits.direction, value) {
+            (BitsDirection::ToBitsLe, ConstrainedValue::Integer(integer)) => {
+                ConstrainedValue::Array(integer.get_bits().into_iter().map(ConstrainedValue::Boolean).collect())
+            }
+            (BitsDirection::ToBitsLe, ConstrainedValue::Field(field)) => ConstrainedValue::Array(
+                field
+                    .to_bits_le(namespace, span)?
+                    .into_iter()
+                    .map(ConstrainedValue::Boolean)
+                    .collect(),
+            ),
+            (BitsDirection::FromBitsLe, ConstrainedValue::Array(array)) => {
+                let booleans = array
+                    .into_iter()
+                    .map(|element| match element {
+                        ConstrainedValue::Boolean(bit) => Ok(bit),
+                        element => Err(CompilerError::incompatible_types(
+                            format!("{}({})", bits.direction.name(), element),
+                            span,
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match &bits.target_type {
+                    Type::Integer(integer_type) => ConstrainedValue::Integer(Integer::from_bits_le(integer_type, &booleans)),
+                    Type::Field => ConstrainedValue::Field(FieldType::from_bits_le(namespace, &booleans, span)?),
+                    type_ => {
+                        return Err(CompilerError::incompatible_types(
+                            format!("{}() -> {}", bits.direction.name(), type_),
+                            span,
+                        )
+                        .into());
+                    }
+                }
+            }
+            (direction, value) => {
+                return Err(CompilerError::incompatible_types(format!("{}({})", direction.name(), value), span).into());
+            }
+        })
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Enforces a cast in a compiled Leo program.
+
+use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use leo_asg::{CastExpression, Type};
+use leo_errors::{CompilerError, Result, Span};
+
+use snarkvm_fields::PrimeField;
+use snarkvm_r1cs::ConstraintSystem;
+
+impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
+    /// Enforce casts of integers to wider types of the same signedness, such as those of integer widening.
+    pub fn enforce_cast<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        cast: &'a CastExpression<'a>,
+        span: &Span,
+    ) -> Result<ConstrainedValue<'a, F, G>> {
+        let value = self.enforce_expression(cs, cast.inner.get())?;
+
+        Ok(match (value, &cast.target_type) {
+            (ConstrainedValue::Integer(integer), Type::Integer(target))
+                if integer.get_type().is_signed() == target.is_signed()
+                    && integer.get_type().bit_width() <= target.bit_width() =>
+            {
+                ConstrainedValue::Integer(integer.widen(target))
+            }
+            (value, target) => {
+                return Err(CompilerError::incompatible_types(format!("{} as {}", value, target), span).into());
+            }
+        })
+    }
+}
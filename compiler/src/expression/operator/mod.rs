@@ -14,5 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod bits;
+pub use self::bits::*;
+
+pub mod cast;
+pub use self::cast::*;
+
 pub mod lengthof;
 pub use self::lengthof::*;
@@ -34,6 +34,9 @@ pub fn evaluate_lt<'a, F: PrimeField, G: GroupType<F>, CS: ConstraintSystem<F>>(
         (ConstrainedValue::Integer(num_1), ConstrainedValue::Integer(num_2)) => {
             num_1.less_than(unique_namespace, &num_2)
         }
+        (ConstrainedValue::Field(field_1), ConstrainedValue::Field(field_2)) => {
+            field_1.less_than(unique_namespace, &field_2)
+        }
         (val_1, val_2) => {
             return Err(CompilerError::incompatible_types(format!("{} < {}", val_1, val_2), span).into());
         }
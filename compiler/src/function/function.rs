@@ -16,9 +16,9 @@
 
 //! Enforces constraints on a function in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType, IndicatorAndConstrainedValue};
 
-use leo_asg::{Expression, Function, FunctionQualifier};
+use leo_asg::{BlockStatement, Expression, Function, FunctionQualifier, Node, Statement};
 use leo_errors::{CompilerError, Result};
 use std::cell::Cell;
 
@@ -47,22 +47,7 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             None
         };
 
-        if function.arguments.len() != arguments.len() {
-            return Err(CompilerError::function_input_not_found(
-                &function.name.borrow().name.to_string(),
-                "arguments length invalid",
-                &function.span.clone().unwrap_or_default(),
-            )
-            .into());
-        }
-
-        // Store input values as new variables in resolved program
-        for ((_, variable), input_expression) in function.arguments.iter().zip(arguments.iter()) {
-            let input_value = self.enforce_expression(cs, input_expression.get())?;
-            let variable = variable.get().borrow();
-
-            self.store(variable.id, input_value);
-        }
+        self.bind_arguments(cs, function, arguments)?;
 
         // Evaluate every statement in the function and save all potential results
         let mut results = vec![];
@@ -70,11 +55,21 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
 
         let output = function.output.clone();
 
-        let mut result = self.enforce_statement(
-            cs,
-            &indicator,
-            function.body.get().expect("attempted to call function header"),
-        )?;
+        // Count unrolled statements per function.
+        let caller_statements = std::mem::take(&mut self.unrolled_statements);
+        let body = function.body.get().ok_or_else(|| {
+            CompilerError::function_has_unresolved_errors(
+                &function.name.borrow().name,
+                &function.span.clone().unwrap_or_default(),
+            )
+        })?;
+        let mut result = match (function.budget(), body) {
+            (Some(budget), Statement::Block(block)) => {
+                self.enforce_budgeted_block(cs, &indicator, function, block, budget)?
+            }
+            _ => self.enforce_statement(cs, &indicator, body)?,
+        };
+        self.unrolled_statements = caller_statements;
 
         results.append(&mut result);
 
@@ -94,4 +89,77 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         // Conditionally select a result based on returned indicators
         Self::conditionally_select_result(cs, &output, results, &function.span.clone().unwrap_or_default())
     }
+
+    /// Stores the values of the arguments of a call as the parameters of the function.
+    pub(crate) fn bind_arguments<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        function: &'a Function<'a>,
+        arguments: &[Cell<&'a Expression<'a>>],
+    ) -> Result<()> {
+        if function.arguments.len() != arguments.len() {
+            return Err(CompilerError::function_input_not_found(
+                &function.name.borrow().name.to_string(),
+                "arguments length invalid",
+                &function.span.clone().unwrap_or_default(),
+            )
+            .into());
+        }
+
+        // Store input values as new variables in resolved program
+        for ((_, variable), input_expression) in function.arguments.iter().zip(arguments.iter()) {
+            let input_value = self.enforce_expression(cs, input_expression.get())?;
+            let variable = variable.get().borrow();
+
+            self.store(variable.id, input_value);
+        }
+        Ok(())
+    }
+
+    /// Enforces the body of a function annotated with `@budget`.
+    /// Returns an error if the constraints synthesized by the body, including those of inlined calls,
+    /// exceed the budget. The error names the three statements that synthesize the most constraints.
+    pub(crate) fn enforce_budgeted_block<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        indicator: &Boolean,
+        function: &'a Function<'a>,
+        block: &BlockStatement<'a>,
+        budget: usize,
+    ) -> Result<Vec<IndicatorAndConstrainedValue<'a, F, G>>> {
+        let span = block.span.clone().unwrap_or_default();
+        let mut cs = cs.ns(|| format!("block {}:{}", &span.line_start, &span.col_start));
+
+        let start = cs.num_constraints();
+        let mut results = Vec::with_capacity(block.statements.len());
+        let mut contributors = Vec::with_capacity(block.statements.len());
+        for statement in block.statements.iter() {
+            let before = cs.num_constraints();
+            results.extend(self.enforce_statement(&mut cs, indicator, statement.get())?);
+            let span = statement.get().span().cloned().unwrap_or_default();
+            contributors.push((cs.num_constraints() - before, span));
+        }
+
+        let total = cs.num_constraints() - start;
+        if total > budget {
+            // Stable, so statements of equal cost stay in source order.
+            contributors.sort_by(|(a, _), (b, _)| b.cmp(a));
+            let largest = contributors
+                .iter()
+                .take(3)
+                .map(|(count, span)| format!("line {} ({} instructions)", span.line_start, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(CompilerError::function_budget_exceeded(
+                &function.name.borrow().name,
+                total,
+                budget,
+                largest,
+                &function.span.clone().unwrap_or_default(),
+            )
+            .into());
+        }
+
+        Ok(results)
+    }
 }
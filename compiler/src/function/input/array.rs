@@ -14,62 +14,39 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-//! Allocates an array as a main function input parameter in a compiled Leo program.
+//! Flattens an array given as a main function input parameter in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use crate::{program::ConstrainedProgram, GroupType};
 
 use leo_asg::Type;
 use leo_ast::InputValue;
 use leo_errors::{CompilerError, Result, Span};
 
 use snarkvm_fields::PrimeField;
-use snarkvm_r1cs::ConstraintSystem;
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
-    pub fn allocate_array<CS: ConstraintSystem<F>>(
-        &mut self,
-        cs: &mut CS,
-        name: &str,
-        array_type: &Type,
+    ///
+    /// Returns the scalar values of the array `input_value`, in the order of the array's layout.
+    ///
+    pub fn flatten_input_array(
+        element_type: &Type,
         array_len: usize,
-        input_value: Option<InputValue>,
+        input_value: InputValue,
         span: &Span,
-    ) -> Result<ConstrainedValue<'a, F, G>> {
-        // Build the array value using the expected types.
-        let mut array_value = vec![];
-
+    ) -> Result<Vec<InputValue>> {
         match input_value {
-            Some(InputValue::Array(arr)) => {
+            InputValue::Array(arr) => {
                 if array_len != arr.len() {
                     return Err(CompilerError::invalid_input_array_dimensions(arr.len(), array_len, span).into());
                 }
 
-                // Allocate each value in the current row
-                for (i, value) in arr.into_iter().enumerate() {
-                    let value_name = format!("{}_{}", &name, &i.to_string());
-
-                    array_value.push(self.allocate_main_function_input(
-                        cs,
-                        array_type,
-                        &value_name,
-                        Some(value),
-                        span,
-                    )?)
+                let mut values = vec![];
+                for value in arr {
+                    values.extend(Self::flatten_input_value(element_type, value, span)?);
                 }
+                Ok(values)
             }
-            None => {
-                // Allocate all row values as none
-                for i in 0..array_len {
-                    let value_name = format!("{}_{}", &name, &i.to_string());
-
-                    array_value.push(self.allocate_main_function_input(cs, array_type, &value_name, None, span)?);
-                }
-            }
-            _ => {
-                return Err(CompilerError::invalid_function_input_array(input_value.unwrap(), span).into());
-            }
+            input_value => Err(CompilerError::invalid_function_input_array(input_value, span).into()),
         }
-
-        Ok(ConstrainedValue::Array(array_value))
     }
 }
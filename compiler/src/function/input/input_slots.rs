@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Counts the scalar input slots taken by the parameters of `main`.
+
+use leo_asg::Function;
+use leo_errors::{CompilerError, Result};
+
+/// The number of largest parameters named when the input slot limit is exceeded.
+const LARGEST_PARAMETERS_REPORTED: usize = 3;
+
+///
+/// The scalar input slots taken by the non-const parameters of a function.
+///
+/// Const parameters are compiled into the circuit, so they take no input slots.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputSlots {
+    /// The slots taken by every non-const parameter, largest first.
+    pub parameters: Vec<(String, usize)>,
+    /// The slots taken by all non-const parameters together.
+    pub total: usize,
+}
+
+impl InputSlots {
+    ///
+    /// Counts the slots of the parameters with the size cache of the function's program.
+    ///
+    /// The program was checked for circuits that contain themselves, so every count is finite.
+    ///
+    pub fn count(function: &Function) -> Self {
+        let span = function.span.clone().unwrap_or_default();
+        let mut parameters: Vec<(String, usize)> = function
+            .arguments
+            .iter()
+            .map(|(name, variable)| (name, variable.get().borrow()))
+            .filter(|(_, variable)| !variable.const_)
+            .map(|(name, variable)| {
+                let slots = function.scope.types.slot_count(&variable.type_, &span);
+                (name.clone(), slots.ok().flatten().unwrap_or(usize::MAX))
+            })
+            .collect();
+        parameters.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let total = parameters
+            .iter()
+            .fold(0usize, |total, (_, slots)| total.saturating_add(*slots));
+        InputSlots { parameters, total }
+    }
+
+    ///
+    /// Returns an error naming the largest parameters if they take more than `limit` slots together.
+    ///
+    pub fn check(&self, limit: usize) -> Result<()> {
+        if self.total <= limit {
+            return Ok(());
+        }
+
+        let largest = self
+            .parameters
+            .iter()
+            .take(LARGEST_PARAMETERS_REPORTED)
+            .map(|(name, slots)| format!("`{}` ({} slots)", name, slots))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(CompilerError::input_slot_limit_exceeded(self.total, limit, largest).into())
+    }
+}
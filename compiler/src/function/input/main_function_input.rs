@@ -15,6 +15,8 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 //! Allocates a main function input parameter in a compiled Leo program.
+//!
+//! A parameter is allocated scalar by scalar, each in the slot and under the path its type's [`Layout`] gives it.
 
 use crate::{
     address::Address,
@@ -23,14 +25,13 @@ use crate::{
         boolean::input::bool_from_input, char::char_from_input, field::input::field_from_input,
         group::input::group_from_input, ConstrainedValue,
     },
-    CharType, FieldType, GroupType, Integer,
+    GroupType, Integer,
 };
-use leo_asg::{ConstInt, Type};
-use leo_ast::{Char, InputValue};
-use leo_errors::{CompilerError, Result, Span};
+use leo_asg::{Layout, Type};
+use leo_ast::InputValue;
+use leo_errors::{Result, Span};
 
 use snarkvm_fields::PrimeField;
-use snarkvm_gadgets::boolean::Boolean;
 use snarkvm_r1cs::ConstraintSystem;
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
@@ -41,102 +42,76 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         name: &str,
         input_option: Option<InputValue>,
         span: &Span,
+    ) -> Result<ConstrainedValue<'a, F, G>> {
+        let layout = Layout::flatten(type_, name, span)?;
+        let values = match input_option {
+            Some(input_value) => Self::flatten_input_value(type_, input_value, span)?
+                .into_iter()
+                .map(Some)
+                .collect(),
+            None => vec![None; layout.len()],
+        };
+
+        let mut scalars = Vec::with_capacity(layout.len());
+        for (slot, value) in layout.slots.iter().zip(values) {
+            scalars.push(self.allocate_input_slot(cs, &slot.type_, &slot.path, value, span)?);
+        }
+
+        Ok(Self::assemble_input(type_, &mut scalars.into_iter()))
+    }
+
+    ///
+    /// Returns the scalar values of `input_value`, in the order of the layout of `type_`.
+    ///
+    pub fn flatten_input_value(type_: &Type, input_value: InputValue, span: &Span) -> Result<Vec<InputValue>> {
+        match type_ {
+            Type::Array(element_type, len) => Self::flatten_input_array(element_type, *len, input_value, span),
+            Type::Tuple(types) => Self::flatten_input_tuple(types, input_value, span),
+            _ => Ok(vec![input_value]),
+        }
+    }
+
+    fn allocate_input_slot<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        type_: &Type,
+        path: &str,
+        input_option: Option<InputValue>,
+        span: &Span,
     ) -> Result<ConstrainedValue<'a, F, G>> {
         match type_ {
-            Type::Address => Ok(Address::from_input(cs, name, input_option, span)?),
-            Type::Boolean => Ok(bool_from_input(cs, name, input_option, span)?),
-            Type::Char => Ok(char_from_input(cs, name, input_option, span)?),
-            Type::Field => Ok(field_from_input(cs, name, input_option, span)?),
-            Type::Group => Ok(group_from_input(cs, name, input_option, span)?),
+            Type::Address => Ok(Address::from_input(cs, path, input_option, span)?),
+            Type::Boolean => Ok(bool_from_input(cs, path, input_option, span)?),
+            Type::Char => Ok(char_from_input(cs, path, input_option, span)?),
+            Type::Field => Ok(field_from_input(cs, path, input_option, span)?),
+            Type::Group => Ok(group_from_input(cs, path, input_option, span)?),
             Type::Integer(integer_type) => Ok(ConstrainedValue::Integer(Integer::from_input(
                 cs,
                 integer_type,
-                name,
+                path,
                 input_option,
                 span,
             )?)),
-            Type::Array(type_, len) => self.allocate_array(cs, name, &*type_, *len, input_option, span),
-            Type::Tuple(types) => self.allocate_tuple(cs, name, types, input_option, span),
             _ => unimplemented!("main function input not implemented for type {}", type_), // Should not happen.
         }
     }
-}
 
-/// Process constant inputs and return ConstrainedValue with constant value in it.
-impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
-    pub fn constant_main_function_input<CS: ConstraintSystem<F>>(
-        &mut self,
-        cs: &mut CS,
+    ///
+    /// Rebuilds a value of `type_` from its allocated scalars, taken in layout order.
+    ///
+    fn assemble_input(
         type_: &Type,
-        name: &str,
-        input_option: Option<InputValue>,
-        span: &Span,
-    ) -> Result<ConstrainedValue<'a, F, G>> {
-        let input = input_option.ok_or_else(|| CompilerError::function_input_not_found("main", name, span))?;
-
-        match (type_, input) {
-            (Type::Address, InputValue::Address(addr)) => Ok(ConstrainedValue::Address(Address::constant(addr, span)?)),
-            (Type::Boolean, InputValue::Boolean(value)) => Ok(ConstrainedValue::Boolean(Boolean::constant(value))),
-            (Type::Char, InputValue::Char(character)) => match character.character {
-                Char::Scalar(scalar) => Ok(ConstrainedValue::Char(crate::Char::constant(
-                    cs,
-                    CharType::Scalar(scalar),
-                    format!("{}", scalar as u32),
-                    span,
-                )?)),
-                Char::NonScalar(non_scalar) => Ok(ConstrainedValue::Char(crate::Char::constant(
-                    cs,
-                    CharType::NonScalar(non_scalar),
-                    format!("{}", non_scalar),
-                    span,
-                )?)),
-            },
-            (Type::Field, InputValue::Field(value)) => {
-                Ok(ConstrainedValue::Field(FieldType::constant(cs, value, span)?))
-            }
-            (Type::Group, InputValue::Group(value)) => Ok(ConstrainedValue::Group(G::constant(&value.into(), span)?)),
-            (Type::Integer(integer_type), InputValue::Integer(input_type, value)) => {
-                let parsed = ConstInt::parse(integer_type, &value, span)?;
-                let parsed_type = parsed.get_int_type();
-                let input_type = input_type.into();
-                if std::mem::discriminant(&parsed_type) != std::mem::discriminant(&input_type) {
-                    return Err(
-                        CompilerError::integer_value_integer_type_mismatch(input_type, parsed_type, span).into(),
-                    );
-                }
-                Ok(ConstrainedValue::Integer(Integer::new(&parsed)))
-            }
-            (Type::Array(type_, arr_len), InputValue::Array(values)) => {
-                if *arr_len != values.len() {
-                    return Err(CompilerError::invalid_input_array_dimensions(*arr_len, values.len(), span).into());
-                }
-
-                Ok(ConstrainedValue::Array(
-                    values
-                        .iter()
-                        .map(|x| self.constant_main_function_input(cs, type_, name, Some(x.clone()), span))
-                        .collect::<Result<Vec<_>, _>>()?,
-                ))
+        scalars: &mut std::vec::IntoIter<ConstrainedValue<'a, F, G>>,
+    ) -> ConstrainedValue<'a, F, G> {
+        match type_ {
+            Type::Array(element_type, len) => {
+                ConstrainedValue::Array((0..*len).map(|_| Self::assemble_input(element_type, scalars)).collect())
             }
-            (Type::Tuple(types), InputValue::Tuple(values)) => {
-                if values.len() != types.len() {
-                    return Err(CompilerError::input_tuple_size_mismatch(types.len(), values.len(), span).into());
-                }
-
-                Ok(ConstrainedValue::Tuple(
-                    values
-                        .iter()
-                        .enumerate()
-                        .map(|(i, x)| {
-                            self.constant_main_function_input(cs, types.get(i).unwrap(), name, Some(x.clone()), span)
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                ))
+            Type::Tuple(types) => {
+                ConstrainedValue::Tuple(types.iter().map(|type_| Self::assemble_input(type_, scalars)).collect())
             }
-            (Type::Circuit(_), _) => unimplemented!("main function input not implemented for type {}", type_), // Should not happen.
-
-            // Return an error if the input type and input value do not match.
-            (_, input) => Err(CompilerError::input_variable_type_mismatch(type_, input, name, span).into()),
+            Type::Circuit(_) => unimplemented!("main function input not implemented for type {}", type_), // Should not happen.
+            _ => scalars.next().expect("the layout has a slot for every scalar"),
         }
     }
 }
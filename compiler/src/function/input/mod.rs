@@ -28,5 +28,11 @@ pub use self::input_keyword::*;
 pub mod input_section;
 pub use self::input_section::*;
 
+pub mod input_slots;
+pub use self::input_slots::*;
+
+pub mod resolved_inputs;
+pub use self::resolved_inputs::*;
+
 pub mod tuple;
 pub use self::tuple::*;
@@ -0,0 +1,246 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks program input values against the main function and converts them into constant values.
+
+use leo_asg::{
+    field_modulus, reduce_field, CharValue, Circuit, CircuitMember, ConstInt, ConstValue, Function, IntegerType,
+    Program, Type, FIELD_MODULUS, RECORD_DATA_CIRCUIT,
+};
+use leo_ast::{FunctionInput, Input, InputValue, Parameter};
+use leo_errors::{AsgError, AstError, CompilerError, Result, Span};
+
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+
+/// The values of a program input, checked against the types they are passed as.
+///
+/// Values are `None` where the input leaves them out, as it does for private values while setting up a circuit.
+#[derive(Clone, Default)]
+pub struct ResolvedInputs<'a> {
+    /// The value of every main function parameter, in declaration order.
+    pub main: IndexMap<String, Option<ConstValue<'a>>>,
+    /// The fields of the `[record]`, `[state]` and `[state_leaf]` sections, by section name.
+    /// Only present if the main function uses the `input` keyword.
+    pub sections: IndexMap<&'static str, IndexMap<String, Option<ConstValue<'a>>>>,
+}
+
+impl<'a> ResolvedInputs<'a> {
+    ///
+    /// Resolves `input` against the `parameters` of `function` and the input schema of `program`.
+    ///
+    /// The parameters are those the program expects as input, and must be the parameters of the function.
+    /// Values of the main and constants sections are reported at the parameter they are passed to,
+    /// and fields of the other sections at their definition in the input file.
+    ///
+    pub fn resolve(
+        program: &Program<'a>,
+        function: &'a Function<'a>,
+        parameters: &[FunctionInput],
+        input: &Input,
+    ) -> Result<Self> {
+        let mut resolved = ResolvedInputs::default();
+
+        for parameter in parameters.iter() {
+            let parameter = match parameter {
+                FunctionInput::Variable(parameter) => parameter,
+                _ => continue,
+            };
+            let name = parameter.identifier.name.to_string();
+            let span = &parameter.identifier.span;
+
+            let value = match (parameter.const_, input.get(&name), input.get_constant(&name)) {
+                (_, Some(_), Some(_)) => return Err(CompilerError::double_input_declaration(name, span).into()),
+                (false, Some(value), _) => value,
+                (true, _, Some(value)) => {
+                    Some(value.ok_or_else(|| CompilerError::function_input_not_found("main", &name, span))?)
+                }
+                (true, Some(_), None) => return Err(CompilerError::expected_const_input_variable(name, span).into()),
+                (false, None, Some(_)) => {
+                    return Err(CompilerError::expected_non_const_input_variable(name, span).into());
+                }
+                (_, None, None) => {
+                    return Err(
+                        CompilerError::function_input_not_found(&function.name.borrow().name, name, span).into(),
+                    );
+                }
+            };
+
+            let type_ = program.scope.resolve_ast_type(&parameter.type_, span)?;
+            let value = value
+                .map(|value| resolve_input_value(&type_, &name, value, span))
+                .transpose()?;
+            resolved.main.insert(name, value);
+        }
+
+        // Inputs are allocated for the parameters of the function, so they must be the ones resolved.
+        let matches = resolved.main.len() == function.arguments.len()
+            && function.arguments.keys().all(|name| resolved.main.contains_key(name));
+        if !matches {
+            let expected = parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let found = function
+                .arguments
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(AstError::expected_input_mismatch(expected, found).into());
+        }
+
+        if let Some(asg_input) = function.scope.resolve_input() {
+            let sections = vec![
+                (
+                    asg_input.record,
+                    input.get_record().values(),
+                    input.get_record().is_present(),
+                ),
+                (
+                    asg_input.state,
+                    input.get_state().values(),
+                    input.get_state().is_present(),
+                ),
+                (
+                    asg_input.state_leaf,
+                    input.get_state_leaf().values(),
+                    input.get_state_leaf().is_present(),
+                ),
+            ];
+            for (circuit, values, is_present) in sections {
+                let fields = Self::resolve_section(program, circuit, values, is_present)?;
+                resolved.sections.insert(circuit.input_section_name(), fields);
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolves the fields of an input state section against its schema.
+    fn resolve_section(
+        program: &Program<'a>,
+        circuit: &'a Circuit<'a>,
+        values: IndexMap<Parameter, Option<InputValue>>,
+        is_present: bool,
+    ) -> Result<IndexMap<String, Option<ConstValue<'a>>>> {
+        let section = circuit.input_section_name();
+        let mut fields = IndexMap::new();
+
+        // A section the program never reads is not allocated, so its fields are not checked either.
+        if circuit.members.borrow().is_empty() {
+            return Ok(fields);
+        }
+
+        for (parameter, value) in values.into_iter() {
+            let name = parameter.variable.name.to_string();
+            let declared_type = program.scope.resolve_ast_type(&parameter.type_, &parameter.span)?;
+            match circuit.input_field_type(program.scope, &name) {
+                Some(field_type) if field_type.accepts(&declared_type) => (),
+                Some(field_type) => {
+                    return Err(AsgError::unexpected_type(field_type, declared_type, &parameter.span).into());
+                }
+                None => return Err(AsgError::unknown_input_field(section, &name, &parameter.span).into()),
+            }
+
+            let value = value
+                .map(|value| resolve_input_value(&declared_type, &name, value, &parameter.span))
+                .transpose()?;
+            fields.insert(name, value);
+        }
+
+        // A record must define every custom data field the program declares.
+        if is_present && section == "record" {
+            if let Some(record) = program.scope.resolve_circuit(RECORD_DATA_CIRCUIT) {
+                for (name, member) in record.members.borrow().iter() {
+                    if matches!(member, CircuitMember::Variable(_)) && !fields.contains_key(name) {
+                        let span = record.span.clone().unwrap_or_default();
+                        return Err(AsgError::missing_input_field(section, name, &span).into());
+                    }
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+///
+/// Converts an input value into a constant value of `type_`, checking integer ranges,
+/// array lengths and tuple sizes.
+///
+pub fn resolve_input_value<'a>(type_: &Type<'a>, name: &str, value: InputValue, span: &Span) -> Result<ConstValue<'a>> {
+    Ok(match (type_, value) {
+        (Type::Address, InputValue::Address(address)) => ConstValue::Address(address.into()),
+        (Type::Boolean, InputValue::Boolean(boolean)) => ConstValue::Boolean(boolean),
+        (Type::Char, InputValue::Char(character)) => ConstValue::Char(CharValue::from(character)),
+        (Type::Field, InputValue::Field(number)) => {
+            let field: BigInt = number
+                .parse()
+                .map_err(|_| CompilerError::field_value_invalid_field(&number, span))?;
+            if field >= field_modulus() {
+                return Err(AsgError::field_literal_out_of_range(number, FIELD_MODULUS, span).into());
+            }
+            ConstValue::Field(reduce_field(&field))
+        }
+        (Type::Group, InputValue::Group(group)) => ConstValue::Group(group.into()),
+        (Type::Integer(integer_type), InputValue::Integer(input_type, number)) => {
+            let input_type = IntegerType::from(input_type);
+            if std::mem::discriminant(&input_type) != std::mem::discriminant(integer_type) {
+                return Err(CompilerError::integer_value_integer_type_mismatch(integer_type, input_type, span).into());
+            }
+            let value = ConstInt::parse(integer_type, &number, span)
+                .map_err(|_| CompilerError::integer_value_invalid_integer(&number, span))?;
+            ConstValue::Int(value)
+        }
+        (Type::Integer(_), value) => return Err(CompilerError::integer_value_invalid_integer(value, span).into()),
+        (Type::Array(element, len), InputValue::Array(values)) => {
+            if *len != values.len() {
+                return Err(CompilerError::invalid_input_array_dimensions(len, values.len(), span).into());
+            }
+            ConstValue::Array(
+                values
+                    .into_iter()
+                    .map(|value| resolve_input_value(element, name, value, span))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        }
+        (Type::ArrayWithoutSize(element), InputValue::Array(values)) => ConstValue::Array(
+            values
+                .into_iter()
+                .map(|value| resolve_input_value(element, name, value, span))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        (Type::Array(..), value) | (Type::ArrayWithoutSize(_), value) => {
+            return Err(CompilerError::invalid_function_input_array(value, span).into());
+        }
+        (Type::Tuple(types), InputValue::Tuple(values)) => {
+            if types.len() != values.len() {
+                return Err(CompilerError::input_tuple_size_mismatch(types.len(), values.len(), span).into());
+            }
+            ConstValue::Tuple(
+                types
+                    .iter()
+                    .zip(values.into_iter())
+                    .map(|(type_, value)| resolve_input_value(type_, name, value, span))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        }
+        (Type::Tuple(_), value) => return Err(CompilerError::invalid_function_input_tuple(value, span).into()),
+        (type_, value) => return Err(CompilerError::input_variable_type_mismatch(type_, value, name, span).into()),
+    })
+}
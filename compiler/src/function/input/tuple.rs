@@ -14,54 +14,34 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-//! Allocates an array as a main function input parameter in a compiled Leo program.
+//! Flattens a tuple given as a main function input parameter in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use crate::{program::ConstrainedProgram, GroupType};
 
 use leo_asg::Type;
 use leo_ast::InputValue;
 use leo_errors::{CompilerError, Result, Span};
 
 use snarkvm_fields::PrimeField;
-use snarkvm_r1cs::ConstraintSystem;
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
-    pub fn allocate_tuple<CS: ConstraintSystem<F>>(
-        &mut self,
-        cs: &mut CS,
-        name: &str,
-        types: &[Type],
-        input_value: Option<InputValue>,
-        span: &Span,
-    ) -> Result<ConstrainedValue<'a, F, G>> {
-        let mut tuple_values = vec![];
-
+    ///
+    /// Returns the scalar values of the tuple `input_value`, in the order of the tuple's layout.
+    ///
+    pub fn flatten_input_tuple(types: &[Type], input_value: InputValue, span: &Span) -> Result<Vec<InputValue>> {
         match input_value {
-            Some(InputValue::Tuple(values)) => {
+            InputValue::Tuple(values) => {
                 if values.len() != types.len() {
                     return Err(CompilerError::input_tuple_size_mismatch(types.len(), values.len(), span).into());
                 }
 
-                // Allocate each value in the tuple.
-                for (i, (value, type_)) in values.into_iter().zip(types.iter()).enumerate() {
-                    let value_name = format!("{}_{}", &name, &i.to_string());
-
-                    tuple_values.push(self.allocate_main_function_input(cs, type_, &value_name, Some(value), span)?)
+                let mut flattened = vec![];
+                for (value, type_) in values.into_iter().zip(types.iter()) {
+                    flattened.extend(Self::flatten_input_value(type_, value, span)?);
                 }
+                Ok(flattened)
             }
-            None => {
-                // Allocate all tuple values as none
-                for (i, type_) in types.iter().enumerate() {
-                    let value_name = format!("{}_{}", &name, &i.to_string());
-
-                    tuple_values.push(self.allocate_main_function_input(cs, type_, &value_name, None, span)?);
-                }
-            }
-            _ => {
-                return Err(CompilerError::invalid_function_input_tuple(input_value.unwrap(), span).into());
-            }
+            input_value => Err(CompilerError::invalid_function_input_tuple(input_value, span).into()),
         }
-
-        Ok(ConstrainedValue::Tuple(tuple_values))
     }
 }
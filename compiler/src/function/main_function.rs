@@ -16,24 +16,50 @@
 
 //! Enforces constraints on the main function of a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, GroupType, Output};
+use crate::{program::ConstrainedProgram, GroupType, Output, ResolvedInputs};
 
 use leo_asg::{Expression, Function, FunctionQualifier};
-use leo_ast::Input;
-use leo_errors::{CompilerError, Result};
+use leo_ast::{FunctionInput, Input};
+use leo_errors::Result;
 use std::cell::Cell;
 
 use snarkvm_fields::PrimeField;
 use snarkvm_r1cs::ConstraintSystem;
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
+    ///
+    /// Enforces `function` as the entrypoint, with `parameters` as the input it expects.
+    ///
     pub fn enforce_main_function<CS: ConstraintSystem<F>>(
         &mut self,
         cs: &mut CS,
         function: &'a Function<'a>,
+        parameters: &[FunctionInput],
         input: &Input,
     ) -> Result<Output> {
         let registers = input.get_registers();
+        let arguments = self.allocate_main_inputs(cs, function, parameters, input)?;
+
+        let span = function.span.clone().unwrap_or_default();
+        let result_value = self.enforce_function(cs, function, None, &arguments)?;
+        self.check_circuit_limits(cs, &span)?;
+        let output = Output::new(&self.asg, registers, result_value, &span)?;
+
+        Ok(output)
+    }
+
+    ///
+    /// Allocates the input of the entrypoint `function`, returning the arguments to call it with.
+    ///
+    pub(crate) fn allocate_main_inputs<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        function: &'a Function<'a>,
+        parameters: &[FunctionInput],
+        input: &Input,
+    ) -> Result<Vec<Cell<&'a Expression<'a>>>> {
+        // Check every input value against the main function and the input schema before allocating any.
+        let resolved = ResolvedInputs::resolve(&self.asg, function, parameters, input)?;
 
         // Iterate over main function input variables and allocate new values
         let asg_input = function.scope.resolve_input();
@@ -54,57 +80,22 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
 
         let mut arguments = vec![];
 
-        for (_, input_variable) in function.arguments.iter() {
+        for (name, input_variable) in function.arguments.iter() {
             {
                 let input_variable = input_variable.get().borrow();
-                let name = input_variable.name.name.clone();
-
-                let input_value = match (
-                    input_variable.const_,
-                    input.get(&name),
-                    input.get_constant(name.as_ref()),
-                ) {
-                    // If variable is in both [main] and [constants] sections - error.
-                    (_, Some(_), Some(_)) => {
-                        return Err(CompilerError::double_input_declaration(name, &input_variable.name.span).into());
-                    }
-                    // If input option is found in [main] section and input is not const.
-                    (false, Some(input_option), _) => self.allocate_main_function_input(
-                        cs,
-                        &input_variable.type_.clone(),
-                        &name,
-                        input_option,
-                        &input_variable.name.span,
-                    )?,
-                    // If input option is found in [constants] section and function argument is const.
-                    (true, _, Some(input_option)) => self.constant_main_function_input(
+                let span = &input_variable.name.span;
+
+                let input_value = match resolved.main.get(name).cloned().flatten() {
+                    // Constant inputs are known values.
+                    Some(value) if input_variable.const_ => self.enforce_const_value(cs, &value, span)?,
+                    // Private inputs are allocated, with or without a value.
+                    _ => self.allocate_main_function_input(
                         cs,
                         &input_variable.type_.clone(),
-                        &name,
-                        input_option,
-                        &input_variable.name.span,
+                        name,
+                        input.get(name).flatten(),
+                        span,
                     )?,
-                    // Function argument is const, input is not.
-                    (true, Some(_), None) => {
-                        return Err(
-                            CompilerError::expected_const_input_variable(name, &input_variable.name.span).into(),
-                        );
-                    }
-                    // Input is const, function argument is not.
-                    (false, None, Some(_)) => {
-                        return Err(
-                            CompilerError::expected_non_const_input_variable(name, &input_variable.name.span).into(),
-                        );
-                    }
-                    // When not found - Error out.
-                    (_, _, _) => {
-                        return Err(CompilerError::function_input_not_found(
-                            function.name.borrow().name.to_string(),
-                            name,
-                            &input_variable.name.span,
-                        )
-                        .into());
-                    }
                 };
 
                 // Store a new variable for every function input.
@@ -119,10 +110,6 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             )));
         }
 
-        let span = function.span.clone().unwrap_or_default();
-        let result_value = self.enforce_function(cs, function, None, &arguments)?;
-        let output = Output::new(&self.asg, registers, result_value, &span)?;
-
-        Ok(output)
+        Ok(arguments)
     }
 }
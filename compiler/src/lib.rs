@@ -22,6 +22,12 @@
 #![allow(clippy::upper_case_acronyms)]
 #![doc = include_str!("../README.md")]
 
+pub mod artifact;
+pub use artifact::*;
+
+pub mod audit;
+pub use audit::*;
+
 pub mod compiler;
 
 pub mod console;
@@ -32,6 +38,12 @@ pub use constraints::*;
 
 pub mod definition;
 
+pub mod entrypoint;
+pub use entrypoint::*;
+
+pub mod estimate;
+pub use estimate::*;
+
 pub mod expression;
 pub use expression::*;
 
@@ -44,6 +56,27 @@ pub use output::*;
 pub mod program;
 pub use program::*;
 
+pub mod progress;
+pub use progress::*;
+
+pub mod provenance;
+pub use provenance::*;
+
+pub mod reproducibility;
+pub use reproducibility::*;
+
+pub mod self_check;
+pub use self_check::*;
+
+pub mod session;
+pub use session::*;
+
+pub mod signing;
+pub use signing::*;
+
+pub mod source;
+pub use source::*;
+
 pub mod statement;
 pub use statement::*;
 
@@ -14,32 +14,367 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{ArtifactSigner, Progress};
+use leo_asg_passes::{Case, NamingRules};
+use leo_ast::Edition;
+use leo_errors::{CancellationToken, CompilerError, Result};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::Path};
+
 ///
 /// Toggles compiler optimizations on the program.
 ///
-#[derive(Clone, Debug)]
+/// The options are read from and written to TOML files, where every option left out takes its default.
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CompilerOptions {
     pub constant_folding_enabled: bool,
     pub dead_code_elimination_enabled: bool,
+    /// Replaces comparisons decided by the ranges of their operand types with their result.
+    pub fold_range_comparisons: bool,
+    /// Rewrites boolean expressions into smaller equivalent ones, see [`leo_asg_passes::BooleanSimplifier`].
+    pub simplify_boolean_expressions: bool,
+    /// Keeps the asserts that always hold, which are removed otherwise, as documentation.
+    pub keep_true_asserts: bool,
+    pub renaming_enabled: bool,
+    pub max_expression_depth: usize,
+    pub limits: ProgramLimits,
+    pub strict_field_literals: bool,
+    /// Whether operations may mix integers of one signedness but different widths.
+    pub integer_widening: IntegerWidening,
+    /// The edition of the language the program is compiled against, which gates newer syntax and checks.
+    pub edition: Edition,
+    /// The case the names of each kind of declaration are checked against.
+    pub naming: NamingOptions,
+    /// The most expressions a function without an `@inline` annotation may have to be inlined by size.
+    /// Every call is inlined, as circuits have no call instruction, so this only changes the reported decisions.
+    pub inline_threshold: usize,
+    pub verify_ast: bool,
+    /// Writes the dataflow of every function as a Graphviz `<function>.dot` file.
+    pub emit_dot: bool,
+    /// Groups the nodes of each statement together in the `.dot` files.
+    pub cluster_dot: bool,
+    /// Writes the source code of every constraint as a `<program>.provenance.json` file.
+    pub record_provenance: bool,
+    /// Estimates the size of the circuit of every function, for the audit report.
+    pub estimate_costs: bool,
+    /// The functions compiled as entrypoints by `compile_entrypoints()`, each with its own artifacts.
+    pub entrypoints: Vec<String>,
+    /// Receives the phases of the compilation and the items each of them works through.
+    #[serde(skip)]
+    pub progress: Progress,
+    /// Stops the compilation with an error when cancelled or past its deadline.
+    #[serde(skip)]
+    pub cancellation: CancellationToken,
+    /// Signs every artifact, writing the signatures next to them and in a manifest.
+    #[serde(skip)]
+    pub signer: ArtifactSigner,
+    /// The compilation unit whose directory the artifacts are written to, see [`crate::CompilationUnits`].
+    #[serde(skip)]
+    pub unit_name: Option<String>,
 }
 
+/// The default of [`CompilerOptions::inline_threshold`].
+pub const DEFAULT_INLINE_THRESHOLD: usize = 32;
+
 impl Default for CompilerOptions {
     ///
     /// All compiler optimizations are enabled by default.
+    /// Comparisons decided by the ranges of their operand types are reported but not folded.
+    /// Boolean expressions are not simplified.
+    /// Asserts that always hold are removed.
+    /// Renaming is opt-in.
+    /// Expressions may nest up to the default depth limit.
+    /// Program size is bounded by the default limits.
+    /// Field literals outside of the field are reduced with a warning.
+    /// Integers of different widths are not widened.
+    /// Programs are compiled against the latest edition.
+    /// Names are not checked against naming conventions.
+    /// Functions of up to 32 expressions are reported as inlined by size.
+    /// The AST is not re-validated between passes.
+    /// No dataflow graphs are written.
+    /// No provenance is recorded.
+    /// The size of every function is estimated.
+    /// `main` is the only entrypoint.
+    /// No progress is reported.
+    /// The compilation is never cancelled.
+    /// Artifacts are not signed.
+    /// Artifacts are written at the root of the output directory, unless they name a compilation unit.
     ///
     fn default() -> Self {
         CompilerOptions {
             constant_folding_enabled: true,
             dead_code_elimination_enabled: true,
+            fold_range_comparisons: false,
+            simplify_boolean_expressions: false,
+            keep_true_asserts: false,
+            renaming_enabled: false,
+            max_expression_depth: leo_ast::DEFAULT_MAX_EXPRESSION_DEPTH,
+            limits: ProgramLimits::default(),
+            strict_field_literals: false,
+            integer_widening: IntegerWidening::default(),
+            edition: Edition::LATEST,
+            naming: NamingOptions::default(),
+            inline_threshold: DEFAULT_INLINE_THRESHOLD,
+            verify_ast: false,
+            emit_dot: false,
+            cluster_dot: false,
+            record_provenance: false,
+            estimate_costs: true,
+            entrypoints: vec!["main".to_string()],
+            progress: Progress::default(),
+            cancellation: CancellationToken::default(),
+            signer: ArtifactSigner::default(),
+            unit_name: None,
+        }
+    }
+}
+
+impl CompilerOptions {
+    ///
+    /// Parses the options of a TOML file, where every option left out takes its default.
+    ///
+    /// Returns the options with the keys of the file that are not options, such as those of a newer compiler.
+    /// Each of them is logged as a warning.
+    ///
+    pub fn from_toml_str(toml: &str, path: &str) -> Result<(Self, Vec<String>)> {
+        let value: toml::Value = toml::from_str(toml).map_err(|e| CompilerError::failed_to_parse_options(path, e))?;
+        let known = toml::Value::try_from(CompilerOptions::default())
+            .map_err(CompilerError::failed_to_convert_options_to_toml)?;
+
+        let mut unknown = vec![];
+        unknown_keys(&value, &known, "", &mut unknown);
+        for key in unknown.iter() {
+            tracing::warn!("unknown compiler option `{}` in `{}` is ignored", key, path);
+        }
+
+        let options = value
+            .try_into()
+            .map_err(|e| CompilerError::failed_to_parse_options(path, e))?;
+        Ok((options, unknown))
+    }
+
+    /// Reads the options of a TOML file like [`CompilerOptions::from_toml_str`].
+    pub fn from_file(path: &Path) -> Result<(Self, Vec<String>)> {
+        let toml = fs::read_to_string(path).map_err(|e| CompilerError::file_read_error(path, e))?;
+        Self::from_toml_str(&toml, &path.display().to_string())
+    }
+
+    /// Returns the options as TOML, with every option written out.
+    pub fn to_toml_string(&self) -> Result<String> {
+        // Going through a value writes the `limits` table after the other options, as TOML requires.
+        let value = toml::Value::try_from(self).map_err(CompilerError::failed_to_convert_options_to_toml)?;
+        Ok(toml::to_string(&value).map_err(CompilerError::failed_to_convert_options_to_toml)?)
+    }
+
+    /// Writes the options to a TOML file, with every option written out.
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml_string()?).map_err(|e| CompilerError::failed_to_write_options_file(path, e))?;
+        Ok(())
+    }
+
+    ///
+    /// Returns every option that affects the compilation, by dotted name, with its value.
+    ///
+    /// How progress is reported, whether the compilation may be cancelled, and how and where artifacts are
+    /// written do not affect what it produces, so they are not serialized and are left out.
+    ///
+    fn entries(&self) -> BTreeMap<String, String> {
+        let value = toml::Value::try_from(self).expect("the compiler options convert to toml");
+        let mut entries = BTreeMap::new();
+        flatten(&value, "", &mut entries);
+        entries
+    }
+
+    ///
+    /// Returns the options that differ from their defaults, by dotted name, with their value.
+    ///
+    pub fn changed_options(&self) -> BTreeMap<String, String> {
+        let defaults = CompilerOptions::default().entries();
+        self.entries()
+            .into_iter()
+            .filter(|(name, value)| defaults.get(name) != Some(value))
+            .collect()
+    }
+
+    ///
+    /// Returns the SHA256 digest of the options, as stamped into artifacts.
+    ///
+    /// The digest is taken over the options that differ from their defaults, sorted by name,
+    /// so options that are added later with a default do not change the digest of existing builds.
+    ///
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        for (name, value) in self.changed_options() {
+            hasher.update(format!("{}={}\n", name, value).as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Collects the dotted names of the keys of `value` that `known` does not have.
+fn unknown_keys(value: &toml::Value, known: &toml::Value, prefix: &str, unknown: &mut Vec<String>) {
+    if let (toml::Value::Table(table), toml::Value::Table(known)) = (value, known) {
+        for (key, value) in table.iter() {
+            let name = format!("{}{}", prefix, key);
+            match known.get(key) {
+                Some(known) => unknown_keys(value, known, &format!("{}.", name), unknown),
+                None => unknown.push(name),
+            }
+        }
+    }
+}
+
+/// Collects the options of `value` by dotted name, with lists written as their elements separated by commas.
+fn flatten(value: &toml::Value, prefix: &str, entries: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter() {
+                flatten(value, &format!("{}{}.", prefix, key), entries);
+            }
+        }
+        value => {
+            entries.insert(prefix.trim_end_matches('.').to_string(), render(value));
+        }
+    }
+}
+
+fn render(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(string) => string.clone(),
+        toml::Value::Array(values) => values.iter().map(render).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }
+}
+
+///
+/// Bounds the size of a program so that pathological inputs fail with a diagnostic
+/// instead of exhausting time or memory.
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgramLimits {
+    /// Maximum number of statements a single function may unroll to.
+    pub max_unrolled_statements: usize,
+    /// Maximum number of constraints in the synthesized circuit.
+    pub max_constraints: usize,
+    /// Maximum number of public and private variables in the synthesized circuit.
+    pub max_variables: usize,
+    /// Maximum number of functions, including circuit member functions.
+    pub max_functions: usize,
+    /// Maximum number of circuits.
+    pub max_circuits: usize,
+    /// Maximum number of scalar input slots taken by the non-const parameters of `main`.
+    pub max_input_slots: usize,
+}
+
+impl Default for ProgramLimits {
+    ///
+    /// The default limits are far above the size of any real program.
+    ///
+    fn default() -> Self {
+        ProgramLimits {
+            max_unrolled_statements: 1_000_000,
+            max_constraints: 100_000_000,
+            max_variables: 100_000_000,
+            max_functions: 10_000,
+            max_circuits: 10_000,
+            max_input_slots: 1_000_000,
+        }
+    }
+}
+
+///
+/// Decides whether an arithmetic operation or comparison may mix integers of one signedness but different widths.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegerWidening {
+    /// The operands must have the same type.
+    Forbid,
+    /// The narrower operand is cast to the type of the wider one, which never changes its value.
+    Lossless,
+}
+
+impl Default for IntegerWidening {
+    fn default() -> Self {
+        IntegerWidening::Forbid
+    }
+}
+
+///
+/// A naming convention the names of one kind of declaration are checked against.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingConvention {
+    /// The names are not checked.
+    Off,
+    SnakeCase,
+    PascalCase,
+    ScreamingSnakeCase,
+}
+
+impl NamingConvention {
+    fn case(self) -> Option<Case> {
+        match self {
+            NamingConvention::Off => None,
+            NamingConvention::SnakeCase => Some(Case::Snake),
+            NamingConvention::PascalCase => Some(Case::Pascal),
+            NamingConvention::ScreamingSnakeCase => Some(Case::ScreamingSnake),
+        }
+    }
+}
+
+impl Default for NamingConvention {
+    fn default() -> Self {
+        NamingConvention::Off
+    }
+}
+
+///
+/// Picks the naming convention of each kind of declaration, see [`leo_asg_passes::NamingConventions`].
+///
+/// Functions are usually `snake_case`, circuits `PascalCase`, global consts `SCREAMING_SNAKE_CASE`
+/// and variables `snake_case`.
+///
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamingOptions {
+    /// Functions, including circuit member functions.
+    pub functions: NamingConvention,
+    pub circuits: NamingConvention,
+    /// Consts declared at the top level of the program.
+    pub consts: NamingConvention,
+    /// Function parameters, loop variables and the variables and consts declared in functions.
+    pub variables: NamingConvention,
+    /// Acronyms that `PascalCase` names may spell in capitals, such as `R1CS`.
+    pub allowed_acronyms: Vec<String>,
+}
+
+impl NamingOptions {
+    /// Returns the rules the naming lint checks the program against.
+    pub fn rules(&self) -> NamingRules {
+        NamingRules {
+            functions: self.functions.case(),
+            circuits: self.circuits.case(),
+            consts: self.consts.case(),
+            variables: self.variables.case(),
+            acronyms: self.allowed_acronyms.clone(),
         }
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct AstSnapshotOptions {
     pub spans_enabled: bool,
     pub initial: bool,
     pub imports_resolved: bool,
     pub canonicalized: bool,
     pub type_inferenced: bool,
+    /// Writes the input file as `input_ast.json`, see [`leo_ast::InputAst`].
+    pub input: bool,
 }
@@ -22,10 +22,13 @@ pub use self::output_file::*;
 pub mod output_bytes;
 pub use self::output_bytes::*;
 
-use crate::{Char, CharType, ConstrainedValue, GroupType, REGISTERS_VARIABLE_NAME};
+mod registers;
+use self::registers::*;
+
+use crate::{Char, CharType, ConstrainedValue, GroupType, LogLine, Provenance, REGISTERS_VARIABLE_NAME};
 use leo_asg::Program;
-use leo_ast::{Parameter, Registers};
-use leo_errors::{CompilerError, Result, Span};
+use leo_ast::{ArtifactFormat, Registers};
+use leo_errors::{Result, Span};
 
 use snarkvm_fields::PrimeField;
 
@@ -38,9 +41,22 @@ pub struct OutputRegister {
     pub value: String,
 }
 
+/// The format of output files written as artifacts, bumped whenever they change in a way older readers cannot handle.
+pub const OUTPUT_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "output",
+    version: 1,
+    json: false,
+};
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Output {
     pub registers: BTreeMap<String, OutputRegister>,
+    /// Console lines written while the program was evaluated, in order.
+    #[serde(skip)]
+    pub logs: Vec<LogLine>,
+    /// The source code of every constraint, if it was recorded.
+    #[serde(skip)]
+    pub provenance: Option<Provenance>,
 }
 
 impl fmt::Display for Output {
@@ -90,38 +106,11 @@ impl Output {
         value: ConstrainedValue<'a, F, G>,
         span: &Span,
     ) -> Result<Self> {
-        let return_values = match value {
-            ConstrainedValue::Tuple(tuple) => tuple,
-            value => vec![value],
-        };
-        let register_hashmap = registers.values();
-
-        // Create vector of parameter values in alphabetical order
-        let mut register_values = register_hashmap
-            .into_iter()
-            .map(|register| register.0)
-            .collect::<Vec<Parameter>>();
-
-        register_values.sort_by(|a, b| a.variable.name.cmp(&b.variable.name));
-
-        // Return an error if we do not have enough return registers
-        if register_values.len() < return_values.len() {
-            return Err(CompilerError::output_not_enough_registers(span).into());
-        }
+        let binding = RegisterBinding::new(program, registers, &value.to_type(span)?, span)?;
 
         let mut registers = BTreeMap::new();
 
-        for (parameter, value) in register_values.into_iter().zip(return_values.into_iter()) {
-            let name = parameter.variable.name;
-
-            // Check register type == return value type.
-            let register_type = program.scope.resolve_ast_type(&parameter.type_, &parameter.span)?;
-            let return_value_type = value.to_type(span)?;
-
-            if !register_type.is_assignable_from(&return_value_type) {
-                return Err(CompilerError::output_mismatched_types(register_type, return_value_type, span).into());
-            }
-
+        for (register, value) in binding.bind(value) {
             let value = match value {
                 ConstrainedValue::Char(c) => char_to_output_string(&c, true),
                 ConstrainedValue::Array(array) => {
@@ -156,14 +145,18 @@ impl Output {
             };
 
             registers.insert(
-                name.to_string(),
+                register.name.clone(),
                 OutputRegister {
-                    type_: register_type.to_string(),
+                    type_: register.type_.to_string(),
                     value,
                 },
             );
         }
 
-        Ok(Output { registers })
+        Ok(Output {
+            registers,
+            logs: Vec::new(),
+            provenance: None,
+        })
     }
 }
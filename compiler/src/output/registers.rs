@@ -0,0 +1,203 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Binds the output of the entrypoint to the registers of the input file.
+
+use crate::{ConstrainedCircuitMember, ConstrainedValue, GroupType};
+use leo_asg::{CircuitMember, Identifier, Layout, LayoutSlot, Program, Type};
+use leo_ast::Registers;
+use leo_errors::{CompilerError, Result, Span};
+
+use snarkvm_fields::PrimeField;
+
+use std::fmt::Write;
+
+/// The number of mismatching slots spelled out by the error.
+const LISTED_MISMATCHES: usize = 3;
+
+/// A register of the `[registers]` section, with its type resolved.
+pub(crate) struct BoundRegister<'a> {
+    pub(crate) name: String,
+    pub(crate) type_: Type<'a>,
+    /// The declaration as written in the input file, e.g. `r0: Byte`.
+    declaration: String,
+    layout: Layout<'a>,
+}
+
+///
+/// The registers the output of the entrypoint is written to, in alphabetical order.
+///
+/// The registers are compared with the output after aliases are resolved and both sides are flattened
+/// into their scalar slots, so a register may declare a scalar, an element of the output, or an aggregate
+/// of consecutive slots such as `r0: (u8, bool)`. Registers past the last slot of the output are not written.
+///
+pub(crate) struct RegisterBinding<'a> {
+    pub(crate) registers: Vec<BoundRegister<'a>>,
+}
+
+impl<'a> RegisterBinding<'a> {
+    ///
+    /// Binds `output`, the type of the output of the entrypoint, to the registers.
+    ///
+    /// Returns an error listing the first slots that differ if the registers do not flatten to the output.
+    ///
+    pub(crate) fn new(program: &Program<'a>, registers: &Registers, output: &Type<'a>, span: &Span) -> Result<Self> {
+        let mut parameters = registers
+            .values()
+            .into_iter()
+            .map(|(parameter, _)| parameter)
+            .collect::<Vec<_>>();
+        parameters.sort_by(|a, b| a.variable.name.cmp(&b.variable.name));
+
+        let output = Layout::flatten(output, "output", span)?;
+        let mut bound = vec![];
+        let mut slots = 0;
+        for parameter in parameters {
+            if slots >= output.len() {
+                break;
+            }
+            let type_ = program.scope.resolve_ast_type(&parameter.type_, &parameter.span)?;
+            let name = parameter.variable.name.to_string();
+            let layout = Layout::flatten(&type_, &name, &parameter.span)?;
+            slots += layout.len();
+            bound.push(BoundRegister {
+                declaration: format!("{}: {}", name, parameter.type_),
+                name,
+                type_,
+                layout,
+            });
+        }
+
+        let declared = bound
+            .iter()
+            .flat_map(|register| register.layout.slots.iter().map(move |slot| (slot, register)))
+            .collect::<Vec<_>>();
+        if slots < output.len()
+            && declared
+                .iter()
+                .zip(output.slots.iter())
+                .all(|(found, expected)| Self::matches(expected, found.0))
+        {
+            return Err(CompilerError::output_not_enough_registers(span).into());
+        }
+
+        let mismatches = (0..output.len().max(declared.len()))
+            .filter(|index| match (output.slots.get(*index), declared.get(*index)) {
+                (Some(expected), Some((found, _))) => !Self::matches(expected, found),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        if mismatches.is_empty() {
+            return Ok(RegisterBinding { registers: bound });
+        }
+
+        let mut listed = String::new();
+        for index in mismatches.iter().take(LISTED_MISMATCHES) {
+            match output.slots.get(*index) {
+                Some(expected) => writeln!(listed, "- {}: {}", expected.path, expected.type_),
+                None => writeln!(listed, "- (no output slot)"),
+            }
+            .ok();
+            match declared.get(*index) {
+                Some((found, register)) => writeln!(
+                    listed,
+                    "+ {}: {} (register `{}`)",
+                    found.path, found.type_, register.declaration
+                ),
+                None => writeln!(listed, "+ (no register)"),
+            }
+            .ok();
+        }
+        if mismatches.len() > LISTED_MISMATCHES {
+            writeln!(listed, "and {} more slots", mismatches.len() - LISTED_MISMATCHES).ok();
+        }
+
+        Err(CompilerError::output_registers_mismatch(listed.trim_end(), span).into())
+    }
+
+    fn matches(expected: &LayoutSlot<'a>, found: &LayoutSlot<'a>) -> bool {
+        found.type_.is_assignable_from(&expected.type_)
+    }
+
+    ///
+    /// Splits the value of the output into the values of the registers, in the order of the registers.
+    ///
+    pub(crate) fn bind<F: PrimeField, G: GroupType<F>>(
+        &self,
+        value: ConstrainedValue<'a, F, G>,
+    ) -> Vec<(&BoundRegister<'a>, ConstrainedValue<'a, F, G>)> {
+        let mut scalars = vec![];
+        flatten(value, &mut scalars);
+        let mut scalars = scalars.into_iter();
+
+        self.registers
+            .iter()
+            .map(|register| (register, unflatten(&register.type_, &mut scalars)))
+            .collect()
+    }
+}
+
+/// Collects the scalar values of `value`, in the order of its layout.
+fn flatten<'a, F: PrimeField, G: GroupType<F>>(
+    value: ConstrainedValue<'a, F, G>,
+    scalars: &mut Vec<ConstrainedValue<'a, F, G>>,
+) {
+    match value {
+        ConstrainedValue::Array(elements) | ConstrainedValue::Tuple(elements) => {
+            for element in elements {
+                flatten(element, scalars);
+            }
+        }
+        ConstrainedValue::CircuitExpression(circuit, mut members) => {
+            // The members of a value are in the order they were initialized, the layout is in declaration order.
+            for name in circuit.members.borrow().keys() {
+                if let Some(index) = members.iter().position(|member| member.0.name.as_ref() == name) {
+                    flatten(members.remove(index).1, scalars);
+                }
+            }
+        }
+        scalar => scalars.push(scalar),
+    }
+}
+
+/// Builds a value of `type_` from the next scalar values.
+fn unflatten<'a, F: PrimeField, G: GroupType<F>>(
+    type_: &Type<'a>,
+    scalars: &mut impl Iterator<Item = ConstrainedValue<'a, F, G>>,
+) -> ConstrainedValue<'a, F, G> {
+    match type_ {
+        Type::Array(element, len) => ConstrainedValue::Array((0..*len).map(|_| unflatten(element, scalars)).collect()),
+        Type::Tuple(elements) => {
+            ConstrainedValue::Tuple(elements.iter().map(|element| unflatten(element, scalars)).collect())
+        }
+        Type::Circuit(circuit) => {
+            let members = circuit
+                .members
+                .borrow()
+                .iter()
+                .filter_map(|(name, member)| match member {
+                    CircuitMember::Variable(member_type) => Some(ConstrainedCircuitMember(
+                        Identifier::new(name.as_str().into()),
+                        unflatten(member_type, scalars),
+                    )),
+                    CircuitMember::Static(..) | CircuitMember::Function(_) => None,
+                })
+                .collect();
+            ConstrainedValue::CircuitExpression(circuit, members)
+        }
+        _ => scalars.next().expect("the registers flatten to the output"),
+    }
+}
@@ -34,18 +34,19 @@ use leo_asg::{
     VariableRef as AsgVariableRef,
 };
 use leo_ast::{
-    ArrayAccessExpression as AstArrayAccessExpression, ArrayDimensions, ArrayInitExpression as AstArrayInitExpression,
-    ArrayInlineExpression as AstArrayInlineExpression, ArrayRangeAccessExpression as AstArrayRangeAccessExpression,
-    AssignStatement as AstAssignStatement, Assignee, AssigneeAccess as AstAssignAccess,
-    BinaryExpression as AstBinaryExpression, Block as AstBlockStatement, CallExpression as AstCallExpression,
-    CastExpression as AstCastExpression, Char, CharValue as AstCharValue, Circuit as AstCircuit,
-    CircuitImpliedVariableDefinition, CircuitInitExpression as AstCircuitInitExpression,
+    ensure_stack, ArrayAccessExpression as AstArrayAccessExpression, ArrayDimensions,
+    ArrayInitExpression as AstArrayInitExpression, ArrayInlineExpression as AstArrayInlineExpression,
+    ArrayRangeAccessExpression as AstArrayRangeAccessExpression, AssignStatement as AstAssignStatement, Assignee,
+    AssigneeAccess as AstAssignAccess, BinaryExpression as AstBinaryExpression, Block as AstBlockStatement,
+    CallExpression as AstCallExpression, CastExpression as AstCastExpression, Char, CharValue as AstCharValue,
+    Circuit as AstCircuit, CircuitImpliedVariableDefinition, CircuitInitExpression as AstCircuitInitExpression,
     CircuitMember as AstCircuitMember, CircuitMemberAccessExpression, CircuitStaticFunctionAccessExpression,
     ConditionalStatement as AstConditionalStatement, ConsoleArgs as AstConsoleArgs,
-    ConsoleFunction as AstConsoleFunction, ConsoleStatement as AstConsoleStatement,
-    DefinitionStatement as AstDefinitionStatement, Expression as AstExpression,
-    ExpressionStatement as AstExpressionStatement, Function as AstFunction, GroupTuple, GroupValue as AstGroupValue,
-    IterationStatement as AstIterationStatement, PositiveNumber, ReconstructingReducer,
+    ConsoleAssertArgs as AstConsoleAssertArgs, ConsoleFunction as AstConsoleFunction,
+    ConsoleStatement as AstConsoleStatement, DefinitionStatement as AstDefinitionStatement,
+    Expression as AstExpression, ExpressionStatement as AstExpressionStatement, Function as AstFunction, GroupTuple,
+    GroupValue as AstGroupValue, IterationStatement as AstIterationStatement,
+    ParenthesizedExpression as AstParenthesizedExpression, PositiveNumber, ReconstructingReducer,
     ReturnStatement as AstReturnStatement, SpreadOrExpression, Statement as AstStatement,
     TernaryExpression as AstTernaryExpression, TupleAccessExpression as AstTupleAccessExpression,
     TupleInitExpression as AstTupleInitExpression, Type as AstType, UnaryExpression as AstUnaryExpression,
@@ -103,7 +104,15 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
     }
 
     pub fn reduce_expression(&mut self, ast: &AstExpression, asg: &AsgExpression) -> Result<AstExpression> {
+        ensure_stack(|| self.reduce_expression_inner(ast, asg))
+    }
+
+    fn reduce_expression_inner(&mut self, ast: &AstExpression, asg: &AsgExpression) -> Result<AstExpression> {
         let new = match (ast, asg) {
+            // The asg has no parentheses, the expression inside them corresponds to `asg`.
+            (AstExpression::Parenthesized(ast), asg) => {
+                AstExpression::Parenthesized(self.reduce_parenthesized(ast, asg)?)
+            }
             (AstExpression::Value(value), AsgExpression::Constant(const_)) => self.reduce_value(value, const_)?,
             (AstExpression::Binary(ast), AsgExpression::Binary(asg)) => {
                 AstExpression::Binary(self.reduce_binary(ast, asg)?)
@@ -114,6 +123,9 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
             (AstExpression::Ternary(ast), AsgExpression::Ternary(asg)) => {
                 AstExpression::Ternary(self.reduce_ternary(ast, asg)?)
             }
+            (ast, AsgExpression::Cast(asg)) if asg.implicit => {
+                AstExpression::Cast(self.reduce_implicit_cast(ast, asg)?)
+            }
             (AstExpression::Cast(ast), AsgExpression::Cast(asg)) => AstExpression::Cast(self.reduce_cast(ast, asg)?),
 
             (AstExpression::ArrayInline(ast), AsgExpression::ArrayInline(asg)) => {
@@ -245,9 +257,18 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
             }
         }
 
+        // The instance a member function is called on can be an argument in the AST and the target in the ASG,
+        // or the other way around.
+        let mut asg_arguments = asg.arguments.iter().map(|arg| arg.get()).collect::<Vec<_>>();
+        if ast.arguments.len() > asg_arguments.len() {
+            asg_arguments.splice(0..0, asg.target.get());
+        } else if ast.arguments.len() < asg_arguments.len() {
+            asg_arguments.remove(0);
+        }
+
         let mut arguments = vec![];
-        for (ast_arg, asg_arg) in ast.arguments.iter().zip(asg.arguments.iter()) {
-            arguments.push(self.reduce_expression(ast_arg, asg_arg.get())?);
+        for (ast_arg, asg_arg) in ast.arguments.iter().zip(asg_arguments) {
+            arguments.push(self.reduce_expression(ast_arg, asg_arg)?);
         }
 
         self.ast_reducer.reduce_call(ast, function, arguments)
@@ -260,6 +281,19 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
         self.ast_reducer.reduce_cast(ast, inner, target_type)
     }
 
+    /// Reduces a cast inserted by type checking, whose inner expression is the whole of the source expression.
+    pub fn reduce_implicit_cast(&mut self, ast: &AstExpression, asg: &AsgCastExpression) -> Result<AstCastExpression> {
+        let inner = self.reduce_expression(ast, asg.inner.get())?;
+        let target_type: leo_ast::Type = (&asg.target_type).into();
+        let cast = AstCastExpression {
+            inner: Box::new(ast.clone()),
+            target_type: target_type.clone(),
+            span: asg.span.clone().unwrap_or_default(),
+        };
+
+        self.ast_reducer.reduce_cast(&cast, inner, target_type)
+    }
+
     pub fn reduce_circuit_member_access(
         &mut self,
         ast: &CircuitMemberAccessExpression,
@@ -324,6 +358,16 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
         self.ast_reducer.reduce_ternary(ast, condition, if_true, if_false)
     }
 
+    pub fn reduce_parenthesized(
+        &mut self,
+        ast: &AstParenthesizedExpression,
+        asg: &AsgExpression,
+    ) -> Result<AstParenthesizedExpression> {
+        let inner = self.reduce_expression(&ast.inner, asg)?;
+
+        self.ast_reducer.reduce_parenthesized(ast, inner)
+    }
+
     pub fn reduce_tuple_access(
         &mut self,
         ast: &AstTupleAccessExpression,
@@ -528,6 +572,19 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
             (AstConsoleFunction::Assert(ast_expression), AsgConsoleFunction::Assert(asg_expression)) => {
                 AstConsoleFunction::Assert(self.reduce_expression(ast_expression, asg_expression.get())?)
             }
+            (AstConsoleFunction::AssertEq(ast_args), AsgConsoleFunction::AssertEq(asg_args))
+            | (AstConsoleFunction::AssertNe(ast_args), AsgConsoleFunction::AssertNe(asg_args)) => {
+                let args = AstConsoleAssertArgs {
+                    left: self.reduce_expression(&ast_args.left, asg_args.left.get())?,
+                    right: self.reduce_expression(&ast_args.right, asg_args.right.get())?,
+                    span: ast_args.span.clone(),
+                };
+
+                match &ast.function {
+                    AstConsoleFunction::AssertEq(_) => AstConsoleFunction::AssertEq(args),
+                    _ => AstConsoleFunction::AssertNe(args),
+                }
+            }
             (AstConsoleFunction::Error(ast_console_args), AsgConsoleFunction::Error(asg_format))
             | (AstConsoleFunction::Log(ast_console_args), AsgConsoleFunction::Log(asg_format)) => {
                 let mut parameters = vec![];
@@ -589,7 +646,11 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
             };
         }
 
-        let value = self.reduce_expression(&ast.value, asg.value.get())?;
+        let value = ast
+            .value
+            .as_ref()
+            .map(|value| self.reduce_expression(value, asg.value.get()))
+            .transpose()?;
 
         self.ast_reducer
             .reduce_definition(ast, ast.variable_names.clone(), type_, value)
@@ -611,6 +672,10 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
     ) -> Result<AstIterationStatement> {
         let start = self.reduce_expression(&ast.start, asg.start.get())?;
         let stop = self.reduce_expression(&ast.stop, asg.stop.get())?;
+        let step = match (&ast.step, asg.step.get()) {
+            (Some(ast_step), Some(asg_step)) => Some(self.reduce_expression(ast_step, asg_step)?),
+            _ => None,
+        };
         let block;
         if let AsgStatement::Block(asg_block) = asg.body.get() {
             block = self.reduce_block(&ast.block, asg_block)?;
@@ -619,7 +684,7 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
         }
 
         self.ast_reducer
-            .reduce_iteration(ast, ast.variable.clone(), start, stop, block)
+            .reduce_iteration(ast, ast.variable.clone(), start, stop, step, block)
     }
 
     pub fn reduce_return(&mut self, ast: &AstReturnStatement, asg: &AsgReturnStatement) -> Result<AstReturnStatement> {
@@ -672,10 +737,15 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
             .transpose()?;
 
         let mut statements = vec![];
-        if let Some(AsgStatement::Block(asg_block)) = asg.body.get() {
-            for (ast_statement, asg_statement) in ast.block.statements.iter().zip(asg_block.statements.iter()) {
-                statements.push(self.reduce_statement(ast_statement, asg_statement.get())?);
+        match asg.body.get() {
+            Some(AsgStatement::Block(asg_block)) => {
+                for (ast_statement, asg_statement) in ast.block.statements.iter().zip(asg_block.statements.iter()) {
+                    statements.push(self.reduce_statement(ast_statement, asg_statement.get())?);
+                }
             }
+            // The body has placeholders left by error recovery, so it is kept as it was written.
+            None => statements = ast.block.statements.clone(),
+            Some(_) => (),
         }
 
         let block = AstBlockStatement {
@@ -705,6 +775,17 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
                     self.reduce_type(ast_type, asg_type, &identifier.span)?,
                 )
             }
+            (
+                AstCircuitMember::CircuitStatic(identifier, ast_type, ast_value),
+                AsgCircuitMember::Static(asg_type, asg_value),
+            ) => AstCircuitMember::CircuitStatic(
+                identifier.clone(),
+                self.reduce_type(ast_type, asg_type, &identifier.span)?,
+                match asg_value.get() {
+                    Some(asg_value) => self.reduce_expression(ast_value, asg_value)?,
+                    None => ast_value.clone(),
+                },
+            ),
             (AstCircuitMember::CircuitFunction(ast_function), AsgCircuitMember::Function(asg_function)) => {
                 AstCircuitMember::CircuitFunction(self.reduce_function(ast_function, asg_function)?)
             }
@@ -715,9 +796,20 @@ impl<R: ReconstructingReducer, O: CombinerOptions> CombineAstAsgDirector<R, O> {
     }
 
     pub fn reduce_circuit(&mut self, ast: &AstCircuit, asg: &AsgCircuit) -> Result<AstCircuit> {
+        // Members are paired by name, since the asg lists data members and statics before functions.
+        let asg_members = asg.members.borrow();
         let mut members = vec![];
-        for (ast_member, asg_member) in ast.members.iter().zip(asg.members.borrow().iter()) {
-            members.push(self.reduce_circuit_member(ast_member, asg_member.1)?);
+        for ast_member in ast.members.iter() {
+            let name = match ast_member {
+                AstCircuitMember::CircuitVariable(identifier, _) | AstCircuitMember::CircuitStatic(identifier, ..) => {
+                    identifier
+                }
+                AstCircuitMember::CircuitFunction(function) => &function.identifier,
+            };
+            members.push(match asg_members.get(name.name.as_ref()) {
+                Some(asg_member) => self.reduce_circuit_member(ast_member, asg_member)?,
+                None => ast_member.clone(),
+            });
         }
 
         self.ast_reducer.reduce_circuit(ast, ast.circuit_name.clone(), members)
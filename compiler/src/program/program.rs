@@ -16,16 +16,29 @@
 
 //! An in memory store to keep track of defined names when constraining a Leo program.
 
-use crate::{value::ConstrainedValue, GroupType};
+use crate::{
+    console::LogLine, value::ConstrainedValue, FrameKind, GroupType, ProgramLimits, Progress, Provenance,
+    ProvenanceRecorder,
+};
 
 use leo_asg::Program;
+use leo_errors::{CancellationToken, Checkpoint, CompilerError, Result, Span};
 use snarkvm_fields::PrimeField;
+use snarkvm_r1cs::ConstraintSystem;
 
 use indexmap::IndexMap;
 
 pub struct ConstrainedProgram<'a, F: PrimeField, G: GroupType<F>> {
     pub asg: Program<'a>,
     identifiers: IndexMap<u32, ConstrainedValue<'a, F, G>>,
+    logs: Vec<LogLine>,
+    pub(crate) limits: ProgramLimits,
+    pub(crate) progress: Progress,
+    /// Statements evaluated so far in the function being enforced.
+    pub(crate) unrolled_statements: usize,
+    /// Counts the statements and loop iterations enforced, checking for cancellation as they go.
+    pub(crate) checkpoint: Checkpoint,
+    provenance: Option<ProvenanceRecorder>,
 }
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
@@ -33,9 +46,37 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         Self {
             asg,
             identifiers: IndexMap::new(),
+            logs: Vec::new(),
+            limits: ProgramLimits::default(),
+            progress: Progress::default(),
+            unrolled_statements: 0,
+            checkpoint: CancellationToken::default().checkpoint("constraint synthesis"),
+            provenance: None,
         }
     }
 
+    pub fn with_limits(mut self, limits: ProgramLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Stops enforcing statements once `cancellation` is cancelled or past its deadline.
+    pub fn with_cancellation(mut self, cancellation: &CancellationToken) -> Self {
+        self.checkpoint = cancellation.checkpoint("constraint synthesis");
+        self
+    }
+
+    /// Records the source code of every constraint, see [`Provenance`].
+    pub fn with_provenance(mut self) -> Self {
+        self.provenance = Some(ProvenanceRecorder::default());
+        self
+    }
+
     pub(crate) fn store(&mut self, id: u32, value: ConstrainedValue<'a, F, G>) {
         self.identifiers.insert(id, value);
     }
@@ -47,4 +88,63 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
     pub(crate) fn get_mut(&mut self, id: u32) -> Option<&mut ConstrainedValue<'a, F, G>> {
         self.identifiers.get_mut(&id)
     }
+
+    pub(crate) fn push_log(&mut self, line: LogLine) {
+        self.logs.push(line);
+    }
+
+    /// Returns an error if the circuit synthesized so far exceeds the constraint or variable limits.
+    pub(crate) fn check_circuit_limits<CS: ConstraintSystem<F>>(&self, cs: &CS, span: &Span) -> Result<()> {
+        let constraints = cs.num_constraints();
+        if constraints > self.limits.max_constraints {
+            return Err(CompilerError::circuit_limit_exceeded(
+                "constraints",
+                constraints,
+                self.limits.max_constraints,
+                "max_constraints",
+                span,
+            )
+            .into());
+        }
+
+        let variables = cs.num_public_variables() + cs.num_private_variables();
+        if variables > self.limits.max_variables {
+            return Err(CompilerError::circuit_limit_exceeded(
+                "variables",
+                variables,
+                self.limits.max_variables,
+                "max_variables",
+                span,
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Attributes the constraints synthesized from now on to a frame nested in the current one.
+    pub(crate) fn enter_frame<CS: ConstraintSystem<F>>(&mut self, cs: &CS, kind: FrameKind, span: &Span) {
+        if let Some(provenance) = &mut self.provenance {
+            provenance.enter(kind, span, cs.num_constraints());
+        }
+    }
+
+    /// Attributes the constraints synthesized from now on back to the frame enclosing the current one.
+    pub(crate) fn exit_frame<CS: ConstraintSystem<F>>(&mut self, cs: &CS) {
+        if let Some(provenance) = &mut self.provenance {
+            provenance.exit(cs.num_constraints());
+        }
+    }
+
+    /// Returns the provenance of the circuit synthesized so far, if it was recorded.
+    pub fn take_provenance<CS: ConstraintSystem<F>>(&mut self, cs: &CS) -> Option<Provenance> {
+        self.provenance
+            .take()
+            .map(|provenance| provenance.finish(cs.num_constraints()))
+    }
+
+    /// Returns the console lines captured so far, in the order they were written.
+    pub fn take_logs(&mut self) -> Vec<LogLine> {
+        std::mem::take(&mut self.logs)
+    }
 }
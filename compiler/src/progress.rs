@@ -0,0 +1,95 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+pub use leo_asg::ProgressReporter;
+use leo_errors::{CancellationToken, Result};
+
+use std::{fmt, sync::Arc};
+
+///
+/// The optional [`ProgressReporter`] of a compilation.
+///
+/// Without a reporter, every call is a single check of an empty option.
+///
+#[derive(Clone, Default)]
+pub struct Progress(Option<Arc<dyn ProgressReporter>>);
+
+impl Progress {
+    pub fn new(reporter: Arc<dyn ProgressReporter>) -> Self {
+        Self(Some(reporter))
+    }
+
+    pub fn reporter(&self) -> Option<Arc<dyn ProgressReporter>> {
+        self.0.clone()
+    }
+
+    ///
+    /// Runs `phase` between a start and an end report, ending the phase even if it fails.
+    ///
+    pub fn phase<T>(&self, name: &str, items: Option<usize>, phase: impl FnOnce() -> T) -> T {
+        self.start_phase(name, items);
+        let result = phase();
+        self.end_phase(name);
+        result
+    }
+
+    ///
+    /// Reports the start of a phase that runs over several calls, which [`Progress::end_phase`] ends.
+    ///
+    pub fn start_phase(&self, name: &str, items: Option<usize>) {
+        if let Some(reporter) = &self.0 {
+            reporter.on_phase_start(name, items);
+        }
+    }
+
+    pub fn end_phase(&self, name: &str) {
+        if let Some(reporter) = &self.0 {
+            reporter.on_phase_end(name);
+        }
+    }
+
+    ///
+    /// Runs `phase` like [`Progress::phase`], checking `cancellation` before it starts and after it ends,
+    /// so that a cancellation is reported with the phase the compilation was in.
+    ///
+    pub fn checked_phase<T>(
+        &self,
+        cancellation: &CancellationToken,
+        name: &str,
+        items: Option<usize>,
+        phase: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        cancellation.check(name)?;
+        let result = self.phase(name, items, phase)?;
+        cancellation.check(name)?;
+        Ok(result)
+    }
+
+    pub fn item(&self, index: usize, name: &str) {
+        if let Some(reporter) = &self.0 {
+            reporter.on_item(index, name);
+        }
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(_) => write!(f, "Progress(reporter)"),
+            None => write!(f, "Progress(none)"),
+        }
+    }
+}
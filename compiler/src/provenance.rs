@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Records the source code that every synthesized constraint comes from.
+
+use leo_ast::ArtifactFormat;
+use leo_errors::{CompilerError, Result, Span};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::Range};
+
+/// The format of provenance files, bumped whenever their JSON changes in a way older readers cannot handle.
+pub const PROVENANCE_FORMAT: ArtifactFormat = ArtifactFormat {
+    kind: "provenance",
+    version: 1,
+    json: true,
+};
+
+/// What a [`Frame`] of provenance stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FrameKind {
+    /// A statement.
+    Statement,
+    /// One unrolled iteration of a loop, counting from zero, and the step of the loop.
+    Iteration(usize, i128),
+    /// A call of a function, whose body is inlined at the call.
+    Call,
+}
+
+/// One level of the code a constraint comes from, nested in the frame it has as parent.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Frame {
+    pub kind: FrameKind,
+    pub span: Span,
+    /// The index of the enclosing frame, if any.
+    pub parent: Option<usize>,
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FrameKind::Statement => write!(f, "statement")?,
+            FrameKind::Iteration(index, step) => write!(f, "unrolled iteration {} of loop by {}", index, step)?,
+            FrameKind::Call => write!(f, "inlined from call")?,
+        }
+        write!(
+            f,
+            " at {}:{}:{}",
+            self.span.path, self.span.line_start, self.span.col_start
+        )
+    }
+}
+
+///
+/// The source code of every constraint of a synthesized circuit.
+///
+/// In this compiler, the instructions a program compiles to are the constraints of its circuit.
+/// Frames are interned, so a statement evaluated many times in the same context has a single frame,
+/// and constraints are kept as runs of consecutive constraints that share their innermost frame.
+///
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub frames: Vec<Frame>,
+    /// The first constraint of every run with the innermost frame of its constraints, ordered by constraint.
+    pub runs: Vec<(usize, Option<usize>)>,
+    /// The number of constraints covered by the runs.
+    pub constraints: usize,
+}
+
+impl Provenance {
+    ///
+    /// Returns the frames that the constraint at `index` comes from, innermost first.
+    ///
+    /// Returns no frames for constraints synthesized outside of any statement, such as those of `main`'s inputs.
+    ///
+    pub fn provenance(&self, index: usize) -> Vec<&Frame> {
+        if index >= self.constraints {
+            return vec![];
+        }
+        let run = match self.runs.binary_search_by(|(start, _)| start.cmp(&index)) {
+            Ok(run) => run,
+            Err(0) => return vec![],
+            Err(run) => run - 1,
+        };
+
+        let mut frames = vec![];
+        let mut frame = self.runs[run].1;
+        while let Some(index) = frame {
+            frames.push(&self.frames[index]);
+            frame = self.frames[index].parent;
+        }
+        frames
+    }
+
+    ///
+    /// Returns the constraints that come from the code at `span`, including the code it calls.
+    ///
+    /// Code in a loop or a function called more than once is synthesized more than once,
+    /// so its constraints may take several ranges, which are ordered and do not overlap.
+    ///
+    pub fn spans_to_instructions(&self, span: &Span) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = vec![];
+        for (run, (start, frame)) in self.runs.iter().enumerate() {
+            if !self.is_within(*frame, span) {
+                continue;
+            }
+            let end = self
+                .runs
+                .get(run + 1)
+                .map(|(start, _)| *start)
+                .unwrap_or(self.constraints);
+            match ranges.last_mut() {
+                Some(range) if range.end == *start => range.end = end,
+                _ => ranges.push(*start..end),
+            }
+        }
+        ranges
+    }
+
+    /// Returns `true` if `frame` or one of the frames enclosing it is the code at `span`.
+    fn is_within(&self, mut frame: Option<usize>, span: &Span) -> bool {
+        while let Some(index) = frame {
+            if &self.frames[index].span == span {
+                return true;
+            }
+            frame = self.frames[index].parent;
+        }
+        false
+    }
+
+    /// Serializes the provenance into a JSON string.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(|e| CompilerError::failed_to_convert_provenance_to_json(&e))?)
+    }
+}
+
+///
+/// Builds the [`Provenance`] of a circuit while it is synthesized.
+///
+/// Frames are entered and exited with the number of constraints synthesized so far.
+///
+#[derive(Default)]
+pub(crate) struct ProvenanceRecorder {
+    provenance: Provenance,
+    interned: IndexMap<Frame, usize>,
+    stack: Vec<usize>,
+}
+
+impl ProvenanceRecorder {
+    pub(crate) fn enter(&mut self, kind: FrameKind, span: &Span, constraints: usize) {
+        let frame = Frame {
+            kind,
+            span: span.clone(),
+            parent: self.stack.last().copied(),
+        };
+        let index = match self.interned.get(&frame) {
+            Some(index) => *index,
+            None => {
+                let index = self.provenance.frames.len();
+                self.provenance.frames.push(frame.clone());
+                self.interned.insert(frame, index);
+                index
+            }
+        };
+        self.stack.push(index);
+        self.start_run(constraints);
+    }
+
+    pub(crate) fn exit(&mut self, constraints: usize) {
+        self.stack.pop();
+        self.start_run(constraints);
+    }
+
+    pub(crate) fn finish(mut self, constraints: usize) -> Provenance {
+        self.stack.clear();
+        self.start_run(constraints);
+        if matches!(self.provenance.runs.last(), Some((start, _)) if *start == constraints) {
+            self.provenance.runs.pop();
+        }
+        self.provenance.constraints = constraints;
+        self.provenance
+    }
+
+    /// Attributes the constraints from `constraints` on to the current frame.
+    fn start_run(&mut self, constraints: usize) {
+        let frame = self.stack.last().copied();
+        let runs = &mut self.provenance.runs;
+        // A run that ends before any constraint is synthesized is dropped.
+        if matches!(runs.last(), Some((start, _)) if *start == constraints) {
+            runs.pop();
+        }
+        if runs.last().map(|(_, last)| *last) != Some(frame) {
+            runs.push((constraints, frame));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Digests stamped into artifacts so that a build can be checked to reproduce them.
+
+use leo_ast::ArtifactStamp;
+
+use indexmap::IndexMap;
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fmt};
+
+///
+/// Returns the source with line endings and trailing whitespace normalized,
+/// so that sources that only differ in either have the same digest.
+///
+pub fn normalize_source(source: &str) -> String {
+    let mut normalized = source
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let trimmed = normalized.trim_end_matches('\n').len();
+    normalized.truncate(trimmed);
+    normalized
+}
+
+///
+/// Returns the combined SHA256 digest of the given sources, keyed by path.
+///
+/// Every normalized source is hashed along with its path, and the digest is taken over
+/// those hashes in the order of their paths, so it does not depend on the order imports are resolved in.
+///
+pub fn source_digest(sources: &IndexMap<String, String>) -> String {
+    let leaves = sources
+        .iter()
+        .map(|(path, source)| {
+            let mut hasher = Sha256::new();
+            hasher.update(path.as_bytes());
+            hasher.update([0u8]);
+            hasher.update(normalize_source(source).as_bytes());
+            (path, hasher.finalize())
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let mut hasher = Sha256::new();
+    for leaf in leaves.values() {
+        hasher.update(leaf);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// How a component of the stamp of an artifact compares to the current build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StampCheck {
+    /// The artifact was built from the same component.
+    Matches,
+    /// The component changed since the artifact was built.
+    Changed { stamped: String, current: String },
+    /// The artifact or the current build does not record the component.
+    Unrecorded,
+}
+
+impl StampCheck {
+    fn compare(stamped: Option<&String>, current: Option<&String>) -> Self {
+        match (stamped, current) {
+            (Some(stamped), Some(current)) if stamped == current => StampCheck::Matches,
+            (Some(stamped), Some(current)) => StampCheck::Changed {
+                stamped: stamped.clone(),
+                current: current.clone(),
+            },
+            _ => StampCheck::Unrecorded,
+        }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        matches!(self, StampCheck::Changed { .. })
+    }
+}
+
+///
+/// Compares the stamp of an artifact to the current build, component by component.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// The version of the compiler.
+    pub version: StampCheck,
+    /// The main program file and every file it imports.
+    pub source: StampCheck,
+    /// The compiler options.
+    pub options: StampCheck,
+}
+
+impl VerificationReport {
+    /// Returns the report comparing the `stamped` artifact with the `current` stamp of the same kind.
+    pub fn new(stamped: &ArtifactStamp, current: &ArtifactStamp) -> Self {
+        Self {
+            version: StampCheck::compare(Some(&stamped.compiler), Some(&current.compiler)),
+            source: StampCheck::compare(stamped.source.as_ref(), current.source.as_ref()),
+            options: StampCheck::compare(stamped.options.as_ref(), current.options.as_ref()),
+        }
+    }
+
+    /// Returns `true` if no component changed since the artifact was built.
+    pub fn is_reproduced(&self) -> bool {
+        !self.version.is_changed() && !self.source.is_changed() && !self.options.is_changed()
+    }
+
+    fn components(&self) -> [(&'static str, &StampCheck); 3] {
+        [
+            ("version", &self.version),
+            ("source", &self.source),
+            ("options", &self.options),
+        ]
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, check) in self.components().iter() {
+            match check {
+                StampCheck::Matches => writeln!(f, "{}: matches", name)?,
+                StampCheck::Changed { stamped, current } => {
+                    writeln!(f, "{}: changed from {} to {}", name, stamped, current)?
+                }
+                StampCheck::Unrecorded => writeln!(f, "{}: not recorded", name)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reports which features of the language this build of the compiler supports.
+
+use crate::{compile_source, CompileOptions};
+
+use indexmap::IndexMap;
+use std::fmt;
+
+/// A program using one feature of the language, with an input and the output it must produce.
+#[derive(Clone, Copy, Debug)]
+pub struct Probe {
+    pub feature: &'static str,
+    pub source: &'static str,
+    pub input: &'static str,
+    /// A line the output registers must contain.
+    pub expected_output: &'static str,
+}
+
+impl Probe {
+    ///
+    /// Compiles the program with its input, returning whether it produced the expected output.
+    ///
+    /// A probe fails on any diagnostic, including an unsupported construct or a panic inside the compiler.
+    ///
+    pub fn run(&self) -> FeatureStatus {
+        let options = CompileOptions {
+            input: Some(self.input.to_string()),
+            ..Default::default()
+        };
+        let result = compile_source(self.source, options);
+
+        // The first line of a diagnostic is its message, the rest points at the source.
+        if let Some(diagnostic) = result.diagnostics.first() {
+            let message = diagnostic.lines().next().unwrap_or_default().trim();
+            return FeatureStatus::Unsupported(message.to_string());
+        }
+        match result.output {
+            Some(output) if output.contains(self.expected_output) => FeatureStatus::Supported,
+            Some(output) => FeatureStatus::Unsupported(format!(
+                "expected the output `{}`, found `{}`",
+                self.expected_output,
+                output.trim()
+            )),
+            None => FeatureStatus::Unsupported("the circuit was not synthesized".to_string()),
+        }
+    }
+}
+
+/// The programs compiled by [`self_check`], one per feature.
+pub const PROBES: &[Probe] = &[
+    Probe {
+        feature: "arithmetic",
+        source: r#"
+function main(a: u32, b: u32) -> u32 {
+    return a * b + a - b / 2;
+}
+"#,
+        input: "[main]\na: u32 = 6;\nb: u32 = 4;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 28;",
+    },
+    Probe {
+        feature: "conditionals",
+        source: r#"
+function main(a: u32) -> u32 {
+    let b = 1u32;
+    if a > 3 {
+        b = a;
+    } else {
+        b = 2;
+    }
+    return b;
+}
+"#,
+        input: "[main]\na: u32 = 5;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 5;",
+    },
+    Probe {
+        feature: "loops",
+        source: r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0..4 {
+        b += i;
+    }
+    return b;
+}
+"#,
+        input: "[main]\na: u32 = 1;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 7;",
+    },
+    Probe {
+        feature: "arrays",
+        source: r#"
+function main(a: [u8; 3]) -> u8 {
+    let b = [a[0], a[2], 5u8];
+    return b[0] + b[1] + b[2];
+}
+"#,
+        input: "[main]\na: [u8; 3] = [1, 2, 3];\n\n[registers]\nr0: u8 = 0;\n",
+        expected_output: "r0: u8 = 9;",
+    },
+    Probe {
+        feature: "circuits",
+        source: r#"
+circuit Point {
+    x: u32,
+    y: u32,
+
+    function sum(self) -> u32 {
+        return self.x + self.y;
+    }
+}
+
+function main(a: u32) -> u32 {
+    let p = Point { x: a, y: 2 };
+    return p.sum();
+}
+"#,
+        input: "[main]\na: u32 = 3;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 5;",
+    },
+    Probe {
+        feature: "calls",
+        source: r#"
+function double(x: u32) -> u32 {
+    return x + x;
+}
+
+function main(a: u32) -> u32 {
+    return double(double(a));
+}
+"#,
+        input: "[main]\na: u32 = 3;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 12;",
+    },
+    Probe {
+        feature: "console",
+        source: r#"
+function main(a: u32) -> u32 {
+    console.log("a is {}", a);
+    console.assert(a == 2);
+    return a;
+}
+"#,
+        input: "[main]\na: u32 = 2;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 2;",
+    },
+    Probe {
+        feature: "casts",
+        source: r#"
+function main(a: u8) -> u32 {
+    return a as u32 + 300;
+}
+"#,
+        input: "[main]\na: u8 = 200;\n\n[registers]\nr0: u32 = 0;\n",
+        expected_output: "r0: u32 = 500;",
+    },
+    Probe {
+        feature: "inputs",
+        source: r#"
+function main(flag: bool, pair: (u8, u8), values: [u16; 2]) -> u16 {
+    console.assert(pair.0 < pair.1);
+    let total = values[0] + values[1];
+    if flag {
+        total += 1;
+    }
+    return total;
+}
+"#,
+        input: concat!(
+            "[main]\nflag: bool = true;\npair: (u8, u8) = (1, 2);\nvalues: [u16; 2] = [4, 5];\n\n",
+            "[registers]\nr0: u16 = 0;\n",
+        ),
+        expected_output: "r0: u16 = 10;",
+    },
+];
+
+/// Whether a feature works in this build of the compiler.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeatureStatus {
+    Supported,
+    /// The probe of the feature failed, for the given reason.
+    Unsupported(String),
+}
+
+///
+/// The status of every feature probed by [`self_check`], in the order of [`PROBES`].
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeatureReport {
+    pub features: IndexMap<&'static str, FeatureStatus>,
+}
+
+impl FeatureReport {
+    /// Returns `true` if the feature was probed and is supported.
+    pub fn is_supported(&self, feature: &str) -> bool {
+        self.features.get(feature) == Some(&FeatureStatus::Supported)
+    }
+
+    /// Returns the features that are not supported, with the reason.
+    pub fn unsupported(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.features.iter().filter_map(|(feature, status)| match status {
+            FeatureStatus::Supported => None,
+            FeatureStatus::Unsupported(reason) => Some((*feature, reason.as_str())),
+        })
+    }
+}
+
+impl fmt::Display for FeatureReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .features
+            .keys()
+            .map(|feature| feature.len())
+            .max()
+            .unwrap_or_default();
+        for (feature, status) in self.features.iter() {
+            match status {
+                FeatureStatus::Supported => writeln!(f, "{:width$}  supported", feature, width = width)?,
+                FeatureStatus::Unsupported(reason) => {
+                    writeln!(f, "{:width$}  unsupported: {}", feature, reason, width = width)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+///
+/// Compiles every probe through the whole pipeline in memory, reporting which features work.
+///
+/// Adding support for a feature flips its probe to supported; the probes are also run by the tests.
+///
+pub fn self_check() -> FeatureReport {
+    let mut report = FeatureReport::default();
+    for probe in PROBES {
+        report.features.insert(probe.feature, probe.run());
+    }
+    report
+}
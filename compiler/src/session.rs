@@ -0,0 +1,511 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compiles a Leo program held in memory, in a single call or a step at a time.
+
+use crate::{
+    compiler::{Compiler, ProgramCheck},
+    targets::edwards_bls12::EdwardsGroupType,
+    ArtifactSink, AstSnapshotOptions, CompilerOptions, DirectorySink, LogLine, MemorySink, Synthesis,
+};
+use leo_asg::{AsgContext, TypeCacheStatistics};
+use leo_asg_passes::{
+    AssertStatistics, BooleanSimplificationStatistics, EvaluationStatistics, InlineDecision, InlineReason,
+};
+use leo_ast::{metrics, Ast, AstMetrics, CircuitMember, SymbolStatistics};
+use leo_errors::{panic_message, CompilerError, LeoError, Result};
+use leo_synthesizer::CircuitSynthesizer;
+
+use snarkvm_curves::{bls12_377::Bls12_377, edwards_bls12::Fq};
+use snarkvm_r1cs::ConstraintSystem;
+
+use indexmap::IndexMap;
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// The name given to programs compiled with [`compile_source`].
+const SOURCE_PROGRAM_NAME: &str = "main";
+
+///
+/// Configures a call to [`compile_source`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct CompileOptions {
+    pub compiler: CompilerOptions,
+    pub ast_snapshots: AstSnapshotOptions,
+    /// Program input. The circuit is only synthesized when an input is given.
+    pub input: Option<String>,
+    /// Program state, used together with the program input.
+    pub state: Option<String>,
+    /// Directory the artifacts are also written to. Nothing touches the filesystem when unset.
+    pub output_directory: Option<PathBuf>,
+}
+
+///
+/// Counts describing the size of a compiled program.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompileStatistics {
+    pub functions: usize,
+    pub circuits: usize,
+    pub global_consts: usize,
+    /// Node counts, function sizes and nesting depths of the program.
+    pub metrics: AstMetrics,
+    /// Number of scalar input slots taken by the non-const parameters of `main`, if the program was checked.
+    pub input_slots: Option<usize>,
+    /// Number of constraints, if the circuit was synthesized.
+    pub constraints: Option<usize>,
+    /// Number of public and private variables, if the circuit was synthesized.
+    pub variables: Option<usize>,
+    /// Approximate bytes held by the program AST, see [`Ast::deep_size`].
+    pub ast_bytes: usize,
+    /// Number and total length of the distinct identifier names of the program.
+    pub symbols: SymbolStatistics,
+    /// Approximate bytes held by the symbol table, if the program was checked.
+    pub symbol_table_bytes: Option<usize>,
+    /// Peak resident set size of the process so far, where the platform reports it.
+    pub peak_rss_bytes: Option<usize>,
+    /// Lookups in the cache of calls evaluated by constant folding, if it ran.
+    pub evaluation_cache: Option<EvaluationStatistics>,
+    /// Rewrites applied by boolean simplification, if it ran.
+    pub boolean_simplifications: Option<BooleanSimplificationStatistics>,
+    /// Asserts removed because they always hold, if the program was checked.
+    pub removed_asserts: Option<AssertStatistics>,
+    /// How the calls to every function that is called are compiled, if the program was checked.
+    pub inlining: Option<IndexMap<String, InlineDecision>>,
+    /// Queries of the type name and type size caches, if the program was checked.
+    pub type_cache: Option<TypeCacheStatistics>,
+}
+
+impl fmt::Display for CompileStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "functions: {}", self.functions)?;
+        writeln!(f, "circuits: {}", self.circuits)?;
+        writeln!(f, "global constants: {}", self.global_consts)?;
+        writeln!(f, "statements: {}", self.metrics.statement_count())?;
+        writeln!(f, "expressions: {}", self.metrics.expression_count())?;
+        writeln!(
+            f,
+            "nesting: {} expressions, {} blocks",
+            self.metrics.max_expression_depth, self.metrics.max_block_depth
+        )?;
+        if self.metrics.error_nodes > 0 {
+            writeln!(f, "error placeholders: {}", self.metrics.error_nodes)?;
+        }
+        if let Some(input_slots) = self.input_slots {
+            writeln!(f, "input slots: {}", input_slots)?;
+        }
+        if let Some(constraints) = self.constraints {
+            writeln!(f, "constraints: {}", constraints)?;
+        }
+        if let Some(variables) = self.variables {
+            writeln!(f, "variables: {}", variables)?;
+        }
+        if let Some(cache) = self.evaluation_cache {
+            writeln!(
+                f,
+                "evaluated calls: {} cache hits, {} misses, {} entries",
+                cache.hits, cache.misses, cache.entries
+            )?;
+        }
+        if let Some(rewrites) = self.boolean_simplifications {
+            writeln!(
+                f,
+                "boolean simplifications: {} double negations, {} constants, {} absorptions, {} factorings, {} De Morgan",
+                rewrites.double_negations, rewrites.constants, rewrites.absorptions, rewrites.factorings, rewrites.de_morgans
+            )?;
+        }
+        if let Some(asserts) = self.removed_asserts {
+            writeln!(
+                f,
+                "removed asserts: {} constant, {} provable from types",
+                asserts.constant, asserts.from_types
+            )?;
+        }
+        if let Some(decisions) = &self.inlining {
+            let count = |reason| decisions.values().filter(|decision| decision.reason == reason).count();
+            writeln!(
+                f,
+                "inlined functions: {} annotated, {} within the threshold, {} above it",
+                count(InlineReason::Annotated),
+                count(InlineReason::BelowThreshold),
+                count(InlineReason::NoCallInstruction)
+            )?;
+        }
+        if let Some(cache) = self.type_cache {
+            writeln!(
+                f,
+                "type caches: {} name hits, {} name misses, {} size hits, {} size misses",
+                cache.identifier_hits, cache.identifier_misses, cache.size_hits, cache.size_misses
+            )?;
+        }
+        writeln!(f, "memory (approximate, shared span text not counted):")?;
+        writeln!(f, "  ast: {} bytes", self.ast_bytes)?;
+        writeln!(
+            f,
+            "  symbols: {} distinct names, {} bytes",
+            self.symbols.count, self.symbols.bytes
+        )?;
+        if let Some(bytes) = self.symbol_table_bytes {
+            writeln!(f, "  symbol table: {} bytes", bytes)?;
+        }
+        match self.peak_rss_bytes {
+            Some(bytes) => writeln!(f, "  peak resident set: {} bytes (whole process)", bytes),
+            None => writeln!(f, "  peak resident set: not reported on this platform"),
+        }
+    }
+}
+
+///
+/// Everything produced by [`compile_source`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct CompileResult {
+    /// Rendered errors. Compilation succeeded if this is empty.
+    pub diagnostics: Vec<String>,
+    /// Problems that did not stop the compilation, such as an output directory that cannot be written.
+    pub warnings: Vec<String>,
+    /// The program AST after all AST passes, as JSON.
+    pub ast: Option<String>,
+    /// One line per function, circuit, circuit member and global constant, e.g. `circuit Foo` or `function Foo::new`.
+    pub symbols: Vec<String>,
+    /// The output registers, if the circuit was synthesized.
+    pub output: Option<String>,
+    /// Console output produced while synthesizing the circuit.
+    pub logs: Vec<LogLine>,
+    pub statistics: CompileStatistics,
+    /// Artifacts written by the compiler, keyed by file name.
+    pub artifacts: IndexMap<String, Vec<u8>>,
+}
+
+impl CompileResult {
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+///
+/// Compiles the given program source without any other setup.
+///
+/// Every call allocates its own ASG context, so this may be called repeatedly and from any thread.
+/// Errors, including panics inside the compiler, are returned as diagnostics.
+///
+pub fn compile_source(source: &str, options: CompileOptions) -> CompileResult {
+    let arena = leo_asg::new_alloc_context();
+    let context = leo_asg::new_context(&arena);
+
+    let mut session = CompileSession::start(context, source, options);
+    loop {
+        match session.step() {
+            StepResult::Continue => (),
+            StepResult::Done(result) | StepResult::Failed(result) => return *result,
+        }
+    }
+}
+
+///
+/// What a step of a [`CompileSession`] did.
+///
+#[derive(Clone, Debug)]
+pub enum StepResult {
+    /// There is more work to do.
+    Continue,
+    /// The compilation ended without diagnostics.
+    Done(Box<CompileResult>),
+    /// The compilation ended with diagnostics.
+    Failed(Box<CompileResult>),
+}
+
+enum SessionStage<'a> {
+    Parse(String),
+    Check {
+        check: ProgramCheck,
+        parsed: leo_ast::Program,
+        /// Whether the parser recovered from syntax errors.
+        recovered: bool,
+    },
+    Report {
+        parsed: leo_ast::Program,
+        recovered: bool,
+        checked: bool,
+    },
+    Synthesize(Box<Synthesis<'a, Fq, EdwardsGroupType>>, CircuitSynthesizer<Bls12_377>),
+    Finished,
+}
+
+///
+/// Compiles a program a bounded amount of work at a time, for hosts that cannot block, such as an editor.
+///
+/// Every [`CompileSession::step`] runs one phase or pass of checking the program, or a statement or a chunk of
+/// loop iterations of synthesizing its circuit, so the host can poll for other events between steps and drop
+/// the session to stop. The result is the same as that of [`compile_source`], which steps a session to the end.
+///
+/// The session is started with the ASG context the program is checked in, as the compiler borrows it.
+///
+pub struct CompileSession<'a> {
+    compiler: Compiler<'a, Fq, EdwardsGroupType>,
+    options: CompileOptions,
+    sink: MemorySink,
+    result: CompileResult,
+    stage: SessionStage<'a>,
+}
+
+impl<'a> CompileSession<'a> {
+    ///
+    /// Starts compiling `source`. Nothing is done until the first step.
+    ///
+    pub fn start(context: AsgContext<'a>, source: &str, options: CompileOptions) -> Self {
+        let sink = MemorySink::new();
+        let compiler = Compiler::<Fq, EdwardsGroupType>::new(
+            SOURCE_PROGRAM_NAME.to_string(),
+            PathBuf::from(format!("{}.leo", SOURCE_PROGRAM_NAME)),
+            PathBuf::new(),
+            context,
+            Some(options.compiler.clone()),
+            IndexMap::new(),
+            Some(options.ast_snapshots.clone()),
+        )
+        .with_output_writer(sink.clone());
+
+        Self {
+            compiler,
+            options,
+            sink,
+            result: CompileResult::default(),
+            stage: SessionStage::Parse(source.to_string()),
+        }
+    }
+
+    ///
+    /// Does the next step of the compilation.
+    ///
+    /// Once the compilation ended, every step returns its result again.
+    ///
+    pub fn step(&mut self) -> StepResult {
+        if let SessionStage::Finished = self.stage {
+            return self.ended();
+        }
+
+        let stage = std::mem::replace(&mut self.stage, SessionStage::Finished);
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| self.advance(stage)));
+        let error: Option<LeoError> = match outcome {
+            Ok(Ok(Some(stage))) => {
+                self.stage = stage;
+                return StepResult::Continue;
+            }
+            Ok(Ok(None)) => None,
+            Ok(Err(error)) => Some(error),
+            Err(payload) => Some(CompilerError::compiler_panicked(panic_message(payload.as_ref())).into()),
+        };
+        if let Some(error) = error {
+            self.result.diagnostics.push(error.to_string());
+        }
+
+        self.result.artifacts = self.sink.artifacts();
+        if let Some(directory) = &self.options.output_directory {
+            let mut directory_sink = DirectorySink::new(directory.clone());
+            for (name, bytes) in self.result.artifacts.iter() {
+                if let Err(error) = directory_sink.write(name, bytes) {
+                    self.result.diagnostics.push(error.to_string());
+                }
+            }
+            self.result.warnings.extend(directory_sink.warnings());
+        }
+
+        self.ended()
+    }
+
+    fn ended(&self) -> StepResult {
+        let result = Box::new(self.result.clone());
+        if result.is_ok() {
+            StepResult::Done(result)
+        } else {
+            StepResult::Failed(result)
+        }
+    }
+
+    /// Runs the stage, returning the stage after it, or `None` if the compilation ended.
+    fn advance(&mut self, stage: SessionStage<'a>) -> Result<Option<SessionStage<'a>>> {
+        let next = match stage {
+            SessionStage::Parse(source) => {
+                // Keep going past syntax errors so that every one of them is reported, along with
+                // the errors of the rest of the program that do not stem from the recovery.
+                let (parsed, parse_errors) =
+                    leo_parser::parse_recovering(&format!("{}.leo", SOURCE_PROGRAM_NAME), &source);
+                self.result
+                    .diagnostics
+                    .extend(parse_errors.iter().map(ToString::to_string));
+                let check = self.compiler.start_program_from_ast(Ast::new(parsed.clone()));
+                SessionStage::Check {
+                    check,
+                    parsed,
+                    recovered: !parse_errors.is_empty(),
+                }
+            }
+            SessionStage::Check {
+                mut check,
+                parsed,
+                recovered,
+            } => match self.compiler.check_step(&mut check) {
+                Ok(false) => SessionStage::Check {
+                    check,
+                    parsed,
+                    recovered,
+                },
+                Ok(true) => SessionStage::Report {
+                    parsed,
+                    recovered,
+                    checked: true,
+                },
+                Err(error) if !recovered => return Err(error),
+                Err(error) => {
+                    if !parsed.is_recovered(&error) {
+                        self.result.diagnostics.push(error.to_string());
+                    }
+                    SessionStage::Report {
+                        parsed,
+                        recovered,
+                        checked: false,
+                    }
+                }
+            },
+            SessionStage::Report {
+                parsed,
+                recovered,
+                checked,
+            } => {
+                self.report(&parsed, checked)?;
+                if recovered {
+                    return Ok(None);
+                }
+
+                let input = match &self.options.input {
+                    Some(input) => input,
+                    None => return Ok(None),
+                };
+                let input_errors = self.compiler.parse_input_recovering(
+                    input,
+                    Path::new("input.in"),
+                    self.options.state.as_deref().unwrap_or_default(),
+                    Path::new("input.state"),
+                );
+                if !input_errors.is_empty() {
+                    self.result
+                        .diagnostics
+                        .extend(input_errors.iter().map(ToString::to_string));
+                    return Ok(None);
+                }
+
+                let synthesis = self.compiler.start_constraints()?;
+                SessionStage::Synthesize(Box::new(synthesis), CircuitSynthesizer::default())
+            }
+            SessionStage::Synthesize(mut synthesis, mut cs) => {
+                let output = match self.compiler.constraints_step(&mut synthesis, &mut cs)? {
+                    Some(output) => output,
+                    None => return Ok(Some(SessionStage::Synthesize(synthesis, cs))),
+                };
+                let result = &mut self.result;
+                result.statistics.constraints = Some(cs.num_constraints());
+                result.statistics.variables = Some(cs.num_public_variables() + cs.num_private_variables());
+                result.statistics.peak_rss_bytes = peak_rss_bytes();
+                result.output = Some(output.to_string());
+                result.logs = output.logs;
+                return Ok(None);
+            }
+            SessionStage::Finished => return Ok(None),
+        };
+        Ok(Some(next))
+    }
+
+    /// Records the AST, the symbols and the statistics of the program, checked or as parsed.
+    fn report(&mut self, parsed: &leo_ast::Program, checked: bool) -> Result<()> {
+        let compiler = &self.compiler;
+        let result = &mut self.result;
+
+        let program = if checked { compiler.program() } else { parsed };
+        let ast = Ast::new(program.clone());
+        result.ast = Some(ast.to_json_string()?);
+        result.statistics.ast_bytes = ast.deep_size()?;
+        result.statistics.symbols = ast.symbol_statistics()?;
+        if checked {
+            result.statistics.input_slots = compiler.input_slots().map(|slots| slots.total);
+            result.statistics.evaluation_cache = compiler.evaluation_statistics();
+            result.statistics.boolean_simplifications = compiler.boolean_simplification_statistics();
+            result.statistics.removed_asserts = compiler.assert_statistics();
+            result.statistics.inlining = compiler.inline_decisions().cloned();
+            result.statistics.type_cache = compiler.type_cache_statistics();
+            result.statistics.symbol_table_bytes = compiler.symbol_table().map(|table| table.deep_size());
+        }
+        result.symbols = symbols(program);
+        result.statistics.functions = program.functions.len();
+        result.statistics.circuits = program.circuits.len();
+        result.statistics.global_consts = program.global_consts.len();
+        result.statistics.metrics = match compiler.metrics() {
+            Some(metrics) if checked => metrics.clone(),
+            _ => metrics::collect(program),
+        };
+        result.statistics.peak_rss_bytes = peak_rss_bytes();
+        Ok(())
+    }
+}
+
+/// Returns the peak resident set size of the process, on platforms that report it.
+#[cfg(target_os = "linux")]
+fn peak_rss_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: usize = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss_bytes() -> Option<usize> {
+    None
+}
+
+fn symbols(program: &leo_ast::Program) -> Vec<String> {
+    let mut symbols = vec![];
+    for (name, circuit) in program.circuits.iter() {
+        symbols.push(format!("circuit {}", name.name));
+        for member in circuit.members.iter() {
+            symbols.push(match member {
+                CircuitMember::CircuitVariable(member, _) => format!("member {}::{}", name.name, member.name),
+                CircuitMember::CircuitStatic(member, ..) => format!("static {}::{}", name.name, member.name),
+                CircuitMember::CircuitFunction(function) => {
+                    format!("function {}::{}", name.name, function.identifier.name)
+                }
+            });
+        }
+    }
+    for name in program.functions.keys() {
+        symbols.push(format!("function {}", name.name));
+    }
+    for names in program.global_consts.keys() {
+        for name in names.iter() {
+            symbols.push(format!("const {}", name.name));
+        }
+    }
+    symbols
+}
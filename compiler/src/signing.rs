@@ -0,0 +1,170 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Detached signatures of the artifacts written by the compiler.
+//!
+//! The compiler embeds no cryptography: the signatures are made by a [`Signer`] and checked by a
+//! [`Verifier`], which are both given by the caller.
+
+use crate::ArtifactSink;
+use leo_errors::{CompilerError, Result};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt, fs, path::Path, sync::Arc};
+
+/// The name of the manifest listing every signed artifact, at the root of the output directory.
+pub const SIGNATURE_MANIFEST: &str = "manifest.sig.json";
+
+/// Signs the artifacts written by the compiler.
+pub trait Signer {
+    /// Returns the signature of the artifact with the given name and contents.
+    fn sign(&self, artifact_name: &str, bytes: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Checks the signatures made by a [`Signer`].
+pub trait Verifier {
+    /// Returns `true` if `signature` is a signature of the artifact with the given name and contents.
+    fn verify(&self, artifact_name: &str, bytes: &[u8], signature: &[u8]) -> bool;
+}
+
+///
+/// The optional [`Signer`] of a compilation.
+///
+/// With a signer, every artifact is written along with a detached `<artifact>.sig` signature,
+/// and listed with its digest and signature in the [`SIGNATURE_MANIFEST`].
+///
+#[derive(Clone, Default)]
+pub struct ArtifactSigner(Option<Arc<dyn Signer>>);
+
+impl ArtifactSigner {
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self(Some(signer))
+    }
+
+    pub fn signer(&self) -> Option<Arc<dyn Signer>> {
+        self.0.clone()
+    }
+}
+
+impl fmt::Debug for ArtifactSigner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(_) => write!(f, "ArtifactSigner(signer)"),
+            None => write!(f, "ArtifactSigner(none)"),
+        }
+    }
+}
+
+/// An artifact listed in the signature manifest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedArtifact {
+    pub name: String,
+    /// The SHA256 digest of the artifact, in hex.
+    pub digest: String,
+    /// The signature of the artifact, in hex. The `.sig` file next to the artifact holds its bytes.
+    pub signature: String,
+}
+
+///
+/// Every artifact signed by a compilation, written as [`SIGNATURE_MANIFEST`].
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureManifest {
+    pub artifacts: Vec<SignedArtifact>,
+}
+
+impl SignatureManifest {
+    ///
+    /// Signs the artifact written to `sink`, writing its signature and the manifest listing it.
+    ///
+    /// An artifact written again replaces its entry, so the manifest lists what the sink holds.
+    ///
+    pub(crate) fn sign(
+        &mut self,
+        sink: &mut dyn ArtifactSink,
+        signer: &dyn Signer,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<()> {
+        let signature = signer.sign(name, bytes)?;
+        sink.write(&format!("{}.sig", name), &signature)?;
+
+        let artifact = SignedArtifact {
+            name: name.to_string(),
+            digest: digest(bytes),
+            signature: to_hex(&signature),
+        };
+        match self.artifacts.iter_mut().find(|signed| signed.name == name) {
+            Some(signed) => *signed = artifact,
+            None => self.artifacts.push(artifact),
+        }
+
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| CompilerError::failed_to_write_signature_manifest(&e))?;
+        sink.write(SIGNATURE_MANIFEST, json.as_bytes())
+    }
+}
+
+///
+/// Checks the artifacts in `directory` against its signature manifest, returning the names of the artifacts checked.
+///
+/// Returns an error naming the first artifact that is missing, whose digest differs from the manifest,
+/// or whose signature does not verify.
+///
+pub fn verify_artifacts(directory: &Path, verifier: &dyn Verifier) -> Result<Vec<String>> {
+    let path = directory.join(SIGNATURE_MANIFEST);
+    let json = fs::read_to_string(&path).map_err(|e| CompilerError::file_read_error(path.clone(), e))?;
+    let manifest: SignatureManifest = serde_json::from_str(&json)
+        .map_err(|e| CompilerError::failed_to_read_signature_manifest(path.display(), &e))?;
+
+    let mut verified = vec![];
+    for artifact in manifest.artifacts {
+        let read = |name: &str| fs::read(directory.join(name)).ok();
+        let bytes = read(&artifact.name)
+            .ok_or_else(|| CompilerError::artifact_signature_invalid(&artifact.name, "the artifact is missing"))?;
+        if digest(&bytes) != artifact.digest {
+            return Err(CompilerError::artifact_signature_invalid(
+                &artifact.name,
+                "its digest differs from the manifest",
+            )
+            .into());
+        }
+        let signature = read(&format!("{}.sig", artifact.name)).unwrap_or_default();
+        if to_hex(&signature) != artifact.signature {
+            return Err(CompilerError::artifact_signature_invalid(
+                &artifact.name,
+                "its signature file differs from the manifest",
+            )
+            .into());
+        }
+        if !verifier.verify(&artifact.name, &bytes, &signature) {
+            return Err(
+                CompilerError::artifact_signature_invalid(&artifact.name, "its signature does not verify").into(),
+            );
+        }
+        verified.push(artifact.name);
+    }
+    Ok(verified)
+}
+
+fn digest(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Names the sources of a program, and reads them from the filesystem or elsewhere.
+
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+///
+/// The name of a program or input source, as shown in diagnostics.
+///
+/// Only real files have a path. Other sources are named for diagnostics alone,
+/// so nothing is ever read from or written next to their name.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FileName {
+    /// A file on the filesystem, which may also be a named pipe.
+    Real(PathBuf),
+    /// The standard input of the process.
+    Stdin,
+    /// A source given as a string, under a name chosen by the caller.
+    Custom(String),
+}
+
+impl FileName {
+    ///
+    /// Returns the name of a source given on the command line, where `-` stands for the standard input.
+    ///
+    pub fn from_argument(argument: &str) -> Self {
+        if argument == "-" {
+            FileName::Stdin
+        } else {
+            FileName::Real(PathBuf::from(argument))
+        }
+    }
+
+    /// Returns the path of the source, if it is a file.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            FileName::Real(path) => Some(path),
+            FileName::Stdin | FileName::Custom(_) => None,
+        }
+    }
+
+    /// Returns `true` if the source is a file, which can be read again to tell whether it changed.
+    pub fn is_real(&self) -> bool {
+        self.path().is_some()
+    }
+}
+
+impl fmt::Display for FileName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileName::Real(path) => write!(f, "{}", path.display()),
+            FileName::Stdin => write!(f, "<stdin>"),
+            FileName::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl From<PathBuf> for FileName {
+    fn from(path: PathBuf) -> Self {
+        FileName::Real(path)
+    }
+}
+
+impl From<&Path> for FileName {
+    fn from(path: &Path) -> Self {
+        FileName::Real(path.to_path_buf())
+    }
+}
+
+///
+/// Reads the sources that the compiler is given by name rather than as strings.
+///
+pub trait SourceProvider {
+    /// Returns the contents of the named source.
+    fn read_source(&self, name: &FileName) -> io::Result<String>;
+}
+
+///
+/// Reads files from the filesystem and the standard input from the process.
+///
+/// Sources with a custom name only exist as the strings they are given as, so reading one fails.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileSystemSources;
+
+impl SourceProvider for FileSystemSources {
+    fn read_source(&self, name: &FileName) -> io::Result<String> {
+        match name {
+            FileName::Real(path) => fs::read_to_string(path),
+            FileName::Stdin => {
+                let mut source = String::new();
+                io::stdin().read_to_string(&mut source)?;
+                Ok(source)
+            }
+            FileName::Custom(name) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("`{}` is not a file, so its source must be given as a string", name),
+            )),
+        }
+    }
+}
@@ -56,7 +56,14 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             AssignOperation::Mul => enforce_mul(cs, target.clone(), new_value, span)?,
             AssignOperation::Div => enforce_div(cs, target.clone(), new_value, span)?,
             AssignOperation::Pow => enforce_pow(cs, target.clone(), new_value, span)?,
-            _ => unimplemented!("unimplemented assign operator"),
+            operation => {
+                return Err(CompilerError::unsupported_construct(
+                    format!("the `{}` assignment", operation.as_ref()),
+                    "constraint synthesis",
+                    span,
+                )
+                .into());
+            }
         };
         let selected_value = ConstrainedValue::conditionally_select(cs.ns(|| scope), condition, &new_value, target)
             .map_err(|_| CompilerError::statement_select_fail(new_value, target.clone(), span))?;
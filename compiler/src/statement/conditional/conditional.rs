@@ -19,7 +19,7 @@
 use crate::{
     program::ConstrainedProgram, value::ConstrainedValue, GroupType, IndicatorAndConstrainedValue, StatementResult,
 };
-use leo_asg::ConditionalStatement;
+use leo_asg::{ConditionalStatement, Statement};
 use leo_errors::CompilerError;
 
 use snarkvm_fields::PrimeField;
@@ -38,6 +38,9 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
     /// Due to R1CS constraints, we must evaluate every branch to properly construct the circuit.
     /// At program execution, we will pass an `indicator` bit down to all child statements within each branch.
     /// The `indicator` bit will select that branch while keeping the constraint system satisfied.
+    ///
+    /// An `else if` chain is walked arm by arm rather than recursively, carrying the indicator that
+    /// no earlier arm was taken, so every arm costs one `and` for its own indicator and one for the next.
     #[allow(clippy::too_many_arguments)]
     pub fn enforce_conditional_statement<CS: ConstraintSystem<F>>(
         &mut self,
@@ -45,66 +48,77 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         indicator: &Boolean,
         statement: &ConditionalStatement<'a>,
     ) -> StatementResult<Vec<IndicatorAndConstrainedValue<'a, F, G>>> {
-        let span = statement.span.clone().unwrap_or_default();
+        let mut results = vec![];
+
         // Inherit an indicator from a previous statement.
-        let outer_indicator = indicator;
-
-        // Evaluate the conditional boolean as the inner indicator
-        let inner_indicator = match self.enforce_expression(cs, statement.condition.get())? {
-            ConstrainedValue::Boolean(resolved) => resolved,
-            value => {
-                return Err(CompilerError::conditional_boolean_expression_fails_to_resolve_to_bool(
-                    value.to_string(),
-                    &span,
-                )
-                .into());
+        let mut outer_indicator = *indicator;
+        let mut arm = statement;
+        loop {
+            let span = arm.span.clone().unwrap_or_default();
+
+            // Evaluate the conditional boolean as the inner indicator
+            let inner_indicator = match self.enforce_expression(cs, arm.condition.get())? {
+                ConstrainedValue::Boolean(resolved) => resolved,
+                value => {
+                    return Err(CompilerError::conditional_boolean_expression_fails_to_resolve_to_bool(
+                        value.to_string(),
+                        &span,
+                    )
+                    .into());
+                }
+            };
+
+            // If outer_indicator && inner_indicator, then select branch 1
+            let outer_indicator_string = indicator_to_string(&outer_indicator);
+            let inner_indicator_string = indicator_to_string(&inner_indicator);
+            let branch_1_name = format!(
+                "branch indicator 1 {} && {}",
+                outer_indicator_string, inner_indicator_string
+            );
+            let branch_1_indicator = Boolean::and(
+                &mut cs.ns(|| format!("branch 1 {}:{}", &span.line_start, &span.col_start)),
+                &outer_indicator,
+                &inner_indicator,
+            )
+            .map_err(|_| CompilerError::statement_indicator_calculation(branch_1_name, &span))?;
+
+            // Evaluate branch 1
+            let mut branch_1_result = self.enforce_statement(cs, &branch_1_indicator, arm.result.get())?;
+
+            results.append(&mut branch_1_result);
+
+            // If outer_indicator && !inner_indicator, then select branch 2
+            let inner_indicator = inner_indicator.not();
+            let inner_indicator_string = indicator_to_string(&inner_indicator);
+            let branch_2_name = format!(
+                "branch indicator 2 {} && {}",
+                outer_indicator_string, inner_indicator_string
+            );
+            let branch_2_indicator = Boolean::and(
+                &mut cs.ns(|| format!("branch 2 {}:{}", &span.line_start, &span.col_start)),
+                &outer_indicator,
+                &inner_indicator,
+            )
+            .map_err(|_| CompilerError::statement_indicator_calculation(branch_2_name, &span))?;
+
+            // Evaluate branch 2, continuing down an `else if` chain with branch 2 as the outer indicator
+            match arm.next.get() {
+                Some(Statement::Conditional(next)) => {
+                    self.unrolled_statements += 1;
+                    outer_indicator = branch_2_indicator;
+                    arm = next;
+                }
+                Some(next) => {
+                    let mut branch_2_result = self.enforce_statement(cs, &branch_2_indicator, next)?;
+
+                    results.append(&mut branch_2_result);
+                    break;
+                }
+                None => break,
             }
-        };
-
-        // If outer_indicator && inner_indicator, then select branch 1
-        let outer_indicator_string = indicator_to_string(outer_indicator);
-        let inner_indicator_string = indicator_to_string(&inner_indicator);
-        let branch_1_name = format!(
-            "branch indicator 1 {} && {}",
-            outer_indicator_string, inner_indicator_string
-        );
-        let branch_1_indicator = Boolean::and(
-            &mut cs.ns(|| format!("branch 1 {}:{}", &span.line_start, &span.col_start)),
-            outer_indicator,
-            &inner_indicator,
-        )
-        .map_err(|_| CompilerError::statement_indicator_calculation(branch_1_name, &span))?;
-
-        let mut results = vec![];
+        }
 
-        // Evaluate branch 1
-        let mut branch_1_result = self.enforce_statement(cs, &branch_1_indicator, statement.result.get())?;
-
-        results.append(&mut branch_1_result);
-
-        // If outer_indicator && !inner_indicator, then select branch 2
-        let inner_indicator = inner_indicator.not();
-        let inner_indicator_string = indicator_to_string(&inner_indicator);
-        let branch_2_name = format!(
-            "branch indicator 2 {} && {}",
-            outer_indicator_string, inner_indicator_string
-        );
-        let branch_2_indicator = Boolean::and(
-            &mut cs.ns(|| format!("branch 2 {}:{}", &span.line_start, &span.col_start)),
-            outer_indicator,
-            &inner_indicator,
-        )
-        .map_err(|_| CompilerError::statement_indicator_calculation(branch_2_name, &span))?;
-
-        // Evaluate branch 2
-        let mut branch_2_result = match statement.next.get() {
-            Some(next) => self.enforce_statement(cs, &branch_2_indicator, next)?,
-            None => vec![],
-        };
-
-        results.append(&mut branch_2_result);
-
-        // We return the results of both branches and leave it up to the caller to select the appropriate return
+        // We return the results of every branch and leave it up to the caller to select the appropriate return
         Ok(results)
     }
 }
@@ -17,15 +17,32 @@
 //! Enforces an iteration statement in a compiled Leo program.
 
 use crate::{
-    program::ConstrainedProgram, value::ConstrainedValue, GroupType, IndicatorAndConstrainedValue, Integer,
-    IntegerTrait, StatementResult,
+    program::ConstrainedProgram, value::ConstrainedValue, FrameKind, GroupType, IndicatorAndConstrainedValue, Integer,
+    StatementResult,
 };
-use leo_asg::IterationStatement;
-use leo_errors::CompilerError;
+use leo_asg::{ConstInt, IntegerType, IterationStatement, Type};
+use leo_errors::{CompilerError, Span};
 
 use snarkvm_fields::PrimeField;
-use snarkvm_gadgets::{boolean::Boolean, integers::uint::UInt32};
+use snarkvm_gadgets::boolean::Boolean;
 use snarkvm_r1cs::ConstraintSystem;
+use std::{convert::TryFrom, iter::Peekable};
+
+/// The number of iterations of a loop reported as one progress item.
+pub const ITERATION_CHUNK: usize = 256;
+
+/// The iterations of a loop that are left to enforce.
+pub(crate) struct Iterations {
+    index_type: IntegerType,
+    /// The amount added to the counter after every iteration.
+    step: i128,
+    span: Span,
+    counters: Peekable<Box<dyn Iterator<Item = i128>>>,
+    /// The number of chunks of [`ITERATION_CHUNK`] iterations, if the number of iterations fits in a `usize`.
+    pub(crate) chunks: Option<usize>,
+    /// The number of iterations enforced so far.
+    index: usize,
+}
 
 impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
     #[allow(clippy::too_many_arguments)]
@@ -35,48 +52,128 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
         indicator: &Boolean,
         statement: &IterationStatement<'a>,
     ) -> StatementResult<Vec<IndicatorAndConstrainedValue<'a, F, G>>> {
-        let mut results = vec![];
+        let mut iterations = self.start_iterations(cs, statement)?;
 
+        let progress = self.progress.clone();
+        progress.phase("loop unrolling", iterations.chunks, || {
+            let mut results = vec![];
+            while self.enforce_iterations(cs, indicator, statement, &mut iterations, usize::MAX, &mut results)? {}
+            Ok(results)
+        })
+    }
+
+    /// Evaluates the bounds of the loop, returning its iterations.
+    pub(crate) fn start_iterations<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        statement: &IterationStatement<'a>,
+    ) -> StatementResult<Iterations> {
         let span = statement.span.clone().unwrap_or_default();
 
+        let index_type = match &statement.variable.borrow().type_ {
+            Type::Integer(index_type) => index_type.clone(),
+            _ => return Err(CompilerError::statement_loop_index_const(&span).into()),
+        };
+
+        // Bounds are computed in i128 so that ranges ending at the maximum of their type do not overflow.
         let from = self
             .enforce_index(cs, statement.start.get(), &span)?
-            .to_usize()
+            .to_i128()
             .ok_or_else(|| CompilerError::statement_loop_index_const(&span))?;
         let to = self
             .enforce_index(cs, statement.stop.get(), &span)?
-            .to_usize()
+            .to_i128()
             .ok_or_else(|| CompilerError::statement_loop_index_const(&span))?;
-
-        let iter: Box<dyn Iterator<Item = usize>> = match (from < to, statement.inclusive) {
-            (true, true) => Box::new(from..=to),
-            (true, false) => Box::new(from..to),
-            (false, true) => Box::new((to..=from).rev()),
-            // add the range to the values to get correct bound
-            (false, false) => Box::new(((to + 1)..(from + 1)).rev()),
+        let step = match statement.step.get() {
+            Some(step) => Some(
+                self.enforce_index(cs, step, &span)?
+                    .to_i128()
+                    .ok_or_else(|| CompilerError::statement_loop_index_const(&span))?,
+            ),
+            None => None,
         };
+        let counters = statement.counters(from, to, step)?;
+        let step = step.unwrap_or(if from <= to { 1 } else { -1 });
+
+        // Every chunk of iterations is a progress item, if the number of iterations fits in a `usize`.
+        let stride = step.unsigned_abs();
+        let chunks = to
+            .checked_sub(from)
+            .map(|distance| distance.unsigned_abs())
+            .map(|distance| match statement.inclusive {
+                true => distance / stride + 1,
+                false => (distance + stride - 1) / stride,
+            })
+            .and_then(|iterations| usize::try_from(iterations).ok())
+            .map(|iterations| iterations / ITERATION_CHUNK + (iterations % ITERATION_CHUNK != 0) as usize);
+
+        Ok(Iterations {
+            index_type,
+            step,
+            span,
+            counters: counters.peekable(),
+            chunks,
+            index: 0,
+        })
+    }
+
+    ///
+    /// Enforces up to `limit` of the iterations that are left, adding their results to `results`.
+    /// Returns `true` if there are iterations left after them.
+    ///
+    pub(crate) fn enforce_iterations<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        indicator: &Boolean,
+        statement: &IterationStatement<'a>,
+        iterations: &mut Iterations,
+        limit: usize,
+        results: &mut Vec<IndicatorAndConstrainedValue<'a, F, G>>,
+    ) -> StatementResult<bool> {
+        let span = &iterations.span;
+        for _ in 0..limit {
+            let i = match iterations.counters.next() {
+                Some(i) => i,
+                None => break,
+            };
+            let index = iterations.index;
+            iterations.index += 1;
 
-        for i in iter {
             // Store index in current function scope.
             // For loop scope is not implemented.
             let variable = statement.variable.borrow();
+            if index % ITERATION_CHUNK == 0 {
+                self.progress.item(index / ITERATION_CHUNK, variable.name.name.as_ref());
+            }
 
-            // todo: replace definition with var typed
-            self.store(
-                variable.id,
-                ConstrainedValue::Integer(Integer::U32(UInt32::constant(i as u32))),
-            );
+            self.checkpoint.tick()?;
+
+            let counter = ConstInt::parse(&iterations.index_type, &i.to_string(), span)
+                .map_err(|_| CompilerError::statement_loop_counter_overflow(i, &iterations.index_type, span))?;
+            self.store(variable.id, ConstrainedValue::Integer(Integer::new(&counter)));
 
             // Evaluate statements and possibly return early
+            self.enter_frame(cs, FrameKind::Iteration(index, iterations.step), span);
             let result = self.enforce_statement(
                 &mut cs.ns(|| format!("for loop iteration {} {}:{}", i, &span.line_start, &span.col_start)),
                 indicator,
                 statement.body.get(),
             )?;
+            self.exit_frame(cs);
 
             results.extend(result);
+
+            if self.unrolled_statements > self.limits.max_unrolled_statements {
+                return Err(CompilerError::unrolled_statement_limit_exceeded(
+                    self.unrolled_statements,
+                    self.limits.max_unrolled_statements,
+                    span,
+                )
+                .into());
+            }
+            self.check_circuit_limits(cs, span)?;
         }
 
-        Ok(results)
+        Ok(iterations.counters.peek().is_some())
     }
 }
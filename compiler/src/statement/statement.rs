@@ -16,7 +16,7 @@
 
 //! Enforces a statement in a compiled Leo program.
 
-use crate::{program::ConstrainedProgram, value::ConstrainedValue, GroupType};
+use crate::{program::ConstrainedProgram, value::ConstrainedValue, FrameKind, GroupType};
 use leo_asg::{Node, Statement};
 use leo_errors::{CompilerError, Result};
 
@@ -44,8 +44,16 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
     ) -> StatementResult<Vec<IndicatorAndConstrainedValue<'a, F, G>>> {
         let mut results = vec![];
         let span = statement.span().cloned().unwrap_or_default();
+        let is_block = matches!(statement, Statement::Block(_));
+        if !is_block {
+            self.unrolled_statements += 1;
+            self.checkpoint.tick()?;
+        }
         let mut cs = cs.ns(|| format!("statement {}:{}", span.line_start, span.col_start));
         let cs = &mut cs;
+        if !is_block {
+            self.enter_frame(cs, FrameKind::Statement, &span);
+        }
 
         match statement {
             Statement::Return(statement) => {
@@ -101,6 +109,10 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstrainedProgram<'a, F, G> {
             Statement::Empty(_) => (),
         };
 
+        if !is_block {
+            self.exit_frame(cs);
+        }
+
         Ok(results)
     }
 }
@@ -20,7 +20,9 @@ use std::{
 };
 
 use leo_asg::*;
-use leo_errors::Result;
+use leo_asg_passes::{BooleanSimplificationStatistics, EvaluationStatistics, InlineReason};
+use leo_ast::{AstPass, Edition};
+use leo_errors::{CancellationToken, LeoError, Result, Span};
 
 use leo_synthesizer::{CircuitSynthesizer, SerializedCircuit, SummarizedCircuit};
 use leo_test_framework::{
@@ -30,7 +32,14 @@ use leo_test_framework::{
 use serde_yaml::Value;
 use snarkvm_curves::{bls12_377::Bls12_377, edwards_bls12::Fq};
 
-use crate::{compiler::Compiler, targets::edwards_bls12::EdwardsGroupType, AstSnapshotOptions, Output};
+use crate::{
+    compile_source, compiler::Compiler, self_check, targets::edwards_bls12::EdwardsGroupType, verify_artifacts,
+    ArtifactSigner, AstSnapshotOptions, AuditReport, AuditSection, CompilationUnits, CompileOptions, CompileResult,
+    CompileSession, CompilerOptions, FeatureReport, FeatureStatus, FileName, FrameKind, InputSlots, IntegerWidening,
+    Interface, LogLevel, LogLine, MemorySink, NamingConvention, NamingOptions, Output, Probe, ProgramLimits, Progress,
+    ProgressReporter, Provenance, Signer, SourceProvider, StampCheck, StepResult, Verifier, INTERFACE_FORMAT,
+    OUTPUT_FORMAT, PROBES, PROVENANCE_FORMAT, SIGNATURE_MANIFEST,
+};
 use indexmap::IndexMap;
 
 pub type EdwardsTestCompiler = Compiler<'static, Fq, EdwardsGroupType>;
@@ -42,27 +51,81 @@ pub(crate) fn make_test_context() -> AsgContext<'static> {
     new_context(allocator)
 }
 
+///
+/// Returns a compiler of the program `test` at `compiler-test`, writing its artifacts into `output_dir`.
+///
+/// Every compiler of these tests is made here.
+///
+fn test_compiler_with(
+    context: AsgContext<'static>,
+    output_dir: PathBuf,
+    options: CompilerOptions,
+    snapshots: AstSnapshotOptions,
+) -> EdwardsTestCompiler {
+    EdwardsTestCompiler::new(
+        "test".to_string(),
+        "compiler-test".into(),
+        output_dir,
+        context,
+        Some(options),
+        IndexMap::new(),
+        Some(snapshots),
+    )
+}
+
+/// Returns a compiler of the program `test` with the given options, keeping its artifacts in memory.
+fn test_compiler(options: CompilerOptions) -> EdwardsTestCompiler {
+    test_compiler_with(
+        make_test_context(),
+        PathBuf::new(),
+        options,
+        AstSnapshotOptions::default(),
+    )
+    .with_output_writer(MemorySink::new())
+}
+
+/// Parses and checks the program with the given options.
+fn check_with_options(program_string: &str, options: CompilerOptions) -> Result<EdwardsTestCompiler> {
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_string(program_string)?;
+    Ok(compiler)
+}
+
+/// Synthesizes the circuit of a checked program for `input`.
+fn synthesize(compiler: &mut EdwardsTestCompiler, input: &str) -> Result<Output> {
+    compiler.parse_input(input, Path::new("input"), "", Path::new("state"))?;
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    compiler.compile_constraints(&mut cs)
+}
+
+/// The input of the programs whose `main` takes `a: u32` and returns a `u32`.
+const U32_INPUT: &str = "[main]\na: u32 = 2;\n\n[registers]\nr: u32 = 0;\n";
+
+/// Checks the program with the given options and synthesizes its circuit for [`U32_INPUT`].
+fn compile_with_options(program_string: &str, options: CompilerOptions) -> Result<Output> {
+    let mut compiler = check_with_options(program_string, options)?;
+    synthesize(&mut compiler, U32_INPUT)
+}
+
 fn new_compiler(path: PathBuf, theorem_options: Option<AstSnapshotOptions>) -> EdwardsTestCompiler {
-    let program_name = "test".to_string();
     let output_dir = PathBuf::from("/tmp/output/");
     fs::create_dir_all(output_dir.clone()).unwrap();
 
-    EdwardsTestCompiler::new(
-        program_name,
-        path,
-        output_dir,
+    test_compiler_with(
         make_test_context(),
-        None,
-        IndexMap::new(),
-        theorem_options,
+        output_dir,
+        CompilerOptions::default(),
+        theorem_options.unwrap_or_default(),
     )
+    .with_main_file(FileName::Real(path))
 }
 
 fn hash_file(path: &str) -> String {
     use sha2::{Digest, Sha256};
-    let mut file = fs::File::open(&Path::new(path)).unwrap();
+    // Hash the snapshot as serialized, so that the expectations do not change with the compiler version.
+    let bytes = leo_ast::AST_FORMAT.unstamp(&fs::read(&Path::new(path)).unwrap());
     let mut hasher = Sha256::new();
-    std::io::copy(&mut file, &mut hasher).unwrap();
+    hasher.update(&bytes);
     let hash = hasher.finalize();
 
     format!("{:x}", hash)
@@ -123,6 +186,7 @@ impl Namespace for CompileNamespace {
                 imports_resolved: true,
                 canonicalized: true,
                 type_inferenced: true,
+                input: true,
             }),
             cwd,
         )
@@ -251,3 +315,5013 @@ impl Runner for TestRunner {
 pub fn compiler_tests() {
     leo_test_framework::run_tests(&TestRunner, "compiler");
 }
+
+#[test]
+fn test_memory_sink_matches_directory_sink() {
+    let program_string = "function main(a: u32) -> u32 { return a + 1u32; }";
+    let options = AstSnapshotOptions {
+        spans_enabled: false,
+        initial: true,
+        imports_resolved: true,
+        canonicalized: true,
+        type_inferenced: true,
+        input: true,
+    };
+    let directory = tempfile::tempdir().unwrap();
+    let output_dir = directory.path().to_path_buf();
+
+    let new_compiler = || {
+        test_compiler_with(
+            make_test_context(),
+            output_dir.clone(),
+            CompilerOptions::default(),
+            options.clone(),
+        )
+    };
+
+    new_compiler().parse_program_from_string(program_string).unwrap();
+
+    let sink = MemorySink::new();
+    let mut compiler = new_compiler().with_output_writer(sink.clone());
+    compiler.parse_program_from_string(program_string).unwrap();
+
+    let artifacts = sink.artifacts();
+    let mut names: Vec<_> = artifacts.keys().cloned().collect();
+    let mut files: Vec<_> = fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    files.sort();
+    assert_eq!(names, files);
+
+    for (name, bytes) in artifacts {
+        assert_eq!(fs::read(output_dir.join(&name)).unwrap(), bytes);
+    }
+}
+
+fn compile_into_directory(output_dir: PathBuf, snapshots: bool) -> EdwardsTestCompiler {
+    let options = AstSnapshotOptions {
+        spans_enabled: false,
+        initial: snapshots,
+        imports_resolved: snapshots,
+        canonicalized: snapshots,
+        type_inferenced: snapshots,
+        input: snapshots,
+    };
+    let mut compiler = test_compiler_with(make_test_context(), output_dir, CompilerOptions::default(), options);
+    compiler
+        .parse_program_from_string("function main(a: u32) -> u32 { return a + 1u32; }")
+        .unwrap();
+    let output = synthesize(&mut compiler, U32_INPUT).unwrap();
+    assert_eq!(output.registers["r"].value, "3");
+    compiler
+}
+
+#[test]
+fn test_missing_output_directory_is_created() {
+    let root = tempfile::tempdir().unwrap();
+    let output_dir = root.path().join("nested").join("outputs");
+
+    let compiler = compile_into_directory(output_dir.clone(), true);
+    assert!(compiler.artifact_warnings().is_empty());
+    assert!(output_dir.join("initial_ast.json").is_file());
+    assert!(output_dir.join("type_inferenced_ast.json").is_file());
+}
+
+#[test]
+fn test_unwritable_output_directory_warns_once() {
+    // A directory below a file cannot be created, whatever the permissions of the process.
+    let root = tempfile::tempdir().unwrap();
+    let file = root.path().join("not_a_directory");
+    fs::write(&file, "not a directory").unwrap();
+
+    let compiler = compile_into_directory(file.join("outputs"), true);
+    let warnings = compiler.artifact_warnings();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    // The effective options are the first artifact of a build.
+    assert!(
+        warnings[0].starts_with("artifacts disabled: failed to write `effective_options.toml`"),
+        "{:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_check_without_artifacts_leaves_directory_alone() {
+    let root = tempfile::tempdir().unwrap();
+    let output_dir = root.path().join("outputs");
+
+    let compiler = compile_into_directory(output_dir.clone(), false);
+    assert!(compiler.artifact_warnings().is_empty());
+    assert!(!output_dir.exists());
+}
+
+fn compile_logs(program_string: &str) -> Vec<LogLine> {
+    let mut compiler = parse_program(program_string, None, None).unwrap();
+    synthesize(&mut compiler, U32_INPUT).unwrap().logs
+}
+
+fn messages(logs: &[LogLine]) -> Vec<&str> {
+    logs.iter().map(|line| line.message.as_str()).collect()
+}
+
+#[test]
+fn test_console_log_formats_values() {
+    let logs = compile_logs(
+        r#"
+circuit Point { x: u32, y: u32 }
+
+function main(a: u32) -> u32 {
+    let p = Point { x: 1, y: a };
+    console.log("{}", [1u8, 2, 3]);
+    console.log("{}", p);
+    console.log("{} {}", 'a', "hi");
+    console.log("{}", (a, true));
+    console.log("{{}} {}", a);
+    return a;
+}
+"#,
+    );
+
+    assert_eq!(
+        messages(&logs),
+        vec!["[1, 2, 3]", "Point { x: 1, y: 2 }", "'a' hi", "(2, true)", "{} 2",]
+    );
+    assert!(logs.iter().all(|line| line.level == LogLevel::Info));
+}
+
+#[test]
+fn test_console_log_strings() {
+    let logs = compile_logs(
+        r#"
+function main(a: u32) -> u32 {
+    const s = "leo";
+    const joined = "hello, " + s;
+    console.log("{} {} {}", s == "leo", s == "lea", s != "leo!");
+    console.log("{}!", joined);
+    console.log("{}", [s, s]);
+    return a;
+}
+"#,
+    );
+
+    assert_eq!(
+        messages(&logs),
+        vec!["true false true", "hello, leo!", "[\"leo\", \"leo\"]"]
+    );
+}
+
+#[test]
+fn test_console_log_order_and_levels() {
+    let logs = compile_logs(
+        r#"
+function main(a: u32) -> u32 {
+    console.log("first");
+    let b = a + 1;
+    console.error("second {}", b);
+    console.log("third");
+    return b;
+}
+"#,
+    );
+
+    assert_eq!(
+        logs,
+        vec![
+            LogLine::new(LogLevel::Info, "first".to_string()),
+            LogLine::new(LogLevel::Error, "second 3".to_string()),
+            LogLine::new(LogLevel::Info, "third".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_console_log_suppressed_in_untaken_branch() {
+    let logs = compile_logs(
+        r#"
+function main(a: u32) -> u32 {
+    if a == 2 {
+        console.log("taken");
+    } else {
+        console.log("not taken");
+    }
+    return a;
+}
+"#,
+    );
+
+    assert_eq!(messages(&logs), vec!["taken"]);
+}
+
+fn compile_with_limits(program_string: &str, limits: ProgramLimits) -> Result<Output> {
+    let options = CompilerOptions {
+        limits,
+        ..Default::default()
+    };
+    compile_with_options(program_string, options)
+}
+
+const UNROLLING_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0..10 {
+        b += 1;
+        b *= 1;
+    }
+    return b;
+}
+"#;
+
+#[test]
+fn test_unrolled_statement_limit() {
+    let limits = ProgramLimits {
+        max_unrolled_statements: 10,
+        ..Default::default()
+    };
+    let error = compile_with_limits(UNROLLING_PROGRAM, limits).unwrap_err();
+    assert_eq!(error.error_code(), "ECMP0376095");
+    // Reported at the loop.
+    assert!(error.to_string().contains("compiler-test:4:5"));
+
+    let limits = ProgramLimits {
+        max_unrolled_statements: 100,
+        ..Default::default()
+    };
+    assert!(compile_with_limits(UNROLLING_PROGRAM, limits).is_ok());
+}
+
+const PROVENANCE_PROGRAM: &str = r#"
+function double(x: u32) -> u32 {
+    return x + x;
+}
+
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0..3 {
+        b = double(b);
+    }
+    return b;
+}
+"#;
+
+fn provenance_of(program_string: &str) -> Provenance {
+    let options = CompileOptions {
+        compiler: CompilerOptions {
+            record_provenance: true,
+            ..Default::default()
+        },
+        input: Some(U32_INPUT.to_string()),
+        ..Default::default()
+    };
+    let result = compile_source(program_string, options);
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+
+    let artifact = PROVENANCE_FORMAT.unstamp(&result.artifacts["main.provenance.json"]);
+    let provenance: Provenance = serde_json::from_slice(&artifact).unwrap();
+    assert_eq!(Some(provenance.constraints), result.statistics.constraints);
+    provenance
+}
+
+#[test]
+fn test_provenance_of_inlined_unrolled_instruction() {
+    let provenance = provenance_of(PROVENANCE_PROGRAM);
+
+    // Every iteration synthesizes the addition in `double` once.
+    let mut iterations = vec![];
+    for index in 0..provenance.constraints {
+        let frames = provenance.provenance(index);
+        if frames.first().map(|frame| frame.span.line_start) != Some(3) {
+            continue;
+        }
+        let kinds = frames.iter().map(|frame| frame.kind).collect::<Vec<_>>();
+        let lines = frames.iter().map(|frame| frame.span.line_start).collect::<Vec<_>>();
+        assert_eq!(lines, vec![3, 9, 9, 8, 8]);
+        match kinds[..] {
+            [FrameKind::Statement, FrameKind::Call, FrameKind::Statement, FrameKind::Iteration(iteration, 1), FrameKind::Statement] => {
+                iterations.push(iteration)
+            }
+            _ => panic!("unexpected frames {:?}", kinds),
+        }
+        assert_eq!(
+            frames[2].to_string(),
+            format!("statement at main.leo:9:{}", frames[2].span.col_start)
+        );
+    }
+    iterations.dedup();
+    assert_eq!(iterations, vec![0, 1, 2]);
+}
+
+#[test]
+fn test_spans_to_instructions_cover_statement_once() {
+    let provenance = provenance_of(PROVENANCE_PROGRAM);
+
+    for frame in provenance.frames.iter() {
+        let ranges = provenance.spans_to_instructions(&frame.span);
+        for pair in ranges.windows(2) {
+            assert!(pair[0].end < pair[1].start);
+        }
+        for index in 0..provenance.constraints {
+            let covered = ranges.iter().filter(|range| range.contains(&index)).count();
+            let expected = provenance
+                .provenance(index)
+                .iter()
+                .any(|enclosing| enclosing.span == frame.span);
+            assert_eq!(covered, expected as usize);
+        }
+    }
+
+    // The unrolled loop is found by the span of its iterations.
+    let loop_span = &provenance
+        .frames
+        .iter()
+        .find(|frame| frame.kind == FrameKind::Iteration(0, 1))
+        .unwrap()
+        .span;
+    assert!(!provenance.spans_to_instructions(loop_span).is_empty());
+}
+
+#[test]
+fn test_constraint_limit() {
+    // A straight-line fixture of many additions.
+    let mut program_string = String::from("function main(a: u32) -> u32 {\n    let b = a;\n");
+    for _ in 0..50 {
+        program_string.push_str("    b = b + a;\n");
+    }
+    program_string.push_str("    return b;\n}\n");
+
+    let limits = ProgramLimits {
+        max_constraints: 1000,
+        ..Default::default()
+    };
+    let error = compile_with_limits(&program_string, limits).unwrap_err();
+    assert_eq!(error.error_code(), "ECMP0376096");
+    assert!(error.to_string().contains("max_constraints"));
+
+    assert!(compile_with_limits(&program_string, ProgramLimits::default()).is_ok());
+}
+
+#[test]
+fn test_function_limit() {
+    let program_string = r#"
+function one() -> u32 { return 1; }
+function two() -> u32 { return 2; }
+function main(a: u32) -> u32 { return a + one() + two(); }
+"#;
+    let limits = ProgramLimits {
+        max_functions: 2,
+        ..Default::default()
+    };
+    let error = compile_with_limits(program_string, limits).unwrap_err();
+    assert_eq!(error.error_code(), "ECMP0376097");
+}
+
+#[test]
+fn test_multiple_inputs() {
+    let sink = MemorySink::new();
+    let mut compiler = test_compiler(CompilerOptions::default()).with_output_writer(sink.clone());
+    compiler
+        .parse_program_from_string("function main(a: u32) -> u32 { return a + 1; }")
+        .unwrap();
+
+    let registers = "\n[registers]\nr: u32 = 0;\n";
+    for (label, main) in [
+        ("one", "a: u32 = 1;"),
+        ("two", "a: u32 = 2;"),
+        ("bad", "a: bool = true;"),
+    ]
+    .iter()
+    {
+        compiler
+            .add_input(
+                label,
+                &format!("[main]\n{}\n{}", main, registers),
+                Path::new("input"),
+                "",
+                Path::new("state"),
+            )
+            .unwrap();
+    }
+    assert_eq!(compiler.input_labels().collect::<Vec<_>>(), vec!["one", "two", "bad"]);
+
+    let errors = compiler.validate_inputs();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "bad");
+
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    let one = compiler.compile_constraints_with_input("one", &mut cs).unwrap();
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    let two = compiler.compile_constraints_with_input("two", &mut cs).unwrap();
+    assert_eq!(one.registers["r"].value, "2");
+    assert_eq!(two.registers["r"].value, "3");
+    assert_eq!(
+        OUTPUT_FORMAT.unstamp(&sink.get("inputs/one/test.out").unwrap()),
+        one.to_string().as_bytes()
+    );
+    assert_eq!(
+        OUTPUT_FORMAT.unstamp(&sink.get("inputs/two/test.out").unwrap()),
+        two.to_string().as_bytes()
+    );
+
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    assert!(compiler.compile_constraints_with_input("missing", &mut cs).is_err());
+}
+
+const VERIFIED_PROGRAM: &str = "function main(a: u32) -> u32 {\n    return a + 1;\n}\n";
+
+fn verified_options() -> CompilerOptions {
+    CompilerOptions {
+        renaming_enabled: true,
+        ..Default::default()
+    }
+}
+
+/// Builds the verified program with the verified options into `output_dir`, returning the path of its rename map.
+fn verified_artifact(output_dir: &Path) -> PathBuf {
+    let mut compiler = test_compiler_with(
+        make_test_context(),
+        output_dir.to_path_buf(),
+        verified_options(),
+        AstSnapshotOptions::default(),
+    );
+    compiler.parse_program_from_string(VERIFIED_PROGRAM).unwrap();
+    output_dir.join("rename_map.json")
+}
+
+#[test]
+fn test_verify_artifact_after_identical_rebuild() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let artifact = verified_artifact(output_dir.path());
+
+    let rebuild = check_with_options(VERIFIED_PROGRAM, verified_options()).unwrap();
+    let report = rebuild.verify_artifact(&artifact).unwrap();
+    assert!(report.is_reproduced(), "{}", report);
+    assert_eq!(report.version, StampCheck::Matches);
+    assert_eq!(report.source, StampCheck::Matches);
+    assert_eq!(report.options, StampCheck::Matches);
+
+    // Line endings and trailing whitespace are normalized away.
+    let reformatted = VERIFIED_PROGRAM.replace('\n', "  \r\n");
+    let rebuild = check_with_options(&reformatted, verified_options()).unwrap();
+    assert!(rebuild.verify_artifact(&artifact).unwrap().is_reproduced());
+}
+
+#[test]
+fn test_verify_artifact_flags_source_edit() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let artifact = verified_artifact(output_dir.path());
+
+    let edited = VERIFIED_PROGRAM.replace("a + 1", "a + 2");
+    let rebuild = check_with_options(&edited, verified_options()).unwrap();
+    let report = rebuild.verify_artifact(&artifact).unwrap();
+    assert!(!report.is_reproduced());
+    assert!(report.source.is_changed());
+    assert_eq!(report.options, StampCheck::Matches);
+    assert_eq!(report.version, StampCheck::Matches);
+}
+
+#[test]
+fn test_verify_artifact_flags_options_change() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let artifact = verified_artifact(output_dir.path());
+
+    let options = CompilerOptions {
+        constant_folding_enabled: false,
+        ..verified_options()
+    };
+    let rebuild = check_with_options(VERIFIED_PROGRAM, options).unwrap();
+    let report = rebuild.verify_artifact(&artifact).unwrap();
+    assert!(!report.is_reproduced());
+    assert!(report.options.is_changed());
+    assert_eq!(report.source, StampCheck::Matches);
+    assert_eq!(report.version, StampCheck::Matches);
+    assert!(report.to_string().contains("options: changed from"));
+    assert!(report.to_string().contains("source: matches"));
+}
+
+#[test]
+fn test_options_digest_ignores_progress() {
+    let options = CompilerOptions {
+        progress: Progress::new(std::sync::Arc::new(RecordingReporter::default())),
+        ..verified_options()
+    };
+    assert_eq!(options.digest(), verified_options().digest());
+    assert_ne!(CompilerOptions::default().digest(), verified_options().digest());
+}
+
+const RECOVERED_INPUT_PROGRAM: &str = r#"
+function main(a: u32, b: bool, c: u8, const d: u32) -> u32 {
+    return a + d;
+}
+"#;
+
+fn input_errors(input_string: &str) -> Vec<LeoError> {
+    let mut compiler = test_compiler(CompilerOptions::default());
+    compiler.parse_program_from_string(RECOVERED_INPUT_PROGRAM).unwrap();
+    compiler.parse_input_recovering(input_string, Path::new("input"), "", Path::new("state"))
+}
+
+#[test]
+fn test_input_recovery_reports_every_malformed_entry() {
+    let input_string =
+        "[main]\na: u32 = 1u8;\nb: bool = ;\nc: u8 = 3;\n\n[constants]\nd: u32 = 4u32 5;\n\n[registers]\nr: u32 = 0;\n";
+    let errors = input_errors(input_string);
+
+    // One error for each malformed entry, and none for the parameters they were meant for.
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors.iter().all(|error| !error.to_string().contains("not found")));
+
+    let mut compiler = test_compiler(CompilerOptions::default());
+    compiler.parse_program_from_string(RECOVERED_INPUT_PROGRAM).unwrap();
+    let error = compiler
+        .parse_input(input_string, Path::new("input"), "", Path::new("state"))
+        .unwrap_err();
+    assert!(error.to_string().contains("the program input has 3 errors"));
+}
+
+#[test]
+fn test_input_recovery_reports_sections() {
+    let input_string = "[main]\na: u32 = 1;\n\n[mian]\nb: bool = true;\nc: u8 = 3;\n\n[constants]\nd: u32 = 4;\n";
+    let errors = input_errors(input_string);
+
+    // The unknown section is reported, and its entries are not reported missing.
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors.iter().all(|error| !error.to_string().contains("not found")));
+
+    // Entries that are left out entirely are still reported missing.
+    let errors = input_errors("[main]\na: u32 = 1;\nb: bool = true;\n\n[constants]\nd: u32 = 4;\n");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("input c not found"));
+}
+
+#[test]
+fn test_input_recovery_leaves_valid_input_unchanged() {
+    let input_string =
+        "[main]\na: u32 = 1;\nb: bool = true;\nc: u8 = 3;\n\n[constants]\nd: u32 = 4;\n\n[registers]\nr: u32 = 0;\n";
+    assert!(input_errors(input_string).is_empty());
+
+    let mut recovered = leo_ast::Input::new();
+    let errors = recovered.parse_input_recovering(leo_input::LeoInputParser::parse_file(input_string).unwrap());
+    assert!(errors.is_empty());
+    let mut parsed = leo_ast::Input::new();
+    parsed
+        .parse_input(leo_input::LeoInputParser::parse_file(input_string).unwrap())
+        .unwrap();
+    assert!(recovered == parsed);
+    assert_eq!(leo_input::LeoInputParser::recover(input_string).source, input_string);
+}
+
+fn resolve_main_inputs(
+    program_string: &str,
+    input_string: &str,
+) -> Result<IndexMap<String, Option<ConstValue<'static>>>> {
+    resolve_edited_main_inputs(program_string, input_string, |_| ())
+}
+
+/// Resolves the input against the expected input of the canonicalized program, after `edit` changes it.
+fn resolve_edited_main_inputs(
+    program_string: &str,
+    input_string: &str,
+    edit: impl FnOnce(&mut Vec<leo_ast::FunctionInput>),
+) -> Result<IndexMap<String, Option<ConstValue<'static>>>> {
+    let ast = leo_parser::parse_ast("compiler-test", program_string)?;
+    let mut program = leo_ast_passes::Canonicalizer::do_pass(ast.into_repr())?.into_repr();
+    edit(&mut program.expected_input);
+    let asg = Asg::new(make_test_context(), &program)?.into_repr();
+    let mut input = leo_ast::Input::new();
+    input.parse_input(leo_input::LeoInputParser::parse_file(input_string)?)?;
+
+    let main = *asg.functions.get("main").unwrap();
+    Ok(crate::ResolvedInputs::resolve(&asg, main, &program.expected_input, &input)?.main)
+}
+
+#[test]
+fn test_resolve_main_inputs() {
+    let resolved = resolve_main_inputs(
+        "function main(a: u8, const b: [u16; 2], c: (bool, field)) {}",
+        "[main]\na: u8 = 7;\nc: (bool, field) = (true, 5);\n\n[constants]\nb: [u16; 2] = [1, 2];\n",
+    )
+    .unwrap();
+
+    assert_eq!(resolved.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert!(matches!(resolved["a"], Some(ConstValue::Int(ConstInt::U8(7)))));
+    match resolved["b"].as_ref().unwrap() {
+        ConstValue::Array(values) => assert!(matches!(
+            &values[..],
+            [ConstValue::Int(ConstInt::U16(1)), ConstValue::Int(ConstInt::U16(2))]
+        )),
+        _ => panic!("expected an array"),
+    }
+    match resolved["c"].as_ref().unwrap() {
+        ConstValue::Tuple(values) => {
+            assert!(matches!(values[0], ConstValue::Boolean(true)));
+            assert!(values[1] == ConstValue::Field(5.into()));
+        }
+        _ => panic!("expected a tuple"),
+    }
+}
+
+#[test]
+fn test_resolve_main_inputs_from_expected_input() {
+    let program_string = "type Amount = u64;\nfunction main(a: Amount, b: bool) {}";
+    let input_string = "[main]\na: u64 = 7;\nb: bool = true;\n";
+
+    let resolved = resolve_main_inputs(program_string, input_string).unwrap();
+    assert!(matches!(resolved["a"], Some(ConstValue::Int(ConstInt::U64(7)))));
+
+    // A parameter of `main` missing from the expected input means the program is stale.
+    let error = resolve_edited_main_inputs(program_string, input_string, |expected_input| {
+        expected_input.pop();
+    })
+    .err()
+    .unwrap();
+    assert_eq!(error.error_code(), "EAST0372022");
+}
+
+const SOURCE_PROGRAM: &str = r#"
+circuit Point {
+    x: u32,
+}
+
+function main(a: u32) -> u32 {
+    console.log("a is {}", a);
+    let p = Point { x: a };
+    return p.x + 1;
+}
+"#;
+
+fn source_options() -> CompileOptions {
+    CompileOptions {
+        input: Some("[main]\na: u32 = 2;\n\n[registers]\nr: u32 = 0;\n".to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_compile_source_valid() {
+    let result = compile_source(SOURCE_PROGRAM, source_options());
+
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.ast.unwrap().contains(r#"\"name\":\"Point\""#));
+    assert_eq!(
+        result.symbols,
+        vec!["circuit Point", "member Point::x", "function main"]
+    );
+    assert!(result.output.unwrap().contains("r: u32 = 3;"));
+    assert_eq!(messages(&result.logs), vec!["a is 2"]);
+    assert_eq!(result.statistics.functions, 1);
+    assert_eq!(result.statistics.circuits, 1);
+    assert!(result.statistics.constraints.unwrap() > 0);
+}
+
+#[test]
+fn test_compile_source_without_input() {
+    let result = compile_source(SOURCE_PROGRAM, CompileOptions::default());
+
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.ast.is_some());
+    assert!(result.output.is_none());
+    assert!(result.statistics.constraints.is_none());
+}
+
+#[test]
+fn test_compile_source_invalid() {
+    let result = compile_source("function main() -> u32 { return true; }", source_options());
+
+    assert_eq!(result.diagnostics.len(), 1);
+    assert!(result.ast.is_none());
+    assert!(result.output.is_none());
+
+    let result = compile_source("function main( {", CompileOptions::default());
+    assert_eq!(result.diagnostics.len(), 1);
+}
+
+#[test]
+fn test_compile_source_recovers_from_syntax_errors() {
+    let source = r#"
+function greet() {
+    console.log("hello);
+}
+
+function main() -> u8 {
+    return 1u8;
+
+function other() -> u8 {
+    return 2u8;
+}
+"#;
+    let result = compile_source(source, source_options());
+
+    assert_eq!(result.diagnostics.len(), 2, "{:?}", result.diagnostics);
+    assert!(result.diagnostics[0].contains("EPAR0370022"));
+    assert!(result.diagnostics[1].contains("EPAR0370024"));
+    assert_eq!(
+        result.symbols,
+        vec!["function greet", "function main", "function other"]
+    );
+    assert!(result.output.is_none());
+}
+
+#[test]
+fn test_artifacts_are_stamped() {
+    let options = CompileOptions {
+        compiler: CompilerOptions {
+            renaming_enabled: true,
+            emit_dot: true,
+            ..Default::default()
+        },
+        ast_snapshots: AstSnapshotOptions {
+            spans_enabled: false,
+            initial: true,
+            imports_resolved: true,
+            canonicalized: true,
+            type_inferenced: true,
+            input: true,
+        },
+        ..source_options()
+    };
+    let result = compile_source(SOURCE_PROGRAM, options);
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+
+    let artifact = |name: &str| String::from_utf8(result.artifacts[name].clone()).unwrap();
+    let assert_stamped = |name: &str, format: &leo_ast::ArtifactFormat| {
+        let stamp = leo_ast::ArtifactStamp::read(artifact(name).as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (stamp.kind, stamp.format, stamp.compiler),
+            (
+                format.kind.to_string(),
+                format.version,
+                leo_ast::COMPILER_VERSION.to_string()
+            ),
+            "{}",
+            name
+        );
+        assert!(stamp.options.is_some(), "{}", name);
+    };
+    for name in [
+        "initial_ast.json",
+        "imports_resolved_ast.json",
+        "canonicalization_ast.json",
+        "type_inferenced_ast.json",
+    ]
+    .iter()
+    {
+        assert!(artifact(name).starts_with("{\n  \"leo_artifact\": "), "{}", name);
+        assert_stamped(name, &leo_ast::AST_FORMAT);
+    }
+    assert!(artifact("rename_map.json").starts_with("{\n  \"leo_artifact\": "));
+    assert_stamped("rename_map.json", &leo_ast_passes::RENAME_MAP_FORMAT);
+    assert_stamped("input_ast.json", &leo_ast::INPUT_AST_FORMAT);
+    assert!(artifact("main.dot").starts_with("// leo_artifact: "));
+    assert_stamped("main.dot", &DATAFLOW_FORMAT);
+    assert!(artifact("main.dot").contains("digraph"));
+}
+
+#[test]
+fn test_compile_source_unwritable_output_directory() {
+    let root = tempfile::tempdir().unwrap();
+    let file = root.path().join("not_a_directory");
+    fs::write(&file, "not a directory").unwrap();
+    let options = CompileOptions {
+        ast_snapshots: AstSnapshotOptions {
+            initial: true,
+            ..Default::default()
+        },
+        output_directory: Some(file.join("outputs")),
+        ..source_options()
+    };
+
+    let result = compile_source(SOURCE_PROGRAM, options);
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: u32 = 3;"));
+    assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+}
+
+#[test]
+fn test_compile_source_repeated() {
+    for i in 0..8u32 {
+        let options = CompileOptions {
+            input: Some(format!("[main]\na: u32 = {};\n\n[registers]\nr: u32 = 0;\n", i)),
+            ..Default::default()
+        };
+        let result = compile_source(SOURCE_PROGRAM, options);
+        assert!(result.output.unwrap().contains(&format!("r: u32 = {};", i + 1)));
+    }
+}
+
+#[test]
+fn test_compile_source_threads() {
+    let handles = (0..4)
+        .map(|_| std::thread::spawn(|| compile_source(SOURCE_PROGRAM, source_options())))
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let result = handle.join().unwrap();
+        assert!(result.is_ok(), "{:?}", result.diagnostics);
+        assert!(result.output.unwrap().contains("r: u32 = 3;"));
+    }
+}
+
+/// Runs the pass `P` on the ASG of the program, returning the line and message of every warning it reports.
+fn pass_warnings<P: AsgPass<'static>>(program_string: &str) -> Vec<(usize, String)> {
+    let context = make_test_context();
+    let ast = leo_parser::parse_ast("compiler-test", program_string).unwrap();
+    let asg = Asg::new(context, ast.as_repr()).unwrap().into_repr();
+    P::do_pass(asg).unwrap();
+
+    context
+        .take_warnings()
+        .into_iter()
+        .map(|warning| (warning.span.line_start, warning.message))
+        .collect()
+}
+
+#[test]
+fn test_range_comparison_warnings() {
+    let warnings = pass_warnings::<leo_asg_passes::RangeComparison>(
+        r#"
+function main(a: u8, b: i8, c: u32) {
+    let x = a >= 0u8;
+    let y = (a as u16) < 256u16;
+    let z = (b as i16) == 200i16;
+    let w = 5000000000u64 != (c as u64);
+}
+"#,
+    );
+
+    let always = |result: &str| {
+        format!(
+            "comparison is always `{}` because of the ranges of its operand types",
+            result
+        )
+    };
+    assert_eq!(
+        warnings,
+        vec![
+            (3, always("true")),
+            (4, always("true")),
+            (5, always("false")),
+            (6, always("true")),
+        ]
+    );
+}
+
+#[test]
+fn test_range_comparison_boundaries() {
+    let warnings = pass_warnings::<leo_asg_passes::RangeComparison>(
+        r#"
+function main(a: u8, b: i8, c: u16) {
+    let x = a < 255u8;
+    let y = b >= 0i8;
+    let z = (a as u16) < 255u16;
+    let w = (c as u8) == 3u8;
+    let v = 1u8 < 2u8;
+}
+"#,
+    );
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+const UNCONSTRUCTIBLE_PROGRAM: &str = r#"
+circuit Token {
+    _owner: u32,
+    _amount: u64,
+}
+
+function transfer(token: Token, to: u32) -> u32 {
+    return to;
+}
+
+function main(a: u32) -> u32 {
+    return a;
+}
+"#;
+
+#[test]
+fn test_constructibility_warns_about_private_circuit() {
+    let warnings = pass_warnings::<leo_asg_passes::Constructibility>(UNCONSTRUCTIBLE_PROGRAM);
+
+    assert_eq!(
+        warnings,
+        vec![(
+            7,
+            "parameter `token` of function `transfer` takes circuit `Token`, which other programs cannot construct: \
+             its members `_owner`, `_amount` are private and no public function returns it"
+                .to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_constructibility_accepts_public_factory() {
+    let program = format!(
+        "{}{}",
+        UNCONSTRUCTIBLE_PROGRAM,
+        r#"
+function mint(owner: u32, amount: u64) -> Token {
+    return Token { _owner: owner, _amount: amount };
+}
+"#
+    );
+    assert!(pass_warnings::<leo_asg_passes::Constructibility>(&program).is_empty());
+
+    let allowed = UNCONSTRUCTIBLE_PROGRAM.replace(
+        "function transfer",
+        "@allow_unconstructible_parameters\nfunction transfer",
+    );
+    assert!(pass_warnings::<leo_asg_passes::Constructibility>(&allowed).is_empty());
+}
+
+#[test]
+fn test_constructibility_ignores_entrypoint() {
+    let warnings = pass_warnings::<leo_asg_passes::Constructibility>(
+        r#"
+circuit Token {
+    _owner: u32,
+}
+
+function main(token: Token, a: u32) -> u32 {
+    return a;
+}
+"#,
+    );
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+const RANGE_FOLDING_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    if (a as u64) < 5000000000u64 {
+        b += 1;
+    } else {
+        b *= a;
+    }
+    return b;
+}
+"#;
+
+fn range_folding_options(fold_range_comparisons: bool) -> CompileOptions {
+    CompileOptions {
+        compiler: CompilerOptions {
+            fold_range_comparisons,
+            ..Default::default()
+        },
+        ..source_options()
+    }
+}
+
+#[test]
+fn test_range_comparison_folding() {
+    let unfolded = compile_source(RANGE_FOLDING_PROGRAM, range_folding_options(false));
+    let folded = compile_source(RANGE_FOLDING_PROGRAM, range_folding_options(true));
+
+    assert!(unfolded.is_ok(), "{:?}", unfolded.diagnostics);
+    assert!(folded.is_ok(), "{:?}", folded.diagnostics);
+    assert!(unfolded.output.unwrap().contains("r: u32 = 3;"));
+    assert!(folded.output.unwrap().contains("r: u32 = 3;"));
+    assert!(folded.statistics.constraints.unwrap() < unfolded.statistics.constraints.unwrap());
+}
+
+#[test]
+fn test_compile_built_program() {
+    use leo_ast::{builder::*, BinaryOperation, IntegerType, Type};
+
+    let u32_type = || Type::IntegerType(IntegerType::U32);
+    let program = program()
+        .circuit(circuit::new("Counter").variable("count", u32_type()))
+        .function(func::new("main").input("a", u32_type()).returns(u32_type()).body(vec![
+            stmt::let_("c", None, expr::circuit("Counter", vec![("count", expr::ident("a"))])),
+            stmt::return_(expr::binary(
+                expr::member(expr::ident("c"), "count"),
+                BinaryOperation::Add,
+                expr::int(1, IntegerType::U32),
+            )),
+        ]));
+
+    let options = CompilerOptions {
+        verify_ast: true,
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_ast(program.into_ast()).unwrap();
+    let output = synthesize(&mut compiler, U32_INPUT).unwrap();
+    assert_eq!(output.registers["r"].value, "3");
+}
+
+fn compound_index_program(assignment: &str) -> String {
+    format!(
+        r#"
+function main(a: [u32; 4], i: u32) -> u32 {{
+    let b = a;
+    {}
+    return b[0] + b[1] + b[2] + b[3];
+}}
+"#,
+        assignment
+    )
+}
+
+#[test]
+fn test_compound_assignment_computes_index_once() {
+    let options = || CompileOptions {
+        input: Some("[main]\na: [u32; 4] = [1, 2, 3, 4];\ni: u32 = 1;\n\n[registers]\nr: u32 = 0;\n".to_string()),
+        ..Default::default()
+    };
+    let compound = compile_source(&compound_index_program("b[i + 1] += 5;"), options());
+    let manual = compile_source(
+        &compound_index_program("let j = i + 1;\n    b[j] = b[j] + 5;"),
+        options(),
+    );
+
+    assert!(compound.is_ok(), "{:?}", compound.diagnostics);
+    assert!(manual.is_ok(), "{:?}", manual.diagnostics);
+    assert!(compound.output.unwrap().contains("r: u32 = 15;"));
+    assert!(manual.output.unwrap().contains("r: u32 = 15;"));
+    assert_eq!(compound.statistics.constraints, manual.statistics.constraints);
+}
+
+#[test]
+fn test_folded_field_and_group_constants_match_circuit() {
+    // Groups allocated in the circuit cannot be compared, so the group is a constant parameter,
+    // which is only known once the circuit is built.
+    let program = r#"
+function main(a: field, b: field, const g: group) -> bool {
+    const QUOTIENT: field = 3field / 4field - 2field * 5field;
+    const DOUBLE: group = 1group + 1group - 0group;
+    let triple = 2group + g;
+    return QUOTIENT == a / b - 2field * 5field && DOUBLE == g + g && -DOUBLE + triple == g;
+}
+"#;
+    let options = CompileOptions {
+        input: Some(
+            "[main]\na: field = 3;\nb: field = 4;\n\n[constants]\ng: group = 1group;\n\n[registers]\nr: bool = false;\n".to_string(),
+        ),
+        ..Default::default()
+    };
+    let result = compile_source(program, options);
+
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: bool = true;"));
+}
+
+fn fold_calls(program_string: &str, fuel: usize) -> Vec<Option<ConstValue<'static>>> {
+    fold_calls_with_statistics(program_string, fuel).0
+}
+
+fn fold_calls_with_statistics(
+    program_string: &str,
+    fuel: usize,
+) -> (Vec<Option<ConstValue<'static>>>, EvaluationStatistics) {
+    let ast = leo_parser::parse_ast("compiler-test", program_string).unwrap();
+    let asg = Asg::new(make_test_context(), ast.as_repr()).unwrap().into_repr();
+    let (asg, statistics) = leo_asg_passes::ConstantFolding::do_pass_with_statistics(asg, fuel).unwrap();
+
+    let main = *asg.functions.get("main").unwrap();
+    let values = match main.body.get().unwrap() {
+        Statement::Block(block) => block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement.get() {
+                Statement::Definition(definition) => Some(match definition.value.get() {
+                    Expression::Constant(constant) => Some(constant.value.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => panic!("expected a block"),
+    };
+    (values, statistics)
+}
+
+#[test]
+fn test_static_call_through_alias_folded() {
+    let values = fold_calls(
+        r#"
+circuit Point {
+    x: u32,
+
+    function double(a: u32) -> u32 {
+        return a * 2;
+    }
+}
+
+type Vector = Point;
+type Offset = Vector;
+
+function main() {
+    let x = Offset::double(21);
+}
+"#,
+        leo_asg_passes::DEFAULT_CALL_FUEL,
+    );
+
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U32(42)))));
+}
+
+#[test]
+fn test_call_cache_recursive_fibonacci() {
+    // Without the cache, the naive recursion makes about 250,000 calls and runs out of fuel.
+    let (values, statistics) = fold_calls_with_statistics(
+        r#"
+function fib(n: u32) -> u32 {
+    if n < 2 {
+        return n;
+    } else {
+        return fib(n - 1) + fib(n - 2);
+    }
+}
+
+function main() {
+    let x = fib(25);
+}
+"#,
+        leo_asg_passes::DEFAULT_CALL_FUEL,
+    );
+
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U32(75025)))));
+    assert_eq!(
+        statistics,
+        EvaluationStatistics {
+            hits: 44,
+            misses: 49,
+            entries: 49,
+        }
+    );
+}
+
+#[test]
+fn test_call_cache_distinct_environments() {
+    let (values, statistics) = fold_calls_with_statistics(
+        r#"
+function double(a: u32) -> u32 {
+    return a * 2;
+}
+
+function apply(a: u32) -> u32 {
+    return double(a) + double(a + 1);
+}
+
+function main() {
+    let x = apply(1);
+    let y = apply(2);
+    let z = apply(1);
+}
+"#,
+        leo_asg_passes::DEFAULT_CALL_FUEL,
+    );
+
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U32(6)))));
+    assert!(matches!(values[1], Some(ConstValue::Int(ConstInt::U32(10)))));
+    assert!(matches!(values[2], Some(ConstValue::Int(ConstInt::U32(6)))));
+    // The calls of `double` are only shared between the two calls of `apply(1)`.
+    assert_eq!(
+        statistics,
+        EvaluationStatistics {
+            hits: 2,
+            misses: 7,
+            entries: 7,
+        }
+    );
+
+    let result = compile_source(
+        "function double(a: u32) -> u32 {\n    return a * 2;\n}\n\nfunction main() {\n    let x = double(1);\n}\n",
+        CompileOptions::default(),
+    );
+    assert_eq!(
+        result.statistics.evaluation_cache,
+        Some(EvaluationStatistics {
+            hits: 0,
+            misses: 1,
+            entries: 1,
+        })
+    );
+}
+
+const PARAMETER_COPY_FUNCTIONS: &str = r#"
+circuit Point { x: u32 }
+
+function bump(values: [u32; 3], n: u32, p: Point) -> u32 {
+    values[0] += 10;
+    n += 1;
+    p.x += 1;
+    return values[0] + n + p.x;
+}
+
+function check(a: u32) -> u32 {
+    let values = [1u32, 2, 3];
+    let n = a + 3;
+    let p = Point { x: 7 };
+    let bumped = bump(values, n, p);
+    return bumped * 1000 + values[0] * 100 + n * 10 + p.x;
+}
+"#;
+
+#[test]
+fn test_parameters_are_copies() {
+    // The arguments, including the whole array and circuit, keep their values in the caller.
+    let expected = "25157";
+
+    let program_string = format!(
+        "{}\nfunction main(a: u32) -> u32 {{\n    return check(a);\n}}\n",
+        PARAMETER_COPY_FUNCTIONS
+    );
+    let output = compile_with_limits(&program_string, ProgramLimits::default()).unwrap();
+    assert_eq!(output.registers["r"].value, expected);
+
+    let program_string = format!(
+        "{}\nfunction main() {{\n    let x = check(2);\n}}\n",
+        PARAMETER_COPY_FUNCTIONS
+    );
+    let values = fold_calls(&program_string, leo_asg_passes::DEFAULT_CALL_FUEL);
+    match &values[0] {
+        Some(ConstValue::Int(value)) => assert_eq!(value.raw_value(), expected),
+        _ => panic!("the call was not evaluated"),
+    }
+}
+
+fn repeated_program(copies: usize) -> String {
+    let mut program = String::new();
+    for i in 0..copies {
+        program.push_str(&format!(
+            "function scale_{}(a: u32) -> u32 {{\n    let b = a * {};\n    return b + 1;\n}}\n\n",
+            i, i
+        ));
+    }
+    program.push_str("function main(a: u32) -> u32 {\n    return scale_0(a);\n}\n");
+    program
+}
+
+#[test]
+fn test_memory_statistics_grow_with_program() {
+    let sizes: Vec<usize> = [1, 2, 4, 8]
+        .iter()
+        .map(|copies| {
+            let result = compile_source(&repeated_program(*copies), CompileOptions::default());
+            assert!(result.is_ok(), "{:?}", result.diagnostics);
+            result.statistics.ast_bytes
+        })
+        .collect();
+
+    assert!(sizes.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", sizes);
+}
+
+#[test]
+fn test_memory_statistics_categories() {
+    let result = compile_source(&repeated_program(2), CompileOptions::default());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+
+    let statistics = &result.statistics;
+    assert!(statistics.ast_bytes > 0);
+    // `scale_0`, `scale_1`, `main`, `a` and `b`.
+    assert_eq!(statistics.symbols.count, 5);
+    assert_eq!(statistics.symbols.bytes, 7 + 7 + 4 + 1 + 1);
+    assert!(statistics.symbol_table_bytes.unwrap() > 0);
+
+    let rendered = statistics.to_string();
+    assert!(rendered.contains("approximate"));
+    assert!(rendered.contains("  ast: "));
+    assert!(rendered.contains("  symbols: 5 distinct names"));
+    assert!(rendered.contains("  symbol table: "));
+    assert!(rendered.contains("  peak resident set: "));
+}
+
+#[test]
+fn test_structure_statistics() {
+    let result = compile_source(&repeated_program(2), CompileOptions::default());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+
+    let metrics = &result.statistics.metrics;
+    assert_eq!(metrics.functions.len(), 3);
+    assert_eq!(metrics.functions["scale_1"].statements, 2);
+    assert_eq!(metrics.functions["scale_1"].expressions, 6);
+    assert_eq!(metrics.functions["main"].expressions, 3);
+    assert_eq!(metrics.statement_count(), 5);
+    assert_eq!(metrics.expression_count(), 15);
+
+    let rendered = result.statistics.to_string();
+    assert!(rendered.contains("statements: 5"));
+    assert!(rendered.contains("expressions: 15"));
+    assert!(rendered.contains("nesting: 2 expressions, 1 blocks"));
+}
+
+fn else_if_chain(arms: usize) -> String {
+    else_if_chain_with("x: u8", arms, |arm| format!("x == {}", arm))
+}
+
+/// Returns a chain of `arms` arms testing the given conditions of `x`, numbered from zero.
+fn else_if_chain_with(parameters: &str, arms: usize, condition: impl Fn(usize) -> String) -> String {
+    let mut chain = format!(
+        "function classify({}) -> u8 {{\n    if {} {{\n        return x + 50;\n    }}",
+        parameters,
+        condition(0)
+    );
+    for arm in 1..arms {
+        chain.push_str(&format!(
+            " else if {} {{\n        return x + {};\n    }}",
+            condition(arm),
+            arm
+        ));
+    }
+    chain.push_str(" else {\n        return x / 2;\n    }\n}\n");
+    chain
+}
+
+fn else_if_chain_options(a: u8) -> CompileOptions {
+    CompileOptions {
+        input: Some(format!("[main]\na: u8 = {};\n\n[registers]\nr: u8 = 0;\n", a)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_else_if_chain_constraints_linear() {
+    let constraints: Vec<usize> = [4, 8, 16]
+        .iter()
+        .map(|arms| {
+            let program = format!(
+                "{}\nfunction main(a: u8) -> u8 {{\n    return classify(a);\n}}\n",
+                else_if_chain(*arms)
+            );
+            let result = compile_source(&program, else_if_chain_options(0));
+            assert!(result.is_ok(), "{:?}", result.diagnostics);
+            result.statistics.constraints.unwrap()
+        })
+        .collect();
+
+    assert_eq!(
+        constraints[2] - constraints[1],
+        2 * (constraints[1] - constraints[0]),
+        "{:?}",
+        constraints
+    );
+}
+
+#[test]
+fn test_else_if_chain_matches_evaluator() {
+    let chain = else_if_chain(16);
+    let inputs: Vec<u8> = (0..16).chain(Some(200)).collect();
+
+    let calls: String = inputs
+        .iter()
+        .map(|a| format!("    let v{} = classify({}u8);\n", a, a))
+        .collect();
+    let expected = fold_calls(&format!("{}\nfunction main() {{\n{}}}\n", chain, calls), 100_000);
+    assert_eq!(expected.len(), inputs.len());
+
+    let program = format!(
+        "{}\nfunction main(a: u8) -> u8 {{\n    return classify(a);\n}}\n",
+        chain
+    );
+    for (a, expected) in inputs.iter().zip(expected) {
+        let expected = match expected {
+            Some(ConstValue::Int(ConstInt::U8(value))) => value,
+            _ => panic!("classify({}) was not evaluated", a),
+        };
+        let result = compile_source(&program, else_if_chain_options(*a));
+        assert!(result.is_ok(), "{:?}", result.diagnostics);
+        assert!(
+            result.output.unwrap().contains(&format!("r: u8 = {};", expected)),
+            "classify({}) should be {}",
+            a,
+            expected
+        );
+    }
+}
+
+fn boolean_simplification_options(a: u8, enabled: bool, simplify_boolean_expressions: bool) -> CompileOptions {
+    CompileOptions {
+        compiler: CompilerOptions {
+            simplify_boolean_expressions,
+            ..Default::default()
+        },
+        input: Some(format!(
+            "[main]\na: u8 = {};\nenabled: bool = {};\n\n[registers]\nr: u8 = 0;\n",
+            a, enabled
+        )),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_boolean_simplification_reduces_else_if_chain() {
+    // Every arm tests `enabled` twice, which factoring tests once.
+    let chain = else_if_chain_with("x: u8, enabled: bool", 16, |arm| {
+        format!("(enabled && x == {}) || (enabled && x == {})", arm, arm + 100)
+    });
+    let program = format!(
+        "{}\nfunction main(a: u8, enabled: bool) -> u8 {{\n    return classify(a, enabled);\n}}\n",
+        chain
+    );
+
+    for (a, enabled) in [(0, true), (7, true), (107, true), (200, true), (7, false)].iter() {
+        let plain = compile_source(&program, boolean_simplification_options(*a, *enabled, false));
+        let simplified = compile_source(&program, boolean_simplification_options(*a, *enabled, true));
+        assert!(plain.is_ok(), "{:?}", plain.diagnostics);
+        assert!(simplified.is_ok(), "{:?}", simplified.diagnostics);
+
+        assert_eq!(plain.output, simplified.output, "classify({}, {})", a, enabled);
+        assert!(simplified.statistics.constraints.unwrap() < plain.statistics.constraints.unwrap());
+        assert_eq!(plain.statistics.boolean_simplifications, None);
+        assert_eq!(
+            simplified.statistics.boolean_simplifications,
+            Some(BooleanSimplificationStatistics {
+                factorings: 16,
+                ..Default::default()
+            })
+        );
+    }
+}
+
+fn count_input_slots(program_string: &str, max_input_slots: usize) -> Result<InputSlots> {
+    let options = CompilerOptions {
+        limits: ProgramLimits {
+            max_input_slots,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_string(program_string)?;
+    Ok(compiler.input_slots().cloned().unwrap())
+}
+
+#[test]
+fn test_input_slots_within_limit() {
+    let program_string =
+        "function main(a: u32, b: [u8; 4], c: (bool, field), const d: [u8; 10]) -> u32 {\n    return a;\n}\n";
+
+    let slots = count_input_slots(program_string, 7).unwrap();
+    assert_eq!(slots.total, 7);
+    assert_eq!(
+        slots.parameters,
+        vec![("b".to_string(), 4), ("c".to_string(), 2), ("a".to_string(), 1)]
+    );
+
+    let result = compile_source(program_string, CompileOptions::default());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(result.statistics.input_slots, Some(7));
+    assert!(result.statistics.to_string().contains("input slots: 7"));
+}
+
+#[test]
+fn test_type_cache_statistics() {
+    let program_string = r#"
+circuit Point {
+    x: u32,
+    y: u32,
+}
+type Segment = [Point; 2];
+
+function length(s: Segment) -> u32 {
+    return s[1].x - s[0].x;
+}
+
+function main(a: Segment, b: Point, c: (Point, bool)) -> u32 {
+    let p: Point = b;
+    return length(a) + p.y;
+}
+"#;
+
+    let slots = count_input_slots(program_string, 100).unwrap();
+    assert_eq!(
+        slots.parameters,
+        vec![("a".to_string(), 4), ("c".to_string(), 3), ("b".to_string(), 2)]
+    );
+
+    let result = compile_source(program_string, CompileOptions::default());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    let cache = result.statistics.type_cache.unwrap();
+    // `Point` is sized once, then found in the cache for every later parameter and query.
+    assert_eq!(cache.size_misses, 1);
+    assert!(cache.size_hits > 0);
+    assert!(cache.identifier_hits > 0);
+    assert!(result.statistics.to_string().contains("type caches: "));
+}
+
+#[test]
+fn test_input_slots_limit_exceeded() {
+    let program_string = "function main(a: u32, big: [u8; 100000], b: [u8; 4]) {}\n";
+
+    let error = count_input_slots(program_string, 10_000).unwrap_err();
+    assert_eq!(error.error_code(), "ECMP0376101");
+    let message = error.to_string();
+    assert!(message.contains("take 100005 input slots, exceeding the limit of 10000"));
+    assert!(message.contains("the largest are `big` (100000 slots), `b` (4 slots), `a` (1 slots)"));
+}
+
+#[test]
+fn test_input_slots_exclude_const_parameters() {
+    let program_string = "function main(a: u32, const big: [u8; 100000]) {}\n";
+
+    let slots = count_input_slots(program_string, 10_000).unwrap();
+    assert_eq!(slots.total, 1);
+    assert_eq!(slots.parameters, vec![("a".to_string(), 1)]);
+}
+
+#[derive(Default)]
+struct RecordingReporter {
+    events: std::cell::RefCell<Vec<String>>,
+}
+
+impl ProgressReporter for RecordingReporter {
+    fn on_phase_start(&self, phase: &str, items: Option<usize>) {
+        let items = items.map(|items| format!(" ({})", items)).unwrap_or_default();
+        self.events.borrow_mut().push(format!("start {}{}", phase, items));
+    }
+
+    fn on_item(&self, index: usize, name: &str) {
+        self.events.borrow_mut().push(format!("item {} {}", index, name));
+    }
+
+    fn on_phase_end(&self, phase: &str) {
+        self.events.borrow_mut().push(format!("end {}", phase));
+    }
+}
+
+const PROGRESS_PROGRAM: &str = r#"
+function double(x: u32) -> u32 {
+    return x + x;
+}
+
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0..300 {
+        b = double(b) - b;
+    }
+    return b;
+}
+"#;
+
+fn progress_events(program_string: &str) -> Vec<String> {
+    let reporter = std::sync::Arc::new(RecordingReporter::default());
+    let options = CompilerOptions {
+        progress: Progress::new(reporter.clone()),
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_string(program_string).unwrap();
+    synthesize(&mut compiler, U32_INPUT).unwrap();
+
+    reporter.events.take()
+}
+
+#[test]
+fn test_progress_phases() {
+    let events = progress_events(PROGRESS_PROGRAM);
+
+    let phases = events
+        .iter()
+        .filter(|event| event.starts_with("start "))
+        .map(|event| &event["start ".len()..])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        phases,
+        vec![
+            "parsing",
+            "import resolution",
+            "canonicalization",
+            "type checking (2)",
+            "optimization",
+            "range comparison",
+            "constructibility",
+            "constant folding",
+            "static asserts",
+            "dead code elimination",
+            "usage",
+            "inlining",
+            "limits",
+            "constraint synthesis",
+            "loop unrolling (2)",
+        ]
+    );
+
+    // Every phase ends, innermost first.
+    let mut running = vec![];
+    for event in events.iter() {
+        if let Some(phase) = event.strip_prefix("start ") {
+            running.push(phase.split(" (").next().unwrap());
+        } else if let Some(phase) = event.strip_prefix("end ") {
+            assert_eq!(running.pop(), Some(phase));
+        }
+    }
+    assert!(running.is_empty());
+}
+
+#[test]
+fn test_progress_items() {
+    let events = progress_events(PROGRESS_PROGRAM);
+    let items_of = |phase: &str| {
+        let start = events
+            .iter()
+            .position(|event| event.starts_with(&format!("start {}", phase)))
+            .unwrap();
+        let end = events
+            .iter()
+            .position(|event| event == &format!("end {}", phase))
+            .unwrap();
+        events[start + 1..end]
+            .iter()
+            .filter(|event| event.starts_with("item "))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(items_of("type checking"), vec!["item 0 double", "item 1 main"]);
+    // 300 iterations are reported in chunks of 256.
+    assert_eq!(items_of("loop unrolling"), vec!["item 0 i", "item 1 i"]);
+}
+
+const ENTRYPOINTS_PROGRAM: &str = r#"
+function scale(x: u32) -> u32 {
+    return x * 2;
+}
+
+function mint(amount: u32) -> u32 {
+    return scale(amount);
+}
+
+function transfer(amount: u32, fee: u32) -> u32 {
+    return scale(amount) - fee;
+}
+"#;
+
+fn entrypoints_compiler(entrypoints: &[&str], reporter: std::sync::Arc<RecordingReporter>) -> EdwardsTestCompiler {
+    let options = CompilerOptions {
+        entrypoints: entrypoints.iter().map(|entrypoint| entrypoint.to_string()).collect(),
+        progress: Progress::new(reporter),
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_string(ENTRYPOINTS_PROGRAM).unwrap();
+    compiler
+        .add_entrypoint_input(
+            "mint",
+            "[main]\namount: u32 = 3;\n\n[registers]\nr: u32 = 0;\n",
+            Path::new("mint.in"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+    compiler
+}
+
+#[test]
+fn test_entrypoints_share_analysis() {
+    let reporter = std::sync::Arc::new(RecordingReporter::default());
+    let mut compiler = entrypoints_compiler(&["mint", "transfer"], reporter.clone());
+    compiler
+        .add_entrypoint_input(
+            "transfer",
+            "[main]\namount: u32 = 3;\nfee: u32 = 1;\n\n[registers]\nr: u32 = 0;\n",
+            Path::new("transfer.in"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+    let sink = MemorySink::new();
+    let compiler = compiler.with_output_writer(sink.clone());
+
+    let results = compiler.compile_entrypoints();
+    let outputs = results
+        .into_iter()
+        .map(|(entrypoint, result)| (entrypoint, result.unwrap().registers["r"].value.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        outputs,
+        vec![
+            ("mint".to_string(), "6".to_string()),
+            ("transfer".to_string(), "5".to_string())
+        ]
+    );
+
+    // The helper is checked once, and only synthesis runs once per entrypoint.
+    let events = reporter.events.take();
+    let count = |event: &str| events.iter().filter(|e| e.as_str() == event).count();
+    assert_eq!(count("start type checking (3)"), 1);
+    assert_eq!(count("item 0 scale"), 1);
+    assert_eq!(count("start constant folding"), 1);
+    assert_eq!(count("start constraint synthesis"), 2);
+
+    for entrypoint in ["mint", "transfer"].iter() {
+        for artifact in ["out", "interface.json"].iter() {
+            let name = format!("{}/test.{}", entrypoint, artifact);
+            assert!(sink.get(&name).is_some(), "missing {}", name);
+        }
+    }
+    let interface = sink.get("transfer/test.interface.json").unwrap();
+    let interface: Interface = serde_json::from_slice(&INTERFACE_FORMAT.unstamp(&interface)).unwrap();
+    assert_eq!(interface.entrypoint, "transfer");
+    let parameters = interface
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.name.as_str(), parameter.type_.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(parameters, vec![("amount", "u32"), ("fee", "u32")]);
+    assert_eq!(interface.outputs, vec!["u32".to_string()]);
+}
+
+#[test]
+fn test_failing_entrypoint_does_not_block_others() {
+    let reporter = std::sync::Arc::new(RecordingReporter::default());
+    let sink = MemorySink::new();
+    // `transfer` has no input of its own, and `burn` is not a function of the program.
+    let compiler = entrypoints_compiler(&["transfer", "mint", "burn"], reporter).with_output_writer(sink.clone());
+
+    let results = compiler.compile_entrypoints();
+    assert_eq!(results.len(), 3);
+
+    let error = results[0].1.as_ref().unwrap_err().to_string();
+    assert!(error.contains("in entrypoint `transfer`"), "{}", error);
+    assert!(results[1].1.is_ok());
+    let error = results[2].1.as_ref().unwrap_err().to_string();
+    assert!(
+        error.contains("in entrypoint `burn`: the entrypoint `burn` is not a function of the program"),
+        "{}",
+        error
+    );
+
+    assert!(sink.get("mint/test.out").is_some());
+    assert!(sink.get("mint/test.interface.json").is_some());
+    assert!(sink.artifacts().keys().all(|name| !name.starts_with("transfer/")));
+}
+
+/// Fails the test if the compiler reads a source by name, rather than using the string it was given.
+struct NoSources;
+
+impl SourceProvider for NoSources {
+    fn read_source(&self, name: &FileName) -> std::io::Result<String> {
+        panic!("`{}` was read, but its source was given as a string", name)
+    }
+}
+
+/// Serves the given source as the standard input of the process.
+struct StdinSource(&'static str);
+
+impl SourceProvider for StdinSource {
+    fn read_source(&self, name: &FileName) -> std::io::Result<String> {
+        assert_eq!(name, &FileName::Stdin);
+        Ok(self.0.to_string())
+    }
+}
+
+fn named_source_compiler(main_file: FileName, provider: impl SourceProvider + 'static) -> EdwardsTestCompiler {
+    let snapshots = AstSnapshotOptions {
+        initial: true,
+        ..Default::default()
+    };
+    test_compiler_with(
+        make_test_context(),
+        PathBuf::new(),
+        CompilerOptions::default(),
+        snapshots,
+    )
+    .with_main_file(main_file)
+    .with_source_provider(provider)
+}
+
+fn custom(name: &str) -> FileName {
+    FileName::Custom(name.to_string())
+}
+
+#[test]
+fn test_compile_named_source() {
+    let sink = MemorySink::new();
+    let mut compiler = named_source_compiler(custom("generated/main.leo"), NoSources).with_output_writer(sink.clone());
+    compiler.parse_program_from_string(SOURCE_PROGRAM).unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 2;\n\n[registers]\nr: u32 = 0;\n",
+            custom("generated/input.in"),
+            "",
+            custom("generated/input.state"),
+        )
+        .unwrap();
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    let output = compiler.compile_constraints(&mut cs).unwrap();
+    assert!(output.to_string().contains("r: u32 = 3;"));
+
+    // Artifacts are named after the program, not the source.
+    assert!(sink.get("initial_ast.json").is_some());
+    assert!(sink.artifacts().keys().all(|name| !name.contains("generated")));
+
+    // The checksum is taken over the source as given, and only depends on it.
+    let checksum = compiler.checksum().unwrap();
+    assert_eq!(compiler.checksum().unwrap(), checksum);
+    let mut renamed = named_source_compiler(custom("other.leo"), NoSources).with_output_writer(MemorySink::new());
+    renamed.parse_program_from_string(SOURCE_PROGRAM).unwrap();
+    assert_eq!(renamed.checksum().unwrap(), checksum);
+    let mut changed = named_source_compiler(custom("other.leo"), NoSources).with_output_writer(MemorySink::new());
+    changed
+        .parse_program_from_string(&SOURCE_PROGRAM.replace("+ 1", "+ 2"))
+        .unwrap();
+    assert_ne!(changed.checksum().unwrap(), checksum);
+}
+
+#[test]
+fn test_named_source_diagnostics() {
+    let mut compiler =
+        named_source_compiler(custom("generated/main.leo"), NoSources).with_output_writer(MemorySink::new());
+    let error = compiler
+        .parse_program_from_string("function main() {\n    let x: u8 = true;\n}\n")
+        .unwrap_err();
+    assert!(error.to_string().contains("--> generated/main.leo:2:"), "{}", error);
+
+    let mut compiler =
+        named_source_compiler(custom("generated/main.leo"), NoSources).with_output_writer(MemorySink::new());
+    compiler.parse_program_from_string(SOURCE_PROGRAM).unwrap();
+    let errors = compiler.parse_input_recovering(
+        "[main]\na: u32 = 2;\n",
+        custom("generated/input.in"),
+        "[[public]]\n[state\n",
+        custom("generated/input.state"),
+    );
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    // Input syntax errors only display their summary, the name is on the wrapped parser error.
+    assert!(
+        format!("{:?}", errors[0]).contains("generated/input.state"),
+        "{:?}",
+        errors[0]
+    );
+}
+
+#[test]
+fn test_compile_stdin_source() {
+    let mut compiler = named_source_compiler(FileName::from_argument("-"), StdinSource(SOURCE_PROGRAM))
+        .with_output_writer(MemorySink::new());
+    assert!(!compiler.main_file().is_real());
+    compiler.parse_program().unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 2;\n\n[registers]\nr: u32 = 0;\n",
+            custom("input.in"),
+            "",
+            custom("input.state"),
+        )
+        .unwrap();
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    assert!(compiler
+        .compile_constraints(&mut cs)
+        .unwrap()
+        .to_string()
+        .contains("r: u32 = 3;"));
+
+    // The source is not read again for the checksum, since the standard input can only be read once.
+    let mut named = named_source_compiler(custom("main.leo"), NoSources).with_output_writer(MemorySink::new());
+    named.parse_program_from_string(SOURCE_PROGRAM).unwrap();
+    assert_eq!(compiler.checksum().unwrap(), named.checksum().unwrap());
+
+    let mut compiler = named_source_compiler(
+        FileName::Stdin,
+        StdinSource("function main() {\n    let x: u8 = true;\n}\n"),
+    )
+    .with_output_writer(MemorySink::new());
+    let error = compiler.parse_program().unwrap_err();
+    assert!(error.to_string().contains("--> <stdin>:2:"), "{}", error);
+}
+
+const SHARED_WARNING_PROGRAM: &str = r#"
+function is_valid(a: u8) -> bool {
+    return a >= 0u8;
+}
+function main(a: u8) -> bool {
+    return is_valid(a);
+}
+"#;
+
+fn warning_compiler(context: AsgContext<'static>, source: &str) -> EdwardsTestCompiler {
+    test_compiler_with(
+        context,
+        PathBuf::new(),
+        CompilerOptions::default(),
+        AstSnapshotOptions::default(),
+    )
+    .with_main_file(custom("main.leo"))
+    .with_source_provider(NoSources)
+    .with_output_writer(MemorySink::new())
+    .with_warning_source(source)
+}
+
+fn compile_warnings(context: AsgContext<'static>, source: &str, program_string: &str) -> Vec<AsgWarning> {
+    let mut compiler = warning_compiler(context, source);
+    compiler.parse_program_from_string(program_string).unwrap();
+    compiler.warnings().to_vec()
+}
+
+#[test]
+fn test_shared_warning_reported_once() {
+    let context = make_test_context();
+    context.set_deduplicate_warnings(true);
+    let first = compile_warnings(context, "first", SHARED_WARNING_PROGRAM);
+    let second = compile_warnings(context, "second", SHARED_WARNING_PROGRAM);
+
+    assert_eq!(first.len(), 1, "{:?}", first);
+    assert!(second.is_empty(), "{:?}", second);
+    let tally = context.warning_tally();
+    assert_eq!(tally.len(), 1, "{:?}", tally);
+    assert_eq!(tally[0].warning, first[0]);
+    assert_eq!(tally[0].reports, 2);
+    assert_eq!(tally[0].sources, vec!["first", "second"]);
+}
+
+#[test]
+fn test_edited_source_warning_reported_again() {
+    let context = make_test_context();
+    context.set_deduplicate_warnings(true);
+    let first = compile_warnings(context, "first", SHARED_WARNING_PROGRAM);
+    // The same file, edited on the line of the warning without moving it.
+    let edited = SHARED_WARNING_PROGRAM.replace("0u8;", "0u8; // edited");
+    let second = compile_warnings(context, "second", &edited);
+
+    assert_eq!(first.len(), 1, "{:?}", first);
+    assert_eq!(second.len(), 1, "{:?}", second);
+    assert_eq!(first[0].span.path, second[0].span.path);
+    assert_eq!(first[0].span.line_start, second[0].span.line_start);
+    assert_eq!(first[0].span.col_start, second[0].span.col_start);
+    assert_eq!(context.warning_tally().len(), 2);
+}
+
+#[test]
+fn test_warnings_kept_without_deduplication() {
+    let context = make_test_context();
+    let first = compile_warnings(context, "first", SHARED_WARNING_PROGRAM);
+    let second = compile_warnings(context, "second", SHARED_WARNING_PROGRAM);
+
+    assert_eq!(first.len(), 1, "{:?}", first);
+    assert_eq!(second, first);
+    assert!(context.warning_tally().is_empty());
+}
+
+const UNIT_PROGRAM: &str = r#"
+function check(a: u32) {
+    let b = a + 1u32;
+    RETURN
+}
+
+function nothing() -> () {
+    return ();
+}
+
+function main(a: u32) {
+    let x: () = check(a);
+    let y = nothing();
+    check(a);
+    return x;
+}
+"#;
+
+#[test]
+fn test_unit_values() {
+    let result = compile_source(&UNIT_PROGRAM.replace("RETURN", "return;"), source_options());
+
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(result.output.as_deref(), Some("[registers]\n"));
+}
+
+#[test]
+fn test_bare_return_is_unit_return() {
+    let bare = compile_source(&UNIT_PROGRAM.replace("RETURN", "return;"), source_options());
+    let explicit = compile_source(&UNIT_PROGRAM.replace("RETURN", "return ();"), source_options());
+
+    assert!(bare.is_ok(), "{:?}", bare.diagnostics);
+    assert!(explicit.is_ok(), "{:?}", explicit.diagnostics);
+    assert_eq!(bare.output, explicit.output);
+    assert_eq!(bare.statistics.constraints, explicit.statistics.constraints);
+
+    let returned = |source: &str| {
+        let ast = leo_parser::parse_ast("compiler-test", source).unwrap();
+        match &ast.as_repr().functions.values().next().unwrap().block.statements[1] {
+            leo_ast::Statement::Return(statement) => match &statement.expression {
+                leo_ast::Expression::TupleInit(tuple) => tuple.elements.len(),
+                expression => panic!("unexpected return value {}", expression),
+            },
+            statement => panic!("unexpected statement {}", statement),
+        }
+    };
+    assert_eq!(returned(&UNIT_PROGRAM.replace("RETURN", "return;")), 0);
+    assert_eq!(returned(&UNIT_PROGRAM.replace("RETURN", "return ();")), 0);
+}
+
+const ENDLESS_UNROLLING_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0..10000 {
+        for j in 0..10000 {
+            b += 1;
+        }
+    }
+    return b;
+}
+"#;
+
+fn unlimited() -> ProgramLimits {
+    ProgramLimits {
+        max_unrolled_statements: usize::MAX,
+        max_constraints: usize::MAX,
+        max_variables: usize::MAX,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_cancel_during_unrolling() {
+    let cancellation = CancellationToken::new();
+    let canceller = {
+        let cancellation = cancellation.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            cancellation.cancel();
+        })
+    };
+    let options = CompilerOptions {
+        limits: unlimited(),
+        cancellation,
+        ..Default::default()
+    };
+    let error = compile_with_options(ENDLESS_UNROLLING_PROGRAM, options).unwrap_err();
+    canceller.join().unwrap();
+
+    assert_eq!(error.error_code(), "ECMP0376109");
+    assert!(
+        error.to_string().contains("cancelled during constraint synthesis"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_distant_deadline_does_not_interrupt() {
+    let options = CompilerOptions {
+        cancellation: CancellationToken::with_timeout(std::time::Duration::from_secs(600)),
+        ..Default::default()
+    };
+    let output = compile_with_options(UNROLLING_PROGRAM, options).unwrap();
+    assert_eq!(output.registers["r"].value, "12");
+}
+
+#[test]
+fn test_deadline_reports_phase() {
+    let options = CompilerOptions {
+        cancellation: CancellationToken::with_deadline(std::time::Instant::now()),
+        ..Default::default()
+    };
+    let error = compile_with_options(UNROLLING_PROGRAM, options).unwrap_err();
+    assert_eq!(error.error_code(), "ECMP0376110");
+    assert!(
+        error.to_string().contains("past its deadline during parsing"),
+        "{}",
+        error
+    );
+}
+
+/// Cancels the compilation once it starts the given phase, as if that phase took all the time.
+struct CancellingReporter {
+    cancellation: CancellationToken,
+    phase: &'static str,
+}
+
+impl ProgressReporter for CancellingReporter {
+    fn on_phase_start(&self, phase: &str, _items: Option<usize>) {
+        if phase == self.phase {
+            self.cancellation.cancel();
+        }
+    }
+
+    fn on_item(&self, _index: usize, _name: &str) {}
+
+    fn on_phase_end(&self, _phase: &str) {}
+}
+
+#[test]
+fn test_cancellation_reports_phase() {
+    for phase in ["parsing", "type checking", "constant folding", "constraint synthesis"].iter() {
+        let cancellation = CancellationToken::new();
+        let reporter = CancellingReporter {
+            cancellation: cancellation.clone(),
+            phase,
+        };
+        let options = CompilerOptions {
+            progress: Progress::new(std::sync::Arc::new(reporter)),
+            cancellation,
+            ..Default::default()
+        };
+        let error = compile_with_options(UNROLLING_PROGRAM, options).unwrap_err();
+        assert!(
+            error.to_string().contains(&format!("cancelled during {}", phase)),
+            "{}",
+            error
+        );
+    }
+}
+
+const ASSERT_COMPARISON_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let b = [a, 3, 4];
+    let t = (a, true);
+    ASSERTIONS
+    return a;
+}
+"#;
+
+fn assert_comparison_program(assertions: &str) -> String {
+    ASSERT_COMPARISON_PROGRAM.replace("ASSERTIONS", assertions)
+}
+
+fn summarize_circuit(program_string: &str) -> SummarizedCircuit {
+    let mut compiler = parse_program(program_string, None, None).unwrap();
+    compiler
+        .parse_input(U32_INPUT, Path::new("input"), "", Path::new("state"))
+        .unwrap();
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    compiler.compile_constraints(&mut cs).unwrap();
+    SerializedCircuit::from(cs).into()
+}
+
+#[test]
+fn test_console_assert_comparison_parses() {
+    let program_string =
+        assert_comparison_program("console.assert_eq(b, [2u32, 3, 4]);\n    console.assert_ne(t.0, 3u32);");
+    let ast = leo_parser::parse_ast("compiler-test", &program_string).unwrap();
+    let statements = &ast.as_repr().functions.values().next().unwrap().block.statements;
+
+    let functions = statements[2..4]
+        .iter()
+        .map(|statement| match statement {
+            leo_ast::Statement::Console(console) => console.function.to_string(),
+            statement => panic!("unexpected statement {}", statement),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(functions, vec!["assert_eq(b, [2u32, 3, 4])", "assert_ne(t.0, 3u32)"]);
+}
+
+#[test]
+fn test_console_assert_eq_renders_operands() {
+    let program_string = assert_comparison_program("console.assert_eq(b, [2u32, 3, 5]);");
+    let error = compile_with_limits(&program_string, ProgramLimits::default()).unwrap_err();
+
+    assert_eq!(error.error_code(), "ECMP0376111");
+    let message = error.to_string();
+    assert!(
+        message.contains("console.assert_eq(...) failed\n  left: [2, 3, 4]\n right: [2, 3, 5]"),
+        "{}",
+        message
+    );
+    assert!(message.contains("compiler-test:5:5"), "{}", message);
+
+    let program_string = assert_comparison_program("console.assert_ne(t, (a, true));");
+    let message = compile_with_limits(&program_string, ProgramLimits::default())
+        .unwrap_err()
+        .to_string();
+    assert!(
+        message.contains("console.assert_ne(...) failed\n  left: (2, true)\n right: (2, true)"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_console_assert_comparison_matches_manual_assert() {
+    let pairs = [
+        (
+            "console.assert_eq(b, [2u32, 3, 4]);",
+            "console.assert(b == [2u32, 3, 4]);",
+        ),
+        ("console.assert_ne(a, 3u32);", "console.assert(a != 3u32);"),
+        (
+            "console.assert_eq(t, (2u32, true));",
+            "console.assert(t == (2u32, true));",
+        ),
+    ];
+    for (comparison, manual) in pairs.iter() {
+        assert!(
+            summarize_circuit(&assert_comparison_program(comparison))
+                == summarize_circuit(&assert_comparison_program(manual)),
+            "{}",
+            comparison
+        );
+    }
+}
+
+#[test]
+fn test_trailing_commas_in_program_and_input() {
+    let program = r#"
+circuit Point {
+    x: u32,
+    y: u32,
+}
+
+function add(a: u32, b: u32,) -> u32 {
+    return a + b;
+}
+
+function main(a: [u32; (2, 2,)], t: (u32, bool,),) -> u32 {
+    let p = Point { x: a[0][0], y: a[1][1], };
+    let b = [p.x, p.y,];
+    let (c, d,) = (add(b[0], b[1],), t.0,);
+    console.assert(t.1,);
+    console.assert_eq(c, 5u32,);
+    return add(c, d,);
+}
+"#;
+    let result = compile_source(
+        program,
+        CompileOptions {
+            input: Some(
+                "[main]\na: [u32; (2, 2,)] = [[1, 2,], [3, 4,],];\nt: (u32, bool,) = (5, true,);\n\n[registers]\nr: u32 = 0;\n"
+                    .to_string(),
+            ),
+            ..Default::default()
+        },
+    );
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: u32 = 10;"));
+}
+
+const DOCUMENTED_INPUT: &str = r#"// Inputs of the example.
+
+/// The arguments of `main`.
+/// Both are small.
+[main] // the only parameters
+/// The first operand.
+a: u32 = 2; // the operand
+b: [u32; 2] = [
+    // a comment within a value
+    1, 2,
+];
+
+/* block comments are comments too */
+/// Written back by the program.
+[registers]
+
+///
+/// The result.
+r: u32 = 0;
+// trailing comment"#;
+
+#[test]
+fn test_input_comments_in_every_position() {
+    let program = r#"
+function main(a: u32, b: [u32; 2]) -> u32 {
+    return a + b[0] + b[1];
+}
+"#;
+    let result = compile_source(
+        program,
+        CompileOptions {
+            input: Some(DOCUMENTED_INPUT.to_string()),
+            ..Default::default()
+        },
+    );
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: u32 = 5;"));
+}
+
+#[test]
+fn test_input_descriptions_attach_to_their_nodes() {
+    let input = leo_ast::InputAst::parse(DOCUMENTED_INPUT).unwrap();
+    let sections = input
+        .items
+        .iter()
+        .map(|item| match item {
+            leo_ast::InputItem::Section(section) => section,
+            leo_ast::InputItem::Table(_) => panic!("unexpected table"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].header, "main");
+    assert_eq!(
+        sections[0].description.as_deref(),
+        Some("The arguments of `main`.\nBoth are small.")
+    );
+    let entries = &sections[0].entries;
+    assert_eq!(
+        (
+            entries[0].name.as_str(),
+            entries[0].type_.as_str(),
+            entries[0].value.as_str()
+        ),
+        ("a", "u32", "2")
+    );
+    assert_eq!(entries[0].description.as_deref(), Some("The first operand."));
+    assert_eq!(entries[1].type_, "[u32; 2]");
+    assert_eq!(entries[1].description, None);
+
+    assert_eq!(sections[1].header, "registers");
+    assert_eq!(sections[1].description.as_deref(), Some("Written back by the program."));
+    assert_eq!(sections[1].entries[0].description.as_deref(), Some("\nThe result."));
+
+    let input =
+        leo_ast::InputAst::parse("/// The visibility.\n[[public]]\n\n[state]\n/// The root.\nroot: u8 = 1;\n").unwrap();
+    match &input.items[0] {
+        leo_ast::InputItem::Table(table) => {
+            assert_eq!(table.description.as_deref(), Some("The visibility."));
+            assert_eq!(table.visibility, "public");
+            assert_eq!(table.sections[0].description, None);
+            assert_eq!(table.sections[0].entries[0].description.as_deref(), Some("The root."));
+        }
+        leo_ast::InputItem::Section(_) => panic!("expected a table"),
+    }
+
+    let json = String::from_utf8(input.to_json_bytes().unwrap()).unwrap();
+    assert!(json.contains("\"description\": \"The root.\""), "{}", json);
+}
+
+#[test]
+fn test_input_ast_round_trip() {
+    let tabled =
+        "/// The visibility.\n[[public]]\n\n/// The state.\n[state]\nroot: u8 = 1;\nleaf: (u8, bool) = (1, true);\n";
+    for source in [DOCUMENTED_INPUT, tabled].iter() {
+        let input = leo_ast::InputAst::parse(source).unwrap();
+        let text = input.to_text();
+        let reparsed = leo_ast::InputAst::parse(&text).unwrap();
+        assert_eq!(reparsed, input, "{}", text);
+        assert_eq!(reparsed.to_text(), text);
+    }
+}
+
+#[test]
+fn test_comment_only_input() {
+    for source in [
+        "",
+        "// nothing here\n",
+        "/// not attached\n\n/* nor this */\n// trailing",
+    ]
+    .iter()
+    {
+        let input = leo_ast::InputAst::parse(source).unwrap();
+        assert_eq!(input, leo_ast::InputAst::default(), "{}", source);
+        assert_eq!(input.to_text(), "");
+    }
+}
+
+fn usage_warnings(program_string: &str, options: CompilerOptions) -> Vec<(usize, String)> {
+    check_with_options(program_string, options)
+        .unwrap()
+        .warnings()
+        .iter()
+        .map(|warning| (warning.span.line_start, warning.message.clone()))
+        .collect()
+}
+
+const UNUSED_PROGRAM: &str = r#"
+circuit Pair {
+    left: u32,
+    right: u32,
+}
+
+function sum(p: Pair, scale: u32, _reserved: u32) -> u32 {
+    return p.left;
+}
+
+function main(a: u32, b: u32) -> u32 {
+    let p = Pair { left: a, right: b };
+    return sum(p, a, a);
+}
+"#;
+
+#[test]
+fn test_unused_parameter_and_member() {
+    let warnings = usage_warnings(UNUSED_PROGRAM, CompilerOptions::default());
+
+    assert_eq!(
+        warnings,
+        vec![
+            (7, "parameter `scale` of function `sum` is never read".to_string()),
+            (
+                4,
+                "member `right` of circuit `Pair` is never read, but the construction at 12:13 still sets it"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_member_read_by_comparison() {
+    let program = r#"
+circuit Pair {
+    left: u32,
+    right: u32,
+}
+
+function main(a: u32, b: u32) -> bool {
+    let p = Pair { left: a, right: b };
+    let q = Pair { left: b, right: a };
+    return p == q;
+}
+"#;
+    let warnings = usage_warnings(program, CompilerOptions::default());
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn test_parameter_used_only_in_dead_code() {
+    let program = r#"
+function main(a: u32, b: u32) -> u32 {
+    if false {
+        console.log("{}", b);
+    }
+    return a;
+}
+"#;
+
+    // Dead code elimination runs first, so the read it removes does not count.
+    let warnings = usage_warnings(program, CompilerOptions::default());
+    assert_eq!(
+        warnings,
+        vec![(2, "parameter `b` of function `main` is never read".to_string())]
+    );
+
+    // Without it, the read counts like any other.
+    let options = CompilerOptions {
+        dead_code_elimination_enabled: false,
+        ..Default::default()
+    };
+    let warnings = usage_warnings(program, options);
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn test_interface_members_are_used() {
+    let program = r#"
+circuit Inner {
+    value: u32,
+}
+
+circuit Pair {
+    left: u32,
+    inner: Inner,
+}
+
+function make(a: u32) -> Pair {
+    return Pair { left: a, inner: Inner { value: a } };
+}
+
+function main(a: u32) -> u32 {
+    return make(a).left;
+}
+"#;
+    let options = CompilerOptions {
+        entrypoints: vec!["main".to_string(), "make".to_string()],
+        ..Default::default()
+    };
+    let warnings = usage_warnings(program, options);
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    // Only `main` is an entrypoint, so nothing reads `inner` or `value`.
+    let warnings = usage_warnings(program, CompilerOptions::default());
+    assert_eq!(warnings.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![3, 8]);
+}
+
+#[test]
+fn test_options_toml_round_trip() {
+    let defaults = CompilerOptions::default();
+    let toml = defaults.to_toml_string().unwrap();
+    let (loaded, unknown) = CompilerOptions::from_toml_str(&toml, "options.toml").unwrap();
+    assert!(unknown.is_empty(), "{:?}", unknown);
+    assert_eq!(loaded.digest(), defaults.digest());
+    assert_eq!(loaded.to_toml_string().unwrap(), toml);
+
+    // Every option left out takes its default, so the options of older compilers load.
+    let (loaded, unknown) = CompilerOptions::from_toml_str("", "options.toml").unwrap();
+    assert!(unknown.is_empty(), "{:?}", unknown);
+    assert_eq!(loaded.to_toml_string().unwrap(), toml);
+
+    let root = tempfile::tempdir().unwrap();
+    let file = root.path().join("options.toml");
+    verified_options().to_file(&file).unwrap();
+    let (loaded, _) = CompilerOptions::from_file(&file).unwrap();
+    assert!(loaded.renaming_enabled);
+    assert_eq!(loaded.digest(), verified_options().digest());
+}
+
+#[test]
+fn test_options_unknown_keys_are_warnings() {
+    let toml = "constant_folding_enabled = false\nfuture_option = 1\n\n[limits]\nmax_circuits = 5\nmax_future = 2\n";
+    let (options, unknown) = CompilerOptions::from_toml_str(toml, "options.toml").unwrap();
+
+    assert_eq!(unknown, vec!["future_option", "limits.max_future"]);
+    assert!(!options.constant_folding_enabled);
+    assert_eq!(options.limits.max_circuits, 5);
+    assert_eq!(options.limits.max_functions, ProgramLimits::default().max_functions);
+
+    // Options of the wrong type are still errors.
+    assert!(CompilerOptions::from_toml_str("emit_dot = 3\n", "options.toml").is_err());
+}
+
+#[test]
+fn test_effective_options_reflect_overrides() {
+    let options = CompilerOptions {
+        emit_dot: true,
+        limits: ProgramLimits {
+            max_circuits: 7,
+            ..Default::default()
+        },
+        entrypoints: vec!["main".to_string(), "other".to_string()],
+        ..Default::default()
+    };
+    let sink = MemorySink::new();
+    let mut compiler = test_compiler(options.clone()).with_output_writer(sink.clone());
+    compiler
+        .parse_program_from_string("function other() {}\nfunction main() {}\n")
+        .unwrap();
+
+    let effective = compiler.effective_options();
+    assert!(effective.emit_dot);
+    assert_eq!(effective.limits.max_circuits, 7);
+    assert_eq!(effective.limits.max_functions, ProgramLimits::default().max_functions);
+    assert_eq!(effective.entrypoints, vec!["main", "other"]);
+
+    let written = String::from_utf8(sink.artifacts()["effective_options.toml"].clone()).unwrap();
+    assert!(written.starts_with(&format!(
+        "# The compiler options of this build, whose digest `{}`",
+        options.digest()
+    )));
+    let (loaded, unknown) = CompilerOptions::from_toml_str(&written, "effective_options.toml").unwrap();
+    assert!(unknown.is_empty(), "{:?}", unknown);
+    assert_eq!(loaded.digest(), options.digest());
+
+    // Without artifacts, the options are not written.
+    let sink = MemorySink::new();
+    let mut compiler = test_compiler(CompilerOptions::default()).with_output_writer(sink.clone());
+    compiler.parse_program_from_string("function main() {}\n").unwrap();
+    assert!(!sink.artifacts().contains_key("effective_options.toml"));
+}
+
+#[test]
+fn test_options_digest_covers_every_option() {
+    let defaults = toml::Value::try_from(CompilerOptions::default()).unwrap();
+    let mut changed = vec![];
+    let mut tables = vec![(vec![], defaults.as_table().unwrap().clone())];
+    while let Some((path, table)) = tables.pop() {
+        for (key, value) in table.iter() {
+            let mut path = path.clone();
+            path.push(key.clone());
+            let value = match value {
+                toml::Value::Table(table) => {
+                    tables.push((path, table.clone()));
+                    continue;
+                }
+                toml::Value::Boolean(value) => toml::Value::Boolean(!value),
+                toml::Value::Integer(value) => toml::Value::Integer(value + 1),
+                toml::Value::String(value) if value == "forbid" => toml::Value::String("lossless".to_string()),
+                toml::Value::String(value) if value == "off" => toml::Value::String("snake_case".to_string()),
+                toml::Value::String(value) if value == "2022" => toml::Value::String("2021".to_string()),
+                toml::Value::Array(values) => {
+                    let mut values = values.clone();
+                    values.push(toml::Value::String("other".to_string()));
+                    toml::Value::Array(values)
+                }
+                value => panic!("option `{}` has an unexpected value {}", path.join("."), value),
+            };
+            changed.push((path, value));
+        }
+    }
+    assert!(changed.len() > 15, "{:?}", changed);
+
+    let mut digests = vec![CompilerOptions::default().digest()];
+    for (path, value) in changed {
+        let mut options = defaults.clone();
+        let mut table = options.as_table_mut().unwrap();
+        for key in path[..path.len() - 1].iter() {
+            table = table.get_mut(key).unwrap().as_table_mut().unwrap();
+        }
+        table.insert(path.last().unwrap().clone(), value);
+
+        let toml = toml::to_string(&options).unwrap();
+        let (options, _) = CompilerOptions::from_toml_str(&toml, "options.toml").unwrap();
+        let digest = options.digest();
+        assert!(
+            !digests.contains(&digest),
+            "changing `{}` keeps the digest",
+            path.join(".")
+        );
+        digests.push(digest);
+    }
+}
+
+/// Builds the program with every artifact enabled, returning the artifacts it writes.
+fn build_artifacts(program_string: &str) -> IndexMap<String, Vec<u8>> {
+    let options = CompilerOptions {
+        renaming_enabled: true,
+        emit_dot: true,
+        record_provenance: true,
+        ..Default::default()
+    };
+    let snapshots = AstSnapshotOptions {
+        spans_enabled: true,
+        initial: true,
+        imports_resolved: true,
+        canonicalized: true,
+        type_inferenced: true,
+        input: true,
+    };
+    let sink = MemorySink::new();
+    let mut compiler =
+        test_compiler_with(make_test_context(), PathBuf::new(), options, snapshots).with_output_writer(sink.clone());
+    compiler.parse_program_from_string(program_string).unwrap();
+    synthesize(&mut compiler, U32_INPUT).unwrap();
+    sink.artifacts()
+}
+
+#[test]
+fn test_identical_builds_write_identical_artifacts() {
+    let program_string = r#"
+const OFFSET: u32 = 3;
+
+circuit Counter {
+    count: u32,
+
+    function bumped(self, by: u32) -> Counter {
+        return Counter { count: self.count + by };
+    }
+}
+
+function double(x: u32) -> u32 {
+    return x + x;
+}
+
+function main(a: u32) -> u32 {
+    let counter = Counter { count: a };
+    for i in 0..2 {
+        counter = counter.bumped(double(a));
+    }
+    return counter.count + OFFSET;
+}
+"#;
+    // Names are kept as strings rather than interned, so nothing in the artifacts depends on the build.
+    let first = build_artifacts(program_string);
+    let second = build_artifacts(program_string);
+    assert!(first.contains_key("rename_map.json"));
+    assert!(first.contains_key("main.dot"));
+    assert_eq!(first.keys().collect::<Vec<_>>(), second.keys().collect::<Vec<_>>());
+    for (name, bytes) in first.iter() {
+        assert!(&second[name] == bytes, "`{}` differs between identical builds", name);
+    }
+}
+
+const NAVIGATION_PROGRAM: &str = r#"
+type Pair = Point;
+
+circuit Point {
+    x: u32,
+
+    function sum(self, other: Pair) -> u32 {
+        return self.x + other.x;
+    }
+}
+
+/// Adds one to a value.
+/// Never overflows in tests.
+function increment(a: u32) -> u32 {
+    return a + 1u32;
+}
+
+function main(a: u32) -> u32 {
+    let p = Point { x: a };
+    let q: Pair = Point { x: increment(a) };
+    let total = p.sum(q) + q.x;
+    return total;
+}
+"#;
+
+/// Parses the program, keeping the compiler whether or not the program checks.
+fn navigable_compiler(program_string: &str) -> (EdwardsTestCompiler, Result<()>) {
+    let mut compiler = test_compiler(CompilerOptions::default());
+    let parsed = compiler.parse_program_from_string(program_string);
+    (compiler, parsed)
+}
+
+fn at(line: usize, column: usize) -> leo_asg::Position {
+    leo_asg::Position { line, column }
+}
+
+fn start(span: &Span) -> (usize, usize) {
+    (span.line_start, span.col_start)
+}
+
+#[test]
+fn test_definition_at() {
+    let (compiler, parsed) = navigable_compiler(NAVIGATION_PROGRAM);
+    parsed.unwrap();
+    let definition = |line, column| {
+        compiler
+            .definition_at("compiler-test", at(line, column))
+            .map(|span| start(&span))
+    };
+
+    // A local, and a declaration, which is its own definition.
+    assert_eq!(definition(21, 17), Some((19, 9)));
+    assert_eq!(definition(19, 9), Some((19, 9)));
+    // A function.
+    assert_eq!(definition(20, 30), Some((14, 10)));
+    // Circuit members, through the type of the instance.
+    assert_eq!(definition(21, 19), Some((7, 14)));
+    assert_eq!(definition(21, 30), Some((5, 5)));
+    assert_eq!(definition(8, 31), Some((5, 5)));
+    assert_eq!(definition(19, 21), Some((5, 5)));
+    // A type alias.
+    assert_eq!(definition(7, 31), Some((2, 6)));
+    assert_eq!(definition(20, 12), Some((2, 6)));
+
+    assert_eq!(definition(21, 26), None);
+    assert!(compiler.definition_at("other.leo", at(21, 17)).is_none());
+}
+
+#[test]
+fn test_references_of_parameter() {
+    let (compiler, parsed) = navigable_compiler(NAVIGATION_PROGRAM);
+    parsed.unwrap();
+    let parameter = compiler.definition_at("compiler-test", at(18, 15)).unwrap();
+    let references = compiler.references_of(&parameter);
+    assert_eq!(
+        references.iter().map(start).collect::<Vec<_>>(),
+        vec![(19, 24), (20, 40)]
+    );
+
+    // The program does not check, since `a` is declared twice, but the references are answered from its syntax.
+    let program_string = r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    if b > 1u32 {
+        let a = 2u32;
+        b = a;
+    }
+    return a + b;
+}
+"#;
+    let (compiler, parsed) = navigable_compiler(program_string);
+    assert!(parsed.is_err());
+    let parameter = compiler.definition_at("compiler-test", at(2, 15)).unwrap();
+    let references = compiler.references_of(&parameter);
+    assert_eq!(references.iter().map(start).collect::<Vec<_>>(), vec![(3, 13), (8, 12)]);
+    let shadowing = compiler.definition_at("compiler-test", at(6, 13)).unwrap();
+    assert_eq!(start(&shadowing), (5, 13));
+    assert_eq!(
+        compiler.references_of(&shadowing).iter().map(start).collect::<Vec<_>>(),
+        vec![(6, 13)]
+    );
+}
+
+#[test]
+fn test_hover_at() {
+    let (compiler, parsed) = navigable_compiler(NAVIGATION_PROGRAM);
+    parsed.unwrap();
+    let hover = |line, column| compiler.hover_at("compiler-test", at(line, column)).unwrap();
+
+    let variable = hover(21, 17);
+    assert_eq!(start(&variable.span), (21, 17));
+    assert_eq!(variable.type_.unwrap().to_string(), "Point");
+    assert!(variable.doc.is_none());
+
+    let sum = hover(21, 26);
+    assert_eq!(start(&sum.span), (21, 17));
+    assert_eq!(sum.type_.unwrap().to_string(), "u32");
+    assert!(matches!(sum.constness, Some(leo_asg::Constness::Runtime)));
+
+    let call = hover(20, 30);
+    assert_eq!(start(&call.span), (20, 30));
+    assert_eq!(call.type_.unwrap().to_string(), "u32");
+    assert_eq!(
+        call.doc.as_deref(),
+        Some("Adds one to a value.\nNever overflows in tests.")
+    );
+
+    let declaration = hover(14, 10);
+    assert!(declaration.type_.is_none());
+    assert_eq!(declaration.doc, call.doc);
+
+    assert!(compiler.hover_at("compiler-test", at(3, 1)).is_none());
+}
+
+const WIDENING_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let small: u8 = 250;
+    let wide = a * small;
+    let negative: i8 = -3;
+    let big: i64 = 10;
+    if big + negative == 7 && small > a {
+        return wide + 1u16;
+    } else {
+        return 0;
+    }
+}
+"#;
+
+#[test]
+fn test_integer_widening() {
+    let options = CompilerOptions {
+        integer_widening: IntegerWidening::Lossless,
+        ..Default::default()
+    };
+    let output = compile_with_options(WIDENING_PROGRAM, options.clone()).unwrap();
+    assert_eq!(output.registers["r"].value, "501");
+
+    // Without constant folding the casts of constants are lowered as well.
+    let options = CompilerOptions {
+        constant_folding_enabled: false,
+        ..options
+    };
+    let output = compile_with_options(WIDENING_PROGRAM, options).unwrap();
+    assert_eq!(output.registers["r"].value, "501");
+
+    let error = compile_with_options(WIDENING_PROGRAM, CompilerOptions::default())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("operator `*` cannot be applied to `u32` and `u8`, which differ in width"));
+    assert!(error.contains("set the compiler option `integer_widening = \"lossless\"`"));
+}
+
+#[test]
+fn test_integer_widening_casts_in_snapshot() {
+    let options = CompilerOptions {
+        integer_widening: IntegerWidening::Lossless,
+        ..Default::default()
+    };
+    let snapshots = AstSnapshotOptions {
+        initial: true,
+        type_inferenced: true,
+        ..Default::default()
+    };
+    let sink = MemorySink::new();
+    let mut compiler =
+        test_compiler_with(make_test_context(), PathBuf::new(), options, snapshots).with_output_writer(sink.clone());
+    compiler
+        .parse_program_from_string("function main(a: u8, b: u16) -> u16 { return a + b; }")
+        .unwrap();
+
+    let artifacts = sink.artifacts();
+    let initial = String::from_utf8(artifacts["initial_ast.json"].clone()).unwrap();
+    let type_inferenced = String::from_utf8(artifacts["type_inferenced_ast.json"].clone()).unwrap();
+    assert!(!initial.contains("\"Cast\""));
+    assert_eq!(type_inferenced.matches("\"Cast\"").count(), 1, "{}", type_inferenced);
+}
+
+#[test]
+fn test_integer_widening_keeps_signedness() {
+    let program_string = "function main(a: u32) -> u32 { let b: i64 = 1; return a + b; }";
+    for &integer_widening in &[IntegerWidening::Forbid, IntegerWidening::Lossless] {
+        let options = CompilerOptions {
+            integer_widening,
+            ..Default::default()
+        };
+        let error = compile_with_options(program_string, options).err().unwrap().to_string();
+        assert!(error.contains("unexpected type"), "{}", error);
+        assert!(!error.contains("integer_widening"), "{}", error);
+    }
+}
+
+#[test]
+fn test_static_assert_failure_without_folding_shows_values() {
+    let program_string = r#"
+const SIZE: u32 = 8;
+
+function main(a: u32) -> u32 {
+    console.assert_eq(SIZE, 16u32);
+    return a;
+}
+"#;
+    let options = CompilerOptions {
+        constant_folding_enabled: false,
+        ..Default::default()
+    };
+    let message = check_with_options(program_string, options).err().unwrap().to_string();
+    assert!(message.contains("its condition is `8u32 == 16u32`"), "{}", message);
+}
+
+#[test]
+fn test_static_assert_reached_conditionally_warns() {
+    let program_string = r#"
+function check(x: u32) {
+    console.assert(1u8 == 2u8);
+}
+
+function main(a: u32) -> u32 {
+    if a > 5 {
+        console.assert(false);
+    }
+    check(a);
+    return a;
+}
+"#;
+    let compiler = check_with_options(program_string, CompilerOptions::default()).unwrap();
+    let warnings = compiler
+        .warnings()
+        .iter()
+        .filter(|warning| warning.message.starts_with("assertion fails whenever it is reached"))
+        .map(|warning| warning.message.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        vec![
+            "assertion fails whenever it is reached, as its condition is `1u8 == 2u8`",
+            "assertion fails whenever it is reached, as its condition is `false`",
+        ]
+    );
+}
+
+const TRUE_ASSERTS_PROGRAM: &str = r#"
+const SIZE: u32 = 8;
+
+function main(a: u32, b: u8) -> u32 {
+    console.assert(SIZE < 16u32);
+    console.assert_ne(SIZE, 0u32);
+    console.assert((b as u16) < 256u16);
+    console.assert(a == 2u32);
+    return a;
+}
+"#;
+
+/// Runs the static assert pass on the program, returning the number of console statements left in `main`.
+fn remaining_asserts(program_string: &str, keep_true: bool) -> (usize, leo_asg_passes::AssertStatistics) {
+    let context = make_test_context();
+    let ast = leo_parser::parse_ast("compiler-test", program_string).unwrap();
+    let asg = Asg::new(context, ast.as_repr()).unwrap().into_repr();
+    let (asg, statistics) =
+        leo_asg_passes::StaticAsserts::do_pass_with_options(asg, &["main".to_string()], keep_true).unwrap();
+
+    let consoles = match asg.functions["main"].body.get() {
+        Some(Statement::Block(block)) => block
+            .statements
+            .iter()
+            .filter(|statement| matches!(statement.get(), Statement::Console(_)))
+            .count(),
+        _ => 0,
+    };
+    (consoles, statistics)
+}
+
+#[test]
+fn test_static_asserts_that_hold_are_removed() {
+    let (consoles, statistics) = remaining_asserts(TRUE_ASSERTS_PROGRAM, false);
+    assert_eq!(consoles, 1);
+    assert_eq!(
+        statistics,
+        leo_asg_passes::AssertStatistics {
+            constant: 2,
+            from_types: 1
+        }
+    );
+
+    let compiler = check_with_options(TRUE_ASSERTS_PROGRAM, CompilerOptions::default()).unwrap();
+    assert_eq!(compiler.assert_statistics(), Some(statistics));
+
+    let result = compile_source(
+        TRUE_ASSERTS_PROGRAM,
+        CompileOptions {
+            input: Some("[main]\na: u32 = 2;\nb: u8 = 7;\n\n[registers]\nr: u32 = 0;\n".to_string()),
+            ..source_options()
+        },
+    );
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(result.statistics.removed_asserts, Some(statistics));
+    assert!(result
+        .statistics
+        .to_string()
+        .contains("removed asserts: 2 constant, 1 provable from types"));
+}
+
+#[test]
+fn test_keep_true_asserts() {
+    let (consoles, statistics) = remaining_asserts(TRUE_ASSERTS_PROGRAM, true);
+    assert_eq!(consoles, 4);
+    assert_eq!(statistics.total(), 0);
+
+    let options = CompilerOptions {
+        keep_true_asserts: true,
+        ..Default::default()
+    };
+    let compiler = check_with_options(TRUE_ASSERTS_PROGRAM, options).unwrap();
+    assert_eq!(compiler.assert_statistics().unwrap().total(), 0);
+}
+
+fn naming_warnings(program_string: &str, options: CompilerOptions) -> Vec<String> {
+    usage_warnings(program_string, options)
+        .into_iter()
+        .map(|(_, message)| message)
+        .filter(|message| {
+            [
+                " is not snake_case",
+                " is not PascalCase",
+                " is not SCREAMING_SNAKE_CASE",
+            ]
+            .iter()
+            .any(|case| message.contains(case))
+        })
+        .collect()
+}
+
+fn conventional_naming() -> NamingOptions {
+    NamingOptions {
+        functions: NamingConvention::SnakeCase,
+        circuits: NamingConvention::PascalCase,
+        consts: NamingConvention::ScreamingSnakeCase,
+        variables: NamingConvention::SnakeCase,
+        allowed_acronyms: vec![],
+    }
+}
+
+const NAMING_PROGRAM: &str = r#"
+const maxValue: u32 = 10;
+
+circuit point_pair {
+    x: u32,
+
+    function getX(self) -> u32 {
+        return self.x;
+    }
+}
+
+function addTwo(firstValue: u32) -> u32 {
+    let Total = firstValue;
+    for Step in 0..2 {
+        Total += 1;
+    }
+    return Total;
+}
+
+function main(a: u32) -> u32 {
+    let p = point_pair { x: a };
+    return addTwo(p.getX()) + maxValue;
+}
+"#;
+
+#[test]
+fn test_naming_violations_suggest_names() {
+    let options = CompilerOptions {
+        naming: conventional_naming(),
+        ..Default::default()
+    };
+    assert_eq!(
+        naming_warnings(NAMING_PROGRAM, options),
+        vec![
+            "constant `maxValue` is not SCREAMING_SNAKE_CASE, rename it to `MAX_VALUE`",
+            "function `addTwo` is not snake_case, rename it to `add_two`",
+            "variable `firstValue` is not snake_case, rename it to `first_value`",
+            "variable `Total` is not snake_case, rename it to `total`",
+            "variable `Step` is not snake_case, rename it to `step`",
+            "circuit `point_pair` is not PascalCase, rename it to `PointPair`",
+            "function `getX` is not snake_case, rename it to `get_x`",
+        ]
+    );
+}
+
+#[test]
+fn test_naming_checks_original_names_of_renamed_program() {
+    let plain = naming_warnings(
+        NAMING_PROGRAM,
+        CompilerOptions {
+            naming: conventional_naming(),
+            ..Default::default()
+        },
+    );
+    let renamed = naming_warnings(
+        NAMING_PROGRAM,
+        CompilerOptions {
+            naming: conventional_naming(),
+            renaming_enabled: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(renamed, plain);
+}
+
+#[test]
+fn test_naming_off_categories_report_nothing() {
+    assert!(naming_warnings(NAMING_PROGRAM, CompilerOptions::default()).is_empty());
+
+    let options = CompilerOptions {
+        naming: NamingOptions {
+            circuits: NamingConvention::PascalCase,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        naming_warnings(NAMING_PROGRAM, options),
+        vec!["circuit `point_pair` is not PascalCase, rename it to `PointPair`"]
+    );
+}
+
+#[test]
+fn test_naming_suggestion_left_out_when_taken_or_guessed() {
+    let program_string = r#"
+circuit HTTPServer {
+    port: u16,
+}
+
+function my_value() -> u32 {
+    return 1;
+}
+
+function myValue() -> u32 {
+    return my_value();
+}
+
+function main(a: u32, port: u16) -> u32 {
+    let server = HTTPServer { port: port };
+    let total_sum = a;
+    let totalSum = total_sum + myValue();
+    return totalSum + (server.port as u32);
+}
+"#;
+    let options = CompilerOptions {
+        naming: conventional_naming(),
+        ..Default::default()
+    };
+    assert_eq!(
+        naming_warnings(program_string, options),
+        vec![
+            "function `myValue` is not snake_case",
+            "variable `totalSum` is not snake_case",
+            "circuit `HTTPServer` is not PascalCase",
+        ]
+    );
+}
+
+#[test]
+fn test_naming_allowed_acronyms() {
+    let program_string = r#"
+circuit R1CSProof {
+    size: u32,
+}
+
+circuit r1cs_witness {
+    size: u32,
+}
+
+function main(a: u32) -> u32 {
+    let proof = R1CSProof { size: a };
+    let witness = r1cs_witness { size: a };
+    return proof.size + witness.size;
+}
+"#;
+    let naming = NamingOptions {
+        circuits: NamingConvention::PascalCase,
+        ..Default::default()
+    };
+    let options = CompilerOptions {
+        naming: naming.clone(),
+        ..Default::default()
+    };
+    assert_eq!(
+        naming_warnings(program_string, options),
+        vec![
+            "circuit `R1CSProof` is not PascalCase",
+            "circuit `r1cs_witness` is not PascalCase, rename it to `R1csWitness`",
+        ]
+    );
+
+    let options = CompilerOptions {
+        naming: NamingOptions {
+            allowed_acronyms: vec!["R1CS".to_string()],
+            ..naming
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        naming_warnings(program_string, options),
+        vec!["circuit `r1cs_witness` is not PascalCase, rename it to `R1CSWitness`"]
+    );
+}
+
+#[test]
+fn test_naming_allowed_by_annotation() {
+    let program_string = r#"
+@allow_naming
+function addTwo(firstValue: u32) -> u32 {
+    let Total = firstValue + 2;
+    return Total;
+}
+
+function main(a: u32) -> u32 {
+    return addTwo(a);
+}
+"#;
+    let options = CompilerOptions {
+        naming: conventional_naming(),
+        ..Default::default()
+    };
+    assert!(naming_warnings(program_string, options).is_empty());
+}
+
+#[test]
+fn test_unsupported_operator_is_a_diagnostic() {
+    use leo_ast::{builder::*, BinaryOperation, IntegerType, Type};
+
+    // The parser does not read bitwise operators yet, so the program is built.
+    let u32_type = || Type::IntegerType(IntegerType::U32);
+    let program =
+        program().function(
+            func::new("main")
+                .input("a", u32_type())
+                .returns(u32_type())
+                .body(vec![stmt::return_(expr::binary(
+                    expr::ident("a"),
+                    BinaryOperation::BitOr,
+                    expr::ident("a"),
+                ))]),
+        );
+
+    let mut compiler = test_compiler(CompilerOptions::default());
+    compiler.parse_program_from_ast(program.into_ast()).unwrap();
+    let err = synthesize(&mut compiler, U32_INPUT).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("the `|` operator is not supported by constraint synthesis"),
+        "{}",
+        err
+    );
+}
+
+///
+/// Builds a program out of every kind of expression, statement, type and assignee access and compiles it
+/// under several sets of options, checking that each pass handles every construct or rejects it with an error.
+///
+/// The variants are named by matches without a wildcard, so that a new variant does not compile here
+/// until it is listed, and the harness then fails until a sample builds it.
+///
+mod exhaustiveness {
+    use super::{conventional_naming, synthesize, test_compiler};
+    use crate::{CompilerOptions, IntegerWidening};
+    use leo_ast::{
+        builder::*, ArrayComprehensionExpression, ArrayDimensions, ArrayInitExpression, ArrayInlineExpression,
+        ArrayRangeAccessExpression, AssignOperation, AssignStatement, Assignee, AssigneeAccess, Ast, BinaryOperation,
+        Char, CharValue, CircuitStaticFunctionAccessExpression, ConsoleArgs, ConsoleAssertArgs, ConsoleFunction,
+        ConsoleStatement, ErrNode, Expression, FunctionInput, GroupCoordinate, GroupTuple, GroupValue, IntegerType,
+        LengthOfExpression, PositiveNumber, Program, SpreadOrExpression, Statement, Traversal, TupleAccessExpression,
+        Type, UnaryOperation, ValueExpression,
+    };
+    use leo_errors::{panic_message, Result};
+
+    use indexmap::IndexSet;
+    use std::panic::{self, AssertUnwindSafe};
+
+    const ADDRESS: &str = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8sta57j8";
+
+    const HARNESS_INPUT: &str = "[main]\na: u32 = 1;\nflag: bool = true;\n\n[registers]\nr: u32 = 0;\n";
+
+    /// Every variant the samples must build, as named by the functions below.
+    const VARIANTS: &[&str] = &[
+        "Expression::Identifier",
+        "Expression::Value",
+        "Expression::Binary",
+        "Expression::Unary",
+        "Expression::Ternary",
+        "Expression::Cast",
+        "Expression::LengthOf",
+        "Expression::ArrayInline",
+        "Expression::ArrayInit",
+        "Expression::ArrayComprehension",
+        "Expression::ArrayAccess",
+        "Expression::ArrayRangeAccess",
+        "Expression::TupleInit",
+        "Expression::TupleAccess",
+        "Expression::CircuitInit",
+        "Expression::CircuitMemberAccess",
+        "Expression::CircuitStaticFunctionAccess",
+        "Expression::Call",
+        "Expression::Parenthesized",
+        "Expression::Err",
+        "ValueExpression::Address",
+        "ValueExpression::Boolean",
+        "ValueExpression::Char",
+        "ValueExpression::Field",
+        "ValueExpression::Group",
+        "ValueExpression::Implicit",
+        "ValueExpression::Integer",
+        "ValueExpression::String",
+        "BinaryOperation::Add",
+        "BinaryOperation::Sub",
+        "BinaryOperation::Mul",
+        "BinaryOperation::Div",
+        "BinaryOperation::Pow",
+        "BinaryOperation::Or",
+        "BinaryOperation::And",
+        "BinaryOperation::Eq",
+        "BinaryOperation::Ne",
+        "BinaryOperation::Ge",
+        "BinaryOperation::Gt",
+        "BinaryOperation::Le",
+        "BinaryOperation::Lt",
+        "BinaryOperation::BitOr",
+        "BinaryOperation::BitAnd",
+        "BinaryOperation::BitXor",
+        "BinaryOperation::Shr",
+        "BinaryOperation::ShrSigned",
+        "BinaryOperation::Shl",
+        "BinaryOperation::Mod",
+        "UnaryOperation::Not",
+        "UnaryOperation::Negate",
+        "UnaryOperation::BitNot",
+        "Statement::Return",
+        "Statement::Definition",
+        "Statement::Assign",
+        "Statement::Conditional",
+        "Statement::Iteration",
+        "Statement::Console",
+        "Statement::Expression",
+        "Statement::Block",
+        "ConsoleFunction::Assert",
+        "ConsoleFunction::AssertEq",
+        "ConsoleFunction::AssertNe",
+        "ConsoleFunction::Error",
+        "ConsoleFunction::Log",
+        "AssignOperation::Assign",
+        "AssignOperation::Add",
+        "AssignOperation::Sub",
+        "AssignOperation::Mul",
+        "AssignOperation::Div",
+        "AssignOperation::Pow",
+        "AssignOperation::Or",
+        "AssignOperation::And",
+        "AssignOperation::BitOr",
+        "AssignOperation::BitAnd",
+        "AssignOperation::BitXor",
+        "AssignOperation::Shr",
+        "AssignOperation::ShrSigned",
+        "AssignOperation::Shl",
+        "AssignOperation::Mod",
+        "AssigneeAccess::ArrayRange",
+        "AssigneeAccess::ArrayIndex",
+        "AssigneeAccess::Tuple",
+        "AssigneeAccess::Member",
+        "Type::Address",
+        "Type::Boolean",
+        "Type::Char",
+        "Type::Field",
+        "Type::Group",
+        "Type::IntegerType",
+        "Type::Array",
+        "Type::Tuple",
+        "Type::Identifier",
+        "Type::SelfType",
+        "Type::Err",
+    ];
+
+    const BINARY_OPERATIONS: &[BinaryOperation] = &[
+        BinaryOperation::Add,
+        BinaryOperation::Sub,
+        BinaryOperation::Mul,
+        BinaryOperation::Div,
+        BinaryOperation::Pow,
+        BinaryOperation::Or,
+        BinaryOperation::And,
+        BinaryOperation::Eq,
+        BinaryOperation::Ne,
+        BinaryOperation::Ge,
+        BinaryOperation::Gt,
+        BinaryOperation::Le,
+        BinaryOperation::Lt,
+        BinaryOperation::BitOr,
+        BinaryOperation::BitAnd,
+        BinaryOperation::BitXor,
+        BinaryOperation::Shr,
+        BinaryOperation::ShrSigned,
+        BinaryOperation::Shl,
+        BinaryOperation::Mod,
+    ];
+
+    const UNARY_OPERATIONS: &[UnaryOperation] = &[UnaryOperation::Not, UnaryOperation::Negate, UnaryOperation::BitNot];
+
+    const ASSIGN_OPERATIONS: &[AssignOperation] = &[
+        AssignOperation::Assign,
+        AssignOperation::Add,
+        AssignOperation::Sub,
+        AssignOperation::Mul,
+        AssignOperation::Div,
+        AssignOperation::Pow,
+        AssignOperation::Or,
+        AssignOperation::And,
+        AssignOperation::BitOr,
+        AssignOperation::BitAnd,
+        AssignOperation::BitXor,
+        AssignOperation::Shr,
+        AssignOperation::ShrSigned,
+        AssignOperation::Shl,
+        AssignOperation::Mod,
+    ];
+
+    fn expression_variant(expression: &Expression) -> &'static str {
+        match expression {
+            Expression::Identifier(_) => "Expression::Identifier",
+            Expression::Value(_) => "Expression::Value",
+            Expression::Binary(_) => "Expression::Binary",
+            Expression::Unary(_) => "Expression::Unary",
+            Expression::Ternary(_) => "Expression::Ternary",
+            Expression::Cast(_) => "Expression::Cast",
+            Expression::LengthOf(_) => "Expression::LengthOf",
+            Expression::ArrayInline(_) => "Expression::ArrayInline",
+            Expression::ArrayInit(_) => "Expression::ArrayInit",
+            Expression::ArrayComprehension(_) => "Expression::ArrayComprehension",
+            Expression::ArrayAccess(_) => "Expression::ArrayAccess",
+            Expression::ArrayRangeAccess(_) => "Expression::ArrayRangeAccess",
+            Expression::TupleInit(_) => "Expression::TupleInit",
+            Expression::TupleAccess(_) => "Expression::TupleAccess",
+            Expression::CircuitInit(_) => "Expression::CircuitInit",
+            Expression::CircuitMemberAccess(_) => "Expression::CircuitMemberAccess",
+            Expression::CircuitStaticFunctionAccess(_) => "Expression::CircuitStaticFunctionAccess",
+            Expression::Call(_) => "Expression::Call",
+            Expression::Parenthesized(_) => "Expression::Parenthesized",
+            Expression::Err(_) => "Expression::Err",
+        }
+    }
+
+    fn value_variant(value: &ValueExpression) -> &'static str {
+        match value {
+            ValueExpression::Address(..) => "ValueExpression::Address",
+            ValueExpression::Boolean(..) => "ValueExpression::Boolean",
+            ValueExpression::Char(_) => "ValueExpression::Char",
+            ValueExpression::Field(..) => "ValueExpression::Field",
+            ValueExpression::Group(_) => "ValueExpression::Group",
+            ValueExpression::Implicit(..) => "ValueExpression::Implicit",
+            ValueExpression::Integer(..) => "ValueExpression::Integer",
+            ValueExpression::String(..) => "ValueExpression::String",
+        }
+    }
+
+    fn binary_variant(operation: &BinaryOperation) -> &'static str {
+        match operation {
+            BinaryOperation::Add => "BinaryOperation::Add",
+            BinaryOperation::Sub => "BinaryOperation::Sub",
+            BinaryOperation::Mul => "BinaryOperation::Mul",
+            BinaryOperation::Div => "BinaryOperation::Div",
+            BinaryOperation::Pow => "BinaryOperation::Pow",
+            BinaryOperation::Or => "BinaryOperation::Or",
+            BinaryOperation::And => "BinaryOperation::And",
+            BinaryOperation::Eq => "BinaryOperation::Eq",
+            BinaryOperation::Ne => "BinaryOperation::Ne",
+            BinaryOperation::Ge => "BinaryOperation::Ge",
+            BinaryOperation::Gt => "BinaryOperation::Gt",
+            BinaryOperation::Le => "BinaryOperation::Le",
+            BinaryOperation::Lt => "BinaryOperation::Lt",
+            BinaryOperation::BitOr => "BinaryOperation::BitOr",
+            BinaryOperation::BitAnd => "BinaryOperation::BitAnd",
+            BinaryOperation::BitXor => "BinaryOperation::BitXor",
+            BinaryOperation::Shr => "BinaryOperation::Shr",
+            BinaryOperation::ShrSigned => "BinaryOperation::ShrSigned",
+            BinaryOperation::Shl => "BinaryOperation::Shl",
+            BinaryOperation::Mod => "BinaryOperation::Mod",
+        }
+    }
+
+    fn unary_variant(operation: &UnaryOperation) -> &'static str {
+        match operation {
+            UnaryOperation::Not => "UnaryOperation::Not",
+            UnaryOperation::Negate => "UnaryOperation::Negate",
+            UnaryOperation::BitNot => "UnaryOperation::BitNot",
+        }
+    }
+
+    fn statement_variant(statement: &Statement) -> &'static str {
+        match statement {
+            Statement::Return(_) => "Statement::Return",
+            Statement::Definition(_) => "Statement::Definition",
+            Statement::Assign(_) => "Statement::Assign",
+            Statement::Conditional(_) => "Statement::Conditional",
+            Statement::Iteration(_) => "Statement::Iteration",
+            Statement::Console(_) => "Statement::Console",
+            Statement::Expression(_) => "Statement::Expression",
+            Statement::Block(_) => "Statement::Block",
+        }
+    }
+
+    fn console_variant(function: &ConsoleFunction) -> &'static str {
+        match function {
+            ConsoleFunction::Assert(_) => "ConsoleFunction::Assert",
+            ConsoleFunction::AssertEq(_) => "ConsoleFunction::AssertEq",
+            ConsoleFunction::AssertNe(_) => "ConsoleFunction::AssertNe",
+            ConsoleFunction::Error(_) => "ConsoleFunction::Error",
+            ConsoleFunction::Log(_) => "ConsoleFunction::Log",
+        }
+    }
+
+    fn assign_variant(operation: &AssignOperation) -> &'static str {
+        match operation {
+            AssignOperation::Assign => "AssignOperation::Assign",
+            AssignOperation::Add => "AssignOperation::Add",
+            AssignOperation::Sub => "AssignOperation::Sub",
+            AssignOperation::Mul => "AssignOperation::Mul",
+            AssignOperation::Div => "AssignOperation::Div",
+            AssignOperation::Pow => "AssignOperation::Pow",
+            AssignOperation::Or => "AssignOperation::Or",
+            AssignOperation::And => "AssignOperation::And",
+            AssignOperation::BitOr => "AssignOperation::BitOr",
+            AssignOperation::BitAnd => "AssignOperation::BitAnd",
+            AssignOperation::BitXor => "AssignOperation::BitXor",
+            AssignOperation::Shr => "AssignOperation::Shr",
+            AssignOperation::ShrSigned => "AssignOperation::ShrSigned",
+            AssignOperation::Shl => "AssignOperation::Shl",
+            AssignOperation::Mod => "AssignOperation::Mod",
+        }
+    }
+
+    fn access_variant(access: &AssigneeAccess) -> &'static str {
+        match access {
+            AssigneeAccess::ArrayRange(..) => "AssigneeAccess::ArrayRange",
+            AssigneeAccess::ArrayIndex(_) => "AssigneeAccess::ArrayIndex",
+            AssigneeAccess::Tuple(..) => "AssigneeAccess::Tuple",
+            AssigneeAccess::Member(_) => "AssigneeAccess::Member",
+        }
+    }
+
+    fn type_variant(type_: &Type) -> &'static str {
+        match type_ {
+            Type::Address => "Type::Address",
+            Type::Boolean => "Type::Boolean",
+            Type::Char => "Type::Char",
+            Type::Field => "Type::Field",
+            Type::Group => "Type::Group",
+            Type::IntegerType(_) => "Type::IntegerType",
+            Type::Array(..) => "Type::Array",
+            Type::Tuple(_) => "Type::Tuple",
+            Type::Identifier(_) => "Type::Identifier",
+            Type::SelfType => "Type::SelfType",
+            Type::Err(_) => "Type::Err",
+        }
+    }
+
+    /// The variants built by the samples.
+    #[derive(Default)]
+    struct Coverage(IndexSet<&'static str>);
+
+    impl Coverage {
+        fn program(&mut self, program: &Program) {
+            for expression in program.iter_expressions(Traversal::PreOrder) {
+                self.expression(expression);
+            }
+            for statement in program.iter_statements(Traversal::PreOrder) {
+                self.statement(statement);
+            }
+            for function in program.functions.values() {
+                for input in function.input.iter() {
+                    if let FunctionInput::Variable(variable) = input {
+                        self.type_(&variable.type_);
+                    }
+                }
+                if let Some(output) = &function.output {
+                    self.type_(output);
+                }
+            }
+        }
+
+        fn expression(&mut self, expression: &Expression) {
+            self.0.insert(expression_variant(expression));
+            match expression {
+                Expression::Value(value) => {
+                    self.0.insert(value_variant(value));
+                }
+                Expression::Binary(binary) => {
+                    self.0.insert(binary_variant(&binary.op));
+                }
+                Expression::Unary(unary) => {
+                    self.0.insert(unary_variant(&unary.op));
+                }
+                Expression::Cast(cast) => self.type_(&cast.target_type),
+                _ => (),
+            }
+        }
+
+        fn statement(&mut self, statement: &Statement) {
+            self.0.insert(statement_variant(statement));
+            match statement {
+                Statement::Definition(definition) => {
+                    if let Some(type_) = &definition.type_ {
+                        self.type_(type_);
+                    }
+                }
+                Statement::Assign(assign) => {
+                    self.0.insert(assign_variant(&assign.operation));
+                    for access in assign.assignee.accesses.iter() {
+                        self.0.insert(access_variant(access));
+                    }
+                }
+                Statement::Console(console) => {
+                    self.0.insert(console_variant(&console.function));
+                }
+                _ => (),
+            }
+        }
+
+        fn type_(&mut self, type_: &Type) {
+            self.0.insert(type_variant(type_));
+            match type_ {
+                Type::Array(element, _) => self.type_(element),
+                Type::Tuple(types) => {
+                    for type_ in types.iter() {
+                        self.type_(type_);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn u32_type() -> Type {
+        Type::IntegerType(IntegerType::U32)
+    }
+
+    fn array_type() -> Type {
+        Type::Array(Box::new(u32_type()), Some(ArrayDimensions(vec![number(4)])))
+    }
+
+    fn tuple_type() -> Type {
+        Type::Tuple(vec![u32_type(), Type::Boolean])
+    }
+
+    fn number(value: usize) -> PositiveNumber {
+        PositiveNumber {
+            value: value.to_string().into(),
+        }
+    }
+
+    fn index(value: u128) -> Expression {
+        expr::int(value, IntegerType::U32)
+    }
+
+    fn a() -> Expression {
+        expr::ident("a")
+    }
+
+    fn flag() -> Expression {
+        expr::ident("flag")
+    }
+
+    fn character() -> Expression {
+        Expression::Value(ValueExpression::Char(CharValue {
+            character: Char::Scalar('a'),
+            span: span(),
+        }))
+    }
+
+    fn group() -> Expression {
+        Expression::Value(ValueExpression::Group(Box::new(GroupValue::Single("0".into(), span()))))
+    }
+
+    fn placeholder() -> ErrNode {
+        ErrNode {
+            span: span(),
+            text: Some("?".to_string()),
+        }
+    }
+
+    fn array_init(element: Expression) -> Expression {
+        Expression::ArrayInit(ArrayInitExpression {
+            element: Box::new(element),
+            dimensions: ArrayDimensions(vec![number(4)]),
+            span: span(),
+        })
+    }
+
+    fn range(array: Expression, left: Option<u128>, right: Option<u128>) -> Expression {
+        Expression::ArrayRangeAccess(ArrayRangeAccessExpression {
+            array: Box::new(array),
+            left: left.map(|left| Box::new(index(left))),
+            right: right.map(|right| Box::new(index(right))),
+            span: span(),
+        })
+    }
+
+    fn define(value: Expression) -> Statement {
+        stmt::let_("sample", None, value)
+    }
+
+    fn assign_access(access: AssigneeAccess, value: Expression) -> Statement {
+        Statement::Assign(Box::new(AssignStatement {
+            operation: AssignOperation::Assign,
+            assignee: Assignee {
+                identifier: ident("sample"),
+                accesses: vec![access],
+                span: span(),
+            },
+            value,
+            span: span(),
+        }))
+    }
+
+    fn console(function: ConsoleFunction) -> Statement {
+        Statement::Console(ConsoleStatement { function, span: span() })
+    }
+
+    fn console_args() -> ConsoleArgs {
+        ConsoleArgs {
+            string: "{}".chars().map(Char::Scalar).collect(),
+            parameters: vec![a()],
+            span: span(),
+        }
+    }
+
+    ///
+    /// Builds a program whose `check` function runs the statements with `a: u32`, `flag: bool`,
+    /// `values: [u32; 4]`, `pair: (u32, bool)` and `point: Point` in scope.
+    ///
+    fn harness_program(statements: Vec<Statement>) -> Program {
+        let point = circuit::new("Point")
+            .variable("x", u32_type())
+            .function(
+                func::new("origin")
+                    .returns(Type::SelfType)
+                    .body(vec![stmt::return_(expr::circuit("Self", vec![("x", index(0))]))]),
+            )
+            .function(
+                func::new("get")
+                    .self_input()
+                    .returns(u32_type())
+                    .body(vec![stmt::return_(expr::member(expr::ident("self"), "x"))]),
+            );
+        let check = func::new("check")
+            .input("a", u32_type())
+            .input("flag", Type::Boolean)
+            .input("values", array_type())
+            .input("pair", tuple_type())
+            .input("point", Type::Identifier(ident("Point")))
+            .body(statements);
+        let main = func::new("main")
+            .input("a", u32_type())
+            .input("flag", Type::Boolean)
+            .returns(u32_type())
+            .body(vec![
+                stmt::expression(expr::call(
+                    expr::ident("check"),
+                    vec![
+                        a(),
+                        flag(),
+                        array_init(a()),
+                        expr::tuple(vec![a(), flag()]),
+                        expr::circuit("Point", vec![("x", a())]),
+                    ],
+                )),
+                stmt::return_(a()),
+            ]);
+        program().circuit(point).function(check).function(main).build()
+    }
+
+    /// Returns the samples, each the body of the `check` function of [`harness_program`].
+    fn samples() -> Vec<Vec<Statement>> {
+        let mut expressions = vec![
+            a(),
+            expr::address(ADDRESS),
+            expr::boolean(true),
+            character(),
+            expr::field(1),
+            group(),
+            Expression::Value(ValueExpression::Group(Box::new(GroupValue::Tuple(GroupTuple {
+                x: GroupCoordinate::Number("0".into(), span()),
+                y: GroupCoordinate::SignHigh,
+                span: span(),
+            })))),
+            expr::implicit(1),
+            index(1),
+            Expression::Value(ValueExpression::String(
+                "ab".chars().map(Char::Scalar).collect(),
+                span(),
+            )),
+            expr::ternary(flag(), a(), a()),
+            expr::cast(a(), Type::IntegerType(IntegerType::U64)),
+            Expression::LengthOf(LengthOfExpression {
+                inner: Box::new(expr::ident("values")),
+                span: span(),
+            }),
+            expr::array(vec![a(), a()]),
+            Expression::ArrayInline(ArrayInlineExpression {
+                elements: vec![
+                    SpreadOrExpression::Spread(expr::ident("values")),
+                    SpreadOrExpression::Expression(a()),
+                ],
+                span: span(),
+            }),
+            array_init(a()),
+            Expression::ArrayComprehension(ArrayComprehensionExpression {
+                element: Box::new(expr::ident("i")),
+                variable: ident("i"),
+                start: Box::new(index(0)),
+                stop: Box::new(index(4)),
+                inclusive: false,
+                span: span(),
+            }),
+            expr::array_access(expr::ident("values"), index(1)),
+            range(expr::ident("values"), Some(1), Some(3)),
+            range(expr::ident("values"), None, None),
+            expr::tuple(vec![a(), flag()]),
+            Expression::TupleAccess(TupleAccessExpression {
+                tuple: Box::new(expr::ident("pair")),
+                index: number(0),
+                span: span(),
+            }),
+            expr::circuit("Point", vec![("x", a())]),
+            expr::member(expr::ident("point"), "x"),
+            expr::call(
+                Expression::CircuitStaticFunctionAccess(CircuitStaticFunctionAccessExpression {
+                    circuit: Box::new(expr::ident("Point")),
+                    name: ident("origin"),
+                    span: span(),
+                }),
+                vec![],
+            ),
+            expr::call(expr::member(expr::ident("point"), "get"), vec![]),
+            expr::parenthesized(expr::binary(a(), BinaryOperation::Add, a())),
+            Expression::Err(placeholder()),
+        ];
+        for operation in BINARY_OPERATIONS {
+            let (left, right) = match operation {
+                BinaryOperation::Or | BinaryOperation::And => (flag(), flag()),
+                _ => (a(), a()),
+            };
+            expressions.push(expr::binary(left, operation.clone(), right));
+        }
+        for operation in UNARY_OPERATIONS {
+            let inner = match operation {
+                UnaryOperation::Not => flag(),
+                UnaryOperation::Negate | UnaryOperation::BitNot => a(),
+            };
+            expressions.push(expr::unary(operation.clone(), inner));
+        }
+        let mut samples = expressions
+            .into_iter()
+            .map(|expression| vec![define(expression)])
+            .collect::<Vec<_>>();
+
+        for operation in ASSIGN_OPERATIONS {
+            let (initial, operand) = match operation {
+                AssignOperation::Or | AssignOperation::And => (flag(), flag()),
+                _ => (a(), a()),
+            };
+            samples.push(vec![define(initial), stmt::assign("sample", *operation, operand)]);
+        }
+        samples.push(vec![
+            define(expr::ident("values")),
+            assign_access(AssigneeAccess::ArrayIndex(index(1)), a()),
+        ]);
+        samples.push(vec![
+            define(expr::ident("values")),
+            assign_access(
+                AssigneeAccess::ArrayRange(Some(index(1)), Some(index(3))),
+                expr::array(vec![a(), a()]),
+            ),
+        ]);
+        samples.push(vec![
+            define(expr::ident("pair")),
+            assign_access(AssigneeAccess::Tuple(number(0), span()), a()),
+        ]);
+        samples.push(vec![
+            define(expr::ident("point")),
+            assign_access(AssigneeAccess::Member(ident("x")), a()),
+        ]);
+
+        samples.push(vec![stmt::return_(expr::tuple(vec![]))]);
+        samples.push(vec![stmt::const_("sample", Some(u32_type()), index(1))]);
+        samples.push(vec![stmt::if_(flag(), vec![define(a())], Some(vec![define(a())]))]);
+        samples.push(vec![stmt::for_(
+            "i",
+            index(0),
+            index(2),
+            vec![define(expr::ident("i"))],
+        )]);
+        samples.push(vec![console(ConsoleFunction::Assert(flag()))]);
+        samples.push(vec![console(ConsoleFunction::AssertEq(ConsoleAssertArgs {
+            left: a(),
+            right: a(),
+            span: span(),
+        }))]);
+        samples.push(vec![console(ConsoleFunction::AssertNe(ConsoleAssertArgs {
+            left: a(),
+            right: index(0),
+            span: span(),
+        }))]);
+        samples.push(vec![console(ConsoleFunction::Error(console_args()))]);
+        samples.push(vec![console(ConsoleFunction::Log(console_args()))]);
+        samples.push(vec![stmt::expression(expr::call(
+            expr::member(expr::ident("point"), "get"),
+            vec![],
+        ))]);
+        samples.push(vec![Statement::Block(stmt::block(vec![define(a())]))]);
+
+        let typed = vec![
+            (Type::Address, expr::address(ADDRESS)),
+            (Type::Boolean, flag()),
+            (Type::Char, character()),
+            (Type::Field, expr::field(1)),
+            (Type::Group, group()),
+            (u32_type(), a()),
+            (array_type(), expr::ident("values")),
+            (tuple_type(), expr::ident("pair")),
+            (Type::Identifier(ident("Point")), expr::ident("point")),
+            (Type::SelfType, expr::ident("point")),
+            (Type::Err(placeholder()), a()),
+        ];
+        for (type_, value) in typed {
+            samples.push(vec![stmt::let_("sample", Some(type_), value)]);
+        }
+        samples
+    }
+
+    /// Returns the options the samples are compiled under, between them enabling every optional pass.
+    fn harness_options() -> Vec<CompilerOptions> {
+        vec![
+            CompilerOptions::default(),
+            CompilerOptions {
+                constant_folding_enabled: false,
+                dead_code_elimination_enabled: false,
+                ..Default::default()
+            },
+            CompilerOptions {
+                fold_range_comparisons: true,
+                simplify_boolean_expressions: true,
+                keep_true_asserts: true,
+                renaming_enabled: true,
+                integer_widening: IntegerWidening::Lossless,
+                naming: conventional_naming(),
+                verify_ast: true,
+                emit_dot: true,
+                record_provenance: true,
+                ..Default::default()
+            },
+        ]
+    }
+
+    ///
+    /// Compiles the program into constraints, returning the message of the panic if a pass panicked.
+    ///
+    /// The result of a compilation that did not panic is returned as is, as an error is how a pass rejects a construct.
+    ///
+    fn compile(program: Program, options: CompilerOptions) -> std::result::Result<Result<()>, String> {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut compiler = test_compiler(options);
+            compiler.parse_program_from_ast(Ast::new(program))?;
+            synthesize(&mut compiler, HARNESS_INPUT)?;
+            Ok(())
+        }))
+        .map_err(|payload| panic_message(payload.as_ref()))
+    }
+
+    #[test]
+    fn test_samples_build_every_variant() {
+        let mut coverage = Coverage::default();
+        for sample in samples() {
+            coverage.program(&harness_program(sample));
+        }
+        let missing = VARIANTS
+            .iter()
+            .filter(|variant| !coverage.0.contains(*variant))
+            .collect::<Vec<_>>();
+        assert!(missing.is_empty(), "no sample builds {:?}", missing);
+    }
+
+    #[test]
+    fn test_passes_handle_or_reject_every_construct() {
+        let mut panics = vec![];
+        for sample in samples() {
+            let written = sample.iter().map(ToString::to_string).collect::<Vec<_>>().join(" ");
+            let program = harness_program(sample);
+            for options in harness_options() {
+                if let Err(message) = compile(program.clone(), options) {
+                    panics.push(format!("`{}` panicked: {}", written, message));
+                }
+            }
+        }
+        assert!(panics.is_empty(), "{}", panics.join("\n"));
+    }
+}
+
+const INLINING_PROGRAM: &str = r#"
+circuit Counter {
+    count: u32,
+
+    function next(self) -> u32 {
+        return self.count + 1;
+    }
+}
+
+@inline(always)
+function large(a: u32) -> u32 {
+    return a * 2 + a * 3 + a * 4;
+}
+
+function medium(a: u32) -> u32 {
+    return a * 2 + a * 3 + a * 4;
+}
+
+function small(a: u32) -> u32 {
+    return a + 1;
+}
+
+function main(a: u32) -> u32 {
+    let counter = Counter { count: a };
+    return large(a) + medium(a) + small(a) + small(a) + counter.next();
+}
+"#;
+
+#[test]
+fn test_inline_decisions() {
+    let options = CompilerOptions {
+        inline_threshold: 5,
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_string(INLINING_PROGRAM).unwrap();
+
+    let decisions = compiler.inline_decisions().unwrap();
+    assert_eq!(decisions.len(), 4);
+    assert_eq!(decisions["large"].reason, InlineReason::Annotated);
+    assert_eq!(decisions["medium"].reason, InlineReason::NoCallInstruction);
+    assert_eq!(decisions["small"].reason, InlineReason::BelowThreshold);
+    assert_eq!(decisions["small"].calls, 2);
+    assert_eq!(decisions["small"].size, 3);
+    assert_eq!(decisions["Counter::next"].reason, InlineReason::BelowThreshold);
+    assert!(!decisions.contains_key("main"));
+}
+
+#[test]
+fn test_inline_decisions_in_statistics() {
+    let result = compile_source(INLINING_PROGRAM, CompileOptions::default());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+
+    let decisions = result.statistics.inlining.as_ref().unwrap();
+    assert_eq!(decisions["medium"].reason, InlineReason::BelowThreshold);
+    assert!(result
+        .statistics
+        .to_string()
+        .contains("inlined functions: 1 annotated, 3 within the threshold, 0 above it"));
+}
+
+#[test]
+fn test_zero_inline_threshold() {
+    let options = CompilerOptions {
+        inline_threshold: 0,
+        ..Default::default()
+    };
+    let err = compile_with_options("function main(a: u32) -> u32 {\n    return a;\n}\n", options).unwrap_err();
+    assert!(err.to_string().contains("`inline_threshold` must be at least 1"));
+}
+
+/// Steps a session to the end, returning the number of steps and the last one.
+fn step_to_end(source: &str, options: CompileOptions) -> (usize, StepResult) {
+    let arena = leo_asg::new_alloc_context();
+    let context = leo_asg::new_context(&arena);
+    let mut session = CompileSession::start(context, source, options);
+    let mut steps = 1;
+    loop {
+        match session.step() {
+            StepResult::Continue => steps += 1,
+            ended => return (steps, ended),
+        }
+    }
+}
+
+fn ended_result(ended: StepResult) -> CompileResult {
+    match ended {
+        StepResult::Done(result) | StepResult::Failed(result) => *result,
+        StepResult::Continue => unreachable!(),
+    }
+}
+
+#[test]
+fn test_session_matches_compile_source() {
+    let (steps, ended) = step_to_end(SOURCE_PROGRAM, source_options());
+    assert!(steps > 1);
+    let stepped = match ended {
+        StepResult::Done(result) => *result,
+        other => panic!("{:?}", other),
+    };
+    let whole = compile_source(SOURCE_PROGRAM, source_options());
+
+    assert_eq!(stepped.diagnostics, whole.diagnostics);
+    assert_eq!(stepped.ast, whole.ast);
+    assert_eq!(stepped.symbols, whole.symbols);
+    assert_eq!(stepped.output, whole.output);
+    assert_eq!(messages(&stepped.logs), messages(&whole.logs));
+    assert_eq!(stepped.statistics.constraints, whole.statistics.constraints);
+    assert_eq!(
+        stepped.artifacts.keys().collect::<Vec<_>>(),
+        whole.artifacts.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_session_fails_after_first_step() {
+    let (steps, ended) = step_to_end("function main() -> u32 { return true; }", source_options());
+    assert!(steps > 1);
+    let result = match ended {
+        StepResult::Failed(result) => *result,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(result.diagnostics.len(), 1);
+    assert!(result.output.is_none());
+}
+
+#[test]
+fn test_session_steps_through_loop_chunks() {
+    let program = |bound: usize| {
+        format!(
+            "function main(a: u32) -> u32 {{\n    let b = a;\n    for i in 0..{} {{\n        b += 1;\n    }}\n    return b;\n}}\n",
+            bound
+        )
+    };
+    let (short_steps, short) = step_to_end(&program(16), source_options());
+    let (long_steps, long) = step_to_end(&program(4096), source_options());
+
+    // 4096 iterations are 16 chunks, one more step each than the single chunk of 16 iterations.
+    assert_eq!(long_steps, short_steps + 15);
+    assert!(ended_result(short).output.unwrap().contains("r: u32 = 18;"));
+    assert!(ended_result(long).output.unwrap().contains("r: u32 = 4098;"));
+}
+
+#[test]
+fn test_session_synthesizes_top_level_loop_like_nested_one() {
+    let top_level = "function main(a: u32) -> u32 {\n    let b = a;\n    for i in 0..300 {\n        b += 3;\n    }\n    return b;\n}\n";
+    let nested = "function main(a: u32) -> u32 {\n    let b = a;\n    {\n        for i in 0..300 {\n            b += 3;\n        }\n    }\n    return b;\n}\n";
+
+    let top_level = ended_result(step_to_end(top_level, source_options()).1);
+    let nested = ended_result(step_to_end(nested, source_options()).1);
+    assert!(top_level.is_ok(), "{:?}", top_level.diagnostics);
+    assert_eq!(top_level.output, nested.output);
+    assert_eq!(top_level.statistics.constraints, nested.statistics.constraints);
+}
+
+const REGISTERS_PROGRAM: &str = r#"
+type Byte = u8;
+
+circuit Point {
+    x: u8,
+    y: u8,
+}
+
+function main(a: u8) -> (bool, Point, Byte) {
+    return (true, Point { y: a, x: 1 }, a);
+}
+"#;
+
+fn registers_options(registers: &str) -> CompileOptions {
+    CompileOptions {
+        input: Some(format!("[main]\na: u8 = 5;\n\n[registers]\n{}", registers)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_registers_match_aliased_output() {
+    let program = "type Byte = u8;\n\nfunction main(a: u8) -> Byte {\n    return a;\n}\n";
+    let result = compile_source(program, registers_options("r0: u8 = 0;\n"));
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(result.output.unwrap(), "[registers]\nr0: u8 = 5;\n");
+}
+
+#[test]
+fn test_registers_match_flattened_output() {
+    // Every register a scalar, with the circuit flattened in declaration order.
+    let flattened = "r0: bool = false;\nr1: u8 = 0;\nr2: u8 = 0;\nr3: u8 = 0;\n";
+    let result = compile_source(REGISTERS_PROGRAM, registers_options(flattened));
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(
+        result.output.unwrap(),
+        "[registers]\nr0: bool = true;\nr1: u8 = 1;\nr2: u8 = 5;\nr3: u8 = 5;\n"
+    );
+
+    // A register may declare an aggregate of consecutive slots.
+    let aggregated = "r0: (bool, u8) = (false, 0);\nr1: (u8, u8) = (0, 0);\n";
+    let result = compile_source(REGISTERS_PROGRAM, registers_options(aggregated));
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert_eq!(
+        result.output.unwrap(),
+        "[registers]\nr0: (bool, u8) = (true, 1);\nr1: (u8, u8) = (5, 5);\n"
+    );
+}
+
+#[test]
+fn test_registers_mismatch_lists_slots() {
+    let registers = "r0: bool = false;\nr1: u8 = 0;\nr2: bool = false;\nr3: bool = false;\n";
+    let result = compile_source(REGISTERS_PROGRAM, registers_options(registers));
+    assert_eq!(result.diagnostics.len(), 1, "{:?}", result.diagnostics);
+    let error = &result.diagnostics[0];
+    assert!(error.contains("the `[registers]` section does not match the output of the entrypoint"));
+    assert!(
+        error.contains(
+            "- output.1.y: u8\n+ r2: bool (register `r2: bool`)\n- output.2: u8\n+ r3: bool (register `r3: bool`)"
+        ),
+        "{}",
+        error
+    );
+    assert!(!error.contains("output.1.x"), "{}", error);
+
+    // An aggregate that runs past the end of the output names the register it was declared by.
+    let registers = "r0: (bool, u8, u8) = (false, 0, 0);\nr1: (u8, u8) = (0, 0);\n";
+    let result = compile_source(REGISTERS_PROGRAM, registers_options(registers));
+    let error = &result.diagnostics[0];
+    assert!(
+        error.contains("- (no output slot)\n+ r1.1: u8 (register `r1: (u8, u8)`)"),
+        "{}",
+        error
+    );
+
+    let result = compile_source(REGISTERS_PROGRAM, registers_options("r0: bool = false;\n"));
+    assert!(
+        result.diagnostics[0].contains("number of input registers must be greater than or equal to output registers")
+    );
+}
+
+#[test]
+fn test_interface_output_slots() {
+    let interface = |registers: &str| {
+        let options = CompilerOptions {
+            entrypoints: vec!["main".to_string()],
+            ..Default::default()
+        };
+        let sink = MemorySink::new();
+        let mut compiler = test_compiler(options).with_output_writer(sink.clone());
+        compiler.parse_program_from_string(REGISTERS_PROGRAM).unwrap();
+        compiler
+            .add_entrypoint_input(
+                "main",
+                &format!("[main]\na: u8 = 5;\n\n[registers]\n{}", registers),
+                Path::new("main.in"),
+                "",
+                Path::new("state"),
+            )
+            .unwrap();
+        let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+        compiler.compile_entrypoint_constraints("main", &mut cs).unwrap();
+        let bytes = sink.get("main/test.interface.json").unwrap();
+        serde_json::from_slice::<Interface>(&INTERFACE_FORMAT.unstamp(&bytes)).unwrap()
+    };
+
+    let flattened = interface("r0: bool = false;\nr1: u8 = 0;\nr2: u8 = 0;\nr3: u8 = 0;\n");
+    let aggregated = interface("r0: (bool, u8) = (false, 0);\nr1: (u8, u8) = (0, 0);\n");
+    assert_eq!(flattened, aggregated);
+    let slots = flattened
+        .output_slots
+        .iter()
+        .map(|slot| format!("{}: {}", slot.path, slot.type_))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        slots,
+        vec!["output.0: bool", "output.1.x: u8", "output.1.y: u8", "output.2: u8"]
+    );
+}
+
+/// Signs with a keyed digest of the artifact, standing in for a real signature scheme.
+struct StubSigner;
+
+impl StubSigner {
+    fn signature(artifact_name: &str, bytes: &[u8]) -> Vec<u8> {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(b"stub key");
+        hasher.update(artifact_name.as_bytes());
+        hasher.update(bytes);
+        hasher.finalize().to_vec()
+    }
+}
+
+impl Signer for StubSigner {
+    fn sign(&self, artifact_name: &str, bytes: &[u8]) -> Result<Vec<u8>> {
+        Ok(Self::signature(artifact_name, bytes))
+    }
+}
+
+impl Verifier for StubSigner {
+    fn verify(&self, artifact_name: &str, bytes: &[u8], signature: &[u8]) -> bool {
+        Self::signature(artifact_name, bytes) == signature
+    }
+}
+
+#[test]
+fn test_signed_artifacts() {
+    let root = tempfile::tempdir().unwrap();
+    let output_dir = root.path().join("outputs");
+    let options = CompileOptions {
+        compiler: CompilerOptions {
+            signer: ArtifactSigner::new(std::sync::Arc::new(StubSigner)),
+            record_provenance: true,
+            ..Default::default()
+        },
+        output_directory: Some(output_dir.clone()),
+        ..source_options()
+    };
+    let result = compile_source(SOURCE_PROGRAM, options);
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.artifacts.contains_key("main.provenance.json.sig"));
+    assert!(result.artifacts.contains_key(SIGNATURE_MANIFEST));
+
+    let verified = verify_artifacts(&output_dir, &StubSigner).unwrap();
+    assert!(verified.contains(&"main.provenance.json".to_string()));
+    assert_eq!(
+        verified.len(),
+        result.artifacts.keys().filter(|name| name.ends_with(".sig")).count()
+    );
+
+    fs::write(output_dir.join("main.provenance.json"), b"{}").unwrap();
+    let error = verify_artifacts(&output_dir, &StubSigner).unwrap_err().to_string();
+    assert!(error.contains("main.provenance.json"), "{}", error);
+    assert!(error.contains("its digest differs from the manifest"), "{}", error);
+}
+
+#[test]
+fn test_unsigned_artifacts() {
+    let result = compile_source(SOURCE_PROGRAM, source_options());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(!result.artifacts.contains_key(SIGNATURE_MANIFEST));
+    assert!(!result.artifacts.keys().any(|name| name.ends_with(".sig")));
+}
+
+const STEP_PROGRAM: &str = r#"
+function main(a: u32) -> u32 {
+    let b = a;
+    for i in 0u32..10u32 step 3u32 {
+        b += i;
+    }
+    let c = 0i32;
+    for i in 10i32..=0i32 step -5i32 {
+        c += i;
+    }
+    console.assert(c == 15i32);
+    return b;
+}
+"#;
+
+#[test]
+fn test_loop_step() {
+    let result = compile_source(STEP_PROGRAM, source_options());
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: u32 = 20;"));
+
+    // The counter takes the values 0, 3, 6 and 9.
+    let provenance = provenance_of(STEP_PROGRAM);
+    let mut iterations = provenance
+        .frames
+        .iter()
+        .filter_map(|frame| match frame.kind {
+            FrameKind::Iteration(index, 3) => Some(index),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    iterations.dedup();
+    assert_eq!(iterations.last(), Some(&3));
+    assert!(provenance
+        .frames
+        .iter()
+        .any(|frame| frame.to_string().starts_with("unrolled iteration 3 of loop by 3")));
+}
+
+#[test]
+fn test_self_check() {
+    let report = self_check();
+    assert_eq!(
+        report.features.keys().copied().collect::<Vec<_>>(),
+        PROBES.iter().map(|probe| probe.feature).collect::<Vec<_>>()
+    );
+    assert_eq!(report.unsupported().count(), 0, "{}", report);
+    assert!(report.is_supported("loops"));
+    assert!(report.to_string().contains("conditionals  supported\n"));
+}
+
+#[test]
+fn test_self_check_unsupported_probe() {
+    // Comparing allocated groups is still `unimplemented!` in synthesis.
+    let probe = Probe {
+        feature: "group comparisons",
+        source: "function main(a: group, b: group) -> bool {\n    return a == b;\n}\n",
+        input: "[main]\na: group = 1group;\nb: group = 1group;\n\n[registers]\nr0: bool = false;\n",
+        expected_output: "r0: bool = true;",
+    };
+    match probe.run() {
+        FeatureStatus::Unsupported(reason) => {
+            assert!(reason.contains("the compiler panicked: not implemented"), "{}", reason)
+        }
+        FeatureStatus::Supported => panic!("allocated groups can be compared"),
+    }
+
+    let mut report = FeatureReport::default();
+    report.features.insert(probe.feature, probe.run());
+    assert!(!report.is_supported("group comparisons"));
+    assert!(report.to_string().starts_with("group comparisons  unsupported: "));
+}
+
+fn unit_compiler(main_file: &str, unit_name: Option<&str>, sink: &MemorySink) -> EdwardsTestCompiler {
+    let options = CompilerOptions {
+        unit_name: unit_name.map(str::to_string),
+        signer: ArtifactSigner::new(std::sync::Arc::new(StubSigner)),
+        ..Default::default()
+    };
+    let snapshots = AstSnapshotOptions {
+        initial: true,
+        ..Default::default()
+    };
+    test_compiler_with(make_test_context(), PathBuf::new(), options, snapshots)
+        .with_main_file(FileName::Real(main_file.into()))
+        .with_output_writer(sink.clone())
+}
+
+#[test]
+fn test_compilation_units_write_disjoint_trees() {
+    let sink = MemorySink::new();
+    let units = CompilationUnits::new();
+    let mut first = unit_compiler("src/alpha.leo", None, &sink)
+        .with_compilation_units(&units)
+        .unwrap();
+    let mut second = unit_compiler("src/main.leo", Some("beta"), &sink)
+        .with_compilation_units(&units)
+        .unwrap();
+    first
+        .parse_program_from_string("function main(a: u32) -> u32 { return a; }")
+        .unwrap();
+    second
+        .parse_program_from_string("function main(a: u8) -> u8 { return a; }")
+        .unwrap();
+
+    let artifacts = sink.artifacts();
+    assert!(artifacts
+        .keys()
+        .all(|name| name.starts_with("alpha/") || name.starts_with("beta/")));
+    for unit in ["alpha", "beta"].iter() {
+        assert!(artifacts.contains_key(&format!("{}/initial_ast.json", unit)));
+        assert!(artifacts.contains_key(&format!("{}/initial_ast.json.sig", unit)));
+        assert!(artifacts.contains_key(&format!("{}/{}", unit, SIGNATURE_MANIFEST)));
+    }
+    assert_ne!(artifacts["alpha/initial_ast.json"], artifacts["beta/initial_ast.json"]);
+
+    // The manifest of a unit lists its artifacts by their names within the unit.
+    let manifest = String::from_utf8(artifacts[&format!("alpha/{}", SIGNATURE_MANIFEST)].clone()).unwrap();
+    assert!(manifest.contains("\"initial_ast.json\""), "{}", manifest);
+    assert!(!manifest.contains("beta"), "{}", manifest);
+}
+
+#[test]
+fn test_compilation_unit_collision() {
+    let sink = MemorySink::new();
+    let units = CompilationUnits::new();
+    unit_compiler("first/main.leo", None, &sink)
+        .with_compilation_units(&units)
+        .unwrap();
+    let error = unit_compiler("second/main.leo", None, &sink)
+        .with_compilation_units(&units)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("compilation unit `main`"), "{}", error);
+    assert!(error.contains("first/main.leo"), "{}", error);
+    assert!(error.contains("second/main.leo"), "{}", error);
+
+    // Naming the unit of one of the programs resolves the collision.
+    unit_compiler("second/main.leo", Some("second"), &sink)
+        .with_compilation_units(&units)
+        .unwrap();
+    assert_eq!(units.units().keys().collect::<Vec<_>>(), vec!["main", "second"]);
+}
+
+#[test]
+fn test_default_unit_layout() {
+    let sink = MemorySink::new();
+    let mut compiler = unit_compiler("src/main.leo", None, &sink);
+    assert_eq!(compiler.unit_name(), "main");
+    compiler
+        .parse_program_from_string("function main(a: u32) -> u32 { return a; }")
+        .unwrap();
+
+    // A program compiled on its own keeps writing its artifacts at the root of the output directory.
+    let artifacts = sink.artifacts();
+    assert!(artifacts.contains_key("initial_ast.json"));
+    assert!(artifacts.contains_key(SIGNATURE_MANIFEST));
+    assert!(artifacts.keys().all(|name| !name.contains('/')));
+}
+
+#[test]
+fn test_folding_through_parentheses() {
+    let values = fold_calls(
+        r#"
+function main(a: u32) {
+    let x = (2u32 + 3u32) * 4u32;
+    let y = 2u32 + (3u32 * 4u32);
+    let z = ((7u8));
+    let w = -(128i8);
+    let v = (a + 1) * 2;
+}
+"#,
+        leo_asg_passes::DEFAULT_CALL_FUEL,
+    );
+
+    assert!(matches!(values[0], Some(ConstValue::Int(ConstInt::U32(20)))));
+    assert!(matches!(values[1], Some(ConstValue::Int(ConstInt::U32(14)))));
+    assert!(matches!(values[2], Some(ConstValue::Int(ConstInt::U8(7)))));
+    assert!(matches!(values[3], Some(ConstValue::Int(ConstInt::I8(-128)))));
+    assert!(values[4].is_none());
+
+    // The circuit computes the grouping that was written.
+    let result = compile_source(
+        "function main(a: u32) -> u32 {\n    return (a + 3) * (a - 1);\n}\n",
+        source_options(),
+    );
+    assert!(result.is_ok(), "{:?}", result.diagnostics);
+    assert!(result.output.unwrap().contains("r: u32 = 5;"));
+}
+
+/// Writes the files of a package under `src/` of `root` and returns the compiler of its main file.
+fn package_compiler(root: &Path, files: &[(&str, &str)]) -> EdwardsTestCompiler {
+    fs::create_dir_all(root.join("src")).unwrap();
+    for (file, source) in files {
+        fs::write(root.join("src").join(file), source).unwrap();
+    }
+    test_compiler_with(
+        make_test_context(),
+        root.join("outputs"),
+        CompilerOptions::default(),
+        AstSnapshotOptions::default(),
+    )
+    .with_main_file(FileName::Real(root.join("src").join("main.leo")))
+}
+
+#[test]
+fn test_import_cycle_names_its_imports() {
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = package_compiler(
+        package.path(),
+        &[
+            ("main.leo", "import foo.*;\nfunction main() {}\n"),
+            ("foo.leo", "import bar.*;\nfunction f() {}\n"),
+            ("bar.leo", "import foo.*;\nfunction g() {}\n"),
+        ],
+    );
+    let error = compiler.parse_program().unwrap_err().to_string();
+
+    assert!(
+        error.contains("program imports itself via `foo` -> `bar` -> `foo`"),
+        "{}",
+        error
+    );
+    assert!(error.contains("`foo` at "), "{}", error);
+    assert!(error.contains("main.leo:1:12-13"), "{}", error);
+    assert!(error.contains("foo.leo:1:12-13"), "{}", error);
+    assert!(error.contains("bar.leo:1:12-13"), "{}", error);
+}
+
+#[test]
+fn test_program_importing_itself() {
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = package_compiler(package.path(), &[("main.leo", "import main.*;\nfunction main() {}\n")]);
+    let error = compiler.parse_program().unwrap_err().to_string();
+
+    assert!(
+        error.contains("program imports itself via `main` -> `main`"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_repeated_imports_are_warnings() {
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = package_compiler(
+        package.path(),
+        &[
+            (
+                "main.leo",
+                "import foo.f;\nimport foo.f;\nimport foo.*;\nfunction main() {}\n",
+            ),
+            ("foo.leo", "function f() {}\n"),
+        ],
+    );
+    compiler.parse_program().unwrap();
+
+    let warnings = compiler
+        .warnings()
+        .iter()
+        .map(|warning| warning.message.clone())
+        .collect::<Vec<_>>();
+    assert!(
+        warnings
+            .iter()
+            .any(|warning| warning.starts_with("`foo.f` is imported twice, first at ")),
+        "{:?}",
+        warnings
+    );
+    assert!(
+        warnings
+            .iter()
+            .any(|warning| warning.starts_with("note: `foo.f` is also imported by `foo.*`")),
+        "{:?}",
+        warnings
+    );
+}
+
+const AUDIT_PROGRAM: &str = r#"const LIMIT: u32 = 10;
+
+circuit Point {
+    x: u32,
+    y: u32,
+
+    function sum(self) -> u32 {
+        return self.x + self.y;
+    }
+}
+
+function double(a: u32) -> u32 {
+    return a * 2;
+}
+
+function main(a: u32) -> u32 {
+    let p = Point { x: a, y: LIMIT };
+    let b = p.sum();
+    for i in 0..4 {
+        b += double(a);
+    }
+    console.assert(b > a);
+    return b;
+}
+"#;
+
+fn audit_compiler(options: CompilerOptions, sink: MemorySink) -> EdwardsTestCompiler {
+    let mut compiler = test_compiler(options).with_output_writer(sink);
+    compiler.parse_program_from_string(AUDIT_PROGRAM).unwrap();
+    compiler
+}
+
+#[test]
+fn test_audit_report_summarizes_program() {
+    let sink = MemorySink::new();
+    let compiler = audit_compiler(CompilerOptions::default(), sink.clone());
+    let report = compiler.write_audit_report().unwrap();
+
+    let entrypoints = report.entrypoints.content().unwrap();
+    assert_eq!(entrypoints.len(), 1);
+    assert_eq!(entrypoints[0].signature, "function main(a: u32) -> u32");
+
+    let functions = report.functions.content().unwrap();
+    let names = functions
+        .iter()
+        .map(|function| function.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 3, "{:?}", names);
+    assert!(
+        names.contains(&"Point::sum") && names.contains(&"double"),
+        "{:?}",
+        names
+    );
+
+    // The loop calls `double` once per iteration.
+    let estimates = report.estimates.content().unwrap();
+    let main = estimates.iter().find(|estimate| estimate.function == "main").unwrap();
+    let double = estimates.iter().find(|estimate| estimate.function == "double").unwrap();
+    assert!(main.total.instructions >= main.own.instructions + 4 * double.own.instructions);
+
+    let circuits = report.circuits.content().unwrap();
+    assert_eq!(circuits[0].name, "Point");
+    assert_eq!(circuits[0].slots, 2);
+    assert_eq!(circuits[0].functions, vec!["sum"]);
+
+    let constants = report.constants.content().unwrap();
+    assert_eq!(constants.global_consts[0].name, "LIMIT");
+    assert_eq!(constants.global_consts[0].value.as_deref(), Some("10u32"));
+
+    let asserts = report.asserts.content().unwrap();
+    assert_eq!(asserts.sites.len(), 1);
+    assert_eq!(asserts.sites[0].function, "main");
+    assert!(
+        asserts.sites[0].location.starts_with("compiler-test:22:5-"),
+        "{}",
+        asserts.sites[0].location
+    );
+
+    let checksum = compiler.checksum().unwrap();
+    assert_eq!(report.build.source_checksum.as_deref(), Some(&*checksum));
+
+    let markdown = String::from_utf8(sink.get("audit.md").unwrap()).unwrap();
+    assert_eq!(markdown, report.to_markdown());
+    for section in &[
+        "## Entrypoints",
+        "## Functions",
+        "## Estimates",
+        "## Circuits",
+        "## Constants",
+        "## Asserts",
+        "## Build",
+    ] {
+        assert!(markdown.contains(section), "{}", section);
+    }
+    assert!(markdown.contains("3 functions."));
+    assert!(markdown.contains(&asserts.sites[0].location));
+    assert!(markdown.contains(&checksum));
+    assert!(!markdown.contains("_Not available"));
+}
+
+/// Returns where the value does not follow the schema, for the keywords the schema of audit reports uses.
+fn schema_violations(schema: &serde_json::Value, value: &serde_json::Value, path: &str) -> Vec<String> {
+    let mut violations = vec![];
+    if let Some(types) = schema.get("type") {
+        let types = match types {
+            serde_json::Value::Array(types) => types.iter().filter_map(|type_| type_.as_str()).collect(),
+            type_ => vec![type_.as_str().unwrap()],
+        };
+        let matches = |type_: &str| match type_ {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "string" => value.is_string(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            type_ => panic!("unexpected type `{}`", type_),
+        };
+        if !types.into_iter().any(matches) {
+            violations.push(format!("{}: {} is not of type {}", path, value, schema["type"]));
+            return violations;
+        }
+    }
+    if let Some(values) = schema.get("enum").and_then(|values| values.as_array()) {
+        if !values.contains(value) {
+            violations.push(format!("{}: {} is not one of {:?}", path, value, values));
+        }
+    }
+    if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(|m| m.as_i64()), value.as_i64()) {
+        if number < minimum {
+            violations.push(format!("{}: {} is below {}", path, number, minimum));
+        }
+    }
+    if let (Some(items), Some(elements)) = (schema.get("items"), value.as_array()) {
+        for (index, element) in elements.iter().enumerate() {
+            violations.extend(schema_violations(items, element, &format!("{}[{}]", path, index)));
+        }
+    }
+    if let Some(members) = value.as_object() {
+        let properties = schema.get("properties").and_then(|properties| properties.as_object());
+        for required in schema.get("required").and_then(|r| r.as_array()).into_iter().flatten() {
+            if !members.contains_key(required.as_str().unwrap()) {
+                violations.push(format!("{}: {} is missing", path, required));
+            }
+        }
+        for (name, member) in members.iter() {
+            let path = format!("{}.{}", path, name);
+            match (
+                properties.and_then(|properties| properties.get(name)),
+                schema.get("additionalProperties"),
+            ) {
+                (Some(property), _) => violations.extend(schema_violations(property, member, &path)),
+                (None, Some(serde_json::Value::Bool(false))) => violations.push(format!("{} is not allowed", path)),
+                (None, Some(additional)) => violations.extend(schema_violations(additional, member, &path)),
+                (None, None) => (),
+            }
+        }
+    }
+    violations
+}
+
+#[test]
+fn test_audit_json_follows_its_schema() {
+    let schema = AuditReport::json_schema();
+
+    let sink = MemorySink::new();
+    audit_compiler(CompilerOptions::default(), sink.clone())
+        .write_audit_report()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&sink.get("audit.json").unwrap()).unwrap();
+    assert!(json.get(leo_ast::STAMP_KEY).is_some());
+    let violations = schema_violations(&schema, &json, "$");
+    assert!(violations.is_empty(), "{:#?}", violations);
+
+    // Unavailable sections follow it too.
+    let options = CompilerOptions {
+        estimate_costs: false,
+        constant_folding_enabled: false,
+        ..Default::default()
+    };
+    let report = audit_compiler(options, MemorySink::new()).audit_report();
+    let json: serde_json::Value = serde_json::from_str(&report.to_json_string().unwrap()).unwrap();
+    let violations = schema_violations(&schema, &json, "$");
+    assert!(violations.is_empty(), "{:#?}", violations);
+
+    // The schema does not allow members it does not know.
+    let mut json = json;
+    json["functions"]["extra"] = serde_json::json!(1);
+    assert_eq!(
+        schema_violations(&schema, &json, "$"),
+        vec!["$.functions.extra is not allowed"]
+    );
+}
+
+#[test]
+fn test_disabling_estimates_changes_only_their_section() {
+    let report = audit_compiler(CompilerOptions::default(), MemorySink::new()).audit_report();
+    let options = CompilerOptions {
+        estimate_costs: false,
+        ..Default::default()
+    };
+    let without_estimates = audit_compiler(options, MemorySink::new()).audit_report();
+
+    assert!(report.estimates.content().is_some());
+    assert_eq!(
+        without_estimates.estimates,
+        AuditSection::Unavailable {
+            reason: "cost estimation is disabled by the `estimate_costs` option".to_string()
+        }
+    );
+    assert!(without_estimates
+        .to_markdown()
+        .contains("_Not available: cost estimation is disabled by the `estimate_costs` option._"));
+
+    // The build records the options it was made with, and nothing else changes.
+    assert_ne!(report.build.options_digest, without_estimates.build.options_digest);
+    assert_eq!(
+        without_estimates
+            .build
+            .options
+            .get("estimate_costs")
+            .map(String::as_str),
+        Some("false")
+    );
+    let restored = AuditReport {
+        estimates: report.estimates.clone(),
+        build: report.build.clone(),
+        ..without_estimates
+    };
+    assert_eq!(restored, report);
+}
+
+const PLACEHOLDER_PROGRAM: &str = r#"
+function double(x: u32) -> u32 {
+    return x * 2;
+}
+
+function main(a: u32) -> u32 {
+    return double(a);
+}
+
+function broken(a: u32) -> u32 {
+    let b: u32 = a * / 3;
+    return b;
+}
+"#;
+
+#[test]
+fn test_function_with_placeholders_is_refused_alone() {
+    let (program, errors) = leo_parser::parse_recovering("test", PLACEHOLDER_PROGRAM);
+    assert_eq!(errors.len(), 1);
+
+    let options = CompilerOptions {
+        entrypoints: vec!["main".to_string(), "broken".to_string()],
+        verify_ast: true,
+        ..Default::default()
+    };
+    let mut compiler = test_compiler(options);
+    compiler.parse_program_from_ast(leo_ast::Ast::new(program)).unwrap();
+    for entrypoint in ["main", "broken"].iter() {
+        compiler
+            .add_entrypoint_input(
+                entrypoint,
+                "[main]\na: u32 = 3;\n\n[registers]\nr: u32 = 0;\n",
+                Path::new("input.in"),
+                "",
+                Path::new("state"),
+            )
+            .unwrap();
+    }
+    // The placeholder is counted apart from the expressions.
+    assert_eq!(compiler.metrics().unwrap().error_nodes, 1);
+
+    let sink = MemorySink::new();
+    let compiler = compiler.with_output_writer(sink.clone());
+    let results = compiler.compile_entrypoints();
+
+    // The healthy functions still compile.
+    assert!(results[0].1.is_ok(), "{:?}", results[0].1.as_ref().err());
+    assert!(sink.get("main/test.out").is_some());
+
+    let errors = results
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().err())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error.matches("contains unresolved errors").count(), 1, "{}", error);
+    assert!(
+        error.contains("function `broken` contains unresolved errors"),
+        "{}",
+        error
+    );
+}
+
+const EDITION_PROGRAM: &str = r#"function main(a: u32) -> u32 {
+    let b = [i * 2u32 for i in 0u32..3u32];
+    let c = a + b[2];
+    for i in 0u32..4u32 step 2u32 {
+        c += i;
+    }
+    return c;
+}
+"#;
+
+#[test]
+fn test_edition_gates_new_syntax() {
+    let older = CompilerOptions {
+        edition: Edition::E2021,
+        ..Default::default()
+    };
+    let error = compile_with_options(EDITION_PROGRAM, older.clone())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("array comprehensions require edition 2022"), "{}", error);
+
+    let output = compile_with_options(EDITION_PROGRAM, CompilerOptions::default()).unwrap();
+    assert_eq!(output.registers["r"].value, "8");
+
+    // Widening is checked by the type checker, once it is enabled.
+    let widening = CompilerOptions {
+        integer_widening: IntegerWidening::Lossless,
+        ..older
+    };
+    let error = compile_with_options(WIDENING_PROGRAM, widening.clone())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(
+        error.contains("mixed-width integer operations require edition 2022"),
+        "{}",
+        error
+    );
+    let widening = CompilerOptions {
+        edition: Edition::E2022,
+        ..widening
+    };
+    let output = compile_with_options(WIDENING_PROGRAM, widening).unwrap();
+    assert_eq!(output.registers["r"].value, "501");
+}
+
+/// Returns a compiler of `EDITION_PROGRAM` in a package whose `Leo.toml` declares `edition`, if given.
+fn edition_compiler(root: &Path, edition: Option<&str>) -> EdwardsTestCompiler {
+    let compiler = package_compiler(root, &[("main.leo", EDITION_PROGRAM)]);
+    let mut manifest = "[project]\nname = \"test\"\nversion = \"0.1.0\"\n".to_string();
+    if let Some(edition) = edition {
+        manifest += &format!("edition = \"{}\"\n", edition);
+    }
+    fs::write(root.join("Leo.toml"), manifest).unwrap();
+    compiler
+}
+
+fn edition_warnings(compiler: &EdwardsTestCompiler) -> Vec<String> {
+    compiler
+        .warnings()
+        .iter()
+        .map(|warning| warning.message.clone())
+        .filter(|message| message.contains("edition"))
+        .collect()
+}
+
+#[test]
+fn test_path_based_compile_warns_when_the_edition_is_defaulted() {
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = package_compiler(package.path(), &[("main.leo", EDITION_PROGRAM)]);
+    compiler.parse_program().unwrap();
+    assert_eq!(
+        edition_warnings(&compiler),
+        vec![
+            "no edition is declared, so the program is compiled against edition 2022; \
+             declare it as `edition` in the `[project]` of `Leo.toml`"
+        ]
+    );
+    let warning = compiler
+        .warnings()
+        .iter()
+        .find(|warning| warning.message.contains("edition"))
+        .unwrap();
+    assert!(warning.span.path.ends_with("main.leo"), "{}", warning);
+    assert_eq!(compiler.effective_options().edition, Edition::LATEST);
+
+    // A package without an edition is compiled like one without a `Leo.toml`.
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = edition_compiler(package.path(), None);
+    compiler.parse_program().unwrap();
+    assert_eq!(edition_warnings(&compiler).len(), 1);
+
+    // The string-based entry points compile against the latest edition without a warning.
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = package_compiler(package.path(), &[]);
+    compiler.parse_program_from_string(EDITION_PROGRAM).unwrap();
+    assert!(edition_warnings(&compiler).is_empty());
+}
+
+#[test]
+fn test_path_based_compile_reads_the_edition_of_its_package() {
+    let package = tempfile::tempdir().unwrap();
+    let mut compiler = edition_compiler(package.path(), Some("2022"));
+    compiler.parse_program().unwrap();
+    assert!(edition_warnings(&compiler).is_empty());
+
+    let mut compiler = edition_compiler(package.path(), Some("2021"));
+    let error = compiler.parse_program().unwrap_err().to_string();
+    assert!(error.contains("array comprehensions require edition 2022"), "{}", error);
+
+    // An edition given to the compiler takes precedence over that of the package.
+    let mut compiler = edition_compiler(package.path(), Some("2021")).with_edition(Edition::E2022);
+    compiler.parse_program().unwrap();
+    assert!(edition_warnings(&compiler).is_empty());
+
+    let mut compiler = edition_compiler(package.path(), Some("2020"));
+    let error = compiler.parse_program().unwrap_err().to_string();
+    assert!(error.contains("unknown edition `2020` declared in `"), "{}", error);
+    assert!(error.contains("the editions are `2021`, `2022`"), "{}", error);
+}
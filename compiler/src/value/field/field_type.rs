@@ -21,13 +21,16 @@ use leo_errors::{CompilerError, Result, Span};
 
 use snarkvm_fields::PrimeField;
 use snarkvm_gadgets::{
-    bits::{ToBitsBEGadget, ToBytesGadget},
+    bits::{ToBitsBEGadget, ToBitsLEGadget, ToBytesGadget},
     boolean::Boolean,
     fields::FpGadget,
     integers::uint::UInt8,
     traits::{
         alloc::AllocGadget,
-        bits::comparator::{ComparatorGadget, EvaluateLtGadget},
+        bits::{
+            comparator::{ComparatorGadget, EvaluateLtGadget},
+            Xor,
+        },
         eq::{ConditionalEqGadget, EqGadget, EvaluateEqGadget, NEqGadget},
         fields::FieldGadget,
         select::CondSelectGadget,
@@ -121,6 +124,34 @@ impl<F: PrimeField> FieldType<F> {
         self.mul(cs, &inverse, span)
     }
 
+    /// Returns the little-endian bits of the canonical representation of the field element.
+    pub fn to_bits_le<CS: ConstraintSystem<F>>(&self, cs: CS, span: &Span) -> Result<Vec<Boolean>> {
+        let bits = self
+            .0
+            .to_bits_le_strict(cs)
+            .map_err(|e| CompilerError::field_value_binary_operation("to_bits_le", e, span))?;
+
+        Ok(bits)
+    }
+
+    /// Returns a new `FieldType` whose value is the sum of `bits[i] * 2^i`, reduced modulo the field.
+    pub fn from_bits_le<CS: ConstraintSystem<F>>(mut cs: CS, bits: &[Boolean], span: &Span) -> Result<Self> {
+        let map_err = |e| CompilerError::field_value_binary_operation("from_bits_le", e, span);
+
+        let mut value = FpGadget::<F>::zero(cs.ns(|| "zero")).map_err(map_err)?;
+        let mut coefficient = F::one();
+        for (i, bit) in bits.iter().enumerate() {
+            let bit = FpGadget::from_boolean(cs.ns(|| format!("bit {}", i)), *bit).map_err(map_err)?;
+            let term = bit
+                .mul_by_constant(cs.ns(|| format!("scale bit {}", i)), &coefficient)
+                .map_err(map_err)?;
+            value = value.add(cs.ns(|| format!("add bit {}", i)), &term).map_err(map_err)?;
+            coefficient.double_in_place();
+        }
+
+        Ok(FieldType(value))
+    }
+
     pub fn alloc_helper<Fn: FnOnce() -> Result<T, SynthesisError>, T: Borrow<String>>(
         value_gen: Fn,
     ) -> Result<F, SynthesisError> {
@@ -174,8 +205,27 @@ impl<F: PrimeField> PartialOrd for FieldType<F> {
 }
 
 impl<F: PrimeField> EvaluateLtGadget<F> for FieldType<F> {
-    fn less_than<CS: ConstraintSystem<F>>(&self, _cs: CS, _other: &Self) -> Result<Boolean, SynthesisError> {
-        unimplemented!()
+    /// Compares the canonical big-endian bit representations of both field elements.
+    fn less_than<CS: ConstraintSystem<F>>(&self, mut cs: CS, other: &Self) -> Result<Boolean, SynthesisError> {
+        let self_bits = self.0.to_bits_be_strict(cs.ns(|| "self to bits"))?;
+        let other_bits = other.0.to_bits_be_strict(cs.ns(|| "other to bits"))?;
+
+        let mut result = Boolean::constant(false);
+        let mut equal_so_far = Boolean::constant(true);
+        for (i, (self_bit, other_bit)) in self_bits.iter().zip(other_bits.iter()).enumerate() {
+            let bit_less = Boolean::and(cs.ns(|| format!("bit less {}", i)), &self_bit.not(), other_bit)?;
+            let less_here = Boolean::and(cs.ns(|| format!("less at {}", i)), &equal_so_far, &bit_less)?;
+            result = Boolean::or(cs.ns(|| format!("result {}", i)), &result, &less_here)?;
+
+            let bits_differ = self_bit.xor(cs.ns(|| format!("bits differ {}", i)), other_bit)?;
+            equal_so_far = Boolean::and(
+                cs.ns(|| format!("equal so far {}", i)),
+                &equal_so_far,
+                &bits_differ.not(),
+            )?;
+        }
+
+        Ok(result)
     }
 }
 
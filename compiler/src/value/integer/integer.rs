@@ -91,6 +91,41 @@ impl Integer {
         match_integer!(integer => integer.to_bits_le())
     }
 
+    ///
+    /// Returns a new integer of the given type from its little-endian bits.
+    ///
+    pub fn from_bits_le(integer_type: &IntegerType, bits: &[Boolean]) -> Self {
+        match integer_type {
+            IntegerType::U8 => Integer::U8(UInt8::from_bits_le(bits)),
+            IntegerType::U16 => Integer::U16(UInt16::from_bits_le(bits)),
+            IntegerType::U32 => Integer::U32(UInt32::from_bits_le(bits)),
+            IntegerType::U64 => Integer::U64(UInt64::from_bits_le(bits)),
+            IntegerType::U128 => Integer::U128(UInt128::from_bits_le(bits)),
+
+            IntegerType::I8 => Integer::I8(Int8::from_bits_le(bits)),
+            IntegerType::I16 => Integer::I16(Int16::from_bits_le(bits)),
+            IntegerType::I32 => Integer::I32(Int32::from_bits_le(bits)),
+            IntegerType::I64 => Integer::I64(Int64::from_bits_le(bits)),
+            IntegerType::I128 => Integer::I128(Int128::from_bits_le(bits)),
+        }
+    }
+
+    ///
+    /// Returns the integer as the given type of the same signedness and at least its width.
+    ///
+    /// Unsigned integers are extended with zeros and signed integers with their sign bit,
+    /// so the value is kept without adding any constraints.
+    ///
+    pub fn widen(&self, integer_type: &IntegerType) -> Self {
+        let mut bits = self.get_bits();
+        let extension = match bits.last() {
+            Some(sign) if self.get_type().is_signed() => sign.clone(),
+            _ => Boolean::constant(false),
+        };
+        bits.resize(integer_type.bit_width(), extension);
+        Self::from_bits_le(integer_type, &bits)
+    }
+
     pub fn is_allocated(&self) -> bool {
         self.get_bits()
             .into_iter()
@@ -110,6 +145,14 @@ impl Integer {
         match_unsigned_integer!(unsigned_integer => unsigned_integer.value.map(|num| num.try_into().ok()).flatten())
     }
 
+    /// Returns the value of a constant integer of any type, if it fits in an `i128`.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.is_allocated() {
+            return None;
+        }
+        self.get_value()?.parse().ok()
+    }
+
     pub fn get_type(&self) -> IntegerType {
         match self {
             Integer::U8(_u8) => IntegerType::U8,
@@ -0,0 +1,209 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compiles the project in `tests/project` the way a user of the compiler would, through its public API only.
+
+use leo_asg::Position;
+use leo_ast::{ArtifactStamp, COMPILER_VERSION};
+use leo_compiler::{
+    compiler::{thread_leaked_context, Compiler},
+    group::targets::edwards_bls12::EdwardsGroupType,
+    AstSnapshotOptions, CompilerOptions, MemorySink,
+};
+use leo_errors::{AsgError, CompilerError, LeoError, Result, Span};
+use leo_synthesizer::CircuitSynthesizer;
+
+use indexmap::IndexMap;
+use snarkvm_curves::{bls12_377::Bls12_377, edwards_bls12::Fq};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+type ProjectCompiler = Compiler<'static, Fq, EdwardsGroupType>;
+
+/// Returns the directory of the project checked in next to this test.
+fn project() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("project")
+}
+
+fn main_file(root: &Path) -> PathBuf {
+    root.join("src").join("main.leo")
+}
+
+/// Returns a compiler of the project at `root` with every AST snapshot enabled, writing its artifacts to `sink`.
+fn project_compiler(root: &Path, sink: &MemorySink) -> ProjectCompiler {
+    let snapshots = AstSnapshotOptions {
+        spans_enabled: false,
+        initial: true,
+        imports_resolved: true,
+        canonicalized: true,
+        type_inferenced: true,
+        input: true,
+    };
+    let options = CompilerOptions {
+        record_provenance: true,
+        ..Default::default()
+    };
+    Compiler::new(
+        "project".to_string(),
+        main_file(root),
+        root.join("outputs"),
+        thread_leaked_context(),
+        Some(options),
+        IndexMap::new(),
+        Some(snapshots),
+    )
+    .with_output_writer(sink.clone())
+}
+
+/// Parses and checks the program of the project at `root`, then its input files.
+fn parse_project(compiler: &mut ProjectCompiler, root: &Path) -> Result<()> {
+    compiler.parse_program()?;
+    let inputs = root.join("inputs");
+    let (input, state) = (inputs.join("project.in"), inputs.join("project.state"));
+    compiler.parse_input(
+        &fs::read_to_string(&input).unwrap(),
+        input.as_path(),
+        &fs::read_to_string(&state).unwrap(),
+        state.as_path(),
+    )
+}
+
+/// Copies the project to a new directory, replacing `from` with `to` in the file at `path`.
+fn broken_copy(path: &str, from: &str, to: &str) -> tempfile::TempDir {
+    let copy = tempfile::tempdir().unwrap();
+    for directory in &["src", "inputs"] {
+        fs::create_dir_all(copy.path().join(directory)).unwrap();
+    }
+    for file in &["Leo.toml", "src/main.leo", "inputs/project.in", "inputs/project.state"] {
+        let mut content = fs::read_to_string(project().join(file)).unwrap();
+        if *file == path {
+            assert!(content.contains(from), "`{}` is not in `{}`", from, file);
+            content = content.replace(from, to);
+        }
+        fs::write(copy.path().join(file), content).unwrap();
+    }
+    copy
+}
+
+#[test]
+fn test_project_compiles_through_the_public_api() {
+    let root = project();
+    let sink = MemorySink::new();
+    let mut compiler = project_compiler(&root, &sink);
+    parse_project(&mut compiler, &root).unwrap();
+
+    // The project declares its edition and has nothing to warn about.
+    assert!(compiler.warnings().is_empty(), "{:?}", compiler.warnings());
+    assert!(compiler.artifact_warnings().is_empty());
+
+    // The statistics of the program are collected along the way.
+    let metrics = compiler.metrics().unwrap();
+    assert!(metrics.functions.contains_key("main"), "{:?}", metrics.functions.keys());
+    let estimates = compiler.cost_estimates().unwrap();
+    for function in &["main", "scaled", "Point::sum"] {
+        assert!(estimates.contains_key(*function), "{:?}", estimates.keys());
+    }
+
+    // The symbol table answers queries about the source: the call of `scaled` in the loop returns a `u32`.
+    let symbols = compiler.symbol_table().unwrap();
+    let call = Span::new(22, 22, 18, 27, Arc::new(String::new()), "".into());
+    let call = symbols.expr_at("main", &call).unwrap();
+    assert_eq!(call.type_.as_ref().unwrap().to_string(), "u32");
+    assert!(!symbols.expressions("Point::sum").is_empty());
+
+    // Definitions are found from a position in the source: `scaled` in the loop is the function declared above.
+    let file = main_file(&root).display().to_string();
+    let definition = compiler
+        .definition_at(&file, Position { line: 22, column: 18 })
+        .unwrap();
+    assert_eq!((definition.line_start, definition.col_start), (14, 10));
+    let local = compiler
+        .definition_at(&file, Position { line: 24, column: 20 })
+        .unwrap();
+    assert_eq!((local.line_start, local.col_start), (20, 9));
+
+    // 2 + 5, plus 3 times each of 0, 1, 2 and 3.
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    let output = compiler.compile_constraints(&mut cs).unwrap();
+    assert_eq!(output.registers["r"].value, "25");
+    let results = compiler.compile_entrypoints();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1.as_ref().unwrap().registers["r"].value, "25");
+    let report = compiler.write_audit_report().unwrap();
+    assert_eq!(report.build.source_checksum, Some(compiler.checksum().unwrap()));
+
+    let artifacts = sink.artifacts();
+    for name in &[
+        "effective_options.toml",
+        "initial_ast.json",
+        "imports_resolved_ast.json",
+        "canonicalization_ast.json",
+        "type_inferenced_ast.json",
+        "input_ast.json",
+        "project.provenance.json",
+        "main/project.out",
+        "main/project.interface.json",
+        "main/project.provenance.json",
+        "audit.md",
+        "audit.json",
+    ] {
+        assert!(
+            artifacts.contains_key(*name),
+            "`{}` is missing from {:?}",
+            name,
+            artifacts.keys()
+        );
+    }
+    // Every artifact besides the options and the rendered report is stamped by this compiler.
+    for (name, bytes) in artifacts.iter() {
+        if name.ends_with(".toml") || name.ends_with(".md") {
+            continue;
+        }
+        let stamp = ArtifactStamp::read(bytes).unwrap();
+        let stamp = stamp.unwrap_or_else(|| panic!("`{}` is not stamped", name));
+        assert_eq!(stamp.compiler, COMPILER_VERSION, "{}", name);
+    }
+}
+
+/// Returns the error code of `error` as a [`LeoError`].
+fn error_code(error: impl Into<LeoError>) -> String {
+    error.into().error_code()
+}
+
+#[test]
+fn test_broken_project_reports_error_codes() {
+    // A function returning the wrong type fails type checking.
+    let copy = broken_copy("src/main.leo", "return total;", "return total == a;");
+    let mut compiler = project_compiler(copy.path(), &MemorySink::new());
+    let error = parse_project(&mut compiler, copy.path()).unwrap_err();
+    assert_eq!(
+        error.error_code(),
+        error_code(AsgError::unexpected_type("u32", "bool", &Span::default()))
+    );
+    assert!(error.to_string().contains("main.leo:25:"), "{}", error);
+
+    // An edition that does not exist is reported before the program is read.
+    let copy = broken_copy("Leo.toml", "edition = \"2022\"", "edition = \"2020\"");
+    let mut compiler = project_compiler(copy.path(), &MemorySink::new());
+    let error = parse_project(&mut compiler, copy.path()).unwrap_err();
+    assert_eq!(
+        error.error_code(),
+        error_code(CompilerError::unknown_edition("2020", "Leo.toml", "`2021`, `2022`"))
+    );
+}
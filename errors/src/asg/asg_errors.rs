@@ -47,23 +47,23 @@ create_errors!(
     /// For when a user is initializing a circuit, and it's missing circuit member.
     @formatted
     missing_circuit_member {
-        args: (circuit_name: impl Display, name: impl Display),
+        args: (circuit_name: impl Display, name: impl Display, definition: impl Display),
         msg: format!(
             "missing circuit member '{}' for initialization of circuit '{}'",
             name, circuit_name
         ),
-        help: None,
+        help: Some(format!("circuit '{}' is defined at {}", circuit_name, definition)),
     }
 
     /// For when a user is initializing a circuit, and they declare a cirucit member twice.
     @formatted
     overridden_circuit_member {
-        args: (circuit_name: impl Display, name: impl Display),
+        args: (circuit_name: impl Display, name: impl Display, definition: impl Display),
         msg: format!(
             "cannot declare circuit member '{}' more than once for initialization of circuit '{}'",
             name, circuit_name
         ),
-        help: None,
+        help: Some(format!("circuit '{}' is defined at {}", circuit_name, definition)),
     }
 
     /// For when a user is defining a circuit, and they define a circuit member multiple times.
@@ -80,12 +80,12 @@ create_errors!(
     /// For when a user is initializing a circuit, and they add an extra circuit member.
     @formatted
     extra_circuit_member {
-        args: (circuit_name: impl Display, name: impl Display),
+        args: (circuit_name: impl Display, name: impl Display, definition: impl Display),
         msg: format!(
             "extra circuit member '{}' for initialization of circuit '{}' is not allowed",
             name, circuit_name
         ),
-        help: None,
+        help: Some(format!("circuit '{}' is defined at {}", circuit_name, definition)),
     }
 
     /// For when a user attempts to assign to a function.
@@ -474,4 +474,410 @@ create_errors!(
         msg: format!("operator '{}' is only allowed for type '{}', received: '{}'", operator, type_, received),
         help: None,
     }
+
+    /// For when a variable is read on a path where it may not have been assigned.
+    @formatted
+    variable_maybe_unassigned {
+        args: (name: impl Display, notes: impl Display),
+        msg: format!("variable `{}` is read before it is definitely assigned{}", name, notes),
+        help: None,
+    }
+
+    /// For when a circuit contains itself, directly or through an array or tuple.
+    @formatted
+    circuit_contains_itself {
+        args: (name: impl Display),
+        msg: format!("circuit `{}` contains itself and has no finite layout", name),
+        help: None,
+    }
+
+    /// For when a type nests arrays, tuples, and circuits too deeply to lay out.
+    @formatted
+    type_layout_too_deep {
+        args: (type_: impl Display, limit: impl Display),
+        msg: format!("type `{}` is nested more than {} levels deep", type_, limit),
+        help: None,
+    }
+
+    /// For when an ordering operator is applied to field or group elements.
+    @formatted
+    field_ordering_not_allowed {
+        args: (operator: impl Display, type_: impl Display),
+        msg: format!(
+            "operator '{}' is not allowed for type '{}', elements of a prime field have no meaningful order",
+            operator, type_
+        ),
+        help: Some("Annotate the function with `@allow_field_ordering` to compare field elements by their canonical representation. Group elements cannot be ordered.".to_string()),
+    }
+
+    /// For when `from_bits_le` is given an array whose length is not the width of the result type.
+    @formatted
+    from_bits_invalid_width {
+        args: (length: impl Display, expected: impl Display),
+        msg: format!("cannot convert an array of {} bits with `from_bits_le`, expected {}", length, expected),
+        help: None,
+    }
+
+    /// For when a circuit cannot be resolved, but a circuit with a similar name exists.
+    @formatted
+    unresolved_circuit_with_suggestion {
+        args: (name: impl Display, suggestion: impl Display),
+        msg: format!("failed to resolve circuit: '{}'", name),
+        help: Some(format!("did you mean '{}'?", suggestion)),
+    }
+
+    /// For when a field literal is not less than the field modulus and strict field literals are enabled.
+    @formatted
+    field_literal_out_of_range {
+        args: (value: impl Display, modulus: impl Display),
+        msg: format!("field literal `{}` is not less than the field modulus `{}`", value, modulus),
+        help: None,
+    }
+
+    /// For when no context determines the type of an untyped literal.
+    @formatted
+    ambiguous_literal_type {
+        args: (value: impl Display),
+        msg: format!("cannot infer the type of literal `{}`", value),
+        help: Some(format!(
+            "add a type suffix such as `{}u32` or a type annotation; candidates are u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, field and group",
+            value
+        )),
+    }
+
+    /// For when a destructuring definition declares a different number of variables than its value has outputs.
+    @formatted
+    destructuring_arity_mismatch {
+        args: (source: impl Display, found: impl Display, expected: impl Display),
+        msg: format!("{} has {} outputs, but {} variables are declared", source, found, expected),
+        help: None,
+    }
+
+    /// For when a constant array range bound lies outside of the array.
+    @formatted
+    array_range_out_of_bounds {
+        args: (bound: impl Display, value: impl Display, length: impl Display),
+        msg: format!(
+            "array range {} `{}` is out of bounds for an array of length {}",
+            bound, value, length
+        ),
+        help: None,
+    }
+
+    /// For when the start of a constant array range is greater than its stop.
+    @formatted
+    array_range_reversed {
+        args: (start: impl Display, stop: impl Display, length: impl Display),
+        msg: format!(
+            "array range start `{}` is greater than its stop `{}` for an array of length {}",
+            start, stop, length
+        ),
+        help: None,
+    }
+
+    /// For when the length of an array range cannot be determined at compile time.
+    @formatted
+    array_range_length_unknown {
+        args: (length: impl Display),
+        msg: format!(
+            "the length of a range of an array of length {} must be known at compile time",
+            length
+        ),
+        help: Some("Use constant bounds, or declare the type of the result so that only the start may vary.".to_string()),
+    }
+
+    /// For when a program reads or an input file sets a field an input state section does not have.
+    @formatted
+    unknown_input_field {
+        args: (section: impl Display, field: impl Display),
+        msg: format!("input section `[{}]` has no field `{}`", section, field),
+        help: Some("Custom record data fields are declared as members of `circuit Record`.".to_string()),
+    }
+
+    /// For when the `Record` circuit declares a built-in record field with a different type.
+    @formatted
+    record_field_redefined {
+        args: (field: impl Display, expected: impl Display, found: impl Display),
+        msg: format!(
+            "record field `{}` has the built-in type `{}`, but `circuit Record` declares it as `{}`",
+            field, expected, found
+        ),
+        help: None,
+    }
+
+    /// For when a function carries an annotation the compiler does not know.
+    @formatted
+    unknown_annotation {
+        args: (name: impl Display),
+        msg: format!("unknown annotation `@{}`", name),
+        help: Some("Functions may be annotated with `@test`, `@allow_field_ordering`, `@allow_naming`, `@budget` and `@inline`.".to_string()),
+    }
+
+    /// For when a function carries the same annotation more than once.
+    @formatted
+    duplicate_annotation {
+        args: (name: impl Display, first_line: impl Display),
+        msg: format!(
+            "annotation `@{}` is repeated; it was first applied on line {}",
+            name, first_line
+        ),
+        help: None,
+    }
+
+    /// For when an annotation is given a number of arguments it does not accept.
+    @formatted
+    annotation_argument_count {
+        args: (name: impl Display, expected: impl Display, found: impl Display),
+        msg: format!(
+            "annotation `@{}` takes {} argument(s), but {} were given",
+            name, expected, found
+        ),
+        help: None,
+    }
+
+    /// For when an array is indexed with a value of a signed integer type.
+    @formatted
+    signed_array_index {
+        args: (type_: impl Display),
+        msg: format!("array indices must be unsigned integers, but found `{}`", type_),
+        help: None,
+    }
+
+    /// For when an input section leaves out a field that the program declares.
+    @formatted
+    missing_input_field {
+        args: (section: impl Display, field: impl Display),
+        msg: format!("input section `[{}]` is missing the field `{}`", section, field),
+        help: None,
+    }
+
+    /// For when elements of an inline array do not have the type of the array's elements.
+    @formatted
+    mixed_array_elements {
+        args: (expected: impl Display, count: usize),
+        msg: format!(
+            "array elements must all have type `{}`, but {} element(s) do not",
+            expected, count
+        ),
+        help: None,
+    }
+
+    /// For when the element type of an empty inline array is not given by its context.
+    @formatted
+    empty_array_without_type {
+        args: (),
+        msg: "cannot infer the element type of an empty array",
+        help: Some("give the array a type, e.g. `let a: [u8; 0] = [];`".to_string()),
+    }
+
+    /// For when the program input is used in a function that declares its own variable named `input`.
+    @formatted
+    program_input_shadowed {
+        args: (),
+        msg: "the program input cannot be used in a function that declares its own variable named `input`",
+        help: Some("rename the variable to use the program input here".to_string()),
+    }
+
+    /// For when strings are concatenated with `+` and one side is not constant.
+    @formatted
+    string_concatenation_not_constant {
+        args: (),
+        msg: "strings can only be concatenated when both sides are constant",
+        help: Some("strings are fixed-size char arrays, so `+` joins them at compile time".to_string()),
+    }
+
+    /// For when a static access goes through an alias to a circuit without the accessed member.
+    @formatted
+    aliased_circuit_member_unresolved {
+        args: (alias: impl Display, circuit_name: impl Display, name: impl Display),
+        msg: format!("`{}` resolves to `{}`, which has no member `{}`", alias, circuit_name, name),
+        help: None,
+    }
+
+    /// For when a `@budget` annotation is not given a number of instructions.
+    @formatted
+    invalid_budget_annotation {
+        args: (argument: impl Display),
+        msg: format!("invalid budget `{}`; expected `instructions = N`", argument),
+        help: Some("for example, `@budget(instructions = 5000)`".to_string()),
+    }
+
+    /// For when a static circuit member is accessed through an instance of the circuit.
+    @formatted
+    static_member_accessed_through_instance {
+        args: (circuit_name: impl Display, name: impl Display),
+        msg: format!(
+            "`{}` is a static member of circuit `{}` and cannot be accessed through an instance",
+            name, circuit_name
+        ),
+        help: Some(format!("use `{}::{}` instead", circuit_name, name)),
+    }
+
+    /// For when the value of a static circuit member is not constant.
+    @formatted
+    static_member_not_const {
+        args: (circuit_name: impl Display, name: impl Display),
+        msg: format!(
+            "the value of static member `{}` of circuit `{}` must be constant",
+            name, circuit_name
+        ),
+        help: Some("static members are evaluated at compile time, from literals, constants and other statics".to_string()),
+    }
+
+    /// For when a field element is divided by a constant zero.
+    @formatted
+    field_division_by_zero {
+        args: (),
+        msg: "division of a field element by zero",
+        help: Some("zero has no inverse in the field, so the division can never succeed".to_string()),
+    }
+
+    /// For when two members of a circuit have the same name.
+    @formatted
+    duplicate_circuit_member {
+        args: (circuit_name: impl Display, name: impl Display, kind: impl Display, previous_kind: impl Display),
+        msg: format!(
+            "{} `{}` of circuit `{}` has the same name as a {} declared before it",
+            kind, name, circuit_name, previous_kind
+        ),
+        help: Some("variables, statics and functions of a circuit share one namespace, so each needs its own name".to_string()),
+    }
+
+    /// For when a circuit member is given a name the compiler reserves for itself.
+    @formatted
+    reserved_circuit_member_name {
+        args: (circuit_name: impl Display, name: impl Display),
+        msg: format!(
+            "`{}` is reserved by the compiler and cannot name a member of circuit `{}`",
+            name, circuit_name
+        ),
+        help: None,
+    }
+
+    /// For when the empty tuple is an element of a tuple type or value.
+    @formatted
+    unit_in_tuple {
+        args: (),
+        msg: "the empty tuple `()` cannot be an element of a tuple",
+        help: Some("an element of type `()` holds no value, so leave it out of the tuple".to_string()),
+    }
+
+    /// For when circuits contain each other, so none of them has a finite size.
+    @formatted
+    circuit_cycle {
+        args: (name: impl Display, path: impl Display),
+        msg: format!("circuit `{}` contains itself and has no finite layout: {}", name, path),
+        help: Some("a circuit can only hold values of circuits that do not hold it in turn".to_string()),
+    }
+
+    /// For when a member function called on an instance is given the wrong number of arguments.
+    @formatted
+    unexpected_receiver_call_argument_count {
+        args: (expected: impl Display, got: impl Display),
+        msg: format!("function call expected {} arguments plus receiver, got {}", expected, got),
+        help: None,
+    }
+
+    /// For when an operation mixes integers of one signedness but different widths.
+    @formatted
+    mixed_width_integer_operation {
+        args: (operation: impl Display, left: impl Display, right: impl Display),
+        msg: format!(
+            "operator `{}` cannot be applied to `{}` and `{}`, which differ in width",
+            operation, left, right
+        ),
+        help: Some(
+            "cast the narrower operand with `as`, or set the compiler option `integer_widening = \"lossless\"`"
+                .to_string()
+        ),
+    }
+
+    /// For when a `console.assert` that every run of the entrypoint reaches can never hold.
+    @formatted
+    assert_always_fails {
+        args: (condition: impl Display),
+        msg: format!("assertion always fails, as its condition is `{}`", condition),
+        help: Some("no input satisfies a circuit with an assertion that cannot hold".to_string()),
+    }
+
+    /// For when the ranges of the operand types decide that a `console.assert` that every run reaches fails.
+    @formatted
+    assert_always_fails_from_types {
+        args: (),
+        msg: "assertion always fails, as the ranges of its operand types decide its condition",
+        help: Some("no input satisfies a circuit with an assertion that cannot hold".to_string()),
+    }
+
+    /// For when an `@inline` annotation is given an argument other than `always` or `never`.
+    @formatted
+    invalid_inline_annotation {
+        args: (argument: impl Display),
+        msg: format!("invalid inlining `{}`; expected `always` or `never`", argument),
+        help: Some("for example, `@inline(always)`".to_string()),
+    }
+
+    /// For when a function is annotated with both `@inline(always)` and `@inline(never)`.
+    @formatted
+    conflicting_inline_annotations {
+        args: (first: impl Display, first_line: impl Display),
+        msg: format!(
+            "`@inline({})` on line {} contradicts this annotation",
+            first, first_line
+        ),
+        help: Some("keep one of the two annotations".to_string()),
+    }
+
+    /// For when a function annotated with `@inline(never)` is called, which can only be done by inlining it.
+    @formatted
+    inline_never_called {
+        args: (name: impl Display),
+        msg: format!(
+            "function `{}` is annotated with `@inline(never)`, but it is called here and must be inlined",
+            name
+        ),
+        help: Some(
+            "circuits have no call instruction, so every call is inlined; remove the annotation or the call"
+                .to_string()
+        ),
+    }
+
+    /// For when the step of a loop is zero.
+    @formatted
+    loop_step_zero {
+        args: (),
+        msg: "the step of a loop cannot be zero",
+        help: None,
+    }
+
+    /// For when the step of a loop heads away from its stop bound.
+    @formatted
+    loop_step_wrong_sign {
+        args: (step: impl Display, start: impl Display, stop: impl Display),
+        msg: format!("a loop from {} to {} cannot step by {}", start, stop, step),
+        help: Some("the step must be positive for ascending ranges and negative for descending ones".to_string()),
+    }
+
+    /// For when the ASG receives a placeholder left by error recovery.
+    @formatted
+    unresolved_placeholder {
+        args: (),
+        msg: "this code could not be parsed",
+        help: Some("fix the syntax errors reported for it first".to_string()),
+    }
+
+    /// For when a variable declared without an initializer has a type with no default value.
+    @formatted
+    uninitialized_declaration_type {
+        args: (type_: impl Display),
+        msg: format!("a variable of type `{}` must be declared with an initializer", type_),
+        help: None,
+    }
+
+    /// For when a user tries to assign to a const function parameter.
+    @formatted
+    const_parameter_assignment {
+        args: (name: impl Display),
+        msg: format!("illegal assignment to const parameter '{}'", name),
+        help: Some("parameters declared without `const` are copies of their arguments and can be assigned".to_string()),
+    }
 );
@@ -131,4 +131,113 @@ create_errors!(
         help: None,
     }
 
+    /// For when the rename map fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_rename_map_to_json_string {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert rename map to a json string {}", error),
+        help: None,
+    }
+
+    /// For when a JSON string fails to be represented as a rename map.
+    @backtraced
+    failed_to_read_json_string_to_rename_map {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert json string to a rename map {}", error),
+        help: None,
+    }
+
+    /// For when the rename map fails to be written to a file.
+    @backtraced
+    failed_to_write_rename_map_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to write rename map to file `{:?}` {}", path, error),
+        help: None,
+    }
+
+    /// For when expressions nest more deeply than the configured limit.
+    @formatted
+    expression_too_deep {
+        args: (limit: impl Display),
+        msg: format!("expression nests more than {} levels deep", limit),
+        help: Some("Split the expression into intermediate variables.".to_string()),
+    }
+
+    /// For when a compiler pass produces a program that breaks the structural invariants of its stage.
+    @backtraced
+    invariant_violated {
+        args: (pass: impl Display, stage: impl Display, violations: impl Display),
+        msg: format!("internal compiler error: pass '{}' produced an invalid {} program: {}", pass, stage, violations),
+        help: Some("This is a bug in the Leo compiler, please report it.".to_string()),
+    }
+
+    /// For when the range of an array comprehension is not made of integer constants.
+    @formatted
+    array_comprehension_non_const_range {
+        args: (),
+        msg: "the range of an array comprehension must be an integer constant",
+        help: None,
+    }
+
+    /// For when the range of an array comprehension produces no elements.
+    @formatted
+    array_comprehension_empty_range {
+        args: (start: impl Display, stop: impl Display),
+        msg: format!("array comprehension range {}..{} is empty", start, stop),
+        help: None,
+    }
+
+    /// For when an artifact was produced by a compiler whose artifact format is incompatible with this one.
+    @backtraced
+    incompatible_artifact_version {
+        args: (kind: impl Display, found: impl Display, expected: impl Display),
+        msg: format!(
+            "the {} artifact was produced by an incompatible version: {}, but this is {}",
+            kind, found, expected
+        ),
+        help: Some("Regenerate the artifact with this version of the compiler.".to_string()),
+    }
+
+    /// For when the version stamp of an artifact cannot be read.
+    @backtraced
+    invalid_artifact_stamp {
+        args: (kind: impl Display, error: impl ErrorArg),
+        msg: format!("the version stamp of the {} artifact is invalid: {}", kind, error),
+        help: None,
+    }
+
+    /// For when the expected input of a program does not match the parameters of its `main` function.
+    @backtraced
+    expected_input_mismatch {
+        args: (expected: impl Display, found: impl Display),
+        msg: format!(
+            "the program expects the input ({}), but `main` takes ({})",
+            expected, found
+        ),
+        help: Some("The program is stale; regenerate it from its source.".to_string()),
+    }
+
+    /// For when a span does not point into the source text it is applied to.
+    @formatted
+    span_outside_source {
+        args: (),
+        msg: "the span is not within the source text",
+        help: None,
+    }
+
+    /// For when two edits of a source text replace overlapping spans.
+    @formatted
+    overlapping_edits {
+        args: (other: impl Display),
+        msg: format!("the edit overlaps another edit at {}", other),
+        help: None,
+    }
+
+    /// For when a program uses a feature that the edition it is compiled against does not have.
+    @formatted
+    feature_requires_edition {
+        args: (feature: impl Display, edition: impl Display),
+        msg: format!("{} require edition {}", feature, edition),
+        help: Some(format!("Compile against edition {} or later to use them.", edition)),
+    }
 );
@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CompilerError, Result};
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The number of [`Checkpoint::tick`]s between two checks of the token.
+pub const CHECK_INTERVAL: usize = 1024;
+
+///
+/// Stops a compilation early, when it is cancelled or runs past its deadline.
+///
+/// Clones share the cancellation, so a clone can be cancelled from another thread while the compilation runs.
+/// The compilation only notices at its checks, which are made between phases and every [`CHECK_INTERVAL`]
+/// steps of the loops that can run for long, such as unrolling and constant evaluation.
+///
+/// The default token is never cancelled and has no deadline.
+///
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a token that also stops the compilation once `timeout` has passed from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Returns a token that also stops the compilation at `deadline`.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Cancels the compilation of this token and its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token was cancelled, regardless of its deadline.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    ///
+    /// Returns an error naming `phase` if the token was cancelled or its deadline has passed.
+    ///
+    /// The clock is only read if the token has a deadline.
+    ///
+    pub fn check(&self, phase: &str) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(CompilerError::cancelled(phase).into());
+        }
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(CompilerError::deadline_exceeded(phase).into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns a checkpoint for the steps of a loop within `phase`.
+    pub fn checkpoint(&self, phase: &'static str) -> Checkpoint {
+        Checkpoint {
+            token: self.clone(),
+            phase,
+            countdown: CHECK_INTERVAL,
+        }
+    }
+}
+
+///
+/// Checks a [`CancellationToken`] once every [`CHECK_INTERVAL`] steps of a loop, so that each step costs a decrement.
+///
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    token: CancellationToken,
+    phase: &'static str,
+    countdown: usize,
+}
+
+impl Checkpoint {
+    /// Counts a step, checking the token if it is the last of an interval.
+    pub fn tick(&mut self) -> Result<()> {
+        self.countdown -= 1;
+        if self.countdown > 0 {
+            return Ok(());
+        }
+        self.countdown = CHECK_INTERVAL;
+        self.token.check(self.phase)
+    }
+}
@@ -14,13 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{BacktracedError, Span, INDENT};
+use crate::{display_column, BacktracedError, Span, INDENT};
 
 use std::fmt;
 
 use backtrace::Backtrace;
 use color_backtrace::{BacktracePrinter, Verbosity};
 use colored::Colorize;
+use serde::Serialize;
+
+/// A secondary span of a formatted error, with a message describing its part in the error.
+///
+/// The span carries the path and source lines of its own file, which may differ from the primary span's.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
 
 /// Formatted compiler error type
 ///     undefined value `x`
@@ -35,6 +45,8 @@ use colored::Colorize;
 pub struct FormattedError {
     /// The formatted error span information.
     pub span: Span,
+    /// Secondary spans that explain the error, which may be in other files.
+    pub labels: Vec<Label>,
     /// The backtrace to track where the Leo error originated.
     pub backtrace: BacktracedError,
 }
@@ -55,6 +67,7 @@ impl FormattedError {
     {
         Self {
             span: span.clone(),
+            labels: Vec::new(),
             backtrace: BacktracedError::new_from_backtrace(
                 message.to_string(),
                 help,
@@ -66,6 +79,15 @@ impl FormattedError {
         }
     }
 
+    /// Adds a secondary label with its own span, which is rendered after the primary span.
+    pub fn with_label<S: ToString>(mut self, span: &Span, message: S) -> Self {
+        self.labels.push(Label {
+            span: span.clone(),
+            message: message.to_string(),
+        });
+        self
+    }
+
     /// Calls the backtraces error code.
     pub fn exit_code(&self) -> i32 {
         self.backtrace.exit_code()
@@ -77,29 +99,75 @@ impl FormattedError {
     }
 }
 
-impl fmt::Display for FormattedError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let underline = |mut start: usize, mut end: usize| -> String {
-            if start > end {
-                std::mem::swap(&mut start, &mut end)
-            }
+/// Returns the `^` markers under columns `start..end` of a snippet line.
+fn underline(mut start: usize, mut end: usize) -> String {
+    if start > end {
+        std::mem::swap(&mut start, &mut end)
+    }
 
-            let mut underline = String::new();
+    let mut underline = String::new();
 
-            for _ in 0..start {
-                underline.push(' ');
-                end -= 1;
-            }
+    for _ in 0..start {
+        underline.push(' ');
+        end -= 1;
+    }
 
-            for _ in 0..end {
-                underline.push('^');
-            }
+    for _ in 0..end {
+        underline.push('^');
+    }
 
-            underline
-        };
+    underline
+}
 
-        let underlined = underline(self.span.col_start, self.span.col_stop);
+/// Writes the `--> path:line:column` header that starts the snippets of a file.
+fn write_file_header(f: &mut fmt::Formatter, span: &Span) -> fmt::Result {
+    write!(
+        f,
+        "\n{indent     }--> {path}:{line_start}:{start}\n\
+        {indent     } |\n",
+        indent = INDENT,
+        path = &*span.path,
+        line_start = span.line_start,
+        start = span.col_start,
+    )
+}
 
+/// Writes the source lines of a span with the span underlined, followed by the label message if any.
+fn write_snippet(f: &mut fmt::Formatter, span: &Span, message: Option<&str>) -> fmt::Result {
+    let lines = span
+        .content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect::<Vec<_>>();
+    for (line_no, line) in lines.iter().enumerate() {
+        writeln!(
+            f,
+            "{line_no:width$} | {text}",
+            width = INDENT.len(),
+            line_no = span.line_start + line_no,
+            text = line,
+        )?;
+    }
+
+    // Columns count bytes, but the underline is drawn under characters.
+    let start = display_column(lines.first().copied().unwrap_or_default(), span.col_start);
+    let stop = display_column(lines.last().copied().unwrap_or_default(), span.col_stop);
+    write!(
+        f,
+        "{indent     } |{underlined}",
+        indent = INDENT,
+        underlined = underline(start, stop),
+    )?;
+
+    if let Some(message) = message {
+        write!(f, " {}", message)?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for FormattedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let error_message = format!(
             "Error [{error_code}]: {message}",
             error_code = self.error_code(),
@@ -118,32 +186,27 @@ impl fmt::Display for FormattedError {
             write!(f, "{}", error_message)?;
         };
 
-        write!(
-            f,
-            "\n{indent     }--> {path}:{line_start}:{start}\n\
-            {indent     } |\n",
-            indent = INDENT,
-            path = &*self.span.path,
-            line_start = self.span.line_start,
-            start = self.span.col_start,
-        )?;
+        write_file_header(f, &self.span)?;
+        write_snippet(f, &self.span, None)?;
 
-        for (line_no, line) in self.span.content.lines().enumerate() {
-            writeln!(
-                f,
-                "{line_no:width$} | {text}",
-                width = INDENT.len(),
-                line_no = self.span.line_start + line_no,
-                text = line,
-            )?;
+        // Secondary labels are grouped by file: the primary file first, then the others in order of appearance.
+        let mut files: Vec<&str> = vec![self.span.path.as_str()];
+        for label in self.labels.iter() {
+            if !files.contains(&label.span.path.as_str()) {
+                files.push(label.span.path.as_str());
+            }
+        }
+        for (index, file) in files.into_iter().enumerate() {
+            let labels = self.labels.iter().filter(|label| label.span.path.as_str() == file);
+            for (label_index, label) in labels.enumerate() {
+                if index > 0 && label_index == 0 {
+                    write_file_header(f, &label.span)?;
+                } else {
+                    write!(f, "\n{indent     } |\n", indent = INDENT)?;
+                }
+                write_snippet(f, &label.span, Some(&label.message))?;
+            }
         }
-
-        write!(
-            f,
-            "{indent     } |{underlined}",
-            indent = INDENT,
-            underlined = underlined,
-        )?;
 
         if let Some(help) = &self.backtrace.help {
             write!(
@@ -73,6 +73,32 @@ macro_rules! create_errors {
         }
 
 
+        impl $error_type {
+            /// Returns the span of the error, if it is a formatted error.
+            pub fn span(&self) -> Option<&Span> {
+                match self {
+                    Self::FormattedError(formatted) => Some(&formatted.span),
+                    Self::BacktracedError(_) => None,
+                }
+            }
+
+            /// Adds a secondary label to the error, if it is a formatted error.
+            pub fn with_label<S: ToString>(self, span: &Span, message: S) -> Self {
+                match self {
+                    Self::FormattedError(formatted) => Self::FormattedError(formatted.with_label(span, message)),
+                    error => error,
+                }
+            }
+
+            /// Returns the error message without its code or location.
+            pub fn message(&self) -> &str {
+                match self {
+                    Self::FormattedError(formatted) => &formatted.backtrace.message,
+                    Self::BacktracedError(backtraced) => &backtraced.message,
+                }
+            }
+        }
+
         // Steps over the list of functions with an initial error code of 0.
         impl $error_type {
             create_errors!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+/// This module contains the cancellation of long-running compilations.
+pub mod cancellation;
+pub use self::cancellation::*;
+
 /// This module contains a backtraced error and its methods.
 pub mod backtraced;
 pub use self::backtraced::*;
@@ -27,6 +31,14 @@ pub use self::formatted::*;
 pub mod macros;
 pub use self::macros::*;
 
+/// This module contains helpers for reporting caught panics as errors.
+pub mod panic;
+pub use self::panic::*;
+
+/// This module contains conversions between spans and the source text they point into.
+pub mod source_edit;
+pub use self::source_edit::*;
+
 /// This module contains the common span object for Leo crates.
 pub mod span;
 pub use self::span::Span;
@@ -0,0 +1,152 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AstError, Result, Span};
+
+use std::ops::Range;
+
+/// A byte offset into a source text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BytePos(pub usize);
+
+///
+/// Converts between the spans of a source text and the text itself.
+///
+/// Lines end at `\n`, so the `\r` of a CRLF line ending belongs to its line without being part of its content.
+/// Columns count bytes from 1, as the spans produced by the tokenizer do.
+///
+#[derive(Clone, Debug)]
+pub struct SourceEdit<'a> {
+    /// The source text.
+    source: &'a str,
+    /// The byte offset at which every line starts.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceEdit<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(index, _)| index + 1));
+        Self { source, line_starts }
+    }
+
+    /// Returns the content of the line with the given number, counting from 1, without its line ending.
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|next| next - 1)
+            .unwrap_or(self.source.len());
+        Some(self.source[start..end].trim_end_matches('\r'))
+    }
+
+    ///
+    /// Returns the offset of the given line and column, counting from 1.
+    ///
+    /// The column may be one past the content of the line, where its line ending starts, but not inside a character.
+    /// It may also be one past the line ending, where the span of a line comment ends.
+    ///
+    pub fn pos(&self, line: usize, col: usize) -> Option<BytePos> {
+        let content = self.line(line)?;
+        let offset = col.checked_sub(1)?;
+        let start = self.line_starts[line - 1];
+        let past_ending = self.line_starts.get(line).map(|next| next - start);
+        if !content.is_char_boundary(offset) && Some(offset) != past_ending {
+            return None;
+        }
+        Some(BytePos(start + offset))
+    }
+
+    /// Returns the line and column of the given offset, counting from 1.
+    pub fn line_col(&self, pos: BytePos) -> Option<(usize, usize)> {
+        if pos.0 > self.source.len() || !self.source.is_char_boundary(pos.0) {
+            return None;
+        }
+        let line = match self.line_starts.binary_search(&pos.0) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        };
+        Some((line + 1, pos.0 - self.line_starts[line] + 1))
+    }
+
+    /// Returns the byte offsets of the source text that the span covers.
+    pub fn range(&self, span: &Span) -> Result<Range<usize>> {
+        let start = self.pos(span.line_start, span.col_start);
+        let stop = self.pos(span.line_stop, span.col_stop);
+        match (start, stop) {
+            (Some(start), Some(stop)) if start <= stop => Ok(start.0..stop.0),
+            _ => Err(AstError::span_outside_source(span).into()),
+        }
+    }
+
+    /// Returns the source text that the span covers.
+    pub fn extract(&self, span: &Span) -> Result<&'a str> {
+        Ok(&self.source[self.range(span)?])
+    }
+
+    ///
+    /// Returns the source text with the span of every edit replaced by its text.
+    ///
+    /// Edits may not overlap, but several may insert text at the same offset, in the order they are given.
+    ///
+    pub fn apply_edits(&self, edits: Vec<(Span, String)>) -> Result<String> {
+        let mut edits = edits
+            .into_iter()
+            .map(|(span, text)| Ok((self.range(&span)?, span, text)))
+            .collect::<Result<Vec<_>>>()?;
+        edits.sort_by_key(|(range, _, _)| (range.start, range.end));
+
+        for pair in edits.windows(2) {
+            if pair[0].0.end > pair[1].0.start {
+                return Err(AstError::overlapping_edits(&pair[0].1, &pair[1].1).into());
+            }
+        }
+
+        // Edits are applied from the end, so the offsets of the ones before stay valid.
+        let mut edited = self.source.to_string();
+        for (range, _, text) in edits.into_iter().rev() {
+            edited.replace_range(range, &text);
+        }
+        Ok(edited)
+    }
+}
+
+/// Returns the source text with the span of every edit replaced by its text. See [`SourceEdit::apply_edits`].
+pub fn apply_edits(source: &str, edits: Vec<(Span, String)>) -> Result<String> {
+    SourceEdit::new(source).apply_edits(edits)
+}
+
+///
+/// Returns the column at which the given byte column of a line is displayed, counting characters from 1.
+///
+/// Columns past the end of the line are displayed one character per byte.
+///
+pub fn display_column(line: &str, col: usize) -> usize {
+    let offset = col.saturating_sub(1);
+    match line.get(..offset) {
+        Some(prefix) => prefix.chars().count() + 1,
+        None if offset > line.len() => line.chars().count() + offset - line.len() + 1,
+        // The column is inside a character, which is displayed from its start.
+        None => {
+            let start = (0..offset)
+                .rev()
+                .find(|index| line.is_char_boundary(*index))
+                .unwrap_or(0);
+            line[..start].chars().count() + 1
+        }
+    }
+}
@@ -850,4 +850,266 @@ create_errors!(
         msg: format!("array sizes must match for comparison; left: {}, right: {}", lhs, rhs),
         help: None,
     }
+
+    /// For when the compiler fails to write an artifact to its output sink.
+    @backtraced
+    failed_to_write_artifact {
+        args: (name: impl Display, error: impl ErrorArg),
+        msg: format!("failed to write compiler artifact `{}` {}", name, error),
+        help: None,
+    }
+
+    /// For when a loop unrolls its function past the configured statement limit.
+    @formatted
+    unrolled_statement_limit_exceeded {
+        args: (observed: impl Display, limit: impl Display),
+        msg: format!(
+            "loop unrolls its function to {} statements, exceeding the limit of {} unrolled statements",
+            observed, limit
+        ),
+        help: Some("reduce the loop bounds or raise `max_unrolled_statements` in the compiler options".to_string()),
+    }
+
+    /// For when the synthesized circuit exceeds a configured size limit.
+    @formatted
+    circuit_limit_exceeded {
+        args: (kind: impl Display, observed: impl Display, limit: impl Display, option: impl Display),
+        msg: format!("program synthesizes {} {}, exceeding the limit of {}", observed, kind, limit),
+        help: Some(format!("simplify the program or raise `{}` in the compiler options", option)),
+    }
+
+    /// For when a program defines more functions or circuits than the configured limit.
+    @backtraced
+    definition_limit_exceeded {
+        args: (kind: impl Display, observed: impl Display, limit: impl Display, option: impl Display),
+        msg: format!("program defines {} {}, exceeding the limit of {}", observed, kind, limit),
+        help: Some(format!("split the program or raise `{}` in the compiler options", option)),
+    }
+
+    /// For when an input is selected by a label that was never added to the compiler.
+    @backtraced
+    unknown_input_label {
+        args: (label: impl Display),
+        msg: format!("no program input was added with the label `{}`", label),
+        help: None,
+    }
+
+    /// For when the compiler panics on a program, instead of reporting an error.
+    @backtraced
+    compiler_panicked {
+        args: (message: impl Display),
+        msg: format!("internal compiler error: the compiler panicked: {}", message),
+        help: Some("This is a bug in the Leo compiler, please report it.".to_string()),
+    }
+
+    /// For when a loop counter takes a value outside the range of its integer type.
+    @formatted
+    statement_loop_counter_overflow {
+        args: (value: impl Display, type_: impl Display),
+        msg: format!("loop counter value {} does not fit in the loop variable type `{}`", value, type_),
+        help: Some("widen the type of the loop bounds".to_string()),
+    }
+
+    /// For when the parameters of `main` flatten to more input slots than the configured limit.
+    @backtraced
+    input_slot_limit_exceeded {
+        args: (observed: impl Display, limit: impl Display, largest: impl Display),
+        msg: format!(
+            "the parameters of `main` take {} input slots, exceeding the limit of {}; the largest are {}",
+            observed, limit, largest
+        ),
+        help: Some("make large parameters `const`, or raise `max_input_slots` in the compiler options".to_string()),
+    }
+
+    /// For when a function annotated with `@budget` synthesizes more constraints than its budget.
+    @formatted
+    function_budget_exceeded {
+        args: (name: impl Display, observed: impl Display, budget: impl Display, largest: impl Display),
+        msg: format!(
+            "function `{}` emits {} instructions, exceeding its budget of {}; the largest contributors are {}",
+            name, observed, budget, largest
+        ),
+        help: Some("simplify the function or raise its `@budget`".to_string()),
+    }
+
+    /// For when the provenance of a circuit fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_provenance_to_json {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert provenance to a json string {}", error),
+        help: None,
+    }
+
+    /// For when an artifact to verify does not embed a stamp.
+    @backtraced
+    artifact_not_stamped {
+        args: (path: impl Display),
+        msg: format!("the artifact `{}` is not stamped by the compiler", path),
+        help: Some("Only artifacts written by the compiler can be verified.".to_string()),
+    }
+
+    /// For when the program input has more than one error, which are all reported together.
+    @backtraced
+    input_errors {
+        args: (count: impl Display, errors: impl Display),
+        msg: format!("the program input has {} errors:\n{}", count, errors),
+        help: None,
+    }
+
+    /// For when an entrypoint named in the compiler options is not a function of the program.
+    @backtraced
+    unknown_entrypoint {
+        args: (name: impl Display),
+        msg: format!("the entrypoint `{}` is not a function of the program", name),
+        help: Some("Declare the function, or remove it from the `entrypoints` compiler option.".to_string()),
+    }
+
+    /// For when an entrypoint-specific stage of the compilation fails.
+    @backtraced
+    entrypoint_error {
+        args: (entrypoint: impl Display, error: impl Display),
+        msg: format!("in entrypoint `{}`: {}", entrypoint, error),
+        help: None,
+    }
+
+    /// For when the interface of an entrypoint fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_interface_to_json {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert interface to a json string {}", error),
+        help: None,
+    }
+
+    /// For when the compilation is cancelled through its cancellation token.
+    @backtraced
+    cancelled {
+        args: (phase: impl Display),
+        msg: format!("the compilation was cancelled during {}", phase),
+        help: None,
+    }
+
+    /// For when the compilation runs past the deadline of its cancellation token.
+    @backtraced
+    deadline_exceeded {
+        args: (phase: impl Display),
+        msg: format!("the compilation ran past its deadline during {}", phase),
+        help: Some("Simplify the program, such as by unrolling fewer loop iterations, or allow it more time.".to_string()),
+    }
+
+    /// For when the operands of a console assert_eq or assert_ne do not compare as asserted.
+    @formatted
+    console_comparison_failed {
+        args: (function: impl Display, left: impl Display, right: impl Display),
+        msg: format!("console.{}(...) failed\n  left: {}\n right: {}", function, left, right),
+        help: None,
+    }
+
+    /// For when a compiler options file is not valid TOML or its options have the wrong types.
+    @backtraced
+    failed_to_parse_options {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed to parse the compiler options in `{}`: {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler options fail to be represented as TOML.
+    @backtraced
+    failed_to_convert_options_to_toml {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert the compiler options to toml: {}", error),
+        help: None,
+    }
+
+    /// For when a compiler options file cannot be written.
+    @backtraced
+    failed_to_write_options_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to write the compiler options to {:?}: {}", path, error),
+        help: None,
+    }
+
+    /// For when a pass meets a construct that it does not implement.
+    ///
+    /// Passes return this instead of panicking, so that the construct is rejected where it is written.
+    @formatted
+    unsupported_construct {
+        args: (construct: impl Display, pass: impl Display),
+        msg: format!("{} is not supported by {}", construct, pass),
+        help: Some("Rewrite the expression without this construct.".to_string()),
+    }
+
+    /// For when the `inline_threshold` compiler option is zero.
+    @backtraced
+    invalid_inline_threshold {
+        args: (),
+        msg: "the compiler option `inline_threshold` must be at least 1",
+        help: Some("the threshold is the number of expressions up to which a function is inlined by size".to_string()),
+    }
+
+    /// For when the registers of the input file do not flatten to the same slots as the output of the entrypoint.
+    @formatted
+    output_registers_mismatch {
+        args: (mismatches: impl Display),
+        msg: format!("the `[registers]` section does not match the output of the entrypoint:\n{}", mismatches),
+        help: Some("registers are compared with the output slot by slot, after aliases are resolved and tuples, arrays and circuits are flattened".to_string()),
+    }
+
+    /// For when the signature manifest fails to be represented as JSON.
+    @backtraced
+    failed_to_write_signature_manifest {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert the signature manifest to json: {}", error),
+        help: None,
+    }
+
+    /// For when a signature manifest cannot be read.
+    @backtraced
+    failed_to_read_signature_manifest {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed to read the signature manifest `{}`: {}", path, error),
+        help: None,
+    }
+
+    /// For when a signed artifact does not match the signature manifest.
+    @backtraced
+    artifact_signature_invalid {
+        args: (name: impl Display, reason: impl Display),
+        msg: format!("the artifact `{}` fails verification: {}", name, reason),
+        help: None,
+    }
+
+    /// For when two programs compiled into the same output directory have the same compilation unit.
+    @backtraced
+    duplicate_compilation_unit {
+        args: (unit: impl Display, first: impl Display, second: impl Display),
+        msg: format!(
+            "the compilation unit `{}` of `{}` is already the unit of `{}`",
+            unit, second, first
+        ),
+        help: Some("set the compiler option `unit_name` of one of the programs to write its artifacts to another directory".to_string()),
+    }
+
+    /// For when an audit report fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_audit_report_to_json {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert the audit report to a json string {}", error),
+        help: None,
+    }
+
+    /// For when a function is compiled whose body has placeholders left by error recovery.
+    @formatted
+    function_has_unresolved_errors {
+        args: (name: impl Display),
+        msg: format!("function `{}` contains unresolved errors", name),
+        help: Some("fix the syntax errors reported in the function first".to_string()),
+    }
+
+    /// For when a project declares an edition of the language that does not exist.
+    @backtraced
+    unknown_edition {
+        args: (edition: impl Display, path: impl Display, editions: impl Display),
+        msg: format!("unknown edition `{}` declared in `{}`", edition, path),
+        help: Some(format!("the editions are {}", editions)),
+    }
 );
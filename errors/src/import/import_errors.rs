@@ -113,4 +113,12 @@ create_errors!(
         msg: format!("failed to read the stdlib import file `{}`", import),
         help: None,
     }
+
+    /// For when a program imports itself, directly or through the programs it imports.
+    @formatted
+    import_cycle {
+        args: (path: impl Display, imports: impl Display),
+        msg: format!("program imports itself via {}", path),
+        help: Some(format!("the imports of the cycle are {}", imports)),
+    }
 );
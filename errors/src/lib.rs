@@ -58,6 +58,12 @@ pub use self::snarkvm::*;
 pub mod state;
 pub use self::state::*;
 
+/// Contains helpers for asserting on errors in tests.
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "test-utils")]
+pub use self::test_utils::*;
+
 #[macro_use]
 extern crate thiserror;
 
@@ -144,6 +150,42 @@ impl LeoError {
             StateError(error) => error.exit_code(),
         }
     }
+
+    /// Returns the span of the error, for the errors that have one.
+    pub fn span(&self) -> Option<&Span> {
+        use LeoError::*;
+
+        match self {
+            AsgError(error) => error.span(),
+            AstError(error) => error.span(),
+            CliError(error) => error.span(),
+            CompilerError(error) => error.span(),
+            ImportError(error) => error.span(),
+            InputError(_error) => None,
+            PackageError(error) => error.span(),
+            ParserError(error) => error.span(),
+            SnarkVMError(_error) => None,
+            StateError(error) => error.span(),
+        }
+    }
+
+    /// Returns the error message without its code or location.
+    pub fn message(&self) -> String {
+        use LeoError::*;
+
+        match self {
+            AsgError(error) => error.message().to_string(),
+            AstError(error) => error.message().to_string(),
+            CliError(error) => error.message().to_string(),
+            CompilerError(error) => error.message().to_string(),
+            ImportError(error) => error.message().to_string(),
+            InputError(error) => error.to_string(),
+            PackageError(error) => error.message().to_string(),
+            ParserError(error) => error.message().to_string(),
+            SnarkVMError(error) => error.to_string(),
+            StateError(error) => error.message().to_string(),
+        }
+    }
 }
 
 /// A global result type for all Leo crates, that defaults the errors to be a LeoError.
@@ -183,4 +183,76 @@ create_errors!(
         msg: "unable to parse array dimensions",
         help: None,
     }
+
+    /// For when an array comprehension has an `if` filter, which would make its length depend on the filter.
+    @formatted
+    array_comprehension_filter {
+        args: (),
+        msg: "array comprehensions cannot have an `if` filter",
+        help: Some("arrays have a fixed size; select the elements with a ternary expression instead".to_string()),
+    }
+
+    /// For when brackets, blocks, types or unary operators nest too deeply to parse.
+    @formatted
+    nesting_too_deep {
+        args: (limit: impl Display),
+        msg: format!("the program nests more than {} levels deep", limit),
+        help: Some("Split the nested code into intermediate variables or functions.".to_string()),
+    }
+
+    /// For when the source text is not valid UTF-8.
+    @backtraced
+    invalid_utf8 {
+        args: (offset: impl Display),
+        msg: format!("the source is not valid UTF-8: invalid byte at offset {}", offset),
+        help: None,
+    }
+
+    /// For when a string literal is not closed before the end of its line, in recovery mode.
+    @formatted
+    unterminated_string {
+        args: (),
+        msg: "unterminated string literal",
+        help: Some("Add a closing `\"`.".to_string()),
+    }
+
+    /// For when a char literal is not closed before the end of its line, in recovery mode.
+    @formatted
+    unterminated_char {
+        args: (),
+        msg: "unterminated char literal",
+        help: Some("Add a closing `'`.".to_string()),
+    }
+
+    /// For when a delimiter is still open at the start of the next item or at the end of the file, in recovery mode.
+    @formatted
+    unclosed_delimiter {
+        args: (delimiter: impl Display),
+        msg: format!("this `{}` is never closed", delimiter),
+        help: None,
+    }
+
+    /// For when a block comment, or a comment nested in it, is never closed.
+    @formatted
+    unterminated_comment {
+        args: (nested: impl Display),
+        msg: format!("this block comment is never closed{}", nested),
+        help: Some("Add a closing `*/` for every `/*`; block comments nest.".to_string()),
+    }
+
+    /// For when a list has a comma directly after another.
+    @formatted
+    repeated_comma {
+        args: (),
+        msg: "unexpected `,` directly after another",
+        help: Some("Remove the extra `,`. A list takes one `,` between its elements, and at most one after the last.".to_string()),
+    }
+
+    /// For when source is to be migrated to an earlier version of the language.
+    @backtraced
+    migration_downgrade {
+        args: (from: impl Display, to: impl Display),
+        msg: format!("cannot migrate source from Leo {} back to Leo {}", from, to),
+        help: Some("Migration only rewrites source into the syntax of later versions.".to_string()),
+    }
 );
@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::LeoError;
+
+use std::fmt;
+
+/// A snapshot of an error that tests can compare against an expectation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiagnosticSnapshot {
+    /// The unique error code, e.g. `EASG0373027`.
+    pub code: String,
+    /// The error message without its code or location.
+    pub message: String,
+    /// The 1-based line the error starts on, or 0 if the error has no span.
+    pub line: usize,
+    /// The 1-based column the error starts on, or 0 if the error has no span.
+    pub column: usize,
+}
+
+impl From<&LeoError> for DiagnosticSnapshot {
+    fn from(error: &LeoError) -> Self {
+        let (line, column) = error
+            .span()
+            .map(|span| (span.line_start, span.col_start))
+            .unwrap_or_default();
+
+        Self {
+            code: error.error_code(),
+            message: error.message(),
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}:{}: {}", self.code, self.line, self.column, self.message)
+    }
+}
+
+/// Panics with a listing of both sides unless `actual` matches `expected` as `(code, line, column)` triples, in order.
+pub fn assert_diagnostics(actual: &[DiagnosticSnapshot], expected: &[(&str, usize, usize)]) {
+    let matches = actual.len() == expected.len()
+        && actual
+            .iter()
+            .zip(expected.iter())
+            .all(|(actual, (code, line, column))| {
+                actual.code == *code && actual.line == *line && actual.column == *column
+            });
+
+    if !matches {
+        let actual = actual
+            .iter()
+            .map(|diagnostic| format!("    {}", diagnostic))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let expected = expected
+            .iter()
+            .map(|(code, line, column)| format!("    {} at {}:{}", code, line, column))
+            .collect::<Vec<_>>()
+            .join("\n");
+        panic!("diagnostics did not match\nexpected:\n{}\nactual:\n{}", expected, actual);
+    }
+}
+
+/// Asserts that a list of [`DiagnosticSnapshot`]s matches the given `(code, line, column)` triples, in order.
+///
+/// ```ignore
+/// expect_errors!(compile_fail(source), [("EASG0373027", 2, 12)]);
+/// ```
+#[macro_export]
+macro_rules! expect_errors {
+    ($diagnostics:expr, [$(($code:expr, $line:expr, $column:expr)),* $(,)?]) => {
+        $crate::assert_diagnostics(&$diagnostics, &[$(($code, $line, $column)),*])
+    };
+}
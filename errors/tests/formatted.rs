@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{AsgError, FormattedError, Span};
+
+use std::sync::Arc;
+
+fn span(path: &str, line: usize, col_start: usize, col_stop: usize, content: &str) -> Span {
+    Span::new(
+        line,
+        line,
+        col_start,
+        col_stop,
+        Arc::new(path.to_string()),
+        content.into(),
+    )
+}
+
+fn unresolved_circuit(span: &Span) -> FormattedError {
+    // Disables colors in the rendered message.
+    std::env::set_var("LEO_TESTFRAMEWORK", "true");
+    match AsgError::unresolved_circuit("Foo", span) {
+        AsgError::FormattedError(error) => error,
+        _ => unreachable!(),
+    }
+}
+
+const PRIMARY: &str = "Error [EASG0373000]: failed to resolve circuit: 'Foo'
+    --> main.leo:4:13
+     |
+   4 |     let a = Foo { };
+     |             ^^^";
+
+#[test]
+fn test_single_file_unchanged() {
+    let error = unresolved_circuit(&span("main.leo", 4, 13, 16, "    let a = Foo { };"));
+
+    assert_eq!(error.to_string(), PRIMARY);
+}
+
+#[test]
+fn test_labels_grouped_by_file() {
+    let error = unresolved_circuit(&span("main.leo", 4, 13, 16, "    let a = Foo { };"))
+        .with_label(
+            &span("lib.leo", 2, 9, 13, "circuit Fooo {}"),
+            "a similar circuit is defined here",
+        )
+        .with_label(&span("main.leo", 2, 5, 8, "    Bar::new();"), "first used here")
+        .with_label(&span("lib.leo", 7, 9, 12, "circuit Fo {}"), "and here");
+
+    let expected = format!(
+        "{}
+     |
+   2 |     Bar::new();
+     |     ^^^ first used here
+    --> lib.leo:2:9
+     |
+   2 | circuit Fooo {{}}
+     |         ^^^^ a similar circuit is defined here
+     |
+   7 | circuit Fo {{}}
+     |         ^^^ and here",
+        PRIMARY
+    );
+    assert_eq!(error.to_string(), expected);
+}
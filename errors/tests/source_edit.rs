@@ -0,0 +1,104 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{apply_edits, BytePos, SourceEdit, Span};
+
+use std::sync::Arc;
+
+fn span(line_start: usize, col_start: usize, line_stop: usize, col_stop: usize) -> Span {
+    Span::new(
+        line_start,
+        line_stop,
+        col_start,
+        col_stop,
+        Arc::new("main.leo".to_string()),
+        Default::default(),
+    )
+}
+
+#[test]
+fn test_overlapping_edits_rejected() {
+    let source = "let a = 1u8 + 2u8;";
+    let edits = vec![
+        (span(1, 9, 1, 12), "3u8".to_string()),
+        (span(1, 11, 1, 18), "".to_string()),
+    ];
+    let error = apply_edits(source, edits).unwrap_err();
+    assert_eq!(error.error_code(), "EAST0372024");
+
+    // Edits that only touch, and insertions where another edit starts, do not overlap.
+    let edits = vec![
+        (span(1, 15, 1, 18), "4u8".to_string()),
+        (span(1, 9, 1, 12), "3u8".to_string()),
+        (span(1, 12, 1, 15), " * ".to_string()),
+        (span(1, 9, 1, 9), "(".to_string()),
+        (span(1, 9, 1, 9), "(".to_string()),
+    ];
+    assert_eq!(apply_edits(source, edits).unwrap(), "let a = ((3u8 * 4u8;");
+}
+
+#[test]
+fn test_multibyte_replacement() {
+    let source = "let s = \"héllo\";\nlet t = \"wörld\";\n";
+    let text = SourceEdit::new(source);
+
+    // `é` and `ö` take two bytes, which columns count.
+    assert_eq!(text.extract(&span(1, 9, 1, 17)).unwrap(), "\"héllo\"");
+    assert_eq!(text.extract(&span(2, 9, 2, 17)).unwrap(), "\"wörld\"");
+
+    let edits = vec![
+        (span(1, 9, 1, 17), "\"ça\"".to_string()),
+        (span(2, 1, 2, 4), "const".to_string()),
+    ];
+    assert_eq!(
+        text.apply_edits(edits).unwrap(),
+        "let s = \"ça\";\nconst t = \"wörld\";\n"
+    );
+
+    // Columns inside a character, or past the end of a line, are outside the source.
+    let error = text.extract(&span(1, 12, 1, 14)).unwrap_err();
+    assert_eq!(error.error_code(), "EAST0372023");
+    assert!(text.extract(&span(1, 9, 1, 30)).is_err());
+}
+
+#[test]
+fn test_line_col_round_trip() {
+    let source = "a\r\nbé\r\n\r\nc";
+    let text = SourceEdit::new(source);
+
+    assert_eq!(text.line(1), Some("a"));
+    assert_eq!(text.line(2), Some("bé"));
+    assert_eq!(text.line(3), Some(""));
+    assert_eq!(text.line(4), Some("c"));
+    assert_eq!(text.line(5), None);
+
+    assert_eq!(text.pos(2, 1), Some(BytePos(3)));
+    assert_eq!(text.pos(2, 2), Some(BytePos(4)));
+    assert_eq!(text.pos(2, 3), None);
+    // The end of a line is where its line ending starts.
+    assert_eq!(text.pos(2, 4), Some(BytePos(6)));
+    assert_eq!(text.pos(2, 5), None);
+    // A line comment ends past its line ending.
+    assert_eq!(text.pos(2, 6), Some(BytePos(8)));
+
+    // Every offset but those of a `\n`, which is past the column at which its line ends, converts back.
+    for (index, _) in source.char_indices().filter(|(_, c)| *c != '\n') {
+        let (line, col) = text.line_col(BytePos(index)).unwrap();
+        assert_eq!(text.pos(line, col), Some(BytePos(index)));
+    }
+    assert_eq!(text.line_col(BytePos(5)), None);
+    assert_eq!(text.line_col(BytePos(source.len())), Some((4, 2)));
+}
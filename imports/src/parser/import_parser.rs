@@ -14,12 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use leo_ast::Program;
-use leo_ast_passes::ImportResolver;
+use leo_ast::{Edition, Program};
+use leo_ast_passes::{ImportDiagnostic, ImportResolver};
 use leo_errors::{ImportError, LeoError, Result, Span};
 
-use indexmap::{IndexMap, IndexSet};
-use std::path::PathBuf;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+
+/// The extension of the main program file, whose name is the package it could be imported as.
+static MAIN_FILE_EXTENSION: &str = "leo";
 
 /// Stores imported packages.
 ///
@@ -28,9 +31,14 @@ use std::path::PathBuf;
 #[derive(Clone, Default)]
 pub struct ImportParser {
     program_path: PathBuf,
-    partial_imports: IndexSet<String>,
+    /// The packages being imported, each with the span of the import that started it, outermost first.
+    partial_imports: IndexMap<String, Span>,
     imports: IndexMap<String, Program>,
+    sources: IndexMap<String, String>,
+    diagnostics: Vec<ImportDiagnostic>,
     pub imports_map: IndexMap<String, String>,
+    /// The edition the imported files are parsed against, that of the program importing them.
+    pub(crate) edition: Edition,
 }
 
 impl ImportParser {
@@ -39,16 +47,99 @@ impl ImportParser {
             program_path,
             partial_imports: Default::default(),
             imports: Default::default(),
+            sources: Default::default(),
+            diagnostics: Default::default(),
             imports_map,
+            edition: Edition::LATEST,
+        }
+    }
+
+    ///
+    /// Returns the import parser with the imported files parsed against `edition` instead of the latest edition.
+    ///
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = edition;
+        self
+    }
+
+    ///
+    /// Returns the repeated imports of every imported file that was parsed, each file after the files it imports.
+    ///
+    pub fn diagnostics(&self) -> &[ImportDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Records the repeated imports of an imported file.
+    pub(crate) fn add_diagnostics(&mut self, diagnostics: Vec<ImportDiagnostic>) {
+        self.diagnostics.extend(diagnostics);
+    }
+
+    ///
+    /// Returns the package the main program file could be imported as, which is the name of the file.
+    ///
+    fn main_package(&self) -> Option<String> {
+        if self.program_path.extension()? != MAIN_FILE_EXTENSION {
+            return None;
         }
+        Some(self.program_path.file_stem()?.to_string_lossy().into_owned())
+    }
+
+    ///
+    /// Returns the error for an import of `package` while it is still being imported, naming the imports of the cycle.
+    ///
+    fn import_cycle(&self, package: &str, span: &Span) -> LeoError {
+        // A cycle through the main program file starts there, without an import.
+        let start = self.partial_imports.get_index_of(package);
+        let mut imports = self
+            .partial_imports
+            .iter()
+            .skip(start.unwrap_or(0))
+            .map(|(package, span)| (package.as_str(), span))
+            .collect::<Vec<_>>();
+        imports.push((package, span));
+
+        let mut path = imports
+            .iter()
+            .map(|(package, _)| format!("`{}`", package))
+            .collect::<Vec<_>>();
+        if start.is_none() {
+            path.insert(0, format!("`{}`", package));
+        }
+        let locations = imports
+            .iter()
+            .map(|(package, span)| format!("`{}` at {}:{}", package, span.path, span))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        ImportError::import_cycle(path.join(" -> "), locations, span).into()
+    }
+
+    ///
+    /// Returns the source of every imported file that was parsed, keyed by its path.
+    ///
+    /// Paths are relative to the package of the main program file when they are within it.
+    ///
+    pub fn sources(&self) -> &IndexMap<String, String> {
+        &self.sources
+    }
+
+    /// Records the source of an imported file.
+    pub(crate) fn add_source(&mut self, path: &Path, source: String) {
+        // The main file is at `<package>/src/main.leo`.
+        let package = self.program_path.parent().and_then(Path::parent);
+        let path = match package.and_then(|package| path.strip_prefix(package).ok()) {
+            Some(relative) => relative,
+            None => path,
+        };
+        self.sources.insert(path.to_string_lossy().into_owned(), source);
     }
 }
 
 impl ImportResolver for ImportParser {
     fn resolve_package(&mut self, package_segments: &[&str], span: &Span) -> Result<Option<Program>> {
         let full_path = package_segments.join(".");
-        if self.partial_imports.contains(&full_path) {
-            return Err(ImportError::recursive_imports(full_path, span).into());
+        if self.partial_imports.contains_key(&full_path) || self.main_package().as_ref() == Some(&full_path) {
+            return Err(self.import_cycle(&full_path, span));
         }
 
         if let Some(program) = self.imports.get(&full_path) {
@@ -56,14 +147,16 @@ impl ImportResolver for ImportParser {
         }
 
         let path = self.program_path.clone();
-        self.partial_imports.insert(full_path.clone());
+        self.partial_imports.insert(full_path.clone(), span.clone());
         let mut imports = self.clone(); // Self::default() was previously
         let program = imports
             .parse_package(path, package_segments, span)
             .map_err(|x| -> LeoError { x })?;
 
-        self.partial_imports.remove(&full_path);
+        self.partial_imports.shift_remove(&full_path);
         self.imports.insert(full_path, program.clone());
+        self.sources = imports.sources;
+        self.diagnostics = imports.diagnostics;
 
         Ok(Some(program))
     }
@@ -35,10 +35,11 @@ impl ImportParser {
             return self.parse_package(package.path(), remaining_segments, span);
         }
 
-        let program = Self::parse_import_file(package, span)?;
-        let ast = leo_ast_passes::Importer::do_pass(program, self)?.into_repr();
+        let program = self.parse_import_file(package, span)?;
+        let (ast, diagnostics) = leo_ast_passes::Importer::do_pass_with_diagnostics(program, self)?;
+        self.add_diagnostics(diagnostics);
 
-        Ok(ast)
+        Ok(ast.into_repr())
     }
 
     ///
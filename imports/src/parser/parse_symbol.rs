@@ -16,7 +16,7 @@
 
 use crate::ImportParser;
 use leo_ast::Program;
-use leo_errors::{ImportError, Result, Span};
+use leo_errors::{CancellationToken, ImportError, Result, Span};
 
 use std::fs::DirEntry;
 
@@ -28,7 +28,7 @@ impl ImportParser {
     ///
     /// Builds an abstract syntax tree from the given file and then builds the Leo syntax tree.
     ///
-    pub(crate) fn parse_import_file(package: &DirEntry, span: &Span) -> Result<Program> {
+    pub(crate) fn parse_import_file(&mut self, package: &DirEntry, span: &Span) -> Result<Program> {
         // Get the package file type.
         let file_type = package
             .file_type()
@@ -51,9 +51,11 @@ impl ImportParser {
 
         // Build the package abstract syntax tree.
         let program_string =
-            &std::fs::read_to_string(&file_path).map_err(|x| ImportError::io_error(file_path_str, x, span))?;
-        let mut program = leo_parser::parse(file_path_str, program_string)?;
+            std::fs::read_to_string(&file_path).map_err(|x| ImportError::io_error(file_path_str, x, span))?;
+        let cancellation = CancellationToken::default();
+        let mut program = leo_parser::parse_for_edition(file_path_str, &program_string, self.edition, &cancellation)?;
         program.name = file_name;
+        self.add_source(&file_path, program_string);
         Ok(program)
     }
 }
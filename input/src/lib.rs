@@ -30,6 +30,8 @@ pub mod definitions;
 pub mod expressions;
 pub mod files;
 pub mod parameters;
+mod recovery;
+pub use recovery::*;
 pub mod sections;
 pub mod tables;
 pub mod types;
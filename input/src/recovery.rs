@@ -0,0 +1,95 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{ast, InputParserError, LeoInputParser};
+
+use pest::error::LineColLocation;
+
+/// An input file with its malformed lines left out, so that it parses.
+#[derive(Debug, Default)]
+pub struct RecoveredInput {
+    /// The input with every malformed line emptied, keeping the line numbers of the other lines.
+    pub source: String,
+    /// The syntax error of every malformed line, in the order of the lines.
+    pub errors: Vec<InputParserError>,
+    /// The names of the entries on the lines that were left out.
+    pub skipped: Vec<String>,
+}
+
+/// Returns `true` if the line is a section or table header.
+fn is_header(line: &str) -> bool {
+    line.trim_start().starts_with('[')
+}
+
+/// Returns the name of the entry defined on the line, if it starts like `name: type`.
+fn entry_name(line: &str) -> Option<String> {
+    let (name, _) = line.split_once(':')?;
+    let name = name.trim();
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+impl LeoInputParser {
+    ///
+    /// Parses the input file for syntax errors, recovering from each of them.
+    ///
+    /// A malformed entry is left out up to the end of its line. A malformed section or table header
+    /// leaves out the whole section, up to the next header. The recovered source then parses with `parse_file()`.
+    ///
+    pub fn recover(input_file: &str) -> RecoveredInput {
+        let mut lines = input_file.split('\n').map(str::to_string).collect::<Vec<_>>();
+        let mut recovered = RecoveredInput::default();
+
+        // Every malformed line is emptied, so there are at most as many errors as lines.
+        for _ in 0..=lines.len() {
+            let source = lines.join("\n");
+            let error = match ast::parse(&source) {
+                Ok(_) => {
+                    recovered.source = source;
+                    return recovered;
+                }
+                Err(error) => error,
+            };
+            let line = match error.line_col {
+                LineColLocation::Pos((line, _)) | LineColLocation::Span((line, _), _) => line - 1,
+            };
+            recovered.errors.push(InputParserError::from(error));
+
+            if lines.get(line).map(|line| line.trim().is_empty()).unwrap_or(true) {
+                // The error is not on a line that can be left out, such as at the end of the file.
+                break;
+            }
+            let end = if is_header(&lines[line]) {
+                (line + 1..lines.len())
+                    .find(|next| is_header(&lines[*next]))
+                    .unwrap_or_else(|| lines.len())
+            } else {
+                line + 1
+            };
+            for line in lines[line..end].iter_mut() {
+                recovered.skipped.extend(entry_name(line));
+                line.clear();
+            }
+        }
+
+        // Nothing is left to parse.
+        recovered.source = String::new();
+        recovered
+    }
+}
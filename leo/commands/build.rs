@@ -18,7 +18,7 @@ use crate::{commands::Command, context::Context};
 use leo_compiler::{
     compiler::{thread_leaked_context, Compiler},
     group::targets::edwards_bls12::EdwardsGroupType,
-    AstSnapshotOptions, CompilerOptions,
+    AstSnapshotOptions, CompilerOptions, IntegerWidening, ProgramLimits,
 };
 use leo_errors::{CliError, Result};
 use leo_package::{
@@ -41,8 +41,42 @@ pub struct BuildOptions {
     pub disable_constant_folding: bool,
     #[structopt(long, help = "Disable dead code elimination compiler optimization")]
     pub disable_code_elimination: bool,
+    #[structopt(
+        long,
+        help = "Replace comparisons decided by the ranges of their operand types with their result"
+    )]
+    pub fold_range_comparisons: bool,
+    #[structopt(long, help = "Rewrite boolean expressions into smaller equivalent ones")]
+    pub simplify_boolean_expressions: bool,
+    #[structopt(long, help = "Keep the asserts that always hold instead of removing them")]
+    pub keep_true_asserts: bool,
     #[structopt(long, help = "Disable all compiler optimizations")]
     pub disable_all_optimizations: bool,
+    #[structopt(
+        long,
+        help = "Rename internal identifiers to short names and write the mapping to the outputs directory"
+    )]
+    pub enable_renaming: bool,
+    #[structopt(long, help = "Maximum depth that expressions may nest to before compilation fails")]
+    pub max_expression_depth: Option<usize>,
+    #[structopt(long, help = "Reject field literals outside of the field instead of reducing them")]
+    pub strict_field_literals: bool,
+    #[structopt(
+        long,
+        help = "Widen the narrower operand of operations on integers of one signedness but different widths"
+    )]
+    pub widen_integers: bool,
+    #[structopt(long, help = "Check the structure of the AST after every compiler pass")]
+    pub verify_ast: bool,
+    #[structopt(long, help = "Writes the dataflow of every function as a Graphviz .dot file.")]
+    pub emit_dot: bool,
+    #[structopt(long, help = "Groups the nodes of each statement together in .dot files.")]
+    pub cluster_dot: bool,
+    #[structopt(
+        long = "entrypoint",
+        help = "Compiles the function as an entrypoint with its own artifacts; may be given more than once"
+    )]
+    pub entrypoints: Vec<String>,
     #[structopt(long, help = "Enable spans in AST snapshots.")]
     pub enable_spans: bool,
     #[structopt(long, help = "Writes all AST snapshots for the different compiler phases.")]
@@ -55,19 +89,64 @@ pub struct BuildOptions {
     pub enable_canonicalized_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot after the type inference phase.")]
     pub enable_type_inferenced_ast_snapshot: bool,
+    #[structopt(long, help = "Writes AST snapshot of the input file.")]
+    pub enable_input_ast_snapshot: bool,
 }
 
 impl From<BuildOptions> for CompilerOptions {
     fn from(options: BuildOptions) -> Self {
+        let max_expression_depth = options
+            .max_expression_depth
+            .unwrap_or(leo_ast::DEFAULT_MAX_EXPRESSION_DEPTH);
+        let entrypoints = if options.entrypoints.is_empty() {
+            vec!["main".to_string()]
+        } else {
+            options.entrypoints.clone()
+        };
+        let integer_widening = if options.widen_integers {
+            IntegerWidening::Lossless
+        } else {
+            IntegerWidening::Forbid
+        };
         if options.disable_all_optimizations {
             CompilerOptions {
                 constant_folding_enabled: false,
                 dead_code_elimination_enabled: false,
+                fold_range_comparisons: false,
+                simplify_boolean_expressions: false,
+                keep_true_asserts: options.keep_true_asserts,
+                renaming_enabled: options.enable_renaming,
+                max_expression_depth,
+                limits: ProgramLimits::default(),
+                strict_field_literals: options.strict_field_literals,
+                integer_widening,
+                verify_ast: options.verify_ast,
+                emit_dot: options.emit_dot,
+                cluster_dot: options.cluster_dot,
+                record_provenance: false,
+                entrypoints: entrypoints.clone(),
+                progress: Default::default(),
+                cancellation: Default::default(),
             }
         } else {
             CompilerOptions {
                 constant_folding_enabled: !options.disable_constant_folding,
                 dead_code_elimination_enabled: !options.disable_code_elimination,
+                fold_range_comparisons: options.fold_range_comparisons,
+                simplify_boolean_expressions: options.simplify_boolean_expressions,
+                keep_true_asserts: options.keep_true_asserts,
+                renaming_enabled: options.enable_renaming,
+                max_expression_depth,
+                limits: ProgramLimits::default(),
+                strict_field_literals: options.strict_field_literals,
+                integer_widening,
+                verify_ast: options.verify_ast,
+                emit_dot: options.emit_dot,
+                cluster_dot: options.cluster_dot,
+                record_provenance: false,
+                entrypoints: entrypoints.clone(),
+                progress: Default::default(),
+                cancellation: Default::default(),
             }
         }
     }
@@ -82,6 +161,7 @@ impl From<BuildOptions> for AstSnapshotOptions {
                 imports_resolved: true,
                 canonicalized: true,
                 type_inferenced: true,
+                input: true,
             }
         } else {
             AstSnapshotOptions {
@@ -90,6 +170,7 @@ impl From<BuildOptions> for AstSnapshotOptions {
                 imports_resolved: options.enable_imports_resolved_ast_snapshot,
                 canonicalized: options.enable_canonicalized_ast_snapshot,
                 type_inferenced: options.enable_type_inferenced_ast_snapshot,
+                input: options.enable_input_ast_snapshot,
             }
         }
     }
@@ -166,6 +247,8 @@ impl Command for Build {
             Default::default()
         };
 
+        let entrypoints = self.compiler_options.entrypoints.clone();
+
         // Load the program at `main_file_path`
         let program = Compiler::<Fq, EdwardsGroupType>::parse_program_with_input(
             package_name.clone(),
@@ -195,6 +278,10 @@ impl Command for Build {
             let temporary_program = program.clone();
             let output = temporary_program.compile_constraints(&mut cs)?;
 
+            for line in output.logs.iter() {
+                line.emit();
+            }
+
             tracing::debug!("Compiled output - {:#?}", output);
             tracing::info!("Number of constraints - {:#?}", cs.num_constraints());
 
@@ -216,9 +303,29 @@ impl Command for Build {
             // println!("deserialized {:?}", circuit_synthesizer.num_constraints());
         }
 
+        // Compile the other entrypoints, reporting every one that fails before failing the build.
+        if entrypoints.iter().any(|entrypoint| entrypoint != "main") {
+            let mut failed = None;
+            for (entrypoint, result) in program.compile_entrypoints() {
+                match result {
+                    Ok(_) => tracing::info!("Compiled entrypoint `{}`", entrypoint),
+                    Err(error) => {
+                        tracing::error!("{}", error);
+                        failed.get_or_insert(error);
+                    }
+                }
+            }
+            if let Some(error) = failed {
+                return Err(error);
+            }
+        }
+
+        // Sources that are not files, such as the standard input, cannot be compared between builds.
+        let tracked = program.main_file().is_real();
+
         // If a checksum file exists, check if it differs from the new checksum
         let checksum_file = ChecksumFile::new(&package_name);
-        let checksum_differs = if checksum_file.exists_at(&package_path) {
+        let checksum_differs = if tracked && checksum_file.exists_at(&package_path) {
             let previous_checksum = checksum_file.read_from(&package_path)?;
             program_checksum != previous_checksum
         } else {
@@ -227,7 +334,7 @@ impl Command for Build {
         };
 
         // If checksum differs, compile the program
-        if checksum_differs {
+        if checksum_differs && tracked {
             // Write the new checksum to the output directory
             checksum_file.write_to(&path, program_checksum)?;
 
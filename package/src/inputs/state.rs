@@ -88,6 +88,7 @@ commitment: [u8; 32] = [0; 32];
 owner: address = aleo1daxej63vwrmn2zhl4dymygagh89k5d2vaw6rjauueme7le6k2q8sjn0ng9;
 is_dummy: bool = false;
 value: u64 = 0;
+gates: u64 = 0;
 payload: [u8; 32] = [0; 32];
 birth_program_id: [u8; 48] = [0; 48];
 death_program_id: [u8; 48] = [0; 48];
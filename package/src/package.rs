@@ -32,6 +32,8 @@ pub struct Package {
     pub version: String,
     pub description: Option<String>,
     pub license: Option<String>,
+    /// The edition of the language the package is written in, e.g. `2022`.
+    pub edition: Option<String>,
 }
 
 impl Package {
@@ -46,6 +48,7 @@ impl Package {
             version: "0.1.0".to_owned(),
             description: None,
             license: None,
+            edition: None,
         })
     }
 
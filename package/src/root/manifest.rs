@@ -108,6 +108,10 @@ impl Manifest {
         self.project.license.clone()
     }
 
+    pub fn get_package_edition(&self) -> Option<String> {
+        self.project.edition.clone()
+    }
+
     pub fn get_package_remote(&self) -> Option<Remote> {
         self.remote.clone()
     }
@@ -138,6 +142,7 @@ name = "{name}"
 version = "0.1.0"
 description = "The {name} package"
 license = "MIT"
+edition = "2022"
 
 [remote]
 author = "{author}" # Add your Aleo Package Manager username or team name.
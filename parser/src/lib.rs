@@ -26,11 +26,15 @@ pub(crate) mod tokenizer;
 pub use tokenizer::KEYWORD_TOKENS;
 pub(crate) use tokenizer::*;
 
+pub mod migrate;
+
 pub mod parser;
 pub use parser::*;
 
-use leo_ast::Ast;
-use leo_errors::Result;
+use leo_ast::{Ast, Edition, Program};
+use leo_errors::{panic_message, CancellationToken, CompilerError, LeoError, ParserError, Result};
+
+use std::panic;
 
 #[cfg(test)]
 mod test;
@@ -39,3 +43,51 @@ mod test;
 pub fn parse_ast<T: AsRef<str>, Y: AsRef<str>>(path: T, source: Y) -> Result<Ast> {
     Ok(Ast::new(parser::parse(path.as_ref(), source.as_ref())?))
 }
+
+/// Like [`parse_ast`], but stops with an error once `cancellation` is cancelled or past its deadline.
+pub fn parse_ast_cancellable<T: AsRef<str>, Y: AsRef<str>>(
+    path: T,
+    source: Y,
+    cancellation: &CancellationToken,
+) -> Result<Ast> {
+    Ok(Ast::new(parser::parse_cancellable(
+        path.as_ref(),
+        source.as_ref(),
+        cancellation,
+    )?))
+}
+
+/// Like [`parse_ast_cancellable`], but fails on syntax that `edition` does not have.
+pub fn parse_ast_for_edition<T: AsRef<str>, Y: AsRef<str>>(
+    path: T,
+    source: Y,
+    edition: Edition,
+    cancellation: &CancellationToken,
+) -> Result<Ast> {
+    Ok(Ast::new(parser::parse_for_edition(
+        path.as_ref(),
+        source.as_ref(),
+        edition,
+        cancellation,
+    )?))
+}
+
+/// Parses source text of unknown origin, such as fuzzer input, without ever panicking.
+///
+/// Returns the program, if the source parses, along with the diagnostics produced on the way.
+/// Invalid UTF-8 is reported as a diagnostic, as is a panic inside the parser, which is always a bug.
+pub fn parse_fallible<T: AsRef<[u8]>>(source: T) -> (Option<Program>, Vec<LeoError>) {
+    let source = match std::str::from_utf8(source.as_ref()) {
+        Ok(source) => source,
+        Err(error) => return (None, vec![ParserError::invalid_utf8(error.valid_up_to()).into()]),
+    };
+
+    match panic::catch_unwind(|| parser::parse("input.leo", source)) {
+        Ok(Ok(program)) => (Some(program), Vec::new()),
+        Ok(Err(error)) => (None, vec![error]),
+        Err(payload) => (
+            None,
+            vec![CompilerError::compiler_panicked(panic_message(payload.as_ref())).into()],
+        ),
+    }
+}
@@ -0,0 +1,547 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Rewrites Leo source written for one version of the language into the syntax of a later version.
+//!
+//! Migration works on the tokens of the source rather than on its AST, so it also applies to files
+//! that parse under neither version. Every version step has its own [`Rule`]s, each of which finds
+//! the places it rewrites with a small parser of its own. Where a rule cannot tell what to write,
+//! it leaves a `TODO(migrate)` comment in front of the code instead of guessing.
+
+use std::fmt;
+
+use crate::{tokenize, SpannedToken, Token};
+use leo_ast::Edition;
+use leo_errors::{ParserError, Result, Span};
+
+/// The prefix of the comments left where a rule could not rewrite the source.
+const TODO_MARKER: &str = "/* TODO(migrate):";
+
+/// A version of the Leo language whose syntax differs from the version before it.
+///
+/// Each version is an [`Edition`], so that migrating a program one step is what moving it to the next edition takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LangVersion {
+    /// Leo 1.4, which marked mutable variables and parameters with `mut` and declared tests with `test function`.
+    V1_4,
+    /// Leo 1.5, the syntax this parser reads.
+    V1_5,
+}
+
+impl LangVersion {
+    /// The version of the syntax this parser reads.
+    pub const CURRENT: LangVersion = LangVersion::V1_5;
+
+    /// Returns the version after this one, if there is one.
+    pub fn next(self) -> Option<LangVersion> {
+        match self {
+            LangVersion::V1_4 => Some(LangVersion::V1_5),
+            LangVersion::V1_5 => None,
+        }
+    }
+
+    /// Returns the edition of this version of the syntax.
+    pub fn edition(self) -> Edition {
+        match self {
+            LangVersion::V1_4 => Edition::E2021,
+            LangVersion::V1_5 => Edition::E2022,
+        }
+    }
+}
+
+impl fmt::Display for LangVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LangVersion::V1_4 => write!(f, "1.4"),
+            LangVersion::V1_5 => write!(f, "1.5"),
+        }
+    }
+}
+
+/// A piece of source that a rule rewrote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationEdit {
+    /// The name of the rule.
+    pub rule: &'static str,
+    /// Where the replaced text is, in the source of the version the rule migrates from.
+    pub span: Span,
+    pub original: String,
+    pub replacement: String,
+}
+
+/// A place that a rule could not rewrite without guessing, marked with a `TODO(migrate)` comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationTodo {
+    /// The name of the rule.
+    pub rule: &'static str,
+    /// Where the code to rewrite by hand is, in the source of the version the rule migrates from.
+    pub span: Span,
+    pub message: String,
+}
+
+/// Source migrated to a later version, with what was rewritten and what is left to do by hand.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigratedSource {
+    pub source: String,
+    pub edits: Vec<MigrationEdit>,
+    pub todos: Vec<MigrationTodo>,
+}
+
+///
+/// A rewrite from the syntax of one version into that of the next.
+///
+/// A rule never changes code that already has the syntax of the next version,
+/// nor code marked by an earlier run, so that migrating twice changes nothing.
+///
+pub struct Rule {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The version whose syntax the rule rewrites.
+    pub from: LangVersion,
+    find: fn(&Tokens) -> Vec<Change>,
+}
+
+impl Rule {
+    /// Applies only this rule to `source`.
+    pub fn apply(&self, source: &str) -> Result<MigratedSource> {
+        rewrite(source, &[self])
+    }
+}
+
+/// The rules of every version step, in the order they were introduced.
+pub const RULES: &[Rule] = &[
+    Rule {
+        name: "let_mut",
+        description: "`let mut a` is `let a`, as variables declared with `let` are mutable",
+        from: LangVersion::V1_4,
+        find: let_mut,
+    },
+    Rule {
+        name: "mut_parameter",
+        description: "`mut a: u32` parameters are `a: u32`, as parameters are mutable",
+        from: LangVersion::V1_4,
+        find: mut_parameter,
+    },
+    Rule {
+        name: "test_function",
+        description: "`test function` is `@test function`",
+        from: LangVersion::V1_4,
+        find: test_function,
+    },
+    Rule {
+        name: "context_annotation",
+        description: "`@context(input)` is `@test(input)`",
+        from: LangVersion::V1_4,
+        find: context_annotation,
+    },
+    Rule {
+        name: "reserved_names",
+        description: "declarations named `char` or `type`, which are keywords, are marked to be renamed",
+        from: LangVersion::V1_4,
+        find: reserved_names,
+    },
+];
+
+/// Returns the rule named `name`.
+pub fn rule(name: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.name == name)
+}
+
+///
+/// Rewrites `source`, written for the `from` version of the language, into the syntax of the `to` version.
+///
+/// The steps between the versions are applied one after the other, so the spans of an edit
+/// are in the source of the version its rule migrates from.
+///
+pub fn migrate(source: &str, from: LangVersion, to: LangVersion) -> Result<MigratedSource> {
+    if from > to {
+        return Err(ParserError::migration_downgrade(from, to).into());
+    }
+
+    let mut migrated = MigratedSource {
+        source: source.to_string(),
+        ..Default::default()
+    };
+    let mut version = from;
+    while version < to {
+        let rules = RULES.iter().filter(|rule| rule.from == version).collect::<Vec<_>>();
+        let step = rewrite(&migrated.source, &rules)?;
+        migrated.source = step.source;
+        migrated.edits.extend(step.edits);
+        migrated.todos.extend(step.todos);
+        version = version.next().expect("a version before another has a next one");
+    }
+    Ok(migrated)
+}
+
+/// A change that a rule asks for, at byte offsets into the source.
+pub(crate) enum Change {
+    Replace {
+        start: usize,
+        end: usize,
+        span: Span,
+        replacement: String,
+    },
+    Todo {
+        start: usize,
+        span: Span,
+        message: String,
+    },
+}
+
+impl Change {
+    fn start(&self) -> usize {
+        match self {
+            Change::Replace { start, .. } | Change::Todo { start, .. } => *start,
+        }
+    }
+}
+
+/// A token of the source other than a comment.
+pub(crate) struct Lexeme {
+    token: Token,
+    span: Span,
+    start: usize,
+    end: usize,
+    /// Set if a `TODO(migrate)` comment is right in front of the token.
+    marked: bool,
+}
+
+/// The tokens of the source that the rules look at.
+pub(crate) struct Tokens<'a> {
+    source: &'a str,
+    lexemes: Vec<Lexeme>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(source: &'a str) -> Result<Self> {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect::<Vec<_>>();
+        let offset = |line: usize, col: usize| line_starts[line - 1] + col - 1;
+
+        let mut lexemes = vec![];
+        let mut marked = false;
+        for SpannedToken { token, span } in tokenize("input.leo", source.into())? {
+            match &token {
+                Token::CommentBlock(comment) => marked = comment.starts_with(TODO_MARKER),
+                Token::CommentLine(_) => marked = false,
+                _ => {
+                    lexemes.push(Lexeme {
+                        start: offset(span.line_start, span.col_start),
+                        end: offset(span.line_stop, span.col_stop),
+                        token,
+                        span,
+                        marked,
+                    });
+                    marked = false;
+                }
+            }
+        }
+        Ok(Tokens { source, lexemes })
+    }
+
+    fn token(&self, index: usize) -> Option<&Token> {
+        self.lexemes.get(index).map(|lexeme| &lexeme.token)
+    }
+
+    fn is(&self, index: usize, token: Token) -> bool {
+        self.token(index) == Some(&token)
+    }
+
+    fn is_ident(&self, index: usize, name: &str) -> bool {
+        matches!(self.token(index), Some(Token::Ident(ident)) if ident.as_ref() == name)
+    }
+
+    /// Returns the index of the bracket that closes the one at `open`, or the end of the tokens.
+    fn closing(&self, open: usize) -> usize {
+        let (left, right) = match self.token(open) {
+            Some(Token::LeftParen) => (Token::LeftParen, Token::RightParen),
+            Some(Token::LeftCurly) => (Token::LeftCurly, Token::RightCurly),
+            _ => (Token::LeftSquare, Token::RightSquare),
+        };
+        let mut depth = 0;
+        for index in open..self.lexemes.len() {
+            if self.is(index, left.clone()) {
+                depth += 1;
+            } else if self.is(index, right.clone()) {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+        }
+        self.lexemes.len()
+    }
+
+    fn replace(&self, index: usize, replacement: &str) -> Change {
+        let lexeme = &self.lexemes[index];
+        Change::Replace {
+            start: lexeme.start,
+            end: lexeme.end,
+            span: lexeme.span.clone(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    /// Removes the token along with the spaces after it on its line.
+    fn remove(&self, index: usize) -> Change {
+        let lexeme = &self.lexemes[index];
+        let spaces = self.source[lexeme.end..]
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .count();
+        Change::Replace {
+            start: lexeme.start,
+            end: lexeme.end + spaces,
+            span: lexeme.span.clone(),
+            replacement: String::new(),
+        }
+    }
+
+    fn todo(&self, index: usize, message: String) -> Change {
+        let lexeme = &self.lexemes[index];
+        Change::Todo {
+            start: lexeme.start,
+            span: lexeme.span.clone(),
+            message,
+        }
+    }
+}
+
+///
+/// Applies the rules to the tokens of `source`.
+///
+/// The rules of a step look at the same tokens, so a change that overlaps one before it is dropped.
+///
+fn rewrite(source: &str, rules: &[&Rule]) -> Result<MigratedSource> {
+    let tokens = Tokens::new(source)?;
+    let mut changes = rules
+        .iter()
+        .flat_map(|rule| (rule.find)(&tokens).into_iter().map(move |change| (rule.name, change)))
+        .collect::<Vec<_>>();
+    changes.sort_by_key(|(_, change)| change.start());
+
+    let mut migrated = MigratedSource::default();
+    let mut cursor = 0;
+    for (rule, change) in changes {
+        if change.start() < cursor {
+            continue;
+        }
+        migrated.source.push_str(&source[cursor..change.start()]);
+        match change {
+            Change::Replace {
+                start,
+                end,
+                span,
+                replacement,
+            } => {
+                migrated.source.push_str(&replacement);
+                migrated.edits.push(MigrationEdit {
+                    rule,
+                    span,
+                    original: source[start..end].to_string(),
+                    replacement,
+                });
+                cursor = end;
+            }
+            Change::Todo { start, span, message } => {
+                migrated.source.push_str(&format!("{} {} */ ", TODO_MARKER, message));
+                migrated.todos.push(MigrationTodo { rule, span, message });
+                cursor = start;
+            }
+        }
+    }
+    migrated.source.push_str(&source[cursor..]);
+    Ok(migrated)
+}
+
+/// Finds the `mut` of `let mut a` and `let (mut a, mut b)`.
+fn let_mut(tokens: &Tokens) -> Vec<Change> {
+    let mut changes = vec![];
+    for index in 0..tokens.lexemes.len() {
+        if !tokens.is(index, Token::Let) && !tokens.is(index, Token::Const) {
+            continue;
+        }
+        if tokens.is(index + 1, Token::Mut) {
+            changes.push(tokens.remove(index + 1));
+        } else if tokens.is(index + 1, Token::LeftParen) {
+            let close = tokens.closing(index + 1);
+            changes.extend(
+                (index + 2..close)
+                    .filter(|i| tokens.is(*i, Token::Mut))
+                    .map(|i| tokens.remove(i)),
+            );
+        }
+    }
+    changes
+}
+
+/// Finds the `mut` of function parameters other than `mut self`, which is still written that way.
+fn mut_parameter(tokens: &Tokens) -> Vec<Change> {
+    let mut changes = vec![];
+    for index in 0..tokens.lexemes.len() {
+        if !tokens.is(index, Token::Function) || !tokens.is(index + 2, Token::LeftParen) {
+            continue;
+        }
+        let close = tokens.closing(index + 2);
+        let mut depth = 0;
+        for parameter in index + 2..close {
+            match tokens.token(parameter) {
+                Some(Token::LeftParen) | Some(Token::LeftSquare) => depth += 1,
+                Some(Token::RightParen) | Some(Token::RightSquare) => depth -= 1,
+                // The parameters are never closed, so the rest is the body of the function.
+                Some(Token::LeftCurly) => break,
+                Some(Token::Mut) if depth == 1 && matches!(tokens.token(parameter + 1), Some(Token::Ident(_))) => {
+                    changes.push(tokens.remove(parameter));
+                }
+                _ => (),
+            }
+        }
+    }
+    changes
+}
+
+/// A top-level item with the annotations in front of it.
+struct Item {
+    /// The indices of the names of the annotations.
+    annotations: Vec<usize>,
+    /// The index of the first token of the item that is not part of an annotation.
+    start: usize,
+}
+
+impl Item {
+    fn is_annotated(&self, tokens: &Tokens, name: &str) -> bool {
+        self.annotations.iter().any(|index| tokens.is_ident(*index, name))
+    }
+}
+
+/// Splits the tokens at the top level into items, which end at a `;` or at the `}` closing their body.
+fn items(tokens: &Tokens) -> Vec<Item> {
+    let mut items = vec![];
+    let mut annotations = vec![];
+    let mut index = 0;
+    while index < tokens.lexemes.len() {
+        if tokens.is(index, Token::At) {
+            annotations.push(index + 1);
+            index += 2;
+            if tokens.is(index, Token::LeftParen) {
+                index = tokens.closing(index) + 1;
+            }
+            continue;
+        }
+
+        items.push(Item {
+            annotations: std::mem::take(&mut annotations),
+            start: index,
+        });
+        while index < tokens.lexemes.len() {
+            match tokens.token(index) {
+                Some(Token::Semicolon) => break,
+                // The `;` of an array type such as `[u8; 2]` does not end the item.
+                Some(Token::LeftParen) | Some(Token::LeftSquare) => index = tokens.closing(index) + 1,
+                Some(Token::LeftCurly) => {
+                    index = tokens.closing(index);
+                    // A circuit initializer in a global constant does not end it.
+                    if !tokens.is(index + 1, Token::Semicolon) {
+                        break;
+                    }
+                }
+                _ => index += 1,
+            }
+        }
+        index += 1;
+    }
+    items
+}
+
+/// Finds the `test` of `test function`.
+fn test_function(tokens: &Tokens) -> Vec<Change> {
+    items(tokens)
+        .into_iter()
+        .filter(|item| tokens.is_ident(item.start, "test") && tokens.is(item.start + 1, Token::Function))
+        .map(|item| {
+            if item.is_annotated(tokens, "test") || item.is_annotated(tokens, "context") {
+                tokens.remove(item.start)
+            } else {
+                tokens.replace(item.start, "@test")
+            }
+        })
+        .collect()
+}
+
+/// Finds the `context` of `@context(input)`, which is left alone where the function has another test annotation.
+fn context_annotation(tokens: &Tokens) -> Vec<Change> {
+    let mut changes = vec![];
+    for item in items(tokens) {
+        let tests = item
+            .annotations
+            .iter()
+            .filter(|index| tokens.is_ident(**index, "test") || tokens.is_ident(**index, "context"))
+            .count();
+        for &index in item.annotations.iter() {
+            if !tokens.is_ident(index, "context") || tokens.lexemes[index - 1].marked {
+                continue;
+            }
+            if tests == 1 {
+                changes.push(tokens.replace(index, "test"));
+            } else {
+                changes.push(tokens.todo(
+                    index - 1,
+                    "the function has more than one test annotation; merge them into one `@test`".to_string(),
+                ));
+            }
+        }
+    }
+    changes
+}
+
+/// Finds declarations and member accesses named `char` or `type`, whose new name is for the author to choose.
+fn reserved_names(tokens: &Tokens) -> Vec<Change> {
+    let mut changes = vec![];
+    for (index, lexeme) in tokens.lexemes.iter().enumerate() {
+        if !matches!(lexeme.token, Token::Char | Token::Type) || lexeme.marked {
+            continue;
+        }
+        let declared = index
+            .checked_sub(1)
+            .and_then(|previous| tokens.token(previous))
+            .map(|previous| {
+                matches!(
+                    previous,
+                    Token::Let
+                        | Token::Const
+                        | Token::Mut
+                        | Token::Function
+                        | Token::Circuit
+                        | Token::For
+                        | Token::Dot
+                        | Token::DoubleColon
+                )
+            })
+            .unwrap_or(false);
+        if declared || tokens.is(index + 1, Token::Colon) {
+            changes.push(tokens.todo(
+                index,
+                format!(
+                    "`{}` is a keyword since Leo 1.5; rename this and every use of it",
+                    lexeme.token
+                ),
+            ));
+        }
+    }
+    changes
+}
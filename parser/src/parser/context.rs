@@ -16,25 +16,35 @@
 
 use std::{borrow::Cow, unimplemented};
 
+use super::recovery::Recovery;
 use crate::{assert_no_whitespace, tokenizer::*, Token, KEYWORD_TOKENS};
 use leo_ast::*;
 use leo_errors::{LeoError, ParserError, Result, Span};
 use tendril::format_tendril;
 
+/// How deeply brackets, blocks, types and unary operators may nest before parsing fails with a diagnostic.
+pub const MAX_NESTING_DEPTH: usize = 1_000;
+
 /// Stores a program in tokenized format plus additional context.
 /// May be converted into a [`Program`] AST by parsing all tokens.
 pub struct ParserContext {
-    tokens: Vec<SpannedToken>,
-    end_span: Span,
+    pub(crate) tokens: Vec<SpannedToken>,
+    pub(crate) end_span: Span,
     // true if parsing an expression for an if statement -- means circuit inits are not legal
     pub(crate) fuzzy_struct_state: bool,
+    // how many nested constructs enclose the current token
+    depth: usize,
+    // set when recovering from syntax errors instead of failing on the first one
+    pub(crate) recovery: Option<Recovery>,
+    // the edition gated syntax is checked against
+    pub(crate) edition: Edition,
 }
 
 impl Iterator for ParserContext {
     type Item = SpannedToken;
 
     fn next(&mut self) -> Option<SpannedToken> {
-        self.tokens.pop()
+        self.bump()
     }
 }
 
@@ -57,7 +67,75 @@ impl ParserContext {
                 .unwrap_or_default(),
             tokens,
             fuzzy_struct_state: false,
+            depth: 0,
+            recovery: None,
+            edition: Edition::LATEST,
+        }
+    }
+
+    ///
+    /// Returns the [`ParserContext`] with gated syntax checked against `edition` instead of the latest edition.
+    ///
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = edition;
+        self
+    }
+
+    ///
+    /// Returns a new [`ParserContext`] that recovers from unclosed delimiters by synthesizing their closers.
+    /// `unterminated` are the spans of the literals the tokenizer closed at the end of their line.
+    ///
+    pub fn new_recovering(tokens: Vec<SpannedToken>, unterminated: Vec<Span>) -> Self {
+        let mut context = Self::new(tokens);
+        context.recovery = Some(Recovery::new(unterminated));
+        context
+    }
+
+    ///
+    /// Returns the errors recovered from and the regions of source the parser made up, if recovering.
+    ///
+    pub fn take_recovered(&mut self) -> (Vec<LeoError>, Vec<Span>) {
+        match self.recovery.take() {
+            Some(recovery) => (recovery.errors, recovery.regions),
+            None => (Vec::new(), Vec::new()),
+        }
+    }
+
+    ///
+    /// Removes and returns the next token, if it exists.
+    ///
+    pub(crate) fn bump(&mut self) -> Option<SpannedToken> {
+        let token = self.tokens.pop()?;
+        if let Some(recovery) = &mut self.recovery {
+            recovery.consume(&token);
+        }
+        Some(token)
+    }
+
+    ///
+    /// Runs `parse` one nesting level deeper, or returns an error if that exceeds [`MAX_NESTING_DEPTH`].
+    /// `extra` counts additional levels the caller is about to build without recursing, such as unary operators.
+    ///
+    pub(crate) fn nested<T>(&mut self, extra: usize, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth + extra >= MAX_NESTING_DEPTH {
+            let span = self
+                .tokens
+                .last()
+                .map(|x| x.span.clone())
+                .unwrap_or_else(|| self.end_span.clone());
+            return Err(ParserError::nesting_too_deep(MAX_NESTING_DEPTH, &span).into());
         }
+        self.depth += 1;
+        let result = ensure_stack(|| parse(self));
+        self.depth -= 1;
+        result
+    }
+
+    ///
+    /// Returns an error naming the feature if the edition being parsed does not have it.
+    ///
+    pub(crate) fn require(&self, feature: Feature, span: &Span) -> Result<()> {
+        feature.check(self.edition, span)
     }
 
     ///
@@ -71,7 +149,11 @@ impl ParserContext {
     /// Returns a reference to the next next token or error if it does not exist.
     ///
     pub fn peek_next(&self) -> Result<&SpannedToken> {
-        self.tokens.get(self.tokens.len() - 2).ok_or_else(|| self.eof())
+        self.tokens
+            .len()
+            .checked_sub(2)
+            .and_then(|index| self.tokens.get(index))
+            .ok_or_else(|| self.eof())
     }
 
     ///
@@ -119,7 +201,7 @@ impl ParserContext {
     pub fn eat(&mut self, token: Token) -> Option<SpannedToken> {
         if let Some(SpannedToken { token: inner, .. }) = self.tokens.last() {
             if &token == inner {
-                return self.tokens.pop();
+                return self.bump();
             }
         }
         None
@@ -129,6 +211,9 @@ impl ParserContext {
     /// Appends a token to the back of the vector.
     ///
     pub fn backtrack(&mut self, token: SpannedToken) {
+        if let Some(recovery) = &mut self.recovery {
+            recovery.unconsume();
+        }
         self.tokens.push(token);
     }
 
@@ -141,7 +226,7 @@ impl ParserContext {
             token: Token::Ident(_), ..
         }) = self.tokens.last()
         {
-            let token = self.tokens.pop().unwrap();
+            let token = self.bump().unwrap();
             if let SpannedToken {
                 token: Token::Ident(name),
                 span,
@@ -265,7 +350,7 @@ impl ParserContext {
             token: Token::Int(_), ..
         }) = self.tokens.last()
         {
-            let token = self.tokens.pop().unwrap();
+            let token = self.bump().unwrap();
             if let SpannedToken {
                 token: Token::Int(value),
                 span,
@@ -286,7 +371,7 @@ impl ParserContext {
     pub fn eat_any(&mut self, token: &[Token]) -> Option<SpannedToken> {
         if let Some(SpannedToken { token: inner, .. }) = self.tokens.last() {
             if token.iter().any(|x| x == inner) {
-                return self.tokens.pop();
+                return self.bump();
             }
         }
         None
@@ -296,9 +381,14 @@ impl ParserContext {
     /// Returns the span of the next token if it is equal to the given [`Token`], or error.
     ///
     pub fn expect(&mut self, token: Token) -> Result<Span> {
+        if self.peek_token().as_ref() != &token {
+            if let Some(span) = self.recover_expected(&token) {
+                return Ok(span);
+            }
+        }
         if let Some(SpannedToken { token: inner, span }) = self.tokens.last() {
             if &token == inner {
-                Ok(self.tokens.pop().unwrap().span)
+                Ok(self.bump().unwrap().span)
             } else {
                 Err(ParserError::unexpected(inner, token, span).into())
             }
@@ -313,7 +403,7 @@ impl ParserContext {
     pub fn expect_oneof(&mut self, token: &[Token]) -> Result<SpannedToken> {
         if let Some(SpannedToken { token: inner, span }) = self.tokens.last() {
             if token.iter().any(|x| x == inner) {
-                Ok(self.tokens.pop().unwrap())
+                Ok(self.bump().unwrap())
             } else {
                 return Err(ParserError::unexpected(
                     inner,
@@ -350,7 +440,7 @@ impl ParserContext {
     pub fn expect_ident(&mut self) -> Result<Identifier> {
         if let Some(SpannedToken { token: inner, span }) = self.tokens.last() {
             if let Token::Ident(_) = inner {
-                let token = self.tokens.pop().unwrap();
+                let token = self.bump().unwrap();
                 if let SpannedToken {
                     token: Token::Ident(name),
                     span,
@@ -368,11 +458,27 @@ impl ParserContext {
         }
     }
 
+    ///
+    /// Returns an [`Identifier`] AST node for the name of a new variable or parameter.
+    ///
+    /// Besides identifiers, this accepts `input`: a function may declare its own variable by that name,
+    /// which then hides the program input inside the function.
+    ///
+    pub fn expect_binding_name(&mut self) -> Result<Identifier> {
+        if let Some(token) = self.eat(Token::Input) {
+            return Ok(Identifier {
+                name: token.token.to_string().into(),
+                span: token.span,
+            });
+        }
+        self.expect_ident()
+    }
+
     ///
     /// Returns the next token if it exists or return end of function.
     ///
     pub fn expect_any(&mut self) -> Result<SpannedToken> {
-        if let Some(x) = self.tokens.pop() {
+        if let Some(x) = self.bump() {
             Ok(x)
         } else {
             Err(self.eof())
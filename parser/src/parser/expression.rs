@@ -48,7 +48,7 @@ impl ParserContext {
         self.fuzzy_struct_state = false;
 
         // Parse expression.
-        let result = self.parse_conditional_expression();
+        let result = self.nested(0, Self::parse_conditional_expression);
 
         // Restore prior parser state.
         self.fuzzy_struct_state = prior_fuzzy_state;
@@ -70,7 +70,7 @@ impl ParserContext {
         if self.eat(Token::Question).is_some() {
             let if_true = self.parse_expression()?;
             self.expect(Token::Colon)?;
-            let if_false = self.parse_conditional_expression()?;
+            let if_false = self.nested(0, Self::parse_conditional_expression)?;
             expr = Expression::Ternary(TernaryExpression {
                 span: expr.span() + if_false.span(),
                 condition: Box::new(expr),
@@ -360,7 +360,11 @@ impl ParserContext {
         while let Some(token) = self.eat_any(&[Token::Not, Token::Minus]) {
             ops.push(token);
         }
-        let mut inner = self.parse_postfix_expression()?;
+        let mut inner = if ops.is_empty() {
+            self.parse_postfix_expression()?
+        } else {
+            self.nested(ops.len(), Self::parse_postfix_expression)?
+        };
         for op in ops.into_iter().rev() {
             let operation = match op.token {
                 Token::Not => UnaryOperation::Not,
@@ -370,6 +374,19 @@ impl ParserContext {
             };
             // hack for const signed integer overflow issues
             if matches!(operation, UnaryOperation::Negate) {
+                // `-(128i8)` is the literal `-128i8` as well.
+                inner = match inner {
+                    Expression::Parenthesized(parenthesized)
+                        if matches!(
+                            *parenthesized.inner,
+                            Expression::Value(ValueExpression::Integer(..))
+                                | Expression::Value(ValueExpression::Implicit(..))
+                        ) =>
+                    {
+                        *parenthesized.inner
+                    }
+                    inner => inner,
+                };
                 if let Expression::Value(ValueExpression::Integer(type_, value, span)) = inner {
                     inner = Expression::Value(ValueExpression::Integer(
                         type_,
@@ -487,7 +504,7 @@ impl ParserContext {
                             break;
                         }
                         arguments.push(self.parse_expression()?);
-                        if self.eat(Token::Comma).is_none() {
+                        if !self.eat_separator()? {
                             end_span = self.expect(Token::RightParen)?;
                             break;
                         }
@@ -552,7 +569,7 @@ impl ParserContext {
                     expression: None,
                 });
             }
-            if self.eat(Token::Comma).is_none() {
+            if !self.eat_separator()? {
                 end_span = self.expect(Token::RightCurly)?;
                 break;
             }
@@ -579,6 +596,7 @@ impl ParserContext {
             )))));
         }
         let mut args = Vec::new();
+        let mut trailing_comma = false;
         let end_span;
         loop {
             let end = self.eat(Token::RightParen);
@@ -588,12 +606,18 @@ impl ParserContext {
             }
             let expr = self.parse_expression()?;
             args.push(expr);
-            if self.eat(Token::Comma).is_none() {
+            trailing_comma = self.eat_separator()?;
+            if !trailing_comma {
                 end_span = self.expect(Token::RightParen)?;
                 break;
             }
         }
-        if args.len() == 1 {
+        if args.len() == 1 && !trailing_comma {
+            Ok(Expression::Parenthesized(ParenthesizedExpression {
+                inner: Box::new(args.remove(0)),
+                span: span + &end_span,
+            }))
+        } else if args.len() == 1 {
             Ok(args.remove(0))
         } else {
             Ok(Expression::TupleInit(TupleInitExpression {
@@ -603,6 +627,34 @@ impl ParserContext {
         }
     }
 
+    ///
+    /// Returns an [`Expression`] AST node if the next tokens represent the
+    /// `i in start..stop]` rest of an array comprehension, following its `for`.
+    ///
+    pub fn parse_array_comprehension(&mut self, span: &Span, element: Expression) -> Result<Expression> {
+        let variable = self.expect_ident()?;
+        self.expect(Token::In)?;
+        let start = self.parse_expression()?;
+        self.expect(Token::DotDot)?;
+        let inclusive = self.eat(Token::Assign).is_some();
+        let stop = self.parse_expression()?;
+        if let Some(filter) = self.eat(Token::If) {
+            return Err(ParserError::array_comprehension_filter(&filter.span).into());
+        }
+        let end = self.expect(Token::RightSquare)?;
+        let span = span + &end;
+        self.require(Feature::ArrayComprehensions, &span)?;
+
+        Ok(Expression::ArrayComprehension(ArrayComprehensionExpression {
+            element: Box::new(element),
+            variable,
+            start: Box::new(start),
+            stop: Box::new(stop),
+            inclusive,
+            span,
+        }))
+    }
+
     ///
     /// Returns an [`Expression`] AST node if the next tokens represent an
     /// array initialization expression.
@@ -615,6 +667,16 @@ impl ParserContext {
             }));
         }
         let first = self.parse_spread_or_expression()?;
+        if self.eat(Token::For).is_some() {
+            let element = match first {
+                SpreadOrExpression::Spread(first) => {
+                    let span = span + first.span();
+                    return Err(ParserError::spread_in_array_init(&span).into());
+                }
+                SpreadOrExpression::Expression(x) => x,
+            };
+            return self.parse_array_comprehension(span, element);
+        }
         if self.eat(Token::Semicolon).is_some() {
             let dimensions = self
                 .parse_array_dimensions()?
@@ -641,14 +703,16 @@ impl ParserContext {
                     break;
                 }
                 if elements.len() == 1 {
-                    self.expect(Token::Comma)?;
+                    if !self.eat_separator()? {
+                        self.expect(Token::Comma)?;
+                    }
                     if let Some(token) = self.eat(Token::RightSquare) {
                         end_span = token.span;
                         break;
                     }
                 }
                 elements.push(self.parse_spread_or_expression()?);
-                if self.eat(Token::Comma).is_none() {
+                if !self.eat_separator()? {
                     end_span = self.expect(Token::RightSquare)?;
                     break;
                 }
@@ -744,7 +808,9 @@ impl ParserContext {
             //     inner: Box::new(self.parse_primary_expression()?),
             // }),
             token => {
-                return Err(ParserError::unexpected_str(token, "expression", &span).into());
+                let error = ParserError::unexpected_str(&token, "expression", &span).into();
+                self.backtrack(SpannedToken { token, span });
+                Expression::Err(self.recover_placeholder(error, EXPRESSION_STOPPERS)?)
             }
         })
     }
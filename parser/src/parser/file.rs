@@ -26,69 +26,68 @@ impl ParserContext {
     ///
     /// Returns a [`Program`] AST if all tokens can be consumed and represent a valid Leo program.
     ///
+    /// When recovering, an item that fails to parse is skipped up to the start of the next item.
+    ///
     pub fn parse_program(&mut self) -> Result<Program> {
-        let mut import_statements = Vec::new();
-        let mut aliases = IndexMap::new();
-        let mut circuits = IndexMap::new();
-        let mut functions = IndexMap::new();
-        let mut global_consts = IndexMap::new();
-        // let mut tests = IndexMap::new();
+        let mut program = Program::new(String::new());
 
         while self.has_next() {
-            let token = self.peek()?;
-            match &token.token {
-                Token::Import => {
-                    import_statements.push(self.parse_import_statement()?);
-                }
-                Token::Circuit => {
-                    let (id, circuit) = self.parse_circuit()?;
-                    circuits.insert(id, circuit);
-                }
-                Token::Function | Token::At => {
-                    let (id, function) = self.parse_function_declaration()?;
-                    functions.insert(id, function);
-                }
-                Token::Ident(ident) if ident.as_ref() == "test" => {
-                    return Err(ParserError::test_function(&token.span).into());
-                }
-                Token::Const => {
-                    let (name, global_const) = self.parse_global_const_declaration()?;
-                    global_consts.insert(name, global_const);
-                }
-                Token::Type => {
-                    let (name, alias) = self.parse_type_alias()?;
-                    aliases.insert(name, alias);
-                }
-                _ => {
-                    return Err(ParserError::unexpected(
-                        &token.token,
-                        [
-                            Token::Import,
-                            Token::Circuit,
-                            Token::Function,
-                            Token::Ident("test".into()),
-                            Token::At,
-                        ]
-                        .iter()
-                        .map(|x| format!("'{}'", x))
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                        &token.span,
-                    )
-                    .into());
-                }
+            let remaining = self.tokens.len();
+            if let Err(error) = self.parse_item(&mut program) {
+                self.recover_item(error, remaining)?;
             }
         }
-        Ok(Program {
-            name: String::new(),
-            expected_input: Vec::new(),
-            import_statements,
-            imports: IndexMap::new(),
-            aliases,
-            circuits,
-            functions,
-            global_consts,
-        })
+        Ok(program)
+    }
+
+    ///
+    /// Adds the import, circuit, function, global constant or type alias the next tokens represent to `program`.
+    ///
+    fn parse_item(&mut self, program: &mut Program) -> Result<()> {
+        let token = self.peek()?;
+        match &token.token {
+            Token::Import => {
+                program.import_statements.push(self.parse_import_statement()?);
+            }
+            Token::Circuit => {
+                let (id, circuit) = self.parse_circuit()?;
+                program.circuits.insert(id, circuit);
+            }
+            Token::Function | Token::At => {
+                let (id, function) = self.parse_function_declaration()?;
+                program.functions.insert(id, function);
+            }
+            Token::Ident(ident) if ident.as_ref() == "test" => {
+                return Err(ParserError::test_function(&token.span).into());
+            }
+            Token::Const => {
+                let (name, global_const) = self.parse_global_const_declaration()?;
+                program.global_consts.insert(name, global_const);
+            }
+            Token::Type => {
+                let (name, alias) = self.parse_type_alias()?;
+                program.aliases.insert(name, alias);
+            }
+            _ => {
+                return Err(ParserError::unexpected(
+                    &token.token,
+                    [
+                        Token::Import,
+                        Token::Circuit,
+                        Token::Function,
+                        Token::Ident("test".into()),
+                        Token::At,
+                    ]
+                    .iter()
+                    .map(|x| format!("'{}'", x))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                    &token.span,
+                )
+                .into());
+            }
+        }
+        Ok(())
     }
 
     ///
@@ -126,7 +125,18 @@ impl ParserContext {
                 }
                 comma = false;
                 if let Some(ident) = self.eat_identifier() {
-                    args.push(ident.name);
+                    // A named argument `name = 5` is kept as `name=5`.
+                    if self.eat(Token::Assign).is_some() {
+                        match self.eat_int() {
+                            Some((int, _)) => args.push(format!("{}={}", ident.name, int.value).into()),
+                            None => {
+                                let token = self.peek()?;
+                                return Err(ParserError::unexpected_str(&token.token, "int", &token.span).into());
+                            }
+                        }
+                    } else {
+                        args.push(ident.name);
+                    }
                 } else if let Some((int, _)) = self.eat_int() {
                     args.push(int.value);
                 } else {
@@ -161,7 +171,7 @@ impl ParserContext {
         while self.eat(Token::RightParen).is_none() {
             let access = self.parse_package_access()?;
             out.push(access);
-            if self.eat(Token::Comma).is_none() {
+            if !self.eat_separator()? {
                 self.expect(Token::RightParen)?;
                 break;
             }
@@ -321,7 +331,15 @@ impl ParserContext {
         let mut last_variable = peeked == &Token::Function || peeked == &Token::At;
         let (mut semi_colons, mut commas) = (false, false);
         while self.eat(Token::RightCurly).is_none() {
-            if !last_variable {
+            if self.recover_unclosed(Token::RightCurly, true).is_some() {
+                break;
+            }
+            if self.peek()?.token == Token::Static {
+                members.push(self.parse_member_static_declaration()?);
+
+                let peeked = &self.peek()?.token;
+                last_variable = peeked == &Token::Function || peeked == &Token::At;
+            } else if !last_variable {
                 let (variable, last) = self.parse_member_variable_declaration()?;
 
                 members.push(variable);
@@ -340,7 +358,7 @@ impl ParserContext {
                     }
 
                     commas = true;
-                    self.eat(Token::Comma);
+                    self.eat_separator()?;
                 }
 
                 if last {
@@ -376,6 +394,22 @@ impl ParserContext {
         Ok((CircuitMember::CircuitVariable(name, type_), false))
     }
 
+    ///
+    /// Returns a [`CircuitMember`] AST node if the next tokens represent a static circuit member,
+    /// such as `static SIZE: u32 = 8;`.
+    ///
+    pub fn parse_member_static_declaration(&mut self) -> Result<CircuitMember> {
+        self.expect(Token::Static)?;
+        let name = self.expect_ident()?;
+        self.expect(Token::Colon)?;
+        let type_ = self.parse_type()?.0;
+        self.expect(Token::Assign)?;
+        let value = self.parse_expression()?;
+        self.expect(Token::Semicolon)?;
+
+        Ok(CircuitMember::CircuitStatic(name, type_, value))
+    }
+
     ///
     /// Returns a [`CircuitMember`] AST node if the next tokens represent a circuit member function.
     ///
@@ -430,7 +464,7 @@ impl ParserContext {
                 span: token.span,
             }
         } else {
-            self.expect_ident()?
+            self.expect_binding_name()?
         };
         if name.name.as_ref() == "self" {
             if let Some(mutable) = &mutable {
@@ -479,7 +513,7 @@ impl ParserContext {
         while self.eat(Token::RightParen).is_none() {
             let input = self.parse_function_parameters()?;
             inputs.push(input);
-            if self.eat(Token::Comma).is_none() {
+            if !self.eat_separator()? {
                 self.expect(Token::RightParen)?;
                 break;
             }
@@ -22,6 +22,9 @@
 mod context;
 pub use context::*;
 
+mod recovery;
+use recovery::{EXPRESSION_STOPPERS, TYPE_STOPPERS};
+
 pub mod expression;
 pub mod file;
 pub mod statement;
@@ -30,9 +33,8 @@ pub mod type_;
 use std::unimplemented;
 
 use crate::{tokenizer::*, Token};
-use indexmap::IndexMap;
 use leo_ast::*;
-use leo_errors::{ParserError, Result, Span};
+use leo_errors::{CancellationToken, LeoError, ParserError, Result, Span};
 
 pub(crate) fn assert_no_whitespace(left_span: &Span, right_span: &Span, left: &str, right: &str) -> Result<()> {
     if left_span.col_stop != right_span.col_start {
@@ -47,7 +49,58 @@ pub(crate) fn assert_no_whitespace(left_span: &Span, right_span: &Span, left: &s
 
 /// Creates a new program from a given file path and source code text.
 pub fn parse(path: &str, source: &str) -> Result<Program> {
-    let mut tokens = ParserContext::new(crate::tokenize(path, source.into())?);
+    parse_cancellable(path, source, &CancellationToken::default())
+}
+
+/// Like [`parse`], but stops with an error once `cancellation` is cancelled or past its deadline.
+pub fn parse_cancellable(path: &str, source: &str, cancellation: &CancellationToken) -> Result<Program> {
+    parse_for_edition(path, source, Edition::LATEST, cancellation)
+}
+
+/// Like [`parse_cancellable`], but fails on syntax that `edition` does not have.
+pub fn parse_for_edition(
+    path: &str,
+    source: &str,
+    edition: Edition,
+    cancellation: &CancellationToken,
+) -> Result<Program> {
+    let mut tokens =
+        ParserContext::new(crate::tokenize_cancellable(path, source.into(), cancellation)?).with_edition(edition);
 
     tokens.parse_program()
 }
+
+/// Creates a new program from a given file path and source code text, recovering from syntax errors.
+///
+/// Unterminated string and char literals are closed at the end of their line, unclosed delimiters are closed
+/// before the start of the next item, and items that still fail to parse are skipped.
+/// Returns the program along with an error for each recovery; the program is only complete if there are none.
+pub fn parse_recovering(path: &str, source: &str) -> (Program, Vec<LeoError>) {
+    if let Ok(program) = parse(path, source) {
+        return (program, Vec::new());
+    }
+
+    let RecoveredTokens {
+        tokens,
+        unterminated,
+        mut errors,
+    } = match crate::tokenize_recovering(path, source.into()) {
+        Ok(tokens) => tokens,
+        Err(error) => return (Program::new(String::new()), vec![error]),
+    };
+    let mut context = ParserContext::new_recovering(tokens, unterminated);
+    let program = context.parse_program();
+    let (recovered_errors, recovered) = context.take_recovered();
+    errors.extend(recovered_errors);
+
+    match program {
+        Ok(mut program) => {
+            program.recovered = recovered;
+            (program, errors)
+        }
+        Err(error) => {
+            errors.push(error);
+            (Program::new(String::new()), errors)
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{tokenizer::*, ParserContext, Token};
+use leo_ast::ErrNode;
+use leo_errors::{LeoError, ParserError, Result, Span};
+
+/// The tokens that end an expression the parser could not make sense of, outside of parentheses and brackets.
+pub(crate) const EXPRESSION_STOPPERS: &[Token] = &[
+    Token::Semicolon,
+    Token::Comma,
+    Token::RightParen,
+    Token::RightSquare,
+    Token::LeftCurly,
+];
+
+/// The tokens that end a type the parser could not make sense of, outside of parentheses and brackets.
+pub(crate) const TYPE_STOPPERS: &[Token] = &[
+    Token::Semicolon,
+    Token::Comma,
+    Token::RightParen,
+    Token::RightSquare,
+    Token::LeftCurly,
+    Token::Assign,
+];
+
+///
+/// State kept by a [`ParserContext`] that recovers from syntax errors instead of failing on the first one.
+///
+#[derive(Default)]
+pub(crate) struct Recovery {
+    /// The errors recovered from.
+    pub(crate) errors: Vec<LeoError>,
+    /// The regions of source whose syntax was made up by the parser.
+    pub(crate) regions: Vec<Span>,
+    /// The spans of the literals the tokenizer closed at the end of their line.
+    unterminated: Vec<Span>,
+    /// The tokens consumed so far, including synthesized ones, to find the delimiter a closer belongs to.
+    consumed: Vec<SpannedToken>,
+    /// Whether the last token consumed from the source is an unterminated literal.
+    after_unterminated: bool,
+}
+
+impl Recovery {
+    pub(crate) fn new(unterminated: Vec<Span>) -> Self {
+        Self {
+            regions: unterminated.clone(),
+            unterminated,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn consume(&mut self, token: &SpannedToken) {
+        self.after_unterminated = self.unterminated.contains(&token.span);
+        self.consumed.push(token.clone());
+    }
+
+    pub(crate) fn unconsume(&mut self) {
+        self.consumed.pop();
+    }
+
+    /// Returns the innermost delimiter opened but not closed by the consumed tokens that `closer` would close.
+    fn unclosed_opener(&self, closer: &Token) -> Option<&SpannedToken> {
+        let opener = match closer {
+            Token::RightParen => Token::LeftParen,
+            Token::RightSquare => Token::LeftSquare,
+            Token::RightCurly => Token::LeftCurly,
+            _ => return None,
+        };
+        let mut depth = 0usize;
+        for token in self.consumed.iter().rev() {
+            if &token.token == closer {
+                depth += 1;
+            } else if token.token == opener {
+                if depth == 0 {
+                    return Some(token);
+                }
+                depth -= 1;
+            }
+        }
+        None
+    }
+}
+
+fn is_closer(token: &Token) -> bool {
+    matches!(token, Token::RightParen | Token::RightSquare | Token::RightCurly)
+}
+
+/// Returns the source of the tokens, separated by a space where the source has whitespace between them.
+fn source_text(tokens: &[SpannedToken]) -> String {
+    let mut text = String::new();
+    let mut previous: Option<&Span> = None;
+    for SpannedToken { token, span } in tokens.iter() {
+        if let Some(previous) = previous {
+            if previous.line_stop != span.line_start || previous.col_stop < span.col_start {
+                text.push(' ');
+            }
+        }
+        let source = match span.line_start == span.line_stop {
+            true => span.content.get(span.col_start - 1..span.col_stop - 1),
+            false => None,
+        };
+        match source {
+            Some(source) => text.push_str(source),
+            None => text.push_str(&token.to_string()),
+        }
+        previous = Some(span);
+    }
+    text
+}
+
+impl ParserContext {
+    ///
+    /// Returns `true` if the next token starts a top-level item, or there is no next token.
+    /// Inside a circuit, functions are members rather than items.
+    ///
+    fn at_item_boundary(&self, in_circuit: bool) -> bool {
+        match self.peek_token().as_ref() {
+            Token::Eof | Token::Circuit | Token::Import | Token::Type => true,
+            Token::Function | Token::At => !in_circuit,
+            _ => false,
+        }
+    }
+
+    ///
+    /// In recovery mode, returns the span of a `closer` synthesized before the start of the next item, if one starts.
+    /// The delimiter it closes is reported as unclosed.
+    ///
+    pub(crate) fn recover_unclosed(&mut self, closer: Token, in_circuit: bool) -> Option<Span> {
+        if self.recovery.is_none() || !self.at_item_boundary(in_circuit) {
+            return None;
+        }
+        self.synthesize(closer, true)
+    }
+
+    ///
+    /// In recovery mode, returns the span of an expected `token` synthesized in place of the next token.
+    ///
+    /// After an unterminated literal, which took the rest of its line, closers and semicolons are synthesized silently.
+    /// Before the start of the next item, closers are synthesized and the delimiter they close is reported as unclosed.
+    ///
+    pub(crate) fn recover_expected(&mut self, token: &Token) -> Option<Span> {
+        let after_unterminated = self.recovery.as_ref()?.after_unterminated;
+        if after_unterminated && (is_closer(token) || token == &Token::Semicolon) {
+            self.synthesize(token.clone(), false)
+        } else if is_closer(token) && self.at_item_boundary(false) {
+            self.synthesize(token.clone(), true)
+        } else {
+            None
+        }
+    }
+
+    fn synthesize(&mut self, token: Token, report: bool) -> Option<Span> {
+        let end_span = self.end_span.clone();
+        let recovery = self.recovery.as_mut()?;
+        let span = recovery.consumed.last().map(|x| x.span.clone()).unwrap_or(end_span);
+        if report {
+            if let Some(opener) = recovery.unclosed_opener(&token) {
+                let error = ParserError::unclosed_delimiter(&opener.token, &opener.span).into();
+                let region = &opener.span + &span;
+                recovery.errors.push(error);
+                recovery.regions.push(region);
+            }
+        }
+        recovery.consumed.push(SpannedToken {
+            token,
+            span: span.clone(),
+        });
+        Some(span)
+    }
+
+    ///
+    /// Removes the `,` separating two elements of a list, returning `false` if the next token is not one.
+    ///
+    /// Any `,` repeated right after it is an error. In recovery mode, the error is recorded and the
+    /// extra `,` is skipped, so the list goes on as if it was not there.
+    ///
+    pub(crate) fn eat_separator(&mut self) -> Result<bool> {
+        if self.eat(Token::Comma).is_none() {
+            return Ok(false);
+        }
+        while let Some(extra) = self.eat(Token::Comma) {
+            let error = ParserError::repeated_comma(&extra.span).into();
+            match self.recovery.as_mut() {
+                Some(recovery) => recovery.errors.push(error),
+                None => return Err(error),
+            }
+        }
+        Ok(true)
+    }
+
+    ///
+    /// In recovery mode, records the error an expression or type failed to parse with, and returns a placeholder
+    /// for the tokens up to one of the `stoppers`, a `}` or the start of the next item, which are left to the caller.
+    /// Otherwise, returns the error.
+    ///
+    pub(crate) fn recover_placeholder(&mut self, error: LeoError, stoppers: &[Token]) -> Result<ErrNode> {
+        match self.recovery.as_mut() {
+            Some(recovery) => recovery.errors.push(error),
+            None => return Err(error),
+        }
+
+        let mut skipped = vec![];
+        let mut depth = 0usize;
+        loop {
+            let stop = match self.peek_token().as_ref() {
+                Token::LeftParen | Token::LeftSquare => {
+                    depth += 1;
+                    false
+                }
+                Token::RightParen | Token::RightSquare if depth > 0 => {
+                    depth -= 1;
+                    false
+                }
+                Token::RightCurly | Token::Eof => true,
+                token => depth == 0 && (stoppers.contains(token) || self.at_item_boundary(false)),
+            };
+            if stop {
+                break;
+            }
+            skipped.extend(self.bump());
+        }
+
+        let placeholder = match (skipped.first(), skipped.last()) {
+            (Some(first), Some(last)) => ErrNode {
+                span: &first.span + &last.span,
+                text: Some(source_text(&skipped)),
+            },
+            _ => ErrNode {
+                span: self
+                    .peek()
+                    .map(|next| next.span.clone())
+                    .unwrap_or_else(|_| self.end_span.clone()),
+                text: None,
+            },
+        };
+        if let Some(recovery) = self.recovery.as_mut() {
+            recovery.regions.push(placeholder.span.clone());
+        }
+        Ok(placeholder)
+    }
+
+    ///
+    /// In recovery mode, records the error an item failed to parse with, and skips to the start of the next item.
+    /// `remaining` is the number of tokens left before the item, so that at least one token is skipped.
+    /// Otherwise, returns the error.
+    ///
+    pub(crate) fn recover_item(&mut self, error: LeoError, remaining: usize) -> Result<()> {
+        match self.recovery.as_mut() {
+            Some(recovery) => recovery.errors.push(error),
+            None => return Err(error),
+        }
+        if self.tokens.len() == remaining {
+            self.bump();
+        }
+        while !self.at_item_boundary(false) {
+            self.bump();
+        }
+        Ok(())
+    }
+}
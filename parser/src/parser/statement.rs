@@ -64,6 +64,7 @@ impl ParserContext {
                 accesses.push(AssigneeAccess::ArrayIndex(*expr.index));
             }
             Expression::Identifier(id) => identifier = id,
+            Expression::Parenthesized(expr) => identifier = Self::construct_assignee_access(*expr.inner, accesses)?,
             _ => return Err(ParserError::invalid_assignment_target(expr.span()).into()),
         }
         Ok(identifier)
@@ -151,7 +152,13 @@ impl ParserContext {
         loop {
             match self.eat(Token::RightCurly) {
                 None => {
-                    statements.push(self.parse_statement()?);
+                    if let Some(end) = self.recover_unclosed(Token::RightCurly, false) {
+                        return Ok(Block {
+                            span: start + end,
+                            statements,
+                        });
+                    }
+                    statements.push(self.nested(0, Self::parse_statement)?);
                 }
                 Some(end) => {
                     return Ok(Block {
@@ -168,6 +175,16 @@ impl ParserContext {
     ///
     pub fn parse_return_statement(&mut self) -> Result<ReturnStatement> {
         let start = self.expect(Token::Return)?;
+        // A bare `return;` returns the empty tuple, exactly as `return ();` does.
+        if let Some(end) = self.eat(Token::Semicolon) {
+            return Ok(ReturnStatement {
+                span: &start + &end.span,
+                expression: Expression::TupleInit(TupleInitExpression {
+                    elements: vec![],
+                    span: start,
+                }),
+            });
+        }
         let expr = self.parse_expression()?;
         self.expect(Token::Semicolon)?;
 
@@ -187,7 +204,7 @@ impl ParserContext {
         self.fuzzy_struct_state = false;
         let body = self.parse_block()?;
         let next = if self.eat(Token::Else).is_some() {
-            let s = self.parse_statement()?;
+            let s = self.nested(0, Self::parse_statement)?;
             match s {
                 Statement::Block(_) | Statement::Conditional(_) => Some(Box::new(s)),
                 s => {
@@ -211,13 +228,21 @@ impl ParserContext {
     ///
     pub fn parse_loop_statement(&mut self) -> Result<IterationStatement> {
         let start_span = self.expect(Token::For)?;
-        let ident = self.expect_ident()?;
+        let ident = self.expect_binding_name()?;
         self.expect(Token::In)?;
         let start = self.parse_expression()?;
         self.expect(Token::DotDot)?;
         let inclusive = self.eat(Token::Assign).is_some();
         self.fuzzy_struct_state = true;
         let stop = self.parse_conditional_expression()?;
+        // `step` is not a keyword, so that it can still be used as a name.
+        let step = match self.eat(Token::Ident("step".into())) {
+            Some(step) => {
+                self.require(Feature::LoopSteps, &step.span)?;
+                Some(self.parse_conditional_expression()?)
+            }
+            None => None,
+        };
         self.fuzzy_struct_state = false;
         let block = self.parse_block()?;
 
@@ -227,6 +252,7 @@ impl ParserContext {
             start,
             stop,
             inclusive,
+            step,
             block,
         })
     }
@@ -252,7 +278,7 @@ impl ParserContext {
         // let parts = FormatStringPart::from_string(string);
 
         let mut parameters = Vec::new();
-        while self.eat(Token::Comma).is_some() {
+        while self.eat_separator()? && self.peek_token().as_ref() != &Token::RightParen {
             let param = self.parse_expression()?;
             parameters.push(param);
         }
@@ -264,6 +290,22 @@ impl ParserContext {
         })
     }
 
+    ///
+    /// Returns a [`ConsoleAssertArgs`] AST node if the next tokens represent the two operands of an assertion.
+    ///
+    pub fn parse_console_assert_args(&mut self) -> Result<ConsoleAssertArgs> {
+        let left = self.parse_expression()?;
+        self.expect(Token::Comma)?;
+        let right = self.parse_expression()?;
+        self.eat(Token::Comma);
+
+        Ok(ConsoleAssertArgs {
+            span: left.span() + right.span(),
+            left,
+            right,
+        })
+    }
+
     ///
     /// Returns a [`ConsoleStatement`] AST node if the next tokens represent a console statement.
     ///
@@ -275,12 +317,20 @@ impl ParserContext {
         let function = match &*function.name {
             "assert" => {
                 let expr = self.parse_expression()?;
+                self.eat(Token::Comma);
                 ConsoleFunction::Assert(expr)
             }
+            "assert_eq" => ConsoleFunction::AssertEq(self.parse_console_assert_args()?),
+            "assert_ne" => ConsoleFunction::AssertNe(self.parse_console_assert_args()?),
             "error" => ConsoleFunction::Error(self.parse_console_args()?),
             "log" => ConsoleFunction::Log(self.parse_console_args()?),
             x => {
-                return Err(ParserError::unexpected_ident(x, &["assert", "error", "log"], &function.span).into());
+                return Err(ParserError::unexpected_ident(
+                    x,
+                    &["assert", "assert_eq", "assert_ne", "error", "log"],
+                    &function.span,
+                )
+                .into());
             }
         };
         self.expect(Token::RightParen)?;
@@ -302,7 +352,7 @@ impl ParserContext {
             return Err(ParserError::let_mut_statement(&(&mutable.span + &span.span)).into());
         }
 
-        let name = self.expect_ident()?;
+        let name = self.expect_binding_name()?;
         Ok(VariableName {
             span: name.span.clone(),
             mutable: matches!(span.token, Token::Let),
@@ -321,7 +371,7 @@ impl ParserContext {
         variable_names.push(self.parse_variable_name(&declare)?);
         if next.is_some() {
             let mut eaten_ending = false;
-            while self.eat(Token::Comma).is_some() {
+            while self.eat_separator()? {
                 if self.eat(Token::RightParen).is_some() {
                     eaten_ending = true;
                     break;
@@ -334,10 +384,29 @@ impl ParserContext {
         }
 
         let type_ = if self.eat(Token::Colon).is_some() {
-            Some(self.parse_type()?.0)
+            Some(self.parse_type()?)
         } else {
             None
         };
+        let declaration_type = match declare.token {
+            Token::Let => Declare::Let,
+            Token::Const => Declare::Const,
+            _ => unimplemented!(),
+        };
+
+        // `let a: u32;` declares a variable that is assigned later.
+        if let (Declare::Let, Some((type_, type_span))) = (&declaration_type, &type_) {
+            if let Some(end) = self.eat(Token::Semicolon) {
+                self.require(Feature::UninitializedDeclarations, &(&declare.span + &end.span))?;
+                return Ok(DefinitionStatement {
+                    span: &declare.span + type_span,
+                    declaration_type,
+                    variable_names,
+                    type_: Some(type_.clone()),
+                    value: None,
+                });
+            }
+        }
 
         self.expect(Token::Assign)?;
         let expr = self.parse_expression()?;
@@ -345,14 +414,10 @@ impl ParserContext {
 
         Ok(DefinitionStatement {
             span: &declare.span + expr.span(),
-            declaration_type: match declare.token {
-                Token::Let => Declare::Let,
-                Token::Const => Declare::Const,
-                _ => unimplemented!(),
-            },
+            declaration_type,
             variable_names,
-            type_,
-            value: expr,
+            type_: type_.map(|(type_, _)| type_),
+            value: Some(expr),
         })
     }
 }
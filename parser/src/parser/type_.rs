@@ -73,7 +73,7 @@ impl ParserContext {
                     let token = self.peek()?;
                     return Err(ParserError::unexpected_str(&token.token, "int", &token.span).into());
                 }
-                if self.eat(Token::Comma).is_none() {
+                if !self.eat_separator()? || self.peek_token().as_ref() == &Token::RightParen {
                     break;
                 }
             }
@@ -100,21 +100,28 @@ impl ParserContext {
                     end_span = end.span;
                     break;
                 }
-                types.push(self.parse_type()?.0);
-                if self.eat(Token::Comma).is_none() {
+                types.push(self.nested(0, Self::parse_type)?.0);
+                if !self.eat_separator()? {
                     end_span = self.expect(Token::RightParen)?;
                     break;
                 }
             }
             (Type::Tuple(types), token.span + end_span)
         } else if let Some(token) = self.eat(Token::LeftSquare) {
-            let (inner, _) = self.parse_type()?;
+            let (inner, _) = self.nested(0, Self::parse_type)?;
             self.expect(Token::Semicolon)?;
             let dimensions = self.parse_array_dimensions()?;
             let end_span = self.expect(Token::RightSquare)?;
             (Type::Array(Box::new(inner), dimensions), token.span + end_span)
         } else {
-            let token = self.expect_oneof(TYPE_TOKENS)?;
+            let token = match self.expect_oneof(TYPE_TOKENS) {
+                Ok(token) => token,
+                Err(error) => {
+                    let placeholder = self.recover_placeholder(error, TYPE_STOPPERS)?;
+                    let span = placeholder.span.clone();
+                    return Ok((Type::Err(placeholder), span));
+                }
+            };
             (
                 match token.token {
                     Token::Field => Type::Field,
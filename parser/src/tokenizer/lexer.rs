@@ -40,13 +40,16 @@ fn eat(input: &[u8], wanted: &str) -> Option<usize> {
 /// Returns a new `StrTendril` string if an identifier can be eaten, otherwise returns [`None`].
 /// An identifier can be eaten if its bytes are at the front of the given `input_tendril` string.
 ///
+/// An identifier starts with a letter, or with an underscore that is not on its own.
+///
 fn eat_identifier(input_tendril: &StrTendril) -> Option<StrTendril> {
     if input_tendril.is_empty() {
         return None;
     }
     let input = input_tendril[..].as_bytes();
 
-    if !input[0].is_ascii_alphabetic() {
+    let is_part = |byte: &u8| byte.is_ascii_alphanumeric() || *byte == b'_';
+    if !input[0].is_ascii_alphabetic() && !(input[0] == b'_' && input.get(1).map_or(false, is_part)) {
         return None;
     }
 
@@ -71,7 +74,7 @@ impl Token {
 
         if escaped {
             let string = input_tendril.to_string();
-            let escaped = &string[1..string.len()];
+            let escaped = string.get(1..).unwrap_or_default();
 
             if escaped.len() != 1 {
                 return None;
@@ -95,7 +98,7 @@ impl Token {
 
         if hex {
             let string = input_tendril.to_string();
-            let hex_string = &string[2..string.len()];
+            let hex_string = string.get(2..).unwrap_or_default();
 
             if hex_string.len() != 2 {
                 return None;
@@ -113,11 +116,11 @@ impl Token {
 
         if unicode {
             let string = input_tendril.to_string();
-            if &string[string.len() - 1..] != "}" {
+            if !string.ends_with('}') {
                 return None;
             }
 
-            let unicode_number = &string[3..string.len() - 1];
+            let unicode_number = string.get(3..string.len() - 1).unwrap_or_default();
             let len = unicode_number.len();
             if !(1..=6).contains(&len) {
                 return None;
@@ -205,7 +208,7 @@ impl Token {
             x if x.is_ascii_whitespace() => return (1, None),
             b'"' => {
                 let mut i = 1;
-                let mut len: u8 = 1;
+                let mut len: usize = 1;
                 let mut start = 1;
                 let mut in_escape = false;
                 let mut escaped = false;
@@ -217,8 +220,8 @@ impl Token {
                 while i < input.len() {
                     // If it's an emoji get the length.
                     if input[i] & 0x80 > 0 {
-                        len = Self::utf8_byte_count(input[i]);
-                        i += (len as usize) - 1;
+                        len = Self::utf8_byte_count(input[i]) as usize;
+                        i += len - 1;
                     }
 
                     if !in_escape {
@@ -256,12 +259,12 @@ impl Token {
                     }
 
                     if !in_escape {
-                        match Self::eat_char(
-                            input_tendril.subtendril(start as u32, len as u32),
-                            escaped,
-                            hex,
-                            unicode,
-                        ) {
+                        // Malformed escapes can leave `start..start + len` outside the input or inside a character.
+                        let character = match input_tendril.try_subtendril(start as u32, len as u32) {
+                            Ok(character) => character,
+                            Err(_) => return (0, None),
+                        };
+                        match Self::eat_char(character, escaped, hex, unicode) {
                             Some(character) => {
                                 len = 1;
                                 escaped = false;
@@ -306,7 +309,7 @@ impl Token {
                         if input[i] == b'x' {
                             hex = true;
                         } else if input[i] == b'u' {
-                            if input[i + 1] == b'{' {
+                            if input.get(i + 1) == Some(&b'{') {
                                 unicode = true;
                             } else {
                                 return (0, None);
@@ -356,7 +359,12 @@ impl Token {
             }
             b'(' => return (1, Some(Token::LeftParen)),
             b')' => return (1, Some(Token::RightParen)),
-            b'_' => return (1, Some(Token::Underscore)),
+            b'_' => {
+                if let Some(ident) = eat_identifier(&input_tendril) {
+                    return (ident.len(), Some(Token::Ident(ident)));
+                }
+                return (1, Some(Token::Underscore));
+            }
             b'*' => {
                 if let Some(len) = eat(input, "**") {
                     if let Some(inner_len) = eat(&input[len..], "=") {
@@ -400,11 +408,7 @@ impl Token {
                     let len = if let Some(eol) = eol { eol + 1 } else { input.len() };
                     return (len, Some(Token::CommentLine(input_tendril.subtendril(0, len as u32))));
                 } else if eat(input, "/*").is_some() {
-                    if input.is_empty() {
-                        return (0, None);
-                    }
-                    let eol = input.windows(2).skip(2).position(|x| x[0] == b'*' && x[1] == b'/');
-                    let len = if let Some(eol) = eol { eol + 4 } else { input.len() };
+                    let (len, _) = scan_block_comment(input);
                     return (len, Some(Token::CommentBlock(input_tendril.subtendril(0, len as u32))));
                 } else if let Some(len) = eat(input, "/=") {
                     return (len, Some(Token::DivEq));
@@ -554,6 +558,35 @@ impl fmt::Debug for SpannedToken {
     }
 }
 
+///
+/// Scans the block comment at the front of `input`, which starts with `/*`.
+/// Block comments nest, so every `/*` inside the comment needs its own `*/`.
+///
+/// Returns the length of the comment and, if it is not closed before the end of `input`,
+/// the offset of the innermost comment that is still open.
+///
+pub(crate) fn scan_block_comment(input: &[u8]) -> (usize, Option<usize>) {
+    let mut open = vec![0usize];
+    let mut i = 2usize;
+    while i + 1 < input.len() {
+        match &input[i..i + 2] {
+            b"/*" => {
+                open.push(i);
+                i += 2;
+            }
+            b"*/" => {
+                open.pop();
+                i += 2;
+                if open.is_empty() {
+                    return (i, None);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    (input.len(), open.last().copied())
+}
+
 ///
 /// Returns true if the given string looks like Aleo address.
 /// This method DOES NOT check if the address is valid on-chain.
@@ -28,19 +28,60 @@ pub(crate) use self::token::*;
 pub(crate) mod lexer;
 pub(crate) use self::lexer::*;
 
-use leo_errors::{LeoError, ParserError, Span};
+use leo_errors::{CancellationToken, LeoError, ParserError, Span};
 
 use tendril::StrTendril;
 
 /// Creates a new vector of spanned tokens from a given file path and source code text.
 pub(crate) fn tokenize(path: &str, input: StrTendril) -> Result<Vec<SpannedToken>, LeoError> {
+    tokenize_cancellable(path, input, &CancellationToken::default())
+}
+
+/// Like [`tokenize`], but stops with an error once `cancellation` is cancelled or past its deadline.
+pub(crate) fn tokenize_cancellable(
+    path: &str,
+    input: StrTendril,
+    cancellation: &CancellationToken,
+) -> Result<Vec<SpannedToken>, LeoError> {
+    Ok(tokenize_with(path, input, false, cancellation)?.tokens)
+}
+
+/// The tokens of a source text lexed with [`tokenize_recovering`].
+pub(crate) struct RecoveredTokens {
+    pub tokens: Vec<SpannedToken>,
+    /// The spans of the literals that were closed at the end of their line.
+    pub unterminated: Vec<Span>,
+    /// An error for each unterminated literal.
+    pub errors: Vec<LeoError>,
+}
+
+/// Like [`tokenize`], but closes a string or char literal that is still open at the end of its line, and keeps going.
+pub(crate) fn tokenize_recovering(path: &str, input: StrTendril) -> Result<RecoveredTokens, LeoError> {
+    tokenize_with(path, input, true, &CancellationToken::default())
+}
+
+fn tokenize_with(
+    path: &str,
+    input: StrTendril,
+    recover: bool,
+    cancellation: &CancellationToken,
+) -> Result<RecoveredTokens, LeoError> {
     let path = Arc::new(path.to_string());
+    let mut checkpoint = cancellation.checkpoint("parsing");
     let mut tokens = vec![];
+    let mut unterminated = vec![];
+    let mut errors = vec![];
     let mut index = 0usize;
     let mut line_no = 1usize;
     let mut line_start = 0usize;
     while input.len() > index {
-        match Token::eat(input.subtendril(index as u32, (input.len() - index) as u32)) {
+        let (token_len, token, closed_at_line_end) = if recover && matches!(input.as_bytes()[index], b'"' | b'\'') {
+            eat_literal_in_line(&input, index)
+        } else {
+            let (token_len, token) = Token::eat(input.subtendril(index as u32, (input.len() - index) as u32));
+            (token_len, token, false)
+        };
+        match (token_len, token) {
             (token_len, Some(token)) => {
                 let mut span = Span::new(
                     line_no,
@@ -59,6 +100,10 @@ pub(crate) fn tokenize(path: &str, input: StrTendril) -> Result<Vec<SpannedToken
                         line_start = index + token_len;
                     }
                     Token::CommentBlock(block) => {
+                        if let (_, Some(innermost)) = scan_block_comment(block.as_bytes()) {
+                            span.col_stop = span.col_start + 2;
+                            return Err(unterminated_comment(block, innermost, &span));
+                        }
                         let line_ct = block.chars().filter(|x| *x == '\n').count();
                         line_no += line_ct;
                         if line_ct > 0 {
@@ -73,26 +118,39 @@ pub(crate) fn tokenize(path: &str, input: StrTendril) -> Result<Vec<SpannedToken
                             return Err(ParserError::invalid_address_lit(address, &span).into());
                         }
                     }
+                    Token::StringLit(_) if closed_at_line_end => {
+                        errors.push(ParserError::unterminated_string(&span).into());
+                        unterminated.push(span.clone());
+                    }
+                    Token::CharLit(_) if closed_at_line_end => {
+                        errors.push(ParserError::unterminated_char(&span).into());
+                        unterminated.push(span.clone());
+                    }
                     _ => (),
                 }
                 tokens.push(SpannedToken { token, span });
                 index += token_len;
+                checkpoint.tick()?;
             }
             (token_len, None) => {
                 if token_len == 0 && index == input.len() {
                     break;
                 } else if token_len == 0 {
+                    let character = input
+                        .get(index..)
+                        .and_then(|rest| rest.chars().next())
+                        .unwrap_or_default();
                     return Err(ParserError::unexpected_token(
-                        &input[index..index + 1],
+                        character,
                         &Span::new(
                             line_no,
                             line_no,
                             index - line_start + 1,
-                            index - line_start + 2,
+                            index - line_start + 1 + character.len_utf8(),
                             path,
                             input.subtendril(
                                 line_start as u32,
-                                input[line_start..].find('\n').unwrap_or_else(|| input.len()) as u32,
+                                input[line_start..].find('\n').unwrap_or(input.len() - line_start) as u32,
                             ),
                         ),
                     )
@@ -106,12 +164,63 @@ pub(crate) fn tokenize(path: &str, input: StrTendril) -> Result<Vec<SpannedToken
             }
         }
     }
-    Ok(tokens)
+    Ok(RecoveredTokens {
+        tokens,
+        unterminated,
+        errors,
+    })
+}
+
+///
+/// Returns the error for the block comment `block`, opened at `span`, that is never closed.
+/// If the innermost comment still open is nested in it, the error also says where that one opens.
+///
+fn unterminated_comment(block: &str, innermost: usize, span: &Span) -> LeoError {
+    let nested = if innermost == 0 {
+        String::new()
+    } else {
+        let before = &block[..innermost];
+        let (line, col) = match before.rfind('\n') {
+            Some(newline) => (span.line_start + before.matches('\n').count(), innermost - newline),
+            None => (span.line_start, span.col_start + innermost),
+        };
+        format!("; the innermost comment still open starts at {}:{}", line, col)
+    };
+    ParserError::unterminated_comment(nested, span).into()
+}
+
+///
+/// Eats the string or char literal starting at `index`, which must be closed on the same line.
+/// A literal that is not is closed at the end of the line, with its raw text as content.
+/// Returns the length of the literal, the literal, and whether it had to be closed.
+///
+fn eat_literal_in_line(input: &StrTendril, index: usize) -> (usize, Option<Token>, bool) {
+    let line_len = input[index..].find('\n').unwrap_or(input.len() - index);
+    let line = input.subtendril(index as u32, line_len as u32);
+    if let (token_len, Some(token)) = Token::eat(line.clone()) {
+        return (token_len, Some(token), false);
+    }
+
+    // A literal whose closing quote is on the line is malformed rather than unterminated, and fails as usual.
+    let quote = line.as_bytes()[0] as char;
+    let rest = line[1..].replace("\\\\", "").replace(&format!("\\{}", quote), "");
+    if rest.contains(quote) {
+        return (0, None, false);
+    }
+
+    let content = line[1..].trim_end_matches('\r');
+    let token = if quote == '"' {
+        Token::StringLit(content.chars().map(leo_ast::Char::Scalar).collect())
+    } else {
+        Token::CharLit(Char::Scalar(content.chars().next().unwrap_or_default()))
+    };
+    (content.len() + 1, Some(token), true)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use leo_errors::{display_column, SourceEdit};
 
     #[test]
     fn test_tokenizer() {
@@ -250,21 +359,122 @@ mod tests {
             /* test
             test */
             test
+            /* outer /* inner */
+            still outer */
+            test
             "#;
         let tokens = tokenize("test_path", raw.into()).unwrap();
-        let mut line_indicies = vec![0];
-        for (i, c) in raw.chars().enumerate() {
-            if c == '\n' {
-                line_indicies.push(i + 1);
-            }
-        }
+        let source = SourceEdit::new(raw);
         for token in tokens.iter() {
             let token_raw = token.token.to_string();
-            let start = line_indicies.get(token.span.line_start - 1).unwrap();
-            let stop = line_indicies.get(token.span.line_stop - 1).unwrap();
-            let original = &raw[*start + token.span.col_start - 1..*stop + token.span.col_stop - 1];
-            assert_eq!(original, &token_raw);
+            assert_eq!(source.extract(&token.span).unwrap(), &token_raw);
         }
         // println!("{}", serde_json::to_string_pretty(&tokens).unwrap());
     }
+
+    #[test]
+    fn test_nested_block_comment() {
+        let tokens = tokenize("test_path", "/* outer /* inner */ still outer */ let x = 1;".into()).unwrap();
+        let output = tokens
+            .iter()
+            .map(|token| token.token.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(output, "/* outer /* inner */ still outer */ let x = 1 ;");
+    }
+
+    #[test]
+    fn test_unterminated_nested_block_comment() {
+        let error = tokenize(
+            "test_path",
+            "let a = 1;\n/* outer\n  /* inner */\n  /* innermost\nlet b = 2;\n".into(),
+        )
+        .unwrap_err();
+        assert_eq!(error.error_code(), "EPAR0370025");
+        let message = error.to_string();
+        // Reported at the outermost `/*`, noting the innermost one.
+        assert!(message.contains("test_path:2:1"));
+        assert!(message.contains("the innermost comment still open starts at 4:3"));
+
+        let error = tokenize("test_path", "let a = 1; /* never closed".into()).unwrap_err();
+        assert!(error.to_string().contains("test_path:1:12"));
+        assert!(!error.to_string().contains("innermost"));
+    }
+
+    #[test]
+    fn test_underscore_identifiers() {
+        let tokens = tokenize("test_path", "_owner __x _1 (_, 1)group [u8; _]".into()).unwrap();
+        let kinds = tokens
+            .iter()
+            .map(|token| match &token.token {
+                Token::Ident(name) => format!("ident {}", name),
+                token => token.to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                "ident _owner",
+                "ident __x",
+                "ident _1",
+                "(",
+                "_",
+                ",",
+                "1",
+                ")",
+                "group",
+                "[",
+                "u8",
+                ";",
+                "_",
+                "]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comment_markers_in_string() {
+        let tokens = tokenize("test_path", r#"let s = "/* not a comment"; /* "*/ let t = 1;"#.into()).unwrap();
+        let output = tokens
+            .iter()
+            .map(|token| token.token.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(output, r#"let s = "/* not a comment" ; /* "*/ let t = 1 ;"#);
+    }
+
+    #[test]
+    fn test_spans_with_crlf_and_multibyte_characters() {
+        let raw = "let s = \"é\"; let zz = s;\r\nlet u = zz;\r\n";
+        let tokens = tokenize("test_path", raw.into()).unwrap();
+        let source = SourceEdit::new(raw);
+        for token in tokens
+            .iter()
+            .filter(|token| !matches!(token.token, Token::StringLit(_)))
+        {
+            assert_eq!(source.extract(&token.span).unwrap(), token.token.to_string());
+            let start = source.pos(token.span.line_start, token.span.col_start).unwrap();
+            assert_eq!(
+                source.line_col(start),
+                Some((token.span.line_start, token.span.col_start))
+            );
+        }
+        assert_eq!(source.line(1), Some("let s = \"é\"; let zz = s;"));
+        assert_eq!(source.line(2), Some("let u = zz;"));
+
+        // The diagnostic underlines the token at the column it is displayed at.
+        let zz = tokens.iter().find(|token| token.token.to_string() == "zz").unwrap();
+        assert_eq!(zz.span.col_start, 19);
+        assert_eq!(display_column(source.line(1).unwrap(), zz.span.col_start), 18);
+        let rendered = ParserError::unexpected_eof(&zz.span).to_string();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        let text = lines.iter().find(|line| line.contains("let zz")).unwrap();
+        let underline = lines.iter().find(|line| line.contains('^')).unwrap();
+        assert_eq!(
+            underline.chars().position(|c| c == '^'),
+            text.chars().position(|c| c == 'z')
+        );
+        assert_eq!(underline.matches('^').count(), 2);
+        assert!(!text.contains('\r'));
+    }
 }
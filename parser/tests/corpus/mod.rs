@@ -0,0 +1,339 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runs the fixtures in `fixtures/pass` and `fixtures/fail` against the recovering parser.
+//!
+//! They are kept apart from `tests/parser`, which the test framework reads with its own headers.
+//!
+//! A fixture lists the diagnostics it expects in the comment lines it starts with, one per line:
+//!
+//! ```text
+//! // error: EPAR0370005 at 3:7
+//! ```
+//!
+//! Diagnostics are compared by code and position, in any order. Fixtures in `pass` expect none,
+//! and the JSON of their AST is compared to the snapshot next to them, which is written if missing.
+//! Setting `CLEAR_LEO_TEST_EXPECTATIONS` rewrites the headers and snapshots to what the parser produces.
+
+use leo_errors::{LeoErrorCode, ParserError};
+use leo_parser::{parse, parse_recovering};
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ERROR_PREFIX: &str = "// error: ";
+
+/// The error codes no fixture can produce, with the reason why.
+const UNEXERCISED_CODES: &[(&str, &str)] = &[
+    (
+        "EPAR0370013",
+        "`illegal_self_const` is no longer reported by the parser",
+    ),
+    (
+        "EPAR0370021",
+        "`invalid_utf8` needs source that is not UTF-8, which fixtures are read as",
+    ),
+    (
+        "EPAR0370027",
+        "`migration_downgrade` is reported by source migration, not while parsing",
+    ),
+];
+
+/// A diagnostic by its code and the line and column its span starts at, if it has one.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Diagnostic {
+    position: Option<(usize, usize)>,
+    code: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code)?;
+        if let Some((line, col)) = self.position {
+            write!(f, " at {}:{}", line, col)?;
+        }
+        Ok(())
+    }
+}
+
+impl Diagnostic {
+    fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.split_whitespace();
+        let code = parts.next()?.to_string();
+        let position = match (parts.next(), parts.next()) {
+            (None, _) => None,
+            (Some("at"), Some(position)) => {
+                let (line, col) = position.split_once(':')?;
+                Some((line.parse().ok()?, col.parse().ok()?))
+            }
+            _ => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { position, code })
+    }
+}
+
+fn corpus_dir(kind: &str) -> PathBuf {
+    let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    dir.push("tests/corpus/fixtures");
+    dir.push(kind);
+    dir
+}
+
+fn find_fixtures(dir: &Path, fixtures: &mut Vec<PathBuf>) {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("failed to read fixture entry").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            find_fixtures(&path, fixtures);
+        } else if path.extension().and_then(|x| x.to_str()) == Some("leo") {
+            fixtures.push(path);
+        }
+    }
+}
+
+fn fixtures(kind: &str) -> Vec<(PathBuf, String)> {
+    let mut paths = Vec::new();
+    find_fixtures(&corpus_dir(kind), &mut paths);
+    paths
+        .into_iter()
+        .map(|path| {
+            let source =
+                fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+            (path, source.replace("\r\n", "\n"))
+        })
+        .collect()
+}
+
+/// Returns the number of comment lines the header of `source` takes.
+fn header_len(source: &str) -> usize {
+    source
+        .lines()
+        .take_while(|line| line.trim_start().starts_with("//"))
+        .count()
+}
+
+/// Returns the diagnostics the header of `source` expects, or the first line that does not state one properly.
+fn expected_diagnostics(source: &str) -> Result<Vec<Diagnostic>, String> {
+    let mut expected = source
+        .lines()
+        .take(header_len(source))
+        .filter_map(|line| line.trim().strip_prefix(ERROR_PREFIX))
+        .map(|text| Diagnostic::parse(text).ok_or_else(|| format!("malformed expectation `{}`", text.trim())))
+        .collect::<Result<Vec<_>, _>>()?;
+    expected.sort();
+    Ok(expected)
+}
+
+fn actual_diagnostics(source: &str) -> Vec<Diagnostic> {
+    let (_, errors) = parse_recovering("", source);
+    let mut actual = errors
+        .iter()
+        .map(|error| Diagnostic {
+            position: error.span().map(|span| (span.line_start, span.col_start)),
+            code: error.error_code(),
+        })
+        .collect::<Vec<_>>();
+    actual.sort();
+    actual
+}
+
+/// Returns `source` with the expectations in its header replaced by `diagnostics`, at its top.
+fn with_expectations(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let header_len = header_len(source);
+    let mut lines = diagnostics
+        .iter()
+        .map(|diagnostic| format!("{}{}", ERROR_PREFIX, diagnostic))
+        .collect::<Vec<_>>();
+    for (index, line) in source.lines().enumerate() {
+        if index >= header_len || !line.trim().starts_with(ERROR_PREFIX) {
+            lines.push(line.to_string());
+        }
+    }
+    let mut out = lines.join("\n");
+    if source.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Rewrites the header of `source` to what the parser produces, which moves the code below it.
+fn bless(mut source: String) -> String {
+    // The diagnostics are only stable once the header has as many lines as there are diagnostics.
+    for _ in 0..3 {
+        let blessed = with_expectations(&source, &actual_diagnostics(&source));
+        if blessed == source {
+            break;
+        }
+        source = blessed;
+    }
+    source
+}
+
+fn is_blessing() -> bool {
+    !std::env::var("CLEAR_LEO_TEST_EXPECTATIONS")
+        .unwrap_or_default()
+        .trim()
+        .is_empty()
+}
+
+fn show(diagnostics: &[Diagnostic]) -> String {
+    if diagnostics.is_empty() {
+        return "none".to_string();
+    }
+    diagnostics.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Checks the diagnostics of a `fail` fixture against its header, returning a description of the mismatch, if any.
+fn check_fixture(path: &Path, source: String) -> Option<String> {
+    let source = if is_blessing() {
+        let blessed = bless(source.clone());
+        if blessed != source {
+            fs::write(path, &blessed).unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        }
+        blessed
+    } else {
+        source
+    };
+
+    let expected = match expected_diagnostics(&source) {
+        Ok(expected) => expected,
+        Err(e) => return Some(format!("{}: {}", path.display(), e)),
+    };
+    let actual = actual_diagnostics(&source);
+    if expected != actual {
+        return Some(format!(
+            "{}: expected {}, got {}",
+            path.display(),
+            show(&expected),
+            show(&actual)
+        ));
+    }
+    None
+}
+
+/// Checks the AST of a `pass` fixture against its snapshot, returning a description of the mismatch, if any.
+fn check_snapshot(path: &Path, source: &str) -> Option<String> {
+    let program = match parse("", source) {
+        Ok(program) => program,
+        Err(e) => return Some(format!("{}: failed to parse: {}", path.display(), e)),
+    };
+    let json = serde_json::to_string_pretty(&program).expect("failed to serialize the ast") + "\n";
+
+    let snapshot = path.with_extension("json");
+    if is_blessing() || !snapshot.exists() {
+        fs::write(&snapshot, &json).unwrap_or_else(|e| panic!("failed to write {}: {}", snapshot.display(), e));
+        return None;
+    }
+    let expected = fs::read_to_string(&snapshot)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", snapshot.display(), e))
+        .replace("\r\n", "\n");
+    if expected != json {
+        return Some(format!(
+            "{}: the ast differs from {}",
+            path.display(),
+            snapshot.display()
+        ));
+    }
+    None
+}
+
+fn assert_no_failures(failures: Vec<String>) {
+    if !failures.is_empty() {
+        panic!(
+            "{} corpus fixture(s) failed; set CLEAR_LEO_TEST_EXPECTATIONS to bless them:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+#[test]
+fn test_pass_corpus() {
+    let mut failures = Vec::new();
+    for (path, source) in fixtures("pass") {
+        let actual = actual_diagnostics(&source);
+        if !actual.is_empty() {
+            failures.push(format!("{}: expected none, got {}", path.display(), show(&actual)));
+            continue;
+        }
+        match expected_diagnostics(&source) {
+            Ok(expected) if expected.is_empty() => (),
+            Ok(_) => {
+                failures.push(format!("{}: expects diagnostics, but is in pass", path.display()));
+                continue;
+            }
+            Err(e) => {
+                failures.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        }
+        if let Some(failure) = check_snapshot(&path, &source) {
+            failures.push(failure);
+        }
+    }
+    assert_no_failures(failures);
+}
+
+#[test]
+fn test_fail_corpus() {
+    let mut failures = Vec::new();
+    for (path, source) in fixtures("fail") {
+        if actual_diagnostics(&source).is_empty() {
+            failures.push(format!("{}: parses without diagnostics", path.display()));
+            continue;
+        }
+        if let Some(failure) = check_fixture(&path, source) {
+            failures.push(failure);
+        }
+    }
+    assert_no_failures(failures);
+}
+
+#[test]
+fn test_fail_corpus_covers_every_error() {
+    let covered = fixtures("fail")
+        .iter()
+        .flat_map(|(path, source)| expected_diagnostics(source).unwrap_or_else(|e| panic!("{}: {}", path.display(), e)))
+        .map(|diagnostic| diagnostic.code)
+        .collect::<BTreeSet<_>>();
+
+    let unexercised = (0..ParserError::num_exit_codes())
+        .map(|exit_code| {
+            format!(
+                "E{}{:0>3}{:0>4}",
+                ParserError::error_type(),
+                ParserError::code_identifier(),
+                ParserError::exit_code_mask() + exit_code,
+            )
+        })
+        .filter(|code| !covered.contains(code))
+        .filter(|code| !UNEXERCISED_CODES.iter().any(|(exempt, _)| exempt == code))
+        .collect::<Vec<_>>();
+
+    assert!(
+        unexercised.is_empty(),
+        "no fixture in fixtures/fail expects {}",
+        unexercised.join(", ")
+    );
+}
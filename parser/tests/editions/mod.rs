@@ -0,0 +1,116 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Edition, Feature, FEATURE_GATES};
+use leo_errors::CancellationToken;
+use leo_parser::{migrate::LangVersion, parse, parse_for_edition};
+
+const COMPREHENSION: &str = "function main() {\n    let a = [i * 2u8 for i in 0..4];\n}\n";
+
+const LOOP_STEP: &str = "function main() {\n    for i in 0u32..16u32 step 2u32 {}\n}\n";
+
+const UNINITIALIZED: &str = "function main() {\n    let a: u8;\n    a = 1;\n}\n";
+
+/// Returns the message of the error the source is rejected with, and the span it points at.
+fn parse_error(source: &str, edition: Edition) -> (String, String) {
+    let error = parse_for_edition("test.leo", source, edition, &CancellationToken::default()).unwrap_err();
+    let span = error.span().map(ToString::to_string).unwrap_or_default();
+    (error.to_string(), span)
+}
+
+#[test]
+fn test_gated_syntax_is_rejected_under_older_editions() {
+    let (error, span) = parse_error(COMPREHENSION, Edition::E2021);
+    assert!(error.contains("array comprehensions require edition 2022"), "{}", error);
+    // The whole comprehension is pointed at.
+    assert_eq!(span, "2:13-36");
+
+    let (error, span) = parse_error(LOOP_STEP, Edition::E2021);
+    assert!(error.contains("loop steps require edition 2022"), "{}", error);
+    assert_eq!(span, "2:26-30");
+
+    let (error, span) = parse_error(UNINITIALIZED, Edition::E2021);
+    assert!(
+        error.contains("declarations without an initializer require edition 2022"),
+        "{}",
+        error
+    );
+    assert_eq!(span, "2:5-15");
+}
+
+#[test]
+fn test_gated_syntax_is_accepted_under_its_edition() {
+    for source in [COMPREHENSION, LOOP_STEP, UNINITIALIZED].iter() {
+        let cancellation = CancellationToken::default();
+        let program = parse_for_edition("test.leo", source, Edition::E2022, &cancellation).unwrap();
+        // The string-based entry points parse the latest edition.
+        assert_eq!(parse("test.leo", source).unwrap(), program);
+    }
+
+    // `step` is still a name in older editions.
+    let source = "function main() {\n    let step = 2u32;\n    for i in 0u32..step {}\n}\n";
+    parse_for_edition("test.leo", source, Edition::E2021, &CancellationToken::default()).unwrap();
+}
+
+/// Every feature, listed alongside a match that stops compiling once a feature is added.
+fn features() -> Vec<Feature> {
+    let features = vec![
+        Feature::ArrayComprehensions,
+        Feature::LoopSteps,
+        Feature::IntegerWidening,
+        Feature::UninitializedDeclarations,
+    ];
+    for feature in features.iter() {
+        match feature {
+            Feature::ArrayComprehensions
+            | Feature::LoopSteps
+            | Feature::IntegerWidening
+            | Feature::UninitializedDeclarations => (),
+        }
+    }
+    features
+}
+
+#[test]
+fn test_every_feature_has_one_gate() {
+    assert_eq!(features(), Feature::ALL.to_vec());
+    for feature in features() {
+        let gates = FEATURE_GATES.iter().filter(|gate| gate.feature == feature).count();
+        assert_eq!(gates, 1, "{:?} has {} gates", feature, gates);
+        assert!(feature.gate().since > Edition::E2021, "{:?} is not gated", feature);
+    }
+    assert_eq!(FEATURE_GATES.len(), features().len());
+
+    for edition in Edition::ALL.iter() {
+        assert_eq!(Edition::from_name(&edition.to_string()), Some(*edition));
+    }
+    assert_eq!(Edition::from_name("2020"), None);
+}
+
+#[test]
+fn test_language_versions_align_with_editions() {
+    let mut version = LangVersion::V1_4;
+    assert_eq!(version.edition(), Edition::ALL[0]);
+    while let Some(next) = version.next() {
+        let index = Edition::ALL
+            .iter()
+            .position(|edition| *edition == version.edition())
+            .unwrap();
+        assert_eq!(next.edition(), Edition::ALL[index + 1]);
+        version = next;
+    }
+    assert_eq!(LangVersion::CURRENT.edition(), Edition::LATEST);
+}
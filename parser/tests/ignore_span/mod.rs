@@ -0,0 +1,212 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Expression, Function, IgnoreSpan, IgnoringSpans, Program, Statement, Traversal};
+use leo_parser::parse_ast;
+
+use std::collections::{hash_map::DefaultHasher, HashSet};
+use std::hash::Hasher;
+
+const PROGRAM: &str = r#"
+circuit Point {
+    x: u32,
+
+    function new() -> Self {
+        return Self { x: 0 };
+    }
+}
+
+function double(a: u32) -> u32 {
+    return a + a;
+}
+
+function main(a: u32, b: bool) -> u32 {
+    let arr = [1u8, 2u8, 3u8];
+    const zeros = [0u8; 3];
+    let evens = [i * 2 for i in 0..4];
+    let t = (a, b);
+    let p = Point { x: a };
+    let q = Point::new();
+    let c = b ? -a : !a;
+    let n = arr.len();
+    let s = arr[0..2];
+    let w = arr[1] as u32;
+    let g = 1group;
+    let f = 1field;
+    let ch = 'a';
+    let e = (p.x + t.0) + double(a) + 1;
+    if b {
+        a += 1;
+    } else {
+        a -= 1;
+    }
+    for i in 0..2 {
+        console.log("{}", i);
+    }
+    {
+        double(a);
+    }
+    return e;
+}
+"#;
+
+/// The same program in another file, moved down and indented differently.
+fn moved_program() -> String {
+    format!("// moved\n\n{}", PROGRAM.replace("    ", "        "))
+}
+
+fn parse(path: &str, source: &str) -> Program {
+    parse_ast(path, source).unwrap().into_repr()
+}
+
+fn function<'a>(program: &'a Program, name: &str) -> &'a Function {
+    program
+        .functions
+        .iter()
+        .find(|(identifier, _)| identifier.name.as_ref() == name)
+        .unwrap()
+        .1
+}
+
+fn hash_of<T: IgnoreSpan>(node: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.hash_ignore_span(&mut hasher);
+    hasher.finish()
+}
+
+/// Names every kind of expression. A new kind fails to compile here until the test covers it.
+fn expression_kind(expression: &Expression) -> &'static str {
+    match expression {
+        Expression::Identifier(_) => "identifier",
+        Expression::Value(_) => "value",
+        Expression::Binary(_) => "binary",
+        Expression::Unary(_) => "unary",
+        Expression::Ternary(_) => "ternary",
+        Expression::Cast(_) => "cast",
+        Expression::LengthOf(_) => "length of",
+        Expression::ArrayInline(_) => "array inline",
+        Expression::ArrayInit(_) => "array init",
+        Expression::ArrayComprehension(_) => "array comprehension",
+        Expression::ArrayAccess(_) => "array access",
+        Expression::ArrayRangeAccess(_) => "array range access",
+        Expression::TupleInit(_) => "tuple init",
+        Expression::TupleAccess(_) => "tuple access",
+        Expression::CircuitInit(_) => "circuit init",
+        Expression::CircuitMemberAccess(_) => "circuit member access",
+        Expression::CircuitStaticFunctionAccess(_) => "circuit static function access",
+        Expression::Call(_) => "call",
+        Expression::Parenthesized(_) => "parenthesized",
+        Expression::Err(_) => "error placeholder",
+    }
+}
+
+/// Names every kind of statement. A new kind fails to compile here until the test covers it.
+fn statement_kind(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::Return(_) => "return",
+        Statement::Definition(_) => "definition",
+        Statement::Assign(_) => "assign",
+        Statement::Conditional(_) => "conditional",
+        Statement::Iteration(_) => "iteration",
+        Statement::Console(_) => "console",
+        Statement::Expression(_) => "expression",
+        Statement::Block(_) => "block",
+    }
+}
+
+#[test]
+fn test_every_kind_ignores_spans() {
+    let program = parse("first.leo", PROGRAM);
+    let moved_source = moved_program();
+    let moved = parse("second.leo", &moved_source);
+
+    let expressions = program.iter_expressions(Traversal::PreOrder).collect::<Vec<_>>();
+    let moved_expressions = moved.iter_expressions(Traversal::PreOrder).collect::<Vec<_>>();
+    assert_eq!(expressions.len(), moved_expressions.len());
+    for (expression, moved_expression) in expressions.iter().zip(moved_expressions.iter()) {
+        assert_ne!(expression, moved_expression);
+        assert!(expression.eq_ignore_span(moved_expression), "{}", expression);
+        assert_eq!(hash_of(*expression), hash_of(*moved_expression), "{}", expression);
+    }
+    let kinds = expressions
+        .iter()
+        .map(|expression| expression_kind(expression))
+        .collect::<HashSet<_>>();
+    assert_eq!(kinds.len(), 19, "{:?}", kinds);
+
+    let statements = program.iter_statements(Traversal::PreOrder).collect::<Vec<_>>();
+    let moved_statements = moved.iter_statements(Traversal::PreOrder).collect::<Vec<_>>();
+    assert_eq!(statements.len(), moved_statements.len());
+    for (statement, moved_statement) in statements.iter().zip(moved_statements.iter()) {
+        assert!(statement.eq_ignore_span(moved_statement), "{}", statement);
+        assert_eq!(hash_of(*statement), hash_of(*moved_statement), "{}", statement);
+    }
+    let kinds = statements
+        .iter()
+        .map(|statement| statement_kind(statement))
+        .collect::<HashSet<_>>();
+    assert_eq!(kinds.len(), 8, "{:?}", kinds);
+}
+
+#[test]
+fn test_functions_from_different_files_are_equal() {
+    let program = parse("first.leo", PROGRAM);
+    let moved_source = moved_program();
+    let moved = parse("second.leo", &moved_source);
+
+    for name in ["double", "main"].iter() {
+        let (function, moved_function) = (function(&program, name), function(&moved, name));
+        assert_ne!(function.block, moved_function.block);
+        assert!(function.eq_ignore_span(moved_function));
+        assert_eq!(hash_of(function), hash_of(moved_function));
+    }
+
+    // The copies share one entry of a set.
+    let functions = program
+        .functions
+        .values()
+        .chain(moved.functions.values())
+        .map(IgnoringSpans)
+        .collect::<HashSet<_>>();
+    assert_eq!(functions.len(), 2);
+}
+
+#[test]
+fn test_one_literal_difference_is_unequal() {
+    let program = parse("first.leo", PROGRAM);
+    let changed = parse("first.leo", &PROGRAM.replace("[1u8, 2u8, 3u8]", "[1u8, 2u8, 4u8]"));
+
+    assert!(!function(&program, "main").eq_ignore_span(function(&changed, "main")));
+    assert_ne!(hash_of(function(&program, "main")), hash_of(function(&changed, "main")));
+    assert!(function(&program, "double").eq_ignore_span(function(&changed, "double")));
+    assert_eq!(
+        hash_of(function(&program, "double")),
+        hash_of(function(&changed, "double"))
+    );
+
+    // Types are compared the same way.
+    let type_of = |program: &Program| function(program, "main").output.clone().unwrap();
+    let moved_source = moved_program();
+    assert!(type_of(&program).eq_ignore_span(&type_of(&parse("second.leo", &moved_source))));
+    let other = parse(
+        "first.leo",
+        &PROGRAM.replace(
+            "function main(a: u32, b: bool) -> u32",
+            "function main(a: u32, b: bool) -> u64",
+        ),
+    );
+    assert!(!type_of(&program).eq_ignore_span(&type_of(&other)));
+}
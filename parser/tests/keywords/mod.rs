@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_parser::{parse, KEYWORD_TOKENS};
+
+/// Returns the keywords as written in source, leaving out `.len()`, which is not a word.
+fn keywords() -> Vec<String> {
+    KEYWORD_TOKENS
+        .iter()
+        .map(|token| token.to_string())
+        .filter(|keyword| keyword.chars().all(|c| c.is_ascii_alphanumeric()))
+        .collect()
+}
+
+#[test]
+fn test_keywords_rejected_as_names() {
+    let keywords = keywords();
+    assert!(keywords.contains(&"field".to_string()));
+
+    for keyword in keywords {
+        for source in &[
+            format!("circuit {} {{}}", keyword),
+            format!("function {}() {{}}", keyword),
+            format!("type {} = u8;", keyword),
+            format!("circuit Foo {{ function {}() {{}} }}", keyword),
+        ] {
+            assert!(parse("test", source).is_err(), "`{}` was accepted", source);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{IterationStatement, Program, Statement};
+use leo_parser::parse;
+
+/// Returns the loops of `main`.
+fn loops(program: &Program) -> Vec<&IterationStatement> {
+    program
+        .functions
+        .values()
+        .flat_map(|function| function.block.statements.iter())
+        .filter_map(|statement| match statement {
+            Statement::Iteration(iteration) => Some(&**iteration),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_loop_step() {
+    let program = parse(
+        "test",
+        r#"
+function main() {
+    for i in 0u32..16u32 step 2u32 {}
+    for i in 8i32..=0i32 step -2i32 {}
+    for i in 0..4 {}
+}
+"#,
+    )
+    .unwrap();
+    let loops = loops(&program);
+
+    assert_eq!(loops[0].step.as_ref().unwrap().to_string(), "2u32");
+    assert!(loops[0].to_string().starts_with("for i in 0u32..16u32 step 2u32 {"));
+    assert_eq!(loops[1].step.as_ref().unwrap().to_string(), "-2i32");
+    assert!(loops[1].inclusive);
+    assert!(loops[2].step.is_none());
+    assert!(loops[2].to_string().starts_with("for i in 0..4 {"));
+}
+
+#[test]
+fn test_loop_without_step_serializes_as_before() {
+    let program = parse("test", "function main() { for i in 0..4 {} }").unwrap();
+    let json = serde_json::to_string(loops(&program)[0]).unwrap();
+    assert!(!json.contains("step"));
+}
+
+#[test]
+fn test_step_is_not_a_keyword() {
+    let program = parse(
+        "test",
+        r#"
+function main() {
+    let step = 2u32;
+    for i in 0u32..step step step {}
+}
+"#,
+    )
+    .unwrap();
+    let loops = loops(&program);
+
+    assert_eq!(loops[0].stop.to_string(), "step");
+    assert_eq!(loops[0].step.as_ref().unwrap().to_string(), "step");
+}
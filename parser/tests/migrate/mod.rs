@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_parser::{
+    migrate::{migrate, rule, LangVersion, MigratedSource},
+    parse,
+};
+
+fn apply(name: &str, source: &str) -> MigratedSource {
+    rule(name).unwrap().apply(source).unwrap()
+}
+
+const V1_4_PROGRAM: &str = r#"
+circuit Counter {
+    count: u32,
+
+    function bump(mut self, mut by: u32) {
+        let mut next = self.count + by;
+        self.count = next;
+    }
+}
+
+@context(setup)
+test function test_bump() {
+    let (mut a, b) = (1u32, 2u32);
+    a += b;
+}
+
+test function test_main() {
+    console.assert(main(1u32) == 2u32);
+}
+
+function main(mut a: u32) -> u32 {
+    const mut b = [0u8; 2];
+    a += 1;
+    return a;
+}
+"#;
+
+#[test]
+fn test_let_mut() {
+    let migrated = apply(
+        "let_mut",
+        "let mut a = 1u8;\nlet (mut b, c) = (a, a);\nconst mut d = 0u8;\n",
+    );
+    assert_eq!(migrated.source, "let a = 1u8;\nlet (b, c) = (a, a);\nconst d = 0u8;\n");
+    assert_eq!(migrated.edits.len(), 3);
+    let edit = &migrated.edits[0];
+    assert_eq!(
+        (edit.rule, edit.original.as_str(), edit.replacement.as_str()),
+        ("let_mut", "mut ", "")
+    );
+    assert_eq!((edit.span.line_start, edit.span.col_start), (1, 5));
+    assert_eq!(
+        (migrated.edits[1].span.line_start, migrated.edits[1].span.col_start),
+        (2, 6)
+    );
+}
+
+#[test]
+fn test_mut_parameter() {
+    let migrated = apply(
+        "mut_parameter",
+        "function bump(mut self, mut by: u32, values: [u8; 2]) {\n    let mut a = by;\n}\n",
+    );
+    assert_eq!(
+        migrated.source,
+        "function bump(mut self, by: u32, values: [u8; 2]) {\n    let mut a = by;\n}\n"
+    );
+    assert_eq!(migrated.edits.len(), 1);
+    assert_eq!(migrated.edits[0].span.col_start, 25);
+}
+
+#[test]
+fn test_test_function() {
+    let migrated = apply(
+        "test_function",
+        "test function a() {}\n\n@context(setup)\ntest function b() {}\n\nfunction test() {}\n",
+    );
+    assert_eq!(
+        migrated.source,
+        "@test function a() {}\n\n@context(setup)\nfunction b() {}\n\nfunction test() {}\n"
+    );
+    assert_eq!(migrated.edits.len(), 2);
+}
+
+#[test]
+fn test_context_annotation() {
+    let migrated = apply(
+        "context_annotation",
+        "@context(setup)\ntest function b() {}\n\n@context(other)\nfunction c() {}\n",
+    );
+    assert_eq!(
+        migrated.source,
+        "@test(setup)\ntest function b() {}\n\n@test(other)\nfunction c() {}\n"
+    );
+    assert!(migrated.todos.is_empty());
+}
+
+#[test]
+fn test_ambiguous_context_is_marked() {
+    let source = "@context(setup)\n@test(other)\nfunction b() {}\n";
+    let migrated = apply("context_annotation", source);
+    assert!(migrated.edits.is_empty());
+    assert_eq!(migrated.todos.len(), 1);
+    assert_eq!(migrated.todos[0].span.line_start, 1);
+    assert!(migrated.source.starts_with("/* TODO(migrate): "));
+    assert!(migrated
+        .source
+        .ends_with("*/ @context(setup)\n@test(other)\nfunction b() {}\n"));
+
+    // The marked annotation is not marked again.
+    assert_eq!(apply("context_annotation", &migrated.source).source, migrated.source);
+}
+
+#[test]
+fn test_reserved_names_are_marked() {
+    let source = "circuit Letter {\n    char: u8,\n}\n\nfunction main(type: u8, c: char) -> char {\n    let char = 'a';\n    return char;\n}\n\ntype Byte = u8;\n";
+    let migrated = apply("reserved_names", source);
+    assert!(migrated.edits.is_empty());
+    let marked = migrated
+        .todos
+        .iter()
+        .map(|todo| (todo.span.line_start, todo.span.col_start))
+        .collect::<Vec<_>>();
+    assert_eq!(marked, vec![(2, 5), (5, 15), (6, 9)]);
+    assert!(migrated.todos[0].message.contains("`char` is a keyword"));
+    assert!(migrated.source.contains("let /* TODO(migrate): "));
+
+    assert_eq!(apply("reserved_names", &migrated.source).source, migrated.source);
+}
+
+#[test]
+fn test_migrate_reparses() {
+    assert!(parse("test.leo", V1_4_PROGRAM).is_err());
+
+    let migrated = migrate(V1_4_PROGRAM, LangVersion::V1_4, LangVersion::CURRENT).unwrap();
+    assert!(migrated.todos.is_empty());
+    assert_eq!(migrated.edits.len(), 8);
+    assert!(migrated.source.contains("function bump(mut self, by: u32)"));
+    assert!(migrated.source.contains("@test(setup)\nfunction test_bump()"));
+    assert!(migrated.source.contains("@test function test_main()"));
+    parse("test.leo", &migrated.source).unwrap();
+}
+
+#[test]
+fn test_migrate_is_idempotent() {
+    let source = format!("{}\nfunction f(char: u8) {{}}\n", V1_4_PROGRAM);
+    let once = migrate(&source, LangVersion::V1_4, LangVersion::V1_5).unwrap();
+    assert_eq!(once.todos.len(), 1);
+
+    let twice = migrate(&once.source, LangVersion::V1_4, LangVersion::V1_5).unwrap();
+    assert_eq!(twice.source, once.source);
+    assert!(twice.edits.is_empty());
+    assert!(twice.todos.is_empty());
+}
+
+#[test]
+fn test_migrate_source_that_does_not_parse() {
+    let migrated = migrate(
+        "function main( {\n    let mut a = 1u8;\n",
+        LangVersion::V1_4,
+        LangVersion::V1_5,
+    )
+    .unwrap();
+    assert_eq!(migrated.source, "function main( {\n    let a = 1u8;\n");
+}
+
+#[test]
+fn test_migrate_to_same_version() {
+    let migrated = migrate(V1_4_PROGRAM, LangVersion::V1_5, LangVersion::V1_5).unwrap();
+    assert_eq!(migrated.source, V1_4_PROGRAM);
+    assert!(migrated.edits.is_empty());
+
+    let err = migrate(V1_4_PROGRAM, LangVersion::V1_5, LangVersion::V1_4).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot migrate source from Leo 1.5 back to Leo 1.4"));
+}
@@ -14,4 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+mod corpus;
+
+mod editions;
+
+mod ignore_span;
+
+mod keywords;
+
+mod loop_step;
+
+mod migrate;
+
+mod parentheses;
+
+mod recovery;
+
 mod serialization;
+
+mod trailing_commas;
@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Expression, Function, IgnoreSpan, Program, Statement, ValueExpression};
+use leo_parser::parse;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+fn function<'a>(program: &'a Program, name: &str) -> &'a Function {
+    program
+        .functions
+        .iter()
+        .find(|(identifier, _)| identifier.name.as_ref() == name)
+        .map(|(_, function)| function)
+        .expect("no such function")
+}
+
+/// Returns the values of the definitions of `main`.
+fn main_values(program: &Program) -> Vec<&Expression> {
+    function(program, "main")
+        .block
+        .statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Definition(definition) => definition.value.as_ref(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn main_with(definitions: &str) -> Program {
+    parse(
+        "test",
+        &format!(
+            "function main(a: u32, b: u32, c: u32) -> u32 {{\n{}\n    return a;\n}}\n",
+            definitions
+        ),
+    )
+    .unwrap()
+}
+
+fn hash_of<T: IgnoreSpan>(node: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.hash_ignore_span(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_redundant_parentheses_are_printed_back() {
+    let sources = [
+        "(a) + b",
+        "((a + b)) * c",
+        "(a * b) + c",
+        "a - (b - c)",
+        "(a + b) * c",
+        "a ** (b ** c)",
+        "(a as u64)",
+        "(a)[0]",
+    ];
+    let definitions = sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| format!("    let x{} = {};", index, source))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let program = main_with(&definitions);
+
+    let printed = main_values(&program)
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(printed, sources);
+}
+
+#[test]
+fn test_tuples_and_negated_literals_are_not_parenthesized() {
+    let program = main_with("    let t = (a,);\n    let u = (a, b);\n    let n = -(128i8);");
+    let values = main_values(&program);
+
+    assert!(matches!(values[0], Expression::Identifier(_)));
+    assert!(matches!(values[1], Expression::TupleInit(_)));
+    assert!(matches!(values[2], Expression::Value(ValueExpression::Integer(_, value, _)) if value.as_ref() == "-128"));
+}
+
+#[test]
+fn test_parentheses_are_transparent_to_semantic_equality() {
+    let grouped = main_with("    let x = (a) + ((b));\n    let y = (a + b) * c;");
+    let plain = main_with("    let x = a + b;\n    let y = (a + b) * c;");
+    let regrouped = main_with("    let x = a + b;\n    let y = a + b * c;");
+
+    let (grouped, plain, regrouped) = (
+        function(&grouped, "main"),
+        function(&plain, "main"),
+        function(&regrouped, "main"),
+    );
+    assert!(grouped.eq_ignore_span(plain));
+    assert_eq!(hash_of(grouped), hash_of(plain));
+    // Parentheses that change the grouping change the tree inside them.
+    assert!(!grouped.eq_ignore_span(regrouped));
+}
+
+#[test]
+fn test_parentheses_serialize_as_their_expression() {
+    let program = main_with("    let x = ((a + b));");
+    let value = main_values(&program)[0];
+    assert!(matches!(value, Expression::Parenthesized(_)));
+
+    let json = serde_json::to_value(value).unwrap();
+    assert_eq!(json, serde_json::to_value(value.without_parentheses()).unwrap());
+    assert!(!json.to_string().contains("Parenthesized"));
+
+    let read: Expression = serde_json::from_value(json).unwrap();
+    assert!(matches!(read, Expression::Binary(_)));
+}
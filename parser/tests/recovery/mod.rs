@@ -0,0 +1,233 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{DefinitionStatement, Expression, Program, Statement, Traversal, Type};
+use leo_parser::parse_recovering;
+
+fn recover(source: &str) -> (Program, Vec<(String, usize, usize)>) {
+    let (program, errors) = parse_recovering("test", source);
+    let errors = errors
+        .iter()
+        .map(|error| {
+            let span = error.span().expect("recovery errors have a span");
+            (error.error_code(), span.line_start, span.col_start)
+        })
+        .collect();
+    (program, errors)
+}
+
+fn function_names(program: &Program) -> Vec<String> {
+    program.functions.keys().map(|name| name.name.to_string()).collect()
+}
+
+#[test]
+fn test_valid_program_has_no_errors() {
+    let (program, errors) = recover(
+        r#"
+function main() -> u8 {
+    console.log("hello");
+    return 1u8;
+}
+"#,
+    );
+
+    assert!(errors.is_empty());
+    assert!(program.recovered.is_empty());
+    assert_eq!(function_names(&program), vec!["main"]);
+}
+
+#[test]
+fn test_unterminated_string() {
+    let source = r#"
+function greet() {
+    console.log("hello);
+}
+
+function main() -> u8 {
+    return 1u8;
+}
+"#;
+    assert!(leo_parser::parse("test", source).is_err());
+
+    let (program, errors) = recover(source);
+
+    assert_eq!(errors, vec![("EPAR0370022".to_string(), 3, 17)]);
+    assert_eq!(function_names(&program), vec!["greet", "main"]);
+    assert_eq!(program.recovered.len(), 1);
+}
+
+#[test]
+fn test_unclosed_block() {
+    let (program, errors) = recover(
+        r#"
+function main() -> u8 {
+    let a = 1u8;
+    return a;
+
+function other() -> u8 {
+    return 2u8;
+}
+"#,
+    );
+
+    assert_eq!(errors, vec![("EPAR0370024".to_string(), 2, 23)]);
+    assert_eq!(function_names(&program), vec!["main", "other"]);
+}
+
+#[test]
+fn test_unclosed_circuit() {
+    let (program, errors) = recover(
+        r#"
+circuit Point {
+    x: u8,
+
+circuit Line {
+    a: Point,
+    b: Point,
+}
+"#,
+    );
+
+    assert_eq!(errors, vec![("EPAR0370024".to_string(), 2, 15)]);
+    assert_eq!(program.circuits.len(), 2);
+}
+
+#[test]
+fn test_no_cascading_errors() {
+    let (program, errors) = recover(
+        r#"
+function greet() {
+    console.log("hello);
+}
+
+function main() -> u8 {
+    return 1u8;
+
+function other() -> u8 {
+    return 2u8;
+}
+"#,
+    );
+
+    assert_eq!(
+        errors,
+        vec![("EPAR0370022".to_string(), 3, 17), ("EPAR0370024".to_string(), 6, 23)]
+    );
+    assert_eq!(function_names(&program), vec!["greet", "main", "other"]);
+}
+
+#[test]
+fn test_broken_item_is_skipped() {
+    let (program, errors) = recover(
+        r#"
+function broken() {
+    let a = (1u8 + 2u8;
+}
+
+function main() -> u8 {
+    return 1u8;
+}
+"#,
+    );
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].1, 3);
+    assert_eq!(function_names(&program), vec!["main"]);
+}
+
+#[test]
+fn test_repeated_commas() {
+    let source = r#"
+function add(a: u32,, b: u32) -> u32 {
+    return add(a,, b);
+}
+
+function main() -> u8 {
+    return 1u8;
+}
+"#;
+    assert!(leo_parser::parse("test", source).is_err());
+
+    let (program, errors) = recover(source);
+
+    assert_eq!(
+        errors,
+        vec![("EPAR0370026".to_string(), 2, 21), ("EPAR0370026".to_string(), 3, 18)]
+    );
+    assert_eq!(function_names(&program), vec!["add", "main"]);
+    assert_eq!(program.functions.values().next().unwrap().input.len(), 2);
+    assert!(program.recovered.is_empty());
+}
+
+const PLACEHOLDERS: &str = r#"
+function main(a: u32) -> u32 {
+    let b: u32 = a * / 3;
+    let c: + = b;
+    return c;
+}
+"#;
+
+/// Returns the placeholders of the program, as they were written.
+fn placeholder_texts(program: &Program) -> Vec<Option<String>> {
+    let mut texts = vec![];
+    for statement in program.iter_statements(Traversal::PreOrder) {
+        if let Statement::Definition(DefinitionStatement {
+            type_: Some(Type::Err(err)),
+            ..
+        }) = statement
+        {
+            texts.push(err.text.clone());
+        }
+    }
+    for expression in program.iter_expressions(Traversal::PreOrder) {
+        if let Expression::Err(err) = expression {
+            texts.push(err.text.clone());
+        }
+    }
+    texts
+}
+
+#[test]
+fn test_placeholders_keep_the_function() {
+    assert!(leo_parser::parse("test", PLACEHOLDERS).is_err());
+
+    let (program, errors) = recover(PLACEHOLDERS);
+
+    let lines = errors.iter().map(|(_, line, col)| (*line, *col)).collect::<Vec<_>>();
+    assert_eq!(lines, vec![(3, 22), (4, 12)]);
+    assert_eq!(function_names(&program), vec!["main"]);
+    assert_eq!(program.functions.values().next().unwrap().block.statements.len(), 3);
+    assert_eq!(program.recovered.len(), 2);
+    assert_eq!(
+        placeholder_texts(&program),
+        vec![Some("+".to_string()), Some("/ 3".to_string())]
+    );
+}
+
+#[test]
+fn test_placeholders_round_trip() {
+    let (program, _) = recover(PLACEHOLDERS);
+
+    let printed = program.to_string();
+    assert!(printed.contains("let mut b: u32 = a * /* error */;"), "{}", printed);
+    assert!(printed.contains("let mut c: /* error */ = b;"), "{}", printed);
+
+    let json = serde_json::to_string(&program).unwrap();
+    assert!(json.contains(r#""text":"/ 3""#), "{}", json);
+    let read: Program = serde_json::from_str(&json).unwrap();
+    assert_eq!(placeholder_texts(&read), placeholder_texts(&program));
+    assert_eq!(read.to_string(), printed);
+}
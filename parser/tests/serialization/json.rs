@@ -14,9 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use leo_ast::Ast;
 #[cfg(not(feature = "ci_skip"))]
 use leo_ast::Program;
+use leo_ast::{ArtifactFormat, Ast, AST_FORMAT};
 use leo_errors::{LeoError, Result};
 
 use std::fs::File;
@@ -215,3 +215,35 @@ fn test_generic_parser_error() {
     clean();
     assert!(error_result.err().unwrap());
 }
+
+fn one_plus_one() -> Ast {
+    let mut program_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    program_filepath.push("tests/serialization/leo/one_plus_one.leo");
+    to_ast(&program_filepath).unwrap()
+}
+
+#[test]
+fn test_stamped_ast_round_trips() {
+    let ast = one_plus_one();
+
+    let stamped = String::from_utf8(AST_FORMAT.stamp(&ast.to_json_bytes().unwrap())).unwrap();
+    assert!(stamped.starts_with("{\n  \"leo_artifact\": "));
+    let reparsed = Ast::from_json_string(&stamped).unwrap();
+    assert_eq!(reparsed.to_json_bytes().unwrap(), ast.to_json_bytes().unwrap());
+    assert_eq!(AST_FORMAT.unstamp(stamped.as_bytes()), ast.to_json_bytes().unwrap());
+}
+
+#[test]
+fn test_incompatible_ast_format() {
+    let ast = one_plus_one();
+    let bumped = ArtifactFormat {
+        version: AST_FORMAT.version + 1,
+        ..AST_FORMAT
+    };
+
+    let stamped = String::from_utf8(bumped.stamp(&ast.to_json_bytes().unwrap())).unwrap();
+    let error = Ast::from_json_string(&stamped).unwrap_err();
+    assert_eq!(error.error_code(), "EAST0372020");
+    assert!(error.to_string().contains(&bumped.current().to_string()));
+    assert!(error.to_string().contains(&AST_FORMAT.current().to_string()));
+}
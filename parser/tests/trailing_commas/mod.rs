@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Expression, Function, FunctionInput, Program, Statement, Type};
+use leo_parser::parse;
+
+fn function<'a>(program: &'a Program, name: &str) -> &'a Function {
+    program
+        .functions
+        .iter()
+        .find(|(identifier, _)| identifier.name.as_ref() == name)
+        .map(|(_, function)| function)
+        .expect("no such function")
+}
+
+/// Returns the values of the statements of `main`, which must be definitions and a return.
+fn main_values(program: &Program) -> Vec<&Expression> {
+    function(program, "main")
+        .block
+        .statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Definition(definition) => definition.value.as_ref(),
+            Statement::Return(return_) => Some(&return_.expression),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_trailing_commas_are_accepted() {
+    let program = parse(
+        "test",
+        r#"
+function add(a: u32, b: (u32, bool,), c: [u8; (2, 3,)],) -> u32 {
+    return a;
+}
+
+function main() -> u32 {
+    let (x, y,) = (1u32, 2u32,);
+    let p = Point { x, y: 2u32, };
+    let a = [1u8, 2u8, 3u8,];
+    let b = [[0u8; (2, 3,)],];
+    console.assert(true,);
+    console.assert_eq(x, y,);
+    console.log("{}", x,);
+    return add(x, (y, true,), b[0],);
+}
+"#,
+    )
+    .unwrap();
+
+    let add = function(&program, "add");
+    assert_eq!(add.input.len(), 3);
+    match &add.input[2] {
+        FunctionInput::Variable(c) => assert_eq!(c.type_.to_string(), "[u8; (2, 3)]"),
+        _ => panic!("expected a variable"),
+    }
+    match &add.input[1] {
+        FunctionInput::Variable(b) => assert!(matches!(&b.type_, Type::Tuple(types) if types.len() == 2)),
+        _ => panic!("expected a variable"),
+    }
+
+    let values = main_values(&program);
+    assert!(matches!(values[0], Expression::TupleInit(tuple) if tuple.elements.len() == 2));
+    assert!(matches!(values[1], Expression::CircuitInit(init) if init.members.len() == 2));
+    assert!(matches!(values[2], Expression::ArrayInline(array) if array.elements.len() == 3));
+    assert!(matches!(values[3], Expression::ArrayInline(array) if array.elements.len() == 1));
+    assert!(matches!(values[4], Expression::Call(call) if call.arguments.len() == 3));
+}
+
+#[test]
+fn test_single_trailing_comma_keeps_parentheses() {
+    let program = parse(
+        "test",
+        r#"
+function main() -> u32 {
+    let a = (1u32,);
+    let b = (1u32, 2u32,);
+    return (a);
+}
+"#,
+    )
+    .unwrap();
+
+    let values = main_values(&program);
+    assert!(!matches!(values[0], Expression::TupleInit(_)));
+    assert!(matches!(values[1], Expression::TupleInit(tuple) if tuple.elements.len() == 2));
+    assert!(!matches!(values[2], Expression::TupleInit(_)));
+}
+
+#[test]
+fn test_printed_lists_have_no_trailing_commas() {
+    let source = "function main() {\n    return g(1u8, (2u8, [3u8, 4u8,],), Foo { x: 5u8, y, },);\n}\n";
+    let printed = main_values(&parse("test", source).unwrap())[0].to_string();
+    assert!(!printed.contains(",)") && !printed.contains(",]") && !printed.contains(",}"));
+
+    let reprinted_source = format!("function main() {{\n    return {};\n}}\n", printed);
+    let reprinted = main_values(&parse("test", &reprinted_source).unwrap())[0].to_string();
+    assert_eq!(printed, reprinted);
+}